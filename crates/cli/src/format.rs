@@ -1,18 +1,11 @@
 use std::ffi::OsStr;
+use std::io::Read;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 
 use crate::FormatMode;
-use bumpalo::Bump;
+use roc_compile::{FormatOptions, FormatProblem};
 use roc_error_macros::{internal_error, user_error};
-use roc_fmt::def::fmt_defs;
-use roc_fmt::module::fmt_module;
-use roc_fmt::spaces::RemoveSpaces;
-use roc_fmt::{Ast, Buf};
-use roc_parse::{
-    module::{self, module_defs},
-    parser::{Parser, SyntaxError},
-    state::State,
-};
 
 fn flatten_directories(files: std::vec::Vec<PathBuf>) -> std::vec::Vec<PathBuf> {
     let mut to_flatten = files;
@@ -58,53 +51,96 @@ fn is_roc_file(path: &Path) -> bool {
     matches!(path.extension().and_then(OsStr::to_str), Some("roc"))
 }
 
-pub fn format(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(), String> {
-    let files = flatten_directories(files);
-
-    for file in files {
-        let arena = Bump::new();
-
-        let src = std::fs::read_to_string(&file).unwrap();
+/// Format Roc source code, making sure the result parses the same way and is stable.
+/// `file` is where the source came from; it's used in error messages, and to name the
+/// files written for debugging purposes if the formatter turns out to have a bug.
+fn format_src(file: &Path, src: &str, options: FormatOptions) -> String {
+    roc_compile::format_str(src, options)
+        .unwrap_or_else(|problem| report_format_problem(file, src, None, problem))
+}
 
-        let ast = arena.alloc(parse_all(&arena, &src).unwrap_or_else(|e| {
-            user_error!("Unexpected parse failure when parsing this formatting:\n\n{:?}\n\nParse error was:\n\n{:?}\n\n", src, e)
-        }));
-        let mut buf = Buf::new_in(&arena);
-        fmt_all(&mut buf, ast);
+/// Format only the top-level definitions that overlap the given lines (which start at 1,
+/// like in an editor), and leave the rest of the source exactly as it was.
+fn format_src_lines(
+    file: &Path,
+    src: &str,
+    lines: &RangeInclusive<u32>,
+    options: FormatOptions,
+) -> String {
+    roc_compile::format_lines(src, lines, options)
+        .unwrap_or_else(|problem| report_format_problem(file, src, Some(lines), problem))
+}
 
-        let reparsed_ast = arena.alloc(parse_all(&arena, buf.as_str()).unwrap_or_else(|e| {
-            let mut fail_file = file.clone();
+fn report_format_problem(
+    file: &Path,
+    src: &str,
+    lines: Option<&RangeInclusive<u32>>,
+    problem: FormatProblem,
+) -> ! {
+    match (problem, lines) {
+        (FormatProblem::Syntax(e), _) => {
+            user_error!("Unexpected parse failure when parsing {} for formatting:\n\n{:?}\n\nParse error was:\n\n{}\n\n", file.display(), src, e)
+        }
+        (FormatProblem::Config(message), _) => {
+            user_error!(
+                "{message}\n\n(This setting is in a roc-fmt comment in {}.)",
+                file.display()
+            )
+        }
+        (FormatProblem::InvalidOutput { parse_error, .. }, Some(lines)) => {
+            internal_error!(
+                "Formatting bug; formatting lines {}:{} of {} produced invalid code\n\nParse error was: {}\n\n",
+                lines.start(),
+                lines.end(),
+                file.display(),
+                parse_error
+            )
+        }
+        (FormatProblem::ChangedMeaning { .. }, Some(lines)) => {
+            internal_error!(
+                "Formatting bug; formatting lines {}:{} of {} didn't reparse as the same tree",
+                lines.start(),
+                lines.end(),
+                file.display()
+            )
+        }
+        (
+            FormatProblem::InvalidOutput {
+                formatted,
+                parse_error,
+            },
+            _,
+        ) => {
+            let mut fail_file = file.to_path_buf();
             fail_file.set_extension("roc-format-failed");
-            std::fs::write(&fail_file, buf.as_str()).unwrap();
+            std::fs::write(&fail_file, formatted).unwrap();
             internal_error!(
                 "Formatting bug; formatted code isn't valid\n\n\
                 I wrote the incorrect result to this file for debugging purposes:\n{}\n\n\
-                Parse error was: {:?}\n\n",
+                Parse error was: {}\n\n",
                 fail_file.display(),
-                e
+                parse_error
             );
-        }));
-
-        let ast_normalized = ast.remove_spaces(&arena);
-        let reparsed_ast_normalized = reparsed_ast.remove_spaces(&arena);
-
-        // HACK!
-        // We compare the debug format strings of the ASTs, because I'm finding in practice that _somewhere_ deep inside the ast,
-        // the PartialEq implementation is returning `false` even when the Debug-formatted impl is exactly the same.
-        // I don't have the patience to debug this right now, so let's leave it for another day...
-        // TODO: fix PartialEq impl on ast types
-        if format!("{ast_normalized:?}") != format!("{reparsed_ast_normalized:?}") {
-            let mut fail_file = file.clone();
+        }
+        (
+            FormatProblem::ChangedMeaning {
+                formatted,
+                ast_before,
+                ast_after,
+            },
+            _,
+        ) => {
+            let mut fail_file = file.to_path_buf();
             fail_file.set_extension("roc-format-failed");
-            std::fs::write(&fail_file, buf.as_str()).unwrap();
+            std::fs::write(&fail_file, formatted).unwrap();
 
-            let mut before_file = file.clone();
+            let mut before_file = file.to_path_buf();
             before_file.set_extension("roc-format-failed-ast-before");
-            std::fs::write(&before_file, format!("{ast_normalized:#?}\n")).unwrap();
+            std::fs::write(&before_file, ast_before).unwrap();
 
-            let mut after_file = file.clone();
+            let mut after_file = file.to_path_buf();
             after_file.set_extension("roc-format-failed-ast-after");
-            std::fs::write(&after_file, format!("{reparsed_ast_normalized:#?}\n")).unwrap();
+            std::fs::write(&after_file, ast_after).unwrap();
 
             internal_error!(
                 "Formatting bug; formatting didn't reparse as the same tree\n\n\
@@ -114,18 +150,20 @@ pub fn format(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(), Str
                 before_file.display(),
                 after_file.display());
         }
-
-        // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
-        let mut reformatted_buf = Buf::new_in(&arena);
-        fmt_all(&mut reformatted_buf, reparsed_ast);
-        if buf.as_str() != reformatted_buf.as_str() {
-            let mut unstable_1_file = file.clone();
+        (
+            FormatProblem::Unstable {
+                formatted,
+                reformatted,
+            },
+            _,
+        ) => {
+            let mut unstable_1_file = file.to_path_buf();
             unstable_1_file.set_extension("roc-format-unstable-1");
-            std::fs::write(&unstable_1_file, buf.as_str()).unwrap();
+            std::fs::write(&unstable_1_file, formatted).unwrap();
 
-            let mut unstable_2_file = file.clone();
+            let mut unstable_2_file = file.to_path_buf();
             unstable_2_file.set_extension("roc-format-unstable-2");
-            std::fs::write(&unstable_2_file, reformatted_buf.as_str()).unwrap();
+            std::fs::write(&unstable_2_file, reformatted).unwrap();
 
             internal_error!(
                 "Formatting bug; formatting is not stable. Reformatting the formatted file changed it again.\n\n\
@@ -134,38 +172,206 @@ pub fn format(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(), Str
                 unstable_1_file.display(),
                 unstable_2_file.display());
         }
+    }
+}
+
+/// Check that formatting Roc source is safe, the same way [format_src] does, but describe
+/// what's wrong instead of treating it as a crash. Returns `None` if there's no problem.
+fn verify_src(
+    file: &Path,
+    src: &str,
+    lines: Option<&RangeInclusive<u32>>,
+    options: FormatOptions,
+) -> Option<String> {
+    let result = match lines {
+        Some(lines) => roc_compile::format_lines(src, lines, options),
+        None => roc_compile::format_str(src, options),
+    };
+    let path = file.display();
+
+    match result.err()? {
+        FormatProblem::Syntax(e) => Some(format!(
+            "{path} doesn't parse, so it can't be formatted.\n\nParse error was:\n\n{e}\n"
+        )),
+        FormatProblem::Config(message) => Some(format!(
+            "{message}\n\n(This setting is in a roc-fmt comment in {path}.)\n"
+        )),
+        FormatProblem::InvalidOutput {
+            formatted,
+            parse_error,
+        } => Some(format!(
+            "Formatting bug; formatting {path} would produce invalid code:\n\n{}\n\
+            Parse error was: {parse_error}\n",
+            unified_diff(file, src, &formatted)
+        )),
+        FormatProblem::ChangedMeaning { formatted, .. } => Some(format!(
+            "Formatting bug; formatting {path} would change what it means:\n\n{}",
+            unified_diff(file, src, &formatted)
+        )),
+        FormatProblem::Unstable {
+            formatted,
+            reformatted,
+        } => Some(format!(
+            "Formatting bug; formatting {path} is not stable. Formatting it again would change it again:\n\n{}",
+            unified_diff(file, &formatted, &reformatted)
+        )),
+    }
+}
+
+/// The options to format `file` with: the given ones, with any `# roc-fmt:` settings from the
+/// header of its app or package's main.roc applied on top of them. (Settings in the file
+/// itself get applied on top of those when it's formatted.)
+fn package_options(file: &Path, options: FormatOptions) -> FormatOptions {
+    let main_file = file
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join("main.roc"))
+        .find(|main_file| main_file.is_file());
+
+    match main_file {
+        Some(main_file) if main_file != file => {
+            let src = std::fs::read_to_string(&main_file).unwrap_or_default();
+
+            options.with_config_from(&src).unwrap_or_else(|message| {
+                user_error!(
+                    "{message}\n\n(This setting is in a roc-fmt comment in {}.)",
+                    main_file.display()
+                )
+            })
+        }
+        _ => options,
+    }
+}
+
+/// Parse a line range like `10:42` (or just `10`) for `roc format --range`.
+pub(crate) fn parse_line_range(src: &str) -> Result<RangeInclusive<u32>, String> {
+    let (start, end) = src.split_once(':').unwrap_or((src, src));
+    let parse_line = |line: &str| match line.trim().parse::<u32>() {
+        Ok(line) if line > 0 => Ok(line),
+        _ => Err(format!(
+            "`{src}` is not a range of lines like `10:42` (lines start at 1)"
+        )),
+    };
+    let (start, end) = (parse_line(start)?, parse_line(end)?);
+
+    if start <= end {
+        Ok(start..=end)
+    } else {
+        Err(format!("`{src}` ends before it starts"))
+    }
+}
+
+pub fn format(
+    files: std::vec::Vec<PathBuf>,
+    mode: FormatMode,
+    lines: Option<RangeInclusive<u32>>,
+    options: FormatOptions,
+) -> Result<(), String> {
+    let files = flatten_directories(files);
+    let mut unformatted_files = 0;
+    let mut unverified_files = 0;
+
+    for file in files {
+        let src = std::fs::read_to_string(&file).unwrap();
+        let options = package_options(&file, options);
+
+        if let FormatMode::Verify = mode {
+            if let Some(problem) = verify_src(&file, &src, lines.as_ref(), options) {
+                eprintln!("{problem}");
+                unverified_files += 1;
+            }
+
+            continue;
+        }
+
+        let formatted = match &lines {
+            Some(lines) => format_src_lines(&file, &src, lines, options),
+            None => format_src(&file, &src, options),
+        };
 
         match mode {
             FormatMode::CheckOnly => {
-                // If we notice that this file needs to be formatted, return early
-                if buf.as_str() != src {
-                    return Err("One or more files need to be reformatted.".to_string());
+                // If we notice that this file needs to be formatted, show what would change
+                if formatted != src {
+                    print!("{}", unified_diff(&file, &src, &formatted));
+                    unformatted_files += 1;
                 }
             }
 
             FormatMode::Format => {
                 // If all the checks above passed, actually write out the new file.
-                std::fs::write(&file, buf.as_str()).unwrap();
+                std::fs::write(&file, formatted).unwrap();
             }
+
+            FormatMode::Verify => unreachable!("files are verified before they're formatted"),
         }
     }
 
-    Ok(())
+    match (unformatted_files, unverified_files) {
+        (0, 0) => Ok(()),
+        (0, 1) => Err("1 file can't be formatted safely.".to_string()),
+        (0, n) => Err(format!("{n} files can't be formatted safely.")),
+        (1, _) => Err("1 file needs to be reformatted.".to_string()),
+        (n, _) => Err(format!("{n} files need to be reformatted.")),
+    }
 }
 
-fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
-    let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
-        .map_err(|e| SyntaxError::Header(e.problem))?;
+/// Format the Roc source on stdin, and write the result to stdout (or with
+/// `FormatMode::CheckOnly`, a diff of what formatting would change, and with
+/// `FormatMode::Verify`, nothing unless formatting it isn't safe).
+/// `filename` is the file the source came from, if known, for use in error messages.
+pub fn format_stdin(
+    mode: FormatMode,
+    filename: Option<&Path>,
+    lines: Option<RangeInclusive<u32>>,
+    options: FormatOptions,
+) -> Result<(), String> {
+    let mut src = String::new();
+    std::io::stdin()
+        .read_to_string(&mut src)
+        .map_err(|err| format!("Could not read the source to format from stdin: {err}"))?;
 
-    let (_, defs, _) = module_defs().parse(arena, state, 0).map_err(|(_, e)| e)?;
+    let file = filename.unwrap_or_else(|| Path::new("stdin.roc"));
+    let options = match filename {
+        Some(filename) => package_options(filename, options),
+        None => options,
+    };
 
-    Ok(Ast { module, defs })
-}
+    if let FormatMode::Verify = mode {
+        return match verify_src(file, &src, lines.as_ref(), options) {
+            Some(problem) => Err(problem),
+            None => Ok(()),
+        };
+    }
+
+    let formatted = match &lines {
+        Some(lines) => format_src_lines(file, &src, lines, options),
+        None => format_src(file, &src, options),
+    };
+
+    match mode {
+        FormatMode::CheckOnly => {
+            if formatted != src {
+                print!("{}", unified_diff(file, &src, &formatted));
+
+                return Err(format!("{} needs to be reformatted.", file.display()));
+            }
+        }
 
-fn fmt_all<'a>(buf: &mut Buf<'a>, ast: &'a Ast) {
-    fmt_module(buf, &ast.module);
+        FormatMode::Format => print!("{formatted}"),
+
+        FormatMode::Verify => unreachable!("files are verified before they're formatted"),
+    }
+
+    Ok(())
+}
 
-    fmt_defs(buf, &ast.defs, 0);
+/// Show how formatting would change a file, as a unified diff.
+fn unified_diff(file: &Path, src: &str, formatted: &str) -> String {
+    let path = file.display().to_string();
 
-    buf.fmt_end_of_file();
+    similar::TextDiff::from_lines(src, formatted)
+        .unified_diff()
+        .header(&path, &path)
+        .to_string()
 }
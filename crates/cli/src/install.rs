@@ -0,0 +1,226 @@
+//! `roc install`, `roc update`, and `roc vendor`, which download an app or package's URL
+//! dependencies ahead of time, record them in its `roc.lock`, and copy them into the project.
+use roc_packaging::cache::{self, InstallProblem, InstalledPackage, RocCacheDir};
+use roc_packaging::lock::{lockfile_path, Lockfile, LOCKFILE_NAME};
+use roc_reporting::report::to_https_problem_report_string;
+use std::fs;
+use std::path::Path;
+
+/// Download every URL package that the given module depends on into the Roc cache, so that
+/// building it afterwards doesn't need the network. Creates its `roc.lock` if it doesn't have
+/// one yet, and otherwise checks the packages against it. Returns the exit code.
+pub fn install(roc_file: &Path) -> i32 {
+    let cache_dir = cache::roc_cache_dir();
+
+    let Some(packages) = install_dependencies(roc_file, RocCacheDir::Persistent(&cache_dir))
+    else {
+        return 1;
+    };
+
+    let lock_path = lockfile_path(roc_file);
+
+    if lock_path.exists() {
+        if !matches_lockfile(&lock_path, &packages) {
+            return 1;
+        }
+    } else if !write_lockfile(&lock_path, &packages) {
+        return 1;
+    }
+
+    if packages.is_empty() {
+        println!("{} has no URL dependencies to install.", roc_file.display());
+    } else {
+        print_installed(&packages);
+
+        println!(
+            "\n🎉 Installed {} package{} into {}",
+            packages.len(),
+            if packages.len() == 1 { "" } else { "s" },
+            cache_dir.display()
+        );
+    }
+
+    0
+}
+
+/// Like [install], except that it always writes a fresh `roc.lock`. Returns the exit code.
+pub fn update(roc_file: &Path) -> i32 {
+    let cache_dir = cache::roc_cache_dir();
+
+    let Some(packages) = install_dependencies(roc_file, RocCacheDir::Persistent(&cache_dir))
+    else {
+        return 1;
+    };
+
+    let lock_path = lockfile_path(roc_file);
+
+    if !write_lockfile(&lock_path, &packages) {
+        return 1;
+    }
+
+    print_installed(&packages);
+
+    println!(
+        "\n🎉 Recorded {} package{} in {}",
+        packages.len(),
+        if packages.len() == 1 { "" } else { "s" },
+        lock_path.display()
+    );
+
+    0
+}
+
+/// Copy every URL package that the given module depends on into a `vendor` dir next to it.
+/// Builds use those copies instead of the cache, so they don't need the network at all.
+/// Returns the exit code.
+pub fn vendor(roc_file: &Path) -> i32 {
+    if !roc_file.exists() {
+        eprintln!(
+            "\nThis file was not found: {}\n\nYou can run `roc help` for more information on how to provide a .roc file.\n",
+            roc_file.display()
+        );
+
+        return 1;
+    }
+
+    let cache_dir = cache::roc_cache_dir();
+
+    let packages = match cache::vendor_dependencies(RocCacheDir::Persistent(&cache_dir), roc_file) {
+        Ok(packages) => packages,
+        Err(problem) => {
+            report_install_problem(problem);
+
+            return 1;
+        }
+    };
+
+    if packages.is_empty() {
+        println!("{} has no URL dependencies to vendor.", roc_file.display());
+    } else {
+        print_installed(&packages);
+
+        println!(
+            "\n🎉 Vendored {} package{}. Builds will use these copies instead of downloading them.",
+            packages.len(),
+            if packages.len() == 1 { "" } else { "s" },
+        );
+    }
+
+    0
+}
+
+/// If the module has a `roc.lock`, make sure its dependencies still match it, printing what
+/// changed if they don't. Returns whether building it may go ahead.
+pub(crate) fn check_lockfile(roc_file: &Path, roc_cache_dir: RocCacheDir<'_>) -> bool {
+    let lock_path = lockfile_path(roc_file);
+
+    // Without a persistent cache there's nothing to download the dependencies into
+    // (e.g. in tests), so only the loader's own hash checks apply.
+    if !lock_path.exists() || !matches!(roc_cache_dir, RocCacheDir::Persistent(_)) {
+        return true;
+    }
+
+    match install_dependencies(roc_file, roc_cache_dir) {
+        Some(packages) => matches_lockfile(&lock_path, &packages),
+        None => false,
+    }
+}
+
+fn install_dependencies(
+    roc_file: &Path,
+    roc_cache_dir: RocCacheDir<'_>,
+) -> Option<Vec<InstalledPackage>> {
+    if !roc_file.exists() {
+        eprintln!(
+            "\nThis file was not found: {}\n\nYou can run `roc help` for more information on how to provide a .roc file.\n",
+            roc_file.display()
+        );
+
+        return None;
+    }
+
+    match cache::install_dependencies(roc_cache_dir, roc_file) {
+        Ok(packages) => Some(packages),
+        Err(problem) => {
+            report_install_problem(problem);
+
+            None
+        }
+    }
+}
+
+fn report_install_problem(problem: InstallProblem) {
+    match problem {
+        InstallProblem::Io(path, err) => {
+            eprintln!("I couldn't read or write {}: {err}", path.display());
+        }
+        InstallProblem::InvalidHeader(path) => {
+            eprintln!(
+                "I couldn't parse the header of {}, so I don't know what it depends on. Running `roc check` on it will show the problem.",
+                path.display()
+            );
+        }
+        InstallProblem::Download(url, problem) => {
+            eprintln!("{}", to_https_problem_report_string(&url, problem));
+        }
+    }
+}
+
+fn matches_lockfile(lock_path: &Path, packages: &[InstalledPackage]) -> bool {
+    let locked = match fs::read_to_string(lock_path) {
+        Ok(src) => Lockfile::parse(&src),
+        Err(err) => Err(err.to_string()),
+    };
+
+    let locked = match locked {
+        Ok(locked) => locked,
+        Err(problem) => {
+            eprintln!(
+                "\nI couldn't read {}: {problem}\n\nYou can run `roc update` to regenerate it.\n",
+                lock_path.display()
+            );
+
+            return false;
+        }
+    };
+
+    let differences = locked.differences(&Lockfile::from_installed(packages));
+
+    if differences.is_empty() {
+        return true;
+    }
+
+    eprintln!("\nThe packages this depends on don't match {LOCKFILE_NAME}:\n");
+
+    for difference in differences {
+        eprintln!("    {difference}");
+    }
+
+    eprintln!(
+        "\nIf these changes are intended, run `roc update` to record them in {}.\n",
+        lock_path.display()
+    );
+
+    false
+}
+
+fn write_lockfile(lock_path: &Path, packages: &[InstalledPackage]) -> bool {
+    match fs::write(lock_path, Lockfile::from_installed(packages).to_string()) {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("I couldn't write {}: {err}", lock_path.display());
+
+            false
+        }
+    }
+}
+
+fn print_installed(packages: &[InstalledPackage]) {
+    for package in packages.iter() {
+        println!(
+            "\u{001b}[36m{}\u{001b}[0m\n    is in {}",
+            package.url,
+            package.dir.display()
+        );
+    }
+}
@@ -5,14 +5,17 @@ extern crate const_format;
 
 use bumpalo::Bump;
 use clap::{
-    builder::PossibleValuesParser, parser::ValueSource, value_parser, Arg, ArgAction, ArgMatches,
-    Command,
+    builder::{PossibleValue, PossibleValuesParser},
+    parser::ValueSource,
+    value_parser, Arg, ArgAction, ArgMatches, Command,
 };
+use roc_build::emit::Emit;
 use roc_build::link::{LinkType, LinkingStrategy};
 use roc_build::program::{
     handle_error_module, handle_loading_problem, standard_load_config, BuildFileError,
     BuildOrdering, BuiltFile, CodeGenBackend, CodeGenOptions, DEFAULT_ROC_FILENAME,
 };
+use roc_compile::{FormatOptions, Migrations};
 use roc_error_macros::{internal_error, user_error};
 use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
@@ -20,6 +23,7 @@ use roc_load::{ExpectMetadata, Threading};
 use roc_mono::ir::OptLevel;
 use roc_packaging::cache::RocCacheDir;
 use roc_packaging::tarball::Compression;
+use roc_problem::{Level, WarningLevels};
 use roc_target::Target;
 use std::env;
 use std::ffi::{CString, OsStr, OsString};
@@ -28,14 +32,23 @@ use std::mem::ManuallyDrop;
 use std::os::raw::{c_char, c_int};
 use std::path::{Path, PathBuf};
 use std::process;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use strum::IntoEnumIterator;
 use target_lexicon::{Architecture, Triple};
 #[cfg(not(target_os = "linux"))]
 use tempfile::TempDir;
 
 mod format;
-pub use format::format;
+mod install;
+mod profile;
+mod publish;
+mod watch;
+mod workspace;
+pub use format::{format, format_stdin};
+pub use install::{install, update, vendor};
+pub use publish::publish;
+pub use watch::watch;
+use watch::Watcher;
 
 pub const CMD_BUILD: &str = "build";
 pub const CMD_RUN: &str = "run";
@@ -48,6 +61,12 @@ pub const CMD_FORMAT: &str = "format";
 pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
 pub const CMD_GEN_STUB_LIB: &str = "gen-stub-lib";
+pub const CMD_LSP: &str = "lsp";
+pub const CMD_KERNEL: &str = "kernel";
+pub const CMD_INSTALL: &str = "install";
+pub const CMD_UPDATE: &str = "update";
+pub const CMD_VENDOR: &str = "vendor";
+pub const CMD_PUBLISH: &str = "publish";
 
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_BUNDLE: &str = "bundle";
@@ -59,11 +78,41 @@ pub const FLAG_LIB: &str = "lib";
 pub const FLAG_NO_LINK: &str = "no-link";
 pub const FLAG_TARGET: &str = "target";
 pub const FLAG_TIME: &str = "time";
+pub const FLAG_VERBOSE: &str = "verbose";
 pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_PREBUILT: &str = "prebuilt-platform";
 pub const FLAG_CHECK: &str = "check";
+pub const FLAG_VERIFY: &str = "verify";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
+pub const FLAG_EVAL: &str = "eval";
+pub const FLAG_NO_BANNER: &str = "no-banner";
+pub const FLAG_OUTPUT: &str = "output";
+pub const FLAG_TIMEOUT: &str = "timeout";
+pub const FLAG_MAX_HEAP: &str = "max-heap";
+pub const FLAG_NO_COLOR: &str = "no-color";
+pub const FLAG_WIDTH: &str = "width";
+pub const FLAG_WATCH: &str = "watch";
+pub const FLAG_FAIL_FAST: &str = "fail-fast";
+pub const FLAG_PROFILE: &str = "profile";
+pub const FLAG_TRACE_ALLOCS: &str = "trace-allocs";
+pub const FLAG_EMIT: &str = "emit";
+pub const FLAG_VERIFY_REPRODUCIBLE: &str = "verify-reproducible";
+pub const FLAG_TRACE: &str = "trace";
+pub const FLAG_DOCS_FORMAT: &str = "format";
+pub const FLAG_STDIN: &str = "stdin";
+pub const FLAG_STDIN_FILENAME: &str = "stdin-filename";
+pub const FLAG_RANGE: &str = "range";
+pub const FLAG_PUN_RECORD_FIELDS: &str = "pun-record-fields";
+pub const FLAG_MIGRATE: &str = "migrate";
+pub const FLAG_ALLOW: &str = "allow";
+pub const FLAG_WARN: &str = "warn";
+pub const FLAG_DENY: &str = "deny";
+pub const FLAG_EXPLAIN_TYPES: &str = "explain-types";
+pub const FLAG_INSTALL: &str = "install";
+pub const FLAG_LANG: &str = "lang";
+pub const FLAG_DOC: &str = "doc";
 pub const ROC_FILE: &str = "ROC_FILE";
+pub const CONNECTION_FILE: &str = "CONNECTION_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_DIR: &str = "GLUE_DIR";
 pub const GLUE_SPEC: &str = "GLUE_SPEC";
@@ -99,7 +148,7 @@ pub fn build_app() -> Command {
 
     let flag_debug = Arg::new(FLAG_DEBUG)
         .long(FLAG_DEBUG)
-        .help("Store LLVM debug information in the generated program")
+        .help("Store debug information in the generated program, so debuggers can show which Roc function is running, and crashes print a stack trace of Roc functions\n(This uses the legacy linker, since the surgical linker doesn't keep debug information.)")
         .action(ArgAction::SetTrue)
         .required(false);
 
@@ -109,6 +158,64 @@ pub fn build_app() -> Command {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_verbose = Arg::new(FLAG_VERBOSE)
+        .long(FLAG_VERBOSE)
+        .help("Print how much unused code and data the linker left out of the executable\n(Only the legacy linker leaves out unused code.)")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_watch = Arg::new(FLAG_WATCH)
+        .long(FLAG_WATCH)
+        .help("Rebuild and rerun the app whenever one of its .roc files changes")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_fail_fast = Arg::new(FLAG_FAIL_FAST)
+        .long(FLAG_FAIL_FAST)
+        .help("Stop the app when an inline `expect` fails, instead of reporting it and carrying on")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_output_problems = Arg::new(FLAG_OUTPUT)
+        .long(FLAG_OUTPUT)
+        .help("How to print problems\n(`json` prints one object per problem per line, and then a summary object, for use by editors and other tools.\n`sarif` prints a single SARIF 2.1.0 log, for use by code scanning tools.)")
+        .value_parser(["text", "json", "sarif"])
+        .default_value("text")
+        .required(false);
+
+    let warning_names = || {
+        PossibleValuesParser::new(
+            roc_problem::WARNING_NAMES
+                .iter()
+                .copied()
+                .chain([roc_problem::ALL_WARNINGS]),
+        )
+    };
+
+    let flag_allow = Arg::new(FLAG_ALLOW)
+        .long(FLAG_ALLOW)
+        .value_name("WARNING")
+        .help("Don't report this kind of warning, e.g. `--allow unused-def`\n(`--allow warnings` applies to all of them. When a warning is named more than once, the last one wins.)")
+        .value_parser(warning_names())
+        .action(ArgAction::Append)
+        .required(false);
+
+    let flag_warn = Arg::new(FLAG_WARN)
+        .long(FLAG_WARN)
+        .value_name("WARNING")
+        .help("Report this kind of warning as a warning, which is the default\n(This is useful for making an exception to `--deny warnings`, or for seeing the type inferred for each `_` in type annotations with `--warn type-hole`.)")
+        .value_parser(warning_names())
+        .action(ArgAction::Append)
+        .required(false);
+
+    let flag_deny = Arg::new(FLAG_DENY)
+        .long(FLAG_DENY)
+        .value_name("WARNING")
+        .help("Report this kind of warning as an error, so it fails the build, e.g. `--deny unused-import`\n(`--deny warnings` applies to all of them.)")
+        .value_parser(warning_names())
+        .action(ArgAction::Append)
+        .required(false);
+
     let flag_linker = Arg::new(FLAG_LINKER)
         .long(FLAG_LINKER)
         .help("Set which linker to use\n(The surgical linker is enabled by default only when building for wasm32 or x86_64 Linux, because those are the only targets it currently supports. Otherwise the legacy linker is used by default.)")
@@ -127,6 +234,13 @@ pub fn build_app() -> Command {
         .value_parser(value_parser!(u32))
         .required(false);
 
+    let flag_no_color = Arg::new(FLAG_NO_COLOR)
+        .long(FLAG_NO_COLOR)
+        .help("Don't use colors in the output\n(Setting the ROC_NO_COLOR environment variable does the same.)")
+        .action(ArgAction::SetTrue)
+        .global(true)
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .value_parser(value_parser!(PathBuf))
@@ -139,21 +253,27 @@ pub fn build_app() -> Command {
         .num_args(0..)
         .allow_hyphen_values(true);
 
-    let build_target_values_parser =
-        PossibleValuesParser::new(Target::iter().map(Into::<&'static str>::into));
+    let build_target_values_parser = PossibleValuesParser::new(Target::iter().map(|target| {
+        PossibleValue::new(Into::<&'static str>::into(target)).aliases(target.aliases())
+    }));
 
-    Command::new("roc")
+    let app = Command::new("roc")
         .version(concatcp!(VERSION, "\n"))
         .about("Run the given .roc file, if there are no compilation errors.\nYou can use one of the SUBCOMMANDS below to do something else!")
         .args_conflicts_with_subcommands(true)
         .subcommand(Command::new(CMD_BUILD)
-            .about("Build a binary from the given .roc file, but don't run it")
+            .about("Build a binary from the given .roc file, but don't run it\n(Given a directory, builds every app and package in it.)")
+            .arg(flag_output_problems.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_warn.clone())
+            .arg(flag_deny.clone())
             .arg(flag_optimize.clone())
             .arg(flag_max_threads.clone())
             .arg(flag_opt_size.clone())
             .arg(flag_dev.clone())
             .arg(flag_debug.clone())
             .arg(flag_time.clone())
+            .arg(flag_verbose.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
             .arg(flag_wasm_stack_size_kb)
@@ -168,8 +288,11 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_LIB)
                     .long(FLAG_LIB)
-                    .help("Build a C library instead of an executable")
-                    .action(ArgAction::SetTrue)
+                    .help("Build a C library instead of an executable\n(Use --lib=static for a static library, e.g. a .a file, rather than a shared one.)")
+                    .value_parser(["shared", "static"])
+                    .num_args(0..=1)
+                    .require_equals(true)
+                    .default_missing_value("shared")
                     .required(false),
             )
             .arg(
@@ -180,6 +303,30 @@ pub fn build_app() -> Command {
                     .value_parser([".tar", ".tar.gz", ".tar.br"])
                     .required(false),
             )
+            .arg(
+                Arg::new(FLAG_EMIT)
+                    .long(FLAG_EMIT)
+                    .value_name("STAGE")
+                    .help("Also write out what a stage of the compiler made of the app, into a roc-emit directory next to it\n(`parse`, `can`, and `mono` write a file per module, and `llvm-ir` writes the app's optimized LLVM IR. `borrow-report` prints the calls that may copy a list instead of updating it in place, because something else may still refer to it. Can be given more than once.)")
+                    .value_parser(["parse", "can", "mono", "llvm-ir", "borrow-report"])
+                    .action(ArgAction::Append)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_VERIFY_REPRODUCIBLE)
+                    .long(FLAG_VERIFY_REPRODUCIBLE)
+                    .help("Build a second time, and check that both builds are byte-for-byte identical")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TRACE)
+                    .long(FLAG_TRACE)
+                    .value_name("OUTPUT")
+                    .help("Record how long each phase of the build took for each module, e.g. `--trace chrome://trace.json`\n(Chrome traces can be opened in Perfetto, at https://ui.perfetto.dev)")
+                    .value_parser(parse_trace_output)
+                    .required(false),
+            )
             .arg(
                 Arg::new(FLAG_NO_LINK)
                     .long(FLAG_NO_LINK)
@@ -189,14 +336,14 @@ pub fn build_app() -> Command {
             )
             .arg(
                 Arg::new(ROC_FILE)
-                    .help("The .roc file to build")
+                    .help("The .roc file to build, or a directory to build every app and package in")
                     .value_parser(value_parser!(PathBuf))
                     .required(false)
                     .default_value(DEFAULT_ROC_FILENAME),
             )
         )
         .subcommand(Command::new(CMD_TEST)
-            .about("Run all top-level `expect`s in a main module and any modules it imports")
+            .about("Run all top-level `expect`s in a main module and any modules it imports\n(Given a directory, runs the `expect`s in every .roc file inside it.)")
             .arg(flag_optimize.clone())
             .arg(flag_max_threads.clone())
             .arg(flag_opt_size.clone())
@@ -205,9 +352,16 @@ pub fn build_app() -> Command {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(
+                Arg::new(FLAG_DOC)
+                    .long(FLAG_DOC)
+                    .help("Run the examples in the module's doc comments instead of its `expect`s\n(An example is a Roc code block with `==` in it. Mark a block `unchecked` to leave it out.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
-                    .help("The .roc file for the main module")
+                    .help("The .roc file for the main module, or a directory of .roc files to test")
                     .value_parser(value_parser!(PathBuf))
                     .required(false)
                     .default_value(DEFAULT_ROC_FILENAME)
@@ -216,6 +370,49 @@ pub fn build_app() -> Command {
         )
         .subcommand(Command::new(CMD_REPL)
             .about("Launch the interactive Read Eval Print Loop (REPL)")
+            .arg(
+                Arg::new(FLAG_EVAL)
+                    .long(FLAG_EVAL)
+                    .help("Evaluate the given expression, print the result, and exit\n(Exits with a non-zero code if there were errors.)")
+                    .value_parser(value_parser!(String))
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_NO_BANNER)
+                    .long(FLAG_NO_BANNER)
+                    .help("Don't print the welcome message and instructions on startup\n(Useful when piping input into the REPL.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_OUTPUT)
+                    .long(FLAG_OUTPUT)
+                    .help("How to print results\n(`json` prints one `{ \"value\", \"type\", \"problems\" }` object per line, for use by other programs.)")
+                    .value_parser(["text", "json"])
+                    .default_value("text")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TIMEOUT)
+                    .long(FLAG_TIMEOUT)
+                    .help("Abort any evaluation that runs longer than this, e.g. `5s` or `500ms`")
+                    .value_parser(parse_duration)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_MAX_HEAP)
+                    .long(FLAG_MAX_HEAP)
+                    .help("Abort any evaluation that allocates more than this, e.g. `256M` or `1G`")
+                    .value_parser(parse_size)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_WIDTH)
+                    .long(FLAG_WIDTH)
+                    .help("Break results which are wider than this many columns over multiple lines\n(Defaults to the width of the terminal, if the output goes to one.)")
+                    .value_parser(value_parser!(usize))
+                    .required(false),
+            )
         )
         .subcommand(Command::new(CMD_RUN)
             .about("Run a .roc file even if it has build errors")
@@ -225,8 +422,27 @@ pub fn build_app() -> Command {
             .arg(flag_dev.clone())
             .arg(flag_debug.clone())
             .arg(flag_time.clone())
+            .arg(flag_verbose.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(
+                Arg::new(FLAG_PROFILE)
+                    .long(FLAG_PROFILE)
+                    .help("Sample the program's call stack while it runs, and write the samples to a file that flamegraph tools can draw\n(Only works on Linux. Implies --debug, and defaults to writing profile.folded.)")
+                    .value_parser(value_parser!(PathBuf))
+                    .num_args(0..=1)
+                    .require_equals(true)
+                    .default_missing_value("profile.folded")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TRACE_ALLOCS)
+                    .long(FLAG_TRACE_ALLOCS)
+                    .help("Record which Roc functions the program's heap allocations come from, and print a report when it exits\n(Only works on Linux. Implies --debug.)")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with(FLAG_PROFILE)
+                    .required(false),
+            )
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
@@ -238,13 +454,16 @@ pub fn build_app() -> Command {
             .arg(flag_dev.clone())
             .arg(flag_debug.clone())
             .arg(flag_time.clone())
+            .arg(flag_verbose.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_watch)
+            .arg(flag_fail_fast.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
         .subcommand(Command::new(CMD_FORMAT)
-            .about("Format a .roc file using standard Roc formatting")
+            .about("Format a .roc file using standard Roc formatting\n(A comment like `# roc-fmt: indent = 2, max-width = 100, trailing-commas = false` at the top of the file, or of its app or package's main.roc, changes the standard.)")
             .arg(
                 Arg::new(DIRECTORY_OR_FILES)
                     .index(1)
@@ -254,15 +473,75 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_CHECK)
                     .long(FLAG_CHECK)
-                    .help("Checks that specified files are formatted\n(If formatting is needed, return a non-zero exit code.)")
+                    .help("Checks that specified files are formatted\n(If formatting is needed, print a diff of the changes and return a non-zero exit code, without modifying any files.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_VERIFY)
+                    .long(FLAG_VERIFY)
+                    .help("Checks that formatting the specified files is safe, without modifying any files\n(Reports each file where the formatted code wouldn't parse the same way as the original, or would change again if formatted again, and returns a non-zero exit code.)")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with(FLAG_CHECK)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_STDIN)
+                    .long(FLAG_STDIN)
+                    .help("Format the code on stdin, and write the result to stdout")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with(DIRECTORY_OR_FILES)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_STDIN_FILENAME)
+                    .long(FLAG_STDIN_FILENAME)
+                    .help("The path of the file whose code is on stdin, for use in error messages")
+                    .value_parser(value_parser!(PathBuf))
+                    .requires(FLAG_STDIN)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_RANGE)
+                    .long(FLAG_RANGE)
+                    .help("Only format the top-level definitions that overlap these lines, like `10:42`\n(This needs exactly one file, or --stdin.)")
+                    .value_parser(format::parse_line_range)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_PUN_RECORD_FIELDS)
+                    .long(FLAG_PUN_RECORD_FIELDS)
+                    .help("Write record fields like `{ name: name }` as `{ name }`, in records and in record destructures")
                     .action(ArgAction::SetTrue)
                     .required(false),
             )
+            .arg(
+                Arg::new(FLAG_MIGRATE)
+                    .long(FLAG_MIGRATE)
+                    .value_name("MIGRATION")
+                    .help("Rewrite deprecated syntax into its modern equivalent, e.g. `--migrate backpassing`\n(`backpassing` turns `x <- f a` followed by `rest` into `f a \\x -> rest`. This can be given more than once.)")
+                    .value_parser(PossibleValuesParser::new(Migrations::NAMES))
+                    .action(ArgAction::Append)
+                    .required(false),
+            )
         )
+        .subcommand(Command::new(CMD_LSP)
+            .about("Start a language server, so editors can show problems, types, and definitions\n(It communicates over stdin and stdout using the Language Server Protocol.)"))
         .subcommand(Command::new(CMD_VERSION)
             .about(concatcp!("Print the Roc compiler’s version, which is currently ", VERSION)))
         .subcommand(Command::new(CMD_CHECK)
             .about("Check the code for problems, but don’t build or run it")
+            .arg(flag_output_problems)
+            .arg(flag_allow)
+            .arg(flag_warn)
+            .arg(flag_deny)
+            .arg(
+                Arg::new(FLAG_EXPLAIN_TYPES)
+                    .long(FLAG_EXPLAIN_TYPES)
+                    .help("Follow each type mismatch with the steps that led up to it\n(This makes checking slower.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
             .arg(
@@ -273,9 +552,56 @@ pub fn build_app() -> Command {
                     .default_value(DEFAULT_ROC_FILENAME),
             )
             )
+        .subcommand(Command::new(CMD_INSTALL)
+            .about("Download the packages an app or package depends on into the Roc cache\n(Builds can then happen offline. This also creates a roc.lock if there isn't one, and checks it if there is.)")
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of the app or package whose dependencies to install")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_UPDATE)
+            .about("Install the packages an app or package depends on, and record them in its roc.lock\n(Builds fail when their dependencies don't match roc.lock, so run this after changing them.)")
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of the app or package whose roc.lock to update")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_VENDOR)
+            .about("Copy the packages an app or package depends on into a vendor/ directory next to it\n(Builds use those copies instead of downloading anything, e.g. for CI without network access.)")
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of the app or package whose dependencies to vendor")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_PUBLISH)
+            .about("Check a package and bundle it into a .tar.br archive for people to download\n(Its modules must typecheck, its docs must build, and its exposed definitions may only mention exposed types.)")
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The package's main .roc file")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
         .subcommand(
             Command::new(CMD_DOCS)
                 .about("Generate documentation for a Roc package")
+                .arg(Arg::new(FLAG_DOCS_FORMAT)
+                    .long(FLAG_DOCS_FORMAT)
+                    .help("What to generate\n(`html` is a website, `markdown` is a .md file per module, and `json` is a single docs.json describing the package's API.)")
+                    .value_parser(["html", "markdown", "json"])
+                    .default_value("html")
+                    .required(false)
+                )
                 .arg(Arg::new(ROC_FILE)
                     .help("The package's main .roc file")
                     .value_parser(value_parser!(PathBuf))
@@ -285,18 +611,29 @@ pub fn build_app() -> Command {
         )
         .subcommand(Command::new(CMD_GLUE)
             .about("Generate glue code between a platform's Roc API and its host language")
+            // With --lang there's no spec, so the other arguments each move up one position.
+            // clap assigns positional arguments strictly in order, so main.rs does that shift.
+            .override_usage("roc glue [--dev] <GLUE_SPEC> <GLUE_DIR> [ROC_FILE]\n       roc glue --lang <LANG> <GLUE_DIR> [ROC_FILE]")
             .arg(&flag_dev)
+            .arg(
+                Arg::new(FLAG_LANG)
+                    .long(FLAG_LANG)
+                    .help("Generate glue for this language with one of the backends built into roc, instead of with a spec")
+                    .value_parser(PossibleValuesParser::new(roc_glue::backend::LANGS))
+                    .conflicts_with(FLAG_DEV)
+                    .required(false)
+            )
             .arg(
                 Arg::new(GLUE_SPEC)
-                    .help("The specification for how to translate Roc types into output files.")
+                    .help("The specification for how to translate Roc types into output files.\nLeave this out when using --lang.")
                     .value_parser(value_parser!(PathBuf))
-                    .required(true)
+                    .required_unless_present(FLAG_LANG)
             )
             .arg(
                 Arg::new(GLUE_DIR)
                     .help("The directory for the generated glue code.\nNote: The implementation can write to any file in this directory.")
                     .value_parser(value_parser!(PathBuf))
-                    .required(true)
+                    .required(false)
             )
             .arg(
                 Arg::new(ROC_FILE)
@@ -329,10 +666,35 @@ pub fn build_app() -> Command {
         .arg(flag_dev)
         .arg(flag_debug)
         .arg(flag_time)
+        .arg(flag_verbose)
         .arg(flag_linker)
         .arg(flag_prebuilt)
+        .arg(flag_no_color)
+        .arg(flag_fail_fast)
         .arg(roc_file_to_run)
-        .arg(args_for_app.trailing_var_arg(true))
+        .arg(args_for_app.trailing_var_arg(true));
+
+    // The Jupyter kernel needs libzmq, so it's only there when roc is built with it.
+    if cfg!(feature = "jupyter-kernel") {
+        app.subcommand(Command::new(CMD_KERNEL)
+            .about("Start a Jupyter kernel, so notebooks can run Roc code like the REPL does\n(Jupyter starts this itself, once `roc kernel --install` has told it how.)")
+            .arg(
+                Arg::new(FLAG_INSTALL)
+                    .long(FLAG_INSTALL)
+                    .help("Install the kernel for the current user, instead of starting it")
+                    .action(ArgAction::SetTrue)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(CONNECTION_FILE)
+                    .help("The connection file Jupyter gives the kernel, which says which ports to use")
+                    .value_parser(value_parser!(PathBuf))
+                    .required_unless_present(FLAG_INSTALL),
+            )
+        )
+    } else {
+        app
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -345,6 +707,117 @@ pub enum BuildConfig {
 pub enum FormatMode {
     Format,
     CheckOnly,
+    /// Check that formatting reparses as the same tree and is stable, without writing anything.
+    Verify,
+}
+
+/// Parse a duration like `5s`, `500ms`, or `2m` (a plain number is in seconds).
+fn parse_duration(src: &str) -> Result<Duration, String> {
+    let (number, unit) = split_number(src);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("`{src}` is not a duration like `5s` or `500ms`"))?;
+
+    match unit {
+        "ms" => Ok(Duration::from_millis(number)),
+        "" | "s" => Ok(Duration::from_secs(number)),
+        "m" => Ok(Duration::from_secs(number * 60)),
+        _ => Err(format!(
+            "`{unit}` is not a unit of time; use `ms`, `s`, or `m`"
+        )),
+    }
+}
+
+/// Parse a number of bytes like `256M`, `1G`, or `512K` (a plain number is in bytes).
+fn parse_size(src: &str) -> Result<u64, String> {
+    let (number, unit) = split_number(src);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("`{src}` is not a size like `256M` or `1G`"))?;
+
+    let multiplier: u64 = match unit.to_ascii_uppercase().trim_end_matches('B') {
+        "" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => {
+            return Err(format!(
+                "`{unit}` is not a unit of size; use `K`, `M`, or `G`"
+            ))
+        }
+    };
+
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("`{src}` is too big"))
+}
+
+/// Split e.g. `256M` into `256` and `M`
+fn split_number(src: &str) -> (&str, &str) {
+    let src = src.trim();
+    let unit_start = src
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(src.len());
+
+    src.split_at(unit_start)
+}
+
+/// Parse where `--trace` writes to, e.g. `chrome://trace.json`.
+fn parse_trace_output(src: &str) -> Result<PathBuf, String> {
+    match src.strip_prefix("chrome://") {
+        Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => Err(format!(
+            "`{src}` is not a trace output like `chrome://trace.json`"
+        )),
+    }
+}
+
+/// The levels given with `--allow`, `--warn` and `--deny`, applied in the order
+/// they appear on the command line. Commands without these flags get the default levels.
+pub fn warning_levels_from_flags(matches: &ArgMatches) -> WarningLevels {
+    let mut flags = Vec::new();
+
+    for (flag, level) in [
+        (FLAG_ALLOW, Level::Allow),
+        (FLAG_WARN, Level::Warn),
+        (FLAG_DENY, Level::Deny),
+    ] {
+        if let Ok(Some(names)) = matches.try_get_many::<String>(flag) {
+            let indices = matches.indices_of(flag).unwrap();
+
+            flags.extend(indices.zip(names).map(|(index, name)| (index, name, level)));
+        }
+    }
+
+    flags.sort_by_key(|(index, _, _)| *index);
+
+    let mut levels = WarningLevels::new();
+
+    for (_, name, level) in flags {
+        // clap has already checked that these are names of warnings
+        levels.set(name, level).unwrap();
+    }
+
+    levels
+}
+
+pub fn format_options_from_flags(matches: &ArgMatches) -> FormatOptions {
+    let mut migrations = Migrations::default();
+
+    for name in matches
+        .get_many::<String>(FLAG_MIGRATE)
+        .into_iter()
+        .flatten()
+    {
+        // clap has already checked that these are names of migrations
+        migrations.enable(name).unwrap();
+    }
+
+    FormatOptions {
+        pun_record_fields: matches.get_flag(FLAG_PUN_RECORD_FIELDS),
+        migrations,
+        ..FormatOptions::default()
+    }
 }
 
 fn opt_level_from_flags(matches: &ArgMatches) -> OptLevel {
@@ -368,13 +841,9 @@ pub fn test(_matches: &ArgMatches, _triple: Triple) -> io::Result<i32> {
 
 #[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
-    use roc_build::program::report_problems_monomorphized;
-    use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
-    use roc_packaging::cache;
-    use roc_target::TargetInfo;
+    use roc_collections::MutSet;
 
     let start_time = Instant::now();
-    let arena = Bump::new();
     let opt_level = opt_level_from_flags(matches);
 
     let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
@@ -385,6 +854,7 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     };
 
     let path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+    let doc = matches.get_flag(FLAG_DOC);
 
     // Spawn the root task
     if !path.exists() {
@@ -407,9 +877,152 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         process::exit(1);
     }
 
-    let arena = &arena;
-    let target = &triple;
-    let opt_level = opt_level;
+    let is_dir = path.is_dir();
+    let roc_files = if is_dir {
+        let mut roc_files = Vec::new();
+
+        roc_files_in_dir(path, &mut roc_files)?;
+        roc_files.sort();
+
+        if roc_files.is_empty() {
+            eprintln!(
+                "\nThis directory does not contain any .roc files: {}\n\nYou can run `roc help` for more information on how to provide a .roc file.\n",
+                path.display()
+            );
+
+            process::exit(1);
+        }
+
+        roc_files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    // Every module a root imports has its expects run along with the root's,
+    // so remember which modules have been tested to avoid running them twice.
+    let mut tested_modules = MutSet::default();
+    let (mut failed, mut passed) = (0, 0);
+    // With a directory, keep testing the other modules when one can't be compiled,
+    // and list all the ones that couldn't at the end.
+    let mut not_compiled = Vec::new();
+
+    for roc_file in roc_files {
+        if tested_modules.contains(&canonical_path(&roc_file)) {
+            continue;
+        }
+
+        match test_module(
+            &roc_file,
+            &triple,
+            opt_level,
+            threading,
+            doc,
+            start_time,
+            &mut tested_modules,
+        )? {
+            Ok((module_failed, module_passed)) => {
+                failed += module_failed;
+                passed += module_passed;
+            }
+            Err(exit_code) if !is_dir => return Ok(exit_code),
+            Err(_) => {
+                // Finish the line the problems' summary was printed on.
+                println!();
+
+                not_compiled.push(roc_file);
+            }
+        }
+    }
+
+    let total_time = start_time.elapsed();
+
+    let exit_code = if failed == 0 && passed == 0 && !not_compiled.is_empty() {
+        // The modules that couldn't be compiled may well have had expects,
+        // so don't say there weren't any.
+        1
+    } else if failed == 0 && passed == 0 {
+        // TODO print this in a more nicely formatted way!
+        if doc {
+            println!("No examples were found in doc comments.");
+        } else {
+            println!("No expectations were found.");
+        }
+
+        // If no tests ran, treat that as an error. This is perhaps
+        // briefly annoying at the very beginning of a project when
+        // you actually have zero tests, but it can save you from
+        // having a change to your CI script accidentally stop
+        // running tests altogether!
+        2
+    } else {
+        let failed_color = if failed == 0 {
+            32 // green
+        } else {
+            31 // red
+        };
+
+        let summary = format!(
+            "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
+            total_time.as_millis(),
+        );
+
+        println!(
+            "{}",
+            roc_reporting::report::strip_colors_if_disabled(&summary)
+        );
+
+        (failed > 0 || !not_compiled.is_empty()) as i32
+    };
+
+    if !not_compiled.is_empty() {
+        let mut message = format!(
+            "\x1B[31m{}\x1B[39m {} could not be compiled, so {} not tested:\n",
+            not_compiled.len(),
+            match not_compiled.len() {
+                1 => "module",
+                _ => "modules",
+            },
+            match not_compiled.len() {
+                1 => "it was",
+                _ => "they were",
+            },
+        );
+
+        for roc_file in not_compiled {
+            message.push_str(&format!("\n    {}", roc_file.display()));
+        }
+
+        println!(
+            "{}\n",
+            roc_reporting::report::strip_colors_if_disabled(&message)
+        );
+    }
+
+    Ok(exit_code)
+}
+
+/// Load the module at `path` (along with everything it imports) and run the
+/// top-level expects of every module not already in `tested_modules`. With `doc`,
+/// run the examples in the module's doc comments instead.
+///
+/// Returns the number of failed and passed expects, or the exit code for the module's
+/// problems if it could not be compiled.
+#[cfg(not(windows))]
+fn test_module(
+    path: &Path,
+    target: &Triple,
+    opt_level: OptLevel,
+    threading: Threading,
+    doc: bool,
+    start_time: Instant,
+    tested_modules: &mut roc_collections::MutSet<PathBuf>,
+) -> io::Result<Result<(usize, usize), i32>> {
+    use roc_build::program::report_problems_monomorphized;
+    use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
+    use roc_packaging::cache;
+    use roc_target::TargetInfo;
+
+    let arena = &Bump::new();
     let target_info = TargetInfo::from(target);
     // TODO may need to determine this dynamically based on dev builds.
     let function_kind = FunctionKind::LambdaSet;
@@ -423,25 +1036,88 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Test,
+        warning_levels: Default::default(),
+        explain_types: false,
+    };
+    let roc_cache_dir = RocCacheDir::Persistent(cache::roc_cache_dir().as_path());
+
+    // With --doc, the module is loaded with its examples added to the end as expects,
+    // and only the expects after `doc_examples_start` get run.
+    let (load_result, doc_examples_start) = if doc {
+        let src = std::fs::read_to_string(path)?;
+        let examples = roc_docs::doc_tests::doc_examples(&src);
+
+        tested_modules.insert(canonical_path(path));
+
+        if examples.is_empty() {
+            return Ok(Ok((0, 0)));
+        }
+
+        let src_with_examples = roc_docs::doc_tests::with_doc_examples(&src, &examples);
+        let src_dir = path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        let load_result = roc_load::load_and_monomorphize_from_str(
+            arena,
+            path.to_path_buf(),
+            arena.alloc(src_with_examples),
+            src_dir,
+            roc_cache_dir,
+            load_config,
+        );
+
+        (load_result, Some(src.len() as u32))
+    } else {
+        let load_result =
+            roc_load::load_and_monomorphize(arena, path.to_path_buf(), roc_cache_dir, load_config);
+
+        (load_result, None)
     };
-    let load_result = roc_load::load_and_monomorphize(
-        arena,
-        path.to_path_buf(),
-        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
-        load_config,
-    );
 
     let mut loaded = match load_result {
         Ok(loaded) => loaded,
         Err(LoadMonomorphizedError::LoadingProblem(problem)) => {
-            return handle_loading_problem(problem);
+            return handle_loading_problem(problem).map(Err);
         }
         Err(LoadMonomorphizedError::ErrorModule(module)) => {
-            return handle_error_module(module, start_time.elapsed(), path.as_os_str(), false);
+            return handle_error_module(module, start_time.elapsed(), path.as_os_str(), false)
+                .map(Err);
         }
     };
     let problems = report_problems_monomorphized(&mut loaded);
 
+    // Skip the expects of modules an earlier root already imported and tested.
+    let module_paths: roc_collections::MutMap<_, _> = loaded
+        .sources
+        .iter()
+        .map(|(module_id, (module_path, _))| (*module_id, canonical_path(module_path)))
+        .collect();
+    // With --doc, skip everything but the examples.
+    let root_module_id = loaded.module_id;
+    let already_tested: Vec<_> = loaded
+        .toplevel_expects
+        .pure
+        .iter()
+        .chain(loaded.toplevel_expects.fx.iter())
+        .filter(|(symbol, region)| match doc_examples_start {
+            Some(start) => symbol.module_id() != root_module_id || region.start().offset < start,
+            None => module_paths
+                .get(&symbol.module_id())
+                .map_or(false, |module_path| tested_modules.contains(module_path)),
+        })
+        .map(|(symbol, _)| *symbol)
+        .collect();
+
+    for symbol in already_tested {
+        loaded.toplevel_expects.pure.remove(&symbol);
+        loaded.toplevel_expects.fx.remove(&symbol);
+    }
+
+    if !doc {
+        tested_modules.extend(module_paths.into_values());
+    }
+
     let mut expectations = std::mem::take(&mut loaded.expectations);
 
     let interns = loaded.interns.clone();
@@ -473,7 +1149,7 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
 
     let mut writer = std::io::stdout();
 
-    let (failed, passed) = roc_repl_expect::run::run_toplevel_expects(
+    let counts = roc_repl_expect::run::run_toplevel_expects(
         &mut writer,
         roc_reporting::report::RenderTarget::ColorTerminal,
         arena,
@@ -485,32 +1161,35 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     )
     .unwrap();
 
-    let total_time = start_time.elapsed();
+    Ok(Ok(counts))
+}
 
-    if failed == 0 && passed == 0 {
-        // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+/// Collect every .roc file under `dir`, skipping hidden directories
+/// (such as `.git`) along the way.
+fn roc_files_in_dir(dir: &Path, roc_files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
 
-        // If no tests ran, treat that as an error. This is perhaps
-        // briefly annoying at the very beginning of a project when
-        // you actually have zero tests, but it can save you from
-        // having a change to your CI script accidentally stop
-        // running tests altogether!
-        Ok(2)
-    } else {
-        let failed_color = if failed == 0 {
-            32 // green
-        } else {
-            31 // red
-        };
-
-        println!(
-            "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
-            total_time.as_millis(),
-        );
+        if path.is_dir() {
+            let is_hidden = path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .map_or(false, |name| name.starts_with('.'));
 
-        Ok((failed > 0) as i32)
+            if !is_hidden {
+                roc_files_in_dir(&path, roc_files)?;
+            }
+        } else if path.extension().and_then(OsStr::to_str) == Some("roc") {
+            roc_files.push(path);
+        }
     }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
 }
 
 /// Find the element of `options` with the smallest edit distance to
@@ -531,9 +1210,28 @@ pub fn build(
     roc_cache_dir: RocCacheDir<'_>,
     link_type: LinkType,
 ) -> io::Result<i32> {
-    use roc_build::program::build_file;
-    use BuildConfig::*;
+    build_with_watcher(
+        matches,
+        subcommands,
+        config,
+        triple,
+        roc_cache_dir,
+        link_type,
+        None,
+    )
+}
 
+/// Like `build`, except when given a `Watcher`, it tells the watcher which files
+/// went into the build, and stops the app (rather than exiting) when one of them changes.
+fn build_with_watcher(
+    matches: &ArgMatches,
+    subcommands: &[String],
+    config: BuildConfig,
+    triple: Triple,
+    roc_cache_dir: RocCacheDir<'_>,
+    link_type: LinkType,
+    watcher: Option<&mut Watcher>,
+) -> io::Result<i32> {
     let path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
     {
         // Spawn the root task
@@ -580,6 +1278,19 @@ pub fn build(
             process::exit(1);
         }
 
+        if path.is_dir() {
+            if config == BuildConfig::BuildOnly && !matches.contains_id(FLAG_BUNDLE) {
+                return workspace::build_workspace(matches, path, triple, roc_cache_dir, link_type);
+            }
+
+            eprintln!(
+                "\n{} is a directory. `roc build` can build every app and package in a directory, but otherwise I need the path to a .roc file.\n",
+                path.display()
+            );
+
+            return Ok(1);
+        }
+
         if config == BuildConfig::BuildOnly && matches.contains_id(FLAG_BUNDLE) {
             let start_time = Instant::now();
 
@@ -622,12 +1333,43 @@ pub fn build(
 
             return Ok(0);
         }
+
+        if !install::check_lockfile(path, roc_cache_dir) {
+            return Ok(1);
+        }
     }
 
     // the process will end after this function,
     // so we don't want to spend time freeing these values
     let arena = ManuallyDrop::new(Bump::new());
 
+    build_module(
+        &arena,
+        matches,
+        config,
+        triple,
+        roc_cache_dir,
+        link_type,
+        watcher,
+        path,
+    )
+}
+
+/// Build the module at `path` with the options in `matches`, then run it if `config` says so.
+#[allow(clippy::too_many_arguments)]
+fn build_module(
+    arena: &Bump,
+    matches: &ArgMatches,
+    config: BuildConfig,
+    triple: Triple,
+    roc_cache_dir: RocCacheDir<'_>,
+    link_type: LinkType,
+    mut watcher: Option<&mut Watcher>,
+    path: &Path,
+) -> io::Result<i32> {
+    use roc_build::program::build_file;
+    use BuildConfig::*;
+
     let opt_level = if let BuildConfig::BuildAndRunIfNoErrors = config {
         OptLevel::Development
     } else {
@@ -651,7 +1393,30 @@ pub fn build(
         CodeGenBackend::Llvm(backend_mode)
     };
 
-    let emit_debug_info = matches.get_flag(FLAG_DEBUG);
+    let profile_path = matches.try_get_one::<PathBuf>(FLAG_PROFILE).ok().flatten();
+
+    let trace_allocs = matches
+        .try_get_one::<bool>(FLAG_TRACE_ALLOCS)
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+
+    if (profile_path.is_some() || trace_allocs) && !profile::SUPPORTED {
+        let flag = if trace_allocs {
+            FLAG_TRACE_ALLOCS
+        } else {
+            FLAG_PROFILE
+        };
+
+        eprintln!("\n`--{flag}` only works on Linux (on x86_64 and aarch64) for now.\n");
+
+        return Ok(1);
+    }
+
+    // Profiles and allocation traces are made by following frame pointers,
+    // which builds with debug info keep.
+    let emit_debug_info = matches.get_flag(FLAG_DEBUG) || profile_path.is_some() || trace_allocs;
     let emit_timings = matches.get_flag(FLAG_TIME);
 
     let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
@@ -667,6 +1432,7 @@ pub fn build(
         LinkingStrategy::Additive
     } else if !roc_linker::supported(link_type, &triple)
         || matches.get_one::<String>(FLAG_LINKER).map(|s| s.as_str()) == Some("legacy")
+        || emit_debug_info
     {
         LinkingStrategy::Legacy
     } else {
@@ -691,21 +1457,68 @@ pub fn build(
         .flatten()
         .map(|x| x * 1024);
 
+    let fail_fast = matches
+        .try_get_one::<bool>(FLAG_FAIL_FAST)
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+
+    let verify_reproducible = matches
+        .try_get_one::<bool>(FLAG_VERIFY_REPRODUCIBLE)
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+
     let build_ordering = match config {
         BuildAndRunIfNoErrors => BuildOrdering::BuildIfChecks,
         _ => BuildOrdering::AlwaysBuild,
     };
 
+    let emits: Vec<&String> = matches
+        .try_get_many::<String>(FLAG_EMIT)
+        .ok()
+        .flatten()
+        .map(|emits| emits.collect())
+        .unwrap_or_default();
+    let emitting = |stage: &str| emits.iter().any(|emit| *emit == stage);
+    let emit = Emit {
+        borrow_report: emitting("borrow-report"),
+        parse: emitting("parse"),
+        can: emitting("can"),
+        mono: emitting("mono"),
+        llvm_ir: emitting("llvm-ir"),
+        dead_code_report: matches
+            .try_get_one::<bool>(FLAG_VERBOSE)
+            .ok()
+            .flatten()
+            .copied()
+            .unwrap_or(false),
+    };
+
+    if emit.llvm_ir && !matches!(code_gen_backend, CodeGenBackend::Llvm(_)) {
+        eprintln!("\n`--{FLAG_EMIT} llvm-ir` needs the LLVM backend, so it can't be used with `--{FLAG_DEV}`.\n");
+
+        return Ok(1);
+    }
+
     let code_gen_options = CodeGenOptions {
         backend: code_gen_backend,
         opt_level,
         emit_debug_info,
+        emit,
     };
 
-    let load_config = standard_load_config(&triple, build_ordering, threading);
+    let load_config = standard_load_config(
+        &triple,
+        build_ordering,
+        threading,
+        warning_levels_from_flags(matches),
+    );
 
     let res_binary_path = build_file(
-        &arena,
+        arena,
         &triple,
         path.to_owned(),
         code_gen_options,
@@ -724,7 +1537,12 @@ pub fn build(
             problems,
             total_time,
             expect_metadata,
+            sources,
         }) => {
+            if let Some(watcher) = watcher.as_deref_mut() {
+                watcher.watch(sources);
+            }
+
             match config {
                 BuildOnly => {
                     // If possible, report the generated executable name relative to the current dir.
@@ -739,7 +1557,53 @@ pub fn build(
                     // std::mem::forget(arena);
 
                     problems.print_to_stdout(total_time);
-                    println!(" while successfully building:\n\n    {generated_filename}");
+
+                    if !roc_reporting::cli::json_output_enabled() {
+                        println!(" while successfully building:\n\n    {generated_filename}");
+                    }
+
+                    if verify_reproducible && !problems.fatally_errored {
+                        let first_build = std::fs::read(&binary_path)?;
+
+                        // The second build only needs to produce the binary again.
+                        let second_build = build_file(
+                            arena,
+                            &triple,
+                            path.to_owned(),
+                            CodeGenOptions {
+                                emit: Emit::default(),
+                                ..code_gen_options
+                            },
+                            false,
+                            link_type,
+                            linking_strategy,
+                            prebuilt,
+                            wasm_dev_stack_bytes,
+                            roc_cache_dir,
+                            standard_load_config(
+                                &triple,
+                                BuildOrdering::AlwaysBuild,
+                                threading,
+                                warning_levels_from_flags(matches),
+                            ),
+                        );
+
+                        let exit_code = match second_build {
+                            Ok(built) => {
+                                let second_build = std::fs::read(built.binary_path)?;
+
+                                compare_builds(&first_build, &second_build)
+                            }
+                            Err(BuildFileError::ErrorModule { module, total_time }) => {
+                                handle_error_module(module, total_time, path.as_os_str(), true)?
+                            }
+                            Err(BuildFileError::LoadingProblem(problem)) => {
+                                handle_loading_problem(problem)?
+                            }
+                        };
+
+                        return Ok(exit_code.max(problems.exit_code()));
+                    }
 
                     // Return a nonzero exit code if there were problems
                     Ok(problems.exit_code())
@@ -772,7 +1636,24 @@ pub fn build(
                     // ManuallyDrop will leak the bytes because we don't drop manually
                     let bytes = &ManuallyDrop::new(std::fs::read(&binary_path).unwrap());
 
-                    roc_run(&arena, opt_level, triple, args, bytes, expect_metadata)
+                    if let Some(profile_path) = profile_path {
+                        return profile::run_with_profiler(arena, args, bytes, profile_path);
+                    }
+
+                    if trace_allocs {
+                        return profile::run_with_alloc_tracer(arena, args, bytes);
+                    }
+
+                    roc_run(
+                        arena,
+                        opt_level,
+                        triple,
+                        args,
+                        bytes,
+                        expect_metadata,
+                        fail_fast,
+                        None,
+                    )
                 }
                 BuildAndRunIfNoErrors => {
                     if problems.fatally_errored {
@@ -807,17 +1688,56 @@ pub fn build(
                     // ManuallyDrop will leak the bytes because we don't drop manually
                     let bytes = &ManuallyDrop::new(std::fs::read(&binary_path).unwrap());
 
-                    roc_run(&arena, opt_level, triple, args, bytes, expect_metadata)
+                    roc_run(
+                        arena,
+                        opt_level,
+                        triple,
+                        args,
+                        bytes,
+                        expect_metadata,
+                        fail_fast,
+                        watcher.as_deref(),
+                    )
                 }
             }
         }
         Err(BuildFileError::ErrorModule { module, total_time }) => {
+            if let Some(watcher) = watcher {
+                watcher.watch(module.sources.values().map(|(path, _)| path.clone()));
+            }
+
             handle_error_module(module, total_time, path.as_os_str(), true)
         }
         Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
     }
 }
 
+/// Prints whether two builds of the same module came out the same, and returns the exit code.
+fn compare_builds(first_build: &[u8], second_build: &[u8]) -> i32 {
+    if first_build == second_build {
+        println!(
+            "\nBuilt it a second time, and both builds were byte-for-byte identical ({} bytes).",
+            first_build.len()
+        );
+
+        return 0;
+    }
+
+    let first_difference = first_build
+        .iter()
+        .zip(second_build)
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| first_build.len().min(second_build.len()));
+
+    eprintln!(
+        "\nBuilt it a second time, but the builds are not the same. They first differ at byte {first_difference} (the first build is {} bytes, and the second is {} bytes).\n",
+        first_build.len(),
+        second_build.len()
+    );
+
+    1
+}
+
 fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
     arena: &Bump,
     opt_level: OptLevel,
@@ -825,6 +1745,8 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
     args: I,
     binary_bytes: &[u8],
     expect_metadata: ExpectMetadata,
+    fail_fast: bool,
+    watcher: Option<&Watcher>,
 ) -> io::Result<i32> {
     match triple.architecture {
         Architecture::Wasm32 => {
@@ -835,31 +1757,45 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
                 .strip_prefix(env::current_dir().unwrap())
                 .unwrap_or(path);
 
+            // WASI expects the .wasm file to be argv[0], followed by the args given after `--`
             #[cfg(target_family = "unix")]
             {
                 use std::os::unix::ffi::OsStrExt;
 
+                let program = generated_filename.as_os_str().as_bytes();
+
                 run_wasm(
                     generated_filename,
-                    args.into_iter().map(|os_str| os_str.as_bytes()),
+                    std::iter::once(program)
+                        .chain(args.into_iter().map(|os_str| os_str.as_bytes())),
                 );
             }
 
             #[cfg(not(target_family = "unix"))]
             {
+                let program = generated_filename.to_string_lossy();
+
                 run_wasm(
                     generated_filename,
-                    args.into_iter().map(|os_str| {
+                    std::iter::once(program.as_ref()).chain(args.into_iter().map(|os_str| {
                         os_str.to_str().expect(
                             "Roc does not currently support passing non-UTF8 arguments to Wasm.",
                         )
-                    }),
+                    })),
                 );
             }
 
             Ok(0)
         }
-        _ => roc_run_native(arena, opt_level, args, binary_bytes, expect_metadata),
+        _ => roc_run_native(
+            arena,
+            opt_level,
+            args,
+            binary_bytes,
+            expect_metadata,
+            fail_fast,
+            watcher,
+        ),
     }
 }
 
@@ -927,6 +1863,8 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     args: I,
     binary_bytes: &[u8],
     expect_metadata: ExpectMetadata,
+    fail_fast: bool,
+    watcher: Option<&Watcher>,
 ) -> std::io::Result<i32> {
     use bumpalo::collections::CollectIn;
 
@@ -947,16 +1885,72 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
             .collect_in(arena);
 
         match opt_level {
-            OptLevel::Development => roc_dev_native(arena, executable, argv, envp, expect_metadata),
-            OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => {
-                roc_run_native_fast(executable, &argv, &envp);
-            }
+            OptLevel::Development => roc_dev_native(
+                arena,
+                executable,
+                argv,
+                envp,
+                expect_metadata,
+                fail_fast,
+                watcher,
+            ),
+            OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => match watcher {
+                // Replacing roc with the app would end the watch, so run it as a child instead.
+                Some(watcher) => roc_run_native_watched(executable, &argv, &envp, watcher),
+                None => roc_run_native_fast(executable, &argv, &envp),
+            },
         }
     }
 
     Ok(1)
 }
 
+/// Run the app in a child process until it exits, or until one of the watched files changes,
+/// in which case the app gets stopped.
+#[cfg(target_family = "unix")]
+fn roc_run_native_watched(
+    executable: ExecutableFile,
+    argv: &[*const c_char],
+    envp: &[*const c_char],
+    watcher: &Watcher,
+) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let child = match unsafe { libc::fork() } {
+        0 => unsafe {
+            // we are the child
+
+            executable.execve(argv, envp);
+
+            // Display a human-friendly error message
+            println!("Error {:?}", std::io::Error::last_os_error());
+
+            std::process::exit(1);
+        },
+        -1 => {
+            // Display a human-friendly error message
+            println!("Error {:?}", std::io::Error::last_os_error());
+
+            return;
+        }
+        child => child,
+    };
+
+    let app_done = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            if watcher.wait_for_change(&app_done) {
+                unsafe { libc::kill(child, libc::SIGTERM) };
+            }
+        });
+
+        unsafe { libc::waitpid(child, std::ptr::null_mut(), 0) };
+
+        app_done.store(true, Ordering::Relaxed);
+    });
+}
+
 unsafe fn roc_run_native_fast(
     executable: ExecutableFile,
     argv: &[*const c_char],
@@ -1020,6 +2014,9 @@ impl ExecutableFile {
 }
 
 // with Expect
+//
+// Exits once the app does, unless there's a watcher; in that case, the app gets
+// stopped when a watched file changes, and this returns when the app is done.
 #[cfg(target_family = "unix")]
 fn roc_dev_native(
     arena: &Bump,
@@ -1027,8 +2024,13 @@ fn roc_dev_native(
     argv: bumpalo::collections::Vec<*const c_char>,
     envp: bumpalo::collections::Vec<*const c_char>,
     expect_metadata: ExpectMetadata,
-) -> ! {
-    use std::sync::{atomic::AtomicBool, Arc};
+    fail_fast: bool,
+    watcher: Option<&Watcher>,
+) {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
 
     use roc_repl_expect::run::{ChildProcessMsg, ExpectMemory};
 
@@ -1065,14 +2067,16 @@ fn roc_dev_native(
 
             std::process::exit(1)
         }
-        1.. => {
+        child @ 1.. => {
             let sigchld = Arc::new(AtomicBool::new(false));
-            signal_hook::flag::register(signal_hook::consts::SIGCHLD, Arc::clone(&sigchld))
-                .unwrap();
+            let sigchld_id =
+                signal_hook::flag::register(signal_hook::consts::SIGCHLD, Arc::clone(&sigchld))
+                    .unwrap();
 
-            loop {
+            // Returns whether the app was stopped because an `expect` failed
+            let mut render_until_exit = || loop {
                 match memory.wait_for_child(sigchld.clone()) {
-                    ChildProcessMsg::Terminate => break,
+                    ChildProcessMsg::Terminate => break false,
                     ChildProcessMsg::Expect => {
                         roc_repl_expect::run::render_expects_in_memory(
                             &mut writer,
@@ -1085,6 +2089,12 @@ fn roc_dev_native(
                         .unwrap();
 
                         memory.reset();
+
+                        if fail_fast {
+                            unsafe { libc::kill(child, libc::SIGKILL) };
+
+                            break true;
+                        }
                     }
                     ChildProcessMsg::Dbg => {
                         roc_repl_expect::run::render_dbgs_in_memory(
@@ -1100,9 +2110,31 @@ fn roc_dev_native(
                         memory.reset();
                     }
                 }
-            }
+            };
+
+            let Some(watcher) = watcher else {
+                let exit_code = i32::from(render_until_exit());
+
+                std::process::exit(exit_code)
+            };
 
-            std::process::exit(0)
+            let app_done = AtomicBool::new(false);
+
+            std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    if watcher.wait_for_change(&app_done) {
+                        unsafe { libc::kill(child, libc::SIGTERM) };
+                    }
+                });
+
+                render_until_exit();
+
+                app_done.store(true, Ordering::Relaxed);
+            });
+
+            // Reap the child, so a long watch session doesn't pile up zombies.
+            unsafe { libc::waitpid(child, std::ptr::null_mut(), 0) };
+            signal_hook::low_level::unregister(sigchld_id);
         }
         _ => unreachable!(),
     }
@@ -1189,13 +2221,13 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     args: I,
     binary_bytes: &[u8],
     _expect_metadata: ExpectMetadata,
+    _fail_fast: bool,
+    _watcher: Option<&Watcher>,
 ) -> io::Result<i32> {
     use bumpalo::collections::CollectIn;
 
     unsafe {
         let executable = roc_run_executable_file_path(binary_bytes)?;
-
-        // TODO forward the arguments
         let (argv_cstrings, envp_cstrings) = make_argv_envp(&arena, &executable, args);
 
         let argv: bumpalo::collections::Vec<*const c_char> = argv_cstrings
@@ -1,23 +1,33 @@
 //! The `roc` binary that brings together all functionality in the Roc toolset.
+use clap::parser::ValueSource;
 use roc_build::link::LinkType;
-use roc_build::program::{check_file, CodeGenBackend};
+use roc_build::program::{
+    check_file, handle_loading_problem, CodeGenBackend, DEFAULT_ROC_FILENAME,
+};
 use roc_cli::{
-    build_app, format, test, BuildConfig, FormatMode, CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DOCS,
-    CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VERSION,
-    DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_DEV, FLAG_LIB, FLAG_NO_LINK, FLAG_TARGET, FLAG_TIME,
-    GLUE_DIR, GLUE_SPEC, ROC_FILE,
+    build_app, format, format_options_from_flags, format_stdin, install, publish, test, update,
+    vendor, warning_levels_from_flags, watch, BuildConfig, FormatMode, CMD_BUILD, CMD_CHECK,
+    CMD_DEV, CMD_DOCS, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_INSTALL, CMD_LSP, CMD_PUBLISH,
+    CMD_REPL, CMD_RUN, CMD_TEST, CMD_UPDATE, CMD_VENDOR, CMD_VERSION, DIRECTORY_OR_FILES,
+    FLAG_CHECK, FLAG_DEV, FLAG_DOCS_FORMAT, FLAG_EVAL, FLAG_EXPLAIN_TYPES, FLAG_LANG, FLAG_LIB,
+    FLAG_MAX_HEAP, FLAG_NO_BANNER, FLAG_NO_COLOR, FLAG_NO_LINK, FLAG_OUTPUT, FLAG_RANGE,
+    FLAG_STDIN, FLAG_STDIN_FILENAME, FLAG_TARGET, FLAG_TIME, FLAG_TIMEOUT, FLAG_TRACE, FLAG_VERIFY,
+    FLAG_WATCH, FLAG_WIDTH, GLUE_DIR, GLUE_SPEC, ROC_FILE,
 };
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs, DocsFormat};
 use roc_error_macros::user_error;
 use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
-use roc_load::{FunctionKind, LoadingProblem, Threading};
+use roc_load::{FunctionKind, Threading};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_repl_cli::{EvalLimits, OutputFormat};
 use roc_target::Target;
 use std::fs::{self, FileType};
 use std::io;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use target_lexicon::Triple;
 
 #[macro_use]
@@ -31,8 +41,6 @@ use std::ffi::{OsStr, OsString};
 use roc_cli::build;
 
 fn main() -> io::Result<()> {
-    let _tracing_guards = roc_tracing::setup_tracing!();
-
     let app = build_app();
     let subcommands: Vec<String> = app
         .get_subcommands()
@@ -40,6 +48,39 @@ fn main() -> io::Result<()> {
         .collect();
     let matches = app.get_matches();
 
+    let trace_output = match matches.subcommand() {
+        Some((CMD_BUILD, build_matches)) => build_matches.get_one::<PathBuf>(FLAG_TRACE),
+        _ => None,
+    };
+    let tracing_guards = match trace_output {
+        Some(path) => roc_tracing::setup_chrome_tracing(path).unwrap_or_else(|err| {
+            user_error!("Couldn't create the trace file {}: {err}", path.display())
+        }),
+        None => roc_tracing::setup_tracing!(),
+    };
+
+    // --no-color is global, so it may have been given to a subcommand instead
+    let no_color = matches.get_flag(FLAG_NO_COLOR)
+        || matches.subcommand().map_or(false, |(_, sub_matches)| {
+            sub_matches.get_flag(FLAG_NO_COLOR)
+        });
+
+    if no_color {
+        roc_reporting::report::disable_color();
+    }
+
+    // Only `roc build` and `roc check` have `--output`, and it's about how to print problems
+    if let Some((CMD_BUILD | CMD_CHECK, sub_matches)) = matches.subcommand() {
+        match sub_matches
+            .get_one::<String>(FLAG_OUTPUT)
+            .map(String::as_str)
+        {
+            Some("json") => roc_reporting::cli::enable_json_output(),
+            Some("sarif") => roc_reporting::cli::enable_sarif_output(),
+            _ => {}
+        }
+    }
+
     let exit_code = match matches.subcommand() {
         None => {
             if matches.contains_id(ROC_FILE) {
@@ -81,7 +122,15 @@ fn main() -> io::Result<()> {
             }
         }
         Some((CMD_DEV, matches)) => {
-            if matches.contains_id(ROC_FILE) {
+            if matches.contains_id(ROC_FILE) && matches.get_flag(FLAG_WATCH) {
+                watch(
+                    matches,
+                    &subcommands,
+                    Triple::host(),
+                    RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+                    LinkType::Executable,
+                )
+            } else if matches.contains_id(ROC_FILE) {
                 build(
                     matches,
                     &subcommands,
@@ -97,22 +146,57 @@ fn main() -> io::Result<()> {
             }
         }
         Some((CMD_GLUE, matches)) => {
-            let input_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
-            let output_path = matches.get_one::<PathBuf>(GLUE_DIR).unwrap();
-            let spec_path = matches.get_one::<PathBuf>(GLUE_SPEC).unwrap();
-
-            // have the backend supply `roc_alloc` and friends
-            let backend = match matches.get_flag(FLAG_DEV) {
-                true => CodeGenBackend::Assembly(AssemblyBackendMode::Test),
-                false => CodeGenBackend::Llvm(LlvmBackendMode::BinaryGlue),
+            let lang = matches.get_one::<String>(FLAG_LANG);
+            // --lang takes the place of the spec, so with it, the path given where the spec
+            // would go is actually the output directory, and so on.
+            let mut paths = [GLUE_SPEC, GLUE_DIR, ROC_FILE]
+                .into_iter()
+                .filter(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+                .map(|id| matches.get_one::<PathBuf>(id).unwrap());
+            let spec_path = match lang {
+                Some(_) => None,
+                None => paths.next(),
             };
+            let output_path = paths.next();
+            let input_path = paths
+                .next()
+                .map(PathBuf::as_path)
+                .unwrap_or_else(|| Path::new(DEFAULT_ROC_FILENAME));
 
-            if !output_path.exists() || output_path.is_dir() {
-                roc_glue::generate(input_path, output_path, spec_path, backend)
-            } else {
-                eprintln!("`roc glue` must be given a directory to output into, because the glue might generate multiple files.");
+            match output_path {
+                None => {
+                    eprintln!("`roc glue` must be given a directory to output into, e.g. `roc glue --lang c glue/ platform/main.roc`.");
 
-                Ok(1)
+                    Ok(1)
+                }
+                Some(_) if paths.next().is_some() => {
+                    eprintln!("`roc glue` can be given either a spec or `--lang`, but not both.");
+
+                    Ok(1)
+                }
+                Some(output_path) if output_path.exists() && !output_path.is_dir() => {
+                    eprintln!("`roc glue` must be given a directory to output into, because the glue might generate multiple files.");
+
+                    Ok(1)
+                }
+                Some(output_path) => match (lang, spec_path) {
+                    (Some(lang), _) => {
+                        let mut backend = roc_glue::backend::backend_for_lang(lang)
+                            .expect("clap only accepts languages that have a backend");
+
+                        roc_glue::generate_with_backend(input_path, output_path, backend.as_mut())
+                    }
+                    (None, Some(spec_path)) => {
+                        // have the backend supply `roc_alloc` and friends
+                        let backend = match matches.get_flag(FLAG_DEV) {
+                            true => CodeGenBackend::Assembly(AssemblyBackendMode::Test),
+                            false => CodeGenBackend::Llvm(LlvmBackendMode::BinaryGlue),
+                        };
+
+                        roc_glue::generate(input_path, output_path, spec_path, backend)
+                    }
+                    (None, None) => unreachable!("clap requires a spec unless --lang is given"),
+                },
             }
         }
         Some((CMD_GEN_STUB_LIB, matches)) => {
@@ -134,11 +218,13 @@ fn main() -> io::Result<()> {
                 .get_one::<String>(FLAG_TARGET)
                 .and_then(|s| Target::from_str(s).ok())
                 .unwrap_or_default();
-            let link_type = match (matches.get_flag(FLAG_LIB), matches.get_flag(FLAG_NO_LINK)) {
-                (true, false) => LinkType::Dylib,
-                (true, true) => user_error!("build can only be one of `--lib` or `--no-link`"),
-                (false, true) => LinkType::None,
-                (false, false) => LinkType::Executable,
+            let lib = matches.get_one::<String>(FLAG_LIB).map(String::as_str);
+            let link_type = match (lib, matches.get_flag(FLAG_NO_LINK)) {
+                (Some("static"), false) => LinkType::StaticLib,
+                (Some(_), false) => LinkType::Dylib,
+                (Some(_), true) => user_error!("build can only be one of `--lib` or `--no-link`"),
+                (None, true) => LinkType::None,
+                (None, false) => LinkType::Executable,
             };
 
             Ok(build(
@@ -162,62 +248,94 @@ fn main() -> io::Result<()> {
                 Some(n) => Threading::AtMost(*n),
             };
 
-            match check_file(
-                &arena,
-                roc_file_path.to_owned(),
-                emit_timings,
-                RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
-                threading,
-            ) {
-                Ok((problems, total_time)) => {
-                    println!(
-                        "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
-                        if problems.errors == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.errors,
-                        if problems.errors == 1 {
-                            "error"
-                        } else {
-                            "errors"
-                        },
-                        if problems.warnings == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.warnings,
-                        if problems.warnings == 1 {
-                            "warning"
-                        } else {
-                            "warnings"
-                        },
-                        total_time.as_millis(),
-                    );
-
-                    Ok(problems.exit_code())
+            if !roc_file_path.exists() {
+                eprintln!(
+                    "\nThis file was not found: {}\n\nYou can run `roc help` for more information on how to provide a .roc file.\n",
+                    std::env::current_dir()?.join(roc_file_path).display()
+                );
+
+                Ok(1)
+            } else {
+                match check_file(
+                    &arena,
+                    roc_file_path.to_owned(),
+                    emit_timings,
+                    RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+                    threading,
+                    warning_levels_from_flags(matches),
+                    matches.get_flag(FLAG_EXPLAIN_TYPES),
+                ) {
+                    Ok((problems, total_time)) => {
+                        problems.print_to_stdout(total_time);
+
+                        if !roc_reporting::cli::json_output_enabled() {
+                            println!(".");
+                        }
+
+                        Ok(problems.exit_code())
+                    }
+                    Err(problem) => handle_loading_problem(problem),
                 }
+            }
+        }
+        Some((CMD_REPL, matches)) => {
+            let output_format = match matches.get_one::<String>(FLAG_OUTPUT).map(String::as_str) {
+                Some("json") => OutputFormat::Json,
+                _ => OutputFormat::Text,
+            };
 
-                Err(LoadingProblem::FormattedReport(report)) => {
-                    print!("{report}");
+            let limits = EvalLimits {
+                timeout: matches.get_one::<Duration>(FLAG_TIMEOUT).copied(),
+                max_heap: matches.get_one::<u64>(FLAG_MAX_HEAP).copied(),
+            };
 
-                    Ok(1)
-                }
-                Err(other) => {
-                    panic!("build_file failed with error:\n{other:?}");
-                }
+            let width = matches.get_one::<usize>(FLAG_WIDTH).copied();
+
+            match matches.get_one::<String>(FLAG_EVAL) {
+                Some(src) => Ok(roc_repl_cli::eval_once(src, output_format, limits, width)),
+                None => Ok(roc_repl_cli::main(
+                    !matches.get_flag(FLAG_NO_BANNER),
+                    output_format,
+                    limits,
+                    width,
+                )),
             }
         }
-        Some((CMD_REPL, _)) => Ok(roc_repl_cli::main()),
         Some((CMD_DOCS, matches)) => {
             let root_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+            let format = match matches
+                .get_one::<String>(FLAG_DOCS_FORMAT)
+                .map(String::as_str)
+            {
+                Some("markdown") => DocsFormat::Markdown,
+                Some("json") => DocsFormat::Json,
+                _ => DocsFormat::Html,
+            };
 
-            generate_docs_html(root_path.to_owned());
+            generate_docs(root_path.to_owned(), format);
 
             Ok(0)
         }
+        Some((CMD_FORMAT, matches)) if matches.get_flag(FLAG_STDIN) => {
+            let format_mode = if matches.get_flag(FLAG_VERIFY) {
+                FormatMode::Verify
+            } else if matches.get_flag(FLAG_CHECK) {
+                FormatMode::CheckOnly
+            } else {
+                FormatMode::Format
+            };
+            let filename = matches.get_one::<PathBuf>(FLAG_STDIN_FILENAME);
+            let lines = matches.get_one::<RangeInclusive<u32>>(FLAG_RANGE).cloned();
+            let options = format_options_from_flags(matches);
+
+            match format_stdin(format_mode, filename.map(PathBuf::as_path), lines, options) {
+                Ok(_) => Ok(0),
+                Err(message) => {
+                    eprintln!("{message}");
+                    Ok(1)
+                }
+            }
+        }
         Some((CMD_FORMAT, matches)) => {
             let maybe_values = matches.get_many::<OsString>(DIRECTORY_OR_FILES);
 
@@ -249,12 +367,23 @@ fn main() -> io::Result<()> {
                 roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
             }
 
-            let format_mode = match matches.get_flag(FLAG_CHECK) {
-                true => FormatMode::CheckOnly,
-                false => FormatMode::Format,
+            let format_mode = if matches.get_flag(FLAG_VERIFY) {
+                FormatMode::Verify
+            } else if matches.get_flag(FLAG_CHECK) {
+                FormatMode::CheckOnly
+            } else {
+                FormatMode::Format
             };
 
-            let format_exit_code = match format(roc_files, format_mode) {
+            let lines = matches.get_one::<RangeInclusive<u32>>(FLAG_RANGE).cloned();
+
+            if lines.is_some() && roc_files.len() != 1 {
+                user_error!("--{FLAG_RANGE} can only be used to format a single file.");
+            }
+
+            let options = format_options_from_flags(matches);
+
+            let format_exit_code = match format(roc_files, format_mode, lines, options) {
                 Ok(_) => 0,
                 Err(message) => {
                     eprintln!("{message}");
@@ -264,6 +393,43 @@ fn main() -> io::Result<()> {
 
             Ok(format_exit_code)
         }
+        Some((CMD_LSP, _)) => Ok(roc_language_server::run()?),
+        #[cfg(feature = "jupyter-kernel")]
+        Some((roc_cli::CMD_KERNEL, matches)) => {
+            if matches.get_flag(roc_cli::FLAG_INSTALL) {
+                let dir = roc_kernel::install(&std::env::current_exe()?)?;
+
+                println!("Installed the Roc kernel for Jupyter in {}", dir.display());
+
+                Ok(0)
+            } else {
+                let connection_file = matches
+                    .get_one::<PathBuf>(roc_cli::CONNECTION_FILE)
+                    .unwrap();
+
+                Ok(roc_kernel::run(connection_file)?)
+            }
+        }
+        Some((CMD_INSTALL, matches)) => {
+            let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+            Ok(install(roc_file_path))
+        }
+        Some((CMD_UPDATE, matches)) => {
+            let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+            Ok(update(roc_file_path))
+        }
+        Some((CMD_VENDOR, matches)) => {
+            let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+            Ok(vendor(roc_file_path))
+        }
+        Some((CMD_PUBLISH, matches)) => {
+            let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+            publish(roc_file_path)
+        }
         Some((CMD_VERSION, _)) => {
             print!(
                 "{}",
@@ -275,6 +441,9 @@ fn main() -> io::Result<()> {
         _ => unreachable!(),
     }?;
 
+    // process::exit skips destructors, and the trace is only written out once its guard drops.
+    drop(tracing_guards);
+
     std::process::exit(exit_code);
 }
 
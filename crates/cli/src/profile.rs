@@ -0,0 +1,1098 @@
+//! `roc run --profile` and `roc run --trace-allocs`, which watch the app from the outside
+//! while it runs, using ptrace.
+//!
+//! `--profile` samples the app's call stack: every millisecond it stops the app, reads its
+//! program counter and frame pointer, and follows the chain of frame pointers (which
+//! `--debug` builds keep) up the stack. The samples are written as collapsed stacks: one
+//! line per distinct stack, like `main;Foo.bar;Foo.baz 12`, which flamegraph tools such as
+//! inferno, flamegraph.pl, and speedscope can draw.
+//!
+//! `--trace-allocs` puts breakpoints on the host's `roc_alloc` and `roc_realloc`, and on each
+//! call, attributes the requested bytes to the innermost Roc function on the stack. When the
+//! app exits, it prints how much each Roc function allocated.
+//!
+//! Either way, addresses are mapped to the executable's symbols, and the names of Roc
+//! functions are turned back into `Module.ident`.
+
+/// Whether `--profile` and `--trace-allocs` work on this OS and architecture.
+pub(crate) const SUPPORTED: bool = cfg!(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+));
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+pub(crate) use tracer::{run_with_alloc_tracer, run_with_profiler};
+
+#[cfg(not(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+pub(crate) fn run_with_profiler<I: IntoIterator<Item = S>, S: AsRef<std::ffi::OsStr>>(
+    _arena: &bumpalo::Bump,
+    _args: I,
+    _binary_bytes: &[u8],
+    _profile_path: &std::path::Path,
+) -> std::io::Result<i32> {
+    roc_error_macros::internal_error!("--profile is not supported on this OS")
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+pub(crate) fn run_with_alloc_tracer<I: IntoIterator<Item = S>, S: AsRef<std::ffi::OsStr>>(
+    _arena: &bumpalo::Bump,
+    _args: I,
+    _binary_bytes: &[u8],
+) -> std::io::Result<i32> {
+    roc_error_macros::internal_error!("--trace-allocs is not supported on this OS")
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod tracer {
+    use bumpalo::Bump;
+    use roc_collections::MutMap;
+    use std::ffi::OsStr;
+    use std::fmt::Write;
+    use std::io;
+    use std::path::Path;
+
+    const SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1);
+
+    /// Stacks deeper than this get cut off, which also guards against cycles in a corrupt chain.
+    const MAX_STACK_DEPTH: usize = 512;
+
+    /// Run the app, sampling its call stack until it exits, then write the samples to
+    /// `profile_path`. Returns the app's exit code.
+    pub(crate) fn run_with_profiler<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
+        arena: &Bump,
+        args: I,
+        binary_bytes: &[u8],
+        profile_path: &Path,
+    ) -> io::Result<i32> {
+        let mut tracee = Tracee::spawn(arena, args, binary_bytes)?;
+
+        // Collapsed stack (outermost frame first) -> how many samples had it
+        let mut stacks: MutMap<String, usize> = MutMap::default();
+        let mut samples = 0;
+
+        tracee.resume(tracee.child, 0);
+
+        let exit_code = loop {
+            std::thread::sleep(SAMPLE_INTERVAL);
+
+            if let Some(code) = tracee.stop_all()? {
+                break code;
+            }
+
+            // Each thread's stack counts as a sample of its own.
+            for tid in tracee.threads.keys() {
+                if let Some(regs) = tracee.registers(*tid) {
+                    let mut frames: Vec<&str> = std::iter::once(arch::pc(&regs))
+                        .chain(tracee.return_addresses(arch::frame_pointer(&regs)))
+                        .map(|address| tracee.name_of(address))
+                        .collect();
+
+                    frames.reverse();
+
+                    *stacks.entry(frames.join(";")).or_default() += 1;
+                    samples += 1;
+                }
+            }
+
+            tracee.resume_all();
+
+            // Pass along any signals the app got on its own since the last sample.
+            while let Some((tid, signal)) = tracee.take_deferred_signal() {
+                tracee.resume(tid, signal);
+            }
+        };
+
+        let mut stacks: Vec<_> = stacks.into_iter().collect();
+
+        stacks.sort();
+
+        let collapsed_stacks: String = stacks
+            .into_iter()
+            .map(|(stack, count)| format!("{stack} {count}\n"))
+            .collect();
+
+        std::fs::write(profile_path, collapsed_stacks)?;
+
+        let summary = format!(
+            "\n\x1B[36m{}\x1B[39m\n\nWrote {} sample{} to {}\n\nTo see it as a flamegraph, open it in https://www.speedscope.app or run e.g.\n\n\t\x1B[32minferno-flamegraph < {} > flamegraph.svg\x1B[39m\n",
+            "─".repeat(80),
+            samples,
+            if samples == 1 { "" } else { "s" },
+            profile_path.display(),
+            profile_path.display(),
+        );
+
+        eprintln!(
+            "{}",
+            roc_reporting::report::strip_colors_if_disabled(&summary)
+        );
+
+        Ok(exit_code)
+    }
+
+    #[derive(Clone, Copy)]
+    enum AllocKind {
+        /// `roc_alloc(size, alignment)`
+        Alloc,
+        /// `roc_realloc(ptr, new_size, old_size, alignment)`
+        Realloc,
+    }
+
+    struct Breakpoint {
+        kind: AllocKind,
+        /// The word the breakpoint instruction replaced
+        original_word: u64,
+    }
+
+    #[derive(Default)]
+    struct AllocStats {
+        allocations: usize,
+        reallocations: usize,
+        bytes: u64,
+    }
+
+    /// Run the app, recording every call to `roc_alloc` and `roc_realloc` until it exits,
+    /// then print how much each Roc function allocated. Returns the app's exit code.
+    pub(crate) fn run_with_alloc_tracer<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
+        arena: &Bump,
+        args: I,
+        binary_bytes: &[u8],
+    ) -> io::Result<i32> {
+        let mut tracee = Tracee::spawn(arena, args, binary_bytes)?;
+        let mut breakpoints: MutMap<u64, Breakpoint> = MutMap::default();
+
+        for (name, kind) in [
+            ("roc_alloc", AllocKind::Alloc),
+            ("roc_realloc", AllocKind::Realloc),
+        ] {
+            if let Some(address) = tracee.symbols.address_of(name) {
+                let address = address + tracee.load_bias;
+                let original_word = tracee.insert_breakpoint(address)?;
+
+                breakpoints.insert(
+                    address,
+                    Breakpoint {
+                        kind,
+                        original_word,
+                    },
+                );
+            }
+        }
+
+        if breakpoints.is_empty() {
+            unsafe { libc::kill(tracee.child, libc::SIGKILL) };
+
+            eprintln!("\nI couldn't find the platform's roc_alloc in the executable, so I can't trace allocations. This can happen if the platform was built without symbols.\n");
+
+            return Ok(1);
+        }
+
+        // Index of the innermost Roc function on the stack (if any) -> its allocations
+        let mut stats: MutMap<Option<usize>, AllocStats> = MutMap::default();
+
+        tracee.resume(tracee.child, 0);
+
+        let exit_code = loop {
+            let (tid, signal) = match tracee.next_event()? {
+                Event::Exited(code) => break code,
+                Event::Stopped(tid, signal) => (tid, signal),
+            };
+
+            let Some(mut regs) = tracee.registers(tid) else {
+                tracee.resume(tid, signal);
+
+                continue;
+            };
+
+            let address = arch::pc(&regs).wrapping_sub(arch::BREAKPOINT_PC_OFFSET);
+
+            let Some(breakpoint) = breakpoints
+                .get(&address)
+                .filter(|_| signal == libc::SIGTRAP)
+            else {
+                tracee.resume(tid, signal);
+
+                continue;
+            };
+
+            // We're at the first instruction of roc_alloc or roc_realloc, so the current
+            // frame is still its caller's.
+            let return_address = match arch::link_register(&regs) {
+                Some(link_register) => Some(link_register),
+                None => tracee
+                    .read_words::<1>(arch::stack_pointer(&regs))
+                    .map(|[word]| word),
+            };
+            let function = return_address
+                .map(|return_address| return_address - 1)
+                .into_iter()
+                .chain(tracee.return_addresses(arch::frame_pointer(&regs)))
+                .find_map(|address| tracee.roc_function_at(address));
+
+            let entry = stats.entry(function).or_default();
+
+            match breakpoint.kind {
+                AllocKind::Alloc => {
+                    entry.allocations += 1;
+                    entry.bytes += arch::argument(&regs, 0);
+                }
+                AllocKind::Realloc => {
+                    entry.reallocations += 1;
+                    entry.bytes += arch::argument(&regs, 1);
+                }
+            }
+
+            // Step over the breakpoint with the original instruction, then put it back. The
+            // other threads wait meanwhile, so none of them can run past it while it's lifted.
+            if let Some(code) = tracee.stop_all()? {
+                break code;
+            }
+
+            tracee.write_word(address, breakpoint.original_word)?;
+            arch::set_pc(&mut regs, address);
+            tracee.set_registers(tid, &mut regs)?;
+
+            let signal = match tracee.single_step(tid)? {
+                Step::Done(signal) => signal,
+                Step::Exited(code) => break code,
+            };
+
+            tracee.insert_breakpoint(address)?;
+            tracee.resume(tid, signal);
+            tracee.resume_all();
+        };
+
+        let report = format!(
+            "\n\x1B[36m{}\x1B[39m\n\n{}",
+            "─".repeat(80),
+            alloc_report(&tracee.symbols, stats)
+        );
+
+        eprintln!(
+            "{}",
+            roc_reporting::report::strip_colors_if_disabled(&report)
+        );
+
+        Ok(exit_code)
+    }
+
+    fn alloc_report(symbols: &Symbols, stats: MutMap<Option<usize>, AllocStats>) -> String {
+        const NOT_ROC: &str = "(outside Roc code)";
+        const TOTAL: &str = "Total";
+
+        let mut rows: Vec<(Option<usize>, AllocStats)> = stats.into_iter().collect();
+
+        rows.sort_by(|(_, a), (_, b)| b.bytes.cmp(&a.bytes));
+
+        // Specializations of the same function demangle to the same name, so tell them apart.
+        let mut specializations: MutMap<&str, usize> = MutMap::default();
+
+        for (function, _) in rows.iter() {
+            if let Some(index) = function {
+                *specializations.entry(symbols.name(*index)).or_default() += 1;
+            }
+        }
+
+        let mut seen: MutMap<&str, usize> = MutMap::default();
+        let names: Vec<String> = rows
+            .iter()
+            .map(|(function, _)| match function {
+                Some(index) => {
+                    let name = symbols.name(*index);
+
+                    if specializations[name] > 1 {
+                        let nth = seen.entry(name).or_default();
+
+                        *nth += 1;
+
+                        format!("{name} (specialization {nth})")
+                    } else {
+                        name.to_string()
+                    }
+                }
+                None => NOT_ROC.to_string(),
+            })
+            .collect();
+
+        let name_width = names
+            .iter()
+            .map(|name| name.chars().count())
+            .chain([TOTAL.len(), "Function".len()])
+            .max()
+            .unwrap_or_default();
+
+        let mut buf = String::new();
+        let mut total = AllocStats::default();
+
+        writeln!(
+            buf,
+            "Allocations by the innermost Roc function that made them:\n\n    {:name_width$}  {:>11}  {:>13}  {:>15}",
+            "Function", "Allocations", "Reallocations", "Bytes requested"
+        )
+        .unwrap();
+
+        let write_row = |buf: &mut String, name: &str, stats: &AllocStats| {
+            writeln!(
+                buf,
+                "    {name:name_width$}  {:>11}  {:>13}  {:>15}",
+                stats.allocations, stats.reallocations, stats.bytes
+            )
+            .unwrap()
+        };
+
+        for ((_, stats), name) in rows.iter().zip(names.iter()) {
+            write_row(&mut buf, name, stats);
+
+            total.allocations += stats.allocations;
+            total.reallocations += stats.reallocations;
+            total.bytes += stats.bytes;
+        }
+
+        buf.push('\n');
+        write_row(&mut buf, TOTAL, &total);
+
+        write!(
+            buf,
+            "\nA function that allocates a lot while updating a \x1B[33mList\x1B[39m may be copying it instead of updating it in place, e.g. because the \x1B[33mList\x1B[39m is still used afterwards."
+        )
+        .unwrap();
+
+        buf
+    }
+
+    enum Event {
+        /// The app exited with this code, or was killed (in which case this is 128 + the signal)
+        Exited(i32),
+        /// This thread stopped because of this signal, and stays stopped until it's resumed
+        Stopped(libc::pid_t, i32),
+    }
+
+    enum Step {
+        /// The thread ran the instruction. If this isn't 0, a signal arrived meanwhile, which
+        /// should be passed on when resuming the thread.
+        Done(i32),
+        /// The app exited with this code
+        Exited(i32),
+    }
+
+    /// One of the app's threads, which all get traced
+    struct Thread {
+        /// Whether it's in a ptrace stop, in which case it can be inspected and resumed
+        stopped: bool,
+        /// Whether a SIGSTOP is on its way that shouldn't be passed on to the app, because we
+        /// sent it (or because it's the one every new thread starts with)
+        stop_requested: bool,
+        /// A signal it stopped for while `stop_all` was stopping everything, which `next_event`
+        /// reports next
+        deferred_signal: Option<i32>,
+    }
+
+    impl Thread {
+        fn new() -> Self {
+            Self {
+                stopped: false,
+                stop_requested: true,
+                deferred_signal: None,
+            }
+        }
+    }
+
+    /// The app, running under ptrace
+    struct Tracee {
+        /// The app's process ID, which is also the thread ID of its main thread
+        child: libc::pid_t,
+        threads: MutMap<libc::pid_t, Thread>,
+        /// Whether threads that stop for our own reasons stay stopped, rather than being resumed
+        stopping_all: bool,
+        symbols: Symbols,
+        /// Where the executable got loaded, relative to the addresses in its symbol table
+        load_bias: u64,
+        /// What SIGINT did before we started ignoring it
+        previous_sigint: libc::sighandler_t,
+    }
+
+    impl Tracee {
+        /// Start the app, stopped right after its `exec`.
+        fn spawn<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
+            arena: &Bump,
+            args: I,
+            binary_bytes: &[u8],
+        ) -> io::Result<Self> {
+            use crate::{make_argv_envp, roc_run_executable_file_path};
+            use bumpalo::collections::CollectIn;
+            use std::os::raw::c_char;
+
+            let symbols = Symbols::from_binary(binary_bytes)?;
+            let executable = roc_run_executable_file_path(binary_bytes)?;
+            let (argv_cstrings, envp_cstrings) = make_argv_envp(arena, &executable, args);
+
+            let argv: bumpalo::collections::Vec<*const c_char> = argv_cstrings
+                .iter()
+                .map(|s| s.as_ptr())
+                .chain([std::ptr::null()])
+                .collect_in(arena);
+
+            let envp: bumpalo::collections::Vec<*const c_char> = envp_cstrings
+                .iter()
+                .map(|s| s.as_ptr())
+                .chain([std::ptr::null()])
+                .collect_in(arena);
+
+            let child = match unsafe { libc::fork() } {
+                0 => unsafe {
+                    // we are the child; let the parent trace us, then become the app
+                    libc::ptrace(
+                        libc::PTRACE_TRACEME,
+                        0,
+                        std::ptr::null_mut::<libc::c_void>(),
+                        std::ptr::null_mut::<libc::c_void>(),
+                    );
+
+                    executable.execve(&argv, &envp);
+
+                    // Display a human-friendly error message
+                    println!("Error {:?}", std::io::Error::last_os_error());
+
+                    std::process::exit(1);
+                },
+                -1 => return Err(io::Error::last_os_error()),
+                child => child,
+            };
+
+            // Ctrl-C reaches the app as well as us. Leave it to the app, and keep going so that
+            // we can still report on it once it exits.
+            let previous_sigint = unsafe { libc::signal(libc::SIGINT, libc::SIG_IGN) };
+
+            let mut tracee = Self {
+                child,
+                threads: MutMap::default(),
+                stopping_all: false,
+                symbols,
+                load_bias: 0,
+                previous_sigint,
+            };
+
+            // The child stops with a SIGTRAP once it has exec'd the app.
+            let (_, status) = tracee.wait_for(child)?;
+
+            if let Some(code) = exit_code(status) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("the app exited with code {code} before it started"),
+                ));
+            }
+
+            tracee.threads.insert(
+                child,
+                Thread {
+                    stopped: true,
+                    stop_requested: false,
+                    deferred_signal: None,
+                },
+            );
+
+            // Trace the threads the app starts too, and if we get killed, don't leave the app
+            // behind, stopped.
+            let options = libc::PTRACE_O_TRACECLONE | libc::PTRACE_O_EXITKILL;
+
+            if tracee.ptrace(libc::PTRACE_SETOPTIONS, child, 0, options as u64) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+
+            tracee.load_bias = tracee.find_load_bias()?;
+
+            Ok(tracee)
+        }
+
+        fn wait_for(&self, pid: libc::pid_t) -> io::Result<(libc::pid_t, libc::c_int)> {
+            let mut status = 0;
+            let tid = unsafe { libc::waitpid(pid, &mut status, libc::__WALL) };
+
+            if tid == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok((tid, status))
+            }
+        }
+
+        /// Wait for a thread to stop for a reason the caller needs to deal with. New threads,
+        /// threads exiting, and our own SIGSTOPs are taken care of along the way.
+        fn next_event(&mut self) -> io::Result<Event> {
+            loop {
+                if let Some((tid, signal)) = self.take_deferred_signal() {
+                    return Ok(Event::Stopped(tid, signal));
+                }
+
+                let (tid, status) = self.wait_for(-1)?;
+
+                if let Some(event) = self.handle(tid, status) {
+                    return Ok(event);
+                }
+            }
+        }
+
+        /// Deal with a status from `waitpid`, returning it as an event if it's not ours to
+        /// deal with.
+        fn handle(&mut self, tid: libc::pid_t, status: libc::c_int) -> Option<Event> {
+            if let Some(code) = exit_code(status) {
+                self.threads.remove(&tid);
+
+                // The main thread is always the last one to exit.
+                return (tid == self.child).then_some(Event::Exited(code));
+            }
+
+            let signal = libc::WSTOPSIG(status);
+            let started_thread = status >> 16 == libc::PTRACE_EVENT_CLONE;
+
+            if started_thread {
+                let mut new_tid: libc::c_ulong = 0;
+
+                self.ptrace(
+                    libc::PTRACE_GETEVENTMSG,
+                    tid,
+                    0,
+                    &mut new_tid as *mut libc::c_ulong as u64,
+                );
+                self.threads
+                    .entry(new_tid as libc::pid_t)
+                    .or_insert_with(Thread::new);
+            }
+
+            // The new thread's first stop can come before the event that says it was started.
+            let thread = self.threads.entry(tid).or_insert_with(Thread::new);
+
+            thread.stopped = true;
+
+            let ours = if started_thread {
+                true
+            } else if signal == libc::SIGSTOP && thread.stop_requested {
+                thread.stop_requested = false;
+
+                true
+            } else {
+                false
+            };
+
+            if !ours {
+                if self.stopping_all {
+                    thread.deferred_signal = Some(signal);
+
+                    return None;
+                } else {
+                    return Some(Event::Stopped(tid, signal));
+                }
+            }
+
+            if !self.stopping_all {
+                self.resume(tid, 0);
+            }
+
+            None
+        }
+
+        fn take_deferred_signal(&mut self) -> Option<(libc::pid_t, i32)> {
+            self.threads
+                .iter_mut()
+                .find_map(|(tid, thread)| Some((*tid, thread.deferred_signal.take()?)))
+        }
+
+        /// Stop every thread, e.g. to sample their stacks. Returns the app's exit code if it
+        /// exited instead.
+        fn stop_all(&mut self) -> io::Result<Option<i32>> {
+            self.stopping_all = true;
+
+            for (tid, thread) in self.threads.iter_mut() {
+                if !thread.stopped && !thread.stop_requested {
+                    unsafe { libc::syscall(libc::SYS_tgkill, self.child, *tid, libc::SIGSTOP) };
+
+                    thread.stop_requested = true;
+                }
+            }
+
+            while self.threads.values().any(|thread| !thread.stopped) {
+                let (tid, status) = self.wait_for(-1)?;
+
+                if let Some(Event::Exited(code)) = self.handle(tid, status) {
+                    return Ok(Some(code));
+                }
+            }
+
+            Ok(None)
+        }
+
+        /// Resume the threads `stop_all` stopped, except for those that stopped for a reason of
+        /// their own in the meantime, which `next_event` reports next.
+        fn resume_all(&mut self) {
+            self.stopping_all = false;
+
+            let stopped: Vec<libc::pid_t> = self
+                .threads
+                .iter()
+                .filter(|(_, thread)| thread.stopped && thread.deferred_signal.is_none())
+                .map(|(tid, _)| *tid)
+                .collect();
+
+            for tid in stopped {
+                self.resume(tid, 0);
+            }
+        }
+
+        fn resume(&mut self, tid: libc::pid_t, signal: libc::c_int) {
+            self.ptrace(libc::PTRACE_CONT, tid, 0, signal as u64);
+
+            if let Some(thread) = self.threads.get_mut(&tid) {
+                thread.stopped = false;
+            }
+        }
+
+        /// Run a stopped thread for one instruction.
+        fn single_step(&mut self, tid: libc::pid_t) -> io::Result<Step> {
+            let mut signal = 0;
+
+            loop {
+                self.ptrace(libc::PTRACE_SINGLESTEP, tid, 0, 0);
+
+                let (_, status) = self.wait_for(tid)?;
+
+                if let Some(code) = exit_code(status) {
+                    self.threads.remove(&tid);
+
+                    return Ok(if tid == self.child {
+                        Step::Exited(code)
+                    } else {
+                        Step::Done(0)
+                    });
+                }
+
+                // Otherwise a signal arrived before the instruction ran, so try again.
+                match libc::WSTOPSIG(status) {
+                    libc::SIGTRAP => return Ok(Step::Done(signal)),
+                    libc::SIGSTOP if self.threads[&tid].stop_requested => {
+                        self.threads.get_mut(&tid).unwrap().stop_requested = false;
+                    }
+                    other => signal = other,
+                }
+            }
+        }
+
+        fn ptrace(
+            &self,
+            request: libc::c_uint,
+            tid: libc::pid_t,
+            addr: u64,
+            data: u64,
+        ) -> libc::c_long {
+            unsafe {
+                libc::ptrace(
+                    request,
+                    tid,
+                    addr as *mut libc::c_void,
+                    data as *mut libc::c_void,
+                )
+            }
+        }
+
+        /// A thread that's stopped, for ptrace requests that are about the whole app
+        fn any_stopped_thread(&self) -> libc::pid_t {
+            self.threads
+                .iter()
+                .find(|(_, thread)| thread.stopped)
+                .map_or(self.child, |(tid, _)| *tid)
+        }
+
+        fn find_load_bias(&self) -> io::Result<u64> {
+            if !self.symbols.position_independent {
+                return Ok(0);
+            }
+
+            // The app runs from a memfd, so its mappings are named after the memfd.
+            let maps = std::fs::read_to_string(format!("/proc/{}/maps", self.child))?;
+            let start = maps.lines().find_map(|line| {
+                let mut fields = line.split_whitespace();
+                let range = fields.next()?;
+                let offset = fields.nth(1)?;
+                let pathname = fields.nth(2)?;
+
+                if pathname.contains("roc_file_descriptor")
+                    && u64::from_str_radix(offset, 16) == Ok(0)
+                {
+                    u64::from_str_radix(range.split_once('-')?.0, 16).ok()
+                } else {
+                    None
+                }
+            });
+
+            Ok(start.map_or(0, |start| start - self.symbols.lowest_address))
+        }
+
+        fn registers(&self, tid: libc::pid_t) -> Option<libc::user_regs_struct> {
+            let mut regs: libc::user_regs_struct = unsafe { std::mem::zeroed() };
+            let mut iovec = Self::regs_iovec(&mut regs);
+            let result = self.ptrace(
+                libc::PTRACE_GETREGSET,
+                tid,
+                libc::NT_PRSTATUS as u64,
+                &mut iovec as *mut libc::iovec as u64,
+            );
+
+            (result != -1).then_some(regs)
+        }
+
+        fn set_registers(
+            &self,
+            tid: libc::pid_t,
+            regs: &mut libc::user_regs_struct,
+        ) -> io::Result<()> {
+            let mut iovec = Self::regs_iovec(regs);
+            let result = self.ptrace(
+                libc::PTRACE_SETREGSET,
+                tid,
+                libc::NT_PRSTATUS as u64,
+                &mut iovec as *mut libc::iovec as u64,
+            );
+
+            if result == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+
+        fn regs_iovec(regs: &mut libc::user_regs_struct) -> libc::iovec {
+            libc::iovec {
+                iov_base: (regs as *mut libc::user_regs_struct).cast(),
+                iov_len: std::mem::size_of::<libc::user_regs_struct>(),
+            }
+        }
+
+        fn read_words<const N: usize>(&self, address: u64) -> Option<[u64; N]> {
+            let mut words = [0u64; N];
+            let local = libc::iovec {
+                iov_base: words.as_mut_ptr().cast(),
+                iov_len: std::mem::size_of_val(&words),
+            };
+            let remote = libc::iovec {
+                iov_base: address as *mut libc::c_void,
+                iov_len: std::mem::size_of_val(&words),
+            };
+
+            let read = unsafe { libc::process_vm_readv(self.child, &local, 1, &remote, 1, 0) };
+
+            (read == std::mem::size_of_val(&words) as isize).then_some(words)
+        }
+
+        /// Unlike reading, this works on the read-only pages that code lives in.
+        fn write_word(&self, address: u64, word: u64) -> io::Result<()> {
+            let tid = self.any_stopped_thread();
+
+            if self.ptrace(libc::PTRACE_POKEDATA, tid, address, word) == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Returns the word that the breakpoint instruction replaced.
+        fn insert_breakpoint(&self, address: u64) -> io::Result<u64> {
+            let Some([original_word]) = self.read_words::<1>(address) else {
+                return Err(io::Error::last_os_error());
+            };
+
+            self.write_word(
+                address,
+                (original_word & !arch::BREAKPOINT_MASK) | arch::BREAKPOINT,
+            )?;
+
+            Ok(original_word)
+        }
+
+        /// Follow the chain of frame pointers starting at `frame_pointer`, and return the
+        /// call site that each frame will return to, innermost first.
+        fn return_addresses(&self, mut frame_pointer: u64) -> Vec<u64> {
+            let mut addresses = Vec::new();
+
+            while addresses.len() < MAX_STACK_DEPTH && frame_pointer != 0 && frame_pointer % 8 == 0
+            {
+                // Each frame starts with the caller's frame pointer, then the return address.
+                let Some([next_frame_pointer, return_address]) = self.read_words::<2>(frame_pointer)
+                else {
+                    break;
+                };
+
+                if return_address == 0 {
+                    break;
+                }
+
+                // The return address points just past the call, which may be in the next function.
+                addresses.push(return_address - 1);
+
+                // Frames get older as the stack grows upwards, so anything else means we're lost.
+                if next_frame_pointer <= frame_pointer {
+                    break;
+                }
+
+                frame_pointer = next_frame_pointer;
+            }
+
+            addresses
+        }
+
+        /// The name of the function containing `address`. Addresses outside the executable,
+        /// e.g. in libc, are `[unknown]`, like in perf's output.
+        fn name_of(&self, address: u64) -> &str {
+            match self
+                .symbols
+                .function_at(address.wrapping_sub(self.load_bias))
+            {
+                Some(index) => self.symbols.name(index),
+                None => "[unknown]",
+            }
+        }
+
+        fn roc_function_at(&self, address: u64) -> Option<usize> {
+            self.symbols
+                .function_at(address.wrapping_sub(self.load_bias))
+                .filter(|index| self.symbols.functions[*index].is_roc)
+        }
+    }
+
+    impl Drop for Tracee {
+        fn drop(&mut self) {
+            unsafe { libc::signal(libc::SIGINT, self.previous_sigint) };
+        }
+    }
+
+    /// The exit code from a `waitpid` status, if the thread exited (128 + the signal, if it
+    /// was killed)
+    fn exit_code(status: libc::c_int) -> Option<i32> {
+        if libc::WIFEXITED(status) {
+            Some(libc::WEXITSTATUS(status))
+        } else if libc::WIFSIGNALED(status) {
+            Some(128 + libc::WTERMSIG(status))
+        } else {
+            None
+        }
+    }
+
+    struct Function {
+        start: u64,
+        size: u64,
+        symbol_name: String,
+        /// `Module.ident` for Roc functions, and the symbol name for everything else
+        name: String,
+        is_roc: bool,
+    }
+
+    /// The executable's function symbols, sorted by address
+    struct Symbols {
+        functions: Vec<Function>,
+        position_independent: bool,
+        lowest_address: u64,
+    }
+
+    impl Symbols {
+        fn from_binary(binary_bytes: &[u8]) -> io::Result<Self> {
+            use object::{Object, ObjectKind, ObjectSegment, ObjectSymbol, SymbolKind};
+
+            let file = object::File::parse(binary_bytes)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+            let mut functions: Vec<Function> = file
+                .symbols()
+                .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.address() != 0)
+                .filter_map(|symbol| {
+                    let symbol_name = symbol.name().ok()?;
+                    let roc_name = roc_gen_llvm::llvm::build::demangle_func_spec_name(symbol_name);
+
+                    Some(Function {
+                        start: symbol.address(),
+                        size: symbol.size(),
+                        symbol_name: symbol_name.to_string(),
+                        is_roc: roc_name.is_some(),
+                        name: roc_name.unwrap_or_else(|| symbol_name.to_string()),
+                    })
+                })
+                .collect();
+
+            functions.sort_by_key(|function| function.start);
+
+            Ok(Self {
+                functions,
+                position_independent: file.kind() == ObjectKind::Dynamic,
+                lowest_address: file
+                    .segments()
+                    .map(|segment| segment.address())
+                    .min()
+                    .unwrap_or_default(),
+            })
+        }
+
+        /// The index of the function containing `address`, if it's in the executable
+        fn function_at(&self, address: u64) -> Option<usize> {
+            let index = self
+                .functions
+                .partition_point(|function| function.start <= address)
+                .checked_sub(1)?;
+            let function = &self.functions[index];
+
+            (function.size == 0 || address < function.start + function.size).then_some(index)
+        }
+
+        fn name(&self, index: usize) -> &str {
+            &self.functions[index].name
+        }
+
+        fn address_of(&self, symbol_name: &str) -> Option<u64> {
+            self.functions
+                .iter()
+                .find(|function| function.symbol_name == symbol_name)
+                .map(|function| function.start)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use indoc::indoc;
+
+        fn roc_function(start: u64, name: &str) -> Function {
+            Function {
+                start,
+                size: 0x10,
+                symbol_name: format!("#{name}"),
+                name: name.to_string(),
+                is_roc: true,
+            }
+        }
+
+        fn stats(allocations: usize, reallocations: usize, bytes: u64) -> AllocStats {
+            AllocStats {
+                allocations,
+                reallocations,
+                bytes,
+            }
+        }
+
+        #[test]
+        fn alloc_report_tells_specializations_apart() {
+            let symbols = Symbols {
+                functions: vec![
+                    roc_function(0x100, "Foo.bar"),
+                    roc_function(0x200, "Foo.bar"),
+                    roc_function(0x300, "Foo.baz"),
+                ],
+                position_independent: false,
+                lowest_address: 0,
+            };
+            let mut by_function = MutMap::default();
+
+            by_function.insert(Some(2), stats(1, 0, 100));
+            by_function.insert(None, stats(1, 0, 50));
+            by_function.insert(Some(0), stats(2, 0, 300));
+            by_function.insert(Some(1), stats(1, 1, 200));
+
+            let report = alloc_report(&symbols, by_function);
+
+            assert_eq!(
+                roc_reporting::report::strip_colors(&report),
+                indoc!(
+                    r#"
+                Allocations by the innermost Roc function that made them:
+
+                    Function                    Allocations  Reallocations  Bytes requested
+                    Foo.bar (specialization 1)            2              0              300
+                    Foo.bar (specialization 2)            1              1              200
+                    Foo.baz                               1              0              100
+                    (outside Roc code)                    1              0               50
+
+                    Total                                 5              1              650
+
+                A function that allocates a lot while updating a List may be copying it instead of updating it in place, e.g. because the List is still used afterwards.
+                "#
+                )
+                .trim_end()
+            );
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    mod arch {
+        /// `int3`
+        pub const BREAKPOINT: u64 = 0xcc;
+        pub const BREAKPOINT_MASK: u64 = 0xff;
+        /// Once a breakpoint is hit, the program counter is just past it.
+        pub const BREAKPOINT_PC_OFFSET: u64 = 1;
+
+        pub fn pc(regs: &libc::user_regs_struct) -> u64 {
+            regs.rip
+        }
+
+        pub fn set_pc(regs: &mut libc::user_regs_struct, pc: u64) {
+            regs.rip = pc;
+        }
+
+        pub fn frame_pointer(regs: &libc::user_regs_struct) -> u64 {
+            regs.rbp
+        }
+
+        pub fn stack_pointer(regs: &libc::user_regs_struct) -> u64 {
+            regs.rsp
+        }
+
+        /// x86_64 keeps return addresses on the stack instead.
+        pub fn link_register(_regs: &libc::user_regs_struct) -> Option<u64> {
+            None
+        }
+
+        /// The `index`th integer argument of the function that was just called
+        pub fn argument(regs: &libc::user_regs_struct, index: usize) -> u64 {
+            [regs.rdi, regs.rsi][index]
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    mod arch {
+        /// `brk #0`
+        pub const BREAKPOINT: u64 = 0xd420_0000;
+        pub const BREAKPOINT_MASK: u64 = 0xffff_ffff;
+        /// Once a breakpoint is hit, the program counter still points at it.
+        pub const BREAKPOINT_PC_OFFSET: u64 = 0;
+
+        pub fn pc(regs: &libc::user_regs_struct) -> u64 {
+            regs.pc
+        }
+
+        pub fn set_pc(regs: &mut libc::user_regs_struct, pc: u64) {
+            regs.pc = pc;
+        }
+
+        pub fn frame_pointer(regs: &libc::user_regs_struct) -> u64 {
+            regs.regs[29]
+        }
+
+        pub fn stack_pointer(regs: &libc::user_regs_struct) -> u64 {
+            regs.sp
+        }
+
+        pub fn link_register(regs: &libc::user_regs_struct) -> Option<u64> {
+            Some(regs.regs[30])
+        }
+
+        /// The `index`th integer argument of the function that was just called
+        pub fn argument(regs: &libc::user_regs_struct, index: usize) -> u64 {
+            regs.regs[index]
+        }
+    }
+}
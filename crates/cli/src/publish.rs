@@ -0,0 +1,123 @@
+//! `roc publish`, which checks a package and bundles it for distribution over HTTPS.
+use bumpalo::Bump;
+use roc_build::program::{check_file, handle_loading_problem};
+use roc_load::Threading;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_packaging::tarball::{self, Compression};
+use roc_parse::ast::{Header, Module};
+use roc_parse::module::parse_header;
+use roc_parse::state::State;
+use roc_reporting::report::strip_colors_if_disabled;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Check that the package whose main module is `roc_file` is ready to publish, then bundle it
+/// into a `.tar.br` archive named after its hash. Returns the exit code.
+///
+/// A package is ready when all of its modules typecheck, its docs build, and none of its
+/// exposed definitions mention types that its users couldn't refer to.
+pub fn publish(roc_file: &Path) -> io::Result<i32> {
+    if !roc_file.exists() {
+        eprintln!(
+            "\nThis file was not found: {}\n\nYou can run `roc help` for more information on how to provide a .roc file.\n",
+            roc_file.display()
+        );
+
+        return Ok(1);
+    }
+
+    if !is_package(roc_file)? {
+        eprintln!(
+            "\n{} is not a package's main module, so there's nothing to publish.\n\nPackages start with a `package` header. (To bundle a platform, use `roc build --bundle .tar.br` instead.)\n",
+            roc_file.display()
+        );
+
+        return Ok(1);
+    }
+
+    println!("Checking {}…\n", roc_file.display());
+
+    let arena = Bump::new();
+
+    match check_file(
+        &arena,
+        roc_file.to_path_buf(),
+        false,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        Threading::AllAvailable,
+        Default::default(),
+        false,
+    ) {
+        Ok((problems, _)) if problems.errors > 0 => {
+            eprintln!(
+                "\nThe package has {} error{}. Fix {} before publishing!\n",
+                problems.errors,
+                if problems.errors == 1 { "" } else { "s" },
+                if problems.errors == 1 { "it" } else { "them" },
+            );
+
+            return Ok(1);
+        }
+        Ok(_) => {}
+        Err(problem) => return handle_loading_problem(problem),
+    }
+
+    let loaded_module = roc_docs::load_module_for_docs(roc_file.to_path_buf());
+    let unexposed_types = roc_docs::unexposed_types_in_signatures(&loaded_module);
+
+    if !unexposed_types.is_empty() {
+        let mut buf = String::from("\nThese exposed definitions mention types that the package doesn't expose, so its users couldn't write them down:\n\n");
+
+        for (def_name, type_name) in unexposed_types {
+            buf.push_str(&format!(
+                "    \x1B[33m{def_name}\x1B[39m mentions \x1B[33m{type_name}\x1B[39m\n"
+            ));
+        }
+
+        buf.push_str("\nExpose those types, or stop mentioning them in exposed definitions, before publishing!\n");
+
+        eprintln!("{}", strip_colors_if_disabled(&buf));
+
+        return Ok(1);
+    }
+
+    // Make sure the docs build, without touching any docs the author generated themselves.
+    {
+        let docs_dir = tempfile::tempdir()?;
+
+        roc_docs::write_docs_html(&loaded_module, docs_dir.path());
+    }
+
+    println!("\nCompressing with Brotli at maximum quality level…\n");
+
+    let filename = tarball::build(roc_file, Compression::Brotli)?;
+    let created_path = roc_file.with_file_name(&filename);
+
+    let published = format!(
+        "\nPublished \x1B[33m{}\x1B[39m into the following archive, along with a {}:\n\n\t\x1B[33m{}\x1B[39m\n\nUpload it to some URL, then people can depend on it with:\n\n\t\x1B[32m\"https://your-url-goes-here/{filename}\"\x1B[39m\n\nThe file name is the archive's hash, which roc checks whenever it downloads the package.\n",
+        roc_file.display(),
+        tarball::MANIFEST_FILENAME,
+        created_path.display(),
+    );
+
+    println!("{}", strip_colors_if_disabled(&published));
+
+    Ok(0)
+}
+
+fn is_package(roc_file: &Path) -> io::Result<bool> {
+    let src = fs::read(roc_file)?;
+    let arena = Bump::new();
+
+    Ok(matches!(
+        parse_header(&arena, State::new(&src)),
+        Ok((
+            Module {
+                header: Header::Package(_),
+                ..
+            },
+            _
+        ))
+    ))
+}
@@ -0,0 +1,145 @@
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use clap::ArgMatches;
+use roc_build::link::LinkType;
+use roc_packaging::cache::RocCacheDir;
+use roc_reporting::report::{strip_colors, strip_colors_if_disabled};
+use target_lexicon::Triple;
+
+use crate::{build_with_watcher, BuildConfig, ROC_FILE};
+
+/// How often to check whether a watched file was modified.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Keeps track of the files that went into a build, so it can be redone when
+/// one of them changes.
+pub(crate) struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    fn new(root: PathBuf) -> Self {
+        let mut watcher = Self { files: Vec::new() };
+
+        watcher.watch(std::iter::once(root));
+
+        watcher
+    }
+
+    /// Start watching these files (instead of the previous ones), remembering
+    /// when each was last modified.
+    pub(crate) fn watch(&mut self, files: impl IntoIterator<Item = PathBuf>) {
+        self.files = files
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+
+                (path, modified)
+            })
+            .collect();
+    }
+
+    fn changed(&self) -> bool {
+        self.files
+            .iter()
+            .any(|(path, last_modified)| modified(path) != *last_modified)
+    }
+
+    fn snapshot(&self) -> Vec<Option<SystemTime>> {
+        self.files.iter().map(|(path, _)| modified(path)).collect()
+    }
+
+    /// Block until one of the watched files changes, or until `stop` is set.
+    /// Returns whether a file changed.
+    pub(crate) fn wait_for_change(&self, stop: &AtomicBool) -> bool {
+        while !self.changed() {
+            if stop.load(Ordering::Relaxed) {
+                return false;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        // Editors often save a file in several steps (or save several files at once),
+        // so wait for the changes to settle down before reporting them.
+        let mut snapshot = self.snapshot();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let next = self.snapshot();
+
+            if next == snapshot {
+                return true;
+            }
+
+            snapshot = next;
+        }
+    }
+}
+
+/// A file that can't be read (e.g. because it's in the middle of being saved)
+/// counts as having no modification time.
+fn modified(path: &Path) -> Option<SystemTime> {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// `roc dev --watch`: build and run the app, then do it all again whenever one of
+/// the files that went into the build changes. This only returns if there's an
+/// I/O error; otherwise it keeps going until it's interrupted.
+pub fn watch(
+    matches: &ArgMatches,
+    subcommands: &[String],
+    triple: Triple,
+    roc_cache_dir: RocCacheDir<'_>,
+    link_type: LinkType,
+) -> io::Result<i32> {
+    let path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+    let mut watcher = Watcher::new(path.to_path_buf());
+
+    // When the output goes to a file or another program, keep it free of escape codes.
+    let interactive = io::stdout().is_terminal();
+
+    loop {
+        // Clear the terminal, so each run starts with a clean slate.
+        if interactive {
+            print!("\x1B[2J\x1B[H");
+            io::stdout().flush()?;
+        }
+
+        build_with_watcher(
+            matches,
+            subcommands,
+            BuildConfig::BuildAndRunIfNoErrors,
+            triple.clone(),
+            roc_cache_dir,
+            link_type,
+            Some(&mut watcher),
+        )?;
+
+        // If the app was stopped because a file changed, rebuild right away.
+        // Otherwise, it finished (or didn't build) on its own, so wait for a change.
+        if !watcher.changed() {
+            let banner = format!(
+                "\n\x1B[36m{}\x1B[39m\nWaiting for changes to {} file{}…",
+                "─".repeat(80),
+                watcher.files.len(),
+                if watcher.files.len() == 1 { "" } else { "s" }
+            );
+
+            if interactive {
+                println!("{}", strip_colors_if_disabled(&banner));
+            } else {
+                println!("{}", strip_colors(&banner));
+            }
+
+            watcher.wait_for_change(&AtomicBool::new(false));
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! `roc build` on a directory, which builds every app and package underneath it.
+use crate::{
+    build_module, install, roc_files_in_dir, warning_levels_from_flags, BuildConfig,
+    FLAG_MAX_THREADS, FLAG_TIME,
+};
+use bumpalo::Bump;
+use clap::ArgMatches;
+use roc_build::link::LinkType;
+use roc_build::program::{check_file, handle_loading_problem};
+use roc_error_macros::user_error;
+use roc_load::Threading;
+use roc_packaging::cache::{RocCacheDir, VENDOR_DIR_NAME};
+use roc_parse::ast::{Header, Module};
+use roc_parse::module::parse_header;
+use roc_parse::state::State;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use target_lexicon::Triple;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RootKind {
+    App,
+    Package,
+}
+
+struct Outcome {
+    path: PathBuf,
+    kind: RootKind,
+    exit_code: i32,
+    time: Duration,
+}
+
+/// Build every app under `dir`, and check every package (which has nothing to link),
+/// one after another. They all share the same package cache and thread limit, and
+/// a failure doesn't stop the rest. Prints a summary table at the end, and returns
+/// a nonzero exit code if anything failed.
+pub(crate) fn build_workspace(
+    matches: &ArgMatches,
+    dir: &Path,
+    triple: Triple,
+    roc_cache_dir: RocCacheDir<'_>,
+    link_type: LinkType,
+) -> io::Result<i32> {
+    let roots = find_roots(dir)?;
+
+    if roots.is_empty() {
+        eprintln!(
+            "\nThis directory does not contain any apps or packages to build: {}\n\nYou can run `roc help` for more information on how to provide a .roc file.\n",
+            dir.display()
+        );
+
+        return Ok(1);
+    }
+
+    let json_output = roc_reporting::cli::json_output_enabled();
+    let mut outcomes = Vec::with_capacity(roots.len());
+
+    for (path, kind) in roots {
+        if !json_output {
+            println!(
+                "\n\x1B[36m{} {}\x1B[39m\n",
+                match kind {
+                    RootKind::App => "Building",
+                    RootKind::Package => "Checking",
+                },
+                path.display()
+            );
+        }
+
+        let start_time = Instant::now();
+        let exit_code = match kind {
+            RootKind::App if !install::check_lockfile(&path, roc_cache_dir) => 1,
+            RootKind::App => build_module(
+                &Bump::new(),
+                matches,
+                BuildConfig::BuildOnly,
+                triple.clone(),
+                roc_cache_dir,
+                link_type,
+                None,
+                &path,
+            )?,
+            RootKind::Package => check_package(matches, &path, roc_cache_dir)?,
+        };
+
+        outcomes.push(Outcome {
+            path,
+            kind,
+            exit_code,
+            time: start_time.elapsed(),
+        });
+    }
+
+    if !json_output {
+        println!("\n{}", summary_table(&outcomes));
+    }
+
+    Ok(outcomes
+        .iter()
+        .map(|outcome| outcome.exit_code)
+        .max()
+        .unwrap_or_default())
+}
+
+fn check_package(
+    matches: &ArgMatches,
+    path: &Path,
+    roc_cache_dir: RocCacheDir<'_>,
+) -> io::Result<i32> {
+    let threading = match matches.get_one::<usize>(FLAG_MAX_THREADS) {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(*n),
+    };
+
+    match check_file(
+        &Bump::new(),
+        path.to_path_buf(),
+        matches.get_flag(FLAG_TIME),
+        roc_cache_dir,
+        threading,
+        warning_levels_from_flags(matches),
+    ) {
+        Ok((problems, total_time)) => {
+            problems.print_to_stdout(total_time);
+
+            if !roc_reporting::cli::json_output_enabled() {
+                println!(" while checking:\n\n    {}", path.display());
+            }
+
+            Ok(problems.exit_code())
+        }
+        Err(problem) => handle_loading_problem(problem),
+    }
+}
+
+/// Every .roc file under `dir` with an `app` or `package` header, sorted by path.
+/// Vendored packages are skipped, since they get built along with the app that vendored them.
+fn find_roots(dir: &Path) -> io::Result<Vec<(PathBuf, RootKind)>> {
+    let mut roc_files = Vec::new();
+
+    roc_files_in_dir(dir, &mut roc_files)?;
+    roc_files.sort();
+
+    let mut roots = Vec::new();
+
+    for path in roc_files {
+        let is_vendored = path
+            .strip_prefix(dir)
+            .unwrap_or(&path)
+            .components()
+            .any(|component| component.as_os_str() == OsStr::new(VENDOR_DIR_NAME));
+
+        if is_vendored {
+            continue;
+        }
+
+        if let Some(kind) = root_kind(&path)? {
+            roots.push((path, kind));
+        }
+    }
+
+    Ok(roots)
+}
+
+fn root_kind(path: &Path) -> io::Result<Option<RootKind>> {
+    let src = fs::read(path)?;
+    let arena = Bump::new();
+
+    Ok(match parse_header(&arena, State::new(&src)) {
+        Ok((
+            Module {
+                header: Header::App(_),
+                ..
+            },
+            _,
+        )) => Some(RootKind::App),
+        Ok((
+            Module {
+                header: Header::Package(_),
+                ..
+            },
+            _,
+        )) => Some(RootKind::Package),
+        _ => None,
+    })
+}
+
+fn summary_table(outcomes: &[Outcome]) -> String {
+    let paths: Vec<String> = outcomes
+        .iter()
+        .map(|outcome| outcome.path.display().to_string())
+        .collect();
+    let path_width = paths
+        .iter()
+        .map(|path| path.chars().count())
+        .chain(["Module".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut buf = String::new();
+
+    writeln!(
+        buf,
+        "    {:path_width$}  {:7}  {:6}  {:>12}",
+        "Module", "Kind", "Result", "Time"
+    )
+    .unwrap();
+
+    for (outcome, path) in outcomes.iter().zip(paths.iter()) {
+        let kind = match outcome.kind {
+            RootKind::App => "app",
+            RootKind::Package => "package",
+        };
+        let result = if outcome.exit_code == 0 {
+            "\x1B[32mok    \x1B[39m"
+        } else {
+            "\x1B[31mfailed\x1B[39m"
+        };
+
+        writeln!(
+            buf,
+            "    {path:path_width$}  {kind:7}  {result}  {:>9.3} ms",
+            outcome.time.as_secs_f64() * 1000.0
+        )
+        .unwrap();
+    }
+
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| outcome.exit_code != 0)
+        .count();
+
+    write!(
+        buf,
+        "\n{} of {} built successfully.",
+        outcomes.len() - failed,
+        outcomes.len()
+    )
+    .unwrap();
+
+    roc_reporting::report::strip_colors_if_disabled(&buf).into_owned()
+}
@@ -52,7 +52,9 @@ mod cli_run {
     const OPTIMIZE_FLAG: &str = concatcp!("--", roc_cli::FLAG_OPTIMIZE);
     const LINKER_FLAG: &str = concatcp!("--", roc_cli::FLAG_LINKER);
     const CHECK_FLAG: &str = concatcp!("--", roc_cli::FLAG_CHECK);
+    const VERIFY_FLAG: &str = concatcp!("--", roc_cli::FLAG_VERIFY);
     const PREBUILT_PLATFORM: &str = concatcp!("--", roc_cli::FLAG_PREBUILT);
+    const EMIT_FLAG: &str = concatcp!("--", roc_cli::FLAG_EMIT);
     #[allow(dead_code)]
     const TARGET_FLAG: &str = concatcp!("--", roc_cli::FLAG_TARGET);
 
@@ -584,11 +586,11 @@ mod cli_run {
                 x : Num *
                 x = 42
 
-                [<ignored for tests> 19:9] 42
-                [<ignored for tests> 20:9] "Fjoer en ferdjer frieten oan dyn geve lea"
-                [<ignored for tests> 13:9] "abc"
-                [<ignored for tests> 13:9] 10
-                [<ignored for tests> 13:9] A (B C)
+                [<ignored for tests> 19:9] x = 42 : Num *
+                [<ignored for tests> 20:9] "Fjoer en ferdjer frieten oan dyn geve lea" = "Fjoer en ferdjer frieten oan dyn geve lea" : Str
+                [<ignored for tests> 13:9] x = "abc" : Str
+                [<ignored for tests> 13:9] x = 10 : U8
+                [<ignored for tests> 13:9] x = A (B C) : [A [B [C]]]
                 Program finished!
                 "#
             ),
@@ -628,6 +630,33 @@ mod cli_run {
             UseValgrind::Yes,
             TestCliCommands::Test,
         );
+
+        // With --fail-fast, the app is stopped after the first failed expect is reported,
+        // so the second one never runs.
+        let file = file_path_from_root(
+            "crates/cli_testing_examples/expects",
+            "expects-fail-fast.roc",
+        );
+        let out = run_roc(
+            [
+                CMD_DEV,
+                concatcp!("--", roc_cli::FLAG_FAIL_FAST),
+                file.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+        let stdout = strip_colors(&out.stdout);
+
+        assert_eq!(out.status.code(), Some(1), "{stdout}\n{}", out.stderr);
+        assert_eq!(
+            stdout.matches("This expectation failed:").count(),
+            1,
+            "{stdout}"
+        );
+        assert!(stdout.contains("expect x == 2"), "{stdout}");
+        assert!(!stdout.contains("expect y == 4"), "{stdout}");
+        assert!(!stdout.contains("Program finished!"), "{stdout}");
     }
 
     #[test]
@@ -940,6 +969,62 @@ mod cli_run {
         test_roc_expect("examples/parser/package", "ParserHttp.roc")
     }
 
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn test_directory() {
+        // Quadruple imports Dep, but Dep's expects should still only run once.
+        let out = run_roc(
+            [CMD_TEST, fixtures_dir("test-dir").to_str().unwrap()],
+            &[],
+            &[],
+        );
+        assert!(out.status.success());
+        assert!(strip_colors(&out.stdout).contains("0 failed and 3 passed"));
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn test_directory_keeps_going_after_a_module_fails_to_compile() {
+        let out = run_roc(
+            [
+                CMD_TEST,
+                fixtures_dir("test-dir-not-compiled").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+        let stdout = strip_colors(&out.stdout);
+
+        assert_eq!(out.status.code(), Some(1), "{stdout}");
+        assert!(stdout.contains("0 failed and 1 passed"), "{stdout}");
+        assert!(
+            stdout.contains("2 modules could not be compiled, so they were not tested:"),
+            "{stdout}"
+        );
+        assert!(stdout.contains("Broken.roc"), "{stdout}");
+        assert!(stdout.contains("Worse.roc"), "{stdout}");
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn test_doc_examples() {
+        let out = run_roc(
+            [
+                CMD_TEST,
+                concatcp!("--", roc_cli::FLAG_DOC),
+                fixture_file("doc-tests", "Triple.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+        let stdout = strip_colors(&out.stdout);
+
+        // The module's own failing expect doesn't run, but the wrong example does.
+        assert!(!out.status.success());
+        assert!(stdout.contains("tripleAll [1] == [4]"), "{stdout}");
+        assert!(stdout.contains("1 failed and 4 passed"), "{stdout}");
+    }
+
     #[test]
     #[cfg_attr(windows, ignore)]
     fn inspect_logging() {
@@ -1406,6 +1491,193 @@ mod cli_run {
         );
     }
 
+    #[test]
+    #[serial(multi_dep_thunk)]
+    #[cfg_attr(windows, ignore)]
+    fn build_directory() {
+        let out = run_roc(
+            [CMD_BUILD, fixtures_dir("packages").to_str().unwrap()],
+            &[],
+            &[],
+        );
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(out.status.success(), "{}\n{}", stdout, out.stderr);
+
+        // The app is built and both packages are checked. The platform is only built as part
+        // of the app, so it doesn't get a row of its own.
+        for (file, kind) in [
+            ("app.roc", "app"),
+            ("csv/main.roc", "package"),
+            ("json/main.roc", "package"),
+        ] {
+            let row = stdout
+                .lines()
+                .find(|line| line.replace('\\', "/").contains(file) && line.contains(" ms"))
+                .unwrap_or_else(|| panic!("No row for {file} in:\n{stdout}"));
+
+            assert!(row.contains(kind), "{row}");
+            assert!(row.contains("ok"), "{row}");
+        }
+
+        assert!(stdout.contains("3 of 3 built successfully."), "{stdout}");
+    }
+
+    #[test]
+    fn build_directory_keeps_going_after_a_failure() {
+        let dir = std::env::temp_dir().join("roc_cli_run_build_directory");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        for (file, src) in [
+            (
+                "broken/main.roc",
+                "package \"broken\"\n    exposes [Broken]\n    packages {}\n",
+            ),
+            (
+                "broken/Broken.roc",
+                "interface Broken\n    exposes [value]\n    imports []\n\nvalue : Str\nvalue = 1\n",
+            ),
+            (
+                "fine/main.roc",
+                "package \"fine\"\n    exposes [Fine]\n    packages {}\n",
+            ),
+            (
+                "fine/Fine.roc",
+                "interface Fine\n    exposes [value]\n    imports []\n\nvalue : Str\nvalue = \"fine\"\n",
+            ),
+            // Vendored packages are built along with the app that vendored them, not on their own.
+            (
+                "vendor/somehash/main.roc",
+                "package \"vendored\"\n    exposes []\n    packages {}\n",
+            ),
+        ] {
+            let path = dir.join(file);
+
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, src).unwrap();
+        }
+
+        let out = run_roc([CMD_BUILD, dir.to_str().unwrap()], &[], &[]);
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(!out.status.success(), "{stdout}");
+        assert!(stdout.contains("TYPE MISMATCH"), "{stdout}");
+        assert!(!stdout.contains("somehash"), "{stdout}");
+        assert!(stdout.contains("1 of 2 built successfully."), "{stdout}");
+
+        let result_of = |file: &str| {
+            stdout
+                .lines()
+                .find(|line| line.replace('\\', "/").contains(file) && line.contains(" ms"))
+                .unwrap_or_else(|| panic!("No row for {file} in:\n{stdout}"))
+                .to_string()
+        };
+
+        assert!(result_of("broken/main.roc").contains("failed"));
+        assert!(result_of("fine/main.roc").contains("ok"));
+    }
+
+    #[test]
+    #[serial(multi_dep_thunk)]
+    #[cfg_attr(windows, ignore)]
+    fn build_emit_borrow_report() {
+        let out = run_roc(
+            [
+                CMD_BUILD,
+                EMIT_FLAG,
+                "borrow-report",
+                fixture_file("emit", "app.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(out.status.success(), "{}\n{}", stdout, out.stderr);
+        assert!(
+            stdout.contains("These calls may copy a list instead of updating it in place"),
+            "{stdout}"
+        );
+
+        let line = stdout
+            .lines()
+            .find(|line| line.contains("in keepsOld ("))
+            .unwrap_or_else(|| panic!("keepsOld isn't in the report:\n{stdout}"));
+
+        assert!(line.trim_start().starts_with("List."), "{line}");
+        assert!(line.replace('\\', "/").contains("emit/app.roc:"), "{line}");
+    }
+
+    /// Build the emit fixture with `--emit <stage>`, and return the contents of the emitted
+    /// file with the given name.
+    fn build_and_read_emitted(stage: &str, file_name: &str) -> String {
+        let app = fixture_file("emit", "app.roc");
+        let emit_dir = fixtures_dir("emit").join("roc-emit");
+        let _ = std::fs::remove_dir_all(&emit_dir);
+
+        let out = run_roc(
+            [CMD_BUILD, EMIT_FLAG, stage, app.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}\n{}", out.stdout, out.stderr);
+
+        let path = emit_dir.join(file_name);
+
+        assert!(
+            out.stdout.contains(&format!("Wrote {}", path.display())),
+            "{}",
+            out.stdout
+        );
+
+        std::fs::read_to_string(&path).unwrap()
+    }
+
+    #[test]
+    #[serial(multi_dep_thunk)]
+    #[cfg_attr(windows, ignore)]
+    fn build_emit_parse() {
+        let parse = build_and_read_emitted("parse", "app.parse");
+
+        assert!(parse.contains("App("), "{parse}");
+        assert!(parse.contains("\"keepsOld\""), "{parse}");
+    }
+
+    #[test]
+    #[serial(multi_dep_thunk)]
+    #[cfg_attr(windows, ignore)]
+    fn build_emit_can() {
+        let can = build_and_read_emitted("can", "app.can");
+
+        assert!(can.contains("keepsOld"), "{can}");
+    }
+
+    #[test]
+    #[serial(multi_dep_thunk)]
+    #[cfg_attr(windows, ignore)]
+    fn build_emit_mono() {
+        let mono = build_and_read_emitted("mono", "app.mono");
+
+        assert!(mono.contains("keepsOld"), "{mono}");
+        assert!(
+            fixtures_dir("emit")
+                .join("roc-emit")
+                .join("List.mono")
+                .exists(),
+            "the specializations of List.set go in their own file"
+        );
+    }
+
+    #[test]
+    #[serial(multi_dep_thunk)]
+    #[cfg_attr(windows, ignore)]
+    fn build_emit_llvm_ir() {
+        let llvm_ir = build_and_read_emitted("llvm-ir", "app.ll");
+
+        assert!(llvm_ir.contains("define "), "{llvm_ir}");
+    }
+
     #[test]
     fn known_type_error() {
         check_compile_error(
@@ -1486,6 +1758,176 @@ mod cli_run {
         );
     }
 
+    #[test]
+    fn unused_import_denied() {
+        check_compile_error(
+            &known_bad_file("UnusedImport.roc"),
+            &["--deny", "warnings"],
+            indoc!(
+                r#"
+                ── UNUSED IMPORT ──────────────────────────── tests/known_bad/UnusedImport.roc ─
+
+                Nothing from Symbol is used in this module.
+
+                3│      imports [Symbol.{ Ident }]
+                                 ^^^^^^^^^^^^^^^^
+
+                Since Symbol isn't used, you don't need to import it.
+
+                ────────────────────────────────────────────────────────────────────────────────
+
+                1 error and 0 warnings found in <ignored for test> ms."#
+            ),
+        );
+    }
+
+    #[test]
+    fn unused_import_allowed() {
+        check_compile_error(
+            &known_bad_file("UnusedImport.roc"),
+            &["--deny", "warnings", "--allow", "unused-import"],
+            "0 errors and 0 warnings found in <ignored for test> ms.",
+        );
+    }
+
+    #[test]
+    fn type_hole() {
+        check_compile_error(
+            &known_bad_file("TypeHole.roc"),
+            &["--warn", "type-hole"],
+            indoc!(
+                r#"
+                ── TYPE HOLE ──────────────────────────────────── tests/known_bad/TypeHole.roc ─
+
+                This hole in the type annotation:
+
+                5│  double : List _ -> List I64
+                                  ^
+
+                was inferred to be:
+
+                    I64
+
+                ────────────────────────────────────────────────────────────────────────────────
+
+                0 errors and 1 warning found in <ignored for test> ms."#
+            ),
+        );
+    }
+
+    #[test]
+    fn explain_types() {
+        check_compile_error(
+            &known_bad_file("ExplainTypes.roc"),
+            &["--explain-types"],
+            indoc!(
+                r#"
+                ── TYPE MISMATCH ──────────────────────────── tests/known_bad/ExplainTypes.roc ─
+
+                This 2nd argument to add has an unexpected type:
+
+                9│      count + label
+                                ^^^^^
+
+                This label value is a:
+
+                    Str
+
+                But add needs its 2nd argument to be:
+
+                    Num *
+
+                Here is how I arrived at these types, step by step:
+
+                7│      label = "count"
+                                ^^^^^^^
+
+                It is a string of type:
+
+                    Str
+
+                9│      count + label
+                              ^
+
+                This Num.add value is a:
+
+                    Num a, Num a -> Num a
+
+                9│      count + label
+                        ^^^^^
+
+                This count value is a:
+
+                    Num a
+
+                ────────────────────────────────────────────────────────────────────────────────
+
+                1 error and 0 warnings found in <ignored for test> ms."#
+            ),
+        );
+    }
+
+    #[test]
+    fn unused_import_json() {
+        let file = known_bad_file("UnusedImport.roc");
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), "--output", "json"],
+            &[],
+            &[],
+        );
+        let lines: Vec<&str> = out.stdout.lines().collect();
+
+        assert_eq!(lines.len(), 2, "{}", out.stdout);
+
+        let problem = lines[0];
+        assert!(problem.contains(r#""severity":"warning""#));
+        assert!(problem.contains(r#""code":"UNUSED IMPORT""#));
+        assert!(problem.contains(r#""start":{"column":14,"line":3}"#));
+        assert!(problem.contains("Nothing from Symbol is used in this module."));
+
+        let summary = lines[1];
+        assert!(summary.contains(r#""errors":0"#));
+        assert!(summary.contains(r#""warnings":1"#));
+    }
+
+    #[test]
+    fn unused_import_sarif() {
+        let file = known_bad_file("UnusedImport.roc");
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), "--output", "sarif"],
+            &[],
+            &[],
+        );
+        let lines: Vec<&str> = out.stdout.lines().collect();
+
+        assert_eq!(lines.len(), 1, "{}", out.stdout);
+
+        let log = lines[0];
+        assert!(log.contains(r#""version":"2.1.0""#));
+        assert!(log.contains(r#""level":"warning""#));
+        assert!(log.contains(r#""ruleId":"unused-import""#));
+        assert!(log.contains(r#""startColumn":14,"startLine":3"#));
+        assert!(log.contains("Nothing from Symbol is used in this module."));
+    }
+
+    #[test]
+    fn sarif_columns_count_utf16_code_units() {
+        let file = known_bad_file("UnrecognizedNameAfterEmoji.roc");
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), "--output", "sarif"],
+            &[],
+            &[],
+        );
+
+        // The `y` is at byte 28 of its line, but the 👋 before it is only 2 UTF-16 code units.
+        assert!(out.stdout.contains(r#""columnKind":"utf16CodeUnits""#));
+        assert!(
+            out.stdout.contains(r#""startColumn":26,"startLine":5"#),
+            "{}",
+            out.stdout
+        );
+    }
+
     #[test]
     fn unknown_generates_with() {
         check_compile_error(
@@ -1520,6 +1962,521 @@ mod cli_run {
         check_format_check_as_expected(&fixture_file("format", "NotFormatted.roc"), false);
     }
 
+    #[test]
+    fn format_check_shows_diff() {
+        let file = fixture_file("format", "NotFormatted.roc");
+        let before = std::fs::read_to_string(&file).unwrap();
+        let out = run_roc([CMD_FORMAT, file.to_str().unwrap(), CHECK_FLAG], &[], &[]);
+
+        assert!(!out.status.success());
+        assert!(out.stdout.contains("-  provides [main] to pf\n"));
+        assert!(out.stdout.contains("+    provides [main] to pf\n"));
+        assert!(out.stderr.contains("1 file needs to be reformatted."));
+
+        // --check must never rewrite the file
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), before);
+    }
+
+    #[test]
+    fn format_verify() {
+        let file = fixture_file("format", "NotFormatted.roc");
+        let before = std::fs::read_to_string(&file).unwrap();
+        let out = run_roc([CMD_FORMAT, file.to_str().unwrap(), VERIFY_FLAG], &[], &[]);
+
+        assert!(out.status.success());
+        assert_eq!(out.stdout, "");
+
+        // --verify must never rewrite the file
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), before);
+    }
+
+    #[test]
+    fn format_verify_reports_files_it_cannot_format() {
+        let src = "interface Test exposes [a] imports []\n\na = (\n";
+        let out = run_roc([CMD_FORMAT, "--stdin", VERIFY_FLAG], &[src], &[]);
+
+        assert!(!out.status.success());
+        assert_eq!(out.stdout, "");
+        assert!(out
+            .stderr
+            .contains("stdin.roc doesn't parse, so it can't be formatted."));
+    }
+
+    #[test]
+    fn format_stdin() {
+        let src = std::fs::read_to_string(fixture_file("format", "NotFormatted.roc")).unwrap();
+        let out = run_roc([CMD_FORMAT, "--stdin"], &[&src], &[]);
+
+        assert!(out.status.success());
+        assert!(out.stdout.contains("\n    provides [main] to pf\n"));
+    }
+
+    #[test]
+    fn format_range() {
+        let src = "interface Test exposes [a, b] imports []\n\na =   1\n\nb =   2\n";
+        let out = run_roc([CMD_FORMAT, "--stdin", "--range", "5:5"], &[src], &[]);
+
+        assert!(out.status.success());
+        assert_eq!(
+            out.stdout,
+            "interface Test exposes [a, b] imports []\n\na =   1\n\nb = 2\n"
+        );
+    }
+
+    #[test]
+    fn format_pun_record_fields() {
+        let src = "interface Test exposes [f] imports []\n\nf = \\{ x: x, y: z } -> { x: x, z: z, w: x }\n";
+        let out = run_roc([CMD_FORMAT, "--stdin", "--pun-record-fields"], &[src], &[]);
+
+        assert!(out.status.success());
+        assert_eq!(
+            out.stdout,
+            "interface Test exposes [f] imports []\n\nf = \\{ x, y: z } -> { x, z, w: x }\n"
+        );
+    }
+
+    #[test]
+    fn format_migrate_backpassing() {
+        let src = "interface Test exposes [f] imports []\n\nf =\n    x <- Task.await foo\n    Task.succeed x\n";
+        let out = run_roc(
+            [CMD_FORMAT, "--stdin", "--migrate", "backpassing"],
+            &[src],
+            &[],
+        );
+
+        assert!(out.status.success());
+        assert_eq!(
+            out.stdout,
+            "interface Test exposes [f] imports []\n\nf =\n    Task.await foo \\x ->\n        Task.succeed x\n"
+        );
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn run_with_profile() {
+        let profile_path = std::env::temp_dir().join("roc_cli_run_profile.folded");
+        let _ = std::fs::remove_file(&profile_path);
+
+        let out = run_roc(
+            [
+                CMD_RUN.to_string(),
+                format!("--{}={}", roc_cli::FLAG_PROFILE, profile_path.display()),
+                fixture_file("multi-dep-str", "Main.roc")
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert!(out.stdout.ends_with("I am Dep2.str2\n"));
+
+        // Each line is a stack of function names, then how many samples had that stack.
+        let profile = std::fs::read_to_string(&profile_path).unwrap();
+        let samples: usize = profile
+            .lines()
+            .map(|line| {
+                let (stack, count) = line.rsplit_once(' ').unwrap();
+
+                assert!(!stack.is_empty(), "{line}");
+
+                count.parse::<usize>().unwrap()
+            })
+            .sum();
+
+        let stderr = strip_colors(&out.stderr);
+
+        assert!(
+            stderr.contains(&format!(
+                "Wrote {samples} sample{} to {}",
+                if samples == 1 { "" } else { "s" },
+                profile_path.display()
+            )),
+            "{stderr}"
+        );
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn run_with_trace_allocs() {
+        let out = run_roc(
+            [
+                CMD_RUN,
+                concatcp!("--", roc_cli::FLAG_TRACE_ALLOCS),
+                "--no-color",
+                fixture_file("multi-dep-str", "Main.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert!(out.stdout.ends_with("I am Dep2.str2\n"));
+
+        // --no-color applies to the report too.
+        assert!(!out.stderr.contains('\x1B'), "{}", out.stderr);
+        assert!(out
+            .stderr
+            .contains("Allocations by the innermost Roc function that made them:"));
+
+        let total = out
+            .stderr
+            .lines()
+            .find(|line| line.trim_start().starts_with("Total"))
+            .unwrap();
+        let columns: Vec<&str> = total.split_whitespace().skip(1).collect();
+
+        assert_eq!(columns.len(), 3, "{total}");
+        assert!(columns.iter().all(|column| column.parse::<u64>().is_ok()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn dev_watch_rebuilds_when_a_file_changes() {
+        use cli_utils::helpers::path_to_roc_binary;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        // Work on a copy of the app, so that editing it leaves the fixture alone.
+        let dir = std::env::temp_dir().join("roc_cli_run_dev_watch");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("platform")).unwrap();
+
+        for file in [
+            "Main.roc",
+            "Dep1.roc",
+            "Dep2.roc",
+            "platform/main.roc",
+            "platform/host.zig",
+        ] {
+            std::fs::copy(fixtures_dir("multi-dep-str").join(file), dir.join(file)).unwrap();
+        }
+
+        let mut roc = Command::new(path_to_roc_binary())
+            .args([CMD_DEV, "--watch", dir.join("Main.roc").to_str().unwrap()])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let mut lines = BufReader::new(roc.stdout.take().unwrap()).lines();
+        let mut read_until = |needle: &str| -> Vec<String> {
+            let mut seen = Vec::new();
+
+            loop {
+                let line = lines.next().expect("roc stopped watching").unwrap();
+                let found = line.contains(needle);
+
+                seen.push(line);
+
+                if found {
+                    return seen;
+                }
+            }
+        };
+
+        read_until("I am Dep2.str2");
+
+        // Without a terminal, the banner comes without escape codes.
+        let waiting = read_until("Waiting for changes to");
+
+        assert!(waiting.iter().all(|line| !line.contains('\x1B')));
+
+        let dep2 = std::fs::read_to_string(dir.join("Dep2.roc")).unwrap();
+
+        std::fs::write(
+            dir.join("Dep2.roc"),
+            dep2.replace("str2\"", "str2, edited\""),
+        )
+        .unwrap();
+
+        read_until("I am Dep2.str2, edited");
+
+        roc.kill().unwrap();
+        roc.wait().unwrap();
+    }
+
+    #[test]
+    fn publish_package() {
+        // Publishing writes the archive next to the package, so work on a copy of it.
+        let dir = std::env::temp_dir().join("roc_cli_run_publish");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for file in ["main.roc", "JsonParser.roc"] {
+            std::fs::copy(fixtures_dir("packages/json").join(file), dir.join(file)).unwrap();
+        }
+
+        let out = run_roc(
+            [
+                roc_cli::CMD_PUBLISH,
+                "--no-color",
+                dir.join("main.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert!(!out.stdout.contains('\x1B'), "{}", out.stdout);
+
+        let archives: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.ends_with(".tar.br"))
+            .collect();
+
+        assert_eq!(archives.len(), 1, "{archives:?}");
+        assert!(
+            out.stdout
+                .contains(&format!("\"https://your-url-goes-here/{}\"", archives[0])),
+            "{}",
+            out.stdout
+        );
+    }
+
+    #[test]
+    fn install_vendored_package() {
+        // Installing writes a roc.lock next to the app, so work in a temp dir. The platform is
+        // vendored, so nothing needs to be downloaded.
+        const PLATFORM_URL: &str = "https://example.com/platform/platformhash.tar.br";
+
+        let dir = std::env::temp_dir().join("roc_cli_run_install");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("vendor").join("platformhash")).unwrap();
+
+        std::fs::write(
+            dir.join("main.roc"),
+            format!(
+                "app \"install\"\n    packages {{ pf: \"{PLATFORM_URL}\" }}\n    imports []\n    provides [main] to pf\n\nmain = \"\"\n"
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("vendor").join("platformhash").join("main.roc"),
+            "platform \"install\"\n    requires {} { main : Str }\n    exposes []\n    packages {}\n    imports []\n    provides [mainForHost]\n\nmainForHost : Str\nmainForHost = main\n",
+        )
+        .unwrap();
+
+        let main = dir.join("main.roc");
+        let out = run_roc([roc_cli::CMD_INSTALL, main.to_str().unwrap()], &[], &[]);
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert!(out.stdout.contains(PLATFORM_URL), "{}", out.stdout);
+        assert!(
+            out.stdout.contains("Installed 1 package into"),
+            "{}",
+            out.stdout
+        );
+
+        let lockfile = std::fs::read_to_string(dir.join("roc.lock")).unwrap();
+
+        assert!(lockfile.contains(PLATFORM_URL), "{lockfile}");
+
+        // Installing again checks the packages against the roc.lock that was just written.
+        let out = run_roc([roc_cli::CMD_INSTALL, main.to_str().unwrap()], &[], &[]);
+
+        assert!(out.status.success(), "{}", out.stderr);
+    }
+
+    #[test]
+    fn install_missing_file() {
+        let out = run_roc([roc_cli::CMD_INSTALL, "does-not-exist/main.roc"], &[], &[]);
+
+        assert!(!out.status.success());
+        assert!(
+            out.stderr.contains("This file was not found"),
+            "{}",
+            out.stderr
+        );
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg_attr(windows, ignore)]
+    fn build_static_lib() {
+        use object::read::archive::ArchiveFile;
+        use object::{BinaryFormat, Object};
+
+        let main = fixture_file("multi-dep-str", "Main.roc");
+
+        // `None` is for wasm, which the object crate can't parse as it's configured here
+        for (target, file_name, format) in [
+            ("linux-x64", "multi-dep-str.a", Some(BinaryFormat::Elf)),
+            ("macos-arm64", "multi-dep-str.a", Some(BinaryFormat::MachO)),
+            ("windows-x64", "multi-dep-str.lib", Some(BinaryFormat::Coff)),
+            ("wasm32", "multi-dep-str.a", None),
+        ] {
+            let lib_path = fixtures_dir("multi-dep-str").join(file_name);
+            let _ = std::fs::remove_file(&lib_path);
+
+            let target_flag = format!("{TARGET_FLAG}={target}");
+            let out = run_roc(
+                [
+                    CMD_BUILD,
+                    "--lib=static",
+                    &target_flag,
+                    main.to_str().unwrap(),
+                ],
+                &[],
+                &[],
+            );
+
+            assert!(out.status.success(), "{}\n{}", out.cmd_str, out.stderr);
+
+            let bytes = std::fs::read(&lib_path).unwrap_or_else(|err| {
+                panic!("{target}: couldn't read {}: {err}", lib_path.display())
+            });
+            let archive = ArchiveFile::parse(&*bytes)
+                .unwrap_or_else(|err| panic!("{target}: not an archive: {err}"));
+            let mut members = 0;
+
+            for member in archive.members() {
+                let member = member.unwrap();
+                let name = String::from_utf8_lossy(member.name());
+                let data = member.data(&*bytes).unwrap();
+
+                match format {
+                    Some(format) => {
+                        let file = object::File::parse(data)
+                            .unwrap_or_else(|err| panic!("{target}: {name}: {err}"));
+
+                        assert_eq!(file.format(), format, "{target}: {name}");
+                    }
+                    None => assert!(data.starts_with(b"\0asm"), "{target}: {name}"),
+                }
+
+                members += 1;
+            }
+
+            assert!(members > 0, "{target}: the archive is empty");
+
+            std::fs::remove_file(&lib_path).unwrap();
+        }
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    fn build_is_reproducible() {
+        for optimize in [false, true] {
+            let main = fixture_file("multi-dep-str", "Main.roc");
+            let mut args = vec![
+                CMD_BUILD,
+                concatcp!("--", roc_cli::FLAG_VERIFY_REPRODUCIBLE),
+                main.to_str().unwrap(),
+            ];
+
+            if optimize {
+                args.push(OPTIMIZE_FLAG);
+            }
+
+            let out = run_roc(&args, &[], &[]);
+
+            assert!(out.status.success(), "{}\n{}", out.cmd_str, out.stderr);
+            assert!(
+                out.stdout.contains(
+                    "Built it a second time, and both builds were byte-for-byte identical"
+                ),
+                "{}",
+                out.stdout
+            );
+        }
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    #[cfg_attr(windows, ignore)]
+    fn build_verbose_reports_unused_code() {
+        let main = fixture_file("multi-dep-str", "Main.roc");
+        let main = main.to_str().unwrap();
+
+        let out = run_roc(
+            [
+                CMD_BUILD,
+                concatcp!("--", roc_cli::FLAG_VERBOSE),
+                LINKER_FLAG,
+                "legacy",
+                main,
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}\n{}", out.cmd_str, out.stderr);
+        assert!(
+            out.stdout.contains("The linker left out ")
+                && out
+                    .stdout
+                    .contains(" bytes of code and data in the object files as unused, leaving "),
+            "{}",
+            out.stdout
+        );
+
+        // The report is only printed with --verbose, not with --time.
+        let out = run_roc(
+            [
+                CMD_BUILD,
+                concatcp!("--", roc_cli::FLAG_TIME),
+                LINKER_FLAG,
+                "legacy",
+                main,
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}\n{}", out.cmd_str, out.stderr);
+        assert!(
+            !out.stdout.contains("The linker left out"),
+            "{}",
+            out.stdout
+        );
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    fn build_with_chrome_trace() {
+        let trace_path = std::env::temp_dir().join("roc_cli_run_build_trace.json");
+        let _ = std::fs::remove_file(&trace_path);
+
+        let out = run_roc(
+            [
+                CMD_BUILD,
+                "--trace",
+                &format!("chrome://{}", trace_path.display()),
+                fixture_file("multi-dep-str", "Main.roc").to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        assert!(out.status.success(), "{}", out.stderr);
+
+        let trace = std::fs::read_to_string(&trace_path).unwrap();
+        let events: Vec<serde_json::Value> = serde_json::from_str(&trace)
+            .unwrap_or_else(|err| panic!("The trace is not valid JSON ({err}):\n{trace}"));
+        let span_names: Vec<&str> = events
+            .iter()
+            .filter_map(|event| event["name"].as_str())
+            .collect();
+
+        // Every module gets parsed, and the whole app gets linked.
+        for module in ["Main", "Dep1", "Dep2"] {
+            assert!(
+                events.iter().any(|event| event["name"] == "parse"
+                    && event["args"]["module"]
+                        .as_str()
+                        .map_or(false, |name| name.contains(module))),
+                "No parse span for {module} in {span_names:?}"
+            );
+        }
+
+        assert!(span_names.contains(&"link"), "{span_names:?}");
+    }
+
     #[test]
     fn format_check_folders() {
         // This fails, because "NotFormatted.roc" is present in this folder
@@ -0,0 +1,45 @@
+interface Triple exposes [triple, tripleAll] imports []
+
+## Multiplies a number by 3.
+##
+## ```
+## triple 2 == 6
+## triple 0 == 0
+## ```
+##
+## Examples can define things before checking them:
+##
+## ```
+## n = 5
+##
+## triple n == 15
+## ```
+triple : U64 -> U64
+triple = \n -> n * 3
+
+## Triples every number in a list.
+##
+## ```roc
+## tripleAll [1, 2] == [3, 6]
+## ```
+##
+## This example is wrong, so that there's a failing one to report:
+##
+## ```
+## tripleAll [1] == [4]
+## ```
+##
+## Code blocks in other languages, and ones marked `unchecked`, aren't run:
+##
+## ```sh
+## echo "1 == 2"
+## ```
+##
+## ```roc unchecked
+## tripleAll [1] == [1]
+## ```
+tripleAll : List U64 -> List U64
+tripleAll = \list -> List.map list triple
+
+# With --doc, only the examples run, not this.
+expect triple 1 == 4
@@ -0,0 +1,13 @@
+app "emit"
+    packages { pf: "../packages/platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+# The old list is still used after List.set, so it can't be updated in place.
+keepsOld : List U8 -> Str
+keepsOld = \list ->
+    new = List.set list 0 10
+
+    Num.toStr (List.len list + List.len new)
+
+main = keepsOld [1, 2, 3]
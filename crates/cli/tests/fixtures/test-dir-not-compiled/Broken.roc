@@ -0,0 +1,6 @@
+interface Broken exposes [name] imports []
+
+name : Str
+name = 42
+
+expect name == "Roc"
@@ -0,0 +1,6 @@
+interface Fine exposes [inc] imports []
+
+inc : U64 -> U64
+inc = \n -> n + 1
+
+expect inc 1 == 2
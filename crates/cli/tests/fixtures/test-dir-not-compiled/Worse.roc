@@ -0,0 +1,6 @@
+interface Worse exposes [len] imports []
+
+len : Str -> U64
+len = \str -> List.len str
+
+expect len "" == 0
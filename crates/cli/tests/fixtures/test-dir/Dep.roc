@@ -0,0 +1,8 @@
+interface Dep exposes [double] imports []
+
+double : U64 -> U64
+double = \n -> n * 2
+
+expect double 0 == 0
+
+expect double 21 == 42
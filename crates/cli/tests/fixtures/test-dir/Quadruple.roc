@@ -0,0 +1,6 @@
+interface Quadruple exposes [quadruple] imports [Dep]
+
+quadruple : U64 -> U64
+quadruple = \n -> Dep.double (Dep.double n)
+
+expect quadruple 3 == 12
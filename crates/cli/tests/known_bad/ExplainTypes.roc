@@ -0,0 +1,9 @@
+interface ExplainTypes
+    exposes [total]
+    imports []
+
+total =
+    count = 1
+    label = "count"
+
+    count + label
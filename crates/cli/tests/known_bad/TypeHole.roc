@@ -0,0 +1,6 @@
+interface TypeHole
+    exposes [double]
+    imports []
+
+double : List _ -> List I64
+double = \list -> List.map list \n -> n * 2
@@ -0,0 +1,5 @@
+interface UnrecognizedNameAfterEmoji
+    exposes [greeting]
+    imports []
+
+greeting = { a: "👋", b: y }
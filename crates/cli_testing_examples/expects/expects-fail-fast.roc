@@ -0,0 +1,13 @@
+app "expects-fail-fast"
+    packages { pf: "zig-platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+main =
+    x = 1
+    expect x == 2
+
+    y = 3
+    expect y == 4
+
+    "Program finished!\n"
@@ -0,0 +1,391 @@
+//! Formatting Roc code, checking as it goes that formatting doesn't change what the code means.
+use bumpalo::Bump;
+use roc_can::operator::desugar_defs;
+use roc_fmt::annotation::Formattable;
+use roc_fmt::def::fmt_defs;
+use roc_fmt::module::fmt_module;
+use roc_fmt::spaces::{count_comments, RemoveSpaces};
+use roc_fmt::{Ast, Buf, FormatOptions};
+use roc_parse::module::{self, module_defs};
+use roc_parse::parser::{Parser, SyntaxError};
+use roc_parse::state::State;
+use roc_region::all::LineInfo;
+use std::cell::Cell;
+use std::ops::RangeInclusive;
+
+/// Why some code couldn't be formatted. Apart from `Syntax`, these are bugs in the formatter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatProblem {
+    /// The code doesn't parse, so there's nothing to format yet.
+    Syntax(String),
+    /// The `# roc-fmt:` settings at the top of the module aren't valid.
+    Config(String),
+    /// The formatted code doesn't parse.
+    InvalidOutput {
+        formatted: String,
+        parse_error: String,
+    },
+    /// The formatted code parses, but not into the same tree as the original code did.
+    ChangedMeaning {
+        formatted: String,
+        ast_before: String,
+        ast_after: String,
+    },
+    /// Formatting the formatted code changes it again.
+    Unstable {
+        formatted: String,
+        reformatted: String,
+    },
+}
+
+/// How many more times to format code with a maximum width, at most, for the layout to settle.
+const MAX_WIDTH_PASSES: usize = 3;
+
+/// Format a whole module. Any `# roc-fmt:` settings at the top of it override the options.
+pub fn format_str(src: &str, options: FormatOptions) -> Result<String, FormatProblem> {
+    let options = options
+        .with_config_from(src)
+        .map_err(FormatProblem::Config)?;
+    let arena = Bump::new();
+    let src = arena.alloc_str(src);
+    let ast = arena.alloc(parse_for_formatting(&arena, src)?);
+    let mut buf = Buf::new_in_with_options(&arena, options);
+
+    fmt_all(&mut buf, ast);
+
+    let mut formatted = buf.into_bump_str();
+
+    // Breaking something that's too wide over multiple lines can change how the code
+    // around it is laid out the next time, so keep formatting until that settles.
+    if options.max_width.is_some() {
+        for _ in 0..MAX_WIDTH_PASSES {
+            // If the output doesn't parse, that gets reported below.
+            let Ok(reparsed_ast) = parse_all(&arena, formatted) else {
+                break;
+            };
+            let mut buf = Buf::new_in_with_options(&arena, options);
+
+            fmt_all(&mut buf, arena.alloc(reparsed_ast));
+
+            let reformatted = buf.into_bump_str();
+
+            if reformatted == formatted {
+                break;
+            }
+
+            formatted = reformatted;
+        }
+    }
+
+    let reparsed_ast = match parse_all(&arena, formatted) {
+        Ok(reparsed_ast) => arena.alloc(reparsed_ast),
+        Err(err) => {
+            return Err(FormatProblem::InvalidOutput {
+                formatted: formatted.to_string(),
+                parse_error: format!("{err:?}"),
+            })
+        }
+    };
+
+    let ast_normalized = normalize(&arena, ast, options);
+    let reparsed_ast_normalized = normalize(&arena, reparsed_ast, options);
+
+    // HACK!
+    // We compare the debug format strings of the ASTs, because I'm finding in practice that _somewhere_ deep inside the ast,
+    // the PartialEq implementation is returning `false` even when the Debug-formatted impl is exactly the same.
+    // I don't have the patience to debug this right now, so let's leave it for another day...
+    // TODO: fix PartialEq impl on ast types
+    if format!("{ast_normalized:?}") != format!("{reparsed_ast_normalized:?}") {
+        return Err(FormatProblem::ChangedMeaning {
+            formatted: formatted.to_string(),
+            ast_before: format!("{ast_normalized:#?}\n"),
+            ast_after: format!("{reparsed_ast_normalized:#?}\n"),
+        });
+    }
+
+    // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
+    let mut reformatted_buf = Buf::new_in_with_options(&arena, options);
+
+    fmt_all(&mut reformatted_buf, reparsed_ast);
+
+    if formatted != reformatted_buf.as_str() {
+        return Err(FormatProblem::Unstable {
+            formatted: formatted.to_string(),
+            reformatted: reformatted_buf.as_str().to_string(),
+        });
+    }
+
+    debug_assert_eq!(
+        count_comments(src),
+        count_comments(formatted),
+        "Formatting bug; formatting didn't keep every comment"
+    );
+
+    Ok(formatted.to_string())
+}
+
+/// Format only the top-level definitions that overlap the given lines (which start at 1,
+/// like in an editor), and leave the rest of the source exactly as it was.
+/// Any `# roc-fmt:` settings at the top of the module override the options.
+pub fn format_lines(
+    src: &str,
+    lines: &RangeInclusive<u32>,
+    options: FormatOptions,
+) -> Result<String, FormatProblem> {
+    let options = options
+        .with_config_from(src)
+        .map_err(FormatProblem::Config)?;
+    let arena = Bump::new();
+    let src = arena.alloc_str(src);
+    let ast = parse_for_formatting(&arena, src)?;
+    let line_info = LineInfo::new(src);
+    let mut formatted = src.to_string();
+
+    // Splice from the last definition to the first, so the offsets of the ones
+    // that haven't been spliced yet stay the same.
+    for (index, def) in ast.defs.defs().enumerate().rev() {
+        let region = ast.defs.regions[index];
+        let def_lines = line_info.convert_region(region);
+
+        if def_lines.end.line + 1 < *lines.start() || def_lines.start.line + 1 > *lines.end() {
+            continue;
+        }
+
+        let mut buf = Buf::new_in_with_options(&arena, options);
+
+        match def {
+            Ok(type_def) => type_def.format(&mut buf, 0),
+            Err(value_def) => value_def.format(&mut buf, 0),
+        }
+
+        buf.fmt_end_of_file();
+
+        formatted.replace_range(
+            region.start().offset as usize..region.end().offset as usize,
+            buf.as_str().trim_end(),
+        );
+    }
+
+    // Make sure splicing the formatted definitions in didn't change what the code means.
+    let reparsed_ast = match parse_all(&arena, arena.alloc_str(&formatted)) {
+        Ok(reparsed_ast) => reparsed_ast,
+        Err(err) => {
+            return Err(FormatProblem::InvalidOutput {
+                formatted,
+                parse_error: format!("{err:?}"),
+            })
+        }
+    };
+
+    let ast_normalized = normalize(&arena, &ast, options);
+    let reparsed_ast_normalized = normalize(&arena, &reparsed_ast, options);
+
+    if format!("{ast_normalized:?}") != format!("{reparsed_ast_normalized:?}") {
+        return Err(FormatProblem::ChangedMeaning {
+            formatted,
+            ast_before: format!("{ast_normalized:#?}\n"),
+            ast_after: format!("{reparsed_ast_normalized:#?}\n"),
+        });
+    }
+
+    debug_assert_eq!(
+        count_comments(src),
+        count_comments(&formatted),
+        "Formatting bug; formatting didn't keep every comment"
+    );
+
+    Ok(formatted)
+}
+
+/// The parts of the tree that formatting has to keep the same. Migrations rewrite syntax
+/// into what it desugars to, so with those, it's the desugared trees that have to match.
+fn normalize<'a>(arena: &'a Bump, ast: &Ast<'a>, options: FormatOptions) -> Ast<'a> {
+    if !options.migrations.any() {
+        return ast.remove_spaces(arena);
+    }
+
+    let mut defs = ast.defs.clone();
+
+    desugar_defs(arena, &Cell::new(0), &mut defs);
+
+    Ast {
+        module: ast.module.remove_spaces(arena),
+        defs: defs.remove_spaces(arena),
+    }
+}
+
+fn parse_for_formatting<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, FormatProblem> {
+    parse_all(arena, src).map_err(|err| FormatProblem::Syntax(format!("{err:?}")))
+}
+
+fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
+    let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
+        .map_err(|e| SyntaxError::Header(e.problem))?;
+
+    let (_, defs, _) = module_defs().parse(arena, state, 0).map_err(|(_, e)| e)?;
+
+    Ok(Ast { module, defs })
+}
+
+fn fmt_all<'a>(buf: &mut Buf<'a>, ast: &'a Ast) {
+    fmt_module(buf, &ast.module);
+
+    fmt_defs(buf, &ast.defs, 0);
+
+    buf.fmt_end_of_file();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+    use roc_fmt::Migrations;
+
+    #[test]
+    fn format_str_formats() {
+        let src = "interface Test exposes [answer] imports []\n\nanswer =   42\n";
+
+        assert_eq!(
+            format_str(src, FormatOptions::default()),
+            Ok(indoc!(
+                r#"
+                interface Test exposes [answer] imports []
+
+                answer = 42
+                "#
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn format_lines_leaves_other_defs_alone() {
+        let src = "interface Test exposes [a, b] imports []\n\na =   1\n\nb =   2\n";
+
+        assert_eq!(
+            format_lines(src, &(3..=3), FormatOptions::default()),
+            Ok("interface Test exposes [a, b] imports []\n\na = 1\n\nb =   2\n".to_string())
+        );
+    }
+
+    #[test]
+    fn format_str_breaks_lines_past_max_width() {
+        let src = indoc!(
+            r#"
+            # roc-fmt: max-width = 50
+            interface Test exposes [names] imports []
+
+            names = ["alice", "bob", "carol", "dave", "erin", "frank"]
+            "#
+        );
+
+        assert_eq!(
+            format_str(src, FormatOptions::default()),
+            Ok(indoc!(
+                r#"
+                # roc-fmt: max-width = 50
+                interface Test exposes [names] imports []
+
+                names = [
+                    "alice",
+                    "bob",
+                    "carol",
+                    "dave",
+                    "erin",
+                    "frank",
+                ]
+                "#
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn format_str_indent_and_trailing_commas() {
+        let src = indoc!(
+            r#"
+            # roc-fmt: indent = 2, trailing-commas = false
+            interface Test exposes [point] imports []
+
+            point = {
+                x: 1,
+                y: 2,
+            }
+            "#
+        );
+
+        assert_eq!(
+            format_str(src, FormatOptions::default()),
+            Ok(indoc!(
+                r#"
+                # roc-fmt: indent = 2, trailing-commas = false
+                interface Test exposes [point] imports []
+
+                point = {
+                  x: 1,
+                  y: 2
+                }
+                "#
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn format_str_migrates_backpassing() {
+        let src = indoc!(
+            r#"
+            interface Test exposes [main] imports []
+
+            main =
+                line <- Task.await readLine
+                name <- Task.await getName
+
+                Task.succeed "\(line) \(name)"
+            "#
+        );
+        let options = FormatOptions {
+            migrations: Migrations { backpassing: true },
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            format_str(src, options),
+            Ok(indoc!(
+                r#"
+                interface Test exposes [main] imports []
+
+                main =
+                    Task.await readLine \line ->
+                        Task.await getName \name ->
+                            Task.succeed "\(line) \(name)"
+                "#
+            )
+            .to_string())
+        );
+
+        // Without the migration, backpassing stays as it is.
+        assert_eq!(
+            format_str(src, FormatOptions::default()),
+            Ok(src.to_string())
+        );
+    }
+
+    #[test]
+    fn format_str_invalid_config() {
+        let src = "# roc-fmt: tabs = true\ninterface Test exposes [] imports []\n";
+
+        assert!(matches!(
+            format_str(src, FormatOptions::default()),
+            Err(FormatProblem::Config(_))
+        ));
+    }
+
+    #[test]
+    fn format_str_syntax_error() {
+        assert!(matches!(
+            format_str("interface Test exposes [", FormatOptions::default()),
+            Err(FormatProblem::Syntax(_))
+        ));
+    }
+}
@@ -0,0 +1,391 @@
+//! A small, stable interface to the Roc compiler, for tools that embed it.
+//!
+//! [check_file] and [check_str] type-check a module, [compile_str] takes one as far as the
+//! mono IR that a backend generates code from, and [format_str] formats one. Rather than
+//! being printed, the problems they find come back as [Diagnostic]s.
+//!
+//! The compiler's other crates change whenever the compiler needs them to. This one only
+//! grows, except for the modules that checking and compiling hand back: those are there for
+//! tools that need more than diagnostics (like the REPL and the language server), and they
+//! come without that promise.
+#![warn(clippy::dbg_macro)]
+mod format;
+
+pub use format::{format_lines, format_str, FormatProblem};
+pub use roc_fmt::{FormatOptions, Migrations};
+pub use roc_load::{LoadedModule, MonomorphizedModule, Threading};
+pub use roc_problem::Severity;
+pub use roc_region::all::{LineColumn, LineColumnRegion};
+pub use roc_reporting::report::Palette;
+pub use roc_target::TargetInfo;
+
+use bumpalo::Bump;
+use roc_collections::MutMap;
+use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError, LoadingProblem};
+use roc_module::symbol::{Interns, ModuleId};
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_region::all::{LineInfo, Region};
+use roc_reporting::report::{
+    can_problem, strip_colors, type_problem, RenderTarget, Report, RocDocAllocator, DEFAULT_PALETTE,
+};
+use roc_solve_problem::TypeError;
+use std::path::{Path, PathBuf};
+
+/// How to check or compile a module.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// What the code is going to run on. Type checking only needs it for the size of pointers.
+    pub target_info: TargetInfo,
+    /// How many threads to load modules with. [check_str] always uses just the current one.
+    pub threading: Threading,
+    /// Where packages from URLs get downloaded to. With `None`, modules can only use
+    /// packages that are already on disk.
+    pub cache_dir: Option<PathBuf>,
+    /// Where to find the modules that a module given as a string imports.
+    /// By default, that's the directory of the path it's given with.
+    pub src_dir: Option<PathBuf>,
+    /// Render each diagnostic's message, title included, in these colors for a terminal.
+    /// Otherwise, messages are plain text, and the title is only in its own field.
+    pub palette: Option<Palette>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            target_info: TargetInfo::default_x86_64(),
+            threading: Threading::AllAvailable,
+            cache_dir: Some(cache::roc_cache_dir()),
+            src_dir: None,
+            palette: None,
+        }
+    }
+}
+
+/// An error or warning about some Roc code.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// The module the problem is in. Problems that stopped the compiler before it got to
+    /// any module (like a missing file) are reported for the one it was asked about.
+    pub path: PathBuf,
+    /// Where in the module the problem is, if it's anywhere in particular.
+    /// Lines and columns start at 0.
+    pub region: Option<LineColumnRegion>,
+    pub severity: Severity,
+    /// What kind of problem this is, e.g. `UNUSED IMPORT`. Empty for the problems that
+    /// stopped the compiler before it got to any module.
+    pub title: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Whether this keeps the code from running as written, rather than being a warning.
+    pub fn is_error(&self) -> bool {
+        !matches!(self.severity, Severity::Warning)
+    }
+}
+
+/// What type-checking a module found.
+#[derive(Debug)]
+pub struct Checked {
+    /// The problems in the module and every module it imports, sorted by path.
+    pub diagnostics: Vec<Diagnostic>,
+    /// `None` if the module didn't make it as far as type checking.
+    pub module: Option<LoadedModule>,
+}
+
+/// What compiling a module found.
+#[derive(Debug)]
+pub struct Compiled<'a> {
+    /// The problems in the module and every module it imports, sorted by path.
+    pub diagnostics: Vec<Diagnostic>,
+    /// `None` if problems kept the module from getting as far as the mono IR.
+    pub module: Option<MonomorphizedModule<'a>>,
+}
+
+/// Type-check the module at `path`, along with every module it imports.
+pub fn check_file(path: &Path, options: &Options) -> Checked {
+    let arena = Bump::new();
+    let loaded = roc_load::load_and_typecheck(
+        &arena,
+        path.to_path_buf(),
+        roc_cache_dir(options),
+        load_config(options, ExecutionMode::Check),
+    );
+
+    checked(path, loaded, options)
+}
+
+/// Type-check a module given as a string, e.g. the unsaved contents of an editor.
+/// `path` is where the module is (or would be) on disk.
+pub fn check_str(path: &Path, src: &str, options: &Options) -> Checked {
+    let arena = Bump::new();
+    let loaded = roc_load::load_and_typecheck_str(
+        &arena,
+        path.to_path_buf(),
+        arena.alloc_str(src),
+        src_dir(path, options),
+        options.target_info,
+        FunctionKind::LambdaSet,
+        render_target(options),
+        roc_cache_dir(options),
+        options.palette.unwrap_or(DEFAULT_PALETTE),
+    );
+
+    checked(path, loaded, options)
+}
+
+/// Compile a module given as a string into the mono IR, which is what the backends generate
+/// code from. `path` is where the module is (or would be) on disk.
+pub fn compile_str<'a>(arena: &'a Bump, path: &Path, src: &str, options: &Options) -> Compiled<'a> {
+    let loaded = roc_load::load_and_monomorphize_from_str(
+        arena,
+        path.to_path_buf(),
+        arena.alloc_str(src),
+        src_dir(path, options),
+        roc_cache_dir(options),
+        load_config(options, ExecutionMode::Executable),
+    );
+
+    match loaded {
+        Ok(mut module) => Compiled {
+            diagnostics: module_diagnostics(
+                &module.sources,
+                &module.interns,
+                &mut module.can_problems,
+                &mut module.type_problems,
+                options.palette,
+            ),
+            module: Some(module),
+        },
+        Err(LoadMonomorphizedError::ErrorModule(mut module)) => Compiled {
+            diagnostics: module_diagnostics(
+                &module.sources,
+                &module.interns,
+                &mut module.can_problems,
+                &mut module.type_problems,
+                options.palette,
+            ),
+            module: None,
+        },
+        Err(LoadMonomorphizedError::LoadingProblem(problem)) => Compiled {
+            diagnostics: vec![loading_problem_diagnostic(path, problem, options)],
+            module: None,
+        },
+    }
+}
+
+fn checked(
+    path: &Path,
+    loaded: Result<LoadedModule, LoadingProblem<'_>>,
+    options: &Options,
+) -> Checked {
+    match loaded {
+        Ok(mut module) => Checked {
+            diagnostics: module_diagnostics(
+                &module.sources,
+                &module.interns,
+                &mut module.can_problems,
+                &mut module.type_problems,
+                options.palette,
+            ),
+            module: Some(module),
+        },
+        Err(problem) => Checked {
+            diagnostics: vec![loading_problem_diagnostic(path, problem, options)],
+            module: None,
+        },
+    }
+}
+
+fn load_config(options: &Options, exec_mode: ExecutionMode) -> LoadConfig {
+    LoadConfig {
+        target_info: options.target_info,
+        function_kind: FunctionKind::LambdaSet,
+        render: render_target(options),
+        palette: options.palette.unwrap_or(DEFAULT_PALETTE),
+        threading: options.threading,
+        exec_mode,
+        warning_levels: Default::default(),
+        explain_types: false,
+    }
+}
+
+fn render_target(options: &Options) -> RenderTarget {
+    match options.palette {
+        Some(_) => RenderTarget::ColorTerminal,
+        None => RenderTarget::Generic,
+    }
+}
+
+fn roc_cache_dir(options: &Options) -> RocCacheDir<'_> {
+    match &options.cache_dir {
+        Some(dir) => RocCacheDir::Persistent(dir),
+        None => RocCacheDir::Disallowed,
+    }
+}
+
+fn src_dir(path: &Path, options: &Options) -> PathBuf {
+    options.src_dir.clone().unwrap_or_else(|| {
+        path.parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf()
+    })
+}
+
+/// A problem that stopped the compiler before there were any modules to report problems in.
+fn loading_problem_diagnostic(
+    path: &Path,
+    problem: LoadingProblem<'_>,
+    options: &Options,
+) -> Diagnostic {
+    let message = match problem {
+        LoadingProblem::FormattedReport(report) if options.palette.is_some() => report,
+        LoadingProblem::FormattedReport(report) => strip_colors(&report).trim().to_string(),
+        other => format!("{other:?}"),
+    };
+
+    Diagnostic {
+        path: path.to_path_buf(),
+        region: None,
+        severity: Severity::Fatal,
+        title: String::new(),
+        message,
+    }
+}
+
+fn module_diagnostics(
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    palette: Option<Palette>,
+) -> Vec<Diagnostic> {
+    let mut sources: Vec<_> = sources.iter().collect();
+    let mut diagnostics = Vec::new();
+
+    sources.sort_by(|(_, (a, _)), (_, (b, _))| a.cmp(b));
+
+    for (home, (path, src)) in sources {
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let alloc = RocDocAllocator::new(&src_lines, *home, interns);
+
+        for problem in can_problems.remove(home).unwrap_or_default() {
+            let region = problem.region();
+            let report = can_problem(&alloc, &lines, path.clone(), problem);
+
+            diagnostics.push(diagnostic(
+                path,
+                report,
+                region,
+                &alloc,
+                &lines,
+                palette.as_ref(),
+            ));
+        }
+
+        for problem in type_problems.remove(home).unwrap_or_default() {
+            let region = problem.region();
+
+            if let Some(report) = type_problem(&alloc, &lines, path.clone(), problem) {
+                diagnostics.push(diagnostic(
+                    path,
+                    report,
+                    region,
+                    &alloc,
+                    &lines,
+                    palette.as_ref(),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn diagnostic<'b>(
+    path: &Path,
+    report: Report<'b>,
+    region: Option<Region>,
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    palette: Option<&'b Palette>,
+) -> Diagnostic {
+    let title = report.title.clone();
+    let severity = report.severity;
+    let mut message = String::new();
+
+    match palette {
+        Some(palette) => report.render_color_terminal(&mut message, alloc, palette),
+        None => {
+            // The title and path have fields of their own.
+            Report {
+                title: String::new(),
+                ..report
+            }
+            .render_ci(&mut message, alloc);
+
+            message = message.trim().to_string();
+        }
+    }
+
+    Diagnostic {
+        path: path.to_path_buf(),
+        region: region.map(|region| lines.convert_region(region)),
+        severity,
+        title,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    fn options() -> Options {
+        Options {
+            cache_dir: None,
+            threading: Threading::Single,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn check_str_reports_problems() {
+        let src = indoc!(
+            r#"
+            interface Test exposes [answer] imports []
+
+            answer : Str
+            answer = 42
+            "#
+        );
+        let checked = check_str(Path::new("Test.roc"), src, &options());
+
+        assert!(checked.module.is_some());
+        assert_eq!(checked.diagnostics.len(), 1);
+
+        let diagnostic = &checked.diagnostics[0];
+
+        assert_eq!(diagnostic.title, "TYPE MISMATCH");
+        assert!(diagnostic.is_error());
+        assert_eq!(diagnostic.region.map(|region| region.start().line), Some(3));
+    }
+
+    #[test]
+    fn compile_str_gets_to_mono() {
+        let src = indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 1 + 2
+            "#
+        );
+        let arena = Bump::new();
+        let compiled = compile_str(&arena, Path::new("Test.roc"), src, &options());
+
+        assert!(compiled.diagnostics.is_empty());
+        assert!(compiled.module.is_some());
+    }
+}
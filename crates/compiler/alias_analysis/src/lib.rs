@@ -5,8 +5,9 @@ use bumpalo::Bump;
 use morphic_lib::TypeContext;
 use morphic_lib::{
     BlockExpr, BlockId, CalleeSpecVar, ConstDefBuilder, ConstName, EntryPointName, ExprContext,
-    FuncDef, FuncDefBuilder, FuncName, ModDefBuilder, ModName, ProgramBuilder, Result,
-    TypeDefBuilder, TypeId, TypeName, UpdateModeVar, ValueId,
+    FuncDef, FuncDefBuilder, FuncName, FuncSpec, FuncSpecSolutions, ModDefBuilder, ModName,
+    ModSolutions, ProgramBuilder, Result, TypeDefBuilder, TypeId, TypeName, UpdateMode,
+    UpdateModeVar, ValueId,
 };
 use roc_collections::all::{MutMap, MutSet};
 use roc_error_macros::internal_error;
@@ -354,6 +355,204 @@ where
     }
 }
 
+/// A call that may have to copy a list instead of updating it in place, because the
+/// alias analysis couldn't prove that nothing else refers to it. Whether it actually
+/// copies is decided at runtime, by checking the reference count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissedInPlaceUpdate {
+    /// The function that makes the call. This is never a builtin; calls that builtins
+    /// make are attributed to the user function that called the builtin.
+    pub caller: Symbol,
+    pub callee: InPlaceCallee,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InPlaceCallee {
+    /// A lowlevel that was inlined into the caller, e.g. the one behind `List.set`
+    LowLevel(LowLevel),
+    /// A builtin function that does such a lowlevel somewhere inside it
+    Builtin(Symbol),
+}
+
+/// Every call in a user function (in any of its specializations) that may copy a list
+/// instead of updating it in place, given the solutions from [spec_program].
+pub fn missed_in_place_updates<'a, 'r, I>(
+    mod_solutions: &ModSolutions,
+    procs: I,
+) -> Vec<MissedInPlaceUpdate>
+where
+    'a: 'r,
+    I: Iterator<Item = &'r Proc<'a>>,
+{
+    let procs: MutMap<[u8; SIZE], &Proc> =
+        procs.map(|proc| (func_name_bytes(proc), proc)).collect();
+    let mut env = MissedUpdatesEnv {
+        mod_solutions,
+        procs: &procs,
+        builtins: MutMap::default(),
+    };
+    let mut missed = Vec::new();
+
+    for (name_bytes, proc) in procs.iter() {
+        let caller = proc.name.name();
+
+        if caller.module_id().is_builtin() {
+            continue;
+        }
+
+        let func_solutions = match mod_solutions.func_solutions(FuncName(name_bytes)) {
+            Ok(func_solutions) => func_solutions,
+            Err(_) => continue,
+        };
+
+        for spec in func_solutions.specs() {
+            let spec_solutions = func_solutions.spec(spec).unwrap();
+            let mut callees = Vec::new();
+
+            env.copying_callees(spec_solutions, &proc.body, &mut callees);
+
+            for callee in callees {
+                let update = MissedInPlaceUpdate { caller, callee };
+
+                if !missed.contains(&update) {
+                    missed.push(update);
+                }
+            }
+        }
+    }
+
+    missed
+}
+
+/// The lowlevels that alias analysis decides the update mode of.
+fn updates_in_place(op: LowLevel) -> bool {
+    use LowLevel::*;
+
+    matches!(
+        op,
+        ListReplaceUnsafe | ListSwap | ListReserve | ListReleaseExcessCapacity | ListAppendUnsafe
+    )
+}
+
+struct MissedUpdatesEnv<'s, 'a> {
+    mod_solutions: &'s ModSolutions,
+    procs: &'s MutMap<[u8; SIZE], &'s Proc<'a>>,
+    /// Whether a specialization of a builtin may copy
+    builtins: MutMap<([u8; SIZE], FuncSpec), bool>,
+}
+
+impl<'s, 'a> MissedUpdatesEnv<'s, 'a> {
+    fn copying_callees(
+        &mut self,
+        spec_solutions: &FuncSpecSolutions,
+        stmt: &Stmt<'a>,
+        callees: &mut Vec<InPlaceCallee>,
+    ) {
+        use Stmt::*;
+
+        match stmt {
+            Let(_, expr, _, continuation) => {
+                if let Expr::Call(Call { call_type, .. }) = expr {
+                    match call_type {
+                        CallType::LowLevel { op, update_mode } if updates_in_place(*op) => {
+                            let bytes = update_mode.to_bytes();
+
+                            if let Ok(UpdateMode::Immutable) =
+                                spec_solutions.update_mode(UpdateModeVar(&bytes))
+                            {
+                                callees.push(InPlaceCallee::LowLevel(*op));
+                            }
+                        }
+                        CallType::ByName {
+                            name,
+                            ret_layout,
+                            arg_layouts,
+                            specialization_id,
+                        } if name.name().module_id().is_builtin() => {
+                            let name_bytes = func_name_bytes_help(
+                                name.name(),
+                                arg_layouts.iter().copied(),
+                                name.niche(),
+                                *ret_layout,
+                            );
+                            let bytes = specialization_id.to_bytes();
+
+                            if let Ok(spec) = spec_solutions.callee_spec(CalleeSpecVar(&bytes)) {
+                                if self.builtin_may_copy(name_bytes, spec) {
+                                    callees.push(InPlaceCallee::Builtin(name.name()));
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                self.copying_callees(spec_solutions, continuation, callees);
+            }
+            Switch {
+                branches,
+                default_branch,
+                ..
+            } => {
+                for (_, _, branch) in branches.iter() {
+                    self.copying_callees(spec_solutions, branch, callees);
+                }
+
+                self.copying_callees(spec_solutions, default_branch.1, callees);
+            }
+            Refcounting(_, continuation)
+            | Expect {
+                remainder: continuation,
+                ..
+            }
+            | ExpectFx {
+                remainder: continuation,
+                ..
+            }
+            | Dbg {
+                remainder: continuation,
+                ..
+            } => self.copying_callees(spec_solutions, continuation, callees),
+            Join {
+                body, remainder, ..
+            } => {
+                self.copying_callees(spec_solutions, body, callees);
+                self.copying_callees(spec_solutions, remainder, callees);
+            }
+            Ret(_) | Jump(_, _) | Crash(_, _) => {}
+        }
+    }
+
+    fn builtin_may_copy(&mut self, name_bytes: [u8; SIZE], spec: FuncSpec) -> bool {
+        if let Some(may_copy) = self.builtins.get(&(name_bytes, spec)) {
+            return *may_copy;
+        }
+
+        // builtins can be recursive, so assume this one doesn't copy while looking inside it
+        self.builtins.insert((name_bytes, spec), false);
+
+        let (mod_solutions, procs) = (self.mod_solutions, self.procs);
+        let spec_solutions = mod_solutions
+            .func_solutions(FuncName(&name_bytes))
+            .and_then(|func_solutions| func_solutions.spec(&spec));
+
+        let may_copy = match (procs.get(&name_bytes), spec_solutions) {
+            (Some(proc), Ok(spec_solutions)) => {
+                let mut callees = Vec::new();
+
+                self.copying_callees(spec_solutions, &proc.body, &mut callees);
+
+                !callees.is_empty()
+            }
+            _ => false,
+        };
+
+        self.builtins.insert((name_bytes, spec), may_copy);
+
+        may_copy
+    }
+}
+
 /// if you want an "escape hatch" which allows you construct "best-case scenario" values
 /// of an arbitrary type in much the same way that 'unknown_with' allows you to construct
 /// "worst-case scenario" values of an arbitrary type, you can use the following terrible hack:
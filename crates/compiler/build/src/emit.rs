@@ -0,0 +1,283 @@
+//! `roc build --emit`, which writes out what each stage of the compiler made of an app,
+//! along with the binary.
+use crate::program::{find_def_locations, mono_entry_point};
+use bumpalo::Bump;
+use inkwell::module::Module;
+use roc_alias_analysis::InPlaceCallee;
+use roc_can::debug::{pretty_print_declarations, PPCtx};
+use roc_collections::all::MutMap;
+use roc_error_macros::internal_error;
+use roc_gen_llvm::llvm::build::DefLocation;
+use roc_load::{ExecutionMode, LoadConfig, MonomorphizedModule};
+use roc_module::ident::ModuleName;
+use roc_module::low_level::LowLevel;
+use roc_module::symbol::{Interns, ModuleId};
+use roc_mono::ir::{OptLevel, Proc};
+use roc_packaging::cache::RocCacheDir;
+use roc_parse::module::{parse_header, parse_module_defs_recovering};
+use roc_parse::state::State;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The directory next to the app's main .roc file that the emitted files are written to
+pub const EMIT_DIR_NAME: &str = "roc-emit";
+
+/// How wide the emitted IR is allowed to get before it wraps
+const EMIT_WIDTH: usize = 100;
+
+/// What to write out besides the binary
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Emit {
+    /// Print the calls that may copy a list instead of updating it in place
+    pub borrow_report: bool,
+    /// Write each module's parse AST to `<module>.parse`
+    pub parse: bool,
+    /// Write each module's canonical AST to `<module>.can`
+    pub can: bool,
+    /// Write the mono IR of each module's specializations, after all of the mono passes,
+    /// to `<module>.mono`
+    pub mono: bool,
+    /// Write the app's optimized LLVM IR to `<app>.ll`
+    pub llvm_ir: bool,
+    /// Print how much of the code and data in the object files the linker left out as unused
+    pub dead_code_report: bool,
+}
+
+/// Write the parse and canonical ASTs of every module the app is made of. These come from
+/// loading the app again just for this, because building it doesn't keep them around.
+/// Problems aren't reported here, since the build that follows reports them anyway.
+pub(crate) fn write_parse_and_can(
+    app_module_path: &Path,
+    emit: Emit,
+    roc_cache_dir: RocCacheDir<'_>,
+    load_config: &LoadConfig,
+) {
+    let arena = Bump::new();
+    let load_config = LoadConfig {
+        exec_mode: ExecutionMode::Check,
+        warning_levels: Default::default(),
+        ..*load_config
+    };
+
+    let loaded = match roc_load::load_and_typecheck(
+        &arena,
+        app_module_path.to_path_buf(),
+        roc_cache_dir,
+        load_config,
+    ) {
+        Ok(loaded) => loaded,
+        Err(_) => return,
+    };
+
+    for (module_id, (path, src)) in loaded.sources.iter() {
+        if module_id.is_builtin() {
+            continue;
+        }
+
+        let name = module_file_name(*module_id, &loaded.interns, Some(path));
+
+        if emit.parse {
+            let contents = match parse_header(&arena, State::new(src.as_bytes())) {
+                Ok((header, state)) => {
+                    let (defs, problems) = parse_module_defs_recovering(&arena, state);
+
+                    format!("{header:#?}\n\n{defs:#?}\n\n{problems:#?}\n")
+                }
+                Err(problem) => format!("{problem:#?}\n"),
+            };
+
+            write_emitted(app_module_path, &format!("{name}.parse"), &contents);
+        }
+
+        if emit.can {
+            if let Some(declarations) = loaded.declarations_by_id.get(module_id) {
+                let ctx = PPCtx {
+                    home: *module_id,
+                    interns: &loaded.interns,
+                    print_lambda_names: true,
+                };
+
+                write_emitted(
+                    app_module_path,
+                    &format!("{name}.can"),
+                    &pretty_print_declarations(&ctx, declarations),
+                );
+            }
+        }
+    }
+}
+
+/// Write the final mono IR, one file per module that has specializations in it.
+/// That includes builtin modules, e.g. `List.mono` for the specializations of `List.map`.
+pub(crate) fn write_mono(loaded: &MonomorphizedModule, app_module_path: &Path) {
+    let mut procs_by_module: MutMap<ModuleId, Vec<&Proc>> = MutMap::default();
+
+    for proc in loaded.procedures.values() {
+        procs_by_module
+            .entry(proc.name.name().module_id())
+            .or_default()
+            .push(proc);
+    }
+
+    for (module_id, mut procs) in procs_by_module {
+        // so that rebuilding the same app makes the same file
+        procs.sort_by_cached_key(|proc| {
+            (
+                proc.name.name().as_str(&loaded.interns).to_string(),
+                proc.to_pretty(&loaded.layout_interner, EMIT_WIDTH, false),
+            )
+        });
+
+        let path = loaded
+            .sources
+            .get(&module_id)
+            .map(|(path, _)| path.as_path());
+        let name = module_file_name(module_id, &loaded.interns, path);
+        let contents = roc_mono::ir::procs_to_pretty_with_names(
+            procs,
+            &loaded.layout_interner,
+            &loaded.interns,
+            EMIT_WIDTH,
+        );
+
+        write_emitted(app_module_path, &format!("{name}.mono"), &contents);
+    }
+}
+
+/// Where to write the app's LLVM IR, making sure the directory for it exists.
+pub(crate) fn llvm_ir_path(app_module_path: &Path) -> Option<PathBuf> {
+    let name = app_module_path.file_stem()?.to_string_lossy();
+
+    emitted_path(app_module_path, &format!("{name}.ll"))
+}
+
+pub(crate) fn write_llvm_ir(module: &Module, path: &Path) {
+    match module.print_to_file(path) {
+        Ok(()) => println!("Wrote {}", path.display()),
+        Err(err) => eprintln!("I couldn't write {}: {err}", path.display()),
+    }
+}
+
+/// Print every call in the app that may copy a list instead of updating it in place,
+/// along with the definition it's in. This runs the alias analysis the way an
+/// `--optimize` build does, because other builds don't run it at all, and instead
+/// check the reference count at runtime every time.
+pub(crate) fn print_borrow_report<'a>(arena: &'a Bump, loaded: &MonomorphizedModule<'a>) {
+    let solutions = match roc_alias_analysis::spec_program(
+        arena,
+        &loaded.layout_interner,
+        OptLevel::Optimize,
+        mono_entry_point(&loaded.entry_point),
+        loaded.procedures.values(),
+        loaded
+            .host_exposed_lambda_sets
+            .iter()
+            .map(|(_, _, hels)| hels),
+    ) {
+        Err(e) => internal_error!("Error in alias analysis: {e}"),
+        Ok(solutions) => solutions,
+    };
+    let mod_solutions = solutions
+        .mod_solutions(roc_alias_analysis::MOD_APP)
+        .unwrap();
+
+    let missed =
+        roc_alias_analysis::missed_in_place_updates(mod_solutions, loaded.procedures.values());
+    let def_locations = find_def_locations(&loaded.def_regions, &loaded.sources);
+    let interns = &loaded.interns;
+
+    let mut lines: Vec<(Option<&DefLocation>, String)> = missed
+        .iter()
+        .map(|update| {
+            let callee = match update.callee {
+                InPlaceCallee::LowLevel(LowLevel::ListReplaceUnsafe) => {
+                    "List.set (or List.replace)".to_string()
+                }
+                InPlaceCallee::LowLevel(LowLevel::ListSwap) => "List.swap".to_string(),
+                InPlaceCallee::LowLevel(LowLevel::ListReserve) => "List.reserve".to_string(),
+                InPlaceCallee::LowLevel(LowLevel::ListReleaseExcessCapacity) => {
+                    "List.releaseExcessCapacity".to_string()
+                }
+                InPlaceCallee::LowLevel(LowLevel::ListAppendUnsafe) => "List.append".to_string(),
+                InPlaceCallee::LowLevel(op) => format!("{op:?}"),
+                InPlaceCallee::Builtin(symbol) => format!(
+                    "{}.{}",
+                    symbol.module_string(interns),
+                    symbol.as_str(interns)
+                ),
+            };
+            let location = def_locations.get(&update.caller);
+            let caller = match location {
+                Some(DefLocation { path, line }) => format!(
+                    "{} ({}:{})",
+                    update.caller.as_str(interns),
+                    path.display(),
+                    line + 1
+                ),
+                None => format!(
+                    "{}.{}",
+                    update.caller.module_string(interns),
+                    update.caller.as_str(interns)
+                ),
+            };
+
+            (location, format!("{callee} in {caller}"))
+        })
+        .collect();
+
+    lines.sort_by(|(a, a_line), (b, b_line)| {
+        let key = |location: &Option<&DefLocation>| location.map(|loc| (&loc.path, loc.line));
+
+        key(a).cmp(&key(b)).then_with(|| a_line.cmp(b_line))
+    });
+
+    if lines.is_empty() {
+        println!("\nNo calls in this app may copy a list instead of updating it in place.\n");
+    } else {
+        println!("\nThese calls may copy a list instead of updating it in place, because something else may still refer to it:\n");
+
+        for (_, line) in lines {
+            println!("    {line}");
+        }
+
+        println!("\nWhether they copy is decided at runtime, by checking the list's reference count. Making sure nothing else uses the list after the call (e.g. not keeping the old one around) lets them update it in place.\n");
+    }
+}
+
+/// The app module is named after its file, since its module name is the same for every app.
+fn module_file_name(module_id: ModuleId, interns: &Interns, path: Option<&Path>) -> String {
+    let module_name = interns
+        .module_ids
+        .get_name(module_id)
+        .map(|name| name.as_str().to_string())
+        .unwrap_or_else(|| format!("{module_id:?}"));
+
+    match path.and_then(Path::file_stem) {
+        Some(stem) if module_name == ModuleName::APP => stem.to_string_lossy().into_owned(),
+        _ => module_name,
+    }
+}
+
+fn emitted_path(app_module_path: &Path, file_name: &str) -> Option<PathBuf> {
+    let dir = app_module_path.with_file_name(EMIT_DIR_NAME);
+
+    match fs::create_dir_all(&dir) {
+        Ok(()) => Some(dir.join(file_name)),
+        Err(err) => {
+            eprintln!("I couldn't create {}: {err}", dir.display());
+
+            None
+        }
+    }
+}
+
+fn write_emitted(app_module_path: &Path, file_name: &str, contents: &str) {
+    let Some(path) = emitted_path(app_module_path, file_name) else {
+        return;
+    };
+
+    match fs::write(&path, contents) {
+        Ok(()) => println!("Wrote {}", path.display()),
+        Err(err) => eprintln!("I couldn't write {}: {err}", path.display()),
+    }
+}
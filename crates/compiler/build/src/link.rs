@@ -25,6 +25,32 @@ pub enum LinkingStrategy {
     Additive,
 }
 
+/// How many bytes of code and data an object file or executable has, not counting symbol
+/// tables, relocations, debug info and so on. This is `None` for files that `object` can't
+/// parse, like the LLVM bitcode that's linked into wasm apps.
+pub(crate) fn code_and_data_size(path: &Path) -> Option<u64> {
+    use object::{Object, ObjectSection, SectionKind};
+
+    let bytes = fs::read(path).ok()?;
+    let file = object::File::parse(bytes.as_slice()).ok()?;
+
+    let size = file
+        .sections()
+        .filter(|section| {
+            matches!(
+                section.kind(),
+                SectionKind::Text
+                    | SectionKind::Data
+                    | SectionKind::ReadOnlyData
+                    | SectionKind::ReadOnlyString
+            )
+        })
+        .map(|section| section.size())
+        .sum();
+
+    Some(size)
+}
+
 /// input_paths can include the host as well as the app. e.g. &["host.o", "roc_app.o"]
 pub fn link(
     target: &Triple,
@@ -32,6 +58,11 @@ pub fn link(
     input_paths: &[&str],
     link_type: LinkType,
 ) -> io::Result<(Child, PathBuf)> {
+    if let LinkType::StaticLib = link_type {
+        // An archive of object files looks the same no matter what it's for.
+        return link_static_lib(target, output_path, input_paths);
+    }
+
     match target {
         Triple {
             architecture: Architecture::Wasm32,
@@ -999,7 +1030,9 @@ fn link_linux(
                 output_path,
             )
         }
-        LinkType::None => internal_error!("link_linux should not be called with link type of none"),
+        LinkType::None | LinkType::StaticLib => {
+            internal_error!("link_linux should not be called with link type of {link_type:?}")
+        }
     };
 
     let env_path = env::var("PATH").unwrap_or_else(|_| "".to_string());
@@ -1061,7 +1094,8 @@ fn link_macos(
     link_type: LinkType,
 ) -> io::Result<(Child, PathBuf)> {
     let (link_type_args, output_path) = match link_type {
-        LinkType::Executable => (vec!["-execute"], output_path),
+        // -dead_strip is how Apple's ld spells --gc-sections
+        LinkType::Executable => (vec!["-execute", "-dead_strip"], output_path),
         LinkType::Dylib => {
             let mut output_path = output_path;
 
@@ -1069,7 +1103,9 @@ fn link_macos(
 
             (vec!["-dylib", "-undefined", "dynamic_lookup"], output_path)
         }
-        LinkType::None => internal_error!("link_macos should not be called with link type of none"),
+        LinkType::None | LinkType::StaticLib => {
+            internal_error!("link_macos should not be called with link type of {link_type:?}")
+        }
     };
 
     let arch = match target.architecture {
@@ -1086,11 +1122,6 @@ fn link_macos(
         .env_clear()
         .args(&link_type_args)
         .args([
-            // NOTE: we don't do --gc-sections on macOS because the default
-            // macOS linker doesn't support it, but it's a performance
-            // optimization, so if we ever switch to a different linker,
-            // we'd like to re-enable it on macOS!
-            // "--gc-sections",
             "-arch",
             &arch,
             // Suppress warnings, because otherwise it prints:
@@ -1268,10 +1299,46 @@ fn link_windows(
 
             Ok((child, output_path))
         }
-        LinkType::None => todo!(),
+        LinkType::None | LinkType::StaticLib => {
+            internal_error!("link_windows should not be called with link type of {link_type:?}")
+        }
     }
 }
 
+/// Bundle the object files into a static library (e.g. `libapp.a` or `app.lib`), which
+/// can then be linked into an application written in another language.
+///
+/// On Windows, `zig build-lib` archives with the same COFF librarian as `llvm-lib`, so this
+/// makes an `app.lib` that MSVC's `link.exe` accepts.
+fn link_static_lib(
+    target: &Triple,
+    output_path: PathBuf,
+    input_paths: &[&str],
+) -> io::Result<(Child, PathBuf)> {
+    let os = roc_target::OperatingSystem::from(target.operating_system);
+    let mut output_path = output_path;
+
+    output_path.set_extension(os.static_library_file_ext());
+
+    let target_str = match target.architecture {
+        Architecture::Wasm32 => "wasm32-wasi-musl",
+        _ => get_target_str(target),
+    };
+
+    // Without -dynamic, `zig build-lib` produces a static library.
+    let child = zig()
+        .args(["build-lib"])
+        .args(input_paths)
+        .args([
+            "-target",
+            target_str,
+            &format!("-femit-bin={}", output_path.to_str().unwrap()),
+        ])
+        .spawn()?;
+
+    Ok((child, output_path))
+}
+
 pub fn llvm_module_to_dylib(
     module: &inkwell::module::Module,
     target: &Triple,
@@ -1298,7 +1365,7 @@ pub fn llvm_module_to_dylib(
 
     // Link app.o into a dylib - e.g. app.so or app.dylib
     let (mut child, dylib_path) = link(
-        &Triple::host(),
+        target,
         app_o_file.clone(),
         &[app_o_file.to_str().unwrap()],
         LinkType::Dylib,
@@ -1,18 +1,24 @@
+use crate::emit::{self, Emit};
 use crate::link::{
-    legacy_host_filename, link, preprocess_host_wasm32, rebuild_host, LinkType, LinkingStrategy,
+    code_and_data_size, legacy_host_filename, link, preprocess_host_wasm32, rebuild_host, LinkType,
+    LinkingStrategy,
 };
 use bumpalo::Bump;
 use inkwell::memory_buffer::MemoryBuffer;
+use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
 use roc_gen_dev::AssemblyBackendMode;
-use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
+use roc_gen_llvm::llvm::build::{module_from_builtins, DefLocation, LlvmBackendMode};
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_load::{
     EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig, LoadMonomorphizedError,
-    LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
+    LoadedModule, LoadingProblem, ModuleTiming, MonomorphizedModule, Threading,
 };
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::ir::{OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
+use roc_problem::WarningLevels;
+use roc_region::all::{LineInfo, Region};
 use roc_reporting::{
     cli::{report_problems, Problems},
     report::{RenderTarget, DEFAULT_PALETTE},
@@ -46,6 +52,7 @@ pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Proble
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        &loaded.warning_levels,
     )
 }
 
@@ -56,6 +63,7 @@ pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        &loaded.warning_levels,
     )
 }
 
@@ -87,6 +95,8 @@ pub struct CodeGenOptions {
     pub backend: CodeGenBackend,
     pub opt_level: OptLevel,
     pub emit_debug_info: bool,
+    /// What to write out besides the binary
+    pub emit: Emit,
 }
 
 type GenFromMono<'a> = (CodeObject, CodeGenTiming, ExpectMetadata<'a>);
@@ -101,6 +111,8 @@ pub fn gen_from_mono_module<'a>(
     preprocessed_host_path: &Path,
     wasm_dev_stack_bytes: Option<u32>,
 ) -> GenFromMono<'a> {
+    let _entered = roc_tracing::info_span!("code gen").entered();
+
     let path = roc_file_path;
     let debug = code_gen_options.emit_debug_info;
     let opt = code_gen_options.opt_level;
@@ -123,7 +135,22 @@ pub fn gen_from_mono_module<'a>(
             backend_mode,
         ),
         CodeGenBackend::Llvm(backend_mode) => {
-            gen_from_mono_module_llvm(arena, loaded, path, target, opt, backend_mode, debug)
+            let llvm_ir_path = if code_gen_options.emit.llvm_ir {
+                emit::llvm_ir_path(path)
+            } else {
+                None
+            };
+
+            gen_from_mono_module_llvm(
+                arena,
+                loaded,
+                path,
+                target,
+                opt,
+                backend_mode,
+                debug,
+                llvm_ir_path.as_deref(),
+            )
         }
     }
 }
@@ -131,6 +158,7 @@ pub fn gen_from_mono_module<'a>(
 // TODO how should imported modules factor into this? What if those use builtins too?
 // TODO this should probably use more helper functions
 // TODO make this polymorphic in the llvm functions so it can be reused for another backend.
+#[allow(clippy::too_many_arguments)]
 fn gen_from_mono_module_llvm<'a>(
     arena: &'a bumpalo::Bump,
     loaded: MonomorphizedModule<'a>,
@@ -139,6 +167,7 @@ fn gen_from_mono_module_llvm<'a>(
     opt_level: OptLevel,
     backend_mode: LlvmBackendMode,
     emit_debug_info: bool,
+    llvm_ir_path: Option<&Path>,
 ) -> GenFromMono<'a> {
     use crate::target::{self, convert_opt_level};
     use inkwell::attributes::{Attribute, AttributeLoc};
@@ -147,6 +176,7 @@ fn gen_from_mono_module_llvm<'a>(
     use inkwell::targets::{FileType, RelocMode};
 
     let all_code_gen_start = Instant::now();
+    let generate_ir_span = roc_tracing::info_span!("generate LLVM IR").entered();
 
     // Generate the binary
     let target_info = roc_target::TargetInfo::from(target);
@@ -192,6 +222,23 @@ fn gen_from_mono_module_llvm<'a>(
     let (dibuilder, compile_unit) = roc_gen_llvm::llvm::build::Env::new_debug_info(module);
     let (mpm, _fpm) = roc_gen_llvm::llvm::build::construct_optimization_passes(module, opt_level);
 
+    let def_locations = if emit_debug_info {
+        if module.get_flag("Debug Info Version").is_none() {
+            // Without this, LLVM drops all the debug info.
+            module.add_basic_value_flag(
+                "Debug Info Version",
+                inkwell::module::FlagBehavior::Warning,
+                context
+                    .i32_type()
+                    .const_int(inkwell::debug_info::debug_metadata_version() as u64, false),
+            );
+        }
+
+        find_def_locations(&loaded.def_regions, &loaded.sources)
+    } else {
+        MutMap::default()
+    };
+
     // Compile and add all the Procs before adding main
     let env = roc_gen_llvm::llvm::build::Env {
         arena,
@@ -210,25 +257,14 @@ fn gen_from_mono_module_llvm<'a>(
             .keys()
             .copied()
             .collect(),
+        def_locations,
     };
 
     // does not add any externs for this mode (we have a host) but cleans up some functions around
     // expects that would confuse the surgical linker
     add_default_roc_externs(&env);
 
-    let entry_point = match loaded.entry_point {
-        EntryPoint::Executable {
-            exposed_to_host,
-            platform_path: _,
-        } => {
-            // TODO support multiple of these!
-            debug_assert_eq!(exposed_to_host.len(), 1);
-            let (symbol, layout) = exposed_to_host[0];
-
-            roc_mono::ir::EntryPoint::Single(SingleEntryPoint { symbol, layout })
-        }
-        EntryPoint::Test => roc_mono::ir::EntryPoint::Expects { symbols: &[] },
-    };
+    let entry_point = mono_entry_point(&loaded.entry_point);
 
     roc_gen_llvm::llvm::build::build_procedures(
         &env,
@@ -245,10 +281,15 @@ fn gen_from_mono_module_llvm<'a>(
     let generate_final_ir = all_code_gen_start.elapsed();
     let code_gen_object_start = Instant::now();
 
+    drop(generate_ir_span);
+    let _entered = roc_tracing::info_span!("optimize and emit object").entered();
+
     env.dibuilder.finalize();
 
-    // we don't use the debug info, and it causes weird errors.
-    module.strip_debug_info();
+    if !emit_debug_info {
+        // we don't use the debug info, and it causes weird errors.
+        module.strip_debug_info();
+    }
 
     // Uncomment this to see the module's optimized LLVM instruction output:
     // env.module.print_to_stderr();
@@ -267,11 +308,13 @@ fn gen_from_mono_module_llvm<'a>(
         );
     }
 
+    if let Some(llvm_ir_path) = llvm_ir_path {
+        emit::write_llvm_ir(env.module, llvm_ir_path);
+    }
+
     // Uncomment this to see the module's optimized LLVM instruction output:
     // env.module.print_to_stderr();
 
-    // annotate the LLVM IR output with debug info
-    // so errors are reported with the line number of the LLVM source
     let memory_buffer = if cfg!(feature = "sanitizers") && std::env::var("ROC_SANITIZERS").is_ok() {
         let dir = tempfile::tempdir().unwrap();
         let dir = dir.into_path();
@@ -361,65 +404,6 @@ fn gen_from_mono_module_llvm<'a>(
 
         assert!(bc_to_object.status.success(), "{bc_to_object:#?}");
 
-        MemoryBuffer::create_from_file(&app_o_file).expect("memory buffer creation works")
-    } else if emit_debug_info {
-        module.strip_debug_info();
-
-        let mut app_ll_dbg_file = PathBuf::from(roc_file_path);
-        app_ll_dbg_file.set_extension("dbg.ll");
-
-        let mut app_o_file = PathBuf::from(roc_file_path);
-        app_o_file.set_extension("o");
-
-        use std::process::Command;
-
-        // write the ll code to a file, so we can modify it
-        module.print_to_file(&app_ll_file).unwrap();
-
-        // run the debugir https://github.com/vaivaswatha/debugir tool
-        match Command::new("debugir")
-            .args(["-instnamer", app_ll_file.to_str().unwrap()])
-            .output()
-        {
-            Ok(_) => {}
-            Err(error) => {
-                use std::io::ErrorKind;
-                match error.kind() {
-                    ErrorKind::NotFound => internal_error!(
-                        r"I could not find the `debugir` tool on the PATH, install it from https://github.com/vaivaswatha/debugir"
-                    ),
-                    _ => internal_error!("{:?}", error),
-                }
-            }
-        }
-
-        use target_lexicon::Architecture;
-        match target.architecture {
-            Architecture::X86_64
-            | Architecture::X86_32(_)
-            | Architecture::Aarch64(_)
-            | Architecture::Wasm32 => {
-                // write the .o file. Note that this builds the .o for the local machine,
-                // and ignores the `target_machine` entirely.
-                //
-                // different systems name this executable differently, so we shotgun for
-                // the most common ones and then give up.
-                let ll_to_object = Command::new("llc")
-                    .args([
-                        "-relocation-model=pic",
-                        "-filetype=obj",
-                        app_ll_dbg_file.to_str().unwrap(),
-                        "-o",
-                        app_o_file.to_str().unwrap(),
-                    ])
-                    .output()
-                    .unwrap();
-
-                assert!(ll_to_object.stderr.is_empty(), "{ll_to_object:#?}");
-            }
-            _ => unreachable!(),
-        }
-
         MemoryBuffer::create_from_file(&app_o_file).expect("memory buffer creation works")
     } else {
         // Emit the .o file
@@ -464,6 +448,52 @@ fn gen_from_mono_module_llvm<'a>(
     )
 }
 
+pub(crate) fn mono_entry_point<'a>(entry_point: &EntryPoint<'a>) -> roc_mono::ir::EntryPoint<'a> {
+    match entry_point {
+        EntryPoint::Executable {
+            exposed_to_host,
+            platform_path: _,
+        } => {
+            // TODO support multiple of these!
+            debug_assert_eq!(exposed_to_host.len(), 1);
+            let (symbol, layout) = exposed_to_host[0];
+
+            roc_mono::ir::EntryPoint::Single(SingleEntryPoint { symbol, layout })
+        }
+        EntryPoint::Test => roc_mono::ir::EntryPoint::Expects { symbols: &[] },
+    }
+}
+
+/// Where each top-level def is in its source file, for debug info and reports.
+pub(crate) fn find_def_locations(
+    def_regions: &MutMap<Symbol, Region>,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+) -> MutMap<Symbol, DefLocation> {
+    let lines: MutMap<ModuleId, LineInfo> = sources
+        .iter()
+        .map(|(module_id, (_, src))| (*module_id, LineInfo::new(src)))
+        .collect();
+
+    def_regions
+        .iter()
+        .filter_map(|(symbol, region)| {
+            let (path, _) = sources.get(&symbol.module_id())?;
+            let line = lines
+                .get(&symbol.module_id())?
+                .convert_pos(region.start())
+                .line;
+
+            Some((
+                *symbol,
+                DefLocation {
+                    path: path.clone(),
+                    line,
+                },
+            ))
+        })
+        .collect()
+}
+
 #[cfg(feature = "target-wasm32")]
 fn gen_from_mono_module_dev<'a>(
     arena: &'a bumpalo::Bump,
@@ -637,6 +667,19 @@ fn gen_from_mono_module_dev_assembly<'a>(
     )
 }
 
+fn module_timings_table(timings: &MutMap<ModuleId, ModuleTiming>, interns: &Interns) -> String {
+    ModuleTiming::table(timings.iter().map(|(module_id, module_timing)| {
+        let module_name: &str = interns.module_name(*module_id);
+
+        if module_name.is_empty() {
+            // the App module
+            ("Application Module", module_timing)
+        } else {
+            (module_name, module_timing)
+        }
+    }))
+}
+
 fn report_timing(buf: &mut String, label: &str, duration: Duration) {
     use std::fmt::Write;
 
@@ -654,6 +697,8 @@ pub struct BuiltFile<'a> {
     pub problems: Problems,
     pub total_time: Duration,
     pub expect_metadata: ExpectMetadata<'a>,
+    /// Every .roc file that went into the build, including the platform's.
+    pub sources: Vec<PathBuf>,
 }
 
 pub enum BuildOrdering {
@@ -699,7 +744,7 @@ pub fn handle_error_module(
 
     problems.print_to_stdout(total_time);
 
-    if print_run_anyway_hint {
+    if print_run_anyway_hint && !roc_reporting::cli::json_output_enabled() {
         // If you're running "main.roc" then you can just do `roc run`
         // to re-run the program.
         print!(".\n\nYou can run the program anyway with \x1B[32mroc run");
@@ -716,6 +761,10 @@ pub fn handle_error_module(
 
 pub fn handle_loading_problem(problem: LoadingProblem) -> std::io::Result<i32> {
     match problem {
+        LoadingProblem::FormattedReport(report) if roc_reporting::cli::json_output_enabled() => {
+            roc_reporting::cli::print_formatted_report_json(&mut std::io::stdout(), &report)?;
+            Ok(1)
+        }
         LoadingProblem::FormattedReport(report) => {
             print!("{report}");
             Ok(1)
@@ -733,6 +782,7 @@ pub fn standard_load_config(
     target: &Triple,
     order: BuildOrdering,
     threading: Threading,
+    warning_levels: WarningLevels,
 ) -> LoadConfig {
     let target_info = TargetInfo::from(target);
 
@@ -759,6 +809,8 @@ pub fn standard_load_config(
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode,
+        warning_levels,
+        explain_types: false,
     }
 }
 
@@ -778,6 +830,15 @@ pub fn build_file<'a>(
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let compilation_start = Instant::now();
 
+    if code_gen_options.emit.parse || code_gen_options.emit.can {
+        emit::write_parse_and_can(
+            &app_module_path,
+            code_gen_options.emit,
+            roc_cache_dir,
+            &load_config,
+        );
+    }
+
     // Step 1: compile the app and generate the .o file
     let loaded =
         roc_load::load_and_monomorphize(arena, app_module_path.clone(), roc_cache_dir, load_config)
@@ -826,10 +887,15 @@ fn build_loaded_file<'a>(
             // and has a file called "host.zig"
             platform_main_roc.with_file_name("host.zig")
         } else {
-            platform_main_roc.with_file_name(legacy_host_filename(target).unwrap())
+            platform_main_roc.with_file_name(
+                legacy_host_filename(target).unwrap_or_else(|| unsupported_target(target)),
+            )
         }
     } else {
-        platform_main_roc.with_file_name(roc_linker::preprocessed_host_filename(target).unwrap())
+        platform_main_roc.with_file_name(
+            roc_linker::preprocessed_host_filename(target)
+                .unwrap_or_else(|| unsupported_target(target)),
+        )
     };
 
     // For example, if we're loading the platform from a URL, it's automatically prebuilt
@@ -844,11 +910,14 @@ fn build_loaded_file<'a>(
     }
 
     // We don't need to spawn a rebuild thread when using a prebuilt host.
-    let rebuild_thread = if matches!(link_type, LinkType::Dylib | LinkType::None) {
+    let rebuild_thread = if matches!(
+        link_type,
+        LinkType::Dylib | LinkType::StaticLib | LinkType::None
+    ) {
         None
     } else if is_platform_prebuilt {
         if !preprocessed_host_path.exists() {
-            invalid_prebuilt_platform(prebuilt_requested, preprocessed_host_path);
+            invalid_prebuilt_platform(target, prebuilt_requested, preprocessed_host_path);
 
             std::process::exit(1);
         }
@@ -884,30 +953,7 @@ fn build_loaded_file<'a>(
         Some(join_handle)
     };
 
-    let buf = &mut String::with_capacity(1024);
-
-    let mut it = loaded.timings.iter().peekable();
-    while let Some((module_id, module_timing)) = it.next() {
-        let module_name = loaded.interns.module_name(*module_id);
-
-        buf.push_str("    ");
-
-        if module_name.is_empty() {
-            // the App module
-            buf.push_str("Application Module");
-        } else {
-            buf.push_str(module_name);
-        }
-
-        buf.push('\n');
-
-        use std::fmt::Write;
-        write!(buf, "{module_timing}").unwrap();
-
-        if it.peek().is_some() {
-            buf.push('\n');
-        }
-    }
+    let module_timings = module_timings_table(&loaded.timings, &loaded.interns);
 
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
@@ -915,6 +961,20 @@ fn build_loaded_file<'a>(
     let problems = report_problems_monomorphized(&mut loaded);
     let loaded = loaded;
 
+    let sources = loaded
+        .sources
+        .values()
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    if code_gen_options.emit.mono {
+        emit::write_mono(&loaded, &app_module_path);
+    }
+
+    if code_gen_options.emit.borrow_report {
+        emit::print_borrow_report(arena, &loaded);
+    }
+
     enum HostRebuildTiming {
         BeforeApp(u128),
         ConcurrentWithApp(JoinHandle<u128>),
@@ -926,10 +986,6 @@ fn build_loaded_file<'a>(
                 .join()
                 .expect("Failed to (re)build platform.");
 
-            if emit_timings && !is_platform_prebuilt {
-                println!("Finished rebuilding the platform in {rebuild_duration} ms\n");
-            }
-
             Some(HostRebuildTiming::BeforeApp(rebuild_duration))
         } else {
             Some(HostRebuildTiming::ConcurrentWithApp(rebuild_thread))
@@ -948,45 +1004,24 @@ fn build_loaded_file<'a>(
         wasm_dev_stack_bytes,
     );
 
-    buf.push('\n');
-    buf.push_str("    ");
-    buf.push_str("Code Generation");
-    buf.push('\n');
-
-    report_timing(
-        buf,
-        "Generate final IR from Mono IR",
-        code_gen_timing.generate_final_ir,
-    );
-    report_timing(buf, "Generate object", code_gen_timing.code_gen_object);
-    buf.push('\n');
-    report_timing(buf, "Total", code_gen_timing.total);
-
     let compilation_end = compilation_start.elapsed();
     let size = roc_app_bytes.len();
 
-    if emit_timings {
-        println!(
-            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{buf}"
-        );
-
-        println!(
-            "Finished compilation and code gen in {} ms\n\nProduced a app.o file of size {:?}\n",
-            compilation_end.as_millis(),
-            size,
-        );
-    }
-
-    if let Some(HostRebuildTiming::ConcurrentWithApp(thread)) = opt_rebuild_timing {
-        let rebuild_duration = thread.join().expect("Failed to (re)build platform.");
-
-        if emit_timings && !is_platform_prebuilt {
-            println!("Finished rebuilding the platform in {rebuild_duration} ms\n");
+    let rebuild_duration = match opt_rebuild_timing {
+        Some(HostRebuildTiming::BeforeApp(rebuild_duration)) => Some(rebuild_duration),
+        Some(HostRebuildTiming::ConcurrentWithApp(thread)) => {
+            Some(thread.join().expect("Failed to (re)build platform."))
         }
-    }
+        None => None,
+    };
 
     // Step 2: link the prebuilt platform and compiled app
     let link_start = Instant::now();
+    let link_span = roc_tracing::info_span!("link").entered();
+
+    // How many bytes of code and data went into the linker, to show how much of it got left
+    // out of the executable as unused. Only measured when it's going to be reported.
+    let mut linked_code_and_data = None;
 
     match (linking_strategy, link_type) {
         (LinkingStrategy::Surgical, _) => {
@@ -1025,7 +1060,10 @@ fn build_loaded_file<'a>(
 
             let mut inputs = vec![app_o_file.to_str().unwrap()];
 
-            if !matches!(link_type, LinkType::Dylib | LinkType::None) {
+            if !matches!(
+                link_type,
+                LinkType::Dylib | LinkType::StaticLib | LinkType::None
+            ) {
                 // the host has been compiled into a .o or .obj file
                 inputs.push(preprocessed_host_path.as_path().to_str().unwrap());
             }
@@ -1034,8 +1072,21 @@ fn build_loaded_file<'a>(
                 inputs.push(builtins_host_tempfile.path().to_str().unwrap());
             }
 
-            let (mut child, _) = link(target, output_exe_path.clone(), &inputs, link_type)
-                .map_err(|_| todo!("gracefully handle `ld` failing to spawn."))?;
+            if code_gen_options.emit.dead_code_report {
+                linked_code_and_data = Some(
+                    inputs
+                        .iter()
+                        .map(|input| code_and_data_size(Path::new(input)))
+                        .sum::<Option<u64>>(),
+                );
+            }
+
+            let (mut child, binary_path) =
+                link(target, output_exe_path.clone(), &inputs, link_type)
+                    .map_err(|_| todo!("gracefully handle `ld` failing to spawn."))?;
+
+            // Libraries get an extension (e.g. `.so` or `.a`) that depends on the target.
+            output_exe_path = binary_path;
 
             let exit_status = child
                 .wait()
@@ -1054,28 +1105,92 @@ fn build_loaded_file<'a>(
         }
     }
 
+    drop(link_span);
+
     let linking_time = link_start.elapsed();
+    let total_time = compilation_start.elapsed();
 
     if emit_timings {
-        println!("Finished linking in {} ms\n", linking_time.as_millis());
+        // The phases after type checking and specialization work on the whole program at once.
+        let buf = &mut String::with_capacity(1024);
+
+        report_timing(
+            buf,
+            "Generate final IR from Mono IR",
+            code_gen_timing.generate_final_ir,
+        );
+        report_timing(buf, "Generate object", code_gen_timing.code_gen_object);
+
+        if let (Some(rebuild_duration), false) = (rebuild_duration, is_platform_prebuilt) {
+            let rebuild_duration = Duration::from_millis(rebuild_duration as u64);
+
+            report_timing(buf, "Rebuild platform", rebuild_duration);
+        }
+
+        report_timing(buf, "Link", linking_time);
+        buf.push('\n');
+        report_timing(buf, "Total", total_time);
+
+        println!(
+            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{module_timings}"
+        );
+        println!("Here's how long the rest of the build took:\n\n{buf}");
+        println!(
+            "Finished compilation and code gen in {} ms\n\nProduced a app.o file of size {:?}\n",
+            compilation_end.as_millis(),
+            size,
+        );
     }
 
-    let total_time = compilation_start.elapsed();
+    if let Some(input_size) = linked_code_and_data {
+        match input_size.zip(code_and_data_size(&output_exe_path)) {
+            Some((input_size, output_size)) => println!(
+                "The linker left out {} of the {input_size} bytes of code and data in the object files as unused, leaving {output_size} bytes in {}\n",
+                input_size.saturating_sub(output_size),
+                output_exe_path.display(),
+            ),
+            None => println!(
+                "I couldn't measure how much unused code and data the linker left out of {}, because I can't read the code and data sections of some of the files that went into it.\n",
+                output_exe_path.display(),
+            ),
+        }
+    }
 
     Ok(BuiltFile {
         binary_path: output_exe_path,
         problems,
         total_time,
         expect_metadata,
+        sources,
     })
 }
 
-fn invalid_prebuilt_platform(prebuilt_requested: bool, preprocessed_host_path: PathBuf) {
-    let prefix = if prebuilt_requested {
+fn unsupported_target(target: &Triple) -> ! {
+    eprintln!("I don't know how to build an executable for the {target} target yet.");
+
+    std::process::exit(1);
+}
+
+fn invalid_prebuilt_platform(
+    target: &Triple,
+    prebuilt_requested: bool,
+    preprocessed_host_path: PathBuf,
+) {
+    let cross_compiling = target != &Triple::host();
+    let prefix = if cross_compiling {
+        // The CLI assumes a prebuilt platform when building for another target,
+        // so suggesting to omit --prebuilt-platform wouldn't help here.
+        "Because I was building for a different target than this machine, "
+    } else if prebuilt_requested {
         "Because I was run with --prebuilt-platform, "
     } else {
         ""
     };
+    let suggestion = if cross_compiling {
+        "The platform may not support this target. If it does, its author may need to release a new build of it that includes a prebuilt host for this target."
+    } else {
+        "If you have the platform's source code locally, you may be able to generate it by re-running this command omitting --prebuilt-platform"
+    };
 
     let preprocessed_host_path_str = preprocessed_host_path.to_string_lossy();
     let extra_err_msg = if preprocessed_host_path_str.ends_with(".rh") {
@@ -1093,12 +1208,13 @@ fn invalid_prebuilt_platform(prebuilt_requested: bool, preprocessed_host_path: P
 
             However, it was not there!{}
 
-            If you have the platform's source code locally, you may be able to generate it by re-running this command omitting --prebuilt-platform
+            {}
             "#
         ),
         prefix,
         preprocessed_host_path.to_string_lossy(),
-        extra_err_msg
+        extra_err_msg,
+        suggestion
     );
 }
 
@@ -1120,6 +1236,7 @@ fn spawn_rebuild_thread(
         eprintln!("🔨 Rebuilding platform...");
 
         let rebuild_host_start = Instant::now();
+        let _entered = roc_tracing::info_span!("rebuild host").entered();
 
         match linking_strategy {
             LinkingStrategy::Additive => {
@@ -1207,6 +1324,8 @@ pub fn check_file<'a>(
     emit_timings: bool,
     roc_cache_dir: RocCacheDir<'_>,
     threading: Threading,
+    warning_levels: WarningLevels,
+    explain_types: bool,
 ) -> Result<(Problems, Duration), LoadingProblem<'a>> {
     let compilation_start = Instant::now();
 
@@ -1225,47 +1344,18 @@ pub fn check_file<'a>(
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Check,
+        warning_levels,
+        explain_types,
     };
     let mut loaded =
         roc_load::load_and_typecheck(arena, roc_file_path, roc_cache_dir, load_config)?;
 
-    let buf = &mut String::with_capacity(1024);
-
-    let mut it = loaded.timings.iter().peekable();
-    while let Some((module_id, module_timing)) = it.next() {
-        let module_name = loaded.interns.module_name(*module_id);
-
-        buf.push_str("    ");
-
-        if module_name.is_empty() {
-            // the App module
-            buf.push_str("Application Module");
-        } else {
-            buf.push_str(module_name);
-        }
-
-        buf.push('\n');
-
-        report_timing(buf, "Read .roc file from disk", module_timing.read_roc_file);
-        report_timing(buf, "Parse header", module_timing.parse_header);
-        report_timing(buf, "Parse body", module_timing.parse_body);
-        report_timing(buf, "Canonicalize", module_timing.canonicalize);
-        report_timing(buf, "Constrain", module_timing.constrain);
-        report_timing(buf, "Solve", module_timing.solve);
-        report_timing(buf, "Other", module_timing.other());
-        buf.push('\n');
-        report_timing(buf, "Total", module_timing.total());
-
-        if it.peek().is_some() {
-            buf.push('\n');
-        }
-    }
-
+    let module_timings = module_timings_table(&loaded.timings, &loaded.interns);
     let compilation_end = compilation_start.elapsed();
 
     if emit_timings {
         println!(
-            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{buf}"
+            "\n\nCompilation finished!\n\nHere's how long each module took to compile:\n\n{module_timings}"
         );
 
         println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
@@ -1286,6 +1376,7 @@ pub fn build_str_test<'a>(
         backend: CodeGenBackend::Llvm(LlvmBackendMode::Binary),
         opt_level: OptLevel::Normal,
         emit_debug_info: false,
+        emit: Emit::default(),
     };
 
     let emit_timings = false;
@@ -1297,7 +1388,8 @@ pub fn build_str_test<'a>(
     let build_ordering = BuildOrdering::AlwaysBuild;
     let threading = Threading::AtMost(2);
 
-    let load_config = standard_load_config(&triple, build_ordering, threading);
+    let load_config =
+        standard_load_config(&triple, build_ordering, threading, WarningLevels::default());
 
     let compilation_start = std::time::Instant::now();
 
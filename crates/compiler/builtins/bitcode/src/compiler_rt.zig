@@ -20,6 +20,8 @@ comptime {
     if (!want_windows_v2u64_abi) {
         @export(__muloti4, .{ .name = "__muloti4", .linkage = .Weak });
         @export(__lshrti3, .{ .name = "__lshrti3", .linkage = .Weak });
+        @export(__ashlti3, .{ .name = "__ashlti3", .linkage = .Weak });
+        @export(__ashrti3, .{ .name = "__ashrti3", .linkage = .Weak });
         @export(__divti3, .{ .name = "__divti3", .linkage = .Weak });
         @export(__modti3, .{ .name = "__modti3", .linkage = .Weak });
         @export(__umodti3, .{ .name = "__umodti3", .linkage = .Weak });
@@ -460,6 +462,64 @@ inline fn lshrXi3(comptime T: type, a: T, b: i32) T {
     return output.all;
 }
 
+pub fn __ashlti3(a: i128, b: i32) callconv(.C) i128 {
+    return ashlXi3(i128, a, b);
+}
+
+// Arithmetic shift left: shift in 0 from right to left
+// Precondition: 0 <= b < T.bit_count
+inline fn ashlXi3(comptime T: type, a: T, b: i32) T {
+    const word_t = HalveInt(T, false);
+    const S = std.math.Log2Int(word_t.HalfT);
+
+    const input = word_t{ .all = a };
+    var output: word_t = undefined;
+
+    if (b >= word_t.bits) {
+        output.s.low = 0;
+        output.s.high = input.s.low << @intCast(S, b - word_t.bits);
+    } else if (b == 0) {
+        return a;
+    } else {
+        output.s.low = input.s.low << @intCast(S, b);
+        output.s.high = input.s.high << @intCast(S, b);
+        output.s.high |= input.s.low >> @intCast(S, word_t.bits - b);
+    }
+
+    return output.all;
+}
+
+pub fn __ashrti3(a: i128, b: i32) callconv(.C) i128 {
+    return ashrXi3(i128, a, b);
+}
+
+// Arithmetic shift right: shift in 1 from left to right
+// Precondition: 0 <= b < T.bit_count
+inline fn ashrXi3(comptime T: type, a: T, b: i32) T {
+    const word_t = HalveInt(T, true);
+    const S = std.math.Log2Int(word_t.HalfT);
+
+    const input = word_t{ .all = a };
+    var output: word_t = undefined;
+
+    if (b >= word_t.bits) {
+        output.s.high = input.s.high >> (word_t.bits - 1);
+        output.s.low = input.s.high >> @intCast(S, b - word_t.bits);
+    } else if (b == 0) {
+        return a;
+    } else {
+        output.s.high = input.s.high >> @intCast(S, b);
+        output.s.low = input.s.high << @intCast(S, word_t.bits - b);
+        // Avoid sign-extension here
+        output.s.low |= @bitCast(
+            word_t.HalfT,
+            @bitCast(word_t.HalfTU, input.s.low) >> @intCast(S, b),
+        );
+    }
+
+    return output.all;
+}
+
 /// Allows to access underlying bits as two equally sized lower and higher
 /// signed or unsigned integers.
 fn HalveInt(comptime T: type, comptime signed_half: bool) type {
@@ -23,6 +23,12 @@ pub fn expectFailedStartSharedBuffer() callconv(.C) [*]u8 {
     return SHARED_BUFFER.ptr;
 }
 
+/// How many bytes the buffer from `setSharedBuffer` has room for, so that the REPL's dbgs can
+/// stop writing once it's full.
+pub fn expectSharedBufferLength() callconv(.C) usize {
+    return SHARED_BUFFER.len;
+}
+
 pub fn expectFailedStartSharedFile() callconv(.C) [*]u8 {
     // IMPORTANT: shared memory object names must begin with / and contain no other slashes!
     var name: [100]u8 = undefined;
@@ -247,8 +247,10 @@ comptime {
     if (builtin.target.cpu.arch != .wasm32) {
         exportUtilsFn(expect.expectFailedStartSharedBuffer, "expect_failed_start_shared_buffer");
         exportUtilsFn(expect.expectFailedStartSharedFile, "expect_failed_start_shared_file");
+        exportUtilsFn(expect.expectSharedBufferLength, "expect_shared_buffer_length");
         exportUtilsFn(expect.notifyParentExpect, "notify_parent_expect");
         exportUtilsFn(expect.notifyParentDbg, "notify_parent_dbg");
+        exportUtilsFn(utils.dumpStackTrace, "dump_stack_trace");
 
         // sets the buffer used for expect failures
         @export(expect.setSharedBuffer, .{ .name = "set_shared_buffer", .linkage = .Weak });
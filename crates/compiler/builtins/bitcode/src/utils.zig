@@ -504,3 +504,11 @@ test "increfC, static data" {
 pub fn dictPseudoSeed() callconv(.C) u64 {
     return @intCast(u64, @ptrToInt(dictPseudoSeed));
 }
+
+// Called right before roc_panic in builds with debug info (`--debug`). The executable's own
+// DWARF info is used to map each frame to the Roc function and line it came from.
+// Frames are found by following frame pointers, which codegen keeps in those builds.
+pub fn dumpStackTrace() callconv(.C) void {
+    std.debug.print("Roc crashed. Stack trace (most recent call first):\n", .{});
+    std.debug.dumpCurrentStackTrace(null);
+}
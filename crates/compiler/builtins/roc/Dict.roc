@@ -32,6 +32,8 @@ interface Dict
         Str,
         Num.{ Nat, U64, U8, I8 },
         Hash.{ Hasher, Hash },
+        Encode.{ Encoder, Encoding, EncoderFormatting },
+        Decode.{ Decoder, Decoding, DecoderFormatting },
     ]
 
 ## A [dictionary](https://en.wikipedia.org/wiki/Associative_array) that lets you
@@ -108,6 +110,12 @@ Dict k v := {
         Hash {
             hash: hashDict,
         },
+        Encoding {
+            toEncoder: encodeDict,
+        },
+        Decoding {
+            decoder: decodeDict,
+        },
     ]
 
 isEq : Dict k v, Dict k v -> Bool where k implements Hash & Eq, v implements Eq
@@ -126,6 +134,17 @@ isEq = \xs, ys ->
 hashDict : hasher, Dict k v -> hasher where k implements Hash & Eq, v implements Hash, hasher implements Hasher
 hashDict = \hasher, dict -> Hash.hashUnordered hasher (toList dict) List.walk
 
+# A dictionary is encoded as a list of (key, value) pairs, in insertion order.
+encodeDict : Dict k v -> Encoder fmt where k implements Hash & Eq & Encoding, v implements Encoding, fmt implements EncoderFormatting
+encodeDict = \dict -> Encode.list (toList dict) Encode.toEncoder
+
+decodeDict : Decoder (Dict k v) fmt where k implements Hash & Eq & Decoding, v implements Decoding, fmt implements DecoderFormatting
+decodeDict =
+    Decode.custom \bytes, fmt ->
+        { result, rest } = Decode.decodeWith bytes (Decode.list Decode.decoder) fmt
+
+        { result: Result.map result fromList, rest }
+
 ## Return an empty dictionary.
 ## ```
 ## emptyDict = Dict.empty {}
@@ -157,9 +176,25 @@ capacity = \@Dict { dataIndices } ->
 ## may provide a performance optimization if you know how many entries will be
 ## inserted.
 withCapacity : Nat -> Dict * *
-withCapacity = \_ ->
-    # TODO: power of 2 * 8 and actual implementation
-    empty {}
+withCapacity = \requested ->
+    slots = slotsForCapacity requested 8
+
+    @Dict {
+        metadata: List.repeat emptySlot slots,
+        dataIndices: List.repeat 0 slots,
+        data: List.withCapacity requested,
+        size: 0,
+    }
+
+# The smallest number of slots (a power of 2 that's at least 8) that can hold the
+# requested number of elements without going over the max load factor.
+slotsForCapacity : Nat, Nat -> Nat
+slotsForCapacity = \requested, slots ->
+    # This is 7/8 * slots, which is the max load factor.
+    if Num.subWrap slots (Num.shiftRightZfBy slots 3) >= requested then
+        slots
+    else
+        slotsForCapacity requested (2 * slots)
 
 ## Returns a dictionary containing the key and value provided as input.
 ## ```
@@ -183,7 +218,7 @@ single = \k, v ->
 fromList : List (k, v) -> Dict k v where k implements Hash & Eq
 fromList = \data ->
     # TODO: make this efficient. Should just set data and then set all indicies in the hashmap.
-    List.walk data (empty {}) (\dict, (k, v) -> insert dict k v)
+    List.walk data (withCapacity (List.len data)) (\dict, (k, v) -> insert dict k v)
 
 ## Returns the number of values in the dictionary.
 ## ```
@@ -21,10 +21,12 @@ interface Hash
         hashStrBytes,
         hashList,
         hashUnordered,
+        hashBox,
     ] imports [
         Bool.{ Bool, isEq },
         List,
         Str,
+        Box,
         Num.{ U8, U16, U32, U64, U128, I8, I16, I32, I64, I128, Nat, Dec },
     ]
 
@@ -72,6 +74,10 @@ hashList = \hasher, lst ->
     List.walk lst hasher \accumHasher, elem ->
         hash accumHasher elem
 
+## Adds the value inside a [Box] to a [Hasher].
+hashBox = \hasher, boxed ->
+    hash hasher (Box.unbox boxed)
+
 ## Adds a single [Bool] to a hasher.
 hashBool : a, Bool -> a where a implements Hasher
 hashBool = \hasher, b ->
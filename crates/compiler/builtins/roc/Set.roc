@@ -25,6 +25,9 @@ interface Set
         Dict.{ Dict },
         Num.{ Nat },
         Hash.{ Hash, Hasher },
+        Encode.{ Encoder, Encoding, EncoderFormatting },
+        Decode.{ Decoder, Decoding, DecoderFormatting },
+        Result,
     ]
 
 ## Provides a [set](https://en.wikipedia.org/wiki/Set_(abstract_data_type))
@@ -37,6 +40,12 @@ Set k := Dict.Dict k {} where k implements Hash & Eq
         Hash {
             hash: hashSet,
         },
+        Encoding {
+            toEncoder: encodeSet,
+        },
+        Decoding {
+            decoder: decodeSet,
+        },
     ]
 
 isEq : Set k, Set k -> Bool where k implements Hash & Eq
@@ -53,6 +62,17 @@ isEq = \xs, ys ->
 hashSet : hasher, Set k -> hasher where k implements Hash & Eq, hasher implements Hasher
 hashSet = \hasher, @Set inner -> Hash.hash hasher inner
 
+# A set is encoded as a list of its elements, in insertion order.
+encodeSet : Set k -> Encoder fmt where k implements Hash & Eq & Encoding, fmt implements EncoderFormatting
+encodeSet = \set -> Encode.list (toList set) Encode.toEncoder
+
+decodeSet : Decoder (Set k) fmt where k implements Hash & Eq & Decoding, fmt implements DecoderFormatting
+decodeSet =
+    Decode.custom \bytes, fmt ->
+        { result, rest } = Decode.decodeWith bytes (Decode.list Decode.decoder) fmt
+
+        { result: Result.map result fromList, rest }
+
 ## Creates a new empty `Set`.
 ## ```
 ## emptySet = Set.empty {}
@@ -560,6 +560,7 @@ fn pattern<'a>(
             f.text(&**n)
         }
         StrLiteral(s) => text!(f, r#""{}""#, s),
+        StrPrefix(s, rest) => text!(f, r#""{}" .. "#, s).append(pattern(c, Free, f, &rest.value)),
         SingleQuote(_, _, c, _) => text!(f, "'{}'", c),
         Underscore => f.text("_"),
 
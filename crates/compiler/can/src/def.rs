@@ -731,6 +731,8 @@ fn canonicalize_opaque<'a>(
                             if let Some(members) = opt_members {
                                 // This is an ability we already imported into the scope,
                                 // or which is also undergoing canonicalization at the moment.
+                                output.references.insert_type_lookup(ability);
+
                                 (ability, members)
                             } else {
                                 env.problem(Problem::NotAnAbility(ability_region));
@@ -2077,6 +2079,14 @@ fn pattern_to_vars_by_symbol(
             for pat in patterns.patterns.iter() {
                 pattern_to_vars_by_symbol(vars_by_symbol, &pat.value, *elem_var);
             }
+
+            if let Some((_, Some(rest))) = patterns.opt_rest {
+                vars_by_symbol.insert(rest, expr_var);
+            }
+        }
+
+        StrPrefix(_, rest) => {
+            pattern_to_vars_by_symbol(vars_by_symbol, &rest.value, expr_var);
         }
 
         NumLiteral(..)
@@ -337,6 +337,9 @@ fn sketch_pattern(pattern: &crate::pattern::Pattern) -> SketchedPattern {
         }
         &FloatLiteral(_, _, _, f, _) => SP::Literal(Literal::Float(f64::to_bits(f))),
         StrLiteral(v) => SP::Literal(Literal::Str(v.clone())),
+        // `"" .. rest` matches every string, like a wildcard does
+        StrPrefix(v, _) if v.is_empty() => SP::Anything,
+        StrPrefix(v, _) => SP::Literal(Literal::StrPrefix(v.clone())),
         &SingleQuote(_, _, c, _) => SP::Literal(Literal::Byte(c as u8)),
         RecordDestructure { destructs, .. } => {
             let tag_id = TagId(0);
@@ -3061,7 +3061,10 @@ pub struct DestructureDef {
     pub pattern_vars: VecMap<Symbol, Variable>,
 }
 
+/// The symbols looked up in an `expect` condition, in the order they appear in the source,
+/// so a failure report lists their values in the order the reader sees them.
 pub(crate) fn get_lookup_symbols(expr: &Expr) -> Vec<ExpectLookup> {
+    // Children are pushed in reverse source order, so they are popped in source order.
     let mut stack: Vec<&Expr> = vec![expr];
     let mut lookups: Vec<ExpectLookup> = Vec::new();
 
@@ -3092,22 +3095,22 @@ pub(crate) fn get_lookup_symbols(expr: &Expr) -> Vec<ExpectLookup> {
                 }
             }
             Expr::List { loc_elems, .. } => {
-                stack.extend(loc_elems.iter().map(|loc_elem| &loc_elem.value));
+                stack.extend(loc_elems.iter().rev().map(|loc_elem| &loc_elem.value));
             }
             Expr::When {
                 loc_cond, branches, ..
             } => {
-                stack.push(&loc_cond.value);
-
-                stack.reserve(branches.len());
+                stack.reserve(1 + branches.len() * 2);
 
-                for branch in branches {
+                for branch in branches.iter().rev() {
                     stack.push(&branch.value.value);
 
                     if let Some(guard) = &branch.guard {
                         stack.push(&guard.value);
                     }
                 }
+
+                stack.push(&loc_cond.value);
             }
             Expr::If {
                 branches,
@@ -3116,26 +3119,31 @@ pub(crate) fn get_lookup_symbols(expr: &Expr) -> Vec<ExpectLookup> {
             } => {
                 stack.reserve(1 + branches.len() * 2);
 
-                for (loc_cond, loc_body) in branches {
-                    stack.push(&loc_cond.value);
+                stack.push(&final_else.value);
+
+                for (loc_cond, loc_body) in branches.iter().rev() {
                     stack.push(&loc_body.value);
+                    stack.push(&loc_cond.value);
                 }
-
-                stack.push(&final_else.value);
             }
             Expr::LetRec(defs, expr, _illegal_cycle_mark) => {
-                for def in defs {
+                stack.push(&expr.value);
+
+                for def in defs.iter().rev() {
                     stack.push(&def.loc_expr.value);
                 }
-                stack.push(&expr.value);
             }
             Expr::LetNonRec(def, expr) => {
-                stack.push(&def.loc_expr.value);
                 stack.push(&expr.value);
+                stack.push(&def.loc_expr.value);
             }
             Expr::Call(boxed_expr, args, _called_via) => {
                 stack.reserve(1 + args.len());
 
+                for (_var, loc_arg) in args.iter().rev() {
+                    stack.push(&loc_arg.value);
+                }
+
                 match &boxed_expr.1.value {
                     Expr::Var(_, _) => {
                         // do nothing
@@ -3145,16 +3153,17 @@ pub(crate) fn get_lookup_symbols(expr: &Expr) -> Vec<ExpectLookup> {
                         stack.push(function_expr);
                     }
                 }
-
-                for (_var, loc_arg) in args {
-                    stack.push(&loc_arg.value);
-                }
             }
             Expr::Tag { arguments, .. } => {
-                stack.extend(arguments.iter().map(|(_var, loc_expr)| &loc_expr.value));
+                stack.extend(
+                    arguments
+                        .iter()
+                        .rev()
+                        .map(|(_var, loc_expr)| &loc_expr.value),
+                );
             }
             Expr::RunLowLevel { args, .. } | Expr::ForeignCall { args, .. } => {
-                stack.extend(args.iter().map(|(_var, arg)| arg));
+                stack.extend(args.iter().rev().map(|(_var, arg)| arg));
             }
             Expr::OpaqueRef { argument, .. } => {
                 stack.push(&argument.1.value);
@@ -3170,7 +3179,7 @@ pub(crate) fn get_lookup_symbols(expr: &Expr) -> Vec<ExpectLookup> {
                 stack.extend(fields.iter().map(|(_, field)| &field.loc_expr.value));
             }
             Expr::Tuple { elems, .. } => {
-                stack.extend(elems.iter().map(|(_, elem)| &elem.value));
+                stack.extend(elems.iter().rev().map(|(_, elem)| &elem.value));
             }
             Expr::Expect {
                 loc_continuation, ..
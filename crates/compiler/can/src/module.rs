@@ -9,7 +9,7 @@ use crate::expr::{
 use crate::pattern::{BindingsFromPattern, Pattern};
 use crate::scope::Scope;
 use bumpalo::Bump;
-use roc_collections::{MutMap, SendMap, VecMap, VecSet};
+use roc_collections::{MutMap, MutSet, SendMap, VecMap, VecSet};
 use roc_error_macros::internal_error;
 use roc_module::ident::Ident;
 use roc_module::ident::Lowercase;
@@ -21,6 +21,7 @@ use roc_problem::can::{Problem, RuntimeError};
 use roc_region::all::{Loc, Region};
 use roc_types::subs::{ExposedTypesStorageSubs, Subs, VarStore, Variable};
 use roc_types::types::{AbilitySet, Alias, AliasKind, AliasVar, Type};
+use std::cell::Cell;
 
 /// The types of all exposed values/functions of a collection of modules
 #[derive(Clone, Debug, Default)]
@@ -310,7 +311,7 @@ pub fn canonicalize_module_defs<'a>(
     // visited a BinOp node we'd recursively try to apply this to each of its nested
     // operators, and then again on *their* nested operators, ultimately applying the
     // rules multiple times unnecessarily.
-    crate::operator::desugar_defs(arena, loc_defs);
+    crate::operator::desugar_defs(arena, &Cell::new(0), loc_defs);
 
     let mut rigid_variables = RigidVariables::default();
 
@@ -354,7 +355,11 @@ pub fn canonicalize_module_defs<'a>(
             // the scope
             match scope.import(ident, symbol, region) {
                 Ok(()) => {
-                    // here we do nothing special
+                    // Builtin types are in scope whether they're imported or not,
+                    // so there's no telling whether an import of one is unused.
+                    if !symbol.is_builtin() {
+                        can_exposed_imports.insert(symbol, region);
+                    }
                 }
                 Err((shadowed_symbol, _region)) => {
                     internal_error!(
@@ -675,8 +680,17 @@ pub fn canonicalize_module_defs<'a>(
     // exposed_symbols and added to exposed_vars_by_symbol. If any were
     // not, that means they were declared as exposed but there was
     // no actual declaration with that name!
+    let defined_idents: MutSet<Box<str>> = scope
+        .locals
+        .idents_in_scope()
+        .map(|ident| ident.as_ref().into())
+        .collect();
+
     for symbol in exposed_but_not_defined {
-        env.problem(Problem::ExposedButNotDefined(symbol));
+        env.problem(Problem::ExposedButNotDefined {
+            symbol,
+            suggestion_options: defined_idents.clone(),
+        });
 
         // In case this exposed value is referenced by other modules,
         // create a decl for it whose implementation is a runtime error.
@@ -948,6 +962,7 @@ fn fix_values_captured_in_closure_pattern(
         | IntLiteral(..)
         | FloatLiteral(..)
         | StrLiteral(_)
+        | StrPrefix(..)
         | SingleQuote(..)
         | Underscore
         | Shadowed(..)
@@ -11,6 +11,7 @@ use roc_parse::ast::{
     AssignedField, Collection, RecordBuilderField, StrLiteral, StrSegment, ValueDef, WhenBranch,
 };
 use roc_region::all::{Loc, Region};
+use std::cell::Cell;
 
 // BinOp precedence logic adapted from Gluon by Markus Westerlind
 // https://github.com/gluon-lang/gluon - license information can be found in
@@ -66,11 +67,17 @@ fn new_op_call_expr<'a>(
     Loc { region, value }
 }
 
-fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a> {
+fn desugar_value_def<'a>(
+    arena: &'a Bump,
+    fresh_idents: &Cell<u32>,
+    def: &'a ValueDef<'a>,
+) -> ValueDef<'a> {
     use ValueDef::*;
 
     match def {
-        Body(loc_pattern, loc_expr) => Body(loc_pattern, desugar_expr(arena, loc_expr)),
+        Body(loc_pattern, loc_expr) => {
+            Body(loc_pattern, desugar_expr(arena, fresh_idents, loc_expr))
+        }
         ann @ Annotation(_, _) => *ann,
         AnnotatedBody {
             ann_pattern,
@@ -83,13 +90,13 @@ fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a>
             ann_type,
             comment: *comment,
             body_pattern,
-            body_expr: desugar_expr(arena, body_expr),
+            body_expr: desugar_expr(arena, fresh_idents, body_expr),
         },
         Dbg {
             condition,
             preceding_comment,
         } => {
-            let desugared_condition = &*arena.alloc(desugar_expr(arena, condition));
+            let desugared_condition = &*arena.alloc(desugar_expr(arena, fresh_idents, condition));
             Dbg {
                 condition: desugared_condition,
                 preceding_comment: *preceding_comment,
@@ -99,7 +106,7 @@ fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a>
             condition,
             preceding_comment,
         } => {
-            let desugared_condition = &*arena.alloc(desugar_expr(arena, condition));
+            let desugared_condition = &*arena.alloc(desugar_expr(arena, fresh_idents, condition));
             Expect {
                 condition: desugared_condition,
                 preceding_comment: *preceding_comment,
@@ -109,7 +116,7 @@ fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a>
             condition,
             preceding_comment,
         } => {
-            let desugared_condition = &*arena.alloc(desugar_expr(arena, condition));
+            let desugared_condition = &*arena.alloc(desugar_expr(arena, fresh_idents, condition));
             ExpectFx {
                 condition: desugared_condition,
                 preceding_comment: *preceding_comment,
@@ -118,15 +125,26 @@ fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a>
     }
 }
 
-pub fn desugar_defs<'a>(arena: &'a Bump, defs: &mut roc_parse::ast::Defs<'a>) {
+pub fn desugar_defs<'a>(
+    arena: &'a Bump,
+    fresh_idents: &Cell<u32>,
+    defs: &mut roc_parse::ast::Defs<'a>,
+) {
     for value_def in defs.value_defs.iter_mut() {
-        *value_def = desugar_value_def(arena, arena.alloc(*value_def));
+        *value_def = desugar_value_def(arena, fresh_idents, arena.alloc(*value_def));
     }
 }
 
 /// Reorder the expression tree based on operator precedence and associativity rules,
 /// then replace the BinOp nodes with Apply nodes. Also drop SpaceBefore and SpaceAfter nodes.
-pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc<Expr<'a>> {
+///
+/// `fresh_idents` numbers the identifiers that desugaring makes up, so share one across a
+/// whole module to keep them all different.
+pub fn desugar_expr<'a>(
+    arena: &'a Bump,
+    fresh_idents: &Cell<u32>,
+    loc_expr: &'a Loc<Expr<'a>>,
+) -> &'a Loc<Expr<'a>> {
     match &loc_expr.value {
         Float(..)
         | Num(..)
@@ -149,7 +167,11 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             StrLiteral::PlainLine(_) => loc_expr,
             StrLiteral::Line(segments) => {
                 let region = loc_expr.region;
-                let value = Str(StrLiteral::Line(desugar_str_segments(arena, segments)));
+                let value = Str(StrLiteral::Line(desugar_str_segments(
+                    arena,
+                    fresh_idents,
+                    segments,
+                )));
 
                 arena.alloc(Loc { region, value })
             }
@@ -158,7 +180,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                 let new_lines = Vec::from_iter_in(
                     lines
                         .iter()
-                        .map(|segments| desugar_str_segments(arena, segments)),
+                        .map(|segments| desugar_str_segments(arena, fresh_idents, segments)),
                     arena,
                 );
                 let value = Str(StrLiteral::Block(new_lines.into_bump_slice()));
@@ -173,7 +195,10 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                 region,
                 value: **sub_expr,
             };
-            let value = TupleAccess(&desugar_expr(arena, arena.alloc(loc_sub_expr)).value, paths);
+            let value = TupleAccess(
+                &desugar_expr(arena, fresh_idents, arena.alloc(loc_sub_expr)).value,
+                paths,
+            );
 
             arena.alloc(Loc { region, value })
         }
@@ -183,7 +208,10 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                 region,
                 value: **sub_expr,
             };
-            let value = RecordAccess(&desugar_expr(arena, arena.alloc(loc_sub_expr)).value, paths);
+            let value = RecordAccess(
+                &desugar_expr(arena, fresh_idents, arena.alloc(loc_sub_expr)).value,
+                paths,
+            );
 
             arena.alloc(Loc { region, value })
         }
@@ -191,7 +219,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             let mut new_items = Vec::with_capacity_in(items.len(), arena);
 
             for item in items.iter() {
-                new_items.push(desugar_expr(arena, item));
+                new_items.push(desugar_expr(arena, fresh_idents, item));
             }
             let new_items = new_items.into_bump_slice();
             let value: Expr<'a> = List(items.replace_items(new_items));
@@ -204,7 +232,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
         Record(fields) => arena.alloc(Loc {
             region: loc_expr.region,
             value: Record(fields.map_items(arena, |field| {
-                let value = desugar_field(arena, &field.value);
+                let value = desugar_field(arena, fresh_idents, &field.value);
                 Loc {
                     value,
                     region: field.region,
@@ -213,20 +241,26 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
         }),
         Tuple(fields) => arena.alloc(Loc {
             region: loc_expr.region,
-            value: Tuple(fields.map_items(arena, |field| desugar_expr(arena, field))),
+            value: Tuple(fields.map_items(arena, |field| desugar_expr(arena, fresh_idents, field))),
         }),
         RecordUpdate { fields, update } => {
             // NOTE the `update` field is always a `Var { .. }`, we only desugar it to get rid of
             // any spaces before/after
-            let new_update = desugar_expr(arena, update);
+            let new_update = desugar_expr(arena, fresh_idents, update);
 
             let new_fields = fields.map_items(arena, |field| {
-                let value = desugar_field(arena, &field.value);
+                let value = desugar_field(arena, fresh_idents, &field.value);
                 Loc {
                     value,
                     region: field.region,
                 }
             });
+            let new_fields = new_fields.replace_items(desugar_nested_updates(
+                arena,
+                fresh_idents,
+                new_update,
+                new_fields.items,
+            ));
 
             arena.alloc(Loc {
                 region: loc_expr.region,
@@ -238,7 +272,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
         }
         Closure(loc_patterns, loc_ret) => arena.alloc(Loc {
             region: loc_expr.region,
-            value: Closure(loc_patterns, desugar_expr(arena, loc_ret)),
+            value: Closure(loc_patterns, desugar_expr(arena, fresh_idents, loc_ret)),
         }),
         Backpassing(loc_patterns, loc_body, loc_ret) => {
             // loc_patterns <- loc_body
@@ -246,9 +280,9 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             // loc_ret
 
             // first desugar the body, because it may contain |>
-            let desugared_body = desugar_expr(arena, loc_body);
+            let desugared_body = desugar_expr(arena, fresh_idents, loc_body);
 
-            let desugared_ret = desugar_expr(arena, loc_ret);
+            let desugared_ret = desugar_expr(arena, fresh_idents, loc_ret);
             let closure = Expr::Closure(loc_patterns, desugared_ret);
             let loc_closure = Loc::at(loc_expr.region, closure);
 
@@ -281,12 +315,12 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             value: UnappliedRecordBuilder(loc_expr),
             region: loc_expr.region,
         }),
-        BinOps(lefts, right) => desugar_bin_ops(arena, loc_expr.region, lefts, right),
+        BinOps(lefts, right) => desugar_bin_ops(arena, fresh_idents, loc_expr.region, lefts, right),
         Defs(defs, loc_ret) => {
             let mut defs = (*defs).clone();
-            desugar_defs(arena, &mut defs);
+            desugar_defs(arena, fresh_idents, &mut defs);
 
-            let loc_ret = desugar_expr(arena, loc_ret);
+            let loc_ret = desugar_expr(arena, fresh_idents, loc_ret);
 
             arena.alloc(Loc::at(loc_expr.region, Defs(arena.alloc(defs), loc_ret)))
         }
@@ -318,13 +352,17 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                     }
                 };
 
-                desugared_args.push(desugar_expr(arena, arg));
+                desugared_args.push(desugar_expr(arena, fresh_idents, arg));
             }
 
             let desugared_args = desugared_args.into_bump_slice();
 
             let mut apply: &Loc<Expr> = arena.alloc(Loc {
-                value: Apply(desugar_expr(arena, loc_fn), desugared_args, *called_via),
+                value: Apply(
+                    desugar_expr(arena, fresh_idents, loc_fn),
+                    desugared_args,
+                    *called_via,
+                ),
                 region: loc_expr.region,
             });
 
@@ -333,7 +371,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
 
                 Some(apply_exprs) => {
                     for expr in apply_exprs {
-                        let desugared_expr = desugar_expr(arena, expr);
+                        let desugared_expr = desugar_expr(arena, fresh_idents, expr);
 
                         let args = std::slice::from_ref(arena.alloc(apply));
 
@@ -348,17 +386,18 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             apply
         }
         When(loc_cond_expr, branches) => {
-            let loc_desugared_cond = &*arena.alloc(desugar_expr(arena, loc_cond_expr));
+            let loc_desugared_cond =
+                &*arena.alloc(desugar_expr(arena, fresh_idents, loc_cond_expr));
             let mut desugared_branches = Vec::with_capacity_in(branches.len(), arena);
 
             for branch in branches.iter() {
-                let desugared = desugar_expr(arena, &branch.value);
+                let desugared = desugar_expr(arena, fresh_idents, &branch.value);
 
                 let mut alternatives = Vec::with_capacity_in(branch.patterns.len(), arena);
                 alternatives.extend(branch.patterns.iter().copied());
 
                 let desugared_guard = if let Some(guard) = &branch.guard {
-                    Some(*desugar_expr(arena, guard))
+                    Some(*desugar_expr(arena, fresh_idents, guard))
                 } else {
                     None
                 };
@@ -398,7 +437,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                 },
             };
             let loc_fn_var = arena.alloc(Loc { region, value });
-            let desugared_args = arena.alloc([desugar_expr(arena, loc_arg)]);
+            let desugared_args = arena.alloc([desugar_expr(arena, fresh_idents, loc_arg)]);
 
             arena.alloc(Loc {
                 value: Apply(loc_fn_var, desugared_args, CalledVia::UnaryOp(op)),
@@ -410,6 +449,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             // are no longer needed and should be dropped.
             desugar_expr(
                 arena,
+                fresh_idents,
                 arena.alloc(Loc {
                     value: **expr,
                     region: loc_expr.region,
@@ -419,6 +459,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
         ParensAround(expr) => {
             let desugared = desugar_expr(
                 arena,
+                fresh_idents,
                 arena.alloc(Loc {
                     value: **expr,
                     region: loc_expr.region,
@@ -432,14 +473,15 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
         }
         If(if_thens, final_else_branch) => {
             // If does not get desugared into `when` so we can give more targeted error messages during type checking.
-            let desugared_final_else = &*arena.alloc(desugar_expr(arena, final_else_branch));
+            let desugared_final_else =
+                &*arena.alloc(desugar_expr(arena, fresh_idents, final_else_branch));
 
             let mut desugared_if_thens = Vec::with_capacity_in(if_thens.len(), arena);
 
             for (condition, then_branch) in if_thens.iter() {
                 desugared_if_thens.push((
-                    *desugar_expr(arena, condition),
-                    *desugar_expr(arena, then_branch),
+                    *desugar_expr(arena, fresh_idents, condition),
+                    *desugar_expr(arena, fresh_idents, then_branch),
                 ));
             }
 
@@ -449,16 +491,18 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             })
         }
         Expect(condition, continuation) => {
-            let desugared_condition = &*arena.alloc(desugar_expr(arena, condition));
-            let desugared_continuation = &*arena.alloc(desugar_expr(arena, continuation));
+            let desugared_condition = &*arena.alloc(desugar_expr(arena, fresh_idents, condition));
+            let desugared_continuation =
+                &*arena.alloc(desugar_expr(arena, fresh_idents, continuation));
             arena.alloc(Loc {
                 value: Expect(desugared_condition, desugared_continuation),
                 region: loc_expr.region,
             })
         }
         Dbg(condition, continuation) => {
-            let desugared_condition = &*arena.alloc(desugar_expr(arena, condition));
-            let desugared_continuation = &*arena.alloc(desugar_expr(arena, continuation));
+            let desugared_condition = &*arena.alloc(desugar_expr(arena, fresh_idents, condition));
+            let desugared_continuation =
+                &*arena.alloc(desugar_expr(arena, fresh_idents, continuation));
             arena.alloc(Loc {
                 value: Dbg(desugared_condition, desugared_continuation),
                 region: loc_expr.region,
@@ -469,6 +513,7 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
 
 fn desugar_str_segments<'a>(
     arena: &'a Bump,
+    fresh_idents: &Cell<u32>,
     segments: &'a [StrSegment<'a>],
 ) -> &'a [StrSegment<'a>] {
     Vec::from_iter_in(
@@ -479,6 +524,7 @@ fn desugar_str_segments<'a>(
             StrSegment::Interpolated(loc_expr) => {
                 let loc_desugared = desugar_expr(
                     arena,
+                    fresh_idents,
                     arena.alloc(Loc {
                         region: loc_expr.region,
                         value: *loc_expr.value,
@@ -497,6 +543,7 @@ fn desugar_str_segments<'a>(
 
 fn desugar_field<'a>(
     arena: &'a Bump,
+    fresh_idents: &Cell<u32>,
     field: &'a AssignedField<'a, Expr<'a>>,
 ) -> AssignedField<'a, Expr<'a>> {
     use roc_parse::ast::AssignedField::*;
@@ -508,7 +555,7 @@ fn desugar_field<'a>(
                 region: loc_str.region,
             },
             spaces,
-            desugar_expr(arena, loc_expr),
+            desugar_expr(arena, fresh_idents, loc_expr),
         ),
         OptionalValue(loc_str, spaces, loc_expr) => OptionalValue(
             Loc {
@@ -516,7 +563,7 @@ fn desugar_field<'a>(
                 region: loc_str.region,
             },
             spaces,
-            desugar_expr(arena, loc_expr),
+            desugar_expr(arena, fresh_idents, loc_expr),
         ),
         LabelOnly(loc_str) => {
             // Desugar { x } into { x: x }
@@ -534,16 +581,113 @@ fn desugar_field<'a>(
                     region: loc_str.region,
                 },
                 &[],
-                desugar_expr(arena, arena.alloc(loc_expr)),
+                desugar_expr(arena, fresh_idents, arena.alloc(loc_expr)),
             )
         }
-        SpaceBefore(field, _spaces) => desugar_field(arena, field),
-        SpaceAfter(field, _spaces) => desugar_field(arena, field),
+        SpaceBefore(field, _spaces) => desugar_field(arena, fresh_idents, field),
+        SpaceAfter(field, _spaces) => desugar_field(arena, fresh_idents, field),
 
         Malformed(string) => Malformed(string),
     }
 }
 
+/// Desugar updates of nested fields into updates of the fields that contain them,
+/// e.g. `{ rec & a: 1, b.c: 2, b.d: 3 }` becomes
+///
+///     { rec & a: 1, b: (\#b_0 -> { #b_0 & c: 2, d: 3 }) rec.b }
+///
+/// where the `0` comes from `fresh_idents`, which counts the closure arguments made so far.
+fn desugar_nested_updates<'a>(
+    arena: &'a Bump,
+    fresh_idents: &Cell<u32>,
+    update: &'a Loc<Expr<'a>>,
+    fields: &'a [Loc<AssignedField<'a, Expr<'a>>>],
+) -> &'a [Loc<AssignedField<'a, Expr<'a>>>] {
+    let is_nested = |field: &Loc<AssignedField<'a, Expr<'a>>>| matches!(field.value, AssignedField::RequiredValue(label, _, _) if label.value.contains('.'));
+
+    if !fields.iter().any(is_nested) {
+        return fields;
+    }
+
+    let mut new_fields = Vec::with_capacity_in(fields.len(), arena);
+    let mut nested_fields: Vec<(Loc<&'a str>, Vec<Loc<AssignedField<'a, Expr<'a>>>>)> =
+        Vec::new_in(arena);
+
+    for field in fields {
+        match field.value {
+            AssignedField::RequiredValue(label, spaces, value) if is_nested(field) => {
+                let (outer, inner) = label.value.split_once('.').unwrap();
+                let inner_field = Loc {
+                    value: AssignedField::RequiredValue(
+                        Loc::at(label.region, inner),
+                        spaces,
+                        value,
+                    ),
+                    region: field.region,
+                };
+
+                match nested_fields.iter_mut().find(|(l, _)| l.value == outer) {
+                    Some((_, inner_fields)) => inner_fields.push(inner_field),
+                    None => {
+                        let mut inner_fields = Vec::new_in(arena);
+                        inner_fields.push(inner_field);
+                        nested_fields.push((Loc::at(label.region, outer), inner_fields));
+                    }
+                }
+            }
+            _ => new_fields.push(*field),
+        }
+    }
+
+    for (label, inner_fields) in nested_fields {
+        let region = label.region;
+        // Number the argument, so that it can't shadow the one for an update
+        // it's nested in, or for an update of a field with the same name
+        let id = fresh_idents.get();
+        fresh_idents.set(id + 1);
+
+        let name: &'a str = arena.alloc(format!("#{}_{}", label.value, id));
+
+        let var: &'a Loc<Expr<'a>> = arena.alloc(Loc::at(
+            region,
+            Var {
+                module_name: "",
+                ident: name,
+            },
+        ));
+        let inner_fields =
+            desugar_nested_updates(arena, fresh_idents, var, inner_fields.into_bump_slice());
+        let body = arena.alloc(Loc::at(
+            region,
+            RecordUpdate {
+                update: var,
+                fields: Collection::with_items(inner_fields),
+            },
+        ));
+
+        let arg_pattern = arena.alloc(Loc::at(region, roc_parse::ast::Pattern::Identifier(name)));
+        let closure = arena.alloc(Loc::at(
+            region,
+            Closure(std::slice::from_ref(arg_pattern), body),
+        ));
+        let access = arena.alloc(Loc::at(
+            region,
+            RecordAccess(arena.alloc(update.value), label.value),
+        ));
+        let apply = arena.alloc(Loc::at(
+            region,
+            Apply(closure, arena.alloc([&*access]), CalledVia::Space),
+        ));
+
+        new_fields.push(Loc::at(
+            region,
+            AssignedField::RequiredValue(label, &[], apply),
+        ));
+    }
+
+    new_fields.into_bump_slice()
+}
+
 struct RecordBuilderArg<'a> {
     closure: &'a Loc<Expr<'a>>,
     apply_exprs: Vec<'a, &'a Loc<Expr<'a>>>,
@@ -666,6 +810,7 @@ fn binop_to_function(binop: BinOp) -> (&'static str, &'static str) {
 
 fn desugar_bin_ops<'a>(
     arena: &'a Bump,
+    fresh_idents: &Cell<u32>,
     whole_region: Region,
     lefts: &'a [(Loc<Expr<'_>>, Loc<BinOp>)],
     right: &'a Loc<Expr<'_>>,
@@ -674,14 +819,14 @@ fn desugar_bin_ops<'a>(
     let mut op_stack: Vec<Loc<BinOp>> = Vec::with_capacity_in(lefts.len(), arena);
 
     for (loc_expr, loc_op) in lefts {
-        arg_stack.push(desugar_expr(arena, loc_expr));
+        arg_stack.push(desugar_expr(arena, fresh_idents, loc_expr));
         match run_binop_step(arena, whole_region, &mut arg_stack, &mut op_stack, *loc_op) {
             Err(problem) => return problem,
             Ok(()) => continue,
         }
     }
 
-    let mut expr = desugar_expr(arena, right);
+    let mut expr = desugar_expr(arena, fresh_idents, right);
 
     for (left, loc_op) in arg_stack.into_iter().zip(op_stack.into_iter()).rev() {
         expr = arena.alloc(new_op_call_expr(arena, left, loc_op, expr));
@@ -72,6 +72,9 @@ pub enum Pattern {
     IntLiteral(Variable, Variable, Box<str>, IntValue, IntBound),
     FloatLiteral(Variable, Variable, Box<str>, f64, FloatBound),
     StrLiteral(Box<str>),
+    /// A string that starts with the given prefix, e.g. `"fn " .. rest`.
+    /// The rest is an [Pattern::Identifier] or [Pattern::Underscore].
+    StrPrefix(Box<str>, Box<Loc<Pattern>>),
     SingleQuote(Variable, Variable, char, SingleQuoteBound),
     Underscore,
 
@@ -114,6 +117,7 @@ impl Pattern {
             IntLiteral(var, ..) => Some(*var),
             FloatLiteral(var, ..) => Some(*var),
             StrLiteral(_) => None,
+            StrPrefix(..) => None,
             SingleQuote(..) => None,
             Underscore => None,
 
@@ -158,6 +162,7 @@ impl Pattern {
             | IntLiteral(..)
             | FloatLiteral(..)
             | StrLiteral(..)
+            | StrPrefix(..)
             | SingleQuote(..) => false,
             UnwrappedOpaque { argument, .. } => {
                 // Opaques can only match against one constructor (the opaque symbol), so this is
@@ -185,7 +190,7 @@ impl Pattern {
             NumLiteral(..) => C::Num,
             IntLiteral(..) => C::Int,
             FloatLiteral(..) => C::Float,
-            StrLiteral(_) => C::Str,
+            StrLiteral(_) | StrPrefix(..) => C::Str,
             SingleQuote(..) => C::Character,
             Underscore => C::PatternDefault,
 
@@ -551,6 +556,27 @@ pub fn canonicalize_pattern<'a>(
             ptype => unsupported_pattern(env, ptype, region),
         },
 
+        StrPrefix(literal, loc_rest) => match pattern_type {
+            WhenBranch => match flatten_str_literal(literal) {
+                Pattern::StrLiteral(prefix) => {
+                    let can_rest = canonicalize_pattern(
+                        env,
+                        var_store,
+                        scope,
+                        output,
+                        pattern_type,
+                        &loc_rest.value,
+                        loc_rest.region,
+                        permit_shadows,
+                    );
+
+                    Pattern::StrPrefix(prefix, Box::new(can_rest))
+                }
+                other => other,
+            },
+            ptype => unsupported_pattern(env, ptype, region),
+        },
+
         SingleQuote(string) => {
             let mut it = string.chars().peekable();
             if let Some(char) = it.next() {
@@ -982,6 +1008,9 @@ impl<'a> BindingsFromPattern<'a> {
                             let it = destructs.iter().rev().map(RecordDestruct);
                             stack.extend(it);
                         }
+                        StrPrefix(_, rest) => {
+                            stack.push(Pattern(rest));
+                        }
                         NumLiteral(..)
                         | IntLiteral(..)
                         | FloatLiteral(..)
@@ -994,6 +1023,10 @@ impl<'a> BindingsFromPattern<'a> {
                         | OpaqueNotInScope(..) => (),
                         List { patterns, .. } => {
                             stack.extend(patterns.patterns.iter().rev().map(Pattern));
+
+                            if let Some((_, Some(rest))) = patterns.opt_rest {
+                                return Some((rest, loc_pattern.region));
+                            }
                         }
                     }
                 }
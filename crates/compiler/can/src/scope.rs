@@ -575,7 +575,7 @@ impl ScopedIdentIds {
         result
     }
 
-    fn idents_in_scope(&self) -> impl Iterator<Item = Ident> + '_ {
+    pub(crate) fn idents_in_scope(&self) -> impl Iterator<Item = Ident> + '_ {
         self.ident_ids
             .ident_strs()
             .zip(self.in_scope.iter())
@@ -31,6 +31,7 @@ pub enum DeclarationInfo<'a> {
         expr_var: Variable,
         pattern: Pattern,
         function: &'a Loc<expr::FunctionDef>,
+        annotation: Option<&'a Annotation>,
     },
     Destructure {
         loc_pattern: &'a Loc<Pattern>,
@@ -130,6 +131,7 @@ pub fn walk_decls<V: Visitor>(visitor: &mut V, decls: &Declarations) {
                     expr_var,
                     pattern,
                     function: function_def,
+                    annotation: decls.annotations[index].as_ref(),
                 }
             }
             Destructure(destructure_index) => {
@@ -191,6 +193,7 @@ fn walk_decl<V: Visitor>(visitor: &mut V, decl: DeclarationInfo<'_>) {
             expr_var,
             pattern,
             function,
+            annotation,
         } => {
             visitor.visit_pattern(&pattern, loc_symbol.region, Some(expr_var));
 
@@ -199,7 +202,11 @@ fn walk_decl<V: Visitor>(visitor: &mut V, decl: DeclarationInfo<'_>) {
                 &function.value.arguments,
                 loc_body,
                 function.value.return_type,
-            )
+            );
+
+            if let Some(annot) = annotation {
+                visitor.visit_annotation(annot);
+            }
         }
         Destructure {
             loc_pattern,
@@ -607,6 +614,7 @@ pub fn walk_pattern<V: Visitor>(visitor: &mut V, pattern: &Pattern) {
         IntLiteral(..) => { /* terminal */ }
         FloatLiteral(..) => { /* terminal */ }
         StrLiteral(..) => { /* terminal */ }
+        StrPrefix(_, rest) => visitor.visit_pattern(&rest.value, rest.region, None),
         SingleQuote(..) => { /* terminal */ }
         Underscore => { /* terminal */ }
         AbilityMemberSpecialization { .. } => { /* terminal */ }
@@ -90,6 +90,10 @@ fn headers_from_annotation_help(
         | SingleQuote(..)
         | StrLiteral(_) => true,
 
+        StrPrefix(_, rest) => {
+            headers_from_annotation_help(types, constraints, &rest.value, annotation, headers)
+        }
+
         RecordDestructure { destructs, .. } => {
             let dealiased = types.shallow_dealias(annotation.value);
             match types[dealiased] {
@@ -441,6 +445,27 @@ pub fn constrain_pattern(
             ));
         }
 
+        StrPrefix(_, rest) => {
+            let str_type = constraints.push_type(types, Types::STR);
+            state.constraints.push(constraints.equal_pattern_types(
+                str_type,
+                expected,
+                PatternCategory::Str,
+                region,
+            ));
+
+            // the rest of the string is a Str too
+            constrain_pattern(
+                types,
+                constraints,
+                env,
+                &rest.value,
+                rest.region,
+                expected,
+                state,
+            );
+        }
+
         &SingleQuote(num_var, precision_var, _, bound) => {
             // First constraint on the free num var; this improves the resolved type quality in
             // case the bound is an alias.
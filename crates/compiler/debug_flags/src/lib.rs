@@ -120,6 +120,8 @@ flags! {
     ROC_VERIFY_OCCURS_ONE_RECURSION
 
     // ===Mono===
+    // These print the IR between passes, and only in debug builds of the compiler.
+    // `roc build --emit mono` writes the final IR to files from any build.
 
     /// Type-checks the mono IR after specialization.
     ROC_CHECK_MONO_IR
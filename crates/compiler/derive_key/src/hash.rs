@@ -44,6 +44,7 @@ impl FlatHash {
                 FlatType::Apply(sym, _) => match sym {
                     Symbol::LIST_LIST => Ok(SingleLambdaSetImmediate(Symbol::HASH_HASH_LIST)),
                     Symbol::STR_STR => Ok(SingleLambdaSetImmediate(Symbol::HASH_HASH_STR_BYTES)),
+                    Symbol::BOX_BOX_TYPE => Ok(SingleLambdaSetImmediate(Symbol::HASH_HASH_BOX)),
                     _ => Err(Underivable),
                 },
                 FlatType::Record(fields, ext) => {
@@ -132,6 +132,21 @@ pub enum Literal {
     Float(u64),
     Decimal([u8; 16]),
     Str(Box<str>),
+    /// Any string starting with this prefix. Never empty, since `"" .. rest` matches every
+    /// string and so is sketched as a wildcard instead.
+    StrPrefix(Box<str>),
+}
+
+impl Literal {
+    /// Does this literal match every value that `other` matches?
+    fn covers(&self, other: &Literal) -> bool {
+        match (self, other) {
+            (Literal::StrPrefix(prefix), Literal::Str(string) | Literal::StrPrefix(string)) => {
+                string.starts_with(&**prefix)
+            }
+            _ => self == other,
+        }
+    }
 }
 
 /// Error
@@ -453,7 +468,7 @@ pub fn is_useful(mut old_matrix: PatternMatrix, mut vector: Row) -> bool {
 
                             match head {
                                 Some(Literal(lit)) => {
-                                    if lit == literal {
+                                    if lit.covers(&literal) {
                                         matrix.push(patterns);
                                     } else {
                                         // do nothing
@@ -1,5 +1,6 @@
 use crate::{
     collection::{fmt_collection, Braces},
+    expr::punned_field,
     spaces::{fmt_comments_only, fmt_spaces, NewlineAt, INDENT},
     Buf,
 };
@@ -419,8 +420,13 @@ impl<'a> Formattable for AssignedField<'a, Expr<'a>> {
     }
 
     fn format_with_options(&self, buf: &mut Buf, _parens: Parens, newlines: Newlines, indent: u16) {
+        let field = match punned_field(self) {
+            Some(label) if buf.options().pun_record_fields => AssignedField::LabelOnly(label),
+            _ => *self,
+        };
+
         // we abuse the `Newlines` type to decide between multiline or single-line layout
-        format_assigned_field_help(self, buf, indent, 0, newlines == Newlines::Yes);
+        format_assigned_field_help(&field, buf, indent, 0, newlines == Newlines::Yes);
     }
 }
 
@@ -13,6 +13,24 @@ pub enum Braces {
     Curly,
 }
 
+impl Braces {
+    fn start(self) -> char {
+        match self {
+            Braces::Round => '(',
+            Braces::Curly => '{',
+            Braces::Square => '[',
+        }
+    }
+
+    fn end(self) -> char {
+        match self {
+            Braces::Round => ')',
+            Braces::Curly => '}',
+            Braces::Square => ']',
+        }
+    }
+}
+
 pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable>(
     buf: &mut Buf<'buf>,
     indent: u16,
@@ -22,19 +40,13 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable>(
 ) where
     <T as ExtractSpaces<'a>>::Item: Formattable,
 {
-    let start = match braces {
-        Braces::Round => '(',
-        Braces::Curly => '{',
-        Braces::Square => '[',
-    };
-
-    let end = match braces {
-        Braces::Round => ')',
-        Braces::Curly => '}',
-        Braces::Square => ']',
-    };
-
-    if is_collection_multiline(&items) {
+    let start = braces.start();
+    let end = braces.end();
+
+    let is_multiline = is_collection_multiline(&items)
+        || !buf.fits_on_line(|buf| fmt_collection_on_one_line(buf, indent, braces, items));
+
+    if is_multiline {
         let braces_indent = indent;
         let item_indent = braces_indent + INDENT;
         if newline == Newlines::Yes {
@@ -88,7 +100,7 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable>(
             buf.indent(item_indent);
             item.item.format(buf, item_indent);
 
-            buf.push(',');
+            push_multiline_comma(buf, index + 1 == items.len());
 
             if !item.after.is_empty() {
                 if item.after.iter().any(|s| s.is_newline()) {
@@ -119,27 +131,44 @@ pub fn fmt_collection<'a, 'buf, T: ExtractSpaces<'a> + Formattable>(
 
         buf.ensure_ends_with_newline();
         buf.indent(braces_indent);
+        buf.push(end);
     } else {
-        // is_multiline == false
-        // there is no comment to add
-        buf.indent(indent);
-        buf.push(start);
-        let mut iter = items.iter().enumerate().peekable();
-        while let Some((index, item)) = iter.next() {
-            if braces == Braces::Curly || index != 0 {
-                buf.spaces(1);
-            }
+        fmt_collection_on_one_line(buf, indent, braces, items);
+    }
+}
 
-            item.format(buf, indent);
-            if iter.peek().is_some() {
-                buf.push(',');
-            }
+fn fmt_collection_on_one_line<'a, 'buf, T: Formattable>(
+    buf: &mut Buf<'buf>,
+    indent: u16,
+    braces: Braces,
+    items: Collection<'a, T>,
+) {
+    // there is no comment to add
+    buf.indent(indent);
+    buf.push(braces.start());
+    let mut iter = items.iter().enumerate().peekable();
+    while let Some((index, item)) = iter.next() {
+        if braces == Braces::Curly || index != 0 {
+            buf.spaces(1);
         }
 
-        if !items.is_empty() && braces == Braces::Curly {
-            buf.spaces(1);
+        item.format(buf, indent);
+        if iter.peek().is_some() {
+            buf.push(',');
         }
     }
 
-    buf.push(end);
+    if !items.is_empty() && braces == Braces::Curly {
+        buf.spaces(1);
+    }
+
+    buf.push(braces.end());
+}
+
+/// Push the comma after an item in a multiline collection or record. The last item only
+/// gets one if the options say so.
+pub(crate) fn push_multiline_comma(buf: &mut Buf, is_last: bool) {
+    if !is_last || buf.options().trailing_commas {
+        buf.push(',');
+    }
 }
@@ -22,6 +22,14 @@ impl<'a> Formattable for Defs<'a> {
         _newlines: Newlines,
         indent: u16,
     ) {
+        // A module can have comments but no defs (e.g. if they've all been commented out),
+        // in which case the comments don't belong to any def.
+        if self.tags.is_empty() {
+            fmt_spaces(buf, self.spaces.iter(), indent);
+
+            return;
+        }
+
         let mut prev_spaces = true;
 
         for (index, def) in self.defs().enumerate() {
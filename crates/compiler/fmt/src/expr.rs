@@ -1,5 +1,5 @@
 use crate::annotation::{except_last, is_collection_multiline, Formattable, Newlines, Parens};
-use crate::collection::{fmt_collection, Braces};
+use crate::collection::{fmt_collection, push_multiline_comma, Braces};
 use crate::def::fmt_defs;
 use crate::pattern::fmt_pattern;
 use crate::spaces::{
@@ -7,6 +7,7 @@ use crate::spaces::{
     INDENT,
 };
 use crate::Buf;
+use bumpalo::{collections::Vec, Bump};
 use roc_module::called_via::{self, BinOp};
 use roc_parse::ast::{
     AssignedField, Base, Collection, CommentOrNewline, Expr, ExtractSpaces, Pattern,
@@ -380,7 +381,17 @@ impl<'a> Formattable for Expr<'a> {
                 fmt_closure(buf, loc_patterns, loc_ret, indent);
             }
             Backpassing(loc_patterns, loc_body, loc_ret) => {
-                fmt_backpassing(buf, loc_patterns, loc_body, loc_ret, indent);
+                let arena = Bump::new();
+                let call = if buf.options().migrations.backpassing {
+                    backpassing_as_call(&arena, loc_patterns, loc_body, loc_ret)
+                } else {
+                    None
+                };
+
+                match call {
+                    Some(call) => call.format_with_options(buf, parens, newlines, indent),
+                    None => fmt_backpassing(buf, loc_patterns, loc_body, loc_ret, indent),
+                }
             }
             Defs(defs, ret) => {
                 {
@@ -902,12 +913,12 @@ fn fmt_when<'a>(
         }
 
         if let Some(guard_expr) = &branch.guard {
-            buf.push_str(" if");
+            push_after_branch_comment(buf, "if", indent + INDENT);
             buf.spaces(1);
             guard_expr.format_with_options(buf, Parens::NotNeeded, Newlines::Yes, indent + INDENT);
         }
 
-        buf.push_str(" ->");
+        push_after_branch_comment(buf, "->", indent + INDENT);
 
         match expr.value {
             Expr::SpaceBefore(nested, spaces) => {
@@ -946,6 +957,18 @@ fn fmt_when<'a>(
     }
 }
 
+/// Push the `if` or `->` that comes after a `when` branch's patterns (or guard). If those
+/// ended in a comment, it goes on the next line, lined up with the patterns.
+fn push_after_branch_comment(buf: &mut Buf, token: &str, indent: u16) {
+    if buf.ends_with_newline() {
+        buf.indent(indent);
+    } else {
+        buf.spaces(1);
+    }
+
+    buf.push_str(token);
+}
+
 fn fmt_dbg<'a>(
     buf: &mut Buf,
     condition: &'a Loc<Expr<'a>>,
@@ -1328,6 +1351,87 @@ fn fmt_backpassing<'a>(
     loc_ret.format_with_options(buf, Parens::NotNeeded, Newlines::Yes, indent);
 }
 
+/// The call that `loc_patterns <- loc_body` followed by `loc_ret` desugars to, e.g.
+/// `Task.await foo \x -> rest`, or `None` if it can't be written that way without
+/// losing comments or needing parentheses.
+fn backpassing_as_call<'a>(
+    arena: &'a Bump,
+    loc_patterns: &'a [Loc<Pattern<'a>>],
+    loc_body: &'a Loc<Expr<'a>>,
+    loc_ret: &'a Loc<Expr<'a>>,
+) -> Option<Expr<'a>> {
+    use self::Expr::*;
+
+    // The call starts where the backpassing did, so newlines before the body don't carry over.
+    let body: &Loc<Expr> = match &loc_body.value {
+        SpaceBefore(sub_expr, spaces) if spaces.iter().all(|space| space.is_newline()) => {
+            arena.alloc(Loc::at(loc_body.region, **sub_expr))
+        }
+        SpaceBefore(..) | SpaceAfter(..) => return None,
+        _ => loc_body,
+    };
+
+    // The rest of the block becomes the body of the closure, which starts on the next line.
+    let ret = match &loc_ret.value {
+        SpaceBefore(sub_expr, spaces) => {
+            let first_comment = spaces
+                .iter()
+                .position(|space| !space.is_newline())
+                .unwrap_or(spaces.len());
+
+            Loc::at(
+                loc_ret.region,
+                SpaceBefore(*sub_expr, &spaces[first_comment.saturating_sub(1)..]),
+            )
+        }
+        _ => *loc_ret,
+    };
+    let closure = Loc::at(loc_ret.region, Closure(loc_patterns, arena.alloc(ret)));
+
+    with_last_argument(arena, body, arena.alloc(closure))
+}
+
+/// `loc_expr` with another argument after the ones it's already called with. Pipelines get it
+/// in their last function, since that's where desugaring `|>` puts the argument too.
+fn with_last_argument<'a>(
+    arena: &'a Bump,
+    loc_expr: &'a Loc<Expr<'a>>,
+    loc_arg: &'a Loc<Expr<'a>>,
+) -> Option<Expr<'a>> {
+    use self::Expr::*;
+
+    match &loc_expr.value {
+        Apply(loc_function, loc_args, called_via) => {
+            let mut args = Vec::with_capacity_in(loc_args.len() + 1, arena);
+
+            args.extend(loc_args.iter().copied());
+            args.push(loc_arg);
+
+            Some(Apply(*loc_function, args.into_bump_slice(), *called_via))
+        }
+        Var { .. } => Some(Apply(
+            loc_expr,
+            arena.alloc([loc_arg]),
+            called_via::CalledVia::Space,
+        )),
+        BinOps(lefts, right) => match lefts.last() {
+            Some((_, loc_op)) if loc_op.value == BinOp::Pizza => {
+                let call = with_last_argument(arena, right, loc_arg)?;
+
+                Some(BinOps(*lefts, arena.alloc(Loc::at(right.region, call))))
+            }
+            _ => None,
+        },
+        SpaceBefore(sub_expr, spaces) => {
+            let loc_sub_expr = arena.alloc(Loc::at(loc_expr.region, **sub_expr));
+            let sub_expr = with_last_argument(arena, loc_sub_expr, loc_arg)?;
+
+            Some(SpaceBefore(arena.alloc(sub_expr), *spaces))
+        }
+        _ => None,
+    }
+}
+
 fn pattern_needs_parens_when_backpassing(pat: &Pattern) -> bool {
     match pat {
         Pattern::Apply(_, _) => true,
@@ -1347,7 +1451,7 @@ fn fmt_record_like<'a, Field, Format, ToSpaceBefore>(
     to_space_before: ToSpaceBefore,
 ) where
     Field: Formattable,
-    Format: Fn(&mut Buf, &Field, u16, &str),
+    Format: Fn(&mut Buf, &Field, u16, &str, bool),
     ToSpaceBefore: Fn(&'a Field) -> Option<(&'a Field, &'a [CommentOrNewline<'a>])>,
 {
     let loc_fields = fields.items;
@@ -1356,6 +1460,17 @@ fn fmt_record_like<'a, Field, Format, ToSpaceBefore>(
     if loc_fields.is_empty() && final_comments.iter().all(|c| c.is_newline()) && update.is_none() {
         buf.push_str("{}");
     } else {
+        let is_multiline = loc_fields.iter().any(|loc_field| loc_field.is_multiline())
+            || !final_comments.is_empty()
+            || !buf
+                .fits_on_line(|buf| fmt_record_like_on_one_line(buf, update, loc_fields, indent));
+
+        if !is_multiline {
+            fmt_record_like_on_one_line(buf, update, loc_fields, indent);
+
+            return;
+        }
+
         buf.push('{');
 
         match update {
@@ -1371,64 +1486,42 @@ fn fmt_record_like<'a, Field, Format, ToSpaceBefore>(
             }
         }
 
-        let is_multiline = loc_fields.iter().any(|loc_field| loc_field.is_multiline())
-            || !final_comments.is_empty();
-
-        if is_multiline {
-            let field_indent = indent + INDENT;
-            for (index, field) in loc_fields.iter().enumerate() {
-                // comma addition is handled by the `format_field_multiline` function
-                // since we can have stuff like:
-                // { x # comment
-                // , y
-                // }
-                // In this case, we have to move the comma before the comment.
-
-                let is_first_item = index == 0;
-                if let Some((_sub_field, spaces)) = to_space_before(&field.value) {
-                    let is_only_newlines = spaces.iter().all(|s| s.is_newline());
-                    if !is_first_item
-                        && !is_only_newlines
-                        && count_leading_newlines(spaces.iter()) > 1
-                    {
-                        buf.newline();
-                    }
+        let field_indent = indent + INDENT;
+        for (index, field) in loc_fields.iter().enumerate() {
+            // comma addition is handled by the `format_field_multiline` function
+            // since we can have stuff like:
+            // { x # comment
+            // , y
+            // }
+            // In this case, we have to move the comma before the comment.
+
+            let is_first_item = index == 0;
+            if let Some((_sub_field, spaces)) = to_space_before(&field.value) {
+                let is_only_newlines = spaces.iter().all(|s| s.is_newline());
+                if !is_first_item && !is_only_newlines && count_leading_newlines(spaces.iter()) > 1
+                {
+                    buf.newline();
+                }
 
-                    fmt_comments_only(buf, spaces.iter(), NewlineAt::Top, field_indent);
+                fmt_comments_only(buf, spaces.iter(), NewlineAt::Top, field_indent);
 
-                    if !is_only_newlines && count_leading_newlines(spaces.iter().rev()) > 0 {
-                        buf.newline();
-                    }
+                if !is_only_newlines && count_leading_newlines(spaces.iter().rev()) > 0 {
+                    buf.newline();
                 }
-
-                format_field_multiline(buf, &field.value, field_indent, "");
             }
 
-            if count_leading_newlines(final_comments.iter()) > 1 {
-                buf.newline();
-            }
+            let is_last = index + 1 == loc_fields.len();
 
-            fmt_comments_only(buf, final_comments.iter(), NewlineAt::Top, field_indent);
+            format_field_multiline(buf, &field.value, field_indent, "", is_last);
+        }
 
+        if count_leading_newlines(final_comments.iter()) > 1 {
             buf.newline();
-        } else {
-            // is_multiline == false
-            buf.spaces(1);
-            let field_indent = indent;
-            let mut iter = loc_fields.iter().peekable();
-            while let Some(field) = iter.next() {
-                field.format_with_options(buf, Parens::NotNeeded, Newlines::No, field_indent);
+        }
 
-                if iter.peek().is_some() {
-                    buf.push_str(",");
-                    buf.spaces(1);
-                }
-            }
-            buf.spaces(1);
-            // if we are here, that means that `final_comments` is empty, thus we don't have
-            // to add a comment. Anyway, it is not possible to have a single line record with
-            // a comment in it.
-        };
+        fmt_comments_only(buf, final_comments.iter(), NewlineAt::Top, field_indent);
+
+        buf.newline();
 
         // closes the initial bracket
         buf.indent(indent);
@@ -1436,15 +1529,76 @@ fn fmt_record_like<'a, Field, Format, ToSpaceBefore>(
     }
 }
 
-fn format_assigned_field_multiline<T>(
+fn fmt_record_like_on_one_line<'a, Field: Formattable>(
     buf: &mut Buf,
-    field: &AssignedField<T>,
+    update: Option<&'a Loc<Expr<'a>>>,
+    loc_fields: &[Loc<Field>],
+    indent: u16,
+) {
+    buf.indent(indent);
+    buf.push('{');
+
+    if let Some(record_var) = update {
+        buf.spaces(1);
+        record_var.format(buf, indent);
+        buf.push_str(" &");
+    }
+
+    buf.spaces(1);
+    let mut iter = loc_fields.iter().peekable();
+    while let Some(field) = iter.next() {
+        field.format_with_options(buf, Parens::NotNeeded, Newlines::No, indent);
+
+        if iter.peek().is_some() {
+            buf.push_str(",");
+            buf.spaces(1);
+        }
+    }
+    buf.spaces(1);
+    // if we are here, that means that the record has no comments in it, because it's not
+    // possible to have a single line record with a comment in it.
+
+    buf.push('}');
+}
+
+/// If this field is `label: label`, returns the label, so the field can be written
+/// using the `{ label }` shorthand instead.
+pub(crate) fn punned_field<'a>(field: &AssignedField<'a, Expr<'a>>) -> Option<Loc<&'a str>> {
+    match field {
+        AssignedField::RequiredValue(label, spaces, value) if spaces.is_empty() => {
+            match value.value {
+                Expr::Var {
+                    module_name: "",
+                    ident,
+                } if ident == label.value => Some(*label),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn format_assigned_field_multiline<'a>(
+    buf: &mut Buf,
+    field: &AssignedField<'a, Expr<'a>>,
     indent: u16,
     separator_prefix: &str,
-) where
-    T: Formattable,
-{
+    is_last: bool,
+) {
     use self::AssignedField::*;
+
+    if buf.options().pun_record_fields {
+        if let Some(label) = punned_field(field) {
+            return format_assigned_field_multiline(
+                buf,
+                &LabelOnly(label),
+                indent,
+                separator_prefix,
+                is_last,
+            );
+        }
+    }
+
     match field {
         RequiredValue(name, spaces, ann) => {
             buf.newline();
@@ -1460,7 +1614,7 @@ fn format_assigned_field_multiline<T>(
             buf.push_str(":");
             buf.spaces(1);
             ann.value.format(buf, indent);
-            buf.push(',');
+            push_multiline_comma(buf, is_last);
         }
         OptionalValue(name, spaces, ann) => {
             buf.newline();
@@ -1476,13 +1630,13 @@ fn format_assigned_field_multiline<T>(
             buf.push_str("?");
             buf.spaces(1);
             ann.value.format(buf, indent);
-            buf.push(',');
+            push_multiline_comma(buf, is_last);
         }
         LabelOnly(name) => {
             buf.newline();
             buf.indent(indent);
             buf.push_str(name.value);
-            buf.push(',');
+            push_multiline_comma(buf, is_last);
         }
         AssignedField::SpaceBefore(sub_field, _spaces) => {
             // We have something like that:
@@ -1492,7 +1646,7 @@ fn format_assigned_field_multiline<T>(
             // ```
             // we'd like to preserve this
 
-            format_assigned_field_multiline(buf, sub_field, indent, separator_prefix);
+            format_assigned_field_multiline(buf, sub_field, indent, separator_prefix, is_last);
         }
         AssignedField::SpaceAfter(sub_field, spaces) => {
             // We have something like that:
@@ -1506,7 +1660,7 @@ fn format_assigned_field_multiline<T>(
             // # comment
             // otherfield
             // ```
-            format_assigned_field_multiline(buf, sub_field, indent, separator_prefix);
+            format_assigned_field_multiline(buf, sub_field, indent, separator_prefix, is_last);
             fmt_comments_only(buf, spaces.iter(), NewlineAt::Top, indent);
         }
         Malformed(raw) => {
@@ -1529,6 +1683,7 @@ fn format_record_builder_field_multiline(
     field: &RecordBuilderField,
     indent: u16,
     separator_prefix: &str,
+    is_last: bool,
 ) {
     use self::RecordBuilderField::*;
     match field {
@@ -1553,7 +1708,7 @@ fn format_record_builder_field_multiline(
                 ann.value.format(buf, indent);
             }
 
-            buf.push(',');
+            push_multiline_comma(buf, is_last);
         }
         ApplyValue(name, colon_spaces, arrow_spaces, ann) => {
             buf.newline();
@@ -1583,13 +1738,13 @@ fn format_record_builder_field_multiline(
                 buf.spaces(1);
                 ann.value.format(buf, indent);
             }
-            buf.push(',');
+            push_multiline_comma(buf, is_last);
         }
         LabelOnly(name) => {
             buf.newline();
             buf.indent(indent);
             buf.push_str(name.value);
-            buf.push(',');
+            push_multiline_comma(buf, is_last);
         }
         SpaceBefore(sub_field, _spaces) => {
             // We have something like that:
@@ -1599,7 +1754,13 @@ fn format_record_builder_field_multiline(
             // ```
             // we'd like to preserve this
 
-            format_record_builder_field_multiline(buf, sub_field, indent, separator_prefix);
+            format_record_builder_field_multiline(
+                buf,
+                sub_field,
+                indent,
+                separator_prefix,
+                is_last,
+            );
         }
         SpaceAfter(sub_field, spaces) => {
             // We have something like that:
@@ -1613,7 +1774,13 @@ fn format_record_builder_field_multiline(
             // # comment
             // otherfield
             // ```
-            format_record_builder_field_multiline(buf, sub_field, indent, separator_prefix);
+            format_record_builder_field_multiline(
+                buf,
+                sub_field,
+                indent,
+                separator_prefix,
+                is_last,
+            );
             fmt_comments_only(buf, spaces.iter(), NewlineAt::Top, indent);
         }
         Malformed(raw) => {
@@ -19,24 +19,178 @@ pub struct Ast<'a> {
     pub defs: roc_parse::ast::Defs<'a>,
 }
 
+/// Comments at the top of a module that start with this (after the `#`) hold formatter
+/// settings, e.g. `# roc-fmt: max-width = 100, trailing-commas = false`
+pub const CONFIG_COMMENT: &str = "roc-fmt:";
+
+/// Changes the formatter can make to the standard formatting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Write record fields like `{ name: name }` using the shorthand `{ name }`,
+    /// both in record expressions and in record destructures.
+    pub pun_record_fields: bool,
+    /// How many spaces each level of indentation is.
+    pub indent_width: u16,
+    /// Break collections and records that would make their line wider than this over
+    /// multiple lines. Without a maximum, they're only multiline if they already were.
+    pub max_width: Option<usize>,
+    /// Whether the last item in a multiline collection or record gets a comma after it.
+    pub trailing_commas: bool,
+    /// Deprecated syntax to rewrite into its modern equivalent while formatting.
+    pub migrations: Migrations,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            pun_record_fields: false,
+            indent_width: spaces::INDENT,
+            max_width: None,
+            trailing_commas: true,
+            migrations: Migrations::default(),
+        }
+    }
+}
+
+/// Rewrites from deprecated syntax to its modern equivalent, which each have to be asked for.
+/// Unlike the rest of formatting, these change the parse tree, so the formatter checks
+/// that the code still means the same thing once it's desugared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Migrations {
+    /// `x <- f a` followed by `rest` becomes `f a \x -> rest`.
+    pub backpassing: bool,
+}
+
+impl Migrations {
+    /// The names of the migrations, as they're given to `roc format --migrate`.
+    pub const NAMES: &'static [&'static str] = &["backpassing"];
+
+    pub fn enable(&mut self, name: &str) -> Result<(), String> {
+        match name {
+            "backpassing" => self.backpassing = true,
+            _ => {
+                return Err(format!(
+                    "`{name}` isn't a migration. The migrations are {}.",
+                    Self::NAMES.join(", ")
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether any migration is enabled.
+    pub fn any(&self) -> bool {
+        let Migrations { backpassing } = *self;
+
+        backpassing
+    }
+}
+
+impl FormatOptions {
+    /// These options, with the settings from any `# roc-fmt:` comments at the top of `src`
+    /// (before its header) applied on top of them.
+    pub fn with_config_from(mut self, src: &str) -> Result<Self, String> {
+        for line in src.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let Some(comment) = line.strip_prefix('#') else {
+                // This is the header, and the settings only go before it.
+                break;
+            };
+            let Some(settings) = comment.trim_start().strip_prefix(CONFIG_COMMENT) else {
+                continue;
+            };
+
+            for setting in settings.split(',').map(str::trim) {
+                if setting.is_empty() {
+                    continue;
+                }
+
+                match setting.split_once('=') {
+                    Some((key, value)) => self.set(key.trim(), value.trim())?,
+                    None => {
+                        return Err(format!(
+                            "The roc-fmt setting `{setting}` needs a value, like `{setting} = ...`"
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let invalid = |expected: &str| {
+            format!("The roc-fmt setting `{key}` must be {expected}, not `{value}`")
+        };
+        let parse_bool = |value: &str| match value {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(invalid("`true` or `false`")),
+        };
+
+        match key {
+            "indent" => {
+                self.indent_width = value
+                    .parse()
+                    .ok()
+                    .filter(|width| (1..=8).contains(width))
+                    .ok_or_else(|| invalid("a number of spaces from 1 to 8"))?;
+            }
+            "max-width" => {
+                self.max_width = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|&width: &usize| width > 0)
+                        .ok_or_else(|| invalid("a number of columns"))?,
+                );
+            }
+            "trailing-commas" => self.trailing_commas = parse_bool(value)?,
+            "pun-record-fields" => self.pun_record_fields = parse_bool(value)?,
+            _ => {
+                return Err(format!(
+                    "`{key}` isn't a roc-fmt setting. The settings are indent, max-width, trailing-commas, and pun-record-fields."
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct Buf<'a> {
     text: String<'a>,
     spaces_to_flush: usize,
     newlines_to_flush: usize,
     beginning_of_line: bool,
+    options: FormatOptions,
 }
 
 impl<'a> Buf<'a> {
     pub fn new_in(arena: &'a Bump) -> Buf<'a> {
+        Self::new_in_with_options(arena, FormatOptions::default())
+    }
+
+    pub fn new_in_with_options(arena: &'a Bump, options: FormatOptions) -> Buf<'a> {
         Buf {
             text: String::new_in(arena),
             spaces_to_flush: 0,
             newlines_to_flush: 0,
             beginning_of_line: true,
+            options,
         }
     }
 
+    pub fn options(&self) -> FormatOptions {
+        self.options
+    }
+
     pub fn as_str(&'a self) -> &'a str {
         self.text.as_str()
     }
@@ -47,11 +201,52 @@ impl<'a> Buf<'a> {
 
     pub fn indent(&mut self, indent: u16) {
         if self.beginning_of_line {
-            self.spaces_to_flush = indent as usize;
+            // The formatter counts indentation in steps of INDENT, which the options can make
+            // wider or narrower.
+            let steps = indent / spaces::INDENT;
+            let rest = indent % spaces::INDENT;
+
+            self.spaces_to_flush = (steps * self.options.indent_width + rest) as usize;
         }
         self.beginning_of_line = false;
     }
 
+    /// Whether what `format` writes fits on the current line without going past the
+    /// maximum width, if there is one. `format` should write everything on one line,
+    /// which is what gets measured; anything nested in it is written on one line too.
+    pub fn fits_on_line(&self, format: impl FnOnce(&mut Buf<'a>)) -> bool {
+        let Some(max_width) = self.options.max_width else {
+            return true;
+        };
+
+        let options = FormatOptions {
+            max_width: None,
+            ..self.options
+        };
+        let mut measured = Buf::new_in_with_options(self.text.bump(), options);
+
+        measured.beginning_of_line = self.beginning_of_line;
+        format(&mut measured);
+        measured.flush_spaces();
+
+        if measured.text.contains('\n') {
+            return false;
+        }
+
+        let column = if self.beginning_of_line {
+            0
+        } else if self.newlines_to_flush > 0 {
+            self.spaces_to_flush
+        } else {
+            let text = self.text.as_str();
+            let line_start = text.rfind('\n').map_or(0, |index| index + 1);
+
+            text[line_start..].chars().count() + self.spaces_to_flush
+        };
+
+        column + measured.text.chars().count() <= max_width
+    }
+
     pub fn push(&mut self, ch: char) {
         debug_assert!(!self.beginning_of_line);
         debug_assert!(
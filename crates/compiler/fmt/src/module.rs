@@ -7,10 +7,11 @@ use crate::Buf;
 use bumpalo::Bump;
 use roc_parse::ast::{Collection, Header, Module, Spaced, Spaces};
 use roc_parse::header::{
-    AppHeader, ExposedName, ExposesKeyword, GeneratesKeyword, HostedHeader, ImportsEntry,
-    ImportsKeyword, InterfaceHeader, Keyword, KeywordItem, ModuleName, PackageEntry, PackageHeader,
-    PackageKeyword, PackageName, PackagesKeyword, PlatformHeader, PlatformRequires,
-    ProvidesKeyword, ProvidesTo, RequiresKeyword, To, ToKeyword, TypedIdent, WithKeyword,
+    AppHeader, DocsEntry, DocsKey, DocsKeyword, ExposedName, ExposesKeyword, GeneratesKeyword,
+    HostedHeader, ImportsEntry, ImportsKeyword, InterfaceHeader, Keyword, KeywordItem, ModuleName,
+    PackageEntry, PackageHeader, PackageKeyword, PackageName, PackagesKeyword, PlatformHeader,
+    PlatformRequires, ProvidesKeyword, ProvidesTo, RequiresKeyword, To, ToKeyword, TypedIdent,
+    WithKeyword,
 };
 use roc_parse::ident::UppercaseIdent;
 use roc_region::all::Loc;
@@ -75,6 +76,7 @@ keywords! {
     RequiresKeyword,
     ProvidesKeyword,
     ToKeyword,
+    DocsKeyword,
 }
 
 impl<V: Formattable> Formattable for Option<V> {
@@ -235,6 +237,10 @@ pub fn fmt_package_header<'a>(buf: &mut Buf, header: &'a PackageHeader<'a>) {
     fmt_exposes(buf, header.exposes.item, indent);
     header.packages.keyword.format(buf, indent);
     fmt_packages(buf, header.packages.item, indent);
+    if let Some(docs) = &header.docs {
+        docs.keyword.format(buf, indent);
+        fmt_collection(buf, indent, Braces::Curly, docs.item, Newlines::No);
+    }
 }
 
 pub fn fmt_platform_header<'a>(buf: &mut Buf, header: &'a PlatformHeader<'a>) {
@@ -446,6 +452,22 @@ impl<'a> Formattable for PackageEntry<'a> {
     }
 }
 
+impl<'a> Formattable for DocsEntry<'a> {
+    fn is_multiline(&self) -> bool {
+        false
+    }
+
+    fn format_with_options(
+        &self,
+        buf: &mut Buf,
+        _parens: Parens,
+        _newlines: Newlines,
+        indent: u16,
+    ) {
+        fmt_docs_entry(buf, self, indent);
+    }
+}
+
 impl<'a> Formattable for ImportsEntry<'a> {
     fn is_multiline(&self) -> bool {
         false
@@ -468,6 +490,16 @@ fn fmt_packages_entry(buf: &mut Buf, entry: &PackageEntry, indent: u16) {
     fmt_package_name(buf, entry.package_name.value, indent);
 }
 
+fn fmt_docs_entry(buf: &mut Buf, entry: &DocsEntry, indent: u16) {
+    match entry.key {
+        DocsKey::Setting(name) => buf.push_str(name),
+        DocsKey::LinkText(text) => fmt_package_name(buf, PackageName::from(text), indent),
+    }
+    buf.push(':');
+    fmt_default_spaces(buf, entry.spaces_after_key, indent);
+    fmt_package_name(buf, PackageName::from(entry.value.value), indent);
+}
+
 fn fmt_imports_entry(buf: &mut Buf, entry: &ImportsEntry, indent: u16) {
     use roc_parse::header::ImportsEntry::*;
 
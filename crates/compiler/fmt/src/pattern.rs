@@ -1,4 +1,5 @@
-use crate::annotation::{Formattable, Newlines, Parens};
+use crate::annotation::{is_collection_multiline, Formattable, Newlines, Parens};
+use crate::collection::{fmt_collection, Braces};
 use crate::expr::{fmt_str_literal, format_sq_literal};
 use crate::spaces::{fmt_comments_only, fmt_spaces, NewlineAt, INDENT};
 use crate::Buf;
@@ -47,7 +48,7 @@ impl<'a> Formattable for Pattern<'a> {
                 spaces.iter().any(|s| s.is_comment())
             }
 
-            Pattern::RecordDestructure(fields) => fields.iter().any(|f| f.is_multiline()),
+            Pattern::RecordDestructure(fields) => is_collection_multiline(fields),
             Pattern::RequiredField(_, subpattern) => subpattern.is_multiline(),
 
             Pattern::OptionalField(_, expr) => expr.is_multiline(),
@@ -60,6 +61,8 @@ impl<'a> Formattable for Pattern<'a> {
                 }
             },
 
+            Pattern::StrPrefix(_, rest) => rest.is_multiline(),
+
             Pattern::Identifier(_)
             | Pattern::Tag(_)
             | Pattern::OpaqueRef(_)
@@ -74,9 +77,7 @@ impl<'a> Formattable for Pattern<'a> {
             | Pattern::MalformedIdent(_, _)
             | Pattern::QualifiedIdentifier { .. } => false,
 
-            Pattern::Tuple(patterns) | Pattern::List(patterns) => {
-                patterns.iter().any(|p| p.is_multiline())
-            }
+            Pattern::Tuple(patterns) | Pattern::List(patterns) => is_collection_multiline(patterns),
         }
     }
 
@@ -114,32 +115,20 @@ impl<'a> Formattable for Pattern<'a> {
                 }
             }
             RecordDestructure(loc_patterns) => {
-                buf.indent(indent);
-                buf.push_str("{");
-
-                if !loc_patterns.is_empty() {
-                    buf.spaces(1);
-                    let mut it = loc_patterns.iter().peekable();
-                    while let Some(loc_pattern) = it.next() {
-                        loc_pattern.format(buf, indent);
-
-                        if it.peek().is_some() {
-                            buf.push_str(",");
-                            buf.spaces(1);
-                        }
-                    }
-                    buf.spaces(1);
-                }
-
-                buf.push_str("}");
+                fmt_collection(buf, indent, Braces::Curly, *loc_patterns, Newlines::No);
             }
 
             RequiredField(name, loc_pattern) => {
                 buf.indent(indent);
                 buf.push_str(name);
-                buf.push_str(":");
-                buf.spaces(1);
-                loc_pattern.format(buf, indent);
+
+                let is_pun = matches!(loc_pattern.value, Identifier(ident) if ident == *name);
+
+                if !(is_pun && buf.options().pun_record_fields) {
+                    buf.push_str(":");
+                    buf.spaces(1);
+                    loc_pattern.format(buf, indent);
+                }
             }
 
             OptionalField(name, loc_pattern) => {
@@ -178,6 +167,25 @@ impl<'a> Formattable for Pattern<'a> {
                 buf.push_str(string);
             }
             StrLiteral(literal) => fmt_str_literal(buf, *literal, indent),
+            StrPrefix(prefix, rest) => {
+                // As a tag argument, `Ok ("fn " .. rest)` needs its parens back
+                let parens = parens == Parens::InApply;
+
+                if parens {
+                    buf.indent(indent);
+                    buf.push('(');
+                }
+
+                fmt_str_literal(buf, *prefix, indent);
+                buf.spaces(1);
+                buf.push_str("..");
+                buf.spaces(1);
+                rest.format(buf, indent);
+
+                if parens {
+                    buf.push(')');
+                }
+            }
             SingleQuote(string) => {
                 buf.indent(indent);
                 format_sq_literal(buf, string);
@@ -188,36 +196,10 @@ impl<'a> Formattable for Pattern<'a> {
                 buf.push_str(name);
             }
             Tuple(loc_patterns) => {
-                buf.indent(indent);
-                buf.push_str("(");
-
-                let mut it = loc_patterns.iter().peekable();
-                while let Some(loc_pattern) = it.next() {
-                    loc_pattern.format(buf, indent);
-
-                    if it.peek().is_some() {
-                        buf.push_str(",");
-                        buf.spaces(1);
-                    }
-                }
-
-                buf.push_str(")");
+                fmt_collection(buf, indent, Braces::Round, *loc_patterns, Newlines::No);
             }
             List(loc_patterns) => {
-                buf.indent(indent);
-                buf.push_str("[");
-
-                let mut it = loc_patterns.iter().peekable();
-                while let Some(loc_pattern) = it.next() {
-                    loc_pattern.format(buf, indent);
-
-                    if it.peek().is_some() {
-                        buf.push_str(",");
-                        buf.spaces(1);
-                    }
-                }
-
-                buf.push_str("]");
+                fmt_collection(buf, indent, Braces::Square, *loc_patterns, Newlines::No);
             }
             ListRest(opt_pattern_as) => {
                 buf.indent(indent);
@@ -9,15 +9,16 @@ use roc_parse::{
         TypeDef, TypeHeader, ValueDef, WhenBranch,
     },
     header::{
-        AppHeader, ExposedName, HostedHeader, ImportsEntry, InterfaceHeader, KeywordItem,
-        ModuleName, PackageEntry, PackageHeader, PackageName, PlatformHeader, PlatformRequires,
-        ProvidesTo, To, TypedIdent,
+        AppHeader, DocsEntry, ExposedName, HostedHeader, ImportsEntry, InterfaceHeader,
+        KeywordItem, ModuleName, PackageEntry, PackageHeader, PackageName, PlatformHeader,
+        PlatformRequires, ProvidesTo, To, TypedIdent,
     },
+    highlight::{highlight, Token},
     ident::{BadIdent, UppercaseIdent},
 };
 use roc_region::all::{Loc, Position, Region};
 
-use crate::{Ast, Buf};
+use crate::{expr::punned_field, Ast, Buf};
 
 /// The number of spaces to indent.
 pub const INDENT: u16 = 4;
@@ -185,6 +186,15 @@ where
     count
 }
 
+/// How many comments (doc comments included) there are in some Roc source. The formatter
+/// has to emit every comment it's given somewhere, so formatting mustn't change this.
+pub fn count_comments(src: &str) -> usize {
+    highlight(src)
+        .iter()
+        .filter(|token| matches!(token.value, Token::LineComment | Token::DocComment))
+        .count()
+}
+
 fn fmt_docs(buf: &mut Buf, docs: &str) {
     // The "##" in a doc comment should always be preceded by a newline or a space,
     // unless it's the very beginning of the buffer.
@@ -206,9 +216,8 @@ fn fmt_docs(buf: &mut Buf, docs: &str) {
 /// * Removing comments
 /// * Removing parens in Exprs
 ///
-/// Long term, we actuall want this transform to preserve comments (so we can assert they're maintained by formatting)
-/// - but there are currently several bugs where they're _not_ preserved.
-/// TODO: ensure formatting retains comments
+/// Comments aren't compared here, because formatting can move them (e.g. from after a comma
+/// to before it). Instead, [count_comments] checks that none of them went missing.
 pub trait RemoveSpaces<'a> {
     fn remove_spaces(&self, arena: &'a Bump) -> Self;
 }
@@ -298,6 +307,7 @@ impl<'a> RemoveSpaces<'a> for Module<'a> {
                 name: header.name.remove_spaces(arena),
                 exposes: header.exposes.remove_spaces(arena),
                 packages: header.packages.remove_spaces(arena),
+                docs: header.docs.remove_spaces(arena),
             }),
             Header::Platform(header) => Header::Platform(PlatformHeader {
                 before_name: &[],
@@ -408,6 +418,16 @@ impl<'a> RemoveSpaces<'a> for PackageEntry<'a> {
     }
 }
 
+impl<'a> RemoveSpaces<'a> for DocsEntry<'a> {
+    fn remove_spaces(&self, arena: &'a Bump) -> Self {
+        DocsEntry {
+            key: self.key,
+            spaces_after_key: &[],
+            value: self.value.remove_spaces(arena),
+        }
+    }
+}
+
 impl<'a> RemoveSpaces<'a> for ImportsEntry<'a> {
     fn remove_spaces(&self, arena: &'a Bump) -> Self {
         match *self {
@@ -449,6 +469,22 @@ impl<'a, T: RemoveSpaces<'a>> RemoveSpaces<'a> for Collection<'a, T> {
     }
 }
 
+/// `{ x: x }` is the same record as `{ x }`, so write both the same way; this lets
+/// the formatter introduce the shorthand without changing the normalized tree.
+fn remove_puns<'a>(
+    arena: &'a Bump,
+    fields: Collection<'a, Loc<AssignedField<'a, Expr<'a>>>>,
+) -> Collection<'a, Loc<AssignedField<'a, Expr<'a>>>> {
+    let mut items = Vec::with_capacity_in(fields.items.len(), arena);
+    for field in fields.items {
+        items.push(match punned_field(&field.value) {
+            Some(label) => Loc::at(field.region, AssignedField::LabelOnly(label)),
+            None => *field,
+        });
+    }
+    Collection::with_items(items.into_bump_slice())
+}
+
 impl<'a, T: RemoveSpaces<'a> + std::fmt::Debug> RemoveSpaces<'a> for &'a [T] {
     fn remove_spaces(&self, arena: &'a Bump) -> Self {
         let mut items = Vec::with_capacity_in(self.len(), arena);
@@ -682,9 +718,9 @@ impl<'a> RemoveSpaces<'a> for Expr<'a> {
             Expr::List(a) => Expr::List(a.remove_spaces(arena)),
             Expr::RecordUpdate { update, fields } => Expr::RecordUpdate {
                 update: arena.alloc(update.remove_spaces(arena)),
-                fields: fields.remove_spaces(arena),
+                fields: remove_puns(arena, fields.remove_spaces(arena)),
             },
-            Expr::Record(a) => Expr::Record(a.remove_spaces(arena)),
+            Expr::Record(a) => Expr::Record(remove_puns(arena, a.remove_spaces(arena))),
             Expr::RecordBuilder(a) => Expr::RecordBuilder(a.remove_spaces(arena)),
             Expr::Tuple(a) => Expr::Tuple(a.remove_spaces(arena)),
             Expr::Var { module_name, ident } => Expr::Var { module_name, ident },
@@ -792,7 +828,13 @@ impl<'a> RemoveSpaces<'a> for Pattern<'a> {
             ),
             Pattern::RecordDestructure(a) => Pattern::RecordDestructure(a.remove_spaces(arena)),
             Pattern::RequiredField(a, b) => {
-                Pattern::RequiredField(a, arena.alloc(b.remove_spaces(arena)))
+                let b = b.remove_spaces(arena);
+
+                match b.value {
+                    // `{ a: a }` destructures the same way as `{ a }`
+                    Pattern::Identifier(ident) if ident == a => Pattern::Identifier(ident),
+                    _ => Pattern::RequiredField(a, arena.alloc(b)),
+                }
             }
             Pattern::OptionalField(a, b) => {
                 Pattern::OptionalField(a, arena.alloc(b.remove_spaces(arena)))
@@ -812,6 +854,9 @@ impl<'a> RemoveSpaces<'a> for Pattern<'a> {
             },
             Pattern::FloatLiteral(a) => Pattern::FloatLiteral(a),
             Pattern::StrLiteral(a) => Pattern::StrLiteral(a),
+            Pattern::StrPrefix(a, rest) => {
+                Pattern::StrPrefix(a, arena.alloc(rest.remove_spaces(arena)))
+            }
             Pattern::Underscore(a) => Pattern::Underscore(a),
             Pattern::Malformed(a) => Pattern::Malformed(a),
             Pattern::MalformedIdent(a, b) => Pattern::MalformedIdent(a, remove_spaces_bad_ident(b)),
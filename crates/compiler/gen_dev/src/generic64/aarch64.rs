@@ -1028,8 +1028,16 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
             Self::sub_reg64_reg64_imm32(buf, dst, src, -imm32);
         } else if imm32 < 0xFFF {
             add_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
+        } else if imm32 <= 0xFF_FFFF {
+            // Split the immediate in two halves. Unlike adding a register, this also works
+            // when src is the stack pointer (e.g. when setting up a large stack frame).
+            add_reg64_reg64_imm12(buf, dst, src, (imm32 & 0xFFF) as u16);
+            add_reg64_reg64_imm12_lsl12(buf, dst, dst, (imm32 >> 12) as u16);
         } else {
-            todo!("immediate additions with values greater than 12bits");
+            let tmp = AArch64GeneralReg::X15;
+            debug_assert_ne!(src, tmp);
+            Self::mov_reg64_imm64(buf, tmp, imm32 as i64);
+            add_reg64_reg64_reg64_uxtx(buf, dst, src, tmp);
         }
     }
     #[inline(always)]
@@ -1402,13 +1410,11 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     ) {
         if (-256..256).contains(&offset) {
             ldur_reg_reg_imm9(buf, register_width, dst, src, offset as i16);
-        } else if (0..=u16::MAX as i32).contains(&offset) {
+        } else if (0..=0xFFF << 3).contains(&offset) {
             debug_assert!(offset % 8 == 0);
             ldr_reg_reg_imm12(buf, register_width, dst, src, (offset as u16) >> 3);
         } else {
-            let tmp = AArch64GeneralReg::X15;
-            Self::mov_reg64_imm64(buf, tmp, offset as i64);
-            Self::add_reg64_reg64_reg64(buf, tmp, tmp, src);
+            let tmp = Self::offset_address(buf, src, offset);
             ldr_reg_reg_imm12(buf, register_width, dst, tmp, 0);
         }
     }
@@ -1460,13 +1466,11 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     ) {
         if (-256..256).contains(&offset) {
             stur_reg_reg_imm9(buf, register_width, src, dst, offset as i16);
-        } else if (0..=u16::MAX as i32).contains(&offset) {
+        } else if (0..=0xFFF << 3).contains(&offset) {
             debug_assert_eq!(offset % 8, 0);
             str_reg_reg_imm12(buf, register_width, src, dst, (offset as u16) >> 3);
         } else {
-            let tmp = AArch64GeneralReg::X15;
-            Self::mov_reg64_imm64(buf, tmp, offset as i64);
-            Self::add_reg64_reg64_reg64(buf, tmp, tmp, dst);
+            let tmp = Self::offset_address(buf, dst, offset);
             str_reg_reg_imm12(buf, register_width, src, tmp, 0);
         }
     }
@@ -1478,15 +1482,13 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         offset: i32,
         src: AArch64FloatReg,
     ) {
-        if offset < 0 {
+        if (-256..0).contains(&offset) {
             stur_freg64_reg64_imm9(buf, src, dst, offset as i16)
-        } else if (0..=u16::MAX as i32).contains(&offset) {
+        } else if (0..=0xFFF << 3).contains(&offset) {
             debug_assert!(offset % 8 == 0);
             str_freg64_reg64_imm12(buf, src, dst, (offset as u16) >> 3);
         } else {
-            let tmp = AArch64GeneralReg::X15;
-            Self::mov_reg64_imm64(buf, tmp, offset as i64);
-            Self::add_reg64_reg64_reg64(buf, tmp, tmp, dst);
+            let tmp = Self::offset_address(buf, dst, offset);
             str_freg64_reg64_imm12(buf, src, tmp, 0);
         }
     }
@@ -1573,8 +1575,14 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
             Self::add_reg64_reg64_imm32(buf, dst, src, -imm32)
         } else if imm32 < 0xFFF {
             sub_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
+        } else if imm32 <= 0xFF_FFFF {
+            sub_reg64_reg64_imm12(buf, dst, src, (imm32 & 0xFFF) as u16);
+            sub_reg64_reg64_imm12_lsl12(buf, dst, dst, (imm32 >> 12) as u16);
         } else {
-            todo!("immediate subtractions with values greater than 12bits");
+            let tmp = AArch64GeneralReg::X15;
+            debug_assert_ne!(src, tmp);
+            Self::mov_reg64_imm64(buf, tmp, imm32 as i64);
+            sub_reg64_reg64_reg64_uxtx(buf, dst, src, tmp);
         }
     }
     #[inline(always)]
@@ -1810,13 +1818,14 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         offset: i32,
     ) {
-        if offset < 0 {
+        if (-256..0).contains(&offset) {
             ldur_freg64_reg64_imm9(buf, dst, src, offset as i16)
-        } else if (0..=u16::MAX as i32).contains(&offset) {
+        } else if (0..=0xFFF << 3).contains(&offset) {
             debug_assert!(offset % 8 == 0);
             ldr_freg64_reg64_imm12(buf, dst, src, (offset as u16) >> 3);
         } else {
-            todo!("base offsets over 32k for AArch64");
+            let tmp = Self::offset_address(buf, src, offset);
+            ldr_freg64_reg64_imm12(buf, dst, tmp, 0);
         }
     }
 
@@ -1826,18 +1835,33 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         offset: i32,
     ) {
-        if offset < 0 {
-            ldur_freg64_reg64_imm9(buf, dst, src, offset as i16)
-        } else if (0..=u16::MAX as i32).contains(&offset) {
-            debug_assert!(offset % 8 == 0);
-            ldr_freg64_reg64_imm12(buf, dst, src, (offset as u16) >> 3);
+        if (-256..0).contains(&offset) {
+            ldur_freg32_reg64_imm9(buf, dst, src, offset as i16)
+        } else if (0..=0xFFF << 2).contains(&offset) {
+            debug_assert!(offset % 4 == 0);
+            ldr_freg32_reg64_imm12(buf, dst, src, (offset as u16) >> 2);
         } else {
-            todo!("base offsets over 32k for AArch64");
+            let tmp = Self::offset_address(buf, src, offset);
+            ldr_freg32_reg64_imm12(buf, dst, tmp, 0);
         }
     }
 }
 
-impl AArch64Assembler {}
+impl AArch64Assembler {
+    /// Puts `base + offset` in X15, for loads and stores whose offset doesn't fit in the
+    /// instruction. `base` may be the stack pointer.
+    #[inline(always)]
+    fn offset_address(
+        buf: &mut Vec<'_, u8>,
+        base: AArch64GeneralReg,
+        offset: i32,
+    ) -> AArch64GeneralReg {
+        let tmp = AArch64GeneralReg::X15;
+        Self::add_reg64_reg64_imm32(buf, tmp, base, offset);
+
+        tmp
+    }
+}
 
 // Instructions
 // ARM manual section C3
@@ -2034,6 +2058,54 @@ impl ArithmeticShifted {
     }
 }
 
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct ArithmeticExtended {
+    sf: bool,
+    op: bool, // add or subtract
+    s: bool,
+    fixed: Integer<u8, packed_bits::Bits<5>>, // = 0b01011,
+    opt: Integer<u8, packed_bits::Bits<2>>,   // = 0b00,
+    fixed2: bool,                             // = 0b1,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    option: Integer<u8, packed_bits::Bits<3>>, // extend
+    imm3: Integer<u8, packed_bits::Bits<3>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for ArithmeticExtended {}
+
+pub struct ArithmeticExtendedParams {
+    op: bool,
+    s: bool,
+    rm: AArch64GeneralReg,
+    rn: AArch64GeneralReg,
+    rd: AArch64GeneralReg,
+}
+
+impl ArithmeticExtended {
+    #[inline(always)]
+    fn new(ArithmeticExtendedParams { op, s, rm, rn, rd }: ArithmeticExtendedParams) -> Self {
+        Self {
+            // true for 64 bit addition
+            // false for 32 bit addition
+            sf: true,
+            fixed: 0b01011.into(),
+            op,
+            s,
+            opt: 0b00.into(),
+            fixed2: true,
+            reg_m: rm.id().into(),
+            // UXTX, which leaves Xm as it is
+            option: 0b011.into(),
+            imm3: 0b000.into(),
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+        }
+    }
+}
+
 // ARM manual section C1.2.4
 #[derive(Copy, Clone, PartialEq)]
 #[allow(dead_code)]
@@ -2929,6 +3001,46 @@ fn add_reg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+/// `ADD Xd, Xn, imm12, LSL #12` -> Add Xn and imm12 shifted left by 12 and place the result into Xd.
+#[inline(always)]
+fn add_reg64_reg64_imm12_lsl12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(ArithmeticImmediateParams {
+        op: false,
+        s: false,
+        sh: true,
+        imm12,
+        rd: dst,
+        rn: src,
+    });
+
+    buf.extend(inst.bytes());
+}
+
+/// `ADD Xd|SP, Xn|SP, Xm, UXTX` -> Add Xn and Xm and place the result into Xd.
+/// Unlike `add_reg64_reg64_reg64`, register 31 is SP rather than XZR for Xd and Xn.
+#[inline(always)]
+fn add_reg64_reg64_reg64_uxtx(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticExtended::new(ArithmeticExtendedParams {
+        op: false,
+        s: false,
+        rm: src2,
+        rn: src1,
+        rd: dst,
+    });
+
+    buf.extend(inst.bytes());
+}
+
 /// `ADD Xd, Xm, Xn` -> Add Xm and Xn and place the result into Xd.
 #[inline(always)]
 fn add_reg64_reg64_reg64(
@@ -3211,6 +3323,56 @@ fn ldr_freg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+/// `LDR St, [Xn, #offset]` -> Load Xn + Offset St. ZRSP is SP.
+/// Note: imm12 is the offest divided by 4.
+#[inline(always)]
+fn ldr_freg32_reg64_imm12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    base: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = LoadStoreRegisterImmediate {
+        size: 0b10.into(), // 32-bit
+        fixed: 0b111.into(),
+        fixed2: true,
+        fixed3: 0b01.into(),
+        opc: 0b01.into(), // load
+        imm12: imm12.into(),
+        rn: base.id().into(),
+        rt: dst.id().into(),
+    };
+
+    buf.extend(inst.bytes());
+}
+
+#[inline(always)]
+fn ldur_freg32_reg64_imm9(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    base: AArch64GeneralReg,
+    imm9: i16,
+) {
+    // the value must fit in 8 bits (1 bit for the sign)
+    assert!((-256..256).contains(&imm9));
+
+    let imm9 = u16::from_ne_bytes(imm9.to_ne_bytes());
+    let imm12 = (imm9 & 0b0001_1111_1111) << 2;
+
+    let inst = LoadStoreRegisterImmediate {
+        size: 0b10.into(), // 32-bit
+        fixed: 0b111.into(),
+        fixed2: true,
+        fixed3: 0b00.into(),
+        opc: 0b01.into(), // load
+        imm12: imm12.into(),
+        rn: base.id().into(),
+        rt: dst.id().into(),
+    };
+
+    buf.extend(inst.bytes());
+}
+
 #[inline(always)]
 fn ldur_freg64_reg64_imm9(
     buf: &mut Vec<'_, u8>,
@@ -3500,6 +3662,46 @@ fn sub_reg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+/// `SUB Xd, Xn, imm12, LSL #12` -> Subtract imm12 shifted left by 12 from Xn and place the result into Xd.
+#[inline(always)]
+fn sub_reg64_reg64_imm12_lsl12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(ArithmeticImmediateParams {
+        op: true,
+        s: false,
+        rd: dst,
+        rn: src,
+        imm12,
+        sh: true,
+    });
+
+    buf.extend(inst.bytes());
+}
+
+/// `SUB Xd|SP, Xn|SP, Xm, UXTX` -> Subtract Xm from Xn and place the result into Xd.
+/// Unlike `sub_reg64_reg64_reg64`, register 31 is SP rather than XZR for Xd and Xn.
+#[inline(always)]
+fn sub_reg64_reg64_reg64_uxtx(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticExtended::new(ArithmeticExtendedParams {
+        op: true,
+        s: false,
+        rm: src2,
+        rn: src1,
+        rd: dst,
+    });
+
+    buf.extend(inst.bytes());
+}
+
 /// `SUB Xd, Xm, Xn` -> Subtract Xm and Xn and place the result into Xd.
 #[inline(always)]
 fn sub_reg64_reg64_reg64(
@@ -4194,6 +4396,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_reg64_reg64_imm12_lsl12() {
+        disassembler_test!(
+            add_reg64_reg64_imm12_lsl12,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "add {}, {}, #0x{:x}, lsl #12",
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_add_reg64_reg64_reg64_uxtx() {
+        disassembler_test!(
+            add_reg64_reg64_reg64_uxtx,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                // With SP, capstone shows the equivalent `LSL #0` form, which it leaves out.
+                let uses_sp = reg1 == AArch64GeneralReg::ZRSP || reg2 == AArch64GeneralReg::ZRSP;
+                format!(
+                    "add {}, {}, {}{}",
+                    reg1.capstone_string(UsesSP),
+                    reg2.capstone_string(UsesSP),
+                    reg3.capstone_string(UsesZR),
+                    if uses_sp { "" } else { ", uxtx" }
+                )
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_add_reg64_reg64_imm32_with_sp() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>, imm| AArch64Assembler::add_reg64_reg64_imm32(
+                buf,
+                AArch64GeneralReg::X0,
+                AArch64GeneralReg::ZRSP,
+                imm
+            ),
+            |imm| match imm {
+                0x12348 => "add x0, sp, #0x348\nadd x0, x0, #0x12, lsl #12".to_owned(),
+                _ => "mov x15, #0x5678\nmovk x15, #0x1234, lsl #16\nadd x0, sp, x15".to_owned(),
+            },
+            [0x12348, 0x12345678]
+        );
+    }
+
     #[test]
     fn test_and_reg64_reg64_reg64() {
         disassembler_test!(
@@ -4443,6 +4699,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ldr_freg32_reg64_imm12() {
+        disassembler_test!(
+            ldr_freg32_reg64_imm12,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg, imm| format!(
+                "ldr {}, [{}, #0x{:x}]",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(UsesSP),
+                imm << 2
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_ldur_freg32_reg64_imm9() {
+        disassembler_test!(
+            ldur_freg32_reg64_imm9,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg, imm| format!(
+                "ldur {}, [{}, {}]",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(UsesSP),
+                signed_hex_i16(imm)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS,
+            [4, -4]
+        );
+    }
+
+    #[test]
+    fn test_mov_freg_mem_offset32_far_from_sp() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>, offset| AArch64Assembler::mov_freg64_mem64_offset32(
+                buf,
+                AArch64FloatReg::V0,
+                AArch64GeneralReg::ZRSP,
+                offset
+            ),
+            |offset| match offset {
+                0x12348 => "add x15, sp, #0x348\nadd x15, x15, #0x12, lsl #12\nldr d0, [x15]",
+                _ =>
+                    "mov x15, #0x5678\nmovk x15, #0x1234, lsl #16\nadd x15, sp, x15\nldr d0, [x15]",
+            },
+            [0x12348, 0x12345678]
+        );
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>, offset| AArch64Assembler::mov_freg32_mem32_offset32(
+                buf,
+                AArch64FloatReg::V0,
+                AArch64GeneralReg::ZRSP,
+                offset
+            ),
+            |offset| match offset {
+                0x14 => "ldr s0, [sp, #0x14]",
+                -0x14 => "ldur s0, [sp, #-0x14]",
+                _ => "add x15, sp, #0x348\nadd x15, x15, #0x12, lsl #12\nldr s0, [x15]",
+            },
+            [0x14, -0x14, 0x12348]
+        );
+    }
+
     #[test]
     fn test_ludr_freg64_reg64_imm9() {
         disassembler_test!(
@@ -4744,6 +5064,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sub_reg64_reg64_imm12_lsl12() {
+        disassembler_test!(
+            sub_reg64_reg64_imm12_lsl12,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "sub {}, {}, #0x{:x}, lsl #12",
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_sub_reg64_reg64_reg64_uxtx() {
+        disassembler_test!(
+            sub_reg64_reg64_reg64_uxtx,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                let uses_sp = reg1 == AArch64GeneralReg::ZRSP || reg2 == AArch64GeneralReg::ZRSP;
+                format!(
+                    "sub {}, {}, {}{}",
+                    reg1.capstone_string(UsesSP),
+                    reg2.capstone_string(UsesSP),
+                    reg3.capstone_string(UsesZR),
+                    if uses_sp { "" } else { ", uxtx" }
+                )
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
     fn test_sub_reg64_reg64_reg64() {
         disassembler_test!(
@@ -16,7 +16,7 @@ use inkwell::attributes::{Attribute, AttributeLoc};
 use inkwell::builder::Builder;
 use inkwell::context::Context;
 use inkwell::debug_info::{
-    AsDIScope, DICompileUnit, DIFlagsConstants, DISubprogram, DebugInfoBuilder,
+    AsDIScope, DICompileUnit, DIFlagsConstants, DISubprogram, DISubroutineType, DebugInfoBuilder,
 };
 use inkwell::memory_buffer::MemoryBuffer;
 use inkwell::module::{Linkage, Module};
@@ -52,7 +52,7 @@ use roc_mono::layout::{
 use roc_std::RocDec;
 use roc_target::{PtrWidth, TargetInfo};
 use std::convert::TryInto;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use target_lexicon::{Aarch64Architecture, Architecture, OperatingSystem, Triple};
 
 use super::convert::{struct_type_from_union_layout, RocUnion};
@@ -175,6 +175,9 @@ pub enum LlvmBackendMode {
     GenTest,
     WasmGenTest,
     CliTest,
+    /// Like GenTest, but `dbg` values are cloned into a buffer that the REPL prints from
+    /// once evaluation is done.
+    Repl,
 }
 
 impl LlvmBackendMode {
@@ -186,6 +189,7 @@ impl LlvmBackendMode {
             LlvmBackendMode::GenTest => false,
             LlvmBackendMode::WasmGenTest => true,
             LlvmBackendMode::CliTest => false,
+            LlvmBackendMode::Repl => false,
         }
     }
 
@@ -198,6 +202,7 @@ impl LlvmBackendMode {
             LlvmBackendMode::GenTest => true,
             LlvmBackendMode::WasmGenTest => true,
             LlvmBackendMode::CliTest => true,
+            LlvmBackendMode::Repl => true,
         }
     }
 
@@ -209,6 +214,19 @@ impl LlvmBackendMode {
             LlvmBackendMode::GenTest => false,
             LlvmBackendMode::WasmGenTest => false,
             LlvmBackendMode::CliTest => true,
+            LlvmBackendMode::Repl => false,
+        }
+    }
+
+    pub(crate) fn runs_dbgs(self) -> bool {
+        match self {
+            LlvmBackendMode::Binary => false,
+            LlvmBackendMode::BinaryDev => true,
+            LlvmBackendMode::BinaryGlue => false,
+            LlvmBackendMode::GenTest => false,
+            LlvmBackendMode::WasmGenTest => false,
+            LlvmBackendMode::CliTest => true,
+            LlvmBackendMode::Repl => true,
         }
     }
 }
@@ -224,6 +242,17 @@ pub struct Env<'a, 'ctx, 'env> {
     pub target_info: TargetInfo,
     pub mode: LlvmBackendMode,
     pub exposed_to_host: MutSet<Symbol>,
+    /// Where Roc functions were defined. When this is filled in, the debug info for those
+    /// functions points at their Roc source code.
+    pub def_locations: MutMap<Symbol, DefLocation>,
+}
+
+/// The place in a Roc source file where a function was defined.
+#[derive(Debug, Clone)]
+pub struct DefLocation {
+    pub path: PathBuf,
+    /// Starts at 0, like the lines of a `LineColumn`.
+    pub line: u32,
 }
 
 impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
@@ -378,6 +407,15 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
         message: BasicValueEnum<'ctx>,
         tag: CrashTag,
     ) {
+        if self.dumps_stack_traces() {
+            let dump = self
+                .module
+                .get_function(bitcode::UTILS_DUMP_STACK_TRACE)
+                .unwrap();
+
+            self.builder.build_call(dump, &[], "dump_stack_trace");
+        }
+
         let function = self.module.get_function("roc_panic").unwrap();
         let tag_id = self.context.i32_type().const_int(tag as u32 as u64, false);
 
@@ -403,6 +441,18 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
         call.set_call_convention(C_CALL_CONV);
     }
 
+    /// Builds with debug info (`--debug`) print a stack trace before handing a crash to the
+    /// host, and keep the frame pointers needed to find the frames of that trace.
+    /// (`roc run --profile` relies on those frame pointers too, to sample the call stack.)
+    pub(crate) fn dumps_stack_traces(&self) -> bool {
+        self.mode.has_host()
+            && !self.def_locations.is_empty()
+            && !matches!(
+                self.target_info.architecture,
+                roc_target::Architecture::Wasm32
+            )
+    }
+
     pub fn new_debug_info(module: &Module<'ctx>) -> (DebugInfoBuilder<'ctx>, DICompileUnit<'ctx>) {
         module.create_debug_info_builder(
             true,
@@ -427,29 +477,13 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
         let dibuilder = self.dibuilder;
         let compile_unit = self.compile_unit;
 
-        let ditype = dibuilder
-            .create_basic_type(
-                "type_name",
-                0_u64,
-                0x00,
-                inkwell::debug_info::DIFlags::PUBLIC,
-            )
-            .unwrap();
-
-        let subroutine_type = dibuilder.create_subroutine_type(
-            compile_unit.get_file(),
-            /* return type */ Some(ditype.as_type()),
-            /* parameter types */ &[],
-            inkwell::debug_info::DIFlags::PUBLIC,
-        );
-
         dibuilder.create_function(
             /* scope */ compile_unit.get_file().as_debug_info_scope(),
             /* func name */ function_name,
             /* linkage_name */ None,
             /* file */ compile_unit.get_file(),
             /* line_no */ 0,
-            /* DIType */ subroutine_type,
+            /* DIType */ self.new_subroutine_type(),
             /* is_local_to_unit */ true,
             /* is_definition */ true,
             /* scope_line */ 0,
@@ -457,6 +491,56 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
             /* is_optimized */ false,
         )
     }
+
+    /// Like `new_subprogram`, but for a function whose Roc definition we know the location of,
+    /// so that debuggers can show it.
+    pub fn new_subprogram_at(
+        &self,
+        function_name: &str,
+        location: &DefLocation,
+    ) -> DISubprogram<'ctx> {
+        let file_name = location.path.file_name().unwrap_or_default();
+        let directory = location.path.parent().unwrap_or_else(|| Path::new("."));
+        let file = self
+            .dibuilder
+            .create_file(&file_name.to_string_lossy(), &directory.to_string_lossy());
+
+        // Lines in DWARF start at 1.
+        let line_no = location.line + 1;
+
+        self.dibuilder.create_function(
+            /* scope */ file.as_debug_info_scope(),
+            /* func name */ function_name,
+            /* linkage_name */ None,
+            /* file */ file,
+            /* line_no */ line_no,
+            /* DIType */ self.new_subroutine_type(),
+            /* is_local_to_unit */ true,
+            /* is_definition */ true,
+            /* scope_line */ line_no,
+            /* flags */ inkwell::debug_info::DIFlags::PUBLIC,
+            /* is_optimized */ false,
+        )
+    }
+
+    fn new_subroutine_type(&self) -> DISubroutineType<'ctx> {
+        let ditype = self
+            .dibuilder
+            .create_basic_type(
+                "type_name",
+                0_u64,
+                0x00,
+                inkwell::debug_info::DIFlags::PUBLIC,
+            )
+            .unwrap();
+
+        self.dibuilder.create_subroutine_type(
+            self.compile_unit.get_file(),
+            /* return type */ Some(ditype.as_type()),
+            /* parameter types */ &[],
+            inkwell::debug_info::DIFlags::PUBLIC,
+        )
+    }
 }
 
 pub fn module_from_builtins<'ctx>(
@@ -2996,10 +3080,19 @@ pub(crate) fn build_exp_stmt<'a, 'ctx>(
             variable: specialized_var,
             remainder,
         } => {
-            if env.mode.runs_expects() {
+            if env.mode.runs_dbgs() {
                 let shared_memory = crate::llvm::expect::SharedMemoryPointer::get(env);
                 let region = unsafe { std::mem::transmute::<_, roc_region::all::Region>(*symbol) };
 
+                // The REPL reads the dbgs after evaluation, rather than being notified of each one
+                let next_frame_slot = match env.mode {
+                    LlvmBackendMode::Repl => Some(crate::llvm::expect::reserve_next_frame_offset(
+                        env,
+                        &shared_memory,
+                    )),
+                    _ => None,
+                };
+
                 crate::llvm::expect::clone_to_shared_memory(
                     env,
                     layout_interner,
@@ -3012,7 +3105,10 @@ pub(crate) fn build_exp_stmt<'a, 'ctx>(
                     &[*specialized_var],
                 );
 
-                crate::llvm::expect::notify_parent_dbg(env, &shared_memory);
+                match next_frame_slot {
+                    Some(slot) => crate::llvm::expect::finish_repl_frame(env, &shared_memory, slot),
+                    None => crate::llvm::expect::notify_parent_dbg(env, &shared_memory),
+                }
             }
 
             build_exp_stmt(
@@ -4366,7 +4462,10 @@ fn expose_function_to_host_help_c_abi<'a, 'ctx>(
     c_function_name: &str,
 ) -> FunctionValue<'ctx> {
     match env.mode {
-        LlvmBackendMode::GenTest | LlvmBackendMode::WasmGenTest | LlvmBackendMode::CliTest => {
+        LlvmBackendMode::GenTest
+        | LlvmBackendMode::WasmGenTest
+        | LlvmBackendMode::CliTest
+        | LlvmBackendMode::Repl => {
             return expose_function_to_host_help_c_abi_gen_test(
                 env,
                 layout_interner,
@@ -4427,7 +4526,10 @@ fn expose_function_to_host_help_c_abi<'a, 'ctx>(
     debug_info_init!(env, size_function);
 
     let return_type = match env.mode {
-        LlvmBackendMode::GenTest | LlvmBackendMode::WasmGenTest | LlvmBackendMode::CliTest => {
+        LlvmBackendMode::GenTest
+        | LlvmBackendMode::WasmGenTest
+        | LlvmBackendMode::CliTest
+        | LlvmBackendMode::Repl => {
             roc_call_result_type(env, roc_function.get_type().get_return_type().unwrap()).into()
         }
 
@@ -4874,6 +4976,13 @@ pub(crate) fn build_proc_headers<'a, 'r, 'ctx>(
     roc_mono::ir::Proc<'a>,
     std::vec::Vec<(&'a FuncSpecSolutions, FunctionValue<'ctx>)>,
 )> {
+    // Go through the procs in an order that doesn't depend on their symbols' and layouts' ids,
+    // so that every build of the same program adds its functions to the module in the same order.
+    let mut procedures: std::vec::Vec<_> = procedures.into_iter().collect();
+    procedures.sort_by_cached_key(|((symbol, layout), _)| {
+        stable_proc_key(&env.interns, layout_interner, *symbol, layout)
+    });
+
     // Populate Procs further and get the low-level Expr from the canonical Expr
     let mut headers = std::vec::Vec::with_capacity(procedures.len());
     for ((symbol, layout), proc) in procedures {
@@ -4924,6 +5033,8 @@ pub fn build_procedures<'a>(
     debug_output_file: Option<&Path>,
     glue_layouts: &GlueLayouts<'a>,
 ) {
+    let stable_names = stable_proc_names(env, layout_interner, &procedures);
+
     let mod_solutions = build_procedures_help(
         env,
         layout_interner,
@@ -4969,6 +5080,86 @@ pub fn build_procedures<'a>(
             getter_name,
         );
     }
+
+    // Nothing looks functions up by their func spec after this point.
+    give_procs_stable_names(env, mod_solutions, &stable_names);
+}
+
+/// What [give_procs_stable_names] needs to know about each proc. This has to be collected before
+/// the procs are handed over to [build_procedures_help].
+fn stable_proc_names<'a>(
+    env: &Env<'a, '_, '_>,
+    layout_interner: &STLayoutInterner<'a>,
+    procedures: &MutMap<(Symbol, ProcLayout<'a>), roc_mono::ir::Proc<'a>>,
+) -> std::vec::Vec<(Symbol, std::vec::Vec<u8>, String)> {
+    procedures
+        .iter()
+        .filter(|(_, proc)| !proc.is_erased)
+        .map(|((symbol, layout), proc)| {
+            let key = stable_proc_key(&env.interns, layout_interner, *symbol, layout);
+
+            (
+                *symbol,
+                roc_alias_analysis::func_name_bytes(proc).to_vec(),
+                key,
+            )
+        })
+        .collect()
+}
+
+/// Identifies a proc by its name and layout, without the symbol and layout ids that depend on
+/// the order in which modules happened to be loaded.
+fn stable_proc_key<'a>(
+    interns: &Interns,
+    layout_interner: &STLayoutInterner<'a>,
+    symbol: Symbol,
+    layout: &ProcLayout<'a>,
+) -> String {
+    let module_string = interns.module_ids.get_name(symbol.module_id()).unwrap();
+
+    format!(
+        "{module_string}.{} {:?} {:?} -> {:?}",
+        symbol.as_str(interns),
+        layout.niche.dbg_stable(layout_interner),
+        layout_interner.dbg_stable_iter(layout.arguments),
+        layout_interner.dbg_stable(layout.result),
+    )
+}
+
+/// Renames the functions of Roc procs, whose `func_spec_name`s include a hash of ids that can
+/// differ from build to build, so that building the same program twice gives the same binary.
+/// The new names have the same shape, so `demangle_func_spec_name` still understands them.
+///
+/// The names are hashed with FNV-1a, whose output is fully specified. (std's `DefaultHasher`
+/// makes no such promise, and could change from one Rust release to the next.)
+fn give_procs_stable_names(
+    env: &Env<'_, '_, '_>,
+    mod_solutions: &ModSolutions,
+    procs: &[(Symbol, std::vec::Vec<u8>, String)],
+) {
+    use std::hash::Hasher;
+
+    for (symbol, name_bytes, key) in procs {
+        let func_solutions = mod_solutions.func_solutions(FuncName(name_bytes)).unwrap();
+        let module_string = env.interns.module_ids.get_name(symbol.module_id()).unwrap();
+        let ident_string = symbol.as_str(&env.interns);
+
+        for (index, func_spec) in func_solutions.specs().enumerate() {
+            let fn_val =
+                function_value_by_func_spec(env, FuncBorrowSpec::Some(*func_spec), *symbol);
+
+            let mut hasher = fnv::FnvHasher::default();
+            hasher.write(key.as_bytes());
+            hasher.write(&(index as u64).to_le_bytes());
+
+            let stable_name = format!("{module_string}_{ident_string}_{:x}", hasher.finish());
+
+            fn_val
+                .as_global_value()
+                .as_pointer_value()
+                .set_name(&stable_name);
+        }
+    }
 }
 
 pub fn build_wasm_test_wrapper<'a, 'ctx>(
@@ -5005,6 +5196,8 @@ pub fn build_procedures_return_main<'a, 'ctx>(
     host_exposed_lambda_sets: HostExposedLambdaSets<'a>,
     entry_point: SingleEntryPoint<'a>,
 ) -> (&'static str, FunctionValue<'ctx>) {
+    let stable_names = stable_proc_names(env, layout_interner, &procedures);
+
     let mod_solutions = build_procedures_help(
         env,
         layout_interner,
@@ -5015,13 +5208,18 @@ pub fn build_procedures_return_main<'a, 'ctx>(
         Some(&std::env::temp_dir().join("test.ll")),
     );
 
-    promote_to_main_function(
+    let main = promote_to_main_function(
         env,
         layout_interner,
         mod_solutions,
         entry_point.symbol,
         entry_point.layout,
-    )
+    );
+
+    // promote_to_main_function looks the entry point up by its func spec, so this comes after it.
+    give_procs_stable_names(env, mod_solutions, &stable_names);
+
+    main
 }
 
 pub fn build_procedures_expose_expects<'a>(
@@ -5032,6 +5230,7 @@ pub fn build_procedures_expose_expects<'a>(
     procedures: MutMap<(Symbol, ProcLayout<'a>), roc_mono::ir::Proc<'a>>,
 ) -> Vec<'a, &'a str> {
     let entry_point = EntryPoint::Expects { symbols: expects };
+    let stable_names = stable_proc_names(env, layout_interner, &procedures);
 
     let mod_solutions = build_procedures_help(
         env,
@@ -5051,7 +5250,7 @@ pub fn build_procedures_expose_expects<'a>(
         niche: captures_niche,
     };
 
-    let mut expect_names = Vec::with_capacity_in(expects.len(), env.arena);
+    let mut expect_fns = std::vec::Vec::with_capacity(expects.len());
 
     for symbol in expects.iter().copied() {
         let it = top_level.arguments.iter().copied();
@@ -5072,9 +5271,20 @@ pub fn build_procedures_expose_expects<'a>(
         );
 
         // NOTE fake layout; it is only used for debug prints
-        let roc_main_fn =
-            function_value_by_func_spec(env, FuncBorrowSpec::Some(*func_spec), symbol);
+        expect_fns.push(function_value_by_func_spec(
+            env,
+            FuncBorrowSpec::Some(*func_spec),
+            symbol,
+        ));
+    }
+
+    // The expects are found by their func specs above, and get their stable names (which their
+    // exposed names are made from) only after that.
+    give_procs_stable_names(env, mod_solutions, &stable_names);
 
+    let mut expect_names = Vec::with_capacity_in(expects.len(), env.arena);
+
+    for roc_main_fn in expect_fns {
         let name = roc_main_fn.get_name().to_str().unwrap();
 
         let expect_name = &format!("Expect_{name}");
@@ -5201,7 +5411,8 @@ fn build_procedures_help<'a>(
 
     use LlvmBackendMode::*;
     match env.mode {
-        GenTest | WasmGenTest | CliTest => { /* no host, or exposing types is not supported */ }
+        GenTest | WasmGenTest | CliTest | Repl => { /* no host, or exposing types is not supported */
+        }
         Binary | BinaryDev | BinaryGlue => {
             for (proc_name, alias_name, hels) in host_exposed_lambda_sets.iter() {
                 let ident_string = proc_name.name().as_str(&env.interns);
@@ -5256,6 +5467,24 @@ fn func_spec_name<'a>(
     buf
 }
 
+/// The inverse of `func_spec_name`: turns the name of a Roc proc's LLVM function back into
+/// `Module.ident`, e.g. for showing it in a profile. Returns `None` for every other name,
+/// such as the Zig builtins' or the host's.
+pub fn demangle_func_spec_name(name: &str) -> Option<String> {
+    let (module_and_ident, func_spec) = name.rsplit_once('_')?;
+    let (module_string, ident_string) = module_and_ident.split_once('_')?;
+
+    let is_func_spec = func_spec == "erased"
+        || (!func_spec.is_empty() && func_spec.bytes().all(|byte| byte.is_ascii_hexdigit()));
+    let is_module = module_string.starts_with(|c: char| c.is_ascii_uppercase());
+
+    if is_func_spec && is_module && !ident_string.is_empty() {
+        Some(format!("{module_string}.{ident_string}"))
+    } else {
+        None
+    }
+}
+
 fn build_proc_header<'a, 'ctx>(
     env: &Env<'a, 'ctx, '_>,
     layout_interner: &STLayoutInterner<'a>,
@@ -5295,9 +5524,17 @@ fn build_proc_header<'a, 'ctx>(
         Linkage::Internal,
     );
 
-    let subprogram = env.new_subprogram(&fn_name);
+    let subprogram = match env.def_locations.get(&symbol) {
+        Some(location) => env.new_subprogram_at(&fn_name, location),
+        None => env.new_subprogram(&fn_name),
+    };
     fn_val.set_subprogram(subprogram);
 
+    if env.dumps_stack_traces() {
+        let attr = env.context.create_string_attribute("frame-pointer", "all");
+        fn_val.add_attribute(AttributeLoc::Function, attr);
+    }
+
     if env.exposed_to_host.contains(&symbol) {
         let arguments = Vec::from_iter_in(proc.args.iter().map(|(layout, _)| *layout), env.arena);
         expose_function_to_host(
@@ -5600,7 +5837,21 @@ fn build_proc<'a, 'ctx>(
 
     builder.position_at_end(entry);
 
-    debug_info_init!(env, fn_val);
+    if let Some(location) = env.def_locations.get(&proc.name.name()) {
+        // Point everything in the function at its definition. Mono IR doesn't keep track of
+        // where expressions came from, so that's as precise as we can be.
+        let subprogram = fn_val.get_subprogram().expect("subprogram");
+        let loc = env.dibuilder.create_debug_location(
+            env.context,
+            /* line */ location.line + 1,
+            /* column */ 0,
+            subprogram.as_debug_info_scope(),
+            /* inlined_at */ None,
+        );
+        builder.set_current_debug_location(loc);
+    } else {
+        debug_info_init!(env, fn_val);
+    }
 
     // Add args to scope
     for (arg_val, (layout, arg_symbol)) in fn_val.get_param_iter().zip(args) {
@@ -7,7 +7,7 @@ use inkwell::builder::Builder;
 use inkwell::module::Linkage;
 use inkwell::types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum};
 use inkwell::values::{BasicValueEnum, FunctionValue, IntValue, PointerValue};
-use inkwell::AddressSpace;
+use inkwell::{AddressSpace, IntPredicate};
 use roc_builtins::bitcode;
 use roc_error_macros::{internal_error, todo_lambda_erasure};
 use roc_module::symbol::Symbol;
@@ -160,6 +160,119 @@ pub(crate) fn notify_parent_dbg(env: &Env, shared_memory: &SharedMemoryPointer)
     );
 }
 
+/// The REPL has no parent process to notify of each `dbg`, so it reads them all from the
+/// buffer once evaluation is done. To let it walk from one frame to the next, each frame
+/// starts with the offset of the next one: this reserves room for that offset, and returns
+/// where it is.
+pub(crate) fn reserve_next_frame_offset<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    shared_memory: &SharedMemoryPointer<'ctx>,
+) -> IntValue<'ctx> {
+    let (count, offset) = read_state(env, shared_memory.0);
+
+    let after_slot = offset_add(env.builder, offset, env.target_info.ptr_size() as u32);
+    write_state(env, shared_memory.0, count, after_slot);
+
+    offset
+}
+
+/// Fill in the slot from `reserve_next_frame_offset` with the end of the frame
+/// that was just written, which is where the next one will start.
+///
+/// If the frame didn't fit in the buffer, the writes that would have gone past its end were
+/// left out. Then this takes the frame back out of the count, and marks the buffer as full
+/// (with a next free position just past its end), so that every later frame gets left out too.
+pub(crate) fn finish_repl_frame<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    shared_memory: &SharedMemoryPointer<'ctx>,
+    slot: IntValue<'ctx>,
+) {
+    let (count, next_frame) = read_state(env, shared_memory.0);
+    let length = shared_buffer_length(env);
+    let fits = env
+        .builder
+        .build_int_compare(IntPredicate::ULE, next_frame, length, "frame_fits");
+
+    build_copy(env, shared_memory.0, slot, next_frame.into());
+
+    let one = env.ptr_int().const_int(1, false);
+    let count_without_frame = env.builder.build_int_sub(count, one, "count_without_frame");
+    let past_the_end = env.builder.build_int_add(length, one, "past_the_end");
+
+    let count = env
+        .builder
+        .build_select(fits, count, count_without_frame, "count")
+        .into_int_value();
+    let next_frame = env
+        .builder
+        .build_select(fits, next_frame, past_the_end, "next_frame")
+        .into_int_value();
+
+    write_state(env, shared_memory.0, count, next_frame);
+}
+
+fn shared_buffer_length<'ctx>(env: &Env<'_, 'ctx, '_>) -> IntValue<'ctx> {
+    let func = env
+        .module
+        .get_function(bitcode::UTILS_EXPECT_SHARED_BUFFER_LENGTH)
+        .unwrap();
+
+    env.builder
+        .build_call(func, &[], "shared_buffer_length")
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value()
+}
+
+/// Do a write that ends at `end` (an offset into the buffer), unless it would go past the end of
+/// the REPL's buffer. The other modes don't know how long their buffer is, so they always write.
+fn write_if_room<'ctx>(env: &Env<'_, 'ctx, '_>, end: IntValue<'ctx>, write: impl FnOnce()) {
+    clone_if_room(env, end, || {
+        write();
+
+        end
+    });
+}
+
+/// Like `write_if_room`, for a write that returns the new extra offset. If there's no room,
+/// that's `end` instead.
+fn clone_if_room<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    end: IntValue<'ctx>,
+    write: impl FnOnce() -> IntValue<'ctx>,
+) -> IntValue<'ctx> {
+    if !matches!(env.mode, LlvmBackendMode::Repl) {
+        return write();
+    }
+
+    let has_room = env.builder.build_int_compare(
+        IntPredicate::ULE,
+        end,
+        shared_buffer_length(env),
+        "has_room",
+    );
+
+    let no_room_block = env.builder.get_insert_block().unwrap();
+    let parent = no_room_block.get_parent().unwrap();
+    let write_block = env.context.append_basic_block(parent, "write");
+    let after_write_block = env.context.append_basic_block(parent, "after_write");
+
+    env.builder
+        .build_conditional_branch(has_room, write_block, after_write_block);
+
+    env.builder.position_at_end(write_block);
+    let new_offset = write();
+    let written_block = env.builder.get_insert_block().unwrap();
+    env.builder.build_unconditional_branch(after_write_block);
+
+    env.builder.position_at_end(after_write_block);
+    let phi = env.builder.build_phi(env.ptr_int(), "new_offset");
+    phi.add_incoming(&[(&new_offset, written_block), (&end, no_room_block)]);
+
+    phi.as_basic_value().into_int_value()
+}
+
 // Shape of expect frame:
 //
 //     ===
@@ -251,32 +364,12 @@ pub(crate) fn clone_to_shared_memory<'a, 'ctx>(
 
             // Store the specialized variable of the value
             {
-                let ptr = unsafe {
-                    env.builder.new_build_in_bounds_gep(
-                        env.context.i8_type(),
-                        original_ptr,
-                        &[offset],
-                        "at_current_offset",
-                    )
-                };
-
-                let u32_ptr = env.context.i32_type().ptr_type(AddressSpace::default());
-                let ptr = env
-                    .builder
-                    .build_pointer_cast(ptr, u32_ptr, "cast_ptr_type");
-
                 let var_value = env
                     .context
                     .i32_type()
                     .const_int(lookup_var.index() as _, false);
 
-                env.builder.build_store(ptr, var_value);
-
-                let var_size = env
-                    .ptr_int()
-                    .const_int(std::mem::size_of::<u32>() as _, false);
-
-                offset = env.builder.build_int_add(offset, var_size, "offset");
+                offset = build_copy(env, original_ptr, offset, var_value.into());
             }
         }
     }
@@ -323,21 +416,28 @@ fn build_clone<'a, 'ctx>(
 
         LayoutRepr::Union(union_layout) => {
             if layout.safe_to_memcpy(layout_interner) {
-                let ptr = unsafe {
-                    env.builder.new_build_in_bounds_gep(
-                        env.context.i8_type(),
-                        ptr,
-                        &[cursors.offset],
-                        "at_current_offset",
-                    )
-                };
+                let stack_size = env
+                    .ptr_int()
+                    .const_int(layout.stack_size(layout_interner) as u64, false);
+                let end = env.builder.build_int_add(cursors.offset, stack_size, "end");
+
+                write_if_room(env, end, || {
+                    let ptr = unsafe {
+                        env.builder.new_build_in_bounds_gep(
+                            env.context.i8_type(),
+                            ptr,
+                            &[cursors.offset],
+                            "at_current_offset",
+                        )
+                    };
 
-                let ptr_type = value.get_type().ptr_type(AddressSpace::default());
-                let ptr = env
-                    .builder
-                    .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
+                    let ptr_type = value.get_type().ptr_type(AddressSpace::default());
+                    let ptr = env
+                        .builder
+                        .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
 
-                store_roc_value(env, layout_interner, layout, ptr, value);
+                    store_roc_value(env, layout_interner, layout, ptr, value);
+                });
 
                 cursors.extra_offset
             } else {
@@ -954,24 +1054,28 @@ fn build_copy<'ctx>(
     offset: IntValue<'ctx>,
     value: BasicValueEnum<'ctx>,
 ) -> IntValue<'ctx> {
-    let ptr = unsafe {
-        env.builder.new_build_in_bounds_gep(
-            env.context.i8_type(),
-            ptr,
-            &[offset],
-            "at_current_offset",
-        )
-    };
+    let width = value.get_type().size_of().unwrap();
+    let new_offset = env.builder.build_int_add(offset, width, "new_offset");
 
-    let ptr_type = value.get_type().ptr_type(AddressSpace::default());
-    let ptr = env
-        .builder
-        .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
+    write_if_room(env, new_offset, || {
+        let ptr = unsafe {
+            env.builder.new_build_in_bounds_gep(
+                env.context.i8_type(),
+                ptr,
+                &[offset],
+                "at_current_offset",
+            )
+        };
+
+        let ptr_type = value.get_type().ptr_type(AddressSpace::default());
+        let ptr = env
+            .builder
+            .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
 
-    env.builder.build_store(ptr, value);
+        env.builder.build_store(ptr, value);
+    });
 
-    let width = value.get_type().size_of().unwrap();
-    env.builder.build_int_add(offset, width, "new_offset")
+    new_offset
 }
 
 fn build_clone_builtin<'a, 'ctx>(
@@ -993,20 +1097,41 @@ fn build_clone_builtin<'a, 'ctx>(
         }
 
         Builtin::Str => {
-            //
+            let clone_to = || {
+                call_str_bitcode_fn(
+                    env,
+                    &[value],
+                    &[
+                        ptr.into(),
+                        cursors.offset.into(),
+                        cursors.extra_offset.into(),
+                    ],
+                    crate::llvm::bitcode::BitcodeReturns::Basic,
+                    bitcode::STR_CLONE_TO,
+                )
+                .into_int_value()
+            };
 
-            call_str_bitcode_fn(
+            if !matches!(env.mode, LlvmBackendMode::Repl) {
+                return clone_to();
+            }
+
+            // The string struct goes before `extra_offset`, and any bytes that don't fit in
+            // it go after, so nothing gets written past this.
+            let bytes = call_str_bitcode_fn(
                 env,
                 &[value],
-                &[
-                    ptr.into(),
-                    cursors.offset.into(),
-                    cursors.extra_offset.into(),
-                ],
+                &[],
                 crate::llvm::bitcode::BitcodeReturns::Basic,
-                bitcode::STR_CLONE_TO,
+                bitcode::STR_COUNT_UTF8_BYTES,
             )
-            .into_int_value()
+            .into_int_value();
+            let end = env
+                .builder
+                .build_int_add(cursors.extra_offset, bytes, "end");
+
+            // If it doesn't fit, `end` is as far as it would have gone, which is past the end.
+            clone_if_room(env, end, clone_to)
         }
         Builtin::List(elem) => {
             let bd = env.builder;
@@ -1031,16 +1156,22 @@ fn build_clone_builtin<'a, 'ctx>(
             let elements_start_offset = cursors.extra_offset;
 
             if layout_interner.safe_to_memcpy(elem) {
-                // NOTE we are not actually sure the dest is properly aligned
-                let dest = pointer_at_offset(bd, env.context.i8_type(), ptr, elements_start_offset);
-                let src = bd.build_pointer_cast(
-                    elements,
-                    env.context.i8_type().ptr_type(AddressSpace::default()),
-                    "to_bytes_pointer",
-                );
-                bd.build_memcpy(dest, 1, src, 1, elements_width).unwrap();
+                let new_offset =
+                    bd.build_int_add(elements_start_offset, elements_width, "new_offset");
+
+                write_if_room(env, new_offset, || {
+                    // NOTE we are not actually sure the dest is properly aligned
+                    let dest =
+                        pointer_at_offset(bd, env.context.i8_type(), ptr, elements_start_offset);
+                    let src = bd.build_pointer_cast(
+                        elements,
+                        env.context.i8_type().ptr_type(AddressSpace::default()),
+                        "to_bytes_pointer",
+                    );
+                    bd.build_memcpy(dest, 1, src, 1, elements_width).unwrap();
+                });
 
-                bd.build_int_add(elements_start_offset, elements_width, "new_offset")
+                new_offset
             } else {
                 let element_type =
                     basic_type_from_layout(env, layout_interner, layout_interner.get_repr(elem));
@@ -21,7 +21,7 @@ pub fn add_default_roc_externs(env: &Env<'_, '_, '_>) {
     let i8_ptr_type = ctx.i8_type().ptr_type(AddressSpace::default());
 
     match env.mode {
-        super::build::LlvmBackendMode::CliTest => {
+        super::build::LlvmBackendMode::CliTest | super::build::LlvmBackendMode::Repl => {
             // expose this function
             if let Some(fn_val) = module.get_function("set_shared_buffer") {
                 fn_val.set_linkage(Linkage::External);
@@ -10,6 +10,7 @@ use roc_mono::layout::{Builtin, InLayout, Layout, LayoutInterner, LayoutRepr, Un
 use roc_mono::low_level::HigherOrder;
 
 use crate::backend::{ProcLookupData, ProcSource, WasmBackend};
+use crate::code_builder::CodeBuilder;
 use crate::layout::{CallConv, StackMemoryFormat, WasmLayout};
 use crate::storage::{AddressValue, StackMemoryLocation, StoredValue};
 use crate::PTR_TYPE;
@@ -1313,6 +1314,14 @@ impl<'a> LowLevelCall<'a> {
                             backend.code_builder.i64_div_u()
                         }
                     }
+                    // from compiler_rt
+                    I128 => {
+                        if is_signed {
+                            self.load_args_and_call_zig(backend, "__divti3")
+                        } else {
+                            self.load_args_and_call_zig(backend, "__udivti3")
+                        }
+                    }
                     x => todo!("{:?} for {:?}", self.lowlevel, x),
                 }
             }
@@ -1328,6 +1337,14 @@ impl<'a> LowLevelCall<'a> {
                 match CodeGenNumType::for_symbol(backend, self.arguments[0]) {
                     I32 => backend.code_builder.i32_rem_s(),
                     I64 => backend.code_builder.i64_rem_s(),
+                    // from compiler_rt
+                    I128 => {
+                        if symbol_is_signed_int(backend, self.arguments[0]) {
+                            self.load_args_and_call_zig(backend, "__modti3")
+                        } else {
+                            self.load_args_and_call_zig(backend, "__umodti3")
+                        }
+                    }
                     _ => todo!("{:?} for {:?}", self.lowlevel, self.ret_layout),
                 }
             }
@@ -1434,6 +1451,30 @@ impl<'a> LowLevelCall<'a> {
                 const PANIC_MSG: &str =
                     "integer absolute overflowed because its argument is the minimum value";
 
+                match CodeGenNumType::from(self.ret_layout) {
+                    I128 if layout_is_signed_int(self.ret_layout) => {
+                        return self.neg_num128(backend, true, PANIC_MSG);
+                    }
+                    I128 => {
+                        // The absolute value of an unsigned number is the number itself
+                        let (ret_local, ret_offset) = match &self.ret_storage {
+                            StoredValue::StackMemory { location, .. } => {
+                                location.local_and_offset(backend.storage.stack_frame_pointer)
+                            }
+                            _ => internal_error!("U128 should be in stack memory"),
+                        };
+                        backend.storage.copy_value_to_memory(
+                            &mut backend.code_builder,
+                            ret_local,
+                            ret_offset,
+                            self.arguments[0],
+                        );
+                        return;
+                    }
+                    Decimal => return self.load_args_and_call_zig(backend, bitcode::DEC_ABS),
+                    _ => {}
+                }
+
                 self.load_args(backend);
 
                 match CodeGenNumType::from(self.ret_layout) {
@@ -1498,6 +1539,12 @@ impl<'a> LowLevelCall<'a> {
                 const PANIC_MSG: &str =
                     "integer negation overflowed because its argument is the minimum value";
 
+                match CodeGenNumType::from(self.ret_layout) {
+                    I128 => return self.neg_num128(backend, false, PANIC_MSG),
+                    Decimal => return self.load_args_and_call_zig(backend, bitcode::DEC_NEGATE),
+                    _ => {}
+                }
+
                 self.load_args(backend);
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => {
@@ -1616,6 +1663,14 @@ impl<'a> LowLevelCall<'a> {
                             &bitcode::DEC_FROM_FLOAT[FloatWidth::F64],
                         );
                     }
+                    (Decimal, I128) => {
+                        let int_width = match arg_is_signed {
+                            true => IntWidth::I128,
+                            false => IntWidth::U128,
+                        };
+
+                        self.load_args_and_call_zig(backend, &bitcode::DEC_FROM_INT[int_width]);
+                    }
                     (Decimal, Decimal) => {}
 
                     _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
@@ -1669,7 +1724,10 @@ impl<'a> LowLevelCall<'a> {
                 let width = match ret_type {
                     CodeGenNumType::I32 => IntWidth::I32,
                     CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
+                    CodeGenNumType::I128 => match self.ret_layout_raw {
+                        LayoutRepr::Builtin(Builtin::Int(width)) => width,
+                        _ => internal_error!("Invalid return type for round: {:?}", ret_type),
+                    },
                     _ => internal_error!("Invalid return type for round: {:?}", ret_type),
                 };
 
@@ -1683,6 +1741,22 @@ impl<'a> LowLevelCall<'a> {
                 self.load_args(backend);
                 let arg_type = CodeGenNumType::for_symbol(backend, self.arguments[0]);
                 let ret_type = CodeGenNumType::from(self.ret_layout);
+
+                // Wasm has no instructions to convert floats to 128-bit integers
+                if let (I128, LayoutRepr::Builtin(Builtin::Int(width))) =
+                    (ret_type, self.ret_layout_raw)
+                {
+                    let intrinsic = match (arg_type, self.lowlevel) {
+                        (F32, NumCeiling) => &bitcode::NUM_CEILING_F32[width],
+                        (F64, NumCeiling) => &bitcode::NUM_CEILING_F64[width],
+                        (F32, NumFloor) => &bitcode::NUM_FLOOR_F32[width],
+                        (F64, NumFloor) => &bitcode::NUM_FLOOR_F64[width],
+                        _ => internal_error!("Invalid argument type for ceiling: {:?}", arg_type),
+                    };
+
+                    return self.load_args_and_call_zig(backend, intrinsic);
+                }
+
                 match (arg_type, self.lowlevel) {
                     (F32, NumCeiling) => {
                         backend.code_builder.f32_ceil();
@@ -1704,7 +1778,6 @@ impl<'a> LowLevelCall<'a> {
                     (I32, F64) => backend.code_builder.i32_trunc_s_f64(),
                     (I64, F32) => backend.code_builder.i64_trunc_s_f32(),
                     (I64, F64) => backend.code_builder.i64_trunc_s_f64(),
-                    (I128, _) => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
@@ -1720,7 +1793,10 @@ impl<'a> LowLevelCall<'a> {
                 let width = match ret_type {
                     CodeGenNumType::I32 => IntWidth::I32,
                     CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
+                    CodeGenNumType::I128 => match self.ret_layout_raw {
+                        LayoutRepr::Builtin(Builtin::Int(width)) => width,
+                        _ => internal_error!("Invalid return type for pow: {:?}", ret_type),
+                    },
                     _ => internal_error!("Invalid return type for pow: {:?}", ret_type),
                 };
 
@@ -1762,46 +1838,60 @@ impl<'a> LowLevelCall<'a> {
             NumBytesToU32 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U32),
             NumBytesToU64 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U64),
             NumBytesToU128 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U128),
-            NumBitwiseAnd => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_and(),
-                    I64 => backend.code_builder.i64_and(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+            NumBitwiseAnd => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_and();
                 }
-            }
-            NumBitwiseXor => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_xor(),
-                    I64 => backend.code_builder.i64_xor(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_and();
                 }
-            }
-            NumBitwiseOr => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_or(),
-                    I64 => backend.code_builder.i64_or(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_and),
+                _ => panic_ret_type(),
+            },
+            NumBitwiseXor => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_xor();
                 }
-            }
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_xor();
+                }
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_xor),
+                _ => panic_ret_type(),
+            },
+            NumBitwiseOr => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_or();
+                }
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_or();
+                }
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_or),
+                _ => panic_ret_type(),
+            },
             NumShiftLeftBy => {
                 let num = self.arguments[0];
                 let bits = self.arguments[1];
-                backend
-                    .storage
-                    .load_symbols(&mut backend.code_builder, &[num, bits]);
                 match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_shl(),
+                    I32 => {
+                        backend
+                            .storage
+                            .load_symbols(&mut backend.code_builder, &[num, bits]);
+                        backend.code_builder.i32_shl();
+                    }
                     I64 => {
+                        backend
+                            .storage
+                            .load_symbols(&mut backend.code_builder, &[num, bits]);
                         backend.code_builder.i64_extend_u_i32();
                         backend.code_builder.i64_shl();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
+                    I128 => self.load_args_and_call_zig(backend, "__ashlti3"), // from compiler_rt
                     _ => panic_ret_type(),
                 }
             }
@@ -1848,7 +1938,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.i64_extend_u_i32();
                         backend.code_builder.i64_shr_s();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
+                    I128 => self.load_args_and_call_zig(backend, "__ashrti3"), // from compiler_rt
                     _ => panic_ret_type(),
                 }
             }
@@ -2257,6 +2347,108 @@ impl<'a> LowLevelCall<'a> {
         }
     }
 
+    /// Bitwise operations on 128-bit integers, done on each 64-bit half separately
+    /// (Don't call "load arguments" or "load symbols" helpers before this, there's nothing to load)
+    fn bitwise_num128(&self, backend: &mut WasmBackend<'a, '_>, op: fn(&mut CodeBuilder<'a>)) {
+        let frame_pointer = backend.storage.stack_frame_pointer;
+        let local_and_offset = |stored: &StoredValue| match stored {
+            StoredValue::StackMemory { location, .. } => location.local_and_offset(frame_pointer),
+            _ => internal_error!("I128 should be in stack memory"),
+        };
+
+        let (ret_local, ret_offset) = local_and_offset(&self.ret_storage);
+        let (local0, offset0) = local_and_offset(backend.storage.get(&self.arguments[0]));
+        let (local1, offset1) = local_and_offset(backend.storage.get(&self.arguments[1]));
+
+        for half in [0, 8] {
+            backend.code_builder.get_local(ret_local);
+            backend.code_builder.get_local(local0);
+            backend.code_builder.i64_load(Align::Bytes8, offset0 + half);
+            backend.code_builder.get_local(local1);
+            backend.code_builder.i64_load(Align::Bytes8, offset1 + half);
+            op(&mut backend.code_builder);
+            backend
+                .code_builder
+                .i64_store(Align::Bytes8, ret_offset + half);
+        }
+    }
+
+    /// Negation for 128-bit integers, done on the two 64-bit halves. With `only_if_negative`,
+    /// this is the absolute value instead. The minimum value has no positive counterpart, so
+    /// either way, it panics on that.
+    /// (Don't call "load arguments" or "load symbols" helpers before this, there's nothing to load)
+    fn neg_num128(
+        &self,
+        backend: &mut WasmBackend<'a, '_>,
+        only_if_negative: bool,
+        panic_msg: &'a str,
+    ) {
+        let frame_pointer = backend.storage.stack_frame_pointer;
+        let local_and_offset = |stored: &StoredValue| match stored {
+            StoredValue::StackMemory { location, .. } => location.local_and_offset(frame_pointer),
+            _ => internal_error!("I128 should be in stack memory"),
+        };
+
+        let (ret_local, ret_offset) = local_and_offset(&self.ret_storage);
+        let (arg_local, arg_offset) = local_and_offset(backend.storage.get(&self.arguments[0]));
+
+        let low = backend.storage.create_anonymous_local(ValueType::I64);
+        let high = backend.storage.create_anonymous_local(ValueType::I64);
+
+        backend.code_builder.get_local(arg_local);
+        backend.code_builder.i64_load(Align::Bytes8, arg_offset);
+        backend.code_builder.set_local(low);
+        backend.code_builder.get_local(arg_local);
+        backend.code_builder.i64_load(Align::Bytes8, arg_offset + 8);
+        backend.code_builder.set_local(high);
+
+        // The minimum value is the only one with a high half of i64::MIN and a low half of zero
+        backend.code_builder.get_local(high);
+        backend.code_builder.i64_const(i64::MIN);
+        backend.code_builder.i64_eq();
+        backend.code_builder.get_local(low);
+        backend.code_builder.i64_eqz();
+        backend.code_builder.i32_and();
+        backend.code_builder.if_();
+        backend.stmt_internal_error(panic_msg);
+        backend.code_builder.end();
+
+        if only_if_negative {
+            backend.code_builder.get_local(high);
+            backend.code_builder.i64_const(0);
+            backend.code_builder.i64_lt_s();
+            backend.code_builder.if_();
+        }
+
+        // -(high, low) = (-high - borrow, -low), where there's a borrow unless low is zero
+        backend.code_builder.i64_const(0);
+        backend.code_builder.get_local(high);
+        backend.code_builder.i64_sub();
+        backend.code_builder.get_local(low);
+        backend.code_builder.i64_const(0);
+        backend.code_builder.i64_ne();
+        backend.code_builder.i64_extend_u_i32();
+        backend.code_builder.i64_sub();
+        backend.code_builder.set_local(high);
+
+        backend.code_builder.i64_const(0);
+        backend.code_builder.get_local(low);
+        backend.code_builder.i64_sub();
+        backend.code_builder.set_local(low);
+
+        if only_if_negative {
+            backend.code_builder.end();
+        }
+
+        for (half, offset) in [(low, 0), (high, 8)] {
+            backend.code_builder.get_local(ret_local);
+            backend.code_builder.get_local(half);
+            backend
+                .code_builder
+                .i64_store(Align::Bytes8, ret_offset + offset);
+        }
+    }
+
     /// Equality for 12-bit numbers. Checks if they're finite and contain the same bytes
     /// Takes care of loading the arguments
     fn eq_num128(
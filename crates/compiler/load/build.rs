@@ -110,6 +110,7 @@ fn write_types_for_module_real(module_id: ModuleId, filename: &str, output_path:
         &module.interns,
         &mut module.can_problems,
         &mut module.type_problems,
+        &module.warning_levels,
     );
 
     if problems.errors + problems.warnings > 0 {
@@ -22,7 +22,7 @@ pub use roc_load_internal::file::{
     Threading,
 };
 pub use roc_load_internal::module::{
-    EntryPoint, Expectations, ExposedToHost, LoadedModule, MonomorphizedModule,
+    EntryPoint, Expectations, ExposedToHost, LoadedModule, ModuleTiming, MonomorphizedModule,
 };
 pub use roc_solve::FunctionKind;
 
@@ -71,6 +71,8 @@ pub fn load_single_threaded<'a>(
         render,
         palette,
         exec_mode,
+        Default::default(),
+        false,
         roc_cache_dir,
     )
 }
@@ -682,7 +682,7 @@ fn tag_to_doc(in_func_ann: bool, tag: ast::Tag) -> Option<Tag> {
     }
 }
 
-fn comments_or_new_lines_to_docs<'a>(
+pub fn comments_or_new_lines_to_docs<'a>(
     comments_or_new_lines: &'a [roc_parse::ast::CommentOrNewline<'a>],
 ) -> Option<String> {
     let mut docs = String::new();
@@ -15,6 +15,7 @@ use parking_lot::Mutex;
 use roc_builtins::roc::module_source;
 use roc_can::abilities::{AbilitiesStore, PendingAbilitiesStore, ResolvedImpl};
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints, TypeOrVar};
+use roc_can::def::Annotation;
 use roc_can::expr::{DbgLookup, Declarations, ExpectLookup, PendingDerives};
 use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
@@ -54,9 +55,10 @@ use roc_parse::header::{
     ExposedName, HeaderType, ImportsEntry, PackageEntry, PackageHeader, PlatformHeader, To,
     TypedIdent,
 };
-use roc_parse::module::module_defs;
-use roc_parse::parser::{FileError, Parser, SourceError, SyntaxError};
-use roc_problem::Severity;
+use roc_parse::ident::UppercaseIdent;
+use roc_parse::module::parse_module_defs_recovering;
+use roc_parse::parser::{FileError, SourceError, SyntaxError};
+use roc_problem::{Level, Severity, WarningLevels};
 use roc_region::all::{LineInfo, Loc, Region};
 #[cfg(not(target_family = "wasm"))]
 use roc_reporting::report::to_https_problem_report_string;
@@ -66,7 +68,7 @@ use roc_solve::FunctionKind;
 use roc_solve_problem::TypeError;
 use roc_target::TargetInfo;
 use roc_types::subs::{CopiedImport, ExposedTypesStorageSubs, Subs, VarStore, Variable};
-use roc_types::types::{Alias, Types};
+use roc_types::types::{Alias, Polarity, Types};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
 use std::env::current_dir;
@@ -114,6 +116,12 @@ pub struct LoadConfig {
     pub threading: Threading,
     pub exec_mode: ExecutionMode,
     pub function_kind: FunctionKind,
+    /// What to do about each kind of warning. Warnings that are allowed by default and
+    /// expensive to look for, like `type-hole`, are only looked for if they're reported.
+    pub warning_levels: WarningLevels,
+    /// Follow each type mismatch with the steps that led up to it. This makes type checking
+    /// slower, so it's off unless asked for, e.g. with `roc check --explain-types`.
+    pub explain_types: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -149,7 +157,7 @@ fn start_phase<'a>(
     phase: Phase,
     arena: &'a Bump,
     state: &mut State<'a>,
-) -> Vec<BuildTask<'a>> {
+) -> Vec<QueuedTask<'a>> {
     // we blindly assume all dependencies are met
 
     use crate::work::PrepareStartPhase::*;
@@ -345,6 +353,8 @@ fn start_phase<'a>(
                     constraints,
                     constraint,
                     state.function_kind,
+                    state.warning_levels.level("type-hole") != Level::Allow,
+                    state.explain_types,
                     pending_derives,
                     var_store,
                     imported_modules,
@@ -410,7 +420,7 @@ fn start_phase<'a>(
                 }
             }
             Phase::MakeSpecializations => {
-                let mut specializations_we_must_make = state
+                let mut requested = state
                     .module_cache
                     .external_specializations_requested
                     .remove(&module_id)
@@ -424,9 +434,22 @@ fn start_phase<'a>(
                         .external_specializations_requested
                         .remove(&ModuleId::DERIVED_SYNTH)
                         .unwrap_or_default();
-                    specializations_we_must_make.extend(derived_synth_specializations)
+                    requested.extend(derived_synth_specializations)
                 }
 
+                // The requests arrive in whatever order the other modules' threads finished in.
+                // Making them in a fixed order keeps the symbols they generate, and so the
+                // final binary, the same from one build to the next.
+                let module_names = &state.module_cache.module_names;
+                requested.sort_by_cached_key(|(requester, _)| {
+                    stable_module_key(module_names, *requester)
+                });
+
+                let specializations_we_must_make = requested
+                    .into_iter()
+                    .map(|(_, specializations)| specializations)
+                    .collect();
+
                 let (
                     mut ident_ids,
                     mut subs,
@@ -546,7 +569,54 @@ fn start_phase<'a>(
         }
     };
 
-    vec![task]
+    vec![QueuedTask {
+        span: phase_span(&state.module_cache.module_names, module_id, phase),
+        task,
+    }]
+}
+
+/// The span a module's phase runs in, which is what `roc build --trace` shows it as.
+fn phase_span(
+    module_names: &MutMap<ModuleId, PQModuleName>,
+    module_id: ModuleId,
+    phase: Phase,
+) -> roc_tracing::Span {
+    // Only worked out if tracing is on.
+    let module = || match stable_module_key(module_names, module_id) {
+        (package, name) if package.is_empty() => name,
+        (package, name) => format!("{package}.{name}"),
+    };
+
+    match phase {
+        Phase::LoadHeader => roc_tracing::info_span!("load header", module = %module()),
+        Phase::Parse => roc_tracing::info_span!("parse", module = %module()),
+        Phase::CanonicalizeAndConstrain => {
+            roc_tracing::info_span!("canonicalize and constrain", module = %module())
+        }
+        Phase::SolveTypes => roc_tracing::info_span!("solve", module = %module()),
+        Phase::FindSpecializations => {
+            roc_tracing::info_span!("find specializations", module = %module())
+        }
+        Phase::MakeSpecializations => {
+            roc_tracing::info_span!("make specializations", module = %module())
+        }
+    }
+}
+
+/// Orders modules the same way in every build. Their ModuleIds can't be used for that, since
+/// they're handed out in whatever order the threads parsing the modules' importers get to them.
+/// The derived modules, which have no names here, always get the same IDs anyway.
+fn stable_module_key(
+    module_names: &MutMap<ModuleId, PQModuleName>,
+    module_id: ModuleId,
+) -> (String, String) {
+    match module_names.get(&module_id) {
+        Some(PackageQualified::Qualified(package, name)) => {
+            (package.to_string(), name.as_str().to_string())
+        }
+        Some(PackageQualified::Unqualified(name)) => (String::new(), name.as_str().to_string()),
+        None => (String::new(), format!("{module_id:?}")),
+    }
 }
 
 /// Values used to render expect output
@@ -631,7 +701,7 @@ enum Msg<'a> {
         module_expectations: VecMap<ModuleId, Expectations>,
     },
 
-    FailedToParse(FileError<'a, SyntaxError<'a>>),
+    FailedToParse(Vec<FileError<'a, SyntaxError<'a>>>),
     FailedToReadFile {
         filename: PathBuf,
         error: io::ErrorKind,
@@ -696,6 +766,10 @@ struct State<'a> {
     /// If the root is an app module, the shorthand specified in its header's `to` field
     pub opt_platform_shorthand: Option<&'a str>,
     pub platform_data: Option<PlatformData<'a>>,
+    /// If the root is an app module, the values and types it provides to its platform
+    pub app_provides: &'a [Loc<ExposedName<'a>>],
+    /// If the root is an app module, where its platform comes from, e.g. a URL
+    pub app_platform_src: Option<&'a str>,
     pub exposed_types: ExposedByModule,
     pub output_path: Option<&'a str>,
     pub platform_path: PlatformPath<'a>,
@@ -728,6 +802,9 @@ struct State<'a> {
 
     pub exposed_symbols_by_module: MutMap<ModuleId, VecSet<Symbol>>,
 
+    /// Where each top-level def was defined, for debug info.
+    pub def_regions: MutMap<Symbol, Region>,
+
     pub timings: MutMap<ModuleId, ModuleTiming>,
 
     // Each thread gets its own layout cache. When one "pending specializations"
@@ -740,6 +817,8 @@ struct State<'a> {
     pub render: RenderTarget,
     pub palette: Palette,
     pub exec_mode: ExecutionMode,
+    pub warning_levels: WarningLevels,
+    pub explain_types: bool,
 
     /// All abilities across all modules.
     pub world_abilities: WorldAbilities,
@@ -772,6 +851,8 @@ impl<'a> State<'a> {
         palette: Palette,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        warning_levels: WarningLevels,
+        explain_types: bool,
     ) -> Self {
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
         let cache_dir = roc_packaging::cache::roc_cache_dir();
@@ -785,6 +866,8 @@ impl<'a> State<'a> {
             target_info,
             function_kind,
             platform_data: None,
+            app_provides: &[],
+            app_platform_src: None,
             output_path: None,
             platform_path: PlatformPath::NotSpecified,
             module_cache: ModuleCache::default(),
@@ -801,6 +884,7 @@ impl<'a> State<'a> {
             constrained_ident_ids: IdentIds::exposed_builtins(0),
             ident_ids_by_module,
             declarations_by_id: MutMap::default(),
+            def_regions: MutMap::default(),
             exposed_symbols_by_module: MutMap::default(),
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
@@ -808,6 +892,8 @@ impl<'a> State<'a> {
             render,
             palette,
             exec_mode,
+            warning_levels,
+            explain_types,
             make_specializations_pass: MakeSpecializationsPass::Pass(1),
             world_abilities: Default::default(),
             layout_interner: GlobalLayoutInterner::with_capacity(128, target_info),
@@ -892,6 +978,8 @@ enum BuildTask<'a> {
         constraints: Constraints,
         constraint: ConstraintSoa,
         function_kind: FunctionKind,
+        find_type_holes: bool,
+        explain_types: bool,
         pending_derives: PendingDerives,
         var_store: VarStore,
         declarations: Declarations,
@@ -932,6 +1020,12 @@ enum BuildTask<'a> {
     },
 }
 
+/// A task, along with the span it runs in.
+struct QueuedTask<'a> {
+    task: BuildTask<'a>,
+    span: roc_tracing::Span,
+}
+
 #[derive(Debug)]
 enum WorkerMsg {
     Shutdown,
@@ -951,7 +1045,8 @@ pub enum LoadingProblem<'a> {
         filename: PathBuf,
         error: io::ErrorKind,
     },
-    ParsingFailed(FileError<'a, SyntaxError<'a>>),
+    /// All the syntax errors found in a module; there's always at least one.
+    ParsingFailed(Vec<FileError<'a, SyntaxError<'a>>>),
     UnexpectedHeader(String),
 
     MsgChannelDied,
@@ -977,9 +1072,9 @@ type MsgSender<'a> = Sender<Msg<'a>>;
 
 /// Add a task to the queue, and notify all the listeners.
 fn enqueue_task<'a>(
-    injector: &Injector<BuildTask<'a>>,
+    injector: &Injector<QueuedTask<'a>>,
     listeners: &[Sender<WorkerMsg>],
-    task: BuildTask<'a>,
+    task: QueuedTask<'a>,
 ) -> Result<(), LoadingProblem<'a>> {
     injector.push(task);
 
@@ -1020,6 +1115,8 @@ pub fn load_and_typecheck_str<'a>(
         threading,
         exec_mode: ExecutionMode::Check,
         function_kind,
+        warning_levels: WarningLevels::default(),
+        explain_types: false,
     };
 
     match load(
@@ -1269,6 +1366,8 @@ pub fn load<'a>(
         }
     };
 
+    let _entered = roc_tracing::info_span!("load").entered();
+
     match threads {
         Threads::Single => load_single_threaded(
             arena,
@@ -1280,6 +1379,8 @@ pub fn load<'a>(
             load_config.render,
             load_config.palette,
             load_config.exec_mode,
+            load_config.warning_levels,
+            load_config.explain_types,
             roc_cache_dir,
         ),
         Threads::Many(threads) => load_multi_threaded(
@@ -1293,6 +1394,8 @@ pub fn load<'a>(
             load_config.palette,
             threads,
             load_config.exec_mode,
+            load_config.warning_levels,
+            load_config.explain_types,
             roc_cache_dir,
         ),
     }
@@ -1309,6 +1412,8 @@ pub fn load_single_threaded<'a>(
     render: RenderTarget,
     palette: Palette,
     exec_mode: ExecutionMode,
+    warning_levels: WarningLevels,
+    explain_types: bool,
     roc_cache_dir: RocCacheDir<'_>,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
@@ -1341,6 +1446,8 @@ pub fn load_single_threaded<'a>(
         palette,
         number_of_workers,
         exec_mode,
+        warning_levels,
+        explain_types,
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
@@ -1400,7 +1507,7 @@ fn state_thread_step<'a>(
     state: State<'a>,
     src_dir: &Path,
     worker_listeners: &'a [Sender<WorkerMsg>],
-    injector: &Injector<BuildTask<'a>>,
+    injector: &Injector<QueuedTask<'a>>,
     msg_tx: &crossbeam::channel::Sender<Msg<'a>>,
     msg_rx: &crossbeam::channel::Receiver<Msg<'a>>,
 ) -> Result<ControlFlow<LoadResult<'a>, State<'a>>, LoadingProblem<'a>> {
@@ -1632,6 +1739,8 @@ fn load_multi_threaded<'a>(
     palette: Palette,
     available_threads: usize,
     exec_mode: ExecutionMode,
+    warning_levels: WarningLevels,
+    explain_types: bool,
     roc_cache_dir: RocCacheDir<'_>,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
@@ -1679,6 +1788,8 @@ fn load_multi_threaded<'a>(
         palette,
         num_workers,
         exec_mode,
+        warning_levels,
+        explain_types,
     );
 
     // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
@@ -1802,9 +1913,9 @@ fn load_multi_threaded<'a>(
 
 fn worker_task_step<'a>(
     worker_arena: &'a Bump,
-    worker: &Worker<BuildTask<'a>>,
-    injector: &Injector<BuildTask<'a>>,
-    stealers: &[Stealer<BuildTask<'a>>],
+    worker: &Worker<QueuedTask<'a>>,
+    injector: &Injector<QueuedTask<'a>>,
+    stealers: &[Stealer<QueuedTask<'a>>],
     worker_msg_rx: &crossbeam::channel::Receiver<WorkerMsg>,
     msg_tx: &MsgSender<'a>,
     src_dir: &Path,
@@ -1876,9 +1987,9 @@ fn worker_task_step<'a>(
 
 fn worker_task<'a>(
     worker_arena: &'a Bump,
-    worker: Worker<BuildTask<'a>>,
-    injector: &Injector<BuildTask<'a>>,
-    stealers: &[Stealer<BuildTask<'a>>],
+    worker: Worker<QueuedTask<'a>>,
+    injector: &Injector<QueuedTask<'a>>,
+    stealers: &[Stealer<QueuedTask<'a>>],
     worker_msg_rx: crossbeam::channel::Receiver<WorkerMsg>,
     msg_tx: MsgSender<'a>,
     src_dir: &Path,
@@ -1908,7 +2019,7 @@ fn worker_task<'a>(
                 if let Some(task) = find_task(&worker, injector, stealers) {
                     log!(
                         ">>> {}",
-                        match &task {
+                        match &task.task {
                             BuildTask::LoadModule { module_name, .. } => {
                                 format!("BuildTask::LoadModule({module_name:?})")
                             }
@@ -1972,7 +2083,7 @@ fn start_tasks<'a>(
     arena: &'a Bump,
     state: &mut State<'a>,
     work: MutSet<(ModuleId, Phase)>,
-    injector: &Injector<BuildTask<'a>>,
+    injector: &Injector<QueuedTask<'a>>,
     worker_listeners: &'a [Sender<WorkerMsg>],
 ) -> Result<(), LoadingProblem<'a>> {
     for (module_id, phase) in work {
@@ -2021,7 +2132,8 @@ macro_rules! debug_check_ir {
     };
 }
 
-/// Report modules that are imported, but from which nothing is used
+/// Report modules that are imported, but from which nothing is used,
+/// and values and types that are imported by name, but never used
 fn report_unused_imported_modules(
     state: &mut State<'_>,
     module_id: ModuleId,
@@ -2045,16 +2157,21 @@ fn report_unused_imported_modules(
         Occupied(entry) => entry.into_mut(),
     };
 
-    for (unused, region) in unused_imported_modules.drain() {
+    for (unused, region) in unused_imported_modules.iter() {
         if !unused.is_builtin() {
             existing.push(roc_problem::can::Problem::UnusedModuleImport(
-                unused, region,
+                *unused, *region,
             ));
         }
     }
 
     for (unused, region) in unused_imports.drain() {
-        existing.push(roc_problem::can::Problem::UnusedImport(unused, region));
+        let module_id = unused.module_id();
+
+        // If nothing from the whole module is used, that's already been reported.
+        if module_id.is_builtin() || !unused_imported_modules.contains_key(&module_id) {
+            existing.push(roc_problem::can::Problem::UnusedImport(unused, region));
+        }
     }
 }
 
@@ -2076,7 +2193,7 @@ fn update<'a>(
     src_dir: &Path,
     msg: Msg<'a>,
     msg_tx: MsgSender<'a>,
-    injector: &Injector<BuildTask<'a>>,
+    injector: &Injector<QueuedTask<'a>>,
     worker_listeners: &'a [Sender<WorkerMsg>],
     arena: &'a Bump,
 ) -> Result<State<'a>, LoadingProblem<'a>> {
@@ -2174,8 +2291,20 @@ fn update<'a>(
                 }
 
                 match header.header_type {
-                    App { to_platform, .. } => {
+                    App {
+                        provides,
+                        to_platform,
+                        ..
+                    } => {
                         debug_assert!(matches!(state.platform_path, PlatformPath::NotSpecified));
+                        state.app_provides = provides;
+                        state.app_platform_src = match to_platform {
+                            To::ExistingPackage(shorthand) => header
+                                .packages
+                                .get(shorthand)
+                                .map(|package_name| package_name.as_str()),
+                            To::NewPackage(package_name) => Some(package_name.as_str()),
+                        };
                         state.platform_path = PlatformPath::Valid(to_platform);
                     }
                     Package {
@@ -2192,6 +2321,8 @@ fn update<'a>(
                     Platform {
                         config_shorthand,
                         provides,
+                        requires,
+                        requires_types,
                         exposes_ids,
                         ..
                     } => {
@@ -2239,6 +2370,20 @@ fn update<'a>(
                         if state.opt_platform_shorthand == Some(config_shorthand) {
                             debug_assert!(state.platform_data.is_none());
 
+                            // Before type checking, make sure the app provides exactly what
+                            // the platform requires; otherwise the errors are confusing.
+                            if let Some(report) = to_incompatible_platform_report(
+                                &header,
+                                requires,
+                                requires_types,
+                                state.app_provides,
+                                state.app_platform_src,
+                                state.render,
+                                state.palette,
+                            ) {
+                                return Err(LoadingProblem::FormattedReport(report));
+                            }
+
                             state.platform_data = Some(PlatformData {
                                 module_id: header.module_id,
                                 provides,
@@ -2571,6 +2716,13 @@ fn update<'a>(
                 );
 
                 if state.goal_phase() > Phase::SolveTypes || state.exec_mode.build_if_checks() {
+                    state.def_regions.extend(
+                        decls
+                            .symbols
+                            .iter()
+                            .map(|loc_symbol| (loc_symbol.value, loc_symbol.region)),
+                    );
+
                     let layout_cache = state.layout_caches.pop().unwrap_or_else(|| {
                         LayoutCache::new(state.layout_interner.fork(), state.target_info)
                     });
@@ -2709,17 +2861,17 @@ fn update<'a>(
                 .dependencies
                 .notify(module_id, Phase::MakeSpecializations);
 
-            for (module_id, requested) in external_specializations_requested {
+            for (requested_of, requested) in external_specializations_requested {
                 let existing = match state
                     .module_cache
                     .external_specializations_requested
-                    .entry(module_id)
+                    .entry(requested_of)
                 {
                     Vacant(entry) => entry.insert(vec![]),
                     Occupied(entry) => entry.into_mut(),
                 };
 
-                existing.push(requested);
+                existing.push((module_id, requested));
             }
 
             enum NextStep {
@@ -3012,6 +3164,8 @@ fn finish_specialization<'a>(
     exposed_to_host: ExposedToHost,
     module_expectations: VecMap<ModuleId, Expectations>,
 ) -> Result<MonomorphizedModule<'a>, LoadingProblem<'a>> {
+    let _entered = roc_tracing::info_span!("finish specialization").entered();
+
     if false {
         println!(
             "total Type clones: {} ",
@@ -3155,7 +3309,9 @@ fn finish_specialization<'a>(
         host_exposed_lambda_sets,
         entry_point,
         sources,
+        def_regions: state.def_regions,
         timings: state.timings,
+        warning_levels: state.warning_levels,
         toplevel_expects,
         glue_layouts: GlueLayouts { getters: vec![] },
         uses_prebuilt_platform,
@@ -3241,6 +3397,7 @@ fn finish(
         resolved_implementations,
         sources,
         timings: state.timings,
+        warning_levels: state.warning_levels,
         docs_by_module: documentation,
         abilities_store,
     }
@@ -3355,10 +3512,9 @@ fn load_package_from_disk<'a>(
 
                     Ok(Msg::Header(platform_module_msg))
                 }
-                Err(fail) => Err(LoadingProblem::ParsingFailed(
-                    fail.map_problem(SyntaxError::Header)
-                        .into_file_error(filename.to_path_buf()),
-                )),
+                Err(fail) => Err(LoadingProblem::ParsingFailed(vec![fail
+                    .map_problem(SyntaxError::Header)
+                    .into_file_error(filename.to_path_buf())])),
             }
         }
 
@@ -3668,10 +3824,10 @@ fn verify_interface_matches_file_path<'a>(
     use roc_parse::parser::EHeader;
     let syntax_problem =
         SyntaxError::Header(EHeader::InconsistentModuleName(interface_name.region));
-    let problem = LoadingProblem::ParsingFailed(FileError {
+    let problem = LoadingProblem::ParsingFailed(vec![FileError {
         problem: SourceError::new(syntax_problem, state),
         filename: path.to_path_buf(),
-    });
+    }]);
     Err(problem)
 }
 
@@ -3957,10 +4113,9 @@ fn parse_header<'a>(
                 opt_platform_shorthand: None,
             })
         }
-        Err(fail) => Err(LoadingProblem::ParsingFailed(
-            fail.map_problem(SyntaxError::Header)
-                .into_file_error(filename),
-        )),
+        Err(fail) => Err(LoadingProblem::ParsingFailed(vec![fail
+            .map_problem(SyntaxError::Header)
+            .into_file_error(filename)])),
     }
 }
 
@@ -3994,12 +4149,16 @@ fn load_packages<'a>(
             #[cfg(not(target_family = "wasm"))]
             {
                 // If this is a HTTPS package, synchronously download it
-                // to the cache before proceeding.
+                // to the cache before proceeding (unless it's been vendored).
 
                 // TODO we should do this async; however, with the current
                 // architecture of file.rs (which doesn't use async/await),
                 // this would be very difficult!
-                match cache::install_package(roc_cache_dir, src) {
+                //
+                // A copy from `roc vendor` takes precedence, so that builds can work offline.
+                let vendor_dir = cwd.join(cache::VENDOR_DIR_NAME);
+
+                match cache::find_package(roc_cache_dir, &vendor_dir, src) {
                     Ok((package_dir, opt_root_module)) => {
                         // You can optionally specify the root module using the URL fragment,
                         // e.g. #foo.roc
@@ -4453,6 +4612,8 @@ impl<'a> BuildTask<'a> {
         constraints: Constraints,
         constraint: ConstraintSoa,
         function_kind: FunctionKind,
+        find_type_holes: bool,
+        explain_types: bool,
         pending_derives: PendingDerives,
         var_store: VarStore,
         imported_modules: MutMap<ModuleId, Region>,
@@ -4478,6 +4639,8 @@ impl<'a> BuildTask<'a> {
             constraints,
             constraint,
             function_kind,
+            find_type_holes,
+            explain_types,
             pending_derives,
             var_store,
             declarations,
@@ -4755,6 +4918,7 @@ fn run_solve_solve(
     mut constraints: Constraints,
     constraint: ConstraintSoa,
     function_kind: FunctionKind,
+    explain_types: bool,
     pending_derives: PendingDerives,
     var_store: VarStore,
     module: Module,
@@ -4812,6 +4976,7 @@ fn run_solve_solve(
             pending_derives,
             exposed_by_module: &exposed_for_module.exposed_by_module,
             derived_module,
+            explain_types: !module_id.is_builtin() && explain_types,
             #[cfg(debug_assertions)]
             checkmate,
         };
@@ -4873,6 +5038,37 @@ fn run_solve_solve(
     }
 }
 
+/// The `_`s in this module's type annotations, along with the types that were inferred for them.
+fn type_holes(decls: &Declarations, subs: &mut Subs) -> Vec<TypeError> {
+    use roc_can::traverse::Visitor;
+
+    struct Holes(Vec<Loc<Variable>>);
+
+    impl Visitor for Holes {
+        fn visit_annotation(&mut self, annotation: &Annotation) {
+            // Extension variables can be inferred without a `_` being written for them;
+            // those don't have a region.
+            let holes = annotation.introduced_variables.inferred.iter();
+
+            self.0
+                .extend(holes.filter(|hole| hole.region != Region::zero()).copied());
+        }
+    }
+
+    let mut holes = Holes(Vec::new());
+
+    holes.visit_decls(decls);
+
+    holes
+        .0
+        .into_iter()
+        .map(|hole| TypeError::TypeHole {
+            region: hole.region,
+            typ: subs.var_to_error_type(hole.value, Polarity::OF_VALUE),
+        })
+        .collect()
+}
+
 fn run_solve<'a>(
     module: Module,
     ident_ids: IdentIds,
@@ -4882,6 +5078,8 @@ fn run_solve<'a>(
     constraints: Constraints,
     constraint: ConstraintSoa,
     function_kind: FunctionKind,
+    find_type_holes: bool,
+    explain_types: bool,
     pending_derives: PendingDerives,
     var_store: VarStore,
     decls: Declarations,
@@ -4912,6 +5110,7 @@ fn run_solve<'a>(
                     constraints,
                     constraint,
                     function_kind,
+                    explain_types,
                     pending_derives,
                     var_store,
                     module,
@@ -4943,6 +5142,7 @@ fn run_solve<'a>(
                 constraints,
                 constraint,
                 function_kind,
+                explain_types,
                 pending_derives,
                 var_store,
                 module,
@@ -4958,13 +5158,18 @@ fn run_solve<'a>(
         solved: mut solved_subs,
         solved_implementations,
         exposed_vars_by_symbol,
-        problems,
+        mut problems,
         abilities_store,
 
         #[cfg(debug_assertions)]
         checkmate,
     } = solve_result;
 
+    // Type holes are only reported when asked for, so don't bother finding them otherwise
+    if !module_id.is_builtin() && find_type_holes {
+        problems.extend(type_holes(&decls, solved_subs.inner_mut()));
+    }
+
     let exposed_types = roc_solve::module::exposed_types_storage_subs(
         module_id,
         &mut solved_subs,
@@ -5327,14 +5532,19 @@ fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, Loadi
     let parse_start = Instant::now();
     let source = header.parse_state.original_bytes();
     let parse_state = header.parse_state;
-    let mut parsed_defs = match module_defs().parse(arena, parse_state.clone(), 0) {
-        Ok((_, success, _state)) => success,
-        Err((_, fail)) => {
-            return Err(LoadingProblem::ParsingFailed(
-                fail.into_file_error(header.module_path, &parse_state),
-            ));
-        }
-    };
+    let (mut parsed_defs, syntax_errors) = parse_module_defs_recovering(arena, parse_state.clone());
+
+    if !syntax_errors.is_empty() {
+        // Canonicalizing the defs which did parse would mostly report that the ones which
+        // didn't are missing, so stop here and report all the syntax errors together.
+        let problems = syntax_errors
+            .into_iter()
+            .map(|fail| fail.into_file_error(header.module_path.clone(), &parse_state))
+            .collect();
+
+        return Err(LoadingProblem::ParsingFailed(problems));
+    }
+
     for value in header.defined_values.into_iter() {
         // TODO: should these have a region?
         parsed_defs.push_value_def(value, Region::zero(), &[], &[]);
@@ -6159,7 +6369,7 @@ fn load_derived_partial_procs<'a>(
 }
 
 fn run_task<'a>(
-    task: BuildTask<'a>,
+    QueuedTask { task, span }: QueuedTask<'a>,
     arena: &'a Bump,
     src_dir: &Path,
     msg_tx: MsgSender<'a>,
@@ -6168,6 +6378,8 @@ fn run_task<'a>(
 ) -> Result<(), LoadingProblem<'a>> {
     use BuildTask::*;
 
+    let _entered = span.enter();
+
     let msg = match task {
         LoadModule {
             module_name,
@@ -6217,6 +6429,8 @@ fn run_task<'a>(
             constraints,
             constraint,
             function_kind,
+            find_type_holes,
+            explain_types,
             pending_derives,
             var_store,
             ident_ids,
@@ -6236,6 +6450,8 @@ fn run_task<'a>(
             constraints,
             constraint,
             function_kind,
+            find_type_holes,
+            explain_types,
             pending_derives,
             var_store,
             declarations,
@@ -6422,7 +6638,7 @@ fn to_incorrect_module_name_report<'a>(
 }
 
 fn to_parse_problem_report<'a>(
-    problem: FileError<'a, SyntaxError<'a>>,
+    problems: Vec<FileError<'a, SyntaxError<'a>>>,
     mut module_ids: ModuleIds,
     all_ident_ids: IdentIdsByModule,
     render: RenderTarget,
@@ -6430,8 +6646,14 @@ fn to_parse_problem_report<'a>(
 ) -> String {
     use roc_reporting::report::{parse_problem, RocDocAllocator};
 
+    // All the problems come from the same module.
+    let bytes = match problems.first() {
+        Some(problem) => problem.problem.bytes,
+        None => internal_error!("parsing failed without any syntax errors"),
+    };
+
     // TODO this is not in fact safe
-    let src = unsafe { from_utf8_unchecked(problem.problem.bytes) };
+    let src = unsafe { from_utf8_unchecked(bytes) };
     let src_lines = src.lines().collect::<Vec<_>>();
     // let mut src_lines: Vec<&str> = problem.prefix.lines().collect();
     // src_lines.extend(src.lines().skip(1));
@@ -6450,19 +6672,127 @@ fn to_parse_problem_report<'a>(
 
     let lines = LineInfo::new(src);
 
-    let report = parse_problem(
-        &alloc,
-        &lines,
-        problem.filename.clone(),
-        starting_line,
-        problem,
-    );
+    let reports = problems.into_iter().map(|problem| {
+        let report = parse_problem(
+            &alloc,
+            &lines,
+            problem.filename.clone(),
+            starting_line,
+            problem,
+        );
 
-    let mut buf = String::new();
+        let mut buf = String::new();
+
+        report.render(render, &mut buf, &alloc, &palette);
+
+        buf
+    });
+
+    reports.collect::<Vec<_>>().join("\n\n")
+}
+
+/// A report for when an app doesn't provide exactly the values and types its platform
+/// requires, or None if it does.
+fn to_incompatible_platform_report(
+    platform_header: &ModuleHeader,
+    requires: &[Loc<TypedIdent>],
+    requires_types: &[Loc<UppercaseIdent>],
+    app_provides: &[Loc<ExposedName>],
+    app_platform_src: Option<&str>,
+    render: RenderTarget,
+    palette: Palette,
+) -> Option<String> {
+    use roc_reporting::report::{Annotation, Report, RocDocAllocator};
+    use ven_pretty::DocAllocator;
+
+    let provided: Vec<&str> = app_provides.iter().map(|loc| loc.value.as_str()).collect();
+    let required: Vec<(&str, Region)> = requires
+        .iter()
+        .map(|loc| (loc.value.ident.value, loc.region))
+        .chain(
+            requires_types
+                .iter()
+                .map(|loc| (<&str>::from(loc.value), loc.region)),
+        )
+        .collect();
+
+    let missing: Vec<(&str, Region)> = required
+        .iter()
+        .copied()
+        .filter(|(name, _)| !provided.contains(name))
+        .collect();
+    let extra: Vec<&str> = provided
+        .iter()
+        .copied()
+        .filter(|name| {
+            !required
+                .iter()
+                .any(|(required_name, _)| required_name == name)
+        })
+        .collect();
+
+    if missing.is_empty() && extra.is_empty() {
+        return None;
+    }
+
+    let src = std::str::from_utf8(platform_header.parse_state.original_bytes()).unwrap_or_default();
+    let src_lines = src.lines().collect::<Vec<_>>();
+    let lines = LineInfo::new(src);
+    let interns = Interns::default();
+    let alloc = RocDocAllocator::new(&src_lines, platform_header.module_id, &interns);
+
+    let mut stack = vec![
+        alloc.reflow("This app's header doesn't provide what its platform's header requires.")
+    ];
+
+    for (name, region) in missing {
+        stack.push(alloc.concat([
+            alloc.reflow("The platform requires "),
+            alloc.string(name.to_string()).annotate(Annotation::Symbol),
+            alloc.reflow(", but the app doesn't provide it:"),
+        ]));
+        stack.push(alloc.region(lines.convert_region(region)));
+    }
+
+    if !extra.is_empty() {
+        let extra_count = extra.len();
 
+        stack.push(
+            alloc.concat([
+                alloc.reflow("The app provides "),
+                alloc.intersperse(
+                    extra
+                        .into_iter()
+                        .map(|name| alloc.string(name.to_string()).annotate(Annotation::Symbol)),
+                    alloc.reflow(", "),
+                ),
+                alloc.reflow(", but the platform doesn't require "),
+                alloc.reflow(if extra_count == 1 { "it." } else { "them." }),
+            ]),
+        );
+    }
+
+    stack.push(match app_platform_src {
+        Some(platform_src) => alloc.concat([
+            alloc.reflow("The app's platform comes from "),
+            alloc.string(platform_src.to_string()),
+            alloc.reflow(". "),
+            alloc.reflow("This usually means the app was written for a different version of that platform. Either update the app's `provides` to match this version, or switch the app back to the version it was written for."),
+        ]),
+        None => alloc.reflow("This usually means the app was written for a different version of its platform. Either update the app's `provides` to match this version, or switch the app back to the version it was written for."),
+    });
+
+    let report = Report {
+        filename: platform_header.module_path.clone(),
+        doc: alloc.stack(stack),
+        title: "INCOMPATIBLE PLATFORM".to_string(),
+        severity: Severity::RuntimeError,
+    };
+
+    let mut buf = String::new();
     report.render(render, &mut buf, &alloc, &palette);
 
-    buf
+    Some(buf)
 }
 
 fn to_missing_platform_report(module_id: ModuleId, other: &PlatformPath) -> String {
@@ -15,6 +15,7 @@ use roc_mono::ir::{GlueLayouts, HostExposedLambdaSets, LambdaSetId, Proc, ProcLa
 use roc_mono::layout::{LayoutCache, STLayoutInterner};
 use roc_parse::ast::{CommentOrNewline, Defs, TypeAnnotation, ValueDef};
 use roc_parse::header::{HeaderType, PackageName};
+use roc_problem::WarningLevels;
 use roc_region::all::{Loc, Region};
 use roc_solve::module::Solved;
 use roc_solve_problem::TypeError;
@@ -44,6 +45,8 @@ pub struct LoadedModule {
     pub resolved_implementations: ResolvedImplementations,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
     pub timings: MutMap<ModuleId, ModuleTiming>,
+    /// The warning levels this module was loaded with, for reporting its problems.
+    pub warning_levels: WarningLevels,
     pub docs_by_module: VecMap<ModuleId, ModuleDocumentation>,
     pub abilities_store: AbilitiesStore,
 }
@@ -172,7 +175,11 @@ pub struct MonomorphizedModule<'a> {
     pub entry_point: EntryPoint<'a>,
     pub exposed_to_host: ExposedToHost,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
+    /// Where each top-level def was defined, for debug info.
+    pub def_regions: MutMap<Symbol, Region>,
     pub timings: MutMap<ModuleId, ModuleTiming>,
+    /// The warning levels this module was loaded with, for reporting its problems.
+    pub warning_levels: WarningLevels,
     pub expectations: VecMap<ModuleId, Expectations>,
     pub uses_prebuilt_platform: bool,
     pub glue_layouts: GlueLayouts<'a>,
@@ -307,4 +314,95 @@ impl ModuleTiming {
 
         calculate(Some(end_time.duration_since(*start_time))).unwrap_or_default()
     }
+
+    const TABLE_COLUMNS: [&'static str; 8] = [
+        "Read",
+        "Parse",
+        "Canonicalize",
+        "Constrain",
+        "Solve",
+        "Specialize",
+        "Other",
+        "Total",
+    ];
+
+    fn table_columns(&self) -> [Duration; 8] {
+        let mut parse = self.parse_header;
+        parse += self.parse_body;
+
+        let mut specialize = self.find_specializations;
+        for pass_time in self.make_specializations.iter() {
+            specialize += *pass_time;
+        }
+
+        [
+            self.read_roc_file,
+            parse,
+            self.canonicalize,
+            self.constrain,
+            self.solve,
+            specialize,
+            self.other(),
+            self.total(),
+        ]
+    }
+
+    /// Render a table of how long each phase took in each module, slowest module
+    /// first, followed by the totals across all modules. Modules are compiled in
+    /// parallel, so the totals can add up to more than the time the build took.
+    pub fn table<'a>(modules: impl IntoIterator<Item = (&'a str, &'a ModuleTiming)>) -> String {
+        use std::fmt::Write;
+
+        let mut rows: Vec<(&str, [Duration; 8])> = modules
+            .into_iter()
+            .map(|(name, timing)| (name, timing.table_columns()))
+            .collect();
+
+        rows.sort_by(|(_, a), (_, b)| b[7].as_secs_f64().total_cmp(&a[7].as_secs_f64()));
+
+        let mut totals = [Duration::default(); 8];
+
+        for (_, columns) in rows.iter() {
+            for (total, duration) in totals.iter_mut().zip(columns) {
+                *total += *duration;
+            }
+        }
+
+        const ALL_MODULES: &str = "All modules";
+
+        let name_width = rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .chain([ALL_MODULES.len()])
+            .max()
+            .unwrap_or_default();
+
+        let mut buf = String::new();
+
+        write!(buf, "    {:name_width$}", "Module").unwrap();
+
+        for column in Self::TABLE_COLUMNS {
+            write!(buf, "  {column:>12}").unwrap();
+        }
+
+        buf.push('\n');
+
+        let write_row = |buf: &mut String, name: &str, columns: &[Duration; 8]| {
+            write!(buf, "    {name:name_width$}").unwrap();
+
+            for duration in columns {
+                write!(buf, "  {:>9.3} ms", duration.as_secs_f64() * 1000.0).unwrap();
+            }
+
+            buf.push('\n');
+        };
+
+        for (name, columns) in rows.iter() {
+            write_row(&mut buf, name, columns);
+        }
+
+        write_row(&mut buf, ALL_MODULES, &totals);
+
+        buf
+    }
 }
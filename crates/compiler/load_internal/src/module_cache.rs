@@ -27,8 +27,9 @@ pub(crate) struct ModuleCache<'a> {
     pub(crate) typechecked: MutMap<ModuleId, TypeCheckedModule<'a>>,
     pub(crate) found_specializations: MutMap<ModuleId, FoundSpecializationsModule<'a>>,
     pub(crate) late_specializations: MutMap<ModuleId, LateSpecializationsModule<'a>>,
+    /// The specializations each module was asked for, along with the module that asked
     pub(crate) external_specializations_requested:
-        MutMap<ModuleId, Vec<ExternalSpecializations<'a>>>,
+        MutMap<ModuleId, Vec<(ModuleId, ExternalSpecializations<'a>)>>,
 
     /// Various information
     pub(crate) imports: MutMap<ModuleId, MutSet<ModuleId>>,
@@ -59,6 +59,8 @@ fn load_and_typecheck(
         palette: DEFAULT_PALETTE,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Check,
+        warning_levels: Default::default(),
+        explain_types: false,
     };
 
     match roc_load_internal::file::load(
@@ -662,6 +664,44 @@ fn parse_problem() {
     }
 }
 
+#[test]
+fn header_parse_problem() {
+    // The defs are never parsed when the header doesn't, so only the header's problem is
+    // reported, even though the defs have problems of their own.
+    let modules = vec![(
+        "Main",
+        indoc!(
+            r#"
+                interface Main exposes [main, @Foo] imports []
+
+                main = [1, 2, , 3]
+
+                other = [4, 5, , 6]
+                "#
+        ),
+    )];
+
+    match multiple_modules("header_parse_problem", modules) {
+        Err(report) => assert_eq!(
+            report,
+            indoc!(
+                "
+                    ── WEIRD EXPOSES ─────────────────────────────── tmp/header_parse_problem/Main ─
+
+                    I am partway through parsing an `exposes` list, but I got stuck here:
+
+                    1│  interface Main exposes [main, @Foo] imports []
+                                                      ^
+
+                    I was expecting a type name, value name or function name next, like
+
+                        exposes [Animal, default, tame]"
+            )
+        ),
+        Ok(_) => unreachable!("we expect failure here"),
+    }
+}
+
 #[test]
 #[should_panic(expected = "FILE NOT FOUND")]
 fn file_not_found() {
@@ -776,6 +816,130 @@ fn platform_parse_error() {
     }
 }
 
+#[test]
+fn vendored_url_packages() {
+    // These tests load with RocCacheDir::Disallowed, so this would panic if the loader tried
+    // to download the packages instead of using the copies in vendor/
+    let modules = vec![
+        (
+            "vendor/pfhash/main.roc",
+            indoc!(
+                r#"
+                    platform "vendored"
+                        requires {} { main : Str }
+                        exposes []
+                        packages {}
+                        imports []
+                        provides [mainForHost]
+
+                    mainForHost : Str
+                    mainForHost = main
+                    "#
+            ),
+        ),
+        (
+            "vendor/greethash/main.roc",
+            indoc!(
+                r#"
+                    package "greet"
+                        exposes [Greet]
+                        packages {}
+                    "#
+            ),
+        ),
+        (
+            "vendor/greethash/Greet.roc",
+            indoc!(
+                r#"
+                    interface Greet
+                        exposes [greet]
+                        imports []
+
+                    greet : Str -> Str
+                    greet = \name -> "Hello, \(name)!"
+                    "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                    app "vendored"
+                        packages {
+                            pf: "https://example.com/pf/pfhash.tar.br",
+                            greet: "https://example.com/greet/greethash.tar.br",
+                        }
+                        imports [greet.Greet]
+                        provides [main] to pf
+
+                    main = Greet.greet "World"
+                    "#
+            ),
+        ),
+    ];
+
+    if let Err(report) = multiple_modules("vendored_url_packages", modules) {
+        panic!("The app should load using the vendored packages, but got:\n{report}");
+    }
+}
+
+#[test]
+fn app_provides_something_else_than_platform_requires() {
+    let modules = vec![
+        (
+            "platform/main.roc",
+            indoc!(
+                r#"
+                    platform "hello-world"
+                        requires {} { program : Str }
+                        exposes []
+                        packages {}
+                        imports []
+                        provides [mainForHost]
+
+                    mainForHost : Str
+                    mainForHost = program
+                    "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                    app "hello-world"
+                        packages { pf: "platform/main.roc" }
+                        imports []
+                        provides [main] to pf
+
+                    main = "Hello, World!\n"
+                    "#
+            ),
+        ),
+    ];
+
+    match multiple_modules(
+        "app_provides_something_else_than_platform_requires",
+        modules,
+    ) {
+        Err(report) => {
+            assert!(
+                report.contains("INCOMPATIBLE PLATFORM"),
+                "report=({report})"
+            );
+            assert!(
+                report.contains("The platform requires program, but the app doesn't provide it:"),
+                "report=({report})"
+            );
+            assert!(
+                report.contains("The app provides main, but the platform doesn't require it."),
+                "report=({report})"
+            );
+            assert!(report.contains("program : Str"), "report=({report})");
+        }
+        Ok(_) => unreachable!("we expect failure here"),
+    }
+}
+
 #[test]
 // See https://github.com/roc-lang/roc/issues/2413
 fn platform_exposes_main_return_by_pointer_issue() {
@@ -890,6 +1054,91 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
     );
 }
 
+#[test]
+fn unused_type_import() {
+    let modules = vec![
+        (
+            "Age",
+            indoc!(
+                r#"
+                    interface Age exposes [Age, zero] imports []
+
+                    Age := U32
+
+                    zero : Age
+                    zero = @Age 0
+                    "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                    interface Main exposes [start] imports [Age.{ Age, zero }]
+
+                    start = zero
+                    "#
+            ),
+        ),
+    ];
+
+    let err = multiple_modules("unused_type_import", modules).unwrap_err();
+    assert_eq!(
+        err,
+        indoc!(
+            r#"
+                ── UNUSED IMPORT ───────────────────────────────── tmp/unused_type_import/Main ─
+
+                `Age.Age` is not used in this module.
+
+                1│  interface Main exposes [start] imports [Age.{ Age, zero }]
+                                                                  ^^^
+
+                Since `Age.Age` isn't used, you don't need to import it.
+                "#
+        ),
+        "\n{}",
+        err
+    );
+}
+
+#[test]
+fn exposed_typo() {
+    let modules = vec![(
+        "Main",
+        indoc!(
+            r#"
+                interface Main exposes [parseJson, parse] imports []
+
+                parseJsn = \str -> str
+
+                parse = \str -> parseJsn str
+                "#
+        ),
+    )];
+
+    let err = multiple_modules("exposed_typo", modules).unwrap_err();
+    assert_eq!(
+        err,
+        indoc!(
+            r#"
+                ── MISSING DEFINITION ────────────────────────────────── tmp/exposed_typo/Main ─
+
+                `parseJson` is listed as exposed, but it isn't defined in this module.
+
+                Did you mean one of these?
+
+                    parseJsn
+
+                You can fix this by adding a definition for `parseJson`, or by
+                removing it from `exposes`.
+                "#
+        ),
+        "\n{}",
+        err
+    );
+}
+
 #[test]
 fn issue_2863_module_type_does_not_exist() {
     let modules = vec![
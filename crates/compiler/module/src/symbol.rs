@@ -1588,6 +1588,7 @@ define_builtins! {
         19 HASH_HASH_STR_BYTES: "hashStrBytes"
         20 HASH_HASH_LIST: "hashList"
         21 HASH_HASH_UNORDERED: "hashUnordered"
+        22 HASH_HASH_BOX: "hashBox"
     }
     14 INSPECT: "Inspect" => {
         0 INSPECT_INSPECT_ABILITY: "Inspect" exposed_type=true
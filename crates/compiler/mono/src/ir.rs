@@ -27,7 +27,7 @@ use roc_late_solve::storage::{ExternalModuleStorage, ExternalModuleStorageSnapsh
 use roc_late_solve::{resolve_ability_specialization, AbilitiesView, Resolved, UnificationFailed};
 use roc_module::ident::{ForeignSymbol, Lowercase, TagName};
 use roc_module::low_level::{LowLevel, LowLevelWrapperType};
-use roc_module::symbol::{IdentIds, ModuleId, Symbol};
+use roc_module::symbol::{IdentIds, Interns, ModuleId, Symbol};
 use roc_problem::can::{RuntimeError, ShadowKind};
 use roc_region::all::{Loc, Region};
 use roc_std::RocDec;
@@ -36,6 +36,7 @@ use roc_types::subs::{
     instantiate_rigids, storage_copy_var_to, Content, ExhaustiveMark, FlatType, RedundantMark,
     StorageSubs, Subs, Variable, VariableSubsSlice,
 };
+use std::cell::RefCell;
 use std::collections::HashMap;
 use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder};
 
@@ -49,8 +50,57 @@ mod erased;
 mod literal;
 mod pattern;
 
+thread_local! {
+    /// When set, IR is printed with these symbol names rather than symbol IDs. Release builds
+    /// of the compiler don't record symbol names for Debug, so this is how they print
+    /// readable IR, e.g. for `roc build --emit mono`.
+    static SYMBOL_NAMES: RefCell<Option<Interns>> = RefCell::new(None);
+}
+
+/// Pretty-print the given procs with their symbols' names and the layouts of their arguments,
+/// regardless of how this build of the compiler prints symbols.
+pub fn procs_to_pretty_with_names<'a, 'p, I>(
+    procs: impl IntoIterator<Item = &'p Proc<'a>>,
+    interner: &I,
+    interns: &Interns,
+    width: usize,
+) -> String
+where
+    'a: 'p,
+    I: LayoutInterner<'a>,
+{
+    SYMBOL_NAMES.with(|names| *names.borrow_mut() = Some(interns.clone()));
+
+    let procs_string = procs
+        .into_iter()
+        .map(|proc| proc.to_pretty(interner, width, true))
+        .collect::<std::vec::Vec<_>>()
+        .join("\n");
+
+    SYMBOL_NAMES.with(|names| *names.borrow_mut() = None);
+
+    procs_string
+}
+
+fn symbol_name(symbol: Symbol) -> Option<String> {
+    SYMBOL_NAMES.with(|names| {
+        let names = names.borrow();
+        let interns = names.as_ref()?;
+        let module_name = interns.module_ids.get_name(symbol.module_id())?;
+        let ident = interns
+            .all_ident_ids
+            .get(&symbol.module_id())?
+            .get_name(symbol.ident_id())?;
+
+        Some(format!("{}.{ident}", module_name.as_str()))
+    })
+}
+
 #[inline(always)]
 pub fn pretty_print_ir_symbols() -> bool {
+    if SYMBOL_NAMES.with(|names| names.borrow().is_some()) {
+        return true;
+    }
     dbg_do!(ROC_PRINT_IR_AFTER_SPECIALIZATION, {
         return true;
     });
@@ -1971,7 +2021,9 @@ impl<'a> Literal<'a> {
 pub(crate) fn symbol_to_doc_string(symbol: Symbol, force_pretty: bool) -> String {
     use roc_module::ident::ModuleName;
 
-    if pretty_print_ir_symbols() || force_pretty {
+    if let Some(name) = symbol_name(symbol) {
+        name
+    } else if pretty_print_ir_symbols() || force_pretty {
         format!("{symbol:?}")
     } else {
         let text = format!("{symbol}");
@@ -2920,6 +2972,7 @@ fn pattern_to_when(
         | NumLiteral(..)
         | FloatLiteral(..)
         | StrLiteral(..)
+        | StrPrefix(..)
         | roc_can::pattern::Pattern::SingleQuote(..) => {
             // These patters are refutable, and thus should never occur outside a `when` expression
             // They should have been replaced with `UnsupportedPattern` during canonicalization
@@ -112,6 +112,10 @@ enum Test<'a> {
     IsFloat(u64, FloatWidth),
     IsDecimal([u8; 16]),
     IsStr(Box<str>),
+    /// Unlike the other tests, prefix tests can overlap with one another and with `IsStr`.
+    /// That's fine, because each edge keeps every branch that could still match once
+    /// its test succeeds, so it does not matter which of the overlapping tests we try first.
+    IsStrPrefix(Box<str>),
     IsBit(bool),
     IsByte {
         tag_id: TagIdIntType,
@@ -134,6 +138,7 @@ impl<'a> Test<'a> {
             Test::IsFloat(_, _) => false,
             Test::IsDecimal(_) => false,
             Test::IsStr(_) => false,
+            Test::IsStrPrefix(_) => false,
             Test::IsBit(_) => true,
             Test::IsByte { .. } => true,
             Test::IsListLen { bound, .. } => match bound {
@@ -186,6 +191,10 @@ impl<'a> Hash for Test<'a> {
                 state.write_u8(7);
                 (len, bound).hash(state);
             }
+            IsStrPrefix(v) => {
+                state.write_u8(8);
+                v.hash(state);
+            }
         }
     }
 }
@@ -368,6 +377,7 @@ fn tests_are_complete_help(last_test: &Test, number_of_tests: usize) -> bool {
         Test::IsFloat(_, _) => false,
         Test::IsDecimal(_) => false,
         Test::IsStr(_) => false,
+        Test::IsStrPrefix(_) => false,
         Test::IsListLen {
             bound: ListLenBound::AtLeast,
             len: 0,
@@ -636,6 +646,7 @@ fn test_for_pattern<'a>(pattern: &Pattern<'a>) -> Option<Test<'a>> {
             arity,
             element_layout: _,
             elements: _,
+            opt_rest: _,
         } => IsListLen {
             bound: match arity {
                 ListArity::Exact(_) => ListLenBound::Exact,
@@ -673,6 +684,7 @@ fn test_for_pattern<'a>(pattern: &Pattern<'a>) -> Option<Test<'a>> {
         FloatLiteral(v, precision) => IsFloat(*v, *precision),
         DecimalLiteral(v) => IsDecimal(*v),
         StrLiteral(v) => IsStr(v.clone()),
+        StrPrefix { prefix, .. } => IsStrPrefix(prefix.clone()),
     };
 
     Some(test)
@@ -909,6 +921,7 @@ fn to_relevant_branch_help<'a>(
             arity: my_arity,
             elements,
             element_layout: _,
+            opt_rest: _,
         } => match test {
             IsListLen {
                 bound: test_bound,
@@ -1082,6 +1095,30 @@ fn to_relevant_branch_help<'a>(
                     patterns: start,
                 })
             }
+            // the string might be this literal, but we don't know yet
+            IsStrPrefix(test_prefix) if string.starts_with(&**test_prefix) => Some(branch.clone()),
+            _ => None,
+        },
+
+        StrPrefix { prefix, .. } => match test {
+            IsStr(test_str) if test_str.starts_with(&*prefix) => {
+                start.extend(end);
+                Some(Branch {
+                    goal: branch.goal,
+                    guard: branch.guard.clone(),
+                    patterns: start,
+                })
+            }
+            IsStrPrefix(test_prefix) if test_prefix.starts_with(&*prefix) => {
+                start.extend(end);
+                Some(Branch {
+                    goal: branch.goal,
+                    guard: branch.guard.clone(),
+                    patterns: start,
+                })
+            }
+            // the string might also start with this longer prefix, but we don't know yet
+            IsStrPrefix(test_prefix) if prefix.starts_with(&**test_prefix) => Some(branch.clone()),
             _ => None,
         },
 
@@ -1219,6 +1256,7 @@ fn needs_tests(pattern: &Pattern) -> bool {
         | FloatLiteral(_, _)
         | DecimalLiteral(_)
         | StrLiteral(_)
+        | StrPrefix { .. }
         | List { .. } => true,
 
         Voided { .. } => internal_error!("unreachable"),
@@ -1757,6 +1795,36 @@ fn test_to_comparison<'a>(
             (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
         }
 
+        Test::IsStrPrefix(test_prefix) => {
+            let prefix_expr = Expr::Literal(Literal::Str(env.arena.alloc(test_prefix)));
+            let prefix_symbol = env.unique_symbol();
+
+            let starts_with_expr = Expr::Call(Call {
+                call_type: CallType::LowLevel {
+                    op: LowLevel::StrStartsWith,
+                    update_mode: env.next_update_mode_id(),
+                },
+                arguments: env.arena.alloc([rhs_symbol, prefix_symbol]),
+            });
+            let starts_with_symbol = env.unique_symbol();
+
+            let true_symbol = env.unique_symbol();
+
+            stores.push((prefix_symbol, Layout::STR, prefix_expr));
+            stores.push((starts_with_symbol, Layout::BOOL, starts_with_expr));
+            stores.push((
+                true_symbol,
+                Layout::BOOL,
+                Expr::Literal(Literal::Bool(true)),
+            ));
+
+            (
+                stores,
+                (true_symbol, Comparator::Eq, starts_with_symbol),
+                None,
+            )
+        }
+
         Test::IsListLen { bound, len } => {
             let list_layout = test_layout;
             let list_sym = rhs_symbol;
@@ -2259,7 +2327,9 @@ fn decide_to_branching<'a>(
                         }
                     },
                     Test::IsDecimal(_) => unreachable!("decimals cannot be switched on"),
-                    Test::IsStr(_) => unreachable!("strings cannot be switched on"),
+                    Test::IsStr(_) | Test::IsStrPrefix(_) => {
+                        unreachable!("strings cannot be switched on")
+                    }
                 };
 
                 // branch info is only useful for refcounted values
@@ -2469,6 +2539,12 @@ fn sort_edge_tests_by_priority(edges: &mut [Edge<'_>]) {
             (Test::IsListLen { .. }, t) | (t, Test::IsListLen { .. }) => internal_error!(
                 "list-length tests should never pair with another test {t:?} at the same level"
             ),
+            // Prefix tests don't have to be ordered to be correct, but checking the most
+            // specific test first means fewer branches are carried over to the next test:
+            // exact strings, then longer prefixes before shorter ones.
+            (Test::IsStr(_), Test::IsStrPrefix(_)) => Less,
+            (Test::IsStrPrefix(_), Test::IsStr(_)) => Greater,
+            (Test::IsStrPrefix(p1), Test::IsStrPrefix(p2)) => p2.len().cmp(&p1.len()),
             // We don't care about anything other than list-length tests, since all other tests
             // should be disjoint.
             _ => Equal,
@@ -38,6 +38,11 @@ pub enum Pattern<'a> {
         union: roc_exhaustive::Union,
     },
     StrLiteral(Box<str>),
+    /// A string starting with `prefix`, whose remainder is bound to `rest` if it is named
+    StrPrefix {
+        prefix: Box<str>,
+        rest: Option<Symbol>,
+    },
 
     RecordDestructure(Vec<'a, RecordDestruct<'a>>, &'a [InLayout<'a>]),
     TupleDestructure(Vec<'a, TupleDestruct<'a>>, &'a [InLayout<'a>]),
@@ -63,6 +68,8 @@ pub enum Pattern<'a> {
         arity: ListArity,
         element_layout: InLayout<'a>,
         elements: Vec<'a, Pattern<'a>>,
+        /// The name given to the rest of the list by `.. as rest`, if any
+        opt_rest: Option<Symbol>,
     },
 }
 
@@ -81,7 +88,8 @@ impl<'a> Pattern<'a> {
                 | Pattern::DecimalLiteral(_)
                 | Pattern::BitLiteral { .. }
                 | Pattern::EnumLiteral { .. }
-                | Pattern::StrLiteral(_) => { /* terminal */ }
+                | Pattern::StrLiteral(_)
+                | Pattern::StrPrefix { .. } => { /* terminal */ }
                 Pattern::As(subpattern, _) => stack.push(subpattern),
                 Pattern::RecordDestructure(destructs, _) => {
                     for destruct in destructs {
@@ -153,6 +161,10 @@ impl<'r, 'a> Iterator for PatternBindingIter<'r, 'a> {
                         *self = One(pat, layout);
                         (*symbol, layout).into()
                     }
+                    StrPrefix { rest, .. } => {
+                        *self = Done;
+                        rest.map(|symbol| (symbol, layout))
+                    }
                     RecordDestructure(destructs, _) => {
                         let stack = destructs
                             .iter()
@@ -183,6 +195,7 @@ impl<'r, 'a> Iterator for PatternBindingIter<'r, 'a> {
                     List {
                         element_layout,
                         elements,
+                        opt_rest,
                         ..
                     } => {
                         let stack = elements
@@ -191,7 +204,11 @@ impl<'r, 'a> Iterator for PatternBindingIter<'r, 'a> {
                             .rev()
                             .collect();
                         *self = Stack(stack);
-                        self.next()
+
+                        match opt_rest {
+                            Some(rest) => (*rest, layout).into(),
+                            None => self.next(),
+                        }
                     }
                     IntLiteral(_, _)
                     | FloatLiteral(_, _)
@@ -211,6 +228,9 @@ impl<'r, 'a> Iterator for PatternBindingIter<'r, 'a> {
                                 stack.push((Pat(pat), layout));
                                 return (*symbol, layout).into();
                             }
+                            StrPrefix {
+                                rest: Some(symbol), ..
+                            } => return (*symbol, layout).into(),
                             RecordDestructure(destructs, _) => stack.extend(
                                 destructs
                                     .iter()
@@ -234,11 +254,16 @@ impl<'r, 'a> Iterator for PatternBindingIter<'r, 'a> {
                             List {
                                 element_layout,
                                 elements,
+                                opt_rest,
                                 ..
                             } => {
                                 stack.extend(
                                     elements.iter().map(|p| (Pat(p), *element_layout)).rev(),
                                 );
+
+                                if let Some(rest) = opt_rest {
+                                    return (*rest, layout).into();
+                                }
                             }
                             IntLiteral(_, _)
                             | FloatLiteral(_, _)
@@ -247,6 +272,7 @@ impl<'r, 'a> Iterator for PatternBindingIter<'r, 'a> {
                             | EnumLiteral { .. }
                             | Underscore
                             | StrLiteral(_)
+                            | StrPrefix { rest: None, .. }
                             | Voided { .. } => {}
                         },
                         PatternBindingWork::RecordDestruct(_) => todo!(),
@@ -342,6 +368,26 @@ fn from_can_pattern_help<'a>(
             IntOrFloatValue::Float(*float),
         )),
         StrLiteral(v) => Ok(Pattern::StrLiteral(v.clone())),
+        // `"" .. rest` matches every string, so it's the same as just `rest`
+        StrPrefix(prefix, rest) if prefix.is_empty() => {
+            from_can_pattern_help(env, procs, layout_cache, &rest.value, assignments)
+        }
+        StrPrefix(prefix, rest) => {
+            let rest =
+                match from_can_pattern_help(env, procs, layout_cache, &rest.value, assignments)? {
+                    Pattern::Identifier(symbol) => Some(symbol),
+                    Pattern::Underscore => None,
+                    other => internal_error!(
+                    "the rest of a string prefix pattern must be a name or `_`, but it was {:?}",
+                    other
+                ),
+                };
+
+            Ok(Pattern::StrPrefix {
+                prefix: prefix.clone(),
+                rest,
+            })
+        }
         SingleQuote(var, _, c, _) => {
             let layout = layout_cache.from_var(env.arena, *var, env.subs);
             match layout.map(|l| layout_cache.get_repr(l)) {
@@ -1075,6 +1121,7 @@ fn from_can_pattern_help<'a>(
                 arity,
                 element_layout,
                 elements: mono_patterns,
+                opt_rest: patterns.opt_rest.and_then(|(_, opt_name)| opt_name),
             })
         }
     }
@@ -1190,6 +1237,14 @@ fn store_pattern_help<'a>(
 
             return StorePattern::Productive(stmt);
         }
+        StrPrefix { prefix, rest } => match rest {
+            Some(rest) => {
+                return store_str_prefix_rest(env, prefix, *rest, outer_symbol, stmt);
+            }
+            None => {
+                return StorePattern::NotProductive(stmt);
+            }
+        },
         IntLiteral(_, _)
         | FloatLiteral(_, _)
         | DecimalLiteral(_)
@@ -1242,6 +1297,7 @@ fn store_pattern_help<'a>(
             arity,
             element_layout,
             elements,
+            opt_rest,
         } => {
             return store_list_pattern(
                 env,
@@ -1251,6 +1307,7 @@ fn store_pattern_help<'a>(
                 *arity,
                 *element_layout,
                 elements,
+                *opt_rest,
                 stmt,
             )
         }
@@ -1355,6 +1412,61 @@ fn store_pattern_help<'a>(
     StorePattern::Productive(stmt)
 }
 
+/// Binds `rest` to what comes after `prefix` in the string `str_sym`,
+/// which the decision tree has already checked starts with `prefix`.
+fn store_str_prefix_rest<'a>(
+    env: &mut Env<'a, '_>,
+    prefix: &str,
+    rest: Symbol,
+    str_sym: Symbol,
+    stmt: Stmt<'a>,
+) -> StorePattern<'a> {
+    let usize_layout = Layout::usize(env.target_info);
+
+    let start_sym = env.unique_symbol();
+    let start_expr = Expr::Literal(Literal::Int((prefix.len() as i128).to_ne_bytes()));
+
+    let total_sym = env.unique_symbol();
+    let total_expr = Expr::Call(Call {
+        call_type: CallType::LowLevel {
+            op: LowLevel::StrCountUtf8Bytes,
+            update_mode: env.next_update_mode_id(),
+        },
+        arguments: env.arena.alloc([str_sym]),
+    });
+
+    let len_sym = env.unique_symbol();
+    let len_expr = Expr::Call(Call {
+        call_type: CallType::LowLevel {
+            op: LowLevel::NumSubWrap,
+            update_mode: env.next_update_mode_id(),
+        },
+        arguments: env.arena.alloc([total_sym, start_sym]),
+    });
+
+    let rest_expr = Expr::Call(Call {
+        call_type: CallType::LowLevel {
+            op: LowLevel::StrSubstringUnsafe,
+            update_mode: env.next_update_mode_id(),
+        },
+        arguments: env.arena.alloc([str_sym, start_sym, len_sym]),
+    });
+
+    let stores = [
+        (start_sym, usize_layout, start_expr),
+        (total_sym, usize_layout, total_expr),
+        (len_sym, usize_layout, len_expr),
+        (rest, Layout::STR, rest_expr),
+    ];
+
+    let mut stmt = stmt;
+    for (symbol, layout, expr) in stores.into_iter().rev() {
+        stmt = Stmt::Let(symbol, expr, layout, env.arena.alloc(stmt));
+    }
+
+    StorePattern::Productive(stmt)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct ListIndex(
     /// Positive if we should index from the head, negative if we should index from the tail
@@ -1449,12 +1561,26 @@ fn store_list_pattern<'a>(
     list_arity: ListArity,
     element_layout: InLayout<'a>,
     elements: &[Pattern<'a>],
+    opt_rest: Option<Symbol>,
     mut stmt: Stmt<'a>,
 ) -> StorePattern<'a> {
     use Pattern::*;
 
     let mut is_productive = false;
 
+    if let Some(rest) = opt_rest {
+        stmt = store_list_rest(
+            env,
+            layout_cache,
+            list_sym,
+            list_arity,
+            element_layout,
+            rest,
+            stmt,
+        );
+        is_productive = true;
+    }
+
     for (index, element) in elements.iter().enumerate().rev() {
         let compute_element_load = |env: &mut Env<'a, '_>| {
             let list_index = ListIndex::from_pattern_index(index, list_arity);
@@ -1533,6 +1659,75 @@ fn store_list_pattern<'a>(
     }
 }
 
+/// Binds `rest` to the part of the list matched by the `..` in a list pattern.
+/// The decision tree has already checked that the list is long enough.
+fn store_list_rest<'a>(
+    env: &mut Env<'a, '_>,
+    layout_cache: &mut LayoutCache<'a>,
+    list_sym: Symbol,
+    list_arity: ListArity,
+    element_layout: InLayout<'a>,
+    rest: Symbol,
+    stmt: Stmt<'a>,
+) -> Stmt<'a> {
+    let (before, after) = match list_arity {
+        ListArity::Slice(before, after) => (before, after),
+        ListArity::Exact(_) => internal_error!("only list patterns with a `..` have a rest"),
+    };
+
+    let usize_layout = Layout::usize(env.target_info);
+
+    let len_sym = env.unique_symbol();
+    let len_expr = Expr::Call(Call {
+        call_type: CallType::LowLevel {
+            op: LowLevel::ListLen,
+            update_mode: env.next_update_mode_id(),
+        },
+        arguments: env.arena.alloc([list_sym]),
+    });
+
+    let start_sym = env.unique_symbol();
+    let start_expr = Expr::Literal(Literal::Int((before as i128).to_ne_bytes()));
+
+    let skipped_sym = env.unique_symbol();
+    let skipped_expr = Expr::Literal(Literal::Int(((before + after) as i128).to_ne_bytes()));
+
+    let rest_len_sym = env.unique_symbol();
+    let rest_len_expr = Expr::Call(Call {
+        call_type: CallType::LowLevel {
+            op: LowLevel::NumSubWrap,
+            update_mode: env.next_update_mode_id(),
+        },
+        arguments: env.arena.alloc([len_sym, skipped_sym]),
+    });
+
+    let rest_expr = Expr::Call(Call {
+        call_type: CallType::LowLevel {
+            op: LowLevel::ListSublist,
+            update_mode: env.next_update_mode_id(),
+        },
+        arguments: env.arena.alloc([list_sym, start_sym, rest_len_sym]),
+    });
+
+    let list_layout =
+        layout_cache.put_in_direct_no_semantic(LayoutRepr::Builtin(Builtin::List(element_layout)));
+
+    let stores = [
+        (len_sym, usize_layout, len_expr),
+        (start_sym, usize_layout, start_expr),
+        (skipped_sym, usize_layout, skipped_expr),
+        (rest_len_sym, usize_layout, rest_len_expr),
+        (rest, list_layout, rest_expr),
+    ];
+
+    let mut stmt = stmt;
+    for (symbol, layout, expr) in stores.into_iter().rev() {
+        stmt = Stmt::Let(symbol, expr, layout, env.arena.alloc(stmt));
+    }
+
+    stmt
+}
+
 #[allow(clippy::too_many_arguments)]
 fn store_tag_pattern<'a>(
     env: &mut Env<'a, '_>,
@@ -822,6 +822,10 @@ pub enum Pattern<'a> {
     /// Can only occur inside of a [Pattern::List]
     ListRest(Option<(&'a [CommentOrNewline<'a>], PatternAs<'a>)>),
 
+    /// A string prefix pattern, e.g. `"fn " .. rest`
+    /// The rest is always an [Pattern::Identifier] or [Pattern::Underscore]
+    StrPrefix(StrLiteral<'a>, &'a Loc<Pattern<'a>>),
+
     As(&'a Loc<Pattern<'a>>, PatternAs<'a>),
 
     // Space
@@ -1011,6 +1015,13 @@ impl<'a> Pattern<'a> {
                 }
             }
 
+            StrPrefix(prefix, rest) => match other {
+                StrPrefix(other_prefix, other_rest) => {
+                    prefix == other_prefix && rest.value.equivalent(&other_rest.value)
+                }
+                _ => false,
+            },
+
             ListRest(pattern_as) => match other {
                 ListRest(other_pattern_as) => match (pattern_as, other_pattern_as) {
                     (Some((_, a)), Some((_, b))) => a.equivalent(b),
@@ -1660,6 +1671,7 @@ impl<'a> Malformed for Pattern<'a> {
             Tuple(items) => items.iter().any(|item| item.is_malformed()),
             List(items) => items.iter().any(|item| item.is_malformed()),
             ListRest(_) =>false,
+            StrPrefix(prefix, rest) => prefix.is_malformed() || rest.is_malformed(),
             As(pat, _) => pat.is_malformed(),
             SpaceBefore(pat, _) |
             SpaceAfter(pat, _) => pat.is_malformed(),
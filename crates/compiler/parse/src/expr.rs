@@ -939,83 +939,78 @@ fn parse_defs_end<'a>(
 
         global_state = match parse_single_def(options, min_indent, arena, state) {
             Ok((_, Some(single_def), next_state)) => {
-                let region = single_def.region;
-                let spaces_before_current = single_def.spaces_before;
+                push_single_def(arena, &mut defs, single_def);
 
-                match single_def.type_or_value {
-                    Either::First(type_def) => {
-                        defs.push_type_def(type_def, region, spaces_before_current, &[]);
-                    }
-                    Either::Second(value_def) => {
-                        // If we got a ValueDef::Body, check if a type annotation preceded it.
-                        // If so, we may need to combine them into an AnnotatedBody.
-                        let joined = match value_def {
-                            ValueDef::Body(loc_pattern, loc_def_expr)
-                                if spaces_before_current.len() <= 1 =>
-                            {
-                                let region =
-                                    Region::span_across(&loc_pattern.region, &loc_def_expr.region);
-
-                                match defs.last() {
-                                    Some(Err(ValueDef::Annotation(ann_pattern, ann_type))) => {
-                                        let (value_def, region) = join_ann_to_body!(
-                                            arena,
-                                            loc_pattern,
-                                            loc_def_expr,
-                                            ann_pattern,
-                                            ann_type,
-                                            spaces_before_current,
-                                            region
-                                        );
-
-                                        defs.replace_with_value_def(
-                                            defs.tags.len() - 1,
-                                            value_def,
-                                            region,
-                                        );
-
-                                        true
-                                    }
-                                    Some(Ok(TypeDef::Alias {
-                                        header,
-                                        ann: ann_type,
-                                    })) => {
-                                        let (value_def, region) = join_alias_to_body!(
-                                            arena,
-                                            loc_pattern,
-                                            loc_def_expr,
-                                            header,
-                                            ann_type,
-                                            spaces_before_current,
-                                            region
-                                        );
-
-                                        defs.replace_with_value_def(
-                                            defs.tags.len() - 1,
-                                            value_def,
-                                            region,
-                                        );
-
-                                        true
-                                    }
-                                    _ => false,
-                                }
-                            }
-                            _ => false,
-                        };
+                next_state
+            }
+            Ok((progress, None, s)) => return Ok((progress, defs, s)),
+            Err((progress, err)) => return Err((progress, err)),
+        };
+    }
+}
 
-                        if !joined {
-                            // the previous and current def can't be joined up
-                            defs.push_value_def(value_def, region, spaces_before_current, &[]);
+/// Add a def to the end of `defs`. If it's a body which follows its annotation, the two are
+/// joined up into one def.
+pub(crate) fn push_single_def<'a>(arena: &'a Bump, defs: &mut Defs<'a>, single_def: SingleDef<'a>) {
+    let region = single_def.region;
+    let spaces_before_current = single_def.spaces_before;
+
+    match single_def.type_or_value {
+        Either::First(type_def) => {
+            defs.push_type_def(type_def, region, spaces_before_current, &[]);
+        }
+        Either::Second(value_def) => {
+            // If we got a ValueDef::Body, check if a type annotation preceded it.
+            // If so, we may need to combine them into an AnnotatedBody.
+            let joined = match value_def {
+                ValueDef::Body(loc_pattern, loc_def_expr) if spaces_before_current.len() <= 1 => {
+                    let region = Region::span_across(&loc_pattern.region, &loc_def_expr.region);
+
+                    match defs.last() {
+                        Some(Err(ValueDef::Annotation(ann_pattern, ann_type))) => {
+                            let (value_def, region) = join_ann_to_body!(
+                                arena,
+                                loc_pattern,
+                                loc_def_expr,
+                                ann_pattern,
+                                ann_type,
+                                spaces_before_current,
+                                region
+                            );
+
+                            defs.replace_with_value_def(defs.tags.len() - 1, value_def, region);
+
+                            true
                         }
+                        Some(Ok(TypeDef::Alias {
+                            header,
+                            ann: ann_type,
+                        })) => {
+                            let (value_def, region) = join_alias_to_body!(
+                                arena,
+                                loc_pattern,
+                                loc_def_expr,
+                                header,
+                                ann_type,
+                                spaces_before_current,
+                                region
+                            );
+
+                            defs.replace_with_value_def(defs.tags.len() - 1, value_def, region);
+
+                            true
+                        }
+                        _ => false,
                     }
                 }
+                _ => false,
+            };
 
-                next_state
+            if !joined {
+                // the previous and current def can't be joined up
+                defs.push_value_def(value_def, region, spaces_before_current, &[]);
             }
-            Ok((progress, None, s)) => return Ok((progress, defs, s)),
-            Err((progress, err)) => return Err((progress, err)),
-        };
+        }
     }
 }
 
@@ -2561,6 +2556,28 @@ pub struct FoundApplyValue;
 struct FoundOptionalValue;
 
 impl<'a> RecordField<'a> {
+    /// If this field's label is a path to a nested field, like `b.c: 1`, somewhere
+    /// that isn't allowed, returns the region of the label. Paths are only allowed
+    /// when giving a nested field a new value in a record update.
+    pub fn disallowed_label_path(&self, is_update: bool) -> Option<Region> {
+        let label = match self {
+            RecordField::RequiredValue(_, _, _) if is_update => return None,
+            RecordField::RequiredValue(label, _, _)
+            | RecordField::OptionalValue(label, _, _)
+            | RecordField::LabelOnly(label)
+            | RecordField::ApplyValue(label, _, _, _) => label,
+            RecordField::SpaceBefore(field, _) | RecordField::SpaceAfter(field, _) => {
+                return field.disallowed_label_path(is_update)
+            }
+        };
+
+        if label.value.contains('.') {
+            Some(label.region)
+        } else {
+            None
+        }
+    }
+
     fn is_apply_value(&self) -> bool {
         let mut current = self;
 
@@ -2656,7 +2673,7 @@ pub fn record_field<'a>() -> impl Parser<'a, RecordField<'a>, ERecord<'a>> {
 
     map_with_arena!(
         and!(
-            specialize(|_, pos| ERecord::Field(pos), loc!(lowercase_ident())),
+            specialize(|_, pos| ERecord::Field(pos), loc!(record_field_label())),
             and!(
                 spaces(),
                 optional(either!(
@@ -2696,6 +2713,27 @@ pub fn record_field<'a>() -> impl Parser<'a, RecordField<'a>, ERecord<'a>> {
     )
 }
 
+/// A record field's label. This can also be a path to a nested field, like the `b.c`
+/// in `{ rec & b.c: 1 }`, although that's only allowed in record updates.
+fn record_field_label<'a>() -> impl Parser<'a, &'a str, ()> {
+    move |arena, state: State<'a>, min_indent: u32| {
+        let start = state.clone();
+        let (_, _, mut state) = lowercase_ident().parse(arena, state, min_indent)?;
+
+        while state.bytes().first() == Some(&b'.') {
+            match lowercase_ident().parse(arena, state.clone().advance(1), min_indent) {
+                Ok((_, _, next)) => state = next,
+                Err(_) => break,
+            }
+        }
+
+        let width = (state.pos().offset - start.pos().offset) as usize;
+        let label = std::str::from_utf8(&start.bytes()[..width]).unwrap();
+
+        Ok((MadeProgress, label, state))
+    }
+}
+
 enum RecordFieldExpr<'a> {
     Apply(&'a [CommentOrNewline<'a>], Loc<Expr<'a>>),
     Value(Loc<Expr<'a>>),
@@ -2776,6 +2814,16 @@ fn record_literal_help<'a>() -> impl Parser<'a, Expr<'a>, EExpr<'a>> {
             record_field_access_chain()
         ),
         move |arena, state, _, (record, accessors)| {
+            let is_update = record.update.is_some();
+
+            if let Some(region) = record
+                .fields
+                .iter()
+                .find_map(|field| field.value.disallowed_label_path(is_update))
+            {
+                return Err((MadeProgress, EExpr::RecordFieldPath(region)));
+            }
+
             let expr_result = match record.update {
                 Some(update) => record_update_help(arena, update, record.fields),
                 None => {
@@ -5,7 +5,7 @@ use crate::blankspace::space0_e;
 use crate::expr::merge_spaces;
 use crate::ident::{lowercase_ident, UppercaseIdent};
 use crate::parser::{optional, then};
-use crate::parser::{specialize, word1, EPackageEntry, EPackageName, Parser};
+use crate::parser::{specialize, word1, EDocsEntry, EPackageEntry, EPackageName, Parser};
 use crate::string_literal;
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::Loc;
@@ -176,6 +176,7 @@ keywords! {
     RequiresKeyword => "requires",
     ProvidesKeyword => "provides",
     ToKeyword => "to",
+    DocsKeyword => "docs",
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -242,6 +243,7 @@ pub struct PackageHeader<'a> {
     pub exposes: KeywordItem<'a, ExposesKeyword, Collection<'a, Loc<Spaced<'a, ModuleName<'a>>>>>,
     pub packages:
         KeywordItem<'a, PackagesKeyword, Collection<'a, Loc<Spaced<'a, PackageEntry<'a>>>>>,
+    pub docs: Option<KeywordItem<'a, DocsKeyword, Collection<'a, Loc<Spaced<'a, DocsEntry<'a>>>>>>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -342,6 +344,60 @@ pub fn package_entry<'a>() -> impl Parser<'a, Spaced<'a, PackageEntry<'a>>, EPac
     )
 }
 
+/// What an entry in a package's `docs` section sets
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocsKey<'a> {
+    /// e.g. `logo` in `logo: "https://example.com/logo.svg"`
+    Setting(&'a str),
+    /// The text of a link for the docs' header, e.g. `"Source code"` in
+    /// `"Source code": "https://github.com/me/pkg"`
+    LinkText(&'a str),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DocsEntry<'a> {
+    pub key: DocsKey<'a>,
+    pub spaces_after_key: &'a [CommentOrNewline<'a>],
+    pub value: Loc<&'a str>,
+}
+
+pub fn docs_entry<'a>() -> impl Parser<'a, Spaced<'a, DocsEntry<'a>>, EDocsEntry<'a>> {
+    map_with_arena!(
+        and!(
+            skip_second!(
+                and!(
+                    one_of![
+                        map!(
+                            specialize(|_, pos| EDocsEntry::Key(pos), lowercase_ident()),
+                            DocsKey::Setting
+                        ),
+                        map!(
+                            specialize(EDocsEntry::BadLinkText, package_name()),
+                            |text: PackageName<'a>| DocsKey::LinkText(text.to_str())
+                        )
+                    ],
+                    space0_e(EDocsEntry::IndentValue)
+                ),
+                word1(b':', EDocsEntry::Colon)
+            ),
+            and!(
+                space0_e(EDocsEntry::IndentValue),
+                loc!(map!(
+                    specialize(EDocsEntry::BadValue, package_name()),
+                    PackageName::to_str
+                ))
+            )
+        ),
+        move |arena, ((key, spaces_before_colon), (spaces_after_colon, value))| {
+            Spaced::Item(DocsEntry {
+                key,
+                spaces_after_key: merge_spaces(arena, spaces_before_colon, spaces_after_colon),
+                value,
+            })
+        }
+    )
+}
+
 pub fn package_name<'a>() -> impl Parser<'a, PackageName<'a>, EPackageName<'a>> {
     then(
         loc!(specialize(
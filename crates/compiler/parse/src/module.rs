@@ -1,17 +1,18 @@
 use crate::ast::{Collection, Defs, Header, Module, Spaced, Spaces};
 use crate::blankspace::{space0_around_ee, space0_before_e, space0_e};
 use crate::header::{
-    package_entry, package_name, AppHeader, ExposedName, ExposesKeyword, GeneratesKeyword,
-    HostedHeader, ImportsEntry, ImportsKeyword, InterfaceHeader, Keyword, KeywordItem, ModuleName,
-    PackageEntry, PackageHeader, PackagesKeyword, PlatformHeader, PlatformRequires,
-    ProvidesKeyword, ProvidesTo, RequiresKeyword, To, ToKeyword, TypedIdent, WithKeyword,
+    docs_entry, package_entry, package_name, AppHeader, DocsEntry, DocsKeyword, ExposedName,
+    ExposesKeyword, GeneratesKeyword, HostedHeader, ImportsEntry, ImportsKeyword, InterfaceHeader,
+    Keyword, KeywordItem, ModuleName, PackageEntry, PackageHeader, PackagesKeyword, PlatformHeader,
+    PlatformRequires, ProvidesKeyword, ProvidesTo, RequiresKeyword, To, ToKeyword, TypedIdent,
+    WithKeyword,
 };
 use crate::ident::{self, lowercase_ident, unqualified_ident, uppercase, UppercaseIdent};
 use crate::parser::Progress::{self, *};
 use crate::parser::{
     backtrackable, increment_min_indent, optional, reset_min_indent, specialize, word1, word2,
-    EExposes, EGenerates, EGeneratesWith, EHeader, EImports, EPackages, EProvides, ERequires,
-    ETypedIdent, Parser, SourceError, SpaceProblem, SyntaxError,
+    EDocs, EExposes, EExpr, EGenerates, EGeneratesWith, EHeader, EImports, EPackages, EProvides,
+    ERequires, ETypedIdent, Parser, SourceError, SpaceProblem, SyntaxError,
 };
 use crate::state::State;
 use crate::string_literal::{self, parse_str_literal};
@@ -36,6 +37,73 @@ pub fn module_defs<'a>() -> impl Parser<'a, Defs<'a>, SyntaxError<'a>> {
     )
 }
 
+/// Parse the defs of a module without giving up at the first syntax error. When a def fails to
+/// parse, the error is recorded and parsing picks up again at the next top-level def, so that
+/// all the syntax errors in a module can be reported at once. The defs that did parse are
+/// returned along with the errors.
+pub fn parse_module_defs_recovering<'a>(
+    arena: &'a bumpalo::Bump,
+    state: State<'a>,
+) -> (Defs<'a>, Vec<SyntaxError<'a>>) {
+    // Most modules parse just fine, so try that first.
+    let first_problem = match module_defs().parse(arena, state.clone(), 0) {
+        Ok((_, defs, _)) => return (defs, Vec::new()),
+        Err((_, fail)) => fail,
+    };
+
+    let options = crate::expr::ExprParseOptions {
+        accept_multi_backpassing: true,
+        check_for_arrow: true,
+    };
+
+    let mut defs = Defs::default();
+    let mut problems = Vec::new();
+    let mut state = state;
+
+    loop {
+        let start = state.pos();
+
+        let next_state = match crate::expr::parse_single_def(options, 0, arena, state.clone()) {
+            Ok((_, Some(single_def), next_state)) => {
+                crate::expr::push_single_def(arena, &mut defs, single_def);
+
+                Some(next_state)
+            }
+            Ok((_, None, _)) => match space0_e(EExpr::IndentEnd).parse(arena, state.clone(), 0) {
+                Ok((_, _, rest)) if rest.has_reached_end() => None,
+                Ok((_, _, rest)) => {
+                    problems.push(SyntaxError::NotEndOfFile(rest.pos()));
+
+                    rest.skip_to_next_toplevel_def()
+                }
+                Err((_, fail)) => {
+                    problems.push(SyntaxError::Expr(fail, start));
+
+                    state.skip_to_next_toplevel_def()
+                }
+            },
+            Err((_, fail)) => {
+                problems.push(SyntaxError::Expr(fail, start));
+
+                state.skip_to_next_toplevel_def()
+            }
+        };
+
+        match next_state {
+            Some(next_state) => state = next_state,
+            None => break,
+        }
+    }
+
+    if problems.is_empty() {
+        // We should always find at least the problem that made us try to recover, but if we
+        // somehow didn't, don't lose it.
+        problems.push(first_problem);
+    }
+
+    (defs, problems)
+}
+
 pub fn parse_header<'a>(
     arena: &'a bumpalo::Bump,
     state: State<'a>,
@@ -197,6 +265,7 @@ fn package_header<'a>() -> impl Parser<'a, PackageHeader<'a>, EHeader<'a>> {
         name: loc!(specialize(EHeader::PackageName, package_name())),
         exposes: specialize(EHeader::Exposes, exposes_modules()),
         packages: specialize(EHeader::Packages, packages()),
+        docs: optional(specialize(EHeader::Docs, docs())),
     })
     .trace("package_header")
 }
@@ -490,6 +559,29 @@ fn packages<'a>() -> impl Parser<
     })
 }
 
+#[inline(always)]
+fn docs<'a>() -> impl Parser<
+    'a,
+    KeywordItem<'a, DocsKeyword, Collection<'a, Loc<Spaced<'a, DocsEntry<'a>>>>>,
+    EDocs<'a>,
+> {
+    record!(KeywordItem {
+        keyword: spaces_around_keyword(
+            DocsKeyword,
+            EDocs::Docs,
+            EDocs::IndentDocs,
+            EDocs::IndentListStart
+        ),
+        item: collection_trailing_sep_e!(
+            word1(b'{', EDocs::ListStart),
+            specialize(EDocs::DocsEntry, loc!(docs_entry())),
+            word1(b',', EDocs::ListEnd),
+            word1(b'}', EDocs::ListEnd),
+            Spaced::SpaceBefore
+        )
+    })
+}
+
 #[inline(always)]
 fn generates<'a>(
 ) -> impl Parser<'a, KeywordItem<'a, GeneratesKeyword, UppercaseIdent<'a>>, EGenerates> {
@@ -91,6 +91,8 @@ impl_space_problem! {
     EInParens<'a>,
     EClosure<'a>,
     EList<'a>,
+    EDocs<'a>,
+    EDocsEntry<'a>,
     EPackageEntry<'a>,
     EPackages<'a>,
     EPattern<'a>,
@@ -118,6 +120,7 @@ pub enum EHeader<'a> {
     Imports(EImports, Position),
     Requires(ERequires<'a>, Position),
     Packages(EPackages<'a>, Position),
+    Docs(EDocs<'a>, Position),
     Generates(EGenerates, Position),
     GeneratesWith(EGeneratesWith, Position),
 
@@ -213,6 +216,27 @@ pub enum EPackageEntry<'a> {
     Space(BadInputError, Position),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EDocs<'a> {
+    Space(BadInputError, Position),
+    Docs(Position),
+    IndentDocs(Position),
+    ListStart(Position),
+    ListEnd(Position),
+    IndentListStart(Position),
+    DocsEntry(EDocsEntry<'a>, Position),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EDocsEntry<'a> {
+    Key(Position),
+    BadLinkText(EPackageName<'a>, Position),
+    Colon(Position),
+    IndentValue(Position),
+    BadValue(EPackageName<'a>, Position),
+    Space(BadInputError, Position),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EImports {
     Open(Position),
@@ -355,6 +379,7 @@ pub enum EExpr<'a> {
     Record(ERecord<'a>, Position),
     OptionalValueInRecordBuilder(Region),
     RecordUpdateBuilder(Region),
+    RecordFieldPath(Region),
 
     // SingleQuote errors are folded into the EString
     Str(EString<'a>, Position),
@@ -538,6 +563,9 @@ pub enum EPattern<'a> {
     AsIndentStart(Position),
 
     AccessorFunction(Position),
+
+    /// The `..` in a string prefix pattern like `"fn " .. rest` must be followed by a name or `_`
+    StrPrefixRest(Position),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
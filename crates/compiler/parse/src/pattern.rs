@@ -84,7 +84,7 @@ fn loc_pattern_help_help<'a>() -> impl Parser<'a, Loc<Pattern<'a>>, EPattern<'a>
         )),
         loc!(specialize(EPattern::List, list_pattern_help())),
         loc!(number_pattern_help()),
-        loc!(string_like_pattern_help()),
+        loc_string_like_pattern_or_prefix_help(),
     )
 }
 
@@ -260,6 +260,50 @@ fn string_like_pattern_help<'a>() -> impl Parser<'a, Pattern<'a>, EPattern<'a>>
     )
 }
 
+/// A string literal, optionally followed by `.. rest` to match on it as a prefix,
+/// e.g. `"fn " .. rest`.
+fn loc_string_like_pattern_or_prefix_help<'a>() -> impl Parser<'a, Loc<Pattern<'a>>, EPattern<'a>> {
+    move |arena: &'a Bump, state: State<'a>, min_indent: u32| {
+        let (_, loc_literal, state) =
+            loc!(string_like_pattern_help()).parse(arena, state, min_indent)?;
+
+        let prefix = match loc_literal.value {
+            Pattern::StrLiteral(prefix) => prefix,
+            _ => return Ok((MadeProgress, loc_literal, state)),
+        };
+
+        let literal_state = state.clone();
+
+        let state = match space0_e(EPattern::IndentEnd).parse(arena, state, min_indent) {
+            Err(_) => return Ok((MadeProgress, loc_literal, literal_state)),
+            Ok((_, _, state)) => state,
+        };
+
+        let state = match word2(b'.', b'.', EPattern::Start).parse(arena, state, min_indent) {
+            Err(_) => return Ok((MadeProgress, loc_literal, literal_state)),
+            Ok((_, _, state)) => state,
+        };
+
+        let (_, _, state) = space0_e(EPattern::StrPrefixRest)
+            .parse(arena, state, min_indent)
+            .map_err(|(_, fail)| (MadeProgress, fail))?;
+
+        let position = state.pos();
+
+        let (_, rest, state) = one_of!(
+            loc!(underscore_pattern_help()),
+            loc!(map!(lowercase_ident_pattern(), Pattern::Identifier))
+        )
+        .parse(arena, state, min_indent)
+        .map_err(|_| (MadeProgress, EPattern::StrPrefixRest(position)))?;
+
+        let region = Region::span_across(&loc_literal.region, &rest.region);
+        let pattern = Pattern::StrPrefix(prefix, arena.alloc(rest));
+
+        Ok((MadeProgress, Loc::at(region, pattern), state))
+    }
+}
+
 fn list_pattern_help<'a>() -> impl Parser<'a, Pattern<'a>, PList<'a>> {
     map!(
         collection_trailing_sep_e!(
@@ -122,6 +122,26 @@ impl<'a> State<'a> {
         self.offset == self.original_bytes.len()
     }
 
+    /// Skip ahead to the start of the next line that begins a new top-level def, i.e. one
+    /// that starts in the first column with something other than whitespace, a comment, or a
+    /// closing delimiter. This is where parsing can pick up again after a def fails to parse.
+    pub(crate) fn skip_to_next_toplevel_def(&self) -> Option<State<'a>> {
+        let bytes = self.bytes();
+        let mut newlines = bytes.iter().enumerate().filter(|(_, b)| **b == b'\n');
+
+        newlines.find_map(|(index, _)| match bytes.get(index + 1) {
+            None | Some(b' ' | b'\t' | b'\r' | b'\n' | b'#' | b')' | b']' | b'}') => None,
+            Some(_) => {
+                let mut state = self.clone();
+                state.offset += index + 1;
+                state.line_start = state.pos();
+                state.line_start_after_whitespace = state.pos();
+
+                Some(state)
+            }
+        })
+    }
+
     /// Returns a Region corresponding to the current state, but
     /// with the the end column advanced by the given amount. This is
     /// useful when parsing something "manually" (using input.chars())
@@ -556,6 +556,10 @@ fn parse_implements_ability<'a>() -> impl Parser<'a, ImplementsAbility<'a>, ETyp
 
 fn ability_impl_field<'a>() -> impl Parser<'a, AssignedField<'a, Expr<'a>>, ERecord<'a>> {
     then(record_field(), move |arena, state, _, field| {
+        if let Some(region) = field.disallowed_label_path(false) {
+            return Err((MadeProgress, ERecord::Field(region.start())));
+        }
+
         match field.to_assigned_field(arena) {
             Ok(assigned_field) => Ok((MadeProgress, assigned_field, state)),
             Err(FoundApplyValue) => Err((MadeProgress, ERecord::Field(state.pos()))),
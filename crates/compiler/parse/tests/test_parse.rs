@@ -21,9 +21,9 @@ mod test_parse {
     use roc_parse::ast::Expr::{self, *};
     use roc_parse::ast::StrSegment::*;
     use roc_parse::ast::{self, EscapedChar};
-    use roc_parse::ast::{CommentOrNewline, StrLiteral::*};
-    use roc_parse::module::module_defs;
-    use roc_parse::parser::{Parser, SyntaxError};
+    use roc_parse::ast::{CommentOrNewline, Pattern, StrLiteral::*, ValueDef};
+    use roc_parse::module::{module_defs, parse_module_defs_recovering};
+    use roc_parse::parser::{EExpr, Parser, SyntaxError};
     use roc_parse::state::State;
     use roc_parse::test_helpers::parse_expr_with;
     use roc_region::all::{Loc, Region};
@@ -317,6 +317,78 @@ mod test_parse {
         }
     }
 
+    #[test]
+    fn recover_from_several_syntax_errors() {
+        let arena = &Bump::new();
+
+        let src = indoc!(
+            r#"
+            a = 1
+
+            b = (2 +
+
+            c : Str
+            c = "three"
+
+            d = [4,,]
+
+            e = 5
+            "#
+        );
+
+        let state = State::new(src.as_bytes());
+        let (defs, problems) = parse_module_defs_recovering(arena, state);
+
+        // `b` and `d` are both reported, and the defs around them still get parsed,
+        // with `c`'s annotation joined up with its body.
+        assert!(matches!(
+            problems.as_slice(),
+            [
+                SyntaxError::Expr(_, _),
+                SyntaxError::Expr(EExpr::List(_, _), _)
+            ]
+        ));
+
+        assert!(defs.type_defs.is_empty());
+
+        match defs.value_defs.as_slice() {
+            [ValueDef::Body(a, a_body), ValueDef::AnnotatedBody {
+                ann_pattern,
+                body_pattern,
+                body_expr,
+                ..
+            }, ValueDef::Body(e, e_body)] => {
+                assert_eq!(a.value, Pattern::Identifier("a"));
+                assert_eq!(a_body.value, Num("1"));
+                assert_eq!(ann_pattern.value, Pattern::Identifier("c"));
+                assert_eq!(body_pattern.value, Pattern::Identifier("c"));
+                assert_eq!(body_expr.value, Str(PlainLine("three")));
+                assert_eq!(e.value, Pattern::Identifier("e"));
+                assert_eq!(e_body.value, Num("5"));
+            }
+            other => panic!("expected the defs of `a`, `c` and `e`, but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn recovering_from_nothing() {
+        let arena = &Bump::new();
+
+        let src = indoc!(
+            r#"
+            a = 1
+
+            b = 2
+            "#
+        );
+
+        let state = State::new(src.as_bytes());
+        let (defs, problems) = parse_module_defs_recovering(arena, state);
+
+        assert!(problems.is_empty());
+        assert_eq!(defs.len(), 2);
+    }
+
     #[test]
     fn parse_expr_size() {
         assert_eq!(std::mem::size_of::<roc_parse::ast::Expr>(), 40);
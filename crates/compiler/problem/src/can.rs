@@ -38,7 +38,11 @@ pub enum Problem {
     UnusedDef(Symbol, Region),
     UnusedImport(Symbol, Region),
     UnusedModuleImport(ModuleId, Region),
-    ExposedButNotDefined(Symbol),
+    ExposedButNotDefined {
+        symbol: Symbol,
+        /// The names of the values that are defined, in case the exposed one is a typo
+        suggestion_options: MutSet<Box<str>>,
+    },
     UnknownGeneratesWith(Loc<Ident>),
     /// First symbol is the name of the closure with that argument
     /// Bool is whether the closure is anonymous
@@ -214,6 +218,27 @@ pub enum Problem {
 }
 
 impl Problem {
+    /// The name of the kind of warning this is (see `WARNING_NAMES`), or `None` if
+    /// it's not a warning.
+    pub fn warning_name(&self) -> Option<&'static str> {
+        match self {
+            Problem::UnusedDef(..) | Problem::UnusedBranchDef(..) => Some("unused-def"),
+            Problem::UnusedImport(..) | Problem::UnusedModuleImport(..) => Some("unused-import"),
+            Problem::UnusedArgument(..) => Some("unused-argument"),
+            Problem::DefsOnlyUsedInRecursion(..) => Some("unused-recursive-def"),
+            Problem::PhantomTypeArgument { .. } => Some("phantom-type-argument"),
+            Problem::DuplicateRecordFieldValue { .. } => Some("duplicate-record-field"),
+            Problem::DuplicateImplementsAbility { .. } => Some("duplicate-implements"),
+            Problem::DuplicateImpl { .. } => Some("duplicate-impl"),
+            Problem::NotAnAbility(_) => Some("not-an-ability"),
+            Problem::ImplementsNonRequired { .. } => Some("unnecessary-impl"),
+            Problem::NoIdentifiersIntroduced(_) => Some("no-identifiers-introduced"),
+            Problem::OverloadedSpecialization { .. } => Some("overloaded-specialization"),
+            Problem::UnnecessaryOutputWildcard { .. } => Some("unnecessary-output-wildcard"),
+            _ => None,
+        }
+    }
+
     pub fn severity(&self) -> Severity {
         use Severity::{Fatal, RuntimeError, Warning};
 
@@ -221,7 +246,7 @@ impl Problem {
             Problem::UnusedDef(_, _) => Warning,
             Problem::UnusedImport(_, _) => Warning,
             Problem::UnusedModuleImport(_, _) => Warning,
-            Problem::ExposedButNotDefined(_) => RuntimeError,
+            Problem::ExposedButNotDefined { .. } => RuntimeError,
             Problem::UnknownGeneratesWith(_) => RuntimeError,
             Problem::UnusedArgument(_, _, _, _) => Warning,
             Problem::UnusedBranchDef(_, _) => Warning,
@@ -429,7 +454,7 @@ impl Problem {
             | Problem::RuntimeError(RuntimeError::ExposedButNotDefined(_))
             | Problem::RuntimeError(RuntimeError::NoImplementationNamed { .. })
             | Problem::FileProblem { .. }
-            | Problem::ExposedButNotDefined(_) => None,
+            | Problem::ExposedButNotDefined { .. } => None,
         }
     }
 }
@@ -19,3 +19,136 @@ pub enum Severity {
     /// (e.g. unused def, unused import)
     Warning,
 }
+
+/// Every kind of warning, by the name used to refer to it on the command line
+/// (e.g. `roc check --deny unused-def`). These names are stable, so don't rename them!
+pub const WARNING_NAMES: &[&str] = &[
+    "unused-def",
+    "unused-import",
+    "unused-argument",
+    "unused-recursive-def",
+    "phantom-type-argument",
+    "duplicate-record-field",
+    "duplicate-implements",
+    "duplicate-impl",
+    "not-an-ability",
+    "unnecessary-impl",
+    "no-identifiers-introduced",
+    "overloaded-specialization",
+    "unnecessary-output-wildcard",
+    "redundant-branch",
+    "unmatchable-branch",
+    "type-hole",
+];
+
+/// Warnings that are only reported when asked for, e.g. with `--warn type-hole`.
+/// These aren't affected by `ALL_WARNINGS`.
+const ALLOWED_BY_DEFAULT: &[&str] = &["type-hole"];
+
+/// Refers to all the warnings at once, like in `--deny warnings`.
+/// (Except for the ones that are allowed by default.)
+pub const ALL_WARNINGS: &str = "warnings";
+
+/// What to do about one kind of warning.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Level {
+    /// Don't report it at all.
+    Allow,
+    /// Report it as a warning, which is what happens by default for most warnings.
+    Warn,
+    /// Report it as an error, so it fails the build.
+    Deny,
+}
+
+/// What to do about each kind of warning, as chosen with `--allow`, `--warn` and `--deny`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WarningLevels {
+    /// The level for warnings that weren't given one of their own.
+    default: Option<Level>,
+    levels: Vec<(&'static str, Level)>,
+}
+
+impl WarningLevels {
+    pub const fn new() -> Self {
+        Self {
+            default: None,
+            levels: Vec::new(),
+        }
+    }
+
+    /// Set the level for one kind of warning (or, given `ALL_WARNINGS`, for all of them),
+    /// overriding whatever was set for it before.
+    ///
+    /// Returns `Err` if there's no warning with this name.
+    pub fn set(&mut self, name: &str, level: Level) -> Result<(), ()> {
+        if name == ALL_WARNINGS {
+            self.default = Some(level);
+            self.levels.clear();
+
+            return Ok(());
+        }
+
+        let name = *WARNING_NAMES
+            .iter()
+            .find(|known| **known == name)
+            .ok_or(())?;
+
+        self.levels.retain(|(other, _)| *other != name);
+        self.levels.push((name, level));
+
+        Ok(())
+    }
+
+    pub fn level(&self, name: &str) -> Level {
+        match self.levels.iter().find(|(other, _)| *other == name) {
+            Some((_, level)) => *level,
+            None if ALLOWED_BY_DEFAULT.contains(&name) => Level::Allow,
+            None => self.default.unwrap_or(Level::Warn),
+        }
+    }
+
+    /// The severity a problem should be reported with, given the name of the warning it is
+    /// (if it's a warning at all), or `None` if it shouldn't be reported.
+    pub fn severity(&self, warning_name: Option<&str>, severity: Severity) -> Option<Severity> {
+        match (warning_name, severity) {
+            (Some(name), Severity::Warning) => match self.level(name) {
+                Level::Allow => None,
+                Level::Warn => Some(Severity::Warning),
+                Level::Deny => Some(Severity::RuntimeError),
+            },
+            _ => Some(severity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_levels_override_earlier_ones() {
+        let mut levels = WarningLevels::new();
+
+        levels.set("unused-def", Level::Allow).unwrap();
+        levels.set(ALL_WARNINGS, Level::Deny).unwrap();
+        levels.set("unused-import", Level::Warn).unwrap();
+
+        assert_eq!(levels.level("unused-def"), Level::Deny);
+        assert_eq!(levels.level("unused-import"), Level::Warn);
+        assert_eq!(levels.level("type-hole"), Level::Allow);
+        assert_eq!(levels.set("shadowing", Level::Warn), Err(()));
+    }
+
+    #[test]
+    fn only_warnings_change_severity() {
+        let mut levels = WarningLevels::new();
+
+        levels.set(ALL_WARNINGS, Level::Allow).unwrap();
+
+        assert_eq!(levels.severity(Some("unused-def"), Severity::Warning), None);
+        assert_eq!(
+            levels.severity(None, Severity::RuntimeError),
+            Some(Severity::RuntimeError)
+        );
+    }
+}
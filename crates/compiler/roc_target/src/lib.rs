@@ -3,7 +3,8 @@
 // See github.com/roc-lang/roc/issues/800 for discussion of the large_enum_variant check.
 #![allow(clippy::large_enum_variant)]
 
-use strum_macros::{EnumCount, EnumIter, EnumString, IntoStaticStr};
+use strum::EnumMessage;
+use strum_macros::{EnumCount, EnumIter, EnumMessage, EnumString, IntoStaticStr};
 use target_lexicon::Triple;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -34,6 +35,14 @@ impl OperatingSystem {
         }
     }
 
+    pub const fn static_library_file_ext(&self) -> &str {
+        match self {
+            OperatingSystem::Windows => "lib",
+            OperatingSystem::Unix => "a",
+            OperatingSystem::Wasi => "a",
+        }
+    }
+
     pub const fn executable_file_ext(&self) -> Option<&str> {
         match self {
             OperatingSystem::Windows => Some("exe"),
@@ -160,28 +169,35 @@ impl From<target_lexicon::Architecture> for Architecture {
     }
 }
 
-#[derive(Debug, Copy, Clone, EnumIter, EnumString, IntoStaticStr, PartialEq, Eq, Default)]
+/// A target that `roc build --target` can produce an executable for. Besides its name,
+/// each one can also be referred to by the architecture names that LLVM and Rust use
+/// (e.g. `linux-x86_64` for `linux-x64`).
+#[derive(
+    Debug, Copy, Clone, EnumIter, EnumMessage, EnumString, IntoStaticStr, PartialEq, Eq, Default,
+)]
 pub enum Target {
     #[strum(serialize = "system")]
     #[default]
     System,
-    #[strum(serialize = "linux-x32")]
+    #[strum(to_string = "linux-x32", serialize = "linux-x86")]
     LinuxX32,
-    #[strum(serialize = "linux-x64")]
+    #[strum(to_string = "linux-x64", serialize = "linux-x86_64")]
     LinuxX64,
-    #[strum(serialize = "linux-arm64")]
+    #[strum(to_string = "linux-arm64", serialize = "linux-aarch64")]
     LinuxArm64,
-    #[strum(serialize = "macos-x64")]
+    #[strum(to_string = "macos-x64", serialize = "macos-x86_64")]
     MacX64,
-    #[strum(serialize = "macos-arm64")]
+    #[strum(to_string = "macos-arm64", serialize = "macos-aarch64")]
     MacArm64,
-    #[strum(serialize = "windows-x32")]
+    #[strum(to_string = "windows-x32", serialize = "windows-x86")]
     WinX32,
-    #[strum(serialize = "windows-x64")]
+    #[strum(to_string = "windows-x64", serialize = "windows-x86_64")]
     WinX64,
-    #[strum(serialize = "windows-arm64")]
+    #[strum(to_string = "windows-arm64", serialize = "windows-aarch64")]
     WinArm64,
-    #[strum(serialize = "wasm32")]
+    /// A standalone `.wasm` module that imports WASI for things like stdio and the filesystem,
+    /// so it can be run with e.g. wasmtime or wasmer.
+    #[strum(to_string = "wasm32", serialize = "wasm32-wasi")]
     Wasm32,
 }
 
@@ -192,6 +208,16 @@ const MACOS: target_lexicon::OperatingSystem = target_lexicon::OperatingSystem::
 };
 
 impl Target {
+    /// Other names this target can be referred to by.
+    pub fn aliases(self) -> impl Iterator<Item = &'static str> {
+        let name: &'static str = self.into();
+
+        self.get_serializations()
+            .iter()
+            .copied()
+            .filter(move |alias| *alias != name)
+    }
+
     pub fn to_triple(self) -> Triple {
         use target_lexicon::*;
 
@@ -293,12 +319,21 @@ pub fn get_target_triple_str(target: &target_lexicon::Triple) -> Option<&'static
             ..
         } => Some(Target::LinuxArm64.into()),
         target_lexicon::Triple {
-            operating_system: target_lexicon::OperatingSystem::Darwin,
+            operating_system: target_lexicon::OperatingSystem::Linux,
+            architecture: target_lexicon::Architecture::X86_32(_),
+            ..
+        } => Some(Target::LinuxX32.into()),
+        // `Target::to_triple` uses `MacOSX` for the macOS targets, while the host triple
+        // says `Darwin`; both need to find the same prebuilt host.
+        target_lexicon::Triple {
+            operating_system:
+                target_lexicon::OperatingSystem::Darwin | target_lexicon::OperatingSystem::MacOSX { .. },
             architecture: target_lexicon::Architecture::Aarch64(_),
             ..
         } => Some(Target::MacArm64.into()),
         target_lexicon::Triple {
-            operating_system: target_lexicon::OperatingSystem::Darwin,
+            operating_system:
+                target_lexicon::OperatingSystem::Darwin | target_lexicon::OperatingSystem::MacOSX { .. },
             architecture: target_lexicon::Architecture::X86_64,
             ..
         } => Some(Target::MacX64.into()),
@@ -320,3 +355,60 @@ pub fn get_target_triple_str(target: &target_lexicon::Triple) -> Option<&'static
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn llvm_style_aliases() {
+        let aliases = [
+            ("linux-x86", Target::LinuxX32),
+            ("linux-x86_64", Target::LinuxX64),
+            ("linux-aarch64", Target::LinuxArm64),
+            ("macos-x86_64", Target::MacX64),
+            ("macos-aarch64", Target::MacArm64),
+            ("windows-x86", Target::WinX32),
+            ("windows-x86_64", Target::WinX64),
+            ("windows-aarch64", Target::WinArm64),
+            ("wasm32-wasi", Target::Wasm32),
+        ];
+
+        for (alias, target) in aliases {
+            assert_eq!(Target::from_str(alias), Ok(target), "{alias}");
+            assert!(target.aliases().any(|other| other == alias), "{alias}");
+        }
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for target in Target::iter() {
+            assert_eq!(Target::from_str(&target.to_string()), Ok(target));
+        }
+    }
+
+    #[test]
+    fn unknown_target() {
+        let error = Target::from_str("linux-sparc").unwrap_err();
+
+        assert_eq!(error, strum::ParseError::VariantNotFound);
+        assert_eq!(error.to_string(), "Matching variant not found");
+    }
+
+    #[test]
+    fn wasm32_wasi_round_trip() {
+        assert_eq!(Target::from_str("wasm32-wasi"), Ok(Target::Wasm32));
+        assert_eq!(Target::from_str("wasm32"), Ok(Target::Wasm32));
+        assert_eq!(Target::Wasm32.to_string(), "wasm32");
+        assert_eq!(
+            Target::from_str(&Target::Wasm32.to_string()),
+            Ok(Target::Wasm32)
+        );
+        assert_eq!(
+            Target::Wasm32.aliases().collect::<Vec<_>>(),
+            vec!["wasm32-wasi"]
+        );
+    }
+}
@@ -5,6 +5,7 @@ use roc_derive::SharedDerivedModule;
 use roc_types::subs::{Content, Descriptor, Mark, OptVariable, Rank, Subs, Variable};
 use roc_unify::Env as UEnv;
 
+use crate::explain::Provenance;
 use crate::{FunctionKind, Pools};
 
 pub struct DerivedEnv<'a> {
@@ -31,6 +32,8 @@ pub struct InferenceEnv<'a> {
     pub derived_env: &'a DerivedEnv<'a>,
     pub subs: &'a mut Subs,
     pub pools: &'a mut Pools,
+    /// Only kept when type errors should be explained step by step.
+    pub(crate) provenance: Option<Provenance>,
     #[cfg(debug_assertions)]
     pub checkmate: Option<roc_checkmate::Collector>,
 }
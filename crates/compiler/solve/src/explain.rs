@@ -0,0 +1,136 @@
+//! Remembers which constraints shaped which types while solving, so that a type
+//! mismatch can be explained step by step (see `roc check --explain-types`).
+
+use roc_collections::MutSet;
+use roc_region::all::Region;
+use roc_solve_problem::{TraceStep, TypeError};
+use roc_types::subs::{Content, FlatType, GetSubsSlice, Subs, Variable};
+use roc_types::types::{Category, Polarity};
+
+#[derive(Default)]
+pub struct Provenance {
+    steps: Vec<(Variable, TraceStep)>,
+}
+
+impl Provenance {
+    /// Record that the constraint at `region` was satisfied, and gave `var` its type.
+    pub fn record(&mut self, subs: &mut Subs, var: Variable, region: Region, category: &Category) {
+        let generated = matches!(
+            category,
+            Category::Storage(..)
+                | Category::Unknown
+                | Category::LowLevelOpResult(_)
+                | Category::ForeignCall
+                | Category::Crash
+        );
+
+        if generated || region.is_empty() {
+            // This came from the compiler rather than the program, so it wouldn't
+            // explain anything.
+            return;
+        }
+
+        // Turning a variable into an error type names its flex vars, which must not
+        // leak into the rest of solving.
+        let snapshot = subs.snapshot();
+        let typ = subs.var_to_error_type(var, Polarity::OF_VALUE);
+        subs.rollback_to(snapshot);
+
+        self.steps.push((
+            var,
+            TraceStep {
+                region,
+                category: category.clone(),
+                typ,
+            },
+        ));
+    }
+
+    /// The variables that make up the types of `vars`. This has to be taken before
+    /// trying to unify them, since a failed unification turns both into an error.
+    pub fn involved(&self, subs: &Subs, vars: &[Variable]) -> MutSet<Variable> {
+        reachable(subs, vars.iter().copied())
+    }
+
+    /// Attach the steps that shaped any of the `involved` variables to `problem`.
+    pub fn explain(
+        &self,
+        subs: &Subs,
+        problem: TypeError,
+        involved: &MutSet<Variable>,
+    ) -> TypeError {
+        let mut trace: Vec<TraceStep> = Vec::new();
+
+        for (var, step) in self.steps.iter() {
+            let already_shown = trace.iter().any(|shown| shown.region == step.region);
+
+            if !already_shown
+                && reachable(subs, std::iter::once(*var))
+                    .iter()
+                    .any(|var| involved.contains(var))
+            {
+                trace.push(step.clone());
+            }
+        }
+
+        if trace.is_empty() {
+            problem
+        } else {
+            TypeError::Explained(Box::new(problem), trace)
+        }
+    }
+}
+
+/// The root keys of all the variables that make up the types of `vars`. Lambda
+/// sets are left out, since they're never what a mismatch is about, and so are the
+/// reserved variables (like the one for `Str`), which every module shares.
+fn reachable(subs: &Subs, vars: impl Iterator<Item = Variable>) -> MutSet<Variable> {
+    let mut seen = MutSet::default();
+    let mut stack: Vec<Variable> = vars.collect();
+
+    while let Some(var) = stack.pop() {
+        let var = subs.get_root_key_without_compacting(var);
+
+        if (var.index() as usize) < Variable::NUM_RESERVED_VARS || !seen.insert(var) {
+            continue;
+        }
+
+        use Content::*;
+        use FlatType::*;
+
+        match subs.get_content_without_compacting(var) {
+            FlexVar(_) | RigidVar(_) | FlexAbleVar(..) | RigidAbleVar(..) | LambdaSet(_)
+            | ErasedLambda | RangedNumber(_) | Error => {}
+            RecursionVar { structure, .. } => stack.push(*structure),
+            Alias(_, args, real_var, _) => {
+                stack.extend(subs.get_subs_slice(args.all_variables()));
+                stack.push(*real_var);
+            }
+            Structure(flat_type) => match flat_type {
+                Apply(_, args) => stack.extend(subs.get_subs_slice(*args)),
+                Func(args, _closure, ret) => {
+                    stack.extend(subs.get_subs_slice(*args));
+                    stack.push(*ret);
+                }
+                Record(fields, ext) => {
+                    stack.extend(subs.get_subs_slice(fields.variables()));
+                    stack.push(*ext);
+                }
+                Tuple(elems, ext) => {
+                    stack.extend(subs.get_subs_slice(elems.variables()));
+                    stack.push(*ext);
+                }
+                TagUnion(tags, ext) | RecursiveTagUnion(_, tags, ext) => {
+                    for slice in subs.get_subs_slice(tags.variables()) {
+                        stack.extend(subs.get_subs_slice(*slice));
+                    }
+                    stack.push(ext.var());
+                }
+                FunctionOrTagUnion(_, _, ext) => stack.push(ext.var()),
+                EmptyRecord | EmptyTuple | EmptyTagUnion => {}
+            },
+        }
+    }
+
+    seen
+}
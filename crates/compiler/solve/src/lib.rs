@@ -14,6 +14,7 @@ pub mod specialize;
 mod aliases;
 mod deep_copy;
 mod env;
+mod explain;
 mod kinds;
 mod pools;
 mod to_var;
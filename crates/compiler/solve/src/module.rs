@@ -76,6 +76,9 @@ pub struct SolveConfig<'a> {
     /// Needed during solving to resolve lambda sets from derived implementations that escape into
     /// the user module.
     pub derived_module: SharedDerivedModule,
+    /// Whether to record the steps that lead up to each type mismatch, so they can be
+    /// reported along with it.
+    pub explain_types: bool,
 
     #[cfg(debug_assertions)]
     /// The checkmate collector for this module.
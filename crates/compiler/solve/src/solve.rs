@@ -4,6 +4,7 @@ use crate::ability::{
 };
 use crate::deep_copy::deep_copy_var_in;
 use crate::env::{DerivedEnv, InferenceEnv};
+use crate::explain::Provenance;
 use crate::module::{SolveConfig, Solved};
 use crate::pools::Pools;
 use crate::specialize::{
@@ -16,6 +17,7 @@ use roc_can::abilities::{AbilitiesStore, MemberSpecializationInfo};
 use roc_can::constraint::Constraint::{self, *};
 use roc_can::constraint::{Cycle, LetConstraint, OpportunisticResolve};
 use roc_can::expected::{Expected, PExpected};
+use roc_collections::MutSet;
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
 use roc_debug_flags::ROC_VERIFY_RIGID_LET_GENERALIZED;
@@ -129,6 +131,7 @@ fn run_help(
         exposed_by_module,
         derived_module,
         function_kind,
+        explain_types,
         ..
     } = config;
 
@@ -156,6 +159,7 @@ fn run_help(
         derived_env: &derived_env,
         subs,
         pools: &mut pools,
+        provenance: explain_types.then(Provenance::default),
         #[cfg(debug_assertions)]
         checkmate: config.checkmate,
     };
@@ -499,6 +503,11 @@ fn solve(
                     *expectation.get_type_ref(),
                 );
 
+                let involved = env
+                    .provenance
+                    .as_ref()
+                    .map(|provenance| provenance.involved(env.subs, &[actual, expected]));
+
                 match unify(
                     &mut env.uenv(),
                     actual,
@@ -514,6 +523,10 @@ fn solve(
                     } => {
                         env.introduce(rank, &vars);
 
+                        if let Some(provenance) = env.provenance.as_mut() {
+                            provenance.record(env.subs, actual, *region, category);
+                        }
+
                         if !must_implement_ability.is_empty() {
                             let new_problems = obligation_cache.check_obligations(
                                 env.subs,
@@ -544,7 +557,7 @@ fn solve(
                             expectation.replace_ref(expected_type),
                         );
 
-                        problems.push(problem);
+                        problems.push(explain(env, problem, involved));
 
                         state
                     }
@@ -610,6 +623,12 @@ fn solve(
                             *expectation.get_type_ref(),
                         );
 
+                        // The original variable is included so that the steps which
+                        // shaped the definition of this symbol are explained too.
+                        let involved = env.provenance.as_ref().map(|provenance| {
+                            provenance.involved(env.subs, &[var, actual, expected])
+                        });
+
                         match unify(
                             &mut env.uenv(),
                             actual,
@@ -625,6 +644,15 @@ fn solve(
                             } => {
                                 env.introduce(rank, &vars);
 
+                                if let Some(provenance) = env.provenance.as_mut() {
+                                    provenance.record(
+                                        env.subs,
+                                        expected,
+                                        *region,
+                                        &Category::Lookup(*symbol),
+                                    );
+                                }
+
                                 if !must_implement_ability.is_empty() {
                                     let new_problems = obligation_cache.check_obligations(
                                         env.subs,
@@ -660,7 +688,7 @@ fn solve(
                                     expectation.replace_ref(expected_type),
                                 );
 
-                                problems.push(problem);
+                                problems.push(explain(env, problem, involved));
 
                                 state
                             }
@@ -1409,6 +1437,18 @@ fn chase_alias_content(subs: &Subs, mut var: Variable) -> (Variable, &Content) {
     }
 }
 
+/// Attach the steps that led up to a type mismatch, if we're keeping track of them.
+fn explain(
+    env: &InferenceEnv,
+    problem: TypeError,
+    involved: Option<MutSet<Variable>>,
+) -> TypeError {
+    match (&env.provenance, involved) {
+        (Some(provenance), Some(involved)) => provenance.explain(env.subs, problem, &involved),
+        _ => problem,
+    }
+}
+
 fn compact_lambdas_and_check_obligations(
     env: &mut InferenceEnv,
     problems: &mut Vec<TypeError>,
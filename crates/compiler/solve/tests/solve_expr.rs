@@ -1086,7 +1086,12 @@ mod solve_expr {
 
     #[test]
     fn tuple_literal_ty() {
-        infer_eq("(5, 3.14 )", "( Num *, Frac * )*");
+        infer_eq("(5, 3.14 )", "( Num *, Frac * )");
+    }
+
+    #[test]
+    fn nested_tuple_literal_ty() {
+        infer_eq("(\"a\", (1u8, 2u16))", "( Str, ( U8, U16 ) )");
     }
 
     #[test]
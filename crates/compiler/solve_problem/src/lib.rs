@@ -33,6 +33,25 @@ pub enum TypeError {
     },
     IngestedFileBadUtf8(Box<PathBuf>, Utf8Error),
     IngestedFileUnsupportedType(Box<PathBuf>, ErrorType),
+    /// A `_` in a type annotation, along with the type that was inferred for it.
+    /// These are only reported when asked for, to help with writing out annotations.
+    TypeHole {
+        region: Region,
+        typ: ErrorType,
+    },
+    /// Another problem, along with the steps that led up to it. These are only
+    /// recorded when asked for, with `roc check --explain-types`.
+    Explained(Box<TypeError>, Vec<TraceStep>),
+}
+
+/// A constraint the solver satisfied before running into a type mismatch, which
+/// involved one of the types that ended up colliding.
+#[derive(Debug, Clone)]
+pub struct TraceStep {
+    pub region: Region,
+    pub category: Category,
+    /// The type as it was right after this step.
+    pub typ: ErrorType,
 }
 
 impl TypeError {
@@ -54,6 +73,59 @@ impl TypeError {
             TypeError::WrongSpecialization { .. } => RuntimeError,
             TypeError::IngestedFileBadUtf8(..) => Fatal,
             TypeError::IngestedFileUnsupportedType(..) => Fatal,
+            TypeError::TypeHole { .. } => Warning,
+            TypeError::Explained(problem, _) => problem.severity(),
+        }
+    }
+
+    /// The name of the kind of warning this is (see `roc_problem::WARNING_NAMES`),
+    /// or `None` if it's not a warning.
+    pub fn warning_name(&self) -> Option<&'static str> {
+        match self {
+            TypeError::Exhaustive(roc_exhaustive::Error::Redundant { .. }) => {
+                Some("redundant-branch")
+            }
+            TypeError::Exhaustive(roc_exhaustive::Error::Unmatchable { .. }) => {
+                Some("unmatchable-branch")
+            }
+            TypeError::TypeHole { .. } => Some("type-hole"),
+            TypeError::Explained(problem, _) => problem.warning_name(),
+            _ => None,
+        }
+    }
+
+    /// Where in the source this problem is, if it's about one specific place.
+    pub fn region(&self) -> Option<Region> {
+        use roc_exhaustive::Error::*;
+
+        match self {
+            TypeError::BadExpr(region, ..)
+            | TypeError::BadPattern(region, ..)
+            | TypeError::CircularType(region, ..)
+            | TypeError::BadExprMissingAbility(region, ..)
+            | TypeError::BadPatternMissingAbility(region, ..)
+            | TypeError::StructuralSpecialization { region, .. }
+            | TypeError::WrongSpecialization { region, .. }
+            | TypeError::TypeHole { region, .. }
+            | TypeError::Exhaustive(Incomplete(region, ..))
+            | TypeError::Exhaustive(Redundant {
+                branch_region: region,
+                ..
+            })
+            | TypeError::Exhaustive(Unmatchable {
+                branch_region: region,
+                ..
+            })
+            | TypeError::UnfulfilledAbility(Unfulfilled::OpaqueUnderivable {
+                derive_region: region,
+                ..
+            }) => Some(*region),
+            TypeError::CircularDef(entries) => entries.first().map(|entry| entry.symbol_region),
+            TypeError::Explained(problem, _) => problem.region(),
+            TypeError::UnexposedLookup(_)
+            | TypeError::UnfulfilledAbility(_)
+            | TypeError::IngestedFileBadUtf8(..)
+            | TypeError::IngestedFileUnsupportedType(..) => None,
         }
     }
 }
@@ -82,6 +82,7 @@ fn immediates() {
     check_single_lset_immediate(Hash, v!(STR), Symbol::HASH_HASH_STR_BYTES);
     check_single_lset_immediate(Hash, v!(Symbol::LIST_LIST v!(U8)), Symbol::HASH_HASH_LIST);
     check_single_lset_immediate(Hash, v!(Symbol::LIST_LIST v!(STR)), Symbol::HASH_HASH_LIST);
+    check_single_lset_immediate(Hash, v!(Symbol::BOX_BOX_TYPE v!(U8)), Symbol::HASH_HASH_BOX);
 }
 
 #[test]
@@ -213,9 +214,9 @@ fn two_field_record() {
 fn two_element_tuple() {
     derive_test(Hash, v!((v!(U8), v!(STR),)), |golden| {
         assert_snapshot!(golden, @r###"
-        # derived for ( U8, Str )*
-        # hasher, ( a, a1 )* -[[hash_(arity:2)(0)]]-> hasher where a implements Hash, a1 implements Hash, hasher implements Hasher
-        # hasher, ( a, a1 )* -[[hash_(arity:2)(0)]]-> hasher where a implements Hash, a1 implements Hash, hasher implements Hasher
+        # derived for ( U8, Str )
+        # hasher, ( a, a1 ) -[[hash_(arity:2)(0)]]-> hasher where a implements Hash, a1 implements Hash, hasher implements Hasher
+        # hasher, ( a, a1 ) -[[hash_(arity:2)(0)]]-> hasher where a implements Hash, a1 implements Hash, hasher implements Hasher
         # Specialization lambda sets:
         #   @<1>: [[hash_(arity:2)(0)]]
         #Derived.hash_(arity:2) =
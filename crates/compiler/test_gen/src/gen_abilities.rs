@@ -1226,6 +1226,54 @@ fn encode_then_decode_list_of_strings() {
     });
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_set() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [TotallyNotJson] provides [main] to "./platform"
+
+            main =
+                set = Set.fromList ["foo", "bar", "foo"]
+
+                when Str.fromUtf8 (Encode.toBytes set TotallyNotJson.json) is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from(r#"["foo","bar"]"#),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_then_decode_dict() {
+    with_larger_debug_stack(|| {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [TotallyNotJson] provides [main] to "./platform"
+
+                main =
+                    dict = Dict.fromList [("a", 1u8), ("b", 2u8)]
+
+                    when Encode.toBytes dict TotallyNotJson.json |> Decode.fromBytes TotallyNotJson.json is
+                        Ok decoded ->
+                            when Dict.get decoded "b" is
+                                Ok n -> n
+                                Err KeyNotFound -> 0u8
+
+                        Err _ -> 255u8
+                "#
+            ),
+            2,
+            u8
+        )
+    });
+}
+
 #[test]
 #[cfg(feature = "gen-llvm")]
 #[ignore = "#3696: Currently hits some weird panic in borrow checking, not sure if it's directly related to abilities."]
@@ -1623,7 +1671,6 @@ mod hash {
         }
 
         #[test]
-        #[cfg(not(feature = "gen-wasm"))] // shr not implemented for U128
         fn i128() {
             assert_evals_to!(
                 &build_test("-2i128"),
@@ -1635,7 +1682,6 @@ mod hash {
         }
 
         #[test]
-        #[cfg(not(feature = "gen-wasm"))] // shr not implemented for U128
         fn u128() {
             assert_evals_to!(
                 &build_test("Num.maxU128 - 1"),
@@ -1647,7 +1693,6 @@ mod hash {
         }
 
         #[test]
-        #[cfg(not(feature = "gen-wasm"))] // shr not implemented for U128
         fn dec() {
             assert_evals_to!(
                 &build_test("1.1dec"),
@@ -1691,6 +1736,15 @@ mod hash {
                 RocList<u8>
             )
         }
+
+        #[test]
+        fn box_string() {
+            assert_evals_to!(
+                &build_test(r#"Box.box "ab""#),
+                RocList::from_slice(&[97, 98]),
+                RocList<u8>
+            )
+        }
     }
 
     mod derived {
@@ -29,6 +29,20 @@ fn dict_empty_len() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn dict_with_capacity() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            Dict.capacity (Dict.withCapacity 100)
+            "#
+        ),
+        112,
+        usize
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_insert_empty() {
@@ -3981,6 +3981,49 @@ mod pattern_match {
         )
     }
 
+    #[test]
+    fn bind_rest() {
+        assert_evals_to!(
+            r#"
+            helper : List U16 -> List U16
+            helper = \l -> when l is
+                [x, .. as rest, y] -> List.append rest (x + y)
+                [.. as rest] -> rest
+
+            [
+                helper [],
+                helper [5],
+                helper [3, 5],
+                helper [3, 5, 7, 11],
+            ]
+            "#,
+            RocList::from_slice(&[
+                RocList::from_slice(&[]),
+                RocList::from_slice(&[5]),
+                RocList::from_slice(&[8]),
+                RocList::from_slice(&[5, 7, 14]),
+            ]),
+            RocList<RocList<u16>>
+        )
+    }
+
+    #[test]
+    fn bind_rest_in_guard() {
+        assert_evals_to!(
+            r#"
+            helper : List U16 -> U16
+            helper = \l -> when l is
+                [x, .. as rest] if List.len rest > 1 -> x + List.sum rest
+                [x, ..] -> x
+                [] -> 0
+
+            [helper [], helper [3], helper [3, 5], helper [3, 5, 7]]
+            "#,
+            RocList::from_slice(&[0, 3, 3, 15]),
+            RocList<u16>
+        )
+    }
+
     #[test]
     fn order_list_size_tests_issue_4732() {
         assert_evals_to!(
@@ -467,6 +467,24 @@ fn f32_float_alias() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn f64_far_into_the_stack_frame() {
+    // The last element is more than 32 KiB into the stack frame, which is further than an
+    // aarch64 load can reach on its own.
+    let elements = vec!["0.5f64"; 5000].join(", ");
+
+    assert_evals_to!(&format!("t = ({elements}, 3.5f64)\n\nt.5000"), 3.5, f64);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn f32_far_into_the_stack_frame() {
+    let elements = vec!["0.5f32"; 10000].join(", ");
+
+    assert_evals_to!(&format!("t = ({elements}, 3.5f32)\n\nt.10000"), 3.5, f32);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
 fn f64_sqrt_100() {
@@ -560,16 +578,53 @@ fn various_sized_abs() {
     assert_evals_to!("Num.abs -6i16", 6, i16);
     assert_evals_to!("Num.abs -6i32", 6, i32);
     assert_evals_to!("Num.abs -6i64", 6, i64);
-    if !cfg!(feature = "gen-wasm") {
-        assert_evals_to!("Num.abs -6i128", 6, i128);
-    }
+    assert_evals_to!("Num.abs -6i128", 6, i128);
     assert_evals_to!("Num.abs 6u8", 6, u8);
     assert_evals_to!("Num.abs 6u16", 6, u16);
     assert_evals_to!("Num.abs 6u32", 6, u32);
     assert_evals_to!("Num.abs 6u64", 6, u64);
-    if !cfg!(feature = "gen-wasm") {
-        assert_evals_to!("Num.abs 6u128", 6, u128);
-    }
+    assert_evals_to!("Num.abs 6u128", 6, u128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_abs() {
+    assert_evals_to!(
+        "Num.abs -170_141_183_460_469_231_731i128",
+        170_141_183_460_469_231_731,
+        i128
+    );
+    assert_evals_to!(
+        "Num.abs 170_141_183_460_469_231_731i128",
+        170_141_183_460_469_231_731,
+        i128
+    );
+    assert_evals_to!("Num.abs (Num.minI128 + 1)", i128::MAX, i128);
+    assert_evals_to!("Num.abs Num.maxU128", u128::MAX, u128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[should_panic(
+    expected = r#"Roc failed with message: "integer absolute overflowed because its argument is the minimum value"#
+)]
+fn abs_min_i128_overflow() {
+    assert_evals_to!("Num.abs Num.minI128", 0, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn dec_abs() {
+    assert_evals_to!(
+        "Num.abs -3.9dec",
+        RocDec::from_str_to_i128_unsafe("3.9"),
+        i128
+    );
+    assert_evals_to!(
+        "Num.abs 3.9dec",
+        RocDec::from_str_to_i128_unsafe("3.9"),
+        i128
+    );
 }
 
 #[test]
@@ -1320,6 +1375,43 @@ fn bitwise_or() {
     assert_evals_to!("Num.bitwiseOr 1 2", 3, i64);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn bitwise_u128() {
+    assert_evals_to!(
+        "Num.bitwiseAnd 0xffff_0000_0000_0000_0000_0000_0000_ffffu128 0x0ff0_0000_0000_0000_0000_0000_0000_0ff0u128",
+        0x0ff0_0000_0000_0000_0000_0000_0000_0ff0u128,
+        u128
+    );
+    assert_evals_to!(
+        "Num.bitwiseXor 0xffff_0000_0000_0000_0000_0000_0000_ffffu128 0x0ff0_0000_0000_0000_0000_0000_0000_0ff0u128",
+        0xf00f_0000_0000_0000_0000_0000_0000_f00fu128,
+        u128
+    );
+    assert_evals_to!(
+        "Num.bitwiseOr 0xffff_0000_0000_0000_0000_0000_0000_0000u128 0xffffu128",
+        0xffff_0000_0000_0000_0000_0000_0000_ffffu128,
+        u128
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn div_and_rem_i128() {
+    assert_evals_to!(
+        "Num.divTrunc -170_141_183_460_469_231_731i128 10",
+        -17_014_118_346_046_923_173,
+        i128
+    );
+    assert_evals_to!("Num.rem -170_141_183_460_469_231_731i128 10", -1, i128);
+    assert_evals_to!(
+        "Num.divTrunc 340_282_366_920_938_463_463u128 7",
+        48_611_766_702_991_209_066,
+        u128
+    );
+    assert_evals_to!("Num.rem 340_282_366_920_938_463_463u128 7", 1, u128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn lt_u8() {
@@ -1593,6 +1685,44 @@ fn neg_min_int_overflow() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_negate() {
+    // 2^64, so that negating it has to borrow from the high half
+    assert_evals_to!(
+        "Num.neg 18_446_744_073_709_551_616i128",
+        -(1i128 << 64),
+        i128
+    );
+    assert_evals_to!("Num.neg -123i128", 123, i128);
+    assert_evals_to!("Num.neg 0i128", 0, i128);
+    assert_evals_to!("Num.neg Num.maxI128", -i128::MAX, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[should_panic(
+    expected = r#"Roc failed with message: "integer negation overflowed because its argument is the minimum value"#
+)]
+fn neg_min_i128_overflow() {
+    assert_evals_to!("Num.neg Num.minI128", 0, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn dec_negate() {
+    assert_evals_to!(
+        "Num.neg 3.9dec",
+        RocDec::from_str_to_i128_unsafe("-3.9"),
+        i128
+    );
+    assert_evals_to!(
+        "Num.neg -3.9dec",
+        RocDec::from_str_to_i128_unsafe("3.9"),
+        i128
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn gen_wrap_int_neg() {
@@ -1799,6 +1929,12 @@ fn pow_int() {
     assert_evals_to!("Num.powInt 2 3", 8, i64);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn pow_int_i128() {
+    assert_evals_to!("Num.powInt 10i128 30", 10i128.pow(30), i128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
 fn atan() {
@@ -2140,6 +2276,21 @@ fn shift_right_by() {
     }
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn shift_i128() {
+    assert_evals_to!("Num.shiftLeftBy 3i128 100", 3i128 << 100, i128);
+    assert_evals_to!(
+        "Num.shiftLeftBy 0xffff_ffff_ffff_ffffu128 8",
+        0xff_ffff_ffff_ffff_ff00u128,
+        u128
+    );
+    assert_evals_to!("Num.shiftRightBy (Num.shiftLeftBy 3i128 100) 99", 6, i128);
+    assert_evals_to!("Num.shiftRightBy Num.minI128 120", i128::MIN >> 120, i128);
+    // shiftRightBy sign-extends unsigned numbers too
+    assert_evals_to!("Num.shiftRightBy Num.maxU128 64", u128::MAX, u128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn shift_right_zf_by() {
@@ -3719,6 +3870,59 @@ fn round_to_u32() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn round_ceiling_and_floor_to_i128() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            n : I128
+            n = Num.ceiling -1e20f64
+            n
+            "#
+        ),
+        -100_000_000_000_000_000_000,
+        i128
+    );
+    assert_evals_to!(
+        indoc!(
+            r#"
+            n : U128
+            n = Num.floor 1e20f64
+            n
+            "#
+        ),
+        100_000_000_000_000_000_000,
+        u128
+    );
+    assert_evals_to!(
+        indoc!(
+            r#"
+            n : I128
+            n = Num.round -124.5f32
+            n
+            "#
+        ),
+        -125,
+        i128
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn num_to_frac_i128_to_dec() {
+    assert_evals_to!(
+        "Num.toFrac -123_456_789i128",
+        RocDec::from_str_to_i128_unsafe("-123456789"),
+        i128
+    );
+    assert_evals_to!(
+        "Num.toFrac 123_456_789u128",
+        RocDec::from_str_to_i128_unsafe("123456789"),
+        i128
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn promote_u64_number_layout() {
@@ -3885,7 +4089,7 @@ fn num_abs_diff_int() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn num_abs_diff_large_bits() {
     assert_evals_to!(r#"Num.absDiff 0u128 0u128"#, 0, u128);
     assert_evals_to!(r#"Num.absDiff 1u128 2u128"#, 1, u128);
@@ -3918,7 +4122,7 @@ fn num_abs_int_min_overflow() {
 }
 
 #[test]
-#[cfg(feature = "gen-llvm")]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 #[should_panic(expected = r#"Roc failed with message: "integer subtraction overflowed!"#)]
 fn num_abs_large_bits_min_overflow() {
     assert_evals_to!(r#"Num.absDiff Num.minI128 0"#, 0, i128);
@@ -849,6 +849,42 @@ fn update_record() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn update_nested_fields() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                rec = { foo: 1, bar: { baz: 2, qux: { quux: 3 } }, corge: 4 }
+
+                new = { rec & foo: 10, bar.baz: 20, bar.qux.quux: 30 }
+
+                new.foo + new.bar.baz + new.bar.qux.quux + new.corge
+                "#
+        ),
+        64,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn update_nested_fields_inside_nested_update() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                rec = { b: { x: 1, y: 2 } }
+
+                new = { rec & b.x: ({ rec & b.y: 20 }).b.y }
+
+                new.b.x + new.b.y
+                "#
+        ),
+        22,
+        i64
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
 fn update_single_element_record() {
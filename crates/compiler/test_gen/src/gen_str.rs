@@ -2002,6 +2002,94 @@ fn when_on_strings() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn when_on_string_prefixes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            describe = \line ->
+                when line is
+                    "fn" -> "an empty function"
+                    "fn main" .. _ -> "the main function"
+                    "fn " .. name -> Str.concat "function " name
+                    "f" .. rest -> Str.concat "something else: " rest
+                    _ -> "nothing"
+
+            [describe "fn", describe "fn main()", describe "fn foo", describe "for x", describe ""]
+            "#
+        ),
+        RocList::from_slice(&[
+            RocStr::from("an empty function"),
+            RocStr::from("the main function"),
+            RocStr::from("function foo"),
+            RocStr::from("something else: or x"),
+            RocStr::from("nothing"),
+        ]),
+        RocList<RocStr>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn when_on_empty_string_prefix() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            describe = \line ->
+                when line is
+                    "fn " .. name -> name
+                    "" .. rest -> Str.concat "not a function: " rest
+
+            [describe "fn foo", describe "for x", describe ""]
+            "#
+        ),
+        RocList::from_slice(&[
+            RocStr::from("foo"),
+            RocStr::from("not a function: for x"),
+            RocStr::from("not a function: "),
+        ]),
+        RocList<RocStr>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn when_on_nested_string_prefix() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            when Ok "Roc is fast" is
+                Ok ("Rust " .. _) -> "nope"
+                Ok ("Roc " .. rest) -> rest
+                _ -> "no"
+            "#
+        ),
+        RocStr::from("is fast"),
+        RocStr
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn when_on_string_prefix_with_guard() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            describe = \s ->
+                when s is
+                    "fn " .. name if !(Str.isEmpty name) -> "function \(name)"
+                    "fn " .. _ -> "anonymous"
+                    _ -> "other"
+
+            Str.joinWith [describe "fn main", describe "fn ", describe "let x"] ", "
+            "#
+        ),
+        RocStr::from("function main, anonymous, other"),
+        RocStr
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn with_capacity() {
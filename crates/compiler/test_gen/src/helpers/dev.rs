@@ -62,6 +62,8 @@ pub fn helper(
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        warning_levels: Default::default(),
+        explain_types: false,
         function_kind: FunctionKind::LambdaSet,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
@@ -5,7 +5,7 @@ use std::sync::OnceLock;
 use inkwell::module::Module;
 use libloading::Library;
 use roc_build::link::llvm_module_to_dylib;
-use roc_collections::all::MutSet;
+use roc_collections::all::{MutMap, MutSet};
 use roc_command_utils::zig;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult};
@@ -78,6 +78,8 @@ fn create_llvm_module<'a>(
         palette: DEFAULT_PALETTE,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        warning_levels: Default::default(),
+        explain_types: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -140,7 +142,7 @@ fn create_llvm_module<'a>(
                 | UnusedModuleImport(_, _)
                 | RuntimeError(_)
                 | UnsupportedPattern(_, _)
-                | ExposedButNotDefined(_) => {
+                | ExposedButNotDefined { .. } => {
                     let report = can_problem(&alloc, &line_info, module_path.clone(), problem);
                     let mut buf = String::new();
 
@@ -231,6 +233,7 @@ fn create_llvm_module<'a>(
         mode: config.mode,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        def_locations: MutMap::default(),
     };
 
     // strip Zig debug stuff
@@ -260,6 +263,7 @@ fn create_llvm_module<'a>(
         LlvmBackendMode::BinaryDev => unreachable!(),
         LlvmBackendMode::BinaryGlue => unreachable!(),
         LlvmBackendMode::CliTest => unreachable!(),
+        LlvmBackendMode::Repl => unreachable!(),
         LlvmBackendMode::WasmGenTest => roc_gen_llvm::llvm::build::build_wasm_test_wrapper(
             &env,
             &layout_interner,
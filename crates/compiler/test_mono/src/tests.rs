@@ -111,6 +111,8 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, allow_type_errors: boo
         render: roc_reporting::report::RenderTarget::Generic,
         palette: roc_reporting::report::DEFAULT_PALETTE,
         exec_mode,
+        warning_levels: Default::default(),
+        explain_types: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use roc_compile::{format_str, FormatOptions, FormatProblem};
+
+// The same checks as `roc format --verify`: anything that parses must format into code
+// that parses the same way, and formatting that again must not change it.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        match format_str(input, FormatOptions::default()) {
+            Ok(_) | Err(FormatProblem::Syntax(_)) | Err(FormatProblem::Config(_)) => {}
+            Err(problem) => panic!("Formatting bug: {problem:?}"),
+        }
+    }
+});
@@ -9,7 +9,7 @@ use roc_parse::{
 };
 use roc_test_utils::assert_multiline_str_eq;
 
-use roc_fmt::spaces::RemoveSpaces;
+use roc_fmt::spaces::{count_comments, RemoveSpaces};
 use roc_fmt::Buf;
 
 /// Source code to parse. Usually in the form of a test case.
@@ -238,6 +238,17 @@ impl<'a> Input<'a> {
             );
         }
 
+        // Comments aren't part of the normalized AST, so check separately that none went missing
+        assert_eq!(
+            count_comments(self.as_str()),
+            count_comments(output.as_ref().as_str()),
+            "Formatting bug; formatting didn't keep every comment\n\n\
+            * * * Source code before formatting:\n{}\n\n\
+            * * * Source code after formatting:\n{}\n\n",
+            self.as_str(),
+            output.as_ref().as_str(),
+        );
+
         // Now verify that the resultant formatting is _idempotent_ - i.e. that it doesn't change again if re-formatted
         if check_idempotency {
             let reformatted = reparsed_ast.format();
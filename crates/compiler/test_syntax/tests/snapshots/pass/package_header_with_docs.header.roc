@@ -0,0 +1,4 @@
+package "foo/barbaz"
+    exposes [Foo]
+    packages {}
+    docs { logo: "logo.svg", "Source code": "https://example.com/foo" }
\ No newline at end of file
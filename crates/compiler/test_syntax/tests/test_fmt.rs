@@ -56,7 +56,7 @@ mod test_fmt {
 
         match module::parse_header(&arena, State::new(src.as_bytes())) {
             Ok((actual, state)) => {
-                use roc_fmt::spaces::RemoveSpaces;
+                use roc_fmt::spaces::{count_comments, RemoveSpaces};
 
                 let mut buf = Buf::new_in(&arena);
 
@@ -86,6 +86,14 @@ mod test_fmt {
                     );
                 }
 
+                assert_eq!(
+                    count_comments(src),
+                    count_comments(output),
+                    "Formatting bug; formatting didn't keep every comment\n\n\
+                    * * * Source code before formatting:\n{src}\n\n\
+                    * * * Source code after formatting:\n{output}\n\n"
+                );
+
                 // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
                 let mut reformatted_buf = Buf::new_in(&arena);
 
@@ -1922,6 +1930,28 @@ mod test_fmt {
         );
     }
 
+    #[test]
+    fn nested_record_updating() {
+        expr_formats_same(indoc!(
+            r#"
+            { user & name: "Sam", address.city: "Paris", address.zip.code: "75001" }
+            "#
+        ));
+
+        expr_formats_to(
+            indoc!(
+                r#"
+                { user &  address.city : "Paris" }
+                "#
+            ),
+            indoc!(
+                r#"
+                { user & address.city: "Paris" }
+                "#
+            ),
+        );
+    }
+
     #[test]
     fn record_builder() {
         expr_formats_same(indoc!(
@@ -3600,6 +3630,20 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn when_with_comment_before_arrow() {
+        expr_formats_same(indoc!(
+            r#"
+                when 0 is
+                    1
+                    | 2 # comment
+                    -> "a"
+
+                    _ -> "b"
+            "#
+        ));
+    }
+
     #[test]
     fn nested_when() {
         expr_formats_same(indoc!(
@@ -4775,6 +4819,16 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn interface_with_only_comments() {
+        module_formats_same(indoc!(
+            r#"
+                interface Foo exposes [] imports []
+
+                # main = 42"#
+        ));
+    }
+
     #[test]
     fn defs_with_trailing_comment() {
         // TODO: make the formatter add a space between '42' and # below:
@@ -5909,6 +5963,30 @@ mod test_fmt {
         );
     }
 
+    #[test]
+    fn patterns_with_final_comments() {
+        expr_formats_same(indoc!(
+            r#"
+            {
+                x,
+                y, # the rest don't matter
+            } = point
+
+            x
+            "#
+        ));
+
+        expr_formats_same(indoc!(
+            r#"
+            when list is
+                [
+                    first,
+                    .., # and the rest
+                ] -> first
+            "#
+        ));
+    }
+
     #[test]
     fn format_list_patterns() {
         expr_formats_same(indoc!(
@@ -5968,6 +6046,35 @@ mod test_fmt {
         );
     }
 
+    #[test]
+    fn str_prefix_patterns() {
+        expr_formats_same(indoc!(
+            r#"
+            when line is
+                "fn " .. name -> name
+                Ok ("let " .. _) -> ""
+                _ -> ""
+            "#
+        ));
+
+        expr_formats_to(
+            indoc!(
+                r#"
+                when line is
+                    "fn "..name -> name
+                    "let "   ..   _ -> ""
+                "#
+            ),
+            indoc!(
+                r#"
+                when line is
+                    "fn " .. name -> name
+                    "let " .. _ -> ""
+                "#
+            ),
+        );
+    }
+
     #[test]
     fn format_crash() {
         expr_formats_same(indoc!(
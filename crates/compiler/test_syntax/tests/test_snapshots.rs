@@ -399,6 +399,7 @@ mod test_snapshots {
         pass/outdented_colon_in_record.expr,
         pass/outdented_list.expr,
         pass/outdented_record.expr,
+        pass/package_header_with_docs.header,
         pass/packed_singleton_list.expr,
         pass/parens_in_type_def_apply.expr,
         pass/parens_in_value_def_annotation.expr,
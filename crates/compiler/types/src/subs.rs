@@ -1786,6 +1786,10 @@ impl Subs {
             Variable::EMPTY_RECORD,
             Content::Structure(FlatType::EmptyRecord),
         );
+        subs.set_content(
+            Variable::EMPTY_TUPLE,
+            Content::Structure(FlatType::EmptyTuple),
+        );
         subs.set_content(
             Variable::EMPTY_TAG_UNION,
             Content::Structure(FlatType::EmptyTagUnion),
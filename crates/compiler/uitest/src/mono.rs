@@ -49,6 +49,8 @@ pub(crate) fn write_compiled_ir<'a>(
         render: roc_reporting::report::RenderTarget::Generic,
         palette: roc_reporting::report::DEFAULT_PALETTE,
         exec_mode,
+        warning_levels: Default::default(),
+        explain_types: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
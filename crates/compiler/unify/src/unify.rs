@@ -3198,6 +3198,8 @@ fn unify_flat_type<M: MetaCollector>(
             unify_record(env, pool, ctx, *fields1, *ext1, *fields2, *ext2)
         }
 
+        (EmptyTuple, EmptyTuple) => merge(env, ctx, Structure(*left)),
+
         (Tuple(elems1, ext1), Tuple(elems2, ext2)) => {
             unify_tuple(env, pool, ctx, *elems1, *ext1, *elems2, *ext2)
         }
@@ -0,0 +1,84 @@
+/*
+ * C bindings to the Roc compiler. Link with the roc_compiler_c library
+ * (libroc_compiler_c.so / .dylib / .a, or roc_compiler_c.dll / .lib).
+ *
+ *     RocResult *result = roc_check("main.roc", src, src_len);
+ *
+ *     for (size_t i = 0; i < roc_result_diagnostic_count(result); i++) {
+ *         const RocDiagnostic *d = roc_result_diagnostic(result, i);
+ *         printf("%s:%u: %s\n%s\n", d->path, d->start_line + 1, d->title, d->message);
+ *     }
+ *
+ *     roc_result_free(result);
+ *
+ * Strings are NUL-terminated UTF-8, and belong to the result they came from.
+ * Results aren't thread-safe, but separate results can be made and used on separate threads.
+ */
+#ifndef ROC_COMPILER_H
+#define ROC_COMPILER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum RocSeverity {
+    ROC_SEVERITY_WARNING = 0,
+    ROC_SEVERITY_RUNTIME_ERROR = 1,
+    ROC_SEVERITY_FATAL = 2,
+} RocSeverity;
+
+typedef struct RocDiagnostic {
+    const char *path;
+    RocSeverity severity;
+    /* Whether the problem is anywhere in particular. If not, the lines and columns are 0. */
+    bool has_region;
+    /* Lines and columns start at 0. */
+    uint32_t start_line;
+    uint32_t start_column;
+    uint32_t end_line;
+    uint32_t end_column;
+    /* Empty for the problems that stopped the compiler before it got to any module. */
+    const char *title;
+    const char *message;
+} RocDiagnostic;
+
+/* What checking or building some code found. */
+typedef struct RocResult RocResult;
+
+/*
+ * Type-check a module, along with every module it imports. `path` is where the module is
+ * (or would be) on disk, which is where its imports are found relative to; it may be NULL.
+ * `src` doesn't need to be NUL-terminated. Never returns NULL.
+ */
+RocResult *roc_check(const char *path, const uint8_t *src, size_t src_len);
+
+/*
+ * Compile an app to object code for the machine this is running on, which the app's
+ * platform host can then be linked with. `optimize` is like `roc build --optimize`.
+ * If there are errors, there's no object code. Never returns NULL.
+ */
+RocResult *roc_build(const char *path, const uint8_t *src, size_t src_len, bool optimize);
+
+/* Whether there were no errors, only warnings (if anything). */
+bool roc_result_succeeded(const RocResult *result);
+
+size_t roc_result_diagnostic_count(const RocResult *result);
+
+/* The diagnostic at `index`, or NULL if there aren't that many. Sorted by path. */
+const RocDiagnostic *roc_result_diagnostic(const RocResult *result, size_t index);
+
+/* The object code from roc_build, with its length written to `len`. NULL if there isn't any. */
+const uint8_t *roc_result_object(const RocResult *result, size_t *len);
+
+/* Free a result, along with its diagnostics and object code. Does nothing with NULL. */
+void roc_result_free(RocResult *result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ROC_COMPILER_H */
@@ -0,0 +1,369 @@
+//! C bindings to [roc_compile], so that tools written in other languages (editor plugins,
+//! linters, build systems) can check and build Roc code with the real compiler.
+//!
+//! `roc_compiler.h` next to this crate's manifest declares everything here for C.
+//! Every function takes source code as a pointer and a length, and hands back a
+//! [RocResult] that owns the diagnostics (and for [roc_build], the object code) until
+//! it's given to [roc_result_free]. Panics inside the compiler don't unwind into the
+//! caller: they come back as a fatal diagnostic instead.
+#![warn(clippy::dbg_macro)]
+// This crate is all about taking pointers from C, which the caller promises are valid.
+#![allow(clippy::missing_safety_doc)]
+
+use bumpalo::Bump;
+use roc_build::program::{gen_from_mono_module, CodeGenBackend, CodeGenOptions};
+use roc_compile::{Diagnostic, Options, Severity, TargetInfo};
+use roc_gen_llvm::llvm::build::LlvmBackendMode;
+use roc_mono::ir::OptLevel;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::{ptr, slice};
+use target_lexicon::Triple;
+
+/// The path modules get when the caller doesn't give one.
+const DEFAULT_PATH: &str = "main.roc";
+
+/// How bad a [RocDiagnostic] is. Matches [Severity].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RocSeverity {
+    Warning = 0,
+    RuntimeError = 1,
+    Fatal = 2,
+}
+
+impl From<Severity> for RocSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Warning => RocSeverity::Warning,
+            Severity::RuntimeError => RocSeverity::RuntimeError,
+            Severity::Fatal => RocSeverity::Fatal,
+        }
+    }
+}
+
+/// A [Diagnostic], for C. The strings are NUL-terminated UTF-8, and belong to the
+/// [RocResult] the diagnostic came from.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RocDiagnostic {
+    pub path: *const c_char,
+    pub severity: RocSeverity,
+    /// Whether the problem is anywhere in particular. If not, the lines and columns are 0.
+    pub has_region: bool,
+    /// Lines and columns start at 0.
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+    /// Empty for the problems that stopped the compiler before it got to any module.
+    pub title: *const c_char,
+    pub message: *const c_char,
+}
+
+/// What checking or building some code found. Opaque to C.
+#[derive(Debug)]
+pub struct RocResult {
+    diagnostics: Vec<RocDiagnostic>,
+    /// What the diagnostics' pointers point into.
+    strings: Vec<CString>,
+    /// The object code that [roc_build] made, if it got that far.
+    object: Option<Vec<u8>>,
+}
+
+impl RocResult {
+    fn new(diagnostics: &[Diagnostic], object: Option<Vec<u8>>) -> Self {
+        let mut result = RocResult {
+            diagnostics: Vec::with_capacity(diagnostics.len()),
+            strings: Vec::with_capacity(diagnostics.len() * 3),
+            object,
+        };
+
+        for diagnostic in diagnostics {
+            let region = diagnostic.region;
+            let c_diagnostic = RocDiagnostic {
+                path: result.c_str(&diagnostic.path.to_string_lossy()),
+                severity: diagnostic.severity.into(),
+                has_region: region.is_some(),
+                start_line: region.map_or(0, |region| region.start().line),
+                start_column: region.map_or(0, |region| region.start().column),
+                end_line: region.map_or(0, |region| region.end().line),
+                end_column: region.map_or(0, |region| region.end().column),
+                title: result.c_str(&diagnostic.title),
+                message: result.c_str(&diagnostic.message),
+            };
+
+            result.diagnostics.push(c_diagnostic);
+        }
+
+        result
+    }
+
+    /// A result with just one fatal diagnostic, for when the compiler couldn't even start.
+    fn fatal(path: &Path, message: String) -> Self {
+        let diagnostic = Diagnostic {
+            path: path.to_path_buf(),
+            region: None,
+            severity: Severity::Fatal,
+            title: String::new(),
+            message,
+        };
+
+        RocResult::new(&[diagnostic], None)
+    }
+
+    /// Keep a copy of `string` for as long as the result lives, and point to it.
+    fn c_str(&mut self, string: &str) -> *const c_char {
+        // Roc source can't contain NUL bytes, but a path or message conceivably could.
+        let string = CString::new(string.replace('\0', "")).unwrap_or_default();
+        let ptr = string.as_ptr();
+
+        // Moving a CString doesn't move the bytes it points to.
+        self.strings.push(string);
+
+        ptr
+    }
+
+    fn succeeded(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == RocSeverity::Warning)
+    }
+}
+
+/// Type-check a module, along with every module it imports. `path` is where the module is
+/// (or would be) on disk, which is where its imports are found relative to; it may be NULL.
+/// `src` doesn't need to be NUL-terminated.
+///
+/// Never returns NULL. Free the result with [roc_result_free].
+#[no_mangle]
+pub unsafe extern "C" fn roc_check(
+    path: *const c_char,
+    src: *const u8,
+    src_len: usize,
+) -> *mut RocResult {
+    with_src(path, src, src_len, |path, src| {
+        let checked = roc_compile::check_str(path, src, &host_options(&Triple::host()));
+
+        RocResult::new(&checked.diagnostics, None)
+    })
+}
+
+/// Compile an app to object code for the machine this is running on, which the app's
+/// platform host can then be linked with. Arguments are as for [roc_check];
+/// `optimize` is like `roc build --optimize`.
+///
+/// If there are errors, there's no object code. Never returns NULL.
+/// Free the result with [roc_result_free].
+#[no_mangle]
+pub unsafe extern "C" fn roc_build(
+    path: *const c_char,
+    src: *const u8,
+    src_len: usize,
+    optimize: bool,
+) -> *mut RocResult {
+    with_src(path, src, src_len, |path, src| {
+        let triple = Triple::host();
+        let options = host_options(&triple);
+        let arena = Bump::new();
+        let compiled = roc_compile::compile_str(&arena, path, src, &options);
+
+        let module = match compiled.module {
+            Some(module) if compiled.diagnostics.iter().all(|d| !d.is_error()) => module,
+            _ => return RocResult::new(&compiled.diagnostics, None),
+        };
+
+        let code_gen_options = CodeGenOptions {
+            backend: CodeGenBackend::Llvm(LlvmBackendMode::Binary),
+            opt_level: if optimize {
+                OptLevel::Optimize
+            } else {
+                OptLevel::Normal
+            },
+            emit_debug_info: false,
+            emit: Default::default(),
+        };
+        let (object, _, _) = gen_from_mono_module(
+            &arena,
+            module,
+            path,
+            &triple,
+            code_gen_options,
+            // Only the surgical linker and the wasm backends use the preprocessed host.
+            Path::new(""),
+            None,
+        );
+
+        RocResult::new(&compiled.diagnostics, Some(object.to_vec()))
+    })
+}
+
+/// Options for checking or compiling code that's going to run on `triple`, so that
+/// [roc_check] and [roc_build] agree on things like the size of pointers.
+fn host_options(triple: &Triple) -> Options {
+    Options {
+        target_info: TargetInfo::from(triple),
+        ..Options::default()
+    }
+}
+
+/// Whether there were no errors, only warnings (if anything).
+#[no_mangle]
+pub unsafe extern "C" fn roc_result_succeeded(result: *const RocResult) -> bool {
+    (*result).succeeded()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_result_diagnostic_count(result: *const RocResult) -> usize {
+    (*result).diagnostics.len()
+}
+
+/// The diagnostic at `index`, or NULL if there aren't that many. Diagnostics are sorted by
+/// the path of the module they're in.
+#[no_mangle]
+pub unsafe extern "C" fn roc_result_diagnostic(
+    result: *const RocResult,
+    index: usize,
+) -> *const RocDiagnostic {
+    match (*result).diagnostics.get(index) {
+        Some(diagnostic) => diagnostic,
+        None => ptr::null(),
+    }
+}
+
+/// The object code from [roc_build], with its length written to `len`.
+/// NULL (and a length of 0) if there isn't any.
+#[no_mangle]
+pub unsafe extern "C" fn roc_result_object(result: *const RocResult, len: *mut usize) -> *const u8 {
+    match &(*result).object {
+        Some(object) => {
+            *len = object.len();
+            object.as_ptr()
+        }
+        None => {
+            *len = 0;
+            ptr::null()
+        }
+    }
+}
+
+/// Free a result, along with its diagnostics and object code. Does nothing with NULL.
+#[no_mangle]
+pub unsafe extern "C" fn roc_result_free(result: *mut RocResult) {
+    if !result.is_null() {
+        drop(Box::from_raw(result));
+    }
+}
+
+/// Turn the arguments C gave into Rust values, and run `f` on them without letting it
+/// unwind into C.
+unsafe fn with_src(
+    path: *const c_char,
+    src: *const u8,
+    src_len: usize,
+    f: impl FnOnce(&Path, &str) -> RocResult,
+) -> *mut RocResult {
+    let path = if path.is_null() {
+        PathBuf::from(DEFAULT_PATH)
+    } else {
+        PathBuf::from(CStr::from_ptr(path).to_string_lossy().into_owned())
+    };
+    let src = if src_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(src, src_len)
+    };
+
+    let result = match std::str::from_utf8(src) {
+        Ok(src) => {
+            panic::catch_unwind(AssertUnwindSafe(|| f(&path, src))).unwrap_or_else(|payload| {
+                let reason = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+
+                RocResult::fatal(&path, format!("The compiler crashed: {reason}"))
+            })
+        }
+        Err(err) => RocResult::fatal(&path, format!("The source isn't valid UTF-8: {err}")),
+    };
+
+    Box::into_raw(Box::new(result))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    unsafe fn check(src: &str) -> *mut RocResult {
+        let path = CString::new("Test.roc").unwrap();
+
+        roc_check(path.as_ptr(), src.as_ptr(), src.len())
+    }
+
+    #[test]
+    fn check_reports_diagnostics() {
+        let src = indoc!(
+            r#"
+            interface Test exposes [answer] imports []
+
+            answer : Str
+            answer = 42
+            "#
+        );
+
+        unsafe {
+            let result = check(src);
+
+            assert!(!roc_result_succeeded(result));
+            assert_eq!(roc_result_diagnostic_count(result), 1);
+            assert!(roc_result_diagnostic(result, 1).is_null());
+
+            let diagnostic = &*roc_result_diagnostic(result, 0);
+
+            assert_eq!(
+                CStr::from_ptr(diagnostic.title).to_str(),
+                Ok("TYPE MISMATCH")
+            );
+            assert_eq!(CStr::from_ptr(diagnostic.path).to_str(), Ok("Test.roc"));
+            assert_eq!(diagnostic.severity, RocSeverity::RuntimeError);
+            assert!(diagnostic.has_region);
+            assert_eq!(diagnostic.start_line, 3);
+
+            let mut len = 1;
+
+            assert!(roc_result_object(result, &mut len).is_null());
+            assert_eq!(len, 0);
+
+            roc_result_free(result);
+        }
+    }
+
+    #[test]
+    fn check_and_build_target_the_host() {
+        let triple = Triple::host();
+
+        assert_eq!(host_options(&triple).target_info, TargetInfo::from(&triple));
+    }
+
+    #[test]
+    fn check_rejects_invalid_utf8() {
+        let src = [0xff, 0xfe];
+
+        unsafe {
+            let result = roc_check(ptr::null(), src.as_ptr(), src.len());
+
+            assert!(!roc_result_succeeded(result));
+
+            let diagnostic = &*roc_result_diagnostic(result, 0);
+
+            assert_eq!(diagnostic.severity, RocSeverity::Fatal);
+            assert_eq!(CStr::from_ptr(diagnostic.path).to_str(), Ok(DEFAULT_PATH));
+
+            roc_result_free(result);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! `roc test --doc`, which runs the examples in a module's doc comments so they can't rot.
+//! An example is a Roc code block with `==` in it; each one becomes a top-level `expect`
+//! that's added to the end of the module. Code blocks marked `unchecked` or `repl` are
+//! left out, and so are blocks without `==`, since there's nothing for them to check.
+use crate::is_roc_code_block;
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+
+/// A code block in a doc comment, turned into top-level `expect`s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocExample {
+    /// The line of the module the code block starts on, counting from 1
+    pub line: usize,
+    /// The top-level `expect`s that check the example
+    pub expects: String,
+}
+
+/// The examples in every doc comment of a module
+pub fn doc_examples(module_src: &str) -> Vec<DocExample> {
+    let mut examples = Vec::new();
+    let mut comment = String::new();
+    // The line of the module each line of `comment` came from
+    let mut comment_lines = Vec::new();
+
+    for (index, line) in module_src.lines().enumerate() {
+        match line.trim_start().strip_prefix("##") {
+            Some(text) => {
+                comment.push_str(text.strip_prefix(' ').unwrap_or(text));
+                comment.push('\n');
+                comment_lines.push(index + 1);
+            }
+            None if !comment.is_empty() => {
+                push_examples(&comment, &comment_lines, &mut examples);
+                comment.clear();
+                comment_lines.clear();
+            }
+            None => {}
+        }
+    }
+
+    push_examples(&comment, &comment_lines, &mut examples);
+
+    examples
+}
+
+/// The module's source with its examples added to the end, so that loading it runs them
+/// along with the module's own `expect`s
+pub fn with_doc_examples(module_src: &str, examples: &[DocExample]) -> String {
+    let mut src = module_src.to_string();
+
+    for example in examples {
+        if !src.ends_with('\n') {
+            src.push('\n');
+        }
+
+        src.push_str(&format!(
+            "\n# The example on line {} of this module\n{}",
+            example.line, example.expects
+        ));
+    }
+
+    src
+}
+
+fn push_examples(comment: &str, comment_lines: &[usize], examples: &mut Vec<DocExample>) {
+    // The code in the current block, and the line of the comment it starts on
+    let mut code_block: Option<(String, usize)> = None;
+
+    for (event, range) in Parser::new(comment).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let is_example = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        is_roc_code_block(&info)
+                            && !info.contains("unchecked")
+                            && !info.contains("repl")
+                    }
+                    CodeBlockKind::Indented => true,
+                };
+
+                if is_example {
+                    let line = comment[..range.start].matches('\n').count();
+
+                    code_block = Some((String::new(), line));
+                }
+            }
+            Event::Text(text) => {
+                if let Some((code, _)) = code_block.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some((code, line)) = code_block.take() {
+                    if code.contains("==") {
+                        examples.push(DocExample {
+                            line: comment_lines[line],
+                            expects: to_expects(&code),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A block where every top-level expression is a comparison, like `List.len [] == 0`, gets
+/// an `expect` for each one. Any other block, e.g. one that defines something before
+/// comparing it, becomes a single `expect`. Blocks with their own `expect`s stay as they are.
+fn to_expects(code: &str) -> String {
+    let statements = top_level_statements(code);
+
+    if statements
+        .iter()
+        .any(|statement| statement.split_whitespace().next() == Some("expect"))
+    {
+        let mut expects = code.to_string();
+
+        if !expects.ends_with('\n') {
+            expects.push('\n');
+        }
+
+        expects
+    } else if statements.iter().all(|statement| statement.contains("==")) {
+        statements
+            .iter()
+            .map(|statement| expect(statement))
+            .collect()
+    } else {
+        expect(code)
+    }
+}
+
+/// Each statement starts on an unindented line, and goes on until the next one
+fn top_level_statements(code: &str) -> Vec<String> {
+    let mut statements: Vec<String> = Vec::new();
+
+    for line in code.lines() {
+        let starts_statement = !line.trim().is_empty() && !line.starts_with(char::is_whitespace);
+
+        match statements.last_mut() {
+            Some(statement) if !starts_statement => {
+                statement.push('\n');
+                statement.push_str(line);
+            }
+            _ if line.trim().is_empty() => {}
+            _ => statements.push(line.to_string()),
+        }
+    }
+
+    for statement in statements.iter_mut() {
+        statement.truncate(statement.trim_end().len());
+    }
+
+    statements
+}
+
+fn expect(code: &str) -> String {
+    let mut expect = String::from("expect\n");
+
+    for line in code.trim_end().lines() {
+        if !line.trim().is_empty() {
+            expect.push_str("    ");
+            expect.push_str(line);
+        }
+
+        expect.push('\n');
+    }
+
+    expect
+}
@@ -2,18 +2,24 @@
 //! [roc-lang.org/builtins/Num](https://www.roc-lang.org/builtins/Num).
 extern crate pulldown_cmark;
 extern crate roc_load;
+pub mod doc_tests;
+
 use bumpalo::Bump;
 use roc_can::scope::Scope;
-use roc_collections::VecSet;
-use roc_load::docs::{DocEntry, TypeAnnotation};
+use roc_collections::{MutMap, VecSet};
+use roc_load::docs::{DocDef, DocEntry, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
 use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
-use roc_module::symbol::{Interns, Symbol};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_parse::ast::{Header, Module};
+use roc_parse::header::{DocsKey, PackageHeader};
 use roc_parse::ident::{parse_ident, Accessor, Ident};
 use roc_parse::keyword;
+use roc_parse::module::parse_header;
 use roc_parse::state::State;
 use roc_region::all::Region;
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -21,21 +27,137 @@ const BUILD_DIR: &str = "./generated-docs";
 
 const LINK_SVG: &str = include_str!("./static/link.svg");
 
-pub fn generate_docs_html(root_file: PathBuf) {
-    let build_dir = Path::new(BUILD_DIR);
-    let loaded_module = load_module_for_docs(root_file);
+const LOGO_SVG: &str = include_str!("./static/logo.svg");
 
-    // TODO get these from the platform's source file rather than hardcoding them!
-    // github.com/roc-lang/roc/issues/5712
-    let package_name = "Documentation".to_string();
+/// Where the builtins' docs are published, for linking to builtin types like `Str`
+const BUILTINS_DOCS_URL: &str = "https://www.roc-lang.org/builtins/";
+
+/// The name of a package's root module, unless its URL says otherwise
+const PACKAGE_ROOT_FILENAME: &str = "main.roc";
+
+/// The kinds of output `roc docs` can generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    /// A static website, with a page per module
+    Html,
+    /// A Markdown file per module
+    Markdown,
+    /// A single JSON file describing every module's documented API
+    Json,
+}
+
+/// How a package customizes the header and colors of its generated html docs.
+/// Every page also gets a toggle between the light and dark themes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiteOptions {
+    /// URL of an image to show in the header instead of the Roc logo
+    pub logo_url: Option<String>,
+    /// CSS color to use instead of Roc's purple for links and highlights
+    pub accent_color: Option<String>,
+    /// Extra links for the header, as (text, URL) pairs, e.g. to the package's repository
+    pub header_links: Vec<(String, String)>,
+    /// Where the package's docs are published, so the docs of packages that use it can link
+    /// to its types
+    pub url: Option<String>,
+}
+
+impl SiteOptions {
+    /// The options in the `docs` section of a package's header, like this:
+    ///
+    /// ```roc
+    /// package "json"
+    ///     exposes [Json]
+    ///     packages {}
+    ///     docs {
+    ///         logo: "https://example.com/logo.svg",
+    ///         accentColor: "#1e7b5c",
+    ///         url: "https://example.com/json/",
+    ///         "Source code": "https://github.com/me/json",
+    ///     }
+    /// ```
+    ///
+    /// Other kinds of modules, and packages without a `docs` section, get the defaults.
+    pub fn from_header(module_src: &str) -> Self {
+        let arena = Bump::new();
+        let mut site = SiteOptions::default();
+
+        let docs = match parse_header(&arena, State::new(module_src.as_bytes())) {
+            Ok((
+                Module {
+                    header:
+                        Header::Package(PackageHeader {
+                            docs: Some(docs), ..
+                        }),
+                    ..
+                },
+                _,
+            )) => docs,
+            _ => return site,
+        };
+
+        for entry in docs.item.items {
+            let entry = entry.value.item();
+            let value = entry.value.value.to_string();
+
+            match entry.key {
+                DocsKey::Setting("logo") => site.logo_url = Some(value),
+                DocsKey::Setting("accentColor") => site.accent_color = Some(value),
+                DocsKey::Setting("url") => site.url = Some(value),
+                DocsKey::Setting(other) => {
+                    eprintln!(
+                        "Warning: `{other}` in the package's `docs` section isn't a setting I know about, so I'm ignoring it. The settings are `logo`, `accentColor` and `url`."
+                    );
+                }
+                DocsKey::LinkText(text) => site.header_links.push((text.to_string(), value)),
+            }
+        }
+
+        site
+    }
+}
+
+pub fn generate_docs(root_file: PathBuf, format: DocsFormat) {
+    match format {
+        DocsFormat::Html => generate_docs_html(root_file),
+        DocsFormat::Markdown => generate_docs_markdown(root_file),
+        DocsFormat::Json => generate_docs_json(root_file),
+    }
+}
+
+/// Clear out the generated-docs dir, and create a fresh one
+fn fresh_build_dir() -> &'static Path {
+    let build_dir = Path::new(BUILD_DIR);
 
-    // Clear out the generated-docs dir (we'll create a fresh one at the end)
     if build_dir.exists() {
         fs::remove_dir_all(build_dir)
             .expect("TODO gracefully handle being unable to delete build dir");
     }
     fs::create_dir_all(build_dir).expect("TODO gracefully handle being unable to create build dir");
 
+    build_dir
+}
+
+pub fn generate_docs_html(root_file: PathBuf) {
+    let loaded_module = load_module_for_docs(root_file);
+    let build_dir = fresh_build_dir();
+
+    write_docs_html(&loaded_module, build_dir);
+
+    println!("🎉 Docs generated in {}", build_dir.display());
+}
+
+/// Like [generate_docs_html], for a package that has already been loaded. The docs are written
+/// to `build_dir`, which must already exist.
+pub fn write_docs_html(loaded_module: &LoadedModule, build_dir: &Path) {
+    let site = match loaded_module.sources.get(&loaded_module.module_id) {
+        Some((_, src)) => SiteOptions::from_header(src),
+        None => SiteOptions::default(),
+    };
+
+    // TODO get these from the platform's source file rather than hardcoding them!
+    // github.com/roc-lang/roc/issues/5712
+    let package_name = "Documentation".to_string();
+
     // Copy over the assets
     // For debug builds, read assets from fs to speed up build
     // Otherwise, include as string literal
@@ -117,20 +239,16 @@ pub fn generate_docs_html(root_file: PathBuf) {
                 .as_str(),
         )
         .replace("<!-- base -->", &base_url())
+        .replace("<!-- Theme overrides -->", &render_theme_overrides(&site))
+        .replace("<!-- Logo -->", &render_logo(&site))
+        .replace("<!-- Header links -->", &render_header_links(&site))
         .replace(
             "<!-- Module links -->",
             render_sidebar(loaded_module.docs_by_module.values()).as_str(),
         );
 
-    let all_exposed_symbols = {
-        let mut set = VecSet::default();
-
-        for docs in loaded_module.docs_by_module.values() {
-            set.insert_all(docs.exposed_symbols.iter().copied());
-        }
-
-        set
-    };
+    let all_exposed_symbols = all_exposed_symbols(loaded_module);
+    let dependency_urls = dependency_docs_urls(loaded_module);
 
     // TODO fix: as is, this overrides an existing index.html
     // Write index.html for package (/index.html)
@@ -146,7 +264,7 @@ pub fn generate_docs_html(root_file: PathBuf) {
             )
             .replace(
                 "<!-- Module Docs -->",
-                render_package_index(&loaded_module).as_str(),
+                render_package_index(loaded_module).as_str(),
             );
 
         fs::write(build_dir.join("index.html"), rendered_package).unwrap_or_else(|error| {
@@ -173,17 +291,241 @@ pub fn generate_docs_html(root_file: PathBuf) {
             )
             .replace(
                 "<!-- Module Docs -->",
-                render_module_documentation(module_docs, &loaded_module, &all_exposed_symbols)
-                    .as_str(),
+                render_module_documentation(
+                    module_docs,
+                    loaded_module,
+                    &all_exposed_symbols,
+                    &dependency_urls,
+                )
+                .as_str(),
             );
 
         fs::write(module_dir.join("index.html"), rendered_module)
             .expect("TODO gracefully handle failing to write index.html inside module's dir");
     }
 
+    // Write the index that search.js uses to search every exposed definition
+    fs::write(
+        build_dir.join("search-index.json"),
+        render_search_index(loaded_module.docs_by_module.values(), &all_exposed_symbols),
+    )
+    .unwrap_or_else(|error| {
+        panic!("Attempted to write search-index.json but failed with this error: {error}")
+    });
+}
+
+/// Writes a Markdown file per module, e.g. `generated-docs/Str.md`, for static site generators.
+pub fn generate_docs_markdown(root_file: PathBuf) {
+    let loaded_module = load_module_for_docs(root_file);
+    let build_dir = fresh_build_dir();
+
+    write_docs_markdown(&loaded_module, build_dir);
+
     println!("🎉 Docs generated in {}", build_dir.display());
 }
 
+/// Like [generate_docs_markdown], for a package that has already been loaded. The docs are
+/// written to `build_dir`, which must already exist.
+pub fn write_docs_markdown(loaded_module: &LoadedModule, build_dir: &Path) {
+    let all_exposed_symbols = all_exposed_symbols(loaded_module);
+
+    for module in loaded_module.docs_by_module.values() {
+        let module_name = module.name.as_str();
+        let mut buf = String::new();
+
+        buf.push_str("# ");
+        buf.push_str(module_name);
+        buf.push_str("\n\n");
+
+        for entry in &module.entries {
+            match entry {
+                DocEntry::DocDef(doc_def) => {
+                    if !all_exposed_symbols.contains(&doc_def.symbol) {
+                        continue;
+                    }
+
+                    buf.push_str("## ");
+                    buf.push_str(doc_def.name.as_str());
+                    buf.push_str("\n\n```roc\n");
+                    buf.push_str(&signature_text(doc_def));
+                    buf.push_str("\n```\n\n");
+
+                    if let Some(docs) = &doc_def.docs {
+                        buf.push_str(docs.trim());
+                        buf.push_str("\n\n");
+                    }
+                }
+                DocEntry::DetachedDoc(docs) => {
+                    buf.push_str(docs.trim());
+                    buf.push_str("\n\n");
+                }
+            }
+        }
+
+        let path = build_dir.join(format!("{}.md", module_name.replace('.', "/")));
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .expect("TODO gracefully handle not being able to create the module dir");
+        }
+
+        fs::write(&path, buf.trim_end().to_string() + "\n").unwrap_or_else(|error| {
+            panic!(
+                "Attempted to write {} but failed with this error: {}",
+                path.display(),
+                error
+            )
+        });
+    }
+}
+
+/// Writes `generated-docs/docs.json`, a model of the documented API for package registries
+/// and other tools. For example:
+///
+/// ```json
+/// {"modules":[{"name":"Str","entries":[
+///     {"kind":"docs","docs":"..."},
+///     {"kind":"def","name":"isEmpty","type_vars":[],"type":"Str -> Bool","signature":"isEmpty : Str -> Bool","docs":"..."}
+/// ]}]}
+/// ```
+///
+/// Only exposed definitions are included. `type` is empty when a definition has no annotation.
+pub fn generate_docs_json(root_file: PathBuf) {
+    let loaded_module = load_module_for_docs(root_file);
+    let build_dir = fresh_build_dir();
+
+    write_docs_json(&loaded_module, build_dir);
+
+    println!(
+        "🎉 Docs generated in {}",
+        build_dir.join("docs.json").display()
+    );
+}
+
+/// Like [generate_docs_json], for a package that has already been loaded. `docs.json` is
+/// written to `build_dir`, which must already exist.
+pub fn write_docs_json(loaded_module: &LoadedModule, build_dir: &Path) {
+    let all_exposed_symbols = all_exposed_symbols(loaded_module);
+
+    let modules = loaded_module
+        .docs_by_module
+        .values()
+        .map(|module| JsonModule {
+            name: module.name.as_str(),
+            entries: module
+                .entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    DocEntry::DocDef(doc_def) => {
+                        if !all_exposed_symbols.contains(&doc_def.symbol) {
+                            return None;
+                        }
+
+                        let mut type_ann = String::new();
+
+                        if !matches!(doc_def.type_annotation, TypeAnnotation::NoTypeAnn) {
+                            type_annotation_to_html(
+                                0,
+                                &mut type_ann,
+                                &doc_def.type_annotation,
+                                false,
+                                None,
+                            );
+                        }
+
+                        Some(JsonEntry::Def {
+                            name: doc_def.name.as_str(),
+                            type_vars: doc_def.type_vars.iter().map(String::as_str).collect(),
+                            type_ann: type_ann.trim().to_string(),
+                            signature: signature_text(doc_def),
+                            docs: doc_def.docs.as_deref().unwrap_or("").trim(),
+                        })
+                    }
+                    DocEntry::DetachedDoc(docs) => Some(JsonEntry::Docs { docs: docs.trim() }),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let mut json = serde_json::to_string(&JsonDocs { modules })
+        .expect("a model of the docs made of strings always serializes");
+
+    json.push('\n');
+
+    let path = build_dir.join("docs.json");
+
+    fs::write(&path, json).unwrap_or_else(|error| {
+        panic!("Attempted to write docs.json but failed with this error: {error}")
+    });
+}
+
+/// The model of the documented API in `docs.json`
+#[derive(Debug, Serialize)]
+struct JsonDocs<'a> {
+    modules: Vec<JsonModule<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonModule<'a> {
+    name: &'a str,
+    entries: Vec<JsonEntry<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum JsonEntry<'a> {
+    /// An exposed definition
+    Def {
+        name: &'a str,
+        type_vars: Vec<&'a str>,
+        /// Empty when the definition has no annotation
+        #[serde(rename = "type")]
+        type_ann: String,
+        signature: String,
+        docs: &'a str,
+    },
+    /// Docs that aren't attached to a definition
+    Docs { docs: &'a str },
+}
+
+fn all_exposed_symbols(loaded_module: &LoadedModule) -> VecSet<Symbol> {
+    let mut set = VecSet::default();
+
+    for docs in loaded_module.docs_by_module.values() {
+        set.insert_all(docs.exposed_symbols.iter().copied());
+    }
+
+    set
+}
+
+/// A definition's name and type as plain text, the way it's written in the module,
+/// e.g. `isEmpty : Str -> Bool`
+fn signature_text(doc_def: &DocDef) -> String {
+    let mut buf = String::new();
+
+    buf.push_str(doc_def.name.as_str());
+
+    for type_var in &doc_def.type_vars {
+        buf.push(' ');
+        buf.push_str(type_var.as_str());
+    }
+
+    let type_ann = &doc_def.type_annotation;
+
+    if !matches!(type_ann, TypeAnnotation::NoTypeAnn) {
+        // Ability declarations don't have ":" after the name, just `implements`
+        if !matches!(type_ann, TypeAnnotation::Ability { .. }) {
+            buf.push_str(" :");
+        }
+
+        buf.push(' ');
+
+        type_annotation_to_html(0, &mut buf, type_ann, false, None);
+    }
+
+    buf
+}
+
 fn page_title(package_name: &str, module_name: &str) -> String {
     format!("<title>{module_name} - {package_name}</title>")
 }
@@ -224,6 +566,7 @@ fn render_module_documentation(
     module: &ModuleDocumentation,
     root_module: &LoadedModule,
     all_exposed_symbols: &VecSet<Symbol>,
+    dependency_urls: &MutMap<ModuleId, String>,
 ) -> String {
     let mut buf = String::new();
     let module_name = module.name.as_str();
@@ -236,6 +579,13 @@ fn render_module_documentation(
         link_buf
     });
 
+    let type_links = TypeLinks {
+        all_exposed_symbols,
+        dependency_urls,
+        scope: &module.scope,
+        interns: &root_module.interns,
+    };
+
     for entry in &module.entries {
         match entry {
             DocEntry::DocDef(doc_def) => {
@@ -265,7 +615,13 @@ fn render_module_documentation(
 
                         content.push(' ');
 
-                        type_annotation_to_html(0, &mut content, type_ann, false);
+                        type_annotation_to_html(
+                            0,
+                            &mut content,
+                            type_ann,
+                            false,
+                            Some(&type_links),
+                        );
                     }
 
                     push_html(
@@ -328,6 +684,46 @@ fn push_html(buf: &mut String, tag_name: &str, attrs: Vec<(&str, &str)>, content
     buf.push('>');
 }
 
+/// An inline stylesheet for the package's accent color, which goes after styles.css so it
+/// wins over both themes' colors.
+fn render_theme_overrides(site: &SiteOptions) -> String {
+    match &site.accent_color {
+        Some(color) => {
+            let color = escape_html(color);
+
+            format!(
+                r#"<style>:root, :root[data-theme="dark"], :root:not([data-theme="light"]) {{ --violet: {color}; }}</style>"#
+            )
+        }
+        None => String::new(),
+    }
+}
+
+fn render_logo(site: &SiteOptions) -> String {
+    match &site.logo_url {
+        Some(url) => format!(
+            r#"<img id="logo-link" src="{}" alt="Return to package docs">"#,
+            escape_html(url)
+        ),
+        None => LOGO_SVG.to_string(),
+    }
+}
+
+fn render_header_links(site: &SiteOptions) -> String {
+    let mut buf = String::new();
+
+    for (text, url) in &site.header_links {
+        push_html(
+            &mut buf,
+            "a",
+            vec![("href", escape_html(url).as_str())],
+            escape_html(text),
+        );
+    }
+
+    buf
+}
+
 fn base_url() -> String {
     // e.g. "builtins/" in "https://roc-lang.org/builtins/Str"
     //
@@ -436,6 +832,78 @@ fn render_sidebar<'a, I: Iterator<Item = &'a ModuleDocumentation>>(modules: I) -
     buf
 }
 
+/// A JSON array with one object per exposed definition, e.g.
+///
+/// ```json
+/// [{"name":"List.walkUntil","href":"List#walkUntil","type":"List elem, state, (state, elem -> [Continue state, Break state]) -> state","docs":"..."}]
+/// ```
+///
+/// `docs` is the plain text of the first paragraph of the definition's doc comment.
+/// An exposed definition in `search-index.json`, which search.js searches as you type
+#[derive(Debug, Serialize)]
+struct SearchEntry {
+    /// e.g. `List.walkUntil`
+    name: String,
+    href: String,
+    /// The definition's type, on one line
+    #[serde(rename = "type")]
+    type_ann: String,
+    /// The first paragraph of the definition's docs, on one line
+    docs: String,
+}
+
+fn render_search_index<'a, I: Iterator<Item = &'a ModuleDocumentation>>(
+    modules: I,
+    all_exposed_symbols: &VecSet<Symbol>,
+) -> String {
+    let mut entries = Vec::new();
+
+    for module in modules {
+        let module_name = module.name.as_str();
+
+        for entry in &module.entries {
+            if let DocEntry::DocDef(doc_def) = entry {
+                if !all_exposed_symbols.contains(&doc_def.symbol) {
+                    continue;
+                }
+
+                let def_name = doc_def.name.as_str();
+
+                let mut type_ann = String::new();
+
+                if !matches!(doc_def.type_annotation, TypeAnnotation::NoTypeAnn) {
+                    type_annotation_to_html(
+                        0,
+                        &mut type_ann,
+                        &doc_def.type_annotation,
+                        false,
+                        None,
+                    );
+                }
+
+                let summary = doc_def
+                    .docs
+                    .as_deref()
+                    .and_then(|docs| docs.split("\n\n").map(str::trim).find(|p| !p.is_empty()))
+                    .unwrap_or("");
+
+                entries.push(SearchEntry {
+                    name: format!("{module_name}.{def_name}"),
+                    href: format!("{module_name}#{def_name}"),
+                    type_ann: collapse_whitespace(&type_ann),
+                    docs: collapse_whitespace(summary),
+                });
+            }
+        }
+    }
+
+    serde_json::to_string(&entries).expect("a search index of strings always serializes")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 pub fn load_module_for_docs(filename: PathBuf) -> LoadedModule {
     let arena = Bump::new();
     let load_config = LoadConfig {
@@ -445,6 +913,8 @@ pub fn load_module_for_docs(filename: PathBuf) -> LoadedModule {
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading: Threading::AllAvailable,
         exec_mode: ExecutionMode::Check,
+        warning_levels: Default::default(),
+        explain_types: false,
     };
     match roc_load::load_and_typecheck(
         &arena,
@@ -479,6 +949,7 @@ fn type_annotation_to_html(
     buf: &mut String,
     type_ann: &TypeAnnotation,
     needs_parens: bool,
+    links: Option<&TypeLinks>,
 ) {
     let is_multiline = should_be_multiline(type_ann);
     match type_ann {
@@ -513,7 +984,7 @@ fn type_annotation_to_html(
 
                     for type_value in &tag.values {
                         buf.push(' ');
-                        type_annotation_to_html(next_indent_level, buf, type_value, true);
+                        type_annotation_to_html(next_indent_level, buf, type_value, true, links);
                     }
 
                     if is_multiline {
@@ -532,23 +1003,23 @@ fn type_annotation_to_html(
                 buf.push(']');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, links);
         }
         TypeAnnotation::BoundVariable(var_name) => {
             buf.push_str(var_name);
         }
         TypeAnnotation::Apply { name, parts } => {
             if parts.is_empty() {
-                buf.push_str(name);
+                push_type_name(buf, name, links);
             } else {
                 if needs_parens {
                     buf.push('(');
                 }
 
-                buf.push_str(name);
+                push_type_name(buf, name, links);
                 for part in parts {
                     buf.push(' ');
-                    type_annotation_to_html(indent_level, buf, part, true);
+                    type_annotation_to_html(indent_level, buf, part, true, links);
                 }
 
                 if needs_parens {
@@ -596,13 +1067,25 @@ fn type_annotation_to_html(
                             type_annotation, ..
                         } => {
                             buf.push_str(" : ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                links,
+                            );
                         }
                         RecordField::OptionalField {
                             type_annotation, ..
                         } => {
                             buf.push_str(" ? ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                links,
+                            );
                         }
                         RecordField::LabelOnly { .. } => {}
                     }
@@ -625,7 +1108,7 @@ fn type_annotation_to_html(
                 buf.push('}');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, links);
         }
         TypeAnnotation::Function { args, output } => {
             let mut paren_is_open = false;
@@ -644,7 +1127,7 @@ fn type_annotation_to_html(
                 }
 
                 let child_needs_parens = matches!(arg, TypeAnnotation::Function { .. });
-                type_annotation_to_html(indent_level, buf, arg, child_needs_parens);
+                type_annotation_to_html(indent_level, buf, arg, child_needs_parens, links);
 
                 if peekable_args.peek().is_some() {
                     buf.push_str(", ");
@@ -666,7 +1149,7 @@ fn type_annotation_to_html(
                 next_indent_level += 1;
             }
 
-            type_annotation_to_html(next_indent_level, buf, output, false);
+            type_annotation_to_html(next_indent_level, buf, output, false, links);
             if needs_parens && paren_is_open {
                 buf.push(')');
             }
@@ -690,7 +1173,13 @@ fn type_annotation_to_html(
                 buf.push_str(&member.name);
                 buf.push_str(" : ");
 
-                type_annotation_to_html(indent_level + 1, buf, &member.type_annotation, false);
+                type_annotation_to_html(
+                    indent_level + 1,
+                    buf,
+                    &member.type_annotation,
+                    false,
+                    links,
+                );
 
                 if !member.able_variables.is_empty() {
                     new_line(buf);
@@ -714,7 +1203,7 @@ fn type_annotation_to_html(
 
                             buf.push(' ');
 
-                            type_annotation_to_html(indent_level + 2, buf, ann, false);
+                            type_annotation_to_html(indent_level + 2, buf, ann, false, links);
                         }
                     }
                 }
@@ -750,7 +1239,7 @@ fn type_annotation_to_html(
                     indent(buf, next_indent_level);
                 }
 
-                type_annotation_to_html(next_indent_level, buf, elem, false);
+                type_annotation_to_html(next_indent_level, buf, elem, false, links);
 
                 if is_multiline {
                     if index < (elems_len - 1) {
@@ -767,10 +1256,10 @@ fn type_annotation_to_html(
 
             buf.push(')');
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, links);
         }
         TypeAnnotation::Where { ann, implements } => {
-            type_annotation_to_html(indent_level, buf, ann, false);
+            type_annotation_to_html(indent_level, buf, ann, false, links);
 
             new_line(buf);
             indent(buf, indent_level + 1);
@@ -803,12 +1292,12 @@ fn type_annotation_to_html(
                         buf.push_str(" & ");
                     }
 
-                    type_annotation_to_html(indent_level, buf, ability, false);
+                    type_annotation_to_html(indent_level, buf, ability, false, links);
                 }
             }
         }
         TypeAnnotation::As { ann, name, vars } => {
-            type_annotation_to_html(indent_level, buf, ann, true);
+            type_annotation_to_html(indent_level, buf, ann, true, links);
             buf.push(' ');
             buf.push_str(name);
 
@@ -876,6 +1365,241 @@ struct DocUrl {
     title: String,
 }
 
+/// What's needed to link the type names in a signature to the docs for those types.
+struct TypeLinks<'a> {
+    all_exposed_symbols: &'a VecSet<Symbol>,
+    /// Where the docs for modules from other packages are, see [dependency_docs_urls]
+    dependency_urls: &'a MutMap<ModuleId, String>,
+    scope: &'a Scope,
+    interns: &'a Interns,
+}
+
+impl<'a> TypeLinks<'a> {
+    /// Unlike [doc_url], this returns None rather than panicking for names that can't be
+    /// linked, e.g. types from a package that doesn't say where its docs are.
+    fn url(&self, name: &str) -> Option<DocUrl> {
+        let symbol = self.resolve(name)?;
+        let module_id = symbol.module_id();
+
+        let mut url = if self.all_exposed_symbols.contains(&symbol) {
+            base_url()
+        } else if module_id.is_builtin() {
+            BUILTINS_DOCS_URL.to_string()
+        } else {
+            self.dependency_urls.get(&module_id)?.clone()
+        };
+
+        let module_name = symbol.module_string(self.interns).as_str();
+        let ident = symbol.as_str(self.interns);
+
+        url.push_str(module_name);
+        url.push('#');
+        url.push_str(ident);
+
+        Some(DocUrl {
+            url,
+            title: format!("Docs for {module_name}.{ident}"),
+        })
+    }
+
+    /// The symbol a type name refers to, as written in the module, e.g. `Str` or `Json.Json`
+    fn resolve(&self, name: &str) -> Option<Symbol> {
+        match name.rsplit_once('.') {
+            None => self.scope.lookup_str(name, Region::zero()).ok(),
+            Some((module_name, ident)) => {
+                let module_id = self.interns.module_ids.get_id(&module_name.into())?;
+                let ident_id = self.interns.all_ident_ids.get(&module_id)?.get_id(ident)?;
+
+                Some(Symbol::new(module_id, ident_id))
+            }
+        }
+    }
+}
+
+/// The base URL of the docs for each module that comes from another package, e.g. a platform
+/// or a dependency in `packages`. It's the `url` from the `docs` section of that package's
+/// header, and modules of packages without one aren't included.
+fn dependency_docs_urls(loaded_module: &LoadedModule) -> MutMap<ModuleId, String> {
+    let root_dir = loaded_module
+        .sources
+        .get(&loaded_module.module_id)
+        .and_then(|(path, _)| path.parent());
+    let mut urls_by_dir: MutMap<&Path, Option<String>> = MutMap::default();
+    let mut urls = MutMap::default();
+
+    for (module_id, (path, _)) in loaded_module.sources.iter() {
+        if loaded_module.docs_by_module.contains_key(module_id) {
+            continue;
+        }
+
+        let module_name = match loaded_module.interns.module_ids.get_name(*module_id) {
+            Some(module_name) => module_name.as_str(),
+            None => continue,
+        };
+
+        // A module named `Foo.Bar` is at `Foo/Bar.roc` in its package's directory
+        let package_dir = match path.ancestors().nth(1 + module_name.matches('.').count()) {
+            Some(dir) if Some(dir) != root_dir => dir,
+            _ => continue,
+        };
+
+        let url = urls_by_dir.entry(package_dir).or_insert_with(|| {
+            let root_src = fs::read_to_string(package_dir.join(PACKAGE_ROOT_FILENAME)).ok()?;
+            let mut url = SiteOptions::from_header(&root_src).url?;
+
+            if !url.ends_with('/') {
+                url.push('/');
+            }
+
+            Some(url)
+        });
+
+        if let Some(url) = url {
+            urls.insert(*module_id, url.clone());
+        }
+    }
+
+    urls
+}
+
+/// Types that a package's exposed definitions mention, but that its users can't refer to,
+/// because neither the package nor the types' own modules expose them. Returns the qualified
+/// name of each such definition paired with the type's name as written,
+/// e.g. `("Parser.parse", "State")`.
+pub fn unexposed_types_in_signatures(loaded_module: &LoadedModule) -> Vec<(String, String)> {
+    let all_exposed_symbols = all_exposed_symbols(loaded_module);
+    let dependency_urls = MutMap::default();
+    let mut found = Vec::new();
+
+    for module in loaded_module.docs_by_module.values() {
+        let links = TypeLinks {
+            all_exposed_symbols: &all_exposed_symbols,
+            dependency_urls: &dependency_urls,
+            scope: &module.scope,
+            interns: &loaded_module.interns,
+        };
+
+        for entry in &module.entries {
+            let DocEntry::DocDef(doc_def) = entry else {
+                continue;
+            };
+
+            let mut type_names = Vec::new();
+
+            push_type_names(&doc_def.type_annotation, &mut type_names);
+
+            for type_name in type_names {
+                let is_unexposed = match links.resolve(type_name) {
+                    Some(symbol) => {
+                        !symbol.module_id().is_builtin() && !all_exposed_symbols.contains(&symbol)
+                    }
+                    None => false,
+                };
+
+                let pair = (
+                    format!("{}.{}", module.name, doc_def.name),
+                    type_name.to_string(),
+                );
+
+                if is_unexposed && !found.contains(&pair) {
+                    found.push(pair);
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// The names of the types an annotation applies, e.g. `List` and `Str` in `List Str -> Str`
+fn push_type_names<'a>(type_ann: &'a TypeAnnotation, names: &mut Vec<&'a str>) {
+    match type_ann {
+        TypeAnnotation::Apply { name, parts } => {
+            names.push(name.as_str());
+
+            for part in parts {
+                push_type_names(part, names);
+            }
+        }
+        TypeAnnotation::TagUnion { tags, extension } => {
+            for tag in tags {
+                for value in &tag.values {
+                    push_type_names(value, names);
+                }
+            }
+
+            push_type_names(extension, names);
+        }
+        TypeAnnotation::Function { args, output } => {
+            for arg in args {
+                push_type_names(arg, names);
+            }
+
+            push_type_names(output, names);
+        }
+        TypeAnnotation::Record { fields, extension } => {
+            for field in fields {
+                match field {
+                    RecordField::RecordField {
+                        type_annotation, ..
+                    }
+                    | RecordField::OptionalField {
+                        type_annotation, ..
+                    } => push_type_names(type_annotation, names),
+                    RecordField::LabelOnly { .. } => {}
+                }
+            }
+
+            push_type_names(extension, names);
+        }
+        TypeAnnotation::Tuple { elems, extension } => {
+            for elem in elems {
+                push_type_names(elem, names);
+            }
+
+            push_type_names(extension, names);
+        }
+        TypeAnnotation::Ability { members } => {
+            for member in members {
+                push_type_names(&member.type_annotation, names);
+            }
+        }
+        TypeAnnotation::Where { ann, implements } => {
+            push_type_names(ann, names);
+
+            for clause in implements {
+                for ability in &clause.abilities {
+                    push_type_names(ability, names);
+                }
+            }
+        }
+        TypeAnnotation::As { ann, .. } => push_type_names(ann, names),
+        TypeAnnotation::ObscuredTagUnion
+        | TypeAnnotation::ObscuredRecord
+        | TypeAnnotation::BoundVariable(_)
+        | TypeAnnotation::Wildcard
+        | TypeAnnotation::NoTypeAnn => {}
+    }
+}
+
+fn push_type_name(buf: &mut String, name: &str, links: Option<&TypeLinks>) {
+    match links.and_then(|links| links.url(name)) {
+        Some(DocUrl { url, title }) => {
+            push_html(
+                buf,
+                "a",
+                vec![
+                    ("class", "type-name-link"),
+                    ("href", escape_html(&url).as_str()),
+                    ("title", title.as_str()),
+                ],
+                name,
+            );
+        }
+        None => buf.push_str(name),
+    }
+}
+
 fn doc_url<'a>(
     all_exposed_symbols: &VecSet<Symbol>,
     scope: &Scope,
@@ -1055,27 +1779,30 @@ fn markdown_to_html(
             Event::Start(CodeBlock(CodeBlockKind::Fenced(code_str))) => {
                 in_code_block = Some(code_str);
             }
+            Event::Start(CodeBlock(CodeBlockKind::Indented)) => {
+                // Indented code blocks have no info string, so they're Roc code
+                in_code_block = Some(CowStr::Borrowed(""));
+            }
             Event::End(CodeBlock(_)) => {
-                match in_code_block {
-                    Some(code_str) => {
-                        if code_str.contains("unchecked") {
-                            // TODO HANDLE UNCHECKED
-                        }
+                if let Some(code_str) = in_code_block {
+                    if code_str.contains("unchecked") {
+                        // TODO HANDLE UNCHECKED
+                    }
 
-                        if code_str.contains("repl") {
-                            // TODO HANDLE REPL
-                        }
+                    if code_str.contains("repl") {
+                        // TODO HANDLE REPL
+                    }
 
+                    // Only highlight ```roc blocks (and blocks with no language), so that e.g.
+                    // a ```sh block isn't highlighted as if it were Roc code.
+                    let html = if is_roc_code_block(&code_str) {
                         // TODO HANDLE CHECKING BY DEFAULT
-                        let highlighted_html = roc_highlight::highlight_roc_code(&to_highlight);
-                        docs_parser.push(Event::Html(CowStr::from(highlighted_html)));
-                    }
-                    None => {
-                        // Indented code block
+                        roc_highlight::highlight_roc_code(&to_highlight)
+                    } else {
+                        format!("<pre><samp>{}</samp></pre>", escape_html(&to_highlight))
+                    };
 
-                        let highlighted_html = roc_highlight::highlight_roc_code(&to_highlight);
-                        docs_parser.push(Event::Html(CowStr::from(highlighted_html)));
-                    }
+                    docs_parser.push(Event::Html(CowStr::from(html)));
                 }
 
                 // Reset codeblock buffer
@@ -1107,3 +1834,29 @@ fn markdown_to_html(
 
     pulldown_cmark::html::push_html(buf, docs_parser.into_iter());
 }
+
+/// The first word of a fenced code block's info string is its language, e.g. `roc` in
+/// ```` ```roc repl ````. Roc code blocks may leave the language out, or start with
+/// one of our own `repl` or `unchecked` flags instead.
+fn is_roc_code_block(info_string: &str) -> bool {
+    matches!(
+        info_string.split_whitespace().next(),
+        None | Some("roc" | "repl" | "unchecked")
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
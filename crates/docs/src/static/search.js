@@ -1,11 +1,150 @@
 (() => {
   let sidebar = document.getElementById("sidebar-nav");
   let searchBox = document.getElementById("module-search");
+  let searchResults = document.getElementById("search-results");
+
+  // Every exposed definition, as written by roc_docs. Until it loads (or if it can't be fetched,
+  // e.g. when the docs are opened straight from the file system) search only filters the sidebar.
+  let searchIndex = [];
+
+  const MAX_SEARCH_RESULTS = 30;
+
+  fetch("search-index.json")
+    .then((response) => response.json())
+    .then((index) => {
+      searchIndex = index;
+      search();
+    })
+    .catch(() => {});
+
+  // Split a type on the commas and arrows that aren't nested in brackets, e.g.
+  // "List a, (a -> Bool) -> List a" becomes ["List a", "(a -> Bool)"] and "List a".
+  function splitFunctionType(type) {
+    let depth = 0;
+    let parts = [];
+    let start = 0;
+    let arrow = -1;
+
+    for (let i = 0; i < type.length; i++) {
+      let char = type[i];
+
+      if (char === "(" || char === "[" || char === "{") {
+        depth++;
+      } else if (char === ")" || char === "]" || char === "}") {
+        depth--;
+      } else if (depth === 0 && char === "," && arrow === -1) {
+        parts.push(type.slice(start, i));
+        start = i + 1;
+      } else if (depth === 0 && type.startsWith("->", i) && arrow === -1) {
+        parts.push(type.slice(start, i));
+        arrow = i;
+      }
+    }
+
+    if (arrow === -1) {
+      return null;
+    }
+
+    return { args: parts, ret: type.slice(arrow + 2) };
+  }
+
+  // Type variables (including *) are all treated alike, so that a search doesn't depend on
+  // what they're named.
+  function normalizeType(type) {
+    return type
+      .replace(/\b[a-z][a-zA-Z0-9_]*\b|\*/g, "_")
+      .replace(/\s+/g, "");
+  }
+
+  // Whether a function type matches a query like "List a, (a -> Bool) -> List a",
+  // ignoring the names of type variables and the order of the arguments.
+  function typeMatches(type, query) {
+    let candidate = splitFunctionType(type);
+
+    if (candidate === null || normalizeType(candidate.ret) !== normalizeType(query.ret)) {
+      return false;
+    }
+
+    let candidateArgs = candidate.args.map(normalizeType).sort();
+    let queryArgs = query.args.map(normalizeType).sort();
+
+    return candidateArgs.length === queryArgs.length
+      && candidateArgs.every((arg, index) => arg === queryArgs[index]);
+  }
+
+  function renderSearchResults(text, rawText) {
+    // A query with an arrow in it is a type, like "Str, Str -> Str".
+    let typeQuery = rawText.includes("->") ? splitFunctionType(rawText) : null;
+
+    // Matches on the name come first, then matches in the type, then in the docs.
+    let ranked = [];
+
+    searchIndex.forEach((entry) => {
+      let rank;
+
+      if (typeQuery !== null) {
+        if (!typeMatches(entry.type, typeQuery)) {
+          return;
+        }
+
+        rank = 0;
+      } else if (entry.name.toLowerCase().includes(text)) {
+        rank = 0;
+      } else if (entry.type.toLowerCase().includes(text)) {
+        rank = 1;
+      } else if (entry.docs.toLowerCase().includes(text)) {
+        rank = 2;
+      } else {
+        return;
+      }
+
+      ranked.push({ rank, entry });
+    });
+
+    ranked.sort((a, b) => a.rank - b.rank);
+
+    let links = ranked.slice(0, MAX_SEARCH_RESULTS).map(({ entry }) => {
+      let link = document.createElement("a");
+      link.classList.add("search-result");
+      link.href = entry.href;
+
+      let name = document.createElement("span");
+      name.classList.add("search-result-name");
+      name.textContent = entry.name;
+      link.appendChild(name);
+
+      if (entry.type !== "") {
+        let type = document.createElement("code");
+        type.classList.add("search-result-type");
+        type.textContent = entry.type;
+        link.appendChild(type);
+      }
+
+      if (entry.docs !== "") {
+        let docs = document.createElement("span");
+        docs.classList.add("search-result-docs");
+        docs.textContent = entry.docs;
+        link.appendChild(docs);
+      }
+
+      return link;
+    });
+
+    searchResults.replaceChildren(...links);
+
+    if (links.length === 0) {
+      searchResults.classList.add("hidden");
+    } else {
+      searchResults.classList.remove("hidden");
+    }
+  }
 
   function search() {
     let text = searchBox.value.toLowerCase(); // Search is case-insensitive.
 
     if (text === "") {
+      searchResults.classList.add("hidden");
+
       // Un-hide everything
       sidebar.querySelectorAll(".sidebar-entry a").forEach((entry) => entry.classList.remove("hidden"));
 
@@ -21,6 +160,8 @@
         entry.querySelectorAll(".sidebar-sub-entries a").forEach((subEntry) => subEntry.classList.add("hidden"));
       })
     } else {
+      renderSearchResults(text, searchBox.value);
+
       // First, show/hide all the sub-entries within each module (top-level functions etc.)
       sidebar.querySelectorAll(".sidebar-sub-entries a").forEach((entry) => {
         if (entry.textContent.toLowerCase().includes(text)) {
@@ -65,6 +206,25 @@
     }
   });
 
+  // Switch between the light and dark themes, remembering the choice for the next page.
+  // Until the reader picks one, the theme follows their system's preference.
+  let themeToggle = document.getElementById("theme-toggle");
+
+  themeToggle.addEventListener("click", () => {
+    let root = document.documentElement;
+    let isDark =
+      root.dataset.theme === "dark" ||
+      (root.dataset.theme !== "light" &&
+        window.matchMedia("(prefers-color-scheme: dark)").matches);
+    let theme = isDark ? "light" : "dark";
+
+    root.dataset.theme = theme;
+
+    try {
+      localStorage.setItem("theme", theme);
+    } catch (e) {}
+  });
+
   const isTouchSupported = () => {
     try{ document.createEvent("TouchEvent"); return true; }
     catch(e){ return false; }
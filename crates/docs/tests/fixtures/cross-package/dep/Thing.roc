@@ -0,0 +1,8 @@
+interface Thing
+    exposes [Thing, make]
+    imports []
+
+Thing := U8
+
+make : U8 -> Thing
+make = \n -> @Thing n
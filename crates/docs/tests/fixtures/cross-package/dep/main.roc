@@ -0,0 +1,4 @@
+package "dep"
+    exposes [Thing]
+    packages {}
+    docs { url: "https://example.com/dep" }
@@ -0,0 +1,9 @@
+interface Wrapper
+    exposes [Wrapped, wrap]
+    imports [dep.Thing.{ Thing }]
+
+## A [Thing] with a label
+Wrapped := { thing : Thing, label : Str }
+
+wrap : Thing, Str -> Wrapped
+wrap = \thing, label -> @Wrapped { thing, label }
@@ -0,0 +1,3 @@
+package "pkg"
+    exposes [Wrapper]
+    packages { dep: "../dep/main.roc" }
@@ -0,0 +1,27 @@
+interface Shape
+    exposes [Shape, area, circle]
+    imports []
+
+## A shape you can "measure".
+##
+## This paragraph isn't part of the summary.
+Shape : [Circle F64, Square F64]
+
+## A circle with the given radius
+circle : F64 -> Shape
+circle = \radius -> Circle radius
+
+## How much space a shape takes up,
+## in square units
+area : Shape -> F64
+area = \shape ->
+    when shape is
+        Circle radius -> Num.pi * radius * radius
+        Square side -> side * side
+
+## Not exposed, so it isn't documented
+perimeter : Shape -> F64
+perimeter = \shape ->
+    when shape is
+        Circle radius -> 2 * Num.pi * radius
+        Square side -> 4 * side
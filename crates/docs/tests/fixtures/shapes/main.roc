@@ -0,0 +1,3 @@
+package "shapes"
+    exposes [Shape]
+    packages {}
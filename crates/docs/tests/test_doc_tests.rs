@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod test_doc_tests {
+    use pretty_assertions::assert_eq;
+    use roc_docs::doc_tests::{doc_examples, with_doc_examples, DocExample};
+
+    #[test]
+    fn one_expect_per_comparison() {
+        let src = concat!(
+            "interface Foo exposes [one] imports []\n",
+            "\n",
+            "## Always one.\n",
+            "##\n",
+            "## ```\n",
+            "## one == 1\n",
+            "## one + one\n",
+            "##     == 2\n",
+            "## ```\n",
+            "one = 1\n",
+        );
+
+        assert_eq!(
+            doc_examples(src),
+            vec![DocExample {
+                line: 5,
+                expects: concat!(
+                    "expect\n",
+                    "    one == 1\n",
+                    "expect\n",
+                    "    one + one\n",
+                    "        == 2\n",
+                )
+                .to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn definitions_make_one_expect() {
+        let src = concat!(
+            "    ## ```roc\n",
+            "    ## x = 2\n",
+            "    ##\n",
+            "    ## x * 2 == 4\n",
+            "    ## ```\n",
+        );
+
+        assert_eq!(
+            doc_examples(src),
+            vec![DocExample {
+                line: 1,
+                expects: "expect\n    x = 2\n\n    x * 2 == 4\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn expects_stay_as_they_are() {
+        let src = "## ```\n## expect Bool.true == Bool.true\n## ```\n";
+
+        assert_eq!(
+            doc_examples(src),
+            vec![DocExample {
+                line: 1,
+                expects: "expect Bool.true == Bool.true\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn blocks_that_are_not_examples() {
+        let src = concat!(
+            "## ```\n",
+            "## x = 1\n",
+            "## ```\n",
+            "## ```sh\n",
+            "## test 1 == 1\n",
+            "## ```\n",
+            "## ```roc unchecked\n",
+            "## 1 == 2\n",
+            "## ```\n",
+            "## ```roc repl\n",
+            "## » 1 == 1\n",
+            "## ```\n",
+        );
+
+        assert_eq!(doc_examples(src), Vec::new());
+    }
+
+    #[test]
+    fn examples_go_at_the_end() {
+        let src = "interface Foo exposes [] imports []";
+        let examples = [DocExample {
+            line: 3,
+            expects: "expect\n    1 == 1\n".to_string(),
+        }];
+
+        assert_eq!(
+            with_doc_examples(src, &examples),
+            concat!(
+                "interface Foo exposes [] imports []\n",
+                "\n",
+                "# The example on line 3 of this module\n",
+                "expect\n",
+                "    1 == 1\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod test_docs_formats {
+    use pretty_assertions::assert_eq;
+    use roc_docs::{load_module_for_docs, write_docs_json, write_docs_markdown};
+    use roc_load::LoadedModule;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+
+    fn load_shapes() -> LoadedModule {
+        let root_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("shapes")
+            .join("main.roc");
+
+        load_module_for_docs(root_file)
+    }
+
+    #[test]
+    fn markdown_file_per_module() {
+        let build_dir = tempfile::tempdir().unwrap();
+
+        write_docs_markdown(&load_shapes(), build_dir.path());
+
+        let markdown = std::fs::read_to_string(build_dir.path().join("Shape.md")).unwrap();
+
+        assert!(
+            markdown.starts_with("# Shape\n\n## Shape\n\n```roc\nShape :"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains(concat!(
+                "## circle\n\n",
+                "```roc\n",
+                "circle : F64 -> Shape\n",
+                "```\n\n",
+                "A circle with the given radius\n\n",
+            )),
+            "{markdown}"
+        );
+        assert!(
+            markdown.ends_with(concat!(
+                "## area\n\n",
+                "```roc\n",
+                "area : Shape -> F64\n",
+                "```\n\n",
+                "How much space a shape takes up,\n",
+                "in square units\n",
+            )),
+            "{markdown}"
+        );
+        assert!(!markdown.contains("perimeter"), "{markdown}");
+    }
+
+    #[test]
+    fn json_model_of_the_api() {
+        let build_dir = tempfile::tempdir().unwrap();
+
+        write_docs_json(&load_shapes(), build_dir.path());
+
+        let docs = std::fs::read_to_string(build_dir.path().join("docs.json")).unwrap();
+        let docs: Value = serde_json::from_str(&docs).unwrap();
+        let modules = docs["modules"].as_array().unwrap();
+
+        assert_eq!(modules.len(), 1);
+        assert_eq!(modules[0]["name"], "Shape");
+
+        let entries = modules[0]["entries"].as_array().unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["kind"], "def");
+        assert_eq!(entries[0]["name"], "Shape");
+        assert_eq!(
+            entries[0]["docs"],
+            "A shape you can \"measure\".\n\nThis paragraph isn't part of the summary."
+        );
+        assert_eq!(
+            entries[1],
+            json!({
+                "kind": "def",
+                "name": "circle",
+                "type_vars": [],
+                "type": "F64 -> Shape",
+                "signature": "circle : F64 -> Shape",
+                "docs": "A circle with the given radius",
+            })
+        );
+        assert_eq!(entries[2]["name"], "area");
+    }
+}
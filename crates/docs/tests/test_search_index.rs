@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod test_search_index {
+    use pretty_assertions::assert_eq;
+    use roc_docs::{load_module_for_docs, write_docs_html};
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+
+    #[test]
+    fn exposed_definitions_with_their_types_and_summaries() {
+        let root_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("shapes")
+            .join("main.roc");
+        let loaded_module = load_module_for_docs(root_file);
+        let build_dir = tempfile::tempdir().unwrap();
+
+        write_docs_html(&loaded_module, build_dir.path());
+
+        let index = std::fs::read_to_string(build_dir.path().join("search-index.json")).unwrap();
+        let index: Value = serde_json::from_str(&index).unwrap();
+
+        assert_eq!(
+            index,
+            json!([
+                {
+                    "name": "Shape.Shape",
+                    "href": "Shape#Shape",
+                    "type": "[ Circle F64, Square F64 ]",
+                    "docs": "A shape you can \"measure\".",
+                },
+                {
+                    "name": "Shape.circle",
+                    "href": "Shape#circle",
+                    "type": "F64 -> Shape",
+                    "docs": "A circle with the given radius",
+                },
+                {
+                    "name": "Shape.area",
+                    "href": "Shape#area",
+                    "type": "Shape -> F64",
+                    "docs": "How much space a shape takes up, in square units",
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod test_site_options {
+    use pretty_assertions::assert_eq;
+    use roc_docs::SiteOptions;
+
+    #[test]
+    fn settings_and_links_from_the_package_header() {
+        let src = concat!(
+            "package \"json\"\n",
+            "    exposes [Json]\n",
+            "    packages {}\n",
+            "    docs {\n",
+            "        logo: \"https://example.com/logo.svg\",\n",
+            "        accentColor: \"#1e7b5c\",\n",
+            "        url: \"https://example.com/json/\",\n",
+            "        \"Source code\": \"https://github.com/me/json\",\n",
+            "    }\n",
+        );
+
+        assert_eq!(
+            SiteOptions::from_header(src),
+            SiteOptions {
+                logo_url: Some("https://example.com/logo.svg".to_string()),
+                accent_color: Some("#1e7b5c".to_string()),
+                header_links: vec![(
+                    "Source code".to_string(),
+                    "https://github.com/me/json".to_string()
+                )],
+                url: Some("https://example.com/json/".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn no_docs_section() {
+        let src = "package \"json\" exposes [Json] packages {}";
+
+        assert_eq!(SiteOptions::from_header(src), SiteOptions::default());
+    }
+
+    #[test]
+    fn not_a_package() {
+        let src = "interface Json exposes [] imports []";
+
+        assert_eq!(SiteOptions::from_header(src), SiteOptions::default());
+    }
+}
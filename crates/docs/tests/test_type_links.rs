@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod test_type_links {
+    use roc_docs::{load_module_for_docs, write_docs_html};
+    use std::path::PathBuf;
+
+    fn fixture_root(package: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("cross-package")
+            .join(package)
+            .join("main.roc")
+    }
+
+    #[test]
+    fn links_to_other_packages_docs() {
+        let loaded_module = load_module_for_docs(fixture_root("pkg"));
+        let build_dir = tempfile::tempdir().unwrap();
+
+        write_docs_html(&loaded_module, build_dir.path());
+
+        let html =
+            std::fs::read_to_string(build_dir.path().join("Wrapper").join("index.html")).unwrap();
+
+        // From this package
+        assert!(html.contains(r#"href="/Wrapper#Wrapped""#), "{html}");
+        // From a dependency whose header says where its docs are
+        assert!(
+            html.contains(r#"href="https://example.com/dep/Thing#Thing""#),
+            "{html}"
+        );
+        // From the builtins
+        assert!(
+            html.contains(r#"href="https://www.roc-lang.org/builtins/Str#Str""#),
+            "{html}"
+        );
+    }
+}
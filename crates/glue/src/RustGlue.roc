@@ -1535,8 +1535,21 @@ generateSingleTagStruct = \buf, types, name, tagName, payload ->
                 else
                     generateMultiElementSingleTagStruct b types escapedName tagName fields asStructFields
 
-        HasClosure _ ->
-            Str.concat buf "\\TODO: SingleTagStruct with closures"
+        HasClosure fields ->
+            # A closure's captured environment can't be built or inspected from Rust,
+            # so the payload is kept private: the host can only pass it back to Roc.
+            asStructFields =
+                List.mapWithIndex fields \{ id }, index ->
+                    indexStr = Num.toStr index
+
+                    { name: "f\(indexStr)", id }
+                |> HasNoClosure
+
+            buf
+            |> generateDeriveStr types (TagUnion (SingleTagStruct { name, tagName, payload })) ExcludeDebug
+            |> Str.concat "#[repr(\(repr))]\npub struct \(escapedName) {\n"
+            |> generateStructFields types Private asStructFields
+            |> Str.concat "}\n\n"
 
 generateMultiElementSingleTagStruct = \buf, types, name, tagName, payloadFields, asStructFields ->
     buf
@@ -0,0 +1,647 @@
+app "typescript-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [
+        pf.Types.{ Types },
+        pf.Shape.{ Shape },
+        pf.File.{ File },
+        pf.TypeId.{ TypeId },
+    ]
+    provides [makeGlue] to pf
+
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    wasmTypes =
+        List.findFirst typesByArch \types ->
+            when (Types.target types).architecture is
+                Wasm32 -> Bool.true
+                _ -> Bool.false
+
+    when wasmTypes is
+        Ok types ->
+            declarations = Types.walkShapes types "" \buf, shape, _id -> generateDeclaration buf types shape
+            entryPoints =
+                types
+                |> Types.entryPoints
+                |> List.walk "" \buf, T name id -> generateEntryPoint buf types name id
+
+            content =
+                fileHeader
+                |> Str.concat declarations
+                |> Str.concat "export class RocApp {\n"
+                |> Str.concat "\(indent)readonly memory: RocMemory;\n\n"
+                |> Str.concat "\(indent)constructor(readonly exports: RocExports) {\n"
+                |> Str.concat "\(indent)\(indent)this.memory = new RocMemory(exports);\n"
+                |> Str.concat "\(indent)}\n"
+                |> Str.concat entryPoints
+                |> Str.concat "}\n"
+
+            Ok [{ name: "roc_app.ts", content }]
+
+        Err NotFound ->
+            Err "TypeScript glue can only be generated for wasm32, but there were no wasm32 types to generate it from."
+
+## Named types get a TypeScript type, plus functions to read and write them
+## from linear memory. Everything else is read and written inline.
+generateDeclaration : Str, Types, Shape -> Str
+generateDeclaration = \buf, types, shape ->
+    when shape is
+        Struct { name, fields } ->
+            generateStruct buf types name (structFieldsToList fields)
+
+        TagUnionPayload { name, fields } ->
+            generateStruct buf types name (structFieldsToList fields)
+
+        TagUnion (Enumeration { name, tags, size }) ->
+            generateEnumeration buf name tags size
+
+        TagUnion (NonRecursive { name, tags, discriminantSize, discriminantOffset }) ->
+            if !(List.isEmpty tags) then
+                generateNonRecursiveTagUnion buf types name tags discriminantSize discriminantOffset
+            else
+                # If this tag union has no tags, it's uninhabited and can't be constructed.
+                buf
+
+        TagUnion (SingleTagStruct { name, payload }) ->
+            fields =
+                when payload is
+                    HasNoClosure payloadFields -> List.map payloadFields .id
+                    HasClosure payloadFields -> List.map payloadFields .id
+
+            generateSingleTagStruct buf types name fields
+
+        _ ->
+            buf
+
+structFieldsToList = \fields ->
+    when fields is
+        HasNoClosure list -> List.map list \{ name, id } -> { name, id }
+        HasClosure list -> List.map list \{ name, id } -> { name, id }
+
+generateStruct : Str, Types, Str, List { name : Str, id : TypeId } -> Str
+generateStruct = \buf, types, name, fields ->
+    escapedName = escapeKW name
+    offsets = fieldOffsets types (List.map fields .id)
+    withOffsets =
+        List.map2 fields offsets \{ name: fieldName, id }, offset -> { name: fieldName, id, offset }
+        |> List.dropIf \{ id } -> isUnit (Types.shape types id)
+
+    members =
+        List.walk withOffsets "" \state, { name: fieldName, id } ->
+            Str.concat state "\(indent)\(fieldName): \(tsType types id);\n"
+
+    reads =
+        List.walk withOffsets "" \state, { name: fieldName, id, offset } ->
+            Str.concat state "\(indent)\(indent)\(fieldName): \(readExpr types id (ptrPlus offset)),\n"
+
+    writes =
+        List.walk withOffsets "" \state, { name: fieldName, id, offset } ->
+            Str.concat state "\(indent)\(writeStmt types id (ptrPlus offset) "value.\(fieldName)")\n"
+
+    buf
+    |> Str.concat "export interface \(escapedName) {\n\(members)}\n\n"
+    |> Str.concat "function read_\(escapedName)(mem: RocMemory, ptr: number): \(escapedName) {\n"
+    |> Str.concat "\(indent)return {\n\(reads)\(indent)};\n}\n\n"
+    |> Str.concat "function write_\(escapedName)(mem: RocMemory, ptr: number, value: \(escapedName)): void {\n"
+    |> Str.concat "\(writes)}\n\n"
+
+## A single-tag union is exposed as a tuple of its payloads.
+generateSingleTagStruct : Str, Types, Str, List TypeId -> Str
+generateSingleTagStruct = \buf, types, name, fieldIds ->
+    escapedName = escapeKW name
+    offsets = fieldOffsets types fieldIds
+    withOffsets =
+        List.map2 fieldIds offsets \id, offset -> { id, offset }
+        |> List.dropIf \{ id } -> isUnit (Types.shape types id)
+
+    members =
+        withOffsets
+        |> List.map \{ id } -> tsType types id
+        |> Str.joinWith ", "
+
+    reads =
+        withOffsets
+        |> List.map \{ id, offset } -> readExpr types id (ptrPlus offset)
+        |> Str.joinWith ", "
+
+    writes =
+        List.walkWithIndex withOffsets "" \state, { id, offset }, index ->
+            Str.concat state "\(indent)\(writeStmt types id (ptrPlus offset) "value[\(Num.toStr index)]")\n"
+
+    buf
+    |> Str.concat "export type \(escapedName) = [\(members)];\n\n"
+    |> Str.concat "function read_\(escapedName)(mem: RocMemory, ptr: number): \(escapedName) {\n"
+    |> Str.concat "\(indent)return [\(reads)];\n}\n\n"
+    |> Str.concat "function write_\(escapedName)(mem: RocMemory, ptr: number, value: \(escapedName)): void {\n"
+    |> Str.concat "\(writes)}\n\n"
+
+## Tags without payloads are exposed as string literals. Their discriminant
+## is their index in the (alphabetically sorted) list of tags.
+generateEnumeration : Str, Str, List Str, U32 -> Str
+generateEnumeration = \buf, name, tags, size ->
+    escapedName = escapeKW name
+    tagStrs =
+        tags
+        |> List.map \tag -> "\"\(tag)\""
+        |> Str.joinWith ", "
+
+    buf
+    |> Str.concat "const \(escapedName)_tags = [\(tagStrs)] as const;\n"
+    |> Str.concat "export type \(escapedName) = typeof \(escapedName)_tags[number];\n\n"
+    |> Str.concat "function read_\(escapedName)(mem: RocMemory, ptr: number): \(escapedName) {\n"
+    |> Str.concat "\(indent)return \(escapedName)_tags[\(readUnsigned "ptr" size)];\n}\n\n"
+    |> Str.concat "function write_\(escapedName)(mem: RocMemory, ptr: number, value: \(escapedName)): void {\n"
+    |> Str.concat "\(indent)\(writeUnsigned "ptr" size "\(escapedName)_tags.indexOf(value)");\n}\n\n"
+
+generateNonRecursiveTagUnion : Str, Types, Str, List { name : Str, payload : [Some TypeId, None] }, U32, U32 -> Str
+generateNonRecursiveTagUnion = \buf, types, name, tags, discriminantSize, discriminantOffset ->
+    escapedName = escapeKW name
+    discriminantPtr = ptrPlus discriminantOffset
+
+    variants =
+        tags
+        |> List.map \{ name: tagName, payload } ->
+            when payload is
+                Some id if !(isUnit (Types.shape types id)) ->
+                    "{ tag: \"\(tagName)\"; payload: \(tsType types id) }"
+
+                _ ->
+                    "{ tag: \"\(tagName)\" }"
+        |> Str.joinWith " | "
+
+    reads =
+        List.walkWithIndex tags "" \state, { name: tagName, payload }, index ->
+            value =
+                when payload is
+                    Some id if !(isUnit (Types.shape types id)) ->
+                        "{ tag: \"\(tagName)\", payload: \(readExpr types id "ptr") }"
+
+                    _ ->
+                        "{ tag: \"\(tagName)\" }"
+
+            Str.concat state "\(indent)\(indent)case \(Num.toStr index):\n\(indent)\(indent)\(indent)return \(value);\n"
+
+    writes =
+        List.walkWithIndex tags "" \state, { name: tagName, payload }, index ->
+            writePayload =
+                when payload is
+                    Some id if !(isUnit (Types.shape types id)) ->
+                        "\(indent)\(indent)\(indent)\(writeStmt types id "ptr" "value.payload")\n"
+
+                    _ ->
+                        ""
+
+            state
+            |> Str.concat "\(indent)\(indent)case \"\(tagName)\":\n"
+            |> Str.concat writePayload
+            |> Str.concat "\(indent)\(indent)\(indent)\(writeUnsigned discriminantPtr discriminantSize (Num.toStr index));\n"
+            |> Str.concat "\(indent)\(indent)\(indent)break;\n"
+
+    buf
+    |> Str.concat "export type \(escapedName) = \(variants);\n\n"
+    |> Str.concat "function read_\(escapedName)(mem: RocMemory, ptr: number): \(escapedName) {\n"
+    |> Str.concat "\(indent)switch (\(readUnsigned discriminantPtr discriminantSize)) {\n"
+    |> Str.concat reads
+    |> Str.concat "\(indent)\(indent)default:\n"
+    |> Str.concat "\(indent)\(indent)\(indent)throw new Error(\"Invalid discriminant for \(escapedName)\");\n"
+    |> Str.concat "\(indent)}\n}\n\n"
+    |> Str.concat "function write_\(escapedName)(mem: RocMemory, ptr: number, value: \(escapedName)): void {\n"
+    |> Str.concat "\(indent)switch (value.tag) {\n"
+    |> Str.concat writes
+    |> Str.concat "\(indent)}\n}\n\n"
+
+## Entry points become methods on `RocApp`. Numbers, Bools, and enumerations are
+## passed to Roc directly; everything else is written to linear memory first,
+## and Roc takes ownership of it.
+generateEntryPoint : Str, Types, Str, TypeId -> Str
+generateEntryPoint = \buf, types, name, id ->
+    { args, ret } =
+        when Types.shape types id is
+            Function rocFn ->
+                nonUnitArgs = List.dropIf rocFn.args \argId -> isUnit (Types.shape types argId)
+
+                { args: nonUnitArgs, ret: rocFn.ret }
+
+            _ ->
+                { args: [], ret: id }
+
+    params =
+        args
+        |> List.mapWithIndex \argId, index -> "arg\(Num.toStr index): \(tsType types argId)"
+        |> Str.joinWith ", "
+
+    writeArgs =
+        List.walkWithIndex args "" \state, argId, index ->
+            if isPassedByValue (Types.shape types argId) then
+                state
+            else
+                indexStr = Num.toStr index
+                size = getSizeRoundedToAlignment types argId |> Num.toStr
+                align = Types.alignment types argId |> Num.toStr
+
+                state
+                |> Str.concat "\(indent)\(indent)const arg\(indexStr)Ptr = mem.alloc(\(size), \(align));\n"
+                |> Str.concat "\(indent)\(indent)\(writeStmt types argId "arg\(indexStr)Ptr" "arg\(indexStr)")\n"
+
+    callArgs =
+        args
+        |> List.mapWithIndex \argId, index ->
+            arg = "arg\(Num.toStr index)"
+
+            when Types.shape types argId is
+                Bool -> "\(arg) ? 1 : 0"
+                TagUnion (Enumeration { name: enumName }) -> "\(escapeKW enumName)_tags.indexOf(\(arg))"
+                shape if isPassedByValue shape -> arg
+                _ -> "\(arg)Ptr"
+        |> List.prepend "ret"
+        |> Str.joinWith ", "
+
+    freeArgs =
+        List.walkWithIndex args "" \state, argId, index ->
+            if isPassedByValue (Types.shape types argId) then
+                state
+            else
+                align = Types.alignment types argId |> Num.toStr
+
+                Str.concat state "\(indent)\(indent)mem.free(arg\(Num.toStr index)Ptr, \(align));\n"
+
+    retSize = getSizeRoundedToAlignment types ret |> Num.toStr
+    retAlign = Types.alignment types ret |> Num.toStr
+
+    buf
+    |> Str.concat "\n\(indent)\(name)(\(params)): \(tsType types ret) {\n"
+    |> Str.concat "\(indent)\(indent)const mem = this.memory;\n"
+    |> Str.concat "\(indent)\(indent)const ret = mem.alloc(\(retSize), \(retAlign));\n"
+    |> Str.concat writeArgs
+    |> Str.concat "\(indent)\(indent)this.exports.roc__\(name)_1_exposed_generic(\(callArgs));\n\n"
+    |> Str.concat "\(indent)\(indent)const result = \(readExpr types ret "ret");\n\n"
+    |> Str.concat "\(indent)\(indent)mem.free(ret, \(retAlign));\n"
+    |> Str.concat freeArgs
+    |> Str.concat "\n\(indent)\(indent)return result;\n"
+    |> Str.concat "\(indent)}\n"
+
+isPassedByValue : Shape -> Bool
+isPassedByValue = \shape ->
+    when shape is
+        Bool | TagUnion (Enumeration _) -> Bool.true
+        Num I128 | Num U128 | Num Dec -> Bool.false
+        Num _ -> Bool.true
+        _ -> Bool.false
+
+tsType : Types, TypeId -> Str
+tsType = \types, id ->
+    when Types.shape types id is
+        Unit | EmptyTagUnion -> "undefined"
+        RocStr -> "string"
+        Bool -> "boolean"
+        Num I64 | Num U64 | Num I128 | Num U128 | Num Dec -> "bigint"
+        Num _ -> "number"
+        RocList elemId -> "\(tsType types elemId)[]"
+        RocResult okId errId ->
+            okMember = if isUnit (Types.shape types okId) then "" else "; value: \(tsType types okId)"
+            errMember = if isUnit (Types.shape types errId) then "" else "; error: \(tsType types errId)"
+
+            "{ ok: true\(okMember) } | { ok: false\(errMember) }"
+
+        Struct { name } -> escapeKW name
+        TagUnionPayload { name } -> escapeKW name
+        TagUnion (Enumeration { name }) -> escapeKW name
+        TagUnion (NonRecursive { name }) -> escapeKW name
+        TagUnion (SingleTagStruct { name }) -> escapeKW name
+        # Anything else is referred to by its address in linear memory.
+        _ -> "number"
+
+## A TypeScript expression which reads a value of the given type at `ptr`.
+readExpr : Types, TypeId, Str -> Str
+readExpr = \types, id, ptr ->
+    when Types.shape types id is
+        Unit | EmptyTagUnion -> "undefined"
+        RocStr -> "mem.readStr(\(ptr))"
+        Bool -> "mem.view.getUint8(\(ptr)) !== 0"
+        Num I8 -> "mem.view.getInt8(\(ptr))"
+        Num U8 -> "mem.view.getUint8(\(ptr))"
+        Num I16 -> "mem.view.getInt16(\(ptr), true)"
+        Num U16 -> "mem.view.getUint16(\(ptr), true)"
+        Num I32 -> "mem.view.getInt32(\(ptr), true)"
+        Num U32 -> "mem.view.getUint32(\(ptr), true)"
+        Num I64 -> "mem.view.getBigInt64(\(ptr), true)"
+        Num U64 -> "mem.view.getBigUint64(\(ptr), true)"
+        Num I128 | Num Dec -> "mem.readI128(\(ptr))"
+        Num U128 -> "mem.readU128(\(ptr))"
+        Num F32 -> "mem.view.getFloat32(\(ptr), true)"
+        Num F64 -> "mem.view.getFloat64(\(ptr), true)"
+        RocList elemId ->
+            elemSize = getSizeRoundedToAlignment types elemId |> Num.toStr
+
+            "mem.readList(\(ptr), \(elemSize), (ptr) => \(readExpr types elemId "ptr"))"
+
+        RocResult okId errId ->
+            okValue = if isUnit (Types.shape types okId) then "" else ", value: \(readExpr types okId ptr)"
+            errValue = if isUnit (Types.shape types errId) then "" else ", error: \(readExpr types errId ptr)"
+            tagPtr = "\(ptr) + \(Num.toStr (resultDiscriminantOffset types okId errId))"
+
+            "(mem.view.getUint8(\(tagPtr)) !== 0 ? { ok: true as const\(okValue) } : { ok: false as const\(errValue) })"
+
+        Struct { name } -> "read_\(escapeKW name)(mem, \(ptr))"
+        TagUnionPayload { name } -> "read_\(escapeKW name)(mem, \(ptr))"
+        TagUnion (Enumeration { name }) -> "read_\(escapeKW name)(mem, \(ptr))"
+        TagUnion (NonRecursive { name }) -> "read_\(escapeKW name)(mem, \(ptr))"
+        TagUnion (SingleTagStruct { name }) -> "read_\(escapeKW name)(mem, \(ptr))"
+        _ ->
+            size = getSizeRoundedToAlignment types id |> Num.toStr
+            align = Types.alignment types id |> Num.toStr
+
+            "mem.copy(\(ptr), \(size), \(align))"
+
+## A TypeScript statement which writes `value` (a value of the given type) to `ptr`.
+writeStmt : Types, TypeId, Str, Str -> Str
+writeStmt = \types, id, ptr, value ->
+    when Types.shape types id is
+        Unit | EmptyTagUnion -> ""
+        RocStr -> "mem.writeStr(\(ptr), \(value));"
+        Bool -> "mem.view.setUint8(\(ptr), \(value) ? 1 : 0);"
+        Num I8 -> "mem.view.setInt8(\(ptr), \(value));"
+        Num U8 -> "mem.view.setUint8(\(ptr), \(value));"
+        Num I16 -> "mem.view.setInt16(\(ptr), \(value), true);"
+        Num U16 -> "mem.view.setUint16(\(ptr), \(value), true);"
+        Num I32 -> "mem.view.setInt32(\(ptr), \(value), true);"
+        Num U32 -> "mem.view.setUint32(\(ptr), \(value), true);"
+        Num I64 -> "mem.view.setBigInt64(\(ptr), \(value), true);"
+        Num U64 -> "mem.view.setBigUint64(\(ptr), \(value), true);"
+        Num I128 | Num U128 | Num Dec -> "mem.write128(\(ptr), \(value));"
+        Num F32 -> "mem.view.setFloat32(\(ptr), \(value), true);"
+        Num F64 -> "mem.view.setFloat64(\(ptr), \(value), true);"
+        RocList elemId ->
+            elemSize = getSizeRoundedToAlignment types elemId |> Num.toStr
+            elemAlign = Types.alignment types elemId |> Num.toStr
+
+            "mem.writeList(\(ptr), \(value), \(elemSize), \(elemAlign), (ptr, value) => { \(writeStmt types elemId "ptr" "value") });"
+
+        RocResult okId errId ->
+            writeOk = writeStmt types okId ptr "\(value).value"
+            writeErr = writeStmt types errId ptr "\(value).error"
+            tagPtr = "\(ptr) + \(Num.toStr (resultDiscriminantOffset types okId errId))"
+
+            "if (\(value).ok) { \(writeOk) mem.view.setUint8(\(tagPtr), 1); } else { \(writeErr) mem.view.setUint8(\(tagPtr), 0); }"
+
+        Struct { name } -> "write_\(escapeKW name)(mem, \(ptr), \(value));"
+        TagUnionPayload { name } -> "write_\(escapeKW name)(mem, \(ptr), \(value));"
+        TagUnion (Enumeration { name }) -> "write_\(escapeKW name)(mem, \(ptr), \(value));"
+        TagUnion (NonRecursive { name }) -> "write_\(escapeKW name)(mem, \(ptr), \(value));"
+        TagUnion (SingleTagStruct { name }) -> "write_\(escapeKW name)(mem, \(ptr), \(value));"
+        _ ->
+            size = getSizeRoundedToAlignment types id |> Num.toStr
+
+            "mem.copyInto(\(ptr), \(value), \(size));"
+
+readUnsigned : Str, U32 -> Str
+readUnsigned = \ptr, bytes ->
+    when bytes is
+        2 -> "mem.view.getUint16(\(ptr), true)"
+        4 -> "mem.view.getUint32(\(ptr), true)"
+        _ -> "mem.view.getUint8(\(ptr))"
+
+writeUnsigned : Str, U32, Str -> Str
+writeUnsigned = \ptr, bytes, value ->
+    when bytes is
+        2 -> "mem.view.setUint16(\(ptr), \(value), true)"
+        4 -> "mem.view.setUint32(\(ptr), \(value), true)"
+        _ -> "mem.view.setUint8(\(ptr), \(value))"
+
+ptrPlus : U32 -> Str
+ptrPlus = \offset ->
+    if offset == 0 then
+        "ptr"
+    else
+        "ptr + \(Num.toStr offset)"
+
+## Results are laid out like any other tag union: the payload, followed by
+## the discriminant (`Err` is 0 and `Ok` is 1).
+resultDiscriminantOffset : Types, TypeId, TypeId -> U32
+resultDiscriminantOffset = \types, okId, errId ->
+    size = Num.max (Types.size types okId) (Types.size types errId)
+    alignment = Num.max (Types.alignment types okId) (Types.alignment types errId)
+
+    roundUpToAlignment size alignment
+
+## The offset of each field, given that fields are stored in order, each
+## aligned to its own alignment.
+fieldOffsets : Types, List TypeId -> List U32
+fieldOffsets = \types, ids ->
+    answer =
+        List.walk ids { end: 0, offsets: [] } \{ end, offsets }, id ->
+            start = roundUpToAlignment end (Types.alignment types id)
+
+            { end: start + Types.size types id, offsets: List.append offsets start }
+
+    answer.offsets
+
+getSizeRoundedToAlignment = \types, id ->
+    alignment = Types.alignment types id
+
+    Types.size types id
+    |> roundUpToAlignment alignment
+
+roundUpToAlignment = \width, alignment ->
+    when alignment is
+        0 -> width
+        1 -> width
+        _ ->
+            if width % alignment > 0 then
+                width + alignment - (width % alignment)
+            else
+                width
+
+isUnit : Shape -> Bool
+isUnit = \shape ->
+    when shape is
+        Unit | EmptyTagUnion -> Bool.true
+        _ -> Bool.false
+
+fileHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+    //
+    // The wasm module must export its `memory`, `roc_alloc`, and `roc_dealloc`, as well as
+    // the `roc__*_exposed_generic` function for each entry point.
+
+    export interface RocExports {
+        memory: WebAssembly.Memory;
+        roc_alloc(size: number, alignment: number): number;
+        roc_dealloc(ptr: number, alignment: number): void;
+        [name: string]: any;
+    }
+
+    // Reads and writes Roc values in the wasm module's linear memory (which is
+    // little-endian, with 32-bit pointers).
+    export class RocMemory {
+        private readonly decoder = new TextDecoder();
+        private readonly encoder = new TextEncoder();
+
+        constructor(readonly exports: RocExports) {}
+
+        // The memory's buffer is replaced whenever it grows, so always get a fresh view.
+        get view(): DataView {
+            return new DataView(this.exports.memory.buffer);
+        }
+
+        alloc(size: number, alignment: number): number {
+            const ptr = this.exports.roc_alloc(Math.max(size, 1), alignment);
+
+            if (ptr === 0) {
+                throw new Error("roc_alloc failed");
+            }
+
+            return ptr;
+        }
+
+        free(ptr: number, alignment: number): void {
+            this.exports.roc_dealloc(ptr, alignment);
+        }
+
+        // Allocates the elements of a Str or List, preceded by a refcount of 1.
+        allocRefcounted(size: number, alignment: number): number {
+            const extra = Math.max(alignment, 4);
+            const ptr = this.alloc(size + extra, extra) + extra;
+
+            this.view.setInt32(ptr - 4, -0x80000000, true);
+
+            return ptr;
+        }
+
+        copy(src: number, size: number, alignment: number): number {
+            const ptr = this.alloc(size, alignment);
+
+            this.copyInto(ptr, src, size);
+
+            return ptr;
+        }
+
+        copyInto(dest: number, src: number, size: number): void {
+            const bytes = new Uint8Array(this.exports.memory.buffer);
+
+            bytes.copyWithin(dest, src, src + size);
+        }
+
+        readStr(ptr: number): string {
+            const view = this.view;
+            const lastByte = view.getUint8(ptr + 11);
+
+            // Strings shorter than 12 bytes are stored inline, with their length in the last byte.
+            if (lastByte & 0x80) {
+                return this.decoder.decode(new Uint8Array(view.buffer, ptr, lastByte & 0x7f));
+            }
+
+            const bytes = view.getUint32(ptr, true);
+            const len = view.getUint32(ptr + 4, true) & 0x7fffffff;
+
+            return this.decoder.decode(new Uint8Array(view.buffer, bytes, len));
+        }
+
+        writeStr(ptr: number, value: string): void {
+            const utf8 = this.encoder.encode(value);
+            const bytes = this.allocRefcounted(utf8.length, 1);
+
+            new Uint8Array(this.exports.memory.buffer, bytes, utf8.length).set(utf8);
+            this.writeListHeader(ptr, bytes, utf8.length);
+        }
+
+        readList<T>(ptr: number, elemSize: number, read: (ptr: number) => T): T[] {
+            const elements = this.view.getUint32(ptr, true);
+            const len = this.view.getUint32(ptr + 4, true) & 0x7fffffff;
+            const answer = [];
+
+            for (let i = 0; i < len; i++) {
+                answer.push(read(elements + i * elemSize));
+            }
+
+            return answer;
+        }
+
+        writeList<T>(
+            ptr: number,
+            values: T[],
+            elemSize: number,
+            elemAlignment: number,
+            write: (ptr: number, value: T) => void
+        ): void {
+            const elements = this.allocRefcounted(values.length * elemSize, elemAlignment);
+
+            values.forEach((value, i) => write(elements + i * elemSize, value));
+            this.writeListHeader(ptr, elements, values.length);
+        }
+
+        private writeListHeader(ptr: number, elements: number, len: number): void {
+            const view = this.view;
+
+            view.setUint32(ptr, elements, true);
+            view.setUint32(ptr + 4, len, true);
+            view.setUint32(ptr + 8, len, true);
+        }
+
+        readU128(ptr: number): bigint {
+            const view = this.view;
+
+            return (view.getBigUint64(ptr + 8, true) << 64n) | view.getBigUint64(ptr, true);
+        }
+
+        readI128(ptr: number): bigint {
+            return BigInt.asIntN(128, this.readU128(ptr));
+        }
+
+        write128(ptr: number, value: bigint): void {
+            const view = this.view;
+
+            view.setBigUint64(ptr, BigInt.asUintN(64, value), true);
+            view.setBigUint64(ptr + 8, BigInt.asUintN(64, value >> 64n), true);
+        }
+    }
+
+
+    """
+
+indent = "    "
+
+reservedKeywords = Set.fromList [
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+]
+
+escapeKW = \input ->
+    if Set.contains reservedKeywords input then
+        "\(input)_"
+    else
+        input
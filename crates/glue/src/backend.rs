@@ -0,0 +1,151 @@
+//! Glue for a host language is generated by a [GlueBackend], which visits the types a
+//! platform exposes to its host. Backends for Rust, C, and Zig come with `roc`, and can
+//! be selected with `roc glue --lang <LANG>`; anyone can add another by implementing
+//! the trait and passing it to [emit], or by writing a glue spec in Roc.
+use crate::c_glue::CBackend;
+use crate::rust_glue::RustBackend;
+use crate::types::{
+    File, RocSingleTagPayload, RocStructFields, RocTagUnion, RocType, TypeId, Types,
+};
+use crate::zig_glue::ZigBackend;
+use roc_collections::MutSet;
+
+/// The languages that have a built-in backend, in the order `roc glue --help` lists them.
+pub const LANGS: &[&str] = &["rust", "c", "zig"];
+
+/// Visits the types a platform exposes to its host, and generates glue for them.
+///
+/// Sizes and alignments differ between targets, so [emit] visits every target's [Types]
+/// in turn: first [GlueBackend::begin_target], then each type in the order given by
+/// [GlueBackend::order], then each entry point, and finally [GlueBackend::end_target].
+/// Once every target has been visited, [GlueBackend::finish] returns the files to write.
+pub trait GlueBackend {
+    /// The name that selects this backend, as in `roc glue --lang rust`
+    fn lang(&self) -> &'static str;
+
+    /// The order to visit a target's types in. By default, every type comes after the
+    /// types it contains (see [declaration_order]).
+    fn order(&self, types: &Types) -> Vec<TypeId> {
+        declaration_order(types)
+    }
+
+    fn begin_target(&mut self, _types: &Types) {}
+
+    fn visit_type(&mut self, types: &Types, id: TypeId);
+
+    fn visit_entry_point(&mut self, _types: &Types, _name: &str, _id: TypeId) {}
+
+    fn end_target(&mut self, _types: &Types) {}
+
+    fn finish(&mut self) -> Vec<File>;
+}
+
+/// A new instance of the built-in backend for the given language (see [LANGS])
+pub fn backend_for_lang(lang: &str) -> Option<Box<dyn GlueBackend>> {
+    match lang {
+        "rust" => Some(Box::<RustBackend>::default()),
+        "c" => Some(Box::<CBackend>::default()),
+        "zig" => Some(Box::<ZigBackend>::default()),
+        _ => None,
+    }
+}
+
+/// Run the backend over the types for every target, and return the files it generated.
+pub fn emit(backend: &mut dyn GlueBackend, all_types: &[Types]) -> Vec<File> {
+    for types in all_types {
+        backend.begin_target(types);
+
+        for id in backend.order(types) {
+            backend.visit_type(types, id);
+        }
+
+        for (name, id) in types.entry_points() {
+            backend.visit_entry_point(types, name, *id);
+        }
+
+        backend.end_target(types);
+    }
+
+    backend.finish()
+}
+
+/// Languages like C need each type to be declared before any type that contains it, so this
+/// orders the types depth-first, such that every type comes after the types it contains.
+pub fn declaration_order(types: &Types) -> Vec<TypeId> {
+    let mut visited = MutSet::default();
+    let mut order = Vec::with_capacity(types.ids().len());
+
+    for id in types.ids() {
+        visit_declaration(types, id, &mut visited, &mut order);
+    }
+
+    order
+}
+
+fn visit_declaration(
+    types: &Types,
+    id: TypeId,
+    visited: &mut MutSet<TypeId>,
+    order: &mut Vec<TypeId>,
+) {
+    if visited.insert(id) {
+        for contained_id in contained_types(types.get_type(id)) {
+            visit_declaration(types, contained_id, visited, order);
+        }
+
+        order.push(id);
+    }
+}
+
+/// The types that a type's declaration mentions by value. Heap-allocated tag unions are left
+/// out, since they're only ever referred to by pointer, and so are the elements of lists and
+/// boxes.
+fn contained_types(typ: &RocType) -> Vec<TypeId> {
+    match typ {
+        RocType::Struct { fields, .. } | RocType::TagUnionPayload { fields, .. } => match fields {
+            RocStructFields::HasNoClosure { fields } => fields.iter().map(|(_, id)| *id).collect(),
+            RocStructFields::HasClosure { fields } => fields.iter().map(|(_, id, _)| *id).collect(),
+        },
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload, .. }) => match payload {
+            RocSingleTagPayload::HasNoClosure { payload_fields } => payload_fields.clone(),
+            RocSingleTagPayload::HasClosure { payload_getters } => {
+                payload_getters.iter().map(|(id, _)| *id).collect()
+            }
+        },
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. }) => {
+            tags.iter().filter_map(|(_, payload)| *payload).collect()
+        }
+        RocType::Function(roc_fn) => {
+            if roc_fn.is_toplevel {
+                Vec::new()
+            } else {
+                let mut ids = roc_fn.args.clone();
+
+                ids.push(roc_fn.lambda_set);
+                ids.push(roc_fn.ret);
+
+                ids
+            }
+        }
+        RocType::RocResult(ok_id, err_id) => vec![*ok_id, *err_id],
+        // A recursive pointer always points to a heap-allocated tag union.
+        RocType::TagUnion(
+            RocTagUnion::Recursive { .. }
+            | RocTagUnion::NullableWrapped { .. }
+            | RocTagUnion::NullableUnwrapped { .. }
+            | RocTagUnion::NonNullableUnwrapped { .. }
+            | RocTagUnion::Enumeration { .. },
+        )
+        | RocType::RecursivePointer(_)
+        | RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::EmptyTagUnion
+        | RocType::Unit
+        | RocType::Unsized => Vec::new(),
+    }
+}
@@ -0,0 +1,666 @@
+use crate::backend::GlueBackend;
+use crate::types::{
+    File, RocFn, RocNum, RocSingleTagPayload, RocStructFields, RocTagUnion, RocType, TypeId, Types,
+};
+use roc_target::Architecture;
+
+const INDENT: &str = "    ";
+
+const HEADER: &str = r#"// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#ifndef ROC_APP_H
+#define ROC_APP_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Builtins that have the same layout in every app. `elements` and `bytes` point
+// into a refcounted heap allocation, so hosts must not free them directly.
+typedef struct RocStr {
+    uint8_t *bytes;
+    size_t len;
+    size_t capacity;
+} RocStr;
+
+typedef struct RocList {
+    void *elements;
+    size_t len;
+    size_t capacity;
+} RocList;
+
+// A fixed-point decimal with 18 digits after the decimal point.
+typedef struct RocDec {
+    __int128_t value;
+} RocDec;
+
+typedef void *RocBox;
+"#;
+
+const FOOTER: &str = r#"
+#ifdef __cplusplus
+}
+#endif
+
+#endif // ROC_APP_H
+"#;
+
+const RESERVED_KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "false", "float", "for", "goto", "if", "inline", "int", "long",
+    "register", "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch",
+    "true", "typedef", "union", "unsigned", "void", "volatile", "while",
+];
+
+/// Generates a `roc_app.h`. Sizes and alignments differ between targets, so every target gets
+/// its own section, and the C preprocessor picks the one that matches the host.
+#[derive(Default)]
+pub struct CBackend {
+    buf: String,
+}
+
+impl GlueBackend for CBackend {
+    fn lang(&self) -> &'static str {
+        "c"
+    }
+
+    fn begin_target(&mut self, types: &Types) {
+        let arch = types.target().architecture;
+
+        self.buf.push_str(&format!(
+            "\n#if {} // {}\n\n",
+            arch_condition(arch),
+            arch_name(arch)
+        ));
+
+        // Heap-allocated tag unions are all represented by a pointer, so they can be
+        // declared up front. That way, the payloads of recursive tag unions can refer to them.
+        for id in types.ids() {
+            add_heap_tag_union(&mut self.buf, types.get_type(id));
+        }
+    }
+
+    fn visit_type(&mut self, types: &Types, id: TypeId) {
+        add_declaration(&mut self.buf, types, id);
+    }
+
+    fn visit_entry_point(&mut self, types: &Types, name: &str, id: TypeId) {
+        add_entry_point(&mut self.buf, types, name, id);
+    }
+
+    fn end_target(&mut self, types: &Types) {
+        let arch = types.target().architecture;
+
+        self.buf
+            .push_str(&format!("#endif // {}\n", arch_name(arch)));
+    }
+
+    fn finish(&mut self) -> Vec<File> {
+        let sections = std::mem::take(&mut self.buf);
+
+        vec![File {
+            name: "roc_app.h".to_string(),
+            content: format!("{HEADER}{sections}{FOOTER}"),
+        }]
+    }
+}
+
+fn add_declaration(buf: &mut String, types: &Types, id: TypeId) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } | RocType::TagUnionPayload { name, fields } => {
+            add_struct(buf, types, id, name, fields)
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { name, tags, size }) => {
+            add_enumeration(buf, name, tags, *size)
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive {
+            name,
+            tags,
+            discriminant_size,
+            discriminant_offset,
+        }) => {
+            // If this tag union has no tags, it's uninhabited and can't be constructed.
+            if !tags.is_empty() {
+                add_non_recursive_tag_union(
+                    buf,
+                    types,
+                    id,
+                    name,
+                    tags,
+                    *discriminant_size,
+                    *discriminant_offset,
+                )
+            }
+        }
+        RocType::TagUnion(RocTagUnion::SingleTagStruct {
+            name,
+            tag_name,
+            payload,
+        }) => {
+            let fields = match payload {
+                RocSingleTagPayload::HasNoClosure { payload_fields } => payload_fields.clone(),
+                RocSingleTagPayload::HasClosure { payload_getters } => {
+                    payload_getters.iter().map(|(id, _)| *id).collect()
+                }
+            };
+
+            add_single_tag_struct(buf, types, id, name, tag_name, &fields)
+        }
+        RocType::Function(roc_fn) => {
+            if !roc_fn.is_toplevel {
+                add_function(buf, types, roc_fn)
+            }
+        }
+        RocType::RocResult(ok_id, err_id) => add_result(buf, types, *ok_id, *err_id),
+        RocType::RocDict(_, _) => add_opaque(buf, types, id, "RocDict"),
+        RocType::RocSet(_) => add_opaque(buf, types, id, "RocSet"),
+        // These are either declared in the file header, represented by a pointer
+        // declared in add_heap_tag_union, or zero-sized and never declared.
+        RocType::TagUnion(
+            RocTagUnion::Recursive { .. }
+            | RocTagUnion::NullableWrapped { .. }
+            | RocTagUnion::NullableUnwrapped { .. }
+            | RocTagUnion::NonNullableUnwrapped { .. },
+        )
+        | RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_)
+        | RocType::EmptyTagUnion
+        | RocType::RecursivePointer(_)
+        | RocType::Unit
+        | RocType::Unsized => {}
+    }
+}
+
+fn add_struct(buf: &mut String, types: &Types, id: TypeId, name: &str, fields: &RocStructFields) {
+    let name = escape_kw(name);
+    let fields: Vec<(&str, TypeId)> = match fields {
+        RocStructFields::HasNoClosure { fields } => fields
+            .iter()
+            .map(|(field_name, field_id)| (field_name.as_str(), *field_id))
+            .collect(),
+        RocStructFields::HasClosure { fields } => fields
+            .iter()
+            .map(|(field_name, field_id, _)| (field_name.as_str(), *field_id))
+            .collect(),
+    };
+    let mut body = String::new();
+
+    for (field_name, field_id) in fields {
+        add_field(&mut body, types, &escape_kw(field_name), field_id);
+    }
+
+    buf.push_str(&format!("typedef struct {name} {{\n{body}}} {name};\n"));
+    add_size_assert(buf, types, id, &name);
+    buf.push('\n');
+}
+
+fn add_single_tag_struct(
+    buf: &mut String,
+    types: &Types,
+    id: TypeId,
+    name: &str,
+    tag_name: &str,
+    field_ids: &[TypeId],
+) {
+    let name = escape_kw(name);
+    let mut body = String::new();
+
+    for (index, field_id) in field_ids.iter().enumerate() {
+        add_field(&mut body, types, &format!("f{index}"), *field_id);
+    }
+
+    buf.push_str(&format!(
+        "// The payload of the `{tag_name}` tag, which is the only tag in this union.\n"
+    ));
+    buf.push_str(&format!("typedef struct {name} {{\n{body}}} {name};\n"));
+    add_size_assert(buf, types, id, &name);
+    buf.push('\n');
+}
+
+fn add_enumeration(buf: &mut String, name: &str, tags: &[String], size: u32) {
+    let name = escape_kw(name);
+
+    add_tag_ids(buf, &name, tags.iter().map(String::as_str));
+    buf.push_str(&format!("typedef {} {name};\n\n", unsigned_int(size)));
+}
+
+/// A non-recursive tag union is laid out as a union of its payloads, followed
+/// by the discriminant (the tag id, in alphabetical order of the tag names).
+fn add_non_recursive_tag_union(
+    buf: &mut String,
+    types: &Types,
+    id: TypeId,
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+) {
+    let name = escape_kw(name);
+    let mut payloads = String::new();
+
+    for (tag_name, opt_payload) in tags {
+        if let Some(payload_id) = opt_payload {
+            add_union_member(&mut payloads, types, tag_name, *payload_id);
+        }
+    }
+
+    add_tag_ids(
+        buf,
+        &name,
+        tags.iter().map(|(tag_name, _)| tag_name.as_str()),
+    );
+    buf.push_str(&format!("typedef struct {name} {{\n"));
+    add_union(buf, &payloads);
+    buf.push_str(&format!(
+        "{INDENT}{} discriminant; // {name}_Tag\n",
+        unsigned_int(discriminant_size)
+    ));
+    buf.push_str(&format!("}} {name};\n"));
+    add_size_assert(buf, types, id, &name);
+    buf.push_str(&format!(
+        "_Static_assert(offsetof({name}, discriminant) == {discriminant_offset}, \"{name} has the wrong discriminant offset\");\n\n"
+    ));
+}
+
+/// Recursive tag unions live on the heap; all the host gets is a pointer.
+/// This documents how the tag id is encoded for each kind of heap-allocated union.
+fn add_heap_tag_union(buf: &mut String, typ: &RocType) {
+    match typ {
+        RocType::TagUnion(RocTagUnion::Recursive {
+            name,
+            tags,
+            discriminant_offset,
+            ..
+        }) => {
+            let comment = format!(
+                "// Points to the payload of the active tag. If the tag id fits in the pointer's\n\
+                 // unused low bits (there are fewer tags than the pointer's alignment), it's stored\n\
+                 // there; otherwise it's stored {discriminant_offset} bytes into the allocation."
+            );
+
+            add_pointer_union(
+                buf,
+                name,
+                tags.iter().map(|(tag_name, _)| tag_name.as_str()),
+                &comment,
+            );
+        }
+        RocType::TagUnion(RocTagUnion::NullableWrapped {
+            name,
+            index_of_null_tag,
+            tags,
+            discriminant_offset,
+            ..
+        }) => {
+            let null_tag = match tags.get(*index_of_null_tag as usize) {
+                Some((tag_name, _)) => tag_name,
+                None => unreachable!(
+                    "NullableWrapped {name} has no null tag. This should never happen, and means there was a bug in `roc glue`."
+                ),
+            };
+            let comment = format!(
+                "// NULL means `{null_tag}`. Otherwise, points to the payload of the active tag.\n\
+                 // If the tag id fits in the pointer's unused low bits (there are fewer tags than\n\
+                 // the pointer's alignment), it's stored there; otherwise it's stored {discriminant_offset} bytes\n\
+                 // into the allocation."
+            );
+
+            add_pointer_union(
+                buf,
+                name,
+                tags.iter().map(|(tag_name, _)| tag_name.as_str()),
+                &comment,
+            );
+        }
+        RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            name,
+            null_tag,
+            non_null_tag,
+            null_represents_first_tag,
+            ..
+        }) => {
+            let tag_names = if *null_represents_first_tag {
+                [null_tag.as_str(), non_null_tag.as_str()]
+            } else {
+                [non_null_tag.as_str(), null_tag.as_str()]
+            };
+            let comment = format!(
+                "// NULL means `{null_tag}`. Otherwise, points to the payload of `{non_null_tag}`."
+            );
+
+            add_pointer_union(buf, name, tag_names.into_iter(), &comment);
+        }
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, tag_name, .. }) => {
+            let comment = format!(
+                "// Points to the payload of `{tag_name}`, which is the only tag in this union."
+            );
+
+            add_pointer_union(buf, name, std::iter::once(tag_name.as_str()), &comment);
+        }
+        _ => {}
+    }
+}
+
+fn add_pointer_union<'a>(
+    buf: &mut String,
+    name: &str,
+    tags: impl Iterator<Item = &'a str>,
+    comment: &str,
+) {
+    let name = escape_kw(name);
+
+    add_tag_ids(buf, &name, tags);
+    buf.push_str(comment);
+    buf.push('\n');
+    buf.push_str(&format!(
+        "typedef struct {name} {{\n{INDENT}void *pointer;\n}} {name};\n\n"
+    ));
+}
+
+fn add_tag_ids<'a>(buf: &mut String, name: &str, tags: impl Iterator<Item = &'a str>) {
+    buf.push_str(&format!("enum {name}_Tag {{\n"));
+
+    for (index, tag_name) in tags.enumerate() {
+        buf.push_str(&format!("{INDENT}{name}_{tag_name} = {index},\n"));
+    }
+
+    buf.push_str("};\n\n");
+}
+
+/// A function that isn't exposed directly, e.g. a closure returned by an entry point.
+/// The host calls it by passing its captured environment to the extern function.
+fn add_function(buf: &mut String, types: &Types, roc_fn: &RocFn) {
+    let name = escape_kw(&roc_fn.function_name);
+    let closure_data = if is_unit(types.get_type(roc_fn.lambda_set)) {
+        // C structs can't be empty, so pad the ones for closures that capture nothing.
+        format!("{INDENT}uint8_t _unused;\n")
+    } else {
+        let mut field = String::new();
+
+        add_field(&mut field, types, "closure_data", roc_fn.lambda_set);
+
+        field
+    };
+    let mut arguments = arg_list(types, &roc_fn.args, |arg_id, _, index| {
+        format!("const {} *arg{index}", type_name(types, arg_id))
+    });
+
+    if arguments.is_empty() {
+        // These always have a first argument that's a pointer, even if it's to nothing.
+        arguments.push_str("const void *arg0");
+    }
+
+    buf.push_str(&format!(
+        "typedef struct {name} {{\n{closure_data}}} {name};\n"
+    ));
+    buf.push_str(&format!(
+        "extern void {}({arguments}, uint8_t *closure_data, {}output);\n\n",
+        roc_fn.extern_name,
+        pointer_to(types, roc_fn.ret)
+    ));
+}
+
+/// Result has the same layout as any other non-recursive tag union, with `Err` being tag 0.
+fn add_result(buf: &mut String, types: &Types, ok_id: TypeId, err_id: TypeId) {
+    let name = result_name(types, ok_id, err_id);
+    let mut payloads = String::new();
+
+    add_union_member(&mut payloads, types, "ok", ok_id);
+    add_union_member(&mut payloads, types, "err", err_id);
+
+    buf.push_str(&format!("#ifndef {name}_DEFINED\n#define {name}_DEFINED\n"));
+    buf.push_str(&format!("typedef struct {name} {{\n"));
+    add_union(buf, &payloads);
+    buf.push_str(&format!("{INDENT}uint8_t is_ok;\n"));
+    buf.push_str(&format!("}} {name};\n#endif\n\n"));
+}
+
+/// Builtins whose layout the host shouldn't depend on only get the right size and alignment.
+fn add_opaque(buf: &mut String, types: &Types, id: TypeId, name: &str) {
+    let size = types.size_rounded_to_alignment(id);
+    let align = types.align(id);
+
+    buf.push_str(&format!("#ifndef {name}_DEFINED\n#define {name}_DEFINED\n"));
+    buf.push_str(&format!(
+        "typedef struct {name} {{\n{INDENT}_Alignas({align}) uint8_t bytes[{size}];\n}} {name};\n"
+    ));
+    buf.push_str("#endif\n\n");
+}
+
+fn add_entry_point(buf: &mut String, types: &Types, name: &str, id: TypeId) {
+    let signature = match types.get_type(id) {
+        RocType::Function(roc_fn) => {
+            let arguments = arg_list(types, &roc_fn.args, |arg_id, typ, index| {
+                let type_name = type_name(types, arg_id);
+
+                // Values that are refcounted are passed by reference; Roc takes ownership of them.
+                if can_derive_copy(types, typ) {
+                    format!("{type_name} arg{index}")
+                } else {
+                    format!("{type_name} *arg{index}")
+                }
+            });
+            let ret = pointer_to(types, roc_fn.ret);
+
+            if arguments.is_empty() {
+                format!("{ret}ret")
+            } else {
+                format!("{ret}ret, {arguments}")
+            }
+        }
+        _ => format!("{}ret", pointer_to(types, id)),
+    };
+
+    buf.push_str(&format!(
+        "extern void roc__{name}_1_exposed_generic({signature});\n\n"
+    ));
+}
+
+fn add_field(buf: &mut String, types: &Types, field_name: &str, id: TypeId) {
+    // Zero-sized fields aren't allowed in C, and there's nothing to store anyway.
+    if !is_unit(types.get_type(id)) {
+        buf.push_str(&format!("{INDENT}{} {field_name};\n", type_name(types, id)));
+    }
+}
+
+fn add_union_member(buf: &mut String, types: &Types, field_name: &str, id: TypeId) {
+    if !is_unit(types.get_type(id)) {
+        buf.push_str(&format!(
+            "{INDENT}{INDENT}{} {field_name};\n",
+            type_name(types, id)
+        ));
+    }
+}
+
+fn add_union(buf: &mut String, payloads: &str) {
+    if !payloads.is_empty() {
+        buf.push_str(&format!(
+            "{INDENT}union {{\n{payloads}{INDENT}}} payload;\n"
+        ));
+    }
+}
+
+fn add_size_assert(buf: &mut String, types: &Types, id: TypeId, name: &str) {
+    buf.push_str(&format!(
+        "_Static_assert(sizeof({name}) == {}, \"{name} has the wrong size\");\n",
+        types.size_rounded_to_alignment(id)
+    ));
+}
+
+/// The arguments' declarations, separated by commas. Zero-sized arguments are left out,
+/// since nothing gets passed for them anyway, but they still count toward the index.
+fn arg_list(
+    types: &Types,
+    args: &[TypeId],
+    fmt: impl Fn(TypeId, &RocType, usize) -> String,
+) -> String {
+    args.iter()
+        .enumerate()
+        .filter_map(|(index, arg_id)| {
+            let typ = types.get_type(*arg_id);
+
+            (!is_unit(typ)).then(|| fmt(*arg_id, typ, index))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn type_name(types: &Types, id: TypeId) -> String {
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "void".to_string(),
+        RocType::Unsized => "RocList".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(num) => num_type_name(*num).to_string(),
+        RocType::RocDict(_, _) => "RocDict".to_string(),
+        RocType::RocSet(_) => "RocSet".to_string(),
+        RocType::RocList(_) => "RocList".to_string(),
+        RocType::RocBox(_) => "RocBox".to_string(),
+        RocType::RocResult(ok_id, err_id) => result_name(types, *ok_id, *err_id),
+        RocType::RecursivePointer(content) => type_name(types, *content),
+        RocType::Struct { name, .. } | RocType::TagUnionPayload { name, .. } => escape_kw(name),
+        RocType::TagUnion(tag_union) => escape_kw(tag_union_name(tag_union)),
+        RocType::Function(roc_fn) => escape_kw(&roc_fn.function_name),
+    }
+}
+
+fn num_type_name(num: RocNum) -> &'static str {
+    match num {
+        RocNum::U8 => "uint8_t",
+        RocNum::U16 => "uint16_t",
+        RocNum::U32 => "uint32_t",
+        RocNum::U64 => "uint64_t",
+        RocNum::U128 => "__uint128_t",
+        RocNum::I8 => "int8_t",
+        RocNum::I16 => "int16_t",
+        RocNum::I32 => "int32_t",
+        RocNum::I64 => "int64_t",
+        RocNum::I128 => "__int128_t",
+        RocNum::F32 => "float",
+        RocNum::F64 => "double",
+        RocNum::Dec => "RocDec",
+    }
+}
+
+pub(crate) fn tag_union_name(tag_union: &RocTagUnion) -> &str {
+    match tag_union {
+        RocTagUnion::Enumeration { name, .. }
+        | RocTagUnion::NonRecursive { name, .. }
+        | RocTagUnion::Recursive { name, .. }
+        | RocTagUnion::NonNullableUnwrapped { name, .. }
+        | RocTagUnion::SingleTagStruct { name, .. }
+        | RocTagUnion::NullableWrapped { name, .. }
+        | RocTagUnion::NullableUnwrapped { name, .. } => name,
+    }
+}
+
+/// C has no generics, so every combination of payloads gets its own Result type.
+fn result_name(types: &Types, ok_id: TypeId, err_id: TypeId) -> String {
+    format!(
+        "RocResult_{}_{}",
+        type_name(types, ok_id),
+        type_name(types, err_id)
+    )
+}
+
+fn pointer_to(types: &Types, id: TypeId) -> String {
+    format!("{} *", type_name(types, id))
+}
+
+fn unsigned_int(bytes: u32) -> &'static str {
+    match bytes {
+        0 | 1 => "uint8_t",
+        2 => "uint16_t",
+        4 => "uint32_t",
+        _ => "uint64_t",
+    }
+}
+
+/// Whether values of this type can be copied without touching a refcount.
+pub(crate) fn can_derive_copy(types: &Types, typ: &RocType) -> bool {
+    let all_copy = |ids: &mut dyn Iterator<Item = TypeId>| {
+        ids.all(|id| can_derive_copy(types, types.get_type(id)))
+    };
+
+    match typ {
+        RocType::Function(roc_fn) => can_derive_copy(types, types.get_type(roc_fn.lambda_set)),
+        // unsized values are heap-allocated
+        RocType::Unsized => false,
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. }) => true,
+        RocType::RocStr
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::TagUnion(
+            RocTagUnion::NullableUnwrapped { .. }
+            | RocTagUnion::NullableWrapped { .. }
+            | RocTagUnion::Recursive { .. }
+            | RocTagUnion::NonNullableUnwrapped { .. },
+        )
+        | RocType::RecursivePointer(_) => false,
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload, .. }) => match payload {
+            RocSingleTagPayload::HasNoClosure { payload_fields } => {
+                all_copy(&mut payload_fields.iter().copied())
+            }
+            RocSingleTagPayload::HasClosure { payload_getters } => {
+                all_copy(&mut payload_getters.iter().map(|(id, _)| *id))
+            }
+        },
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. }) => {
+            all_copy(&mut tags.iter().filter_map(|(_, payload)| *payload))
+        }
+        RocType::RocResult(ok_id, err_id) => all_copy(&mut [*ok_id, *err_id].into_iter()),
+        RocType::Struct { fields, .. } | RocType::TagUnionPayload { fields, .. } => match fields {
+            RocStructFields::HasNoClosure { fields } => {
+                all_copy(&mut fields.iter().map(|(_, id)| *id))
+            }
+            RocStructFields::HasClosure { fields } => {
+                all_copy(&mut fields.iter().map(|(_, id, _)| *id))
+            }
+        },
+    }
+}
+
+pub(crate) fn is_unit(typ: &RocType) -> bool {
+    matches!(typ, RocType::Unit | RocType::EmptyTagUnion)
+}
+
+fn escape_kw(input: &str) -> String {
+    if RESERVED_KEYWORDS.contains(&input) {
+        format!("{input}_")
+    } else {
+        input.to_string()
+    }
+}
+
+fn arch_name(arch: Architecture) -> &'static str {
+    match arch {
+        Architecture::Aarch32 => "arm",
+        Architecture::Aarch64 => "aarch64",
+        Architecture::Wasm32 => "wasm32",
+        Architecture::X86_32 => "x86",
+        Architecture::X86_64 => "x86_64",
+    }
+}
+
+fn arch_condition(arch: Architecture) -> &'static str {
+    match arch {
+        Architecture::Aarch32 => "defined(__arm__) || defined(_M_ARM)",
+        Architecture::Aarch64 => "defined(__aarch64__) || defined(_M_ARM64)",
+        Architecture::Wasm32 => "defined(__wasm32__)",
+        Architecture::X86_32 => "defined(__i386__) || defined(_M_IX86)",
+        Architecture::X86_64 => "defined(__x86_64__) || defined(_M_X64)",
+    }
+}
@@ -1,18 +1,22 @@
 //! Generates code needed for platform hosts to communicate with Roc apps.
 //! This tool is not necessary for writing a platform in another language,
-//! however, it's a great convenience! Currently supports Rust platforms, and
-//! the plan is to support any language via a plugin model.
+//! however, it's a great convenience! Glue for Rust, C, and Zig comes with `roc`
+//! (see [backend]), and glue for any other language can be generated by a spec,
+//! which is a Roc app built on the platform in `platform/`.
+pub mod backend;
+pub mod c_glue;
 pub mod enums;
 pub mod load;
 pub mod roc_type;
 pub mod rust_glue;
 pub mod structs;
 pub mod types;
+pub mod zig_glue;
 
 #[rustfmt::skip]
 pub mod glue;
 
-pub use load::generate;
+pub use load::{generate, generate_with_backend};
 
 // required because we use roc_std here
 mod roc_externs {
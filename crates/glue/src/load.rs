@@ -1,8 +1,10 @@
+use crate::backend::GlueBackend;
 use crate::roc_type;
 use crate::types::Types;
 use bumpalo::Bump;
 use libloading::Library;
 use roc_build::{
+    emit::Emit,
     link::{LinkType, LinkingStrategy},
     program::{
         build_file, handle_error_module, handle_loading_problem, standard_load_config,
@@ -55,12 +57,14 @@ pub fn generate(
                 backend,
                 opt_level: OptLevel::Development,
                 emit_debug_info: false,
+                emit: Emit::default(),
             };
 
             let load_config = standard_load_config(
                 &triple,
                 BuildOrdering::BuildIfChecks,
                 Threading::AllAvailable,
+                Default::default(),
             );
 
             let arena = ManuallyDrop::new(Bump::new());
@@ -91,6 +95,7 @@ pub fn generate(
                     problems,
                     total_time,
                     expect_metadata: _,
+                    sources: _,
                 }) => {
                     // TODO: Should binary_path be update to deal with extensions?
                     use target_lexicon::OperatingSystem;
@@ -144,45 +149,7 @@ pub fn generate(
                         process::exit(1);
                     });
                     for roc_type::File { name, content } in &files {
-                        let valid_name = PathBuf::from(name.as_str())
-                            .components()
-                            .all(|comp| matches!(comp, Component::CurDir | Component::Normal(_)));
-                        if !valid_name {
-                            eprintln!("File name was invalid: {}", &name);
-
-                            process::exit(1);
-                        }
-                        let full_path = output_path.join(name.as_str());
-                        if let Some(dir_path) = full_path.parent() {
-                            std::fs::create_dir_all(dir_path).unwrap_or_else(|err| {
-                                eprintln!(
-                                    "Unable to create output directory {} - {:?}",
-                                    dir_path.display(),
-                                    err
-                                );
-
-                                process::exit(1);
-                            });
-                        }
-                        let mut file = File::create(&full_path).unwrap_or_else(|err| {
-                            eprintln!(
-                                "Unable to create output file {} - {:?}",
-                                full_path.display(),
-                                err
-                            );
-
-                            process::exit(1);
-                        });
-
-                        file.write_all(content.as_bytes()).unwrap_or_else(|err| {
-                            eprintln!(
-                                "Unable to write bindings to output file {} - {:?}",
-                                full_path.display(),
-                                err
-                            );
-
-                            process::exit(1);
-                        });
+                        write_glue_file(output_path, name.as_str(), content.as_str());
                     }
 
                     println!(
@@ -198,23 +165,97 @@ pub fn generate(
                 Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
             }
         }
-        Err(err) => match err.kind() {
-            ErrorKind::NotFound => {
-                eprintln!("Platform module file not found: {}", input_path.display());
-                process::exit(1);
-            }
-            error => {
-                eprintln!(
-                    "Error loading platform module file {} - {:?}",
-                    input_path.display(),
-                    error
-                );
-                process::exit(1);
+        Err(err) => exit_with_load_error(input_path, err),
+    }
+}
+
+/// Generate glue with one of the backends in [crate::backend], rather than a spec.
+pub fn generate_with_backend(
+    input_path: &Path,
+    output_path: &Path,
+    backend: &mut dyn GlueBackend,
+) -> io::Result<i32> {
+    match load_types(
+        input_path.to_path_buf(),
+        Threading::AllAvailable,
+        IgnoreErrors::NONE,
+    ) {
+        Ok(types) => {
+            for crate::types::File { name, content } in crate::backend::emit(backend, &types) {
+                write_glue_file(output_path, &name, &content);
             }
-        },
+
+            println!(
+                "🎉 Generated type declarations in:\n\n\t{}",
+                output_path.display()
+            );
+
+            Ok(0)
+        }
+        Err(err) => exit_with_load_error(input_path, err),
     }
 }
 
+fn exit_with_load_error(input_path: &Path, err: io::Error) -> ! {
+    match err.kind() {
+        ErrorKind::NotFound => {
+            eprintln!("Platform module file not found: {}", input_path.display());
+            process::exit(1);
+        }
+        error => {
+            eprintln!(
+                "Error loading platform module file {} - {:?}",
+                input_path.display(),
+                error
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Write one of the files the glue generated, relative to the output directory.
+fn write_glue_file(output_path: &Path, name: &str, content: &str) {
+    let valid_name = PathBuf::from(name)
+        .components()
+        .all(|comp| matches!(comp, Component::CurDir | Component::Normal(_)));
+    if !valid_name {
+        eprintln!("File name was invalid: {}", &name);
+
+        process::exit(1);
+    }
+    let full_path = output_path.join(name);
+    if let Some(dir_path) = full_path.parent() {
+        std::fs::create_dir_all(dir_path).unwrap_or_else(|err| {
+            eprintln!(
+                "Unable to create output directory {} - {:?}",
+                dir_path.display(),
+                err
+            );
+
+            process::exit(1);
+        });
+    }
+    let mut file = File::create(&full_path).unwrap_or_else(|err| {
+        eprintln!(
+            "Unable to create output file {} - {:?}",
+            full_path.display(),
+            err
+        );
+
+        process::exit(1);
+    });
+
+    file.write_all(content.as_bytes()).unwrap_or_else(|err| {
+        eprintln!(
+            "Unable to write bindings to output file {} - {:?}",
+            full_path.display(),
+            err
+        );
+
+        process::exit(1);
+    });
+}
+
 fn number_lambda_sets(subs: &Subs, initial: Variable) -> Vec<Variable> {
     let mut lambda_sets = vec![];
     let mut stack = vec![initial];
@@ -359,6 +400,8 @@ pub fn load_types(
             palette: DEFAULT_PALETTE,
             threading,
             exec_mode: ExecutionMode::Check,
+            warning_levels: Default::default(),
+            explain_types: false,
         },
     )
     .unwrap_or_else(|problem| match problem {
@@ -1,3 +1,4 @@
+use crate::backend::GlueBackend;
 use crate::types::{
     Accessors, File, RocFn, RocNum, RocSingleTagPayload, RocStructFields, RocTagUnion, RocType,
     TypeId, Types,
@@ -61,15 +62,40 @@ fn add_decl(impls: &mut Impls, opt_impl: Impl, target_info: TargetInfo, body: St
 }
 
 pub fn emit(types: &[Types]) -> Vec<File> {
-    let mut buf = std::str::from_utf8(HEADER).unwrap().to_string();
-    let mut impls: Impls = IndexMap::default();
+    crate::backend::emit(&mut RustBackend::default(), types)
+}
 
-    for types in types {
-        for id in types.sorted_ids() {
-            add_type(types.target(), id, types, &mut impls);
-        }
+/// Generates a `mod.rs` which uses `#[cfg(target_arch = ...)]` to pick the declarations
+/// for the host's target.
+#[derive(Default)]
+pub struct RustBackend {
+    impls: Impls,
+}
+
+impl GlueBackend for RustBackend {
+    fn lang(&self) -> &'static str {
+        "rust"
+    }
+
+    fn order(&self, types: &Types) -> Vec<TypeId> {
+        types.sorted_ids()
     }
 
+    fn visit_type(&mut self, types: &Types, id: TypeId) {
+        add_type(types.target(), id, types, &mut self.impls);
+    }
+
+    fn finish(&mut self) -> Vec<File> {
+        vec![File {
+            name: "mod.rs".to_string(),
+            content: render(std::mem::take(&mut self.impls)),
+        }]
+    }
+}
+
+fn render(impls: Impls) -> String {
+    let mut buf = std::str::from_utf8(HEADER).unwrap().to_string();
+
     for (opt_impl, decls) in impls {
         let has_impl;
 
@@ -138,10 +164,7 @@ pub fn emit(types: &[Types]) -> Vec<File> {
         }
     }
 
-    vec![crate::types::File {
-        name: "mod.rs".to_string(),
-        content: buf,
-    }]
+    buf
 }
 
 fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, impls: &mut Impls) {
@@ -0,0 +1,624 @@
+use crate::backend::GlueBackend;
+use crate::c_glue::{can_derive_copy, is_unit, tag_union_name};
+use crate::types::{
+    File, RocFn, RocNum, RocSingleTagPayload, RocStructFields, RocTagUnion, RocType, TypeId, Types,
+};
+use roc_collections::MutSet;
+use roc_target::Architecture;
+
+const INDENT: &str = "    ";
+
+const HEADER: &str = r#"// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+const std = @import("std");
+const builtin = @import("builtin");
+
+// Builtins that have the same layout in every app. `elements` and `bytes` point
+// into a refcounted heap allocation, so hosts must not free them directly.
+pub const RocStr = extern struct {
+    bytes: ?[*]u8,
+    len: usize,
+    capacity: usize,
+};
+
+pub const RocList = extern struct {
+    elements: ?*anyopaque,
+    len: usize,
+    capacity: usize,
+};
+
+// A fixed-point decimal with 18 digits after the decimal point.
+pub const RocDec = extern struct {
+    value: i128,
+};
+
+pub const RocBox = ?*anyopaque;
+
+// Sizes and alignments differ between targets, so every target gets its own
+// declarations, and this picks the ones that match the host.
+pub usingnamespace switch (builtin.cpu.arch) {
+    .arm => arm,
+    .aarch64 => aarch64,
+    .wasm32 => wasm32,
+    .i386 => x86,
+    .x86_64 => x86_64,
+    else => @compileError("roc glue did not generate declarations for this target"),
+};
+"#;
+
+const RESERVED_KEYWORDS: &[&str] = &[
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "linksection",
+    "noalias",
+    "noinline",
+    "nosuspend",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "try",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "volatile",
+    "while",
+];
+
+/// Generates a `roc_app.zig`, with one struct of declarations per target.
+#[derive(Default)]
+pub struct ZigBackend {
+    buf: String,
+    /// The declarations for the target being visited
+    target_buf: String,
+    /// Builtins like `RocDict` get declared once per target, however many types use them.
+    declared: MutSet<String>,
+}
+
+impl GlueBackend for ZigBackend {
+    fn lang(&self) -> &'static str {
+        "zig"
+    }
+
+    fn visit_type(&mut self, types: &Types, id: TypeId) {
+        add_declaration(&mut self.target_buf, &mut self.declared, types, id);
+    }
+
+    fn visit_entry_point(&mut self, types: &Types, name: &str, id: TypeId) {
+        add_entry_point(&mut self.target_buf, types, name, id);
+    }
+
+    fn end_target(&mut self, types: &Types) {
+        let arch = arch_name(types.target().architecture);
+        let declarations = std::mem::take(&mut self.target_buf);
+
+        self.declared.clear();
+        self.buf.push_str(&format!("\nconst {arch} = struct {{\n"));
+
+        for line in declarations.trim_end().lines() {
+            if !line.is_empty() {
+                self.buf.push_str(INDENT);
+                self.buf.push_str(line);
+            }
+
+            self.buf.push('\n');
+        }
+
+        self.buf.push_str("};\n");
+    }
+
+    fn finish(&mut self) -> Vec<File> {
+        let targets = std::mem::take(&mut self.buf);
+
+        vec![File {
+            name: "roc_app.zig".to_string(),
+            content: format!("{HEADER}{targets}"),
+        }]
+    }
+}
+
+fn add_declaration(buf: &mut String, declared: &mut MutSet<String>, types: &Types, id: TypeId) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } | RocType::TagUnionPayload { name, fields } => {
+            let fields: Vec<(String, TypeId)> = match fields {
+                RocStructFields::HasNoClosure { fields } => fields
+                    .iter()
+                    .map(|(field_name, field_id)| (escape_kw(field_name), *field_id))
+                    .collect(),
+                RocStructFields::HasClosure { fields } => fields
+                    .iter()
+                    .map(|(field_name, field_id, _)| (escape_kw(field_name), *field_id))
+                    .collect(),
+            };
+
+            add_struct(buf, types, id, &escape_kw(name), &fields);
+        }
+        RocType::TagUnion(RocTagUnion::SingleTagStruct {
+            name,
+            tag_name,
+            payload,
+        }) => {
+            let field_ids = match payload {
+                RocSingleTagPayload::HasNoClosure { payload_fields } => payload_fields.clone(),
+                RocSingleTagPayload::HasClosure { payload_getters } => {
+                    payload_getters.iter().map(|(id, _)| *id).collect()
+                }
+            };
+            let fields: Vec<(String, TypeId)> = field_ids
+                .into_iter()
+                .enumerate()
+                .map(|(index, field_id)| (format!("f{index}"), field_id))
+                .collect();
+
+            buf.push_str(&format!(
+                "// The payload of the `{tag_name}` tag, which is the only tag in this union.\n"
+            ));
+            add_struct(buf, types, id, &escape_kw(name), &fields);
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { name, tags, size }) => {
+            add_enum(
+                buf,
+                &escape_kw(name),
+                *size,
+                tags.iter().map(String::as_str),
+            );
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive {
+            name,
+            tags,
+            discriminant_size,
+            discriminant_offset,
+        }) => {
+            // If this tag union has no tags, it's uninhabited and can't be constructed.
+            if !tags.is_empty() {
+                add_non_recursive_tag_union(
+                    buf,
+                    types,
+                    id,
+                    &escape_kw(name),
+                    tags,
+                    *discriminant_size,
+                    *discriminant_offset,
+                );
+            }
+        }
+        RocType::TagUnion(
+            tag_union @ (RocTagUnion::Recursive { .. }
+            | RocTagUnion::NullableWrapped { .. }
+            | RocTagUnion::NullableUnwrapped { .. }
+            | RocTagUnion::NonNullableUnwrapped { .. }),
+        ) => add_heap_tag_union(buf, tag_union),
+        RocType::Function(roc_fn) => {
+            if !roc_fn.is_toplevel {
+                add_function(buf, types, roc_fn);
+            }
+        }
+        RocType::RocResult(ok_id, err_id) => {
+            let name = result_name(types, *ok_id, *err_id);
+
+            if declared.insert(name.clone()) {
+                let mut payloads = String::new();
+
+                add_union_member(&mut payloads, types, "ok", *ok_id);
+                add_union_member(&mut payloads, types, "err", *err_id);
+
+                buf.push_str(&format!("pub const {name} = extern struct {{\n"));
+                add_union(buf, &payloads);
+                buf.push_str(&format!("{INDENT}is_ok: u8,\n}};\n\n"));
+            }
+        }
+        RocType::RocDict(_, _) => add_opaque(buf, declared, types, id, "RocDict"),
+        RocType::RocSet(_) => add_opaque(buf, declared, types, id, "RocSet"),
+        // These are either declared in the file header, or zero-sized and never declared.
+        RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_)
+        | RocType::EmptyTagUnion
+        | RocType::RecursivePointer(_)
+        | RocType::Unit
+        | RocType::Unsized => {}
+    }
+}
+
+fn add_struct(
+    buf: &mut String,
+    types: &Types,
+    id: TypeId,
+    name: &str,
+    fields: &[(String, TypeId)],
+) {
+    buf.push_str(&format!("pub const {name} = extern struct {{\n"));
+
+    for (field_name, field_id) in fields {
+        add_field(buf, types, field_name, *field_id);
+    }
+
+    buf.push_str("};\n\n");
+    add_asserts(buf, &[size_assert(types, id, name)]);
+}
+
+/// A non-recursive tag union is laid out as a union of its payloads, followed
+/// by the discriminant (the tag id, in alphabetical order of the tag names).
+fn add_non_recursive_tag_union(
+    buf: &mut String,
+    types: &Types,
+    id: TypeId,
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+) {
+    let mut payloads = String::new();
+
+    for (tag_name, opt_payload) in tags {
+        if let Some(payload_id) = opt_payload {
+            add_union_member(&mut payloads, types, &escape_kw(tag_name), *payload_id);
+        }
+    }
+
+    add_enum(
+        buf,
+        &format!("{name}_Tag"),
+        discriminant_size,
+        tags.iter().map(|(tag_name, _)| tag_name.as_str()),
+    );
+    buf.push_str(&format!("pub const {name} = extern struct {{\n"));
+    add_union(buf, &payloads);
+    buf.push_str(&format!("{INDENT}discriminant: {name}_Tag,\n}};\n\n"));
+    add_asserts(
+        buf,
+        &[
+            size_assert(types, id, name),
+            format!(
+                "std.debug.assert(@offsetOf({name}, \"discriminant\") == {discriminant_offset});"
+            ),
+        ],
+    );
+}
+
+/// Recursive tag unions live on the heap; all the host gets is a pointer.
+/// This documents how the tag id is encoded for each kind of heap-allocated union.
+fn add_heap_tag_union(buf: &mut String, tag_union: &RocTagUnion) {
+    let name = escape_kw(tag_union_name(tag_union));
+    let (tag_names, comment): (Vec<&str>, String) = match tag_union {
+        RocTagUnion::Recursive {
+            tags,
+            discriminant_offset,
+            ..
+        } => (
+            tags.iter().map(|(tag_name, _)| tag_name.as_str()).collect(),
+            format!(
+                "// Points to the payload of the active tag. If the tag id fits in the pointer's\n\
+                 // unused low bits (there are fewer tags than the pointer's alignment), it's stored\n\
+                 // there; otherwise it's stored {discriminant_offset} bytes into the allocation."
+            ),
+        ),
+        RocTagUnion::NullableWrapped {
+            index_of_null_tag,
+            tags,
+            discriminant_offset,
+            ..
+        } => {
+            let null_tag = match tags.get(*index_of_null_tag as usize) {
+                Some((tag_name, _)) => tag_name,
+                None => unreachable!(
+                    "NullableWrapped {name} has no null tag. This should never happen, and means there was a bug in `roc glue`."
+                ),
+            };
+
+            (
+                tags.iter().map(|(tag_name, _)| tag_name.as_str()).collect(),
+                format!(
+                    "// NULL means `{null_tag}`. Otherwise, points to the payload of the active tag.\n\
+                     // If the tag id fits in the pointer's unused low bits (there are fewer tags than\n\
+                     // the pointer's alignment), it's stored there; otherwise it's stored {discriminant_offset} bytes\n\
+                     // into the allocation."
+                ),
+            )
+        }
+        RocTagUnion::NullableUnwrapped {
+            null_tag,
+            non_null_tag,
+            null_represents_first_tag,
+            ..
+        } => (
+            if *null_represents_first_tag {
+                vec![null_tag.as_str(), non_null_tag.as_str()]
+            } else {
+                vec![non_null_tag.as_str(), null_tag.as_str()]
+            },
+            format!(
+                "// NULL means `{null_tag}`. Otherwise, points to the payload of `{non_null_tag}`."
+            ),
+        ),
+        RocTagUnion::NonNullableUnwrapped { tag_name, .. } => (
+            vec![tag_name.as_str()],
+            format!(
+                "// Points to the payload of `{tag_name}`, which is the only tag in this union."
+            ),
+        ),
+        RocTagUnion::Enumeration { .. }
+        | RocTagUnion::NonRecursive { .. }
+        | RocTagUnion::SingleTagStruct { .. } => {
+            unreachable!("{name} is not a heap-allocated tag union")
+        }
+    };
+
+    // The tag id isn't stored in a field of its own, so any tag type that can count them will do.
+    add_enum(buf, &format!("{name}_Tag"), 1, tag_names.into_iter());
+    buf.push_str(&comment);
+    buf.push('\n');
+    buf.push_str(&format!(
+        "pub const {name} = extern struct {{\n{INDENT}pointer: ?*anyopaque,\n}};\n\n"
+    ));
+}
+
+/// The tags, numbered by their tag ids
+fn add_enum<'a>(buf: &mut String, name: &str, size: u32, tags: impl Iterator<Item = &'a str>) {
+    buf.push_str(&format!(
+        "pub const {name} = enum({}) {{\n",
+        unsigned_int(size)
+    ));
+
+    for (index, tag_name) in tags.enumerate() {
+        buf.push_str(&format!("{INDENT}{} = {index},\n", escape_kw(tag_name)));
+    }
+
+    buf.push_str("};\n\n");
+}
+
+/// A function that isn't exposed directly, e.g. a closure returned by an entry point.
+/// The host calls it by passing its captured environment to the extern function.
+fn add_function(buf: &mut String, types: &Types, roc_fn: &RocFn) {
+    let name = escape_kw(&roc_fn.function_name);
+    let mut arguments = arg_list(types, &roc_fn.args, |arg_id, _, index| {
+        format!("arg{index}: {}", pointer_to(types, arg_id, true))
+    });
+
+    if arguments.is_empty() {
+        // These always have a first argument that's a pointer, even if it's to nothing.
+        arguments.push_str("arg0: ?*const anyopaque");
+    }
+
+    buf.push_str(&format!("pub const {name} = extern struct {{\n"));
+
+    if is_unit(types.get_type(roc_fn.lambda_set)) {
+        // Extern structs with no fields have no size in Zig, but they do in C, so pad the
+        // ones for closures that capture nothing.
+        buf.push_str(&format!("{INDENT}_unused: u8,\n"));
+    } else {
+        add_field(buf, types, "closure_data", roc_fn.lambda_set);
+    }
+
+    buf.push_str("};\n\n");
+    buf.push_str(&format!(
+        "pub extern fn {}({arguments}, closure_data: [*]u8, output: {}) void;\n\n",
+        roc_fn.extern_name,
+        pointer_to(types, roc_fn.ret, false)
+    ));
+}
+
+/// Builtins whose layout the host shouldn't depend on only get the right size and alignment.
+fn add_opaque(
+    buf: &mut String,
+    declared: &mut MutSet<String>,
+    types: &Types,
+    id: TypeId,
+    name: &str,
+) {
+    if declared.insert(name.to_string()) {
+        let size = types.size_rounded_to_alignment(id);
+        let align = types.align(id);
+
+        buf.push_str(&format!(
+            "pub const {name} = extern struct {{\n{INDENT}bytes: [{size}]u8 align({align}),\n}};\n\n"
+        ));
+    }
+}
+
+fn add_entry_point(buf: &mut String, types: &Types, name: &str, id: TypeId) {
+    let signature = match types.get_type(id) {
+        RocType::Function(roc_fn) => {
+            let arguments = arg_list(types, &roc_fn.args, |arg_id, typ, index| {
+                // Values that are refcounted are passed by reference; Roc takes ownership of them.
+                if can_derive_copy(types, typ) {
+                    format!("arg{index}: {}", type_name(types, arg_id))
+                } else {
+                    format!("arg{index}: *{}", type_name(types, arg_id))
+                }
+            });
+            let ret = pointer_to(types, roc_fn.ret, false);
+
+            if arguments.is_empty() {
+                format!("ret: {ret}")
+            } else {
+                format!("ret: {ret}, {arguments}")
+            }
+        }
+        _ => format!("ret: {}", pointer_to(types, id, false)),
+    };
+
+    buf.push_str(&format!(
+        "pub extern fn roc__{name}_1_exposed_generic({signature}) void;\n\n"
+    ));
+}
+
+fn add_field(buf: &mut String, types: &Types, field_name: &str, id: TypeId) {
+    // There's nothing to store in a zero-sized field, so leave it out.
+    if !is_unit(types.get_type(id)) {
+        buf.push_str(&format!(
+            "{INDENT}{field_name}: {},\n",
+            type_name(types, id)
+        ));
+    }
+}
+
+fn add_union_member(buf: &mut String, types: &Types, field_name: &str, id: TypeId) {
+    if !is_unit(types.get_type(id)) {
+        buf.push_str(&format!(
+            "{INDENT}{INDENT}{field_name}: {},\n",
+            type_name(types, id)
+        ));
+    }
+}
+
+fn add_union(buf: &mut String, payloads: &str) {
+    if !payloads.is_empty() {
+        buf.push_str(&format!(
+            "{INDENT}payload: extern union {{\n{payloads}{INDENT}}},\n"
+        ));
+    }
+}
+
+fn size_assert(types: &Types, id: TypeId, name: &str) -> String {
+    format!(
+        "std.debug.assert(@sizeOf({name}) == {});",
+        types.size_rounded_to_alignment(id)
+    )
+}
+
+fn add_asserts(buf: &mut String, asserts: &[String]) {
+    buf.push_str("comptime {\n");
+
+    for assert in asserts {
+        buf.push_str(&format!("{INDENT}{assert}\n"));
+    }
+
+    buf.push_str("}\n\n");
+}
+
+/// The arguments' declarations, separated by commas. Zero-sized arguments are left out,
+/// since nothing gets passed for them anyway, but they still count toward the index.
+fn arg_list(
+    types: &Types,
+    args: &[TypeId],
+    fmt: impl Fn(TypeId, &RocType, usize) -> String,
+) -> String {
+    args.iter()
+        .enumerate()
+        .filter_map(|(index, arg_id)| {
+            let typ = types.get_type(*arg_id);
+
+            (!is_unit(typ)).then(|| fmt(*arg_id, typ, index))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn type_name(types: &Types, id: TypeId) -> String {
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "void".to_string(),
+        RocType::Unsized => "RocList".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(num) => num_type_name(*num).to_string(),
+        RocType::RocDict(_, _) => "RocDict".to_string(),
+        RocType::RocSet(_) => "RocSet".to_string(),
+        RocType::RocList(_) => "RocList".to_string(),
+        RocType::RocBox(_) => "RocBox".to_string(),
+        RocType::RocResult(ok_id, err_id) => result_name(types, *ok_id, *err_id),
+        RocType::RecursivePointer(content) => type_name(types, *content),
+        RocType::Struct { name, .. } | RocType::TagUnionPayload { name, .. } => escape_kw(name),
+        RocType::TagUnion(tag_union) => escape_kw(tag_union_name(tag_union)),
+        RocType::Function(roc_fn) => escape_kw(&roc_fn.function_name),
+    }
+}
+
+fn num_type_name(num: RocNum) -> &'static str {
+    match num {
+        RocNum::U8 => "u8",
+        RocNum::U16 => "u16",
+        RocNum::U32 => "u32",
+        RocNum::U64 => "u64",
+        RocNum::U128 => "u128",
+        RocNum::I8 => "i8",
+        RocNum::I16 => "i16",
+        RocNum::I32 => "i32",
+        RocNum::I64 => "i64",
+        RocNum::I128 => "i128",
+        RocNum::F32 => "f32",
+        RocNum::F64 => "f64",
+        RocNum::Dec => "RocDec",
+    }
+}
+
+/// Zig has no generics for extern types, so every combination of payloads gets its own Result type.
+fn result_name(types: &Types, ok_id: TypeId, err_id: TypeId) -> String {
+    format!(
+        "RocResult_{}_{}",
+        type_name(types, ok_id),
+        type_name(types, err_id)
+    )
+}
+
+/// Zig doesn't allow pointers to `void` in extern functions, so those become `anyopaque`.
+fn pointer_to(types: &Types, id: TypeId, is_const: bool) -> String {
+    match (is_unit(types.get_type(id)), is_const) {
+        (true, true) => "?*const anyopaque".to_string(),
+        (true, false) => "?*anyopaque".to_string(),
+        (false, true) => format!("*const {}", type_name(types, id)),
+        (false, false) => format!("*{}", type_name(types, id)),
+    }
+}
+
+fn unsigned_int(bytes: u32) -> &'static str {
+    match bytes {
+        0 | 1 => "u8",
+        2 => "u16",
+        4 => "u32",
+        _ => "u64",
+    }
+}
+
+fn escape_kw(input: &str) -> String {
+    if RESERVED_KEYWORDS.contains(&input) {
+        format!("@\"{input}\"")
+    } else {
+        input.to_string()
+    }
+}
+
+fn arch_name(arch: Architecture) -> &'static str {
+    match arch {
+        Architecture::Aarch32 => "arm",
+        Architecture::Aarch64 => "aarch64",
+        Architecture::Wasm32 => "wasm32",
+        Architecture::X86_32 => "x86",
+        Architecture::X86_64 => "x86_64",
+    }
+}
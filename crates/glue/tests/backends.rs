@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod test_backends {
+    use roc_glue::backend::{backend_for_lang, LANGS};
+
+    #[test]
+    fn every_lang_resolves_to_its_backend() {
+        assert_eq!(LANGS, ["rust", "c", "zig"]);
+
+        for lang in LANGS {
+            let backend = backend_for_lang(lang)
+                .unwrap_or_else(|| panic!("There's no backend for --lang {lang}"));
+
+            assert_eq!(backend.lang(), *lang);
+        }
+    }
+
+    #[test]
+    fn unknown_lang_does_not_resolve() {
+        assert!(backend_for_lang("typescript").is_none());
+        assert!(backend_for_lang("").is_none());
+        // Names are matched exactly, the same way clap matches `--lang`.
+        assert!(backend_for_lang("Rust").is_none());
+    }
+}
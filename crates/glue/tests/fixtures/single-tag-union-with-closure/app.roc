@@ -0,0 +1,7 @@
+app "app"
+    packages { pf: "platform.roc" }
+    imports []
+    provides [main] to pf
+
+main : Str -> Str
+main = \name -> "Hello, \(name)!"
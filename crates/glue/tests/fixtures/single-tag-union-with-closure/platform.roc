@@ -0,0 +1,11 @@
+platform "test-platform"
+    requires {} { main : Str -> Str }
+    exposes []
+    packages {}
+    imports []
+    provides [mainForHost]
+
+Greeter : [Greeter (Str -> Str)]
+
+mainForHost : Str -> Greeter
+mainForHost = \suffix -> Greeter \name -> Str.concat (main name) suffix
@@ -0,0 +1,201 @@
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_c {
+    use crate::helpers::generate_bindings_for_lang;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    /// The header `roc glue --lang c` generates for the given declarations
+    fn c_header(decl_src: &str) -> String {
+        let mut files = generate_bindings_for_lang("c", decl_src);
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "roc_app.h");
+
+        files.remove(0).content
+    }
+
+    /// The part of the header for one target, between its `#if` and `#endif`
+    fn arch_section<'a>(header: &'a str, arch: &str) -> &'a str {
+        let start_marker = format!(" // {arch}\n\n");
+        let end_marker = format!("#endif // {arch}\n");
+        let start = header
+            .find(&start_marker)
+            .unwrap_or_else(|| panic!("no {arch} section in:\n\n{header}"))
+            + start_marker.len();
+        let end = start + header[start..].find(&end_marker).unwrap();
+
+        &header[start..end]
+    }
+
+    #[test]
+    fn one_section_per_target() {
+        let header = c_header(indoc!(
+            r#"
+            mainForHost : U64
+            mainForHost = main
+            "#
+        ));
+
+        assert!(header.starts_with("// ⚠️ GENERATED CODE ⚠️"));
+        assert!(header.contains("#ifndef ROC_APP_H\n#define ROC_APP_H\n"));
+        assert!(header.ends_with("#endif // ROC_APP_H\n"));
+
+        for (condition, arch) in [
+            ("defined(__arm__) || defined(_M_ARM)", "arm"),
+            ("defined(__aarch64__) || defined(_M_ARM64)", "aarch64"),
+            ("defined(__wasm32__)", "wasm32"),
+            ("defined(__i386__) || defined(_M_IX86)", "x86"),
+            ("defined(__x86_64__) || defined(_M_X64)", "x86_64"),
+        ] {
+            assert!(header.contains(&format!("#if {condition} // {arch}\n")));
+            assert_eq!(
+                arch_section(&header, arch),
+                "extern void roc__mainForHost_1_exposed_generic(uint64_t *ret);\n\n"
+            );
+        }
+    }
+
+    #[test]
+    fn nested_record_declared_after_its_fields() {
+        let header = c_header(indoc!(
+            r#"
+            Outer : { x : Inner, y : Str }
+
+            Inner : { a : U16, b : F32 }
+
+            mainForHost : Outer
+            mainForHost = main
+            "#
+        ));
+
+        assert_eq!(
+            arch_section(&header, "x86_64"),
+            indoc!(
+                r#"
+                typedef struct Inner {
+                    float b;
+                    uint16_t a;
+                } Inner;
+                _Static_assert(sizeof(Inner) == 8, "Inner has the wrong size");
+
+                typedef struct Outer {
+                    RocStr y;
+                    Inner x;
+                } Outer;
+                _Static_assert(sizeof(Outer) == 32, "Outer has the wrong size");
+
+                extern void roc__mainForHost_1_exposed_generic(Outer *ret);
+
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn record_in_tag_union_declared_first() {
+        let header = c_header(indoc!(
+            r#"
+            Shape : [Circle Point U32, Dot Point]
+
+            Point : { x : I32, y : I32 }
+
+            mainForHost : Shape
+            mainForHost = main
+            "#
+        ));
+        let x86_64 = arch_section(&header, "x86_64");
+        let point = x86_64.find("typedef struct Point {").unwrap();
+        let shape = x86_64.find("typedef struct Shape {").unwrap();
+
+        assert!(point < shape, "{x86_64}");
+    }
+
+    #[test]
+    fn enumeration() {
+        let header = c_header(indoc!(
+            r#"
+            MyEnum : [Foo, Bar, Baz]
+
+            mainForHost : MyEnum
+            mainForHost = main
+            "#
+        ));
+
+        assert_eq!(
+            arch_section(&header, "x86_64"),
+            indoc!(
+                r#"
+                enum MyEnum_Tag {
+                    MyEnum_Bar = 0,
+                    MyEnum_Baz = 1,
+                    MyEnum_Foo = 2,
+                };
+
+                typedef uint8_t MyEnum;
+
+                extern void roc__mainForHost_1_exposed_generic(MyEnum *ret);
+
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn non_recursive_tag_union() {
+        let header = c_header(indoc!(
+            r#"
+            NonRecursive : [Foo Str, Bar U64, Baz]
+
+            mainForHost : NonRecursive
+            mainForHost = main
+            "#
+        ));
+        let x86_64 = arch_section(&header, "x86_64");
+
+        assert!(x86_64.contains(indoc!(
+            r#"
+            enum NonRecursive_Tag {
+                NonRecursive_Bar = 0,
+                NonRecursive_Baz = 1,
+                NonRecursive_Foo = 2,
+            };
+            "#
+        )));
+        assert!(x86_64.contains("    uint8_t discriminant; // NonRecursive_Tag\n"));
+        assert!(x86_64.contains(
+            "_Static_assert(sizeof(NonRecursive) == 32, \"NonRecursive has the wrong size\");\n"
+        ));
+        assert!(x86_64.contains("_Static_assert(offsetof(NonRecursive, discriminant) == 24, \"NonRecursive has the wrong discriminant offset\");\n"));
+    }
+
+    #[test]
+    fn recursive_tag_union_is_a_pointer() {
+        let header = c_header(indoc!(
+            r#"
+            Expr : [String Str, Concat Expr Expr]
+
+            mainForHost : Expr
+            mainForHost = main
+            "#
+        ));
+        let x86_64 = arch_section(&header, "x86_64");
+
+        // It's declared before anything else, so that its payloads can refer to it.
+        assert!(
+            x86_64.starts_with(indoc!(
+                r#"
+                enum Expr_Tag {
+                    Expr_Concat = 0,
+                    Expr_String = 1,
+                };
+
+                "#
+            )),
+            "{x86_64}"
+        );
+        assert!(x86_64.contains("typedef struct Expr {\n    void *pointer;\n} Expr;\n"));
+        assert!(x86_64.ends_with("extern void roc__mainForHost_1_exposed_generic(Expr *ret);\n\n"));
+    }
+}
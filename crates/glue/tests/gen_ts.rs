@@ -0,0 +1,188 @@
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_ts {
+    use crate::helpers::generate_bindings_with_spec;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    /// The module TypeScriptGlue.roc generates for the given declarations
+    fn ts_module(decl_src: &str) -> String {
+        let mut files = generate_bindings_with_spec("TypeScriptGlue.roc", decl_src);
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "roc_app.ts");
+
+        files.remove(0).content
+    }
+
+    /// The part of the module after the `RocMemory` class every module starts with
+    fn after_runtime(module: &str) -> &str {
+        let class_start = module
+            .find("export class RocMemory {")
+            .unwrap_or_else(|| panic!("no RocMemory class in:\n\n{module}"));
+        let class_end = class_start + module[class_start..].find("\n}\n").unwrap() + "\n}\n".len();
+
+        module[class_end..].trim_start_matches('\n')
+    }
+
+    /// Indents every non-empty line, e.g. to match a method inside `RocApp`
+    fn indented(lines: &str, indent: &str) -> String {
+        lines
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    "\n".to_string()
+                } else {
+                    format!("{indent}{line}\n")
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn record() {
+        let module = ts_module(indoc!(
+            r#"
+            Person : { name : Str, age : U32 }
+
+            mainForHost : Person
+            mainForHost = main
+            "#
+        ));
+
+        assert!(module.starts_with("// ⚠️ GENERATED CODE ⚠️"));
+        assert_eq!(
+            after_runtime(&module),
+            indoc!(
+                r#"
+                export interface Person {
+                    age: number;
+                    name: string;
+                }
+
+                function read_Person(mem: RocMemory, ptr: number): Person {
+                    return {
+                        age: mem.view.getUint32(ptr, true),
+                        name: mem.readStr(ptr + 4),
+                    };
+                }
+
+                function write_Person(mem: RocMemory, ptr: number, value: Person): void {
+                    mem.view.setUint32(ptr, value.age, true);
+                    mem.writeStr(ptr + 4, value.name);
+                }
+
+                export class RocApp {
+                    readonly memory: RocMemory;
+
+                    constructor(readonly exports: RocExports) {
+                        this.memory = new RocMemory(exports);
+                    }
+
+                    mainForHost(): Person {
+                        const mem = this.memory;
+                        const ret = mem.alloc(16, 4);
+                        this.exports.roc__mainForHost_1_exposed_generic(ret);
+
+                        const result = read_Person(mem, ret);
+
+                        mem.free(ret, 4);
+
+                        return result;
+                    }
+                }
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn function_arguments_go_through_linear_memory() {
+        let module = ts_module(indoc!(
+            r#"
+            mainForHost : Str, U32 -> U64
+            mainForHost = main
+            "#
+        ));
+
+        assert!(
+            module.contains(&indented(
+                indoc!(
+                    r#"
+                    mainForHost(arg0: string, arg1: number): bigint {
+                        const mem = this.memory;
+                        const ret = mem.alloc(8, 8);
+                        const arg0Ptr = mem.alloc(12, 4);
+                        mem.writeStr(arg0Ptr, arg0);
+                        this.exports.roc__mainForHost_1_exposed_generic(ret, arg0Ptr, arg1);
+
+                        const result = mem.view.getBigUint64(ret, true);
+
+                        mem.free(ret, 8);
+                        mem.free(arg0Ptr, 4);
+
+                        return result;
+                    }
+                    "#
+                ),
+                "    "
+            )),
+            "{module}"
+        );
+    }
+
+    #[test]
+    fn enumeration() {
+        let module = ts_module(indoc!(
+            r#"
+            MyEnum : [Foo, Bar, Baz]
+
+            mainForHost : MyEnum -> MyEnum
+            mainForHost = main
+            "#
+        ));
+
+        assert!(module.contains(indoc!(
+            r#"
+            const MyEnum_tags = ["Bar", "Baz", "Foo"] as const;
+            export type MyEnum = typeof MyEnum_tags[number];
+
+            function read_MyEnum(mem: RocMemory, ptr: number): MyEnum {
+                return MyEnum_tags[mem.view.getUint8(ptr)];
+            }
+            "#
+        )));
+        // Enumerations are passed to Roc as their discriminant.
+        assert!(module.contains(
+            "this.exports.roc__mainForHost_1_exposed_generic(ret, MyEnum_tags.indexOf(arg0));"
+        ));
+    }
+
+    #[test]
+    fn non_recursive_tag_union() {
+        let module = ts_module(indoc!(
+            r#"
+            NonRecursive : [Foo Str, Bar U32, Baz]
+
+            mainForHost : NonRecursive
+            mainForHost = main
+            "#
+        ));
+
+        assert!(module.contains(
+            "export type NonRecursive = { tag: \"Bar\"; payload: number } | { tag: \"Baz\" } | { tag: \"Foo\"; payload: string };\n"
+        ));
+        // The discriminant comes after the largest payload, which is the Str.
+        assert!(module.contains("    switch (mem.view.getUint8(ptr + 12)) {\n"));
+        assert!(module.contains(&indented(
+            indoc!(
+                r#"
+                case 2:
+                    return { tag: "Foo", payload: mem.readStr(ptr) };
+                "#
+            ),
+            "        "
+        )));
+    }
+}
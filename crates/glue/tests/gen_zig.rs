@@ -0,0 +1,170 @@
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_zig {
+    use crate::helpers::generate_bindings_for_lang;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    /// The module `roc glue --lang zig` generates for the given declarations
+    fn zig_module(decl_src: &str) -> String {
+        let mut files = generate_bindings_for_lang("zig", decl_src);
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "roc_app.zig");
+
+        files.remove(0).content
+    }
+
+    /// The declarations for one target, including the struct they're in
+    fn arch_struct<'a>(module: &'a str, arch: &str) -> &'a str {
+        let start_marker = format!("\nconst {arch} = struct {{\n");
+        let start = module
+            .find(&start_marker)
+            .unwrap_or_else(|| panic!("no {arch} struct in:\n\n{module}"))
+            + 1;
+        let end = start + module[start..].find("\n};\n").unwrap() + "\n};\n".len();
+
+        &module[start..end]
+    }
+
+    #[test]
+    fn one_struct_per_target() {
+        let module = zig_module(indoc!(
+            r#"
+            mainForHost : U64
+            mainForHost = main
+            "#
+        ));
+
+        assert!(module.starts_with("// ⚠️ GENERATED CODE ⚠️"));
+
+        for (cpu_arch, arch) in [
+            ("arm", "arm"),
+            ("aarch64", "aarch64"),
+            ("wasm32", "wasm32"),
+            ("i386", "x86"),
+            ("x86_64", "x86_64"),
+        ] {
+            assert!(module.contains(&format!("    .{cpu_arch} => {arch},\n")));
+            assert_eq!(
+                arch_struct(&module, arch),
+                format!(
+                    "const {arch} = struct {{\n    pub extern fn roc__mainForHost_1_exposed_generic(ret: *u64) void;\n}};\n"
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn nested_record() {
+        let module = zig_module(indoc!(
+            r#"
+            Outer : { x : Inner, y : Str }
+
+            Inner : { a : U16, b : F32 }
+
+            mainForHost : Outer
+            mainForHost = main
+            "#
+        ));
+
+        assert_eq!(
+            arch_struct(&module, "x86_64"),
+            indoc!(
+                r#"
+                const x86_64 = struct {
+                    pub const Inner = extern struct {
+                        b: f32,
+                        a: u16,
+                    };
+
+                    comptime {
+                        std.debug.assert(@sizeOf(Inner) == 8);
+                    }
+
+                    pub const Outer = extern struct {
+                        y: RocStr,
+                        x: Inner,
+                    };
+
+                    comptime {
+                        std.debug.assert(@sizeOf(Outer) == 32);
+                    }
+
+                    pub extern fn roc__mainForHost_1_exposed_generic(ret: *Outer) void;
+                };
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn enumeration() {
+        let module = zig_module(indoc!(
+            r#"
+            MyEnum : [Foo, Bar, Baz]
+
+            mainForHost : MyEnum
+            mainForHost = main
+            "#
+        ));
+
+        assert_eq!(
+            arch_struct(&module, "x86_64"),
+            indoc!(
+                r#"
+                const x86_64 = struct {
+                    pub const MyEnum = enum(u8) {
+                        Bar = 0,
+                        Baz = 1,
+                        Foo = 2,
+                    };
+
+                    pub extern fn roc__mainForHost_1_exposed_generic(ret: *MyEnum) void;
+                };
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn non_recursive_tag_union() {
+        let module = zig_module(indoc!(
+            r#"
+            NonRecursive : [Foo Str, Bar U64, Baz]
+
+            mainForHost : NonRecursive
+            mainForHost = main
+            "#
+        ));
+        let x86_64 = arch_struct(&module, "x86_64");
+
+        assert!(x86_64.contains(concat!(
+            "    pub const NonRecursive = extern struct {\n",
+            "        payload: extern union {\n",
+            "            Bar: u64,\n",
+            "            Foo: RocStr,\n",
+            "        },\n",
+            "        discriminant: NonRecursive_Tag,\n",
+            "    };\n",
+        )));
+        assert!(x86_64.contains(
+            "        std.debug.assert(@offsetOf(NonRecursive, \"discriminant\") == 24);\n"
+        ));
+    }
+
+    #[test]
+    fn keywords_are_escaped() {
+        let module = zig_module(indoc!(
+            r#"
+            Rcd : { error : Str, test : U8 }
+
+            mainForHost : Rcd
+            mainForHost = main
+            "#
+        ));
+
+        assert!(module.contains("        @\"error\": RocStr,\n        @\"test\": u8,\n"));
+    }
+}
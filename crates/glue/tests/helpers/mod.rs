@@ -49,6 +49,121 @@ pub fn generate_bindings(decl_src: &str) -> Vec<roc_glue::types::File> {
     rust_glue::emit(&types)
 }
 
+/// Like [generate_bindings], but with the built-in backend for the given language,
+/// the same way `roc glue --lang <lang>` would.
+/// The given declarations must include a `mainForHost`, e.g. `mainForHost = main`.
+#[allow(dead_code)]
+pub fn generate_bindings_for_lang(lang: &str, decl_src: &str) -> Vec<roc_glue::types::File> {
+    let mut backend = roc_glue::backend::backend_for_lang(lang)
+        .unwrap_or_else(|| panic!("There's no backend for --lang {lang}"));
+    let mut src = indoc!(
+        r#"
+            platform "test-platform"
+                requires {} { main : _ }
+                exposes []
+                packages {}
+                imports []
+                provides [mainForHost]
+
+        "#
+    )
+    .to_string();
+
+    src.push_str(decl_src);
+
+    let types = {
+        let dir = tempfile::tempdir().expect("Unable to create tempdir");
+        let file_path = dir.path().join("platform.roc");
+        let mut file = File::create(&file_path).unwrap();
+        writeln!(file, "{}", &src).unwrap();
+
+        let result = load_types(file_path, Threading::Single, IgnoreErrors { can: false });
+
+        dir.close().expect("Unable to close tempdir");
+
+        result.expect("had problems loading")
+    };
+
+    roc_glue::backend::emit(backend.as_mut(), &types)
+}
+
+/// Like [generate_bindings], but with one of the glue specs in `crates/glue/src`
+/// (e.g. `TypeScriptGlue.roc`), which gets built and run the same way `roc glue` would.
+/// The given declarations must include a `mainForHost`, e.g. `mainForHost = main`.
+#[allow(dead_code)]
+pub fn generate_bindings_with_spec(
+    spec_file_name: &str,
+    decl_src: &str,
+) -> Vec<roc_glue::types::File> {
+    use roc_build::program::CodeGenBackend;
+    use roc_gen_llvm::llvm::build::LlvmBackendMode;
+    use std::fs;
+
+    let dir = tempfile::tempdir().expect("Unable to create tempdir");
+    let glue_dir = root_dir().join("crates").join("glue");
+
+    // Build a copy of the spec (and the platform it refers to by relative path), so that tests
+    // building the same spec at the same time don't overwrite each other's binaries.
+    let spec_path = dir.path().join("src").join(spec_file_name);
+    let platform_dir = dir.path().join("platform");
+
+    fs::create_dir_all(spec_path.parent().unwrap()).unwrap();
+    fs::create_dir_all(&platform_dir).unwrap();
+    fs::copy(glue_dir.join("src").join(spec_file_name), &spec_path).unwrap();
+
+    for entry in fs::read_dir(glue_dir.join("platform")).unwrap() {
+        let entry = entry.unwrap();
+
+        fs::copy(entry.path(), platform_dir.join(entry.file_name())).unwrap();
+    }
+
+    let mut src = indoc!(
+        r#"
+            platform "test-platform"
+                requires {} { main : _ }
+                exposes []
+                packages {}
+                imports []
+                provides [mainForHost]
+
+        "#
+    )
+    .to_string();
+
+    src.push_str(decl_src);
+
+    let platform_path = dir.path().join("platform.roc");
+    let output_dir = dir.path().join("glue");
+
+    fs::write(&platform_path, src).unwrap();
+
+    let exit_code = roc_glue::generate(
+        &platform_path,
+        &output_dir,
+        &spec_path,
+        CodeGenBackend::Llvm(LlvmBackendMode::BinaryGlue),
+    )
+    .expect("had problems generating glue");
+
+    assert_eq!(exit_code, 0, "glue generation failed");
+
+    let mut files: Vec<roc_glue::types::File> = fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+
+            roc_glue::types::File {
+                name: path.file_name().unwrap().to_str().unwrap().to_string(),
+                content: fs::read_to_string(&path).unwrap(),
+            }
+        })
+        .collect();
+
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    files
+}
+
 #[allow(dead_code)]
 pub fn fixtures_dir(dir_name: &str) -> PathBuf {
     let mut path = root_dir();
@@ -77,6 +77,9 @@ mod glue_cli_run {
         single_tag_union:"single-tag-union" => indoc!(r#"
             tag_union was: SingleTagUnion::OneTag
         "#),
+        single_tag_union_with_closure:"single-tag-union-with-closure" => indoc!(r#"
+            Greeter was cloned and dropped
+        "#),
         union_with_padding:"union-with-padding" => indoc!(r#"
             tag_union was: NonRecursive::Foo("This is a test")
             `Foo "small str"` is: NonRecursive::Foo("small str")
@@ -135,6 +138,48 @@ mod glue_cli_run {
         "#),
     }
 
+    #[test]
+    fn glue_with_lang() {
+        let platform_module_path = fixtures_dir("basic-record").join("platform.roc");
+        let glue_dir = tempfile::tempdir().unwrap();
+
+        // With --lang, the output directory goes where the spec would otherwise go.
+        let glue_out = run_glue([
+            "glue",
+            "--lang",
+            "c",
+            glue_dir.path().to_str().unwrap(),
+            platform_module_path.to_str().unwrap(),
+        ]);
+
+        assert!(glue_out.status.success(), "bad status {glue_out:?}");
+
+        let header = fs::read_to_string(glue_dir.path().join("roc_app.h")).unwrap();
+
+        assert!(header.contains("typedef struct MyRcd {\n"), "{header}");
+        assert!(header.contains("extern void roc__mainForHost_1_exposed_generic(MyRcd *ret);\n"));
+    }
+
+    #[test]
+    fn glue_with_lang_and_spec() {
+        let platform_module_path = fixtures_dir("basic-record").join("platform.roc");
+        let glue_dir = tempfile::tempdir().unwrap();
+
+        let glue_out = run_glue([
+            "glue",
+            "--lang",
+            "c",
+            "RustGlue.roc",
+            glue_dir.path().to_str().unwrap(),
+            platform_module_path.to_str().unwrap(),
+        ]);
+
+        assert!(!glue_out.status.success());
+        assert!(glue_out
+            .stderr
+            .contains("can be given either a spec or `--lang`, but not both"));
+    }
+
     fn check_for_tests(all_fixtures: &mut roc_collections::VecSet<String>) {
         use roc_collections::VecSet;
 
@@ -0,0 +1,42 @@
+//! The connection file that Jupyter starts a kernel with.
+use serde::Deserialize;
+use std::io;
+use std::path::Path;
+
+/// Where the kernel's sockets go, and how to sign the messages sent over them.
+#[derive(Debug, Deserialize)]
+pub struct ConnectionInfo {
+    /// `tcp` or `ipc`
+    pub transport: String,
+    pub ip: String,
+    pub shell_port: u16,
+    pub control_port: u16,
+    pub iopub_port: u16,
+    pub stdin_port: u16,
+    pub hb_port: u16,
+    /// Empty if messages aren't signed.
+    pub key: String,
+    pub signature_scheme: String,
+}
+
+impl ConnectionInfo {
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+
+        serde_json::from_str(&json).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} isn't a Jupyter connection file: {err}", path.display()),
+            )
+        })
+    }
+
+    /// The address to bind the socket with the given port to.
+    pub fn endpoint(&self, port: u16) -> String {
+        match self.transport.as_str() {
+            // For IPC, the "ip" is a path, and each socket gets its own file next to it.
+            "ipc" => format!("ipc://{}-{port}", self.ip),
+            transport => format!("{transport}://{}:{port}", self.ip),
+        }
+    }
+}
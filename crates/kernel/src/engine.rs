@@ -0,0 +1,279 @@
+//! Running cells, using the same REPL engine as `roc repl`.
+use bumpalo::Bump;
+use roc_repl_cli::{run_evaluation, EvalLimits};
+use roc_repl_eval::gen::{DisplaySize, Problems, ReplOutput};
+use roc_repl_ui::repl_state::{ReplAction, ReplState};
+use roc_repl_ui::{
+    expect_passed, format_doc_output, format_env_output, format_expect_output, format_output,
+    format_search_output, split_entries, TIPS,
+};
+use roc_reporting::report::{
+    strip_colors, StyleCodes, HTML_STYLE_CODES, PLAIN_PALETTE, PLAIN_STYLE_CODES,
+};
+use roc_target::TargetInfo;
+use target_lexicon::Triple;
+
+const NO_LAST_OUTPUT: &str =
+    "There's no result yet! :expand and :save work with the result of the last expression.";
+
+/// Something a cell showed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The value of an expression, with its type
+    Value { plain: String, html: String },
+    /// Anything else, like the answer to `:type` or what a `dbg` in a def showed
+    Text(String),
+    /// A problem that stopped the cell, so none of the entries after it ran
+    Error(String),
+}
+
+/// Runs cells one after another, remembering the defs from each one for the next.
+pub struct Engine {
+    state: ReplState,
+    target: Triple,
+    last_output: Option<ReplOutput>,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self {
+            state: ReplState::new(),
+            target: Triple::host(),
+            last_output: None,
+        }
+    }
+}
+
+impl Engine {
+    /// Run each entry in the cell, as if it had been entered into the REPL line by line.
+    pub fn execute(&mut self, code: &str) -> Vec<Output> {
+        let mut outputs = Vec::new();
+
+        for entry in split_entries(code) {
+            match self.step(&entry) {
+                Some(Output::Error(message)) => {
+                    outputs.push(Output::Error(message));
+
+                    break;
+                }
+                Some(output) => outputs.push(output),
+                None => {}
+            }
+        }
+
+        outputs
+    }
+
+    /// Completions for the word ending at byte offset `pos` in `code`: where the word
+    /// starts, and what it could be.
+    pub fn completions(&self, code: &str, pos: usize) -> (usize, Vec<String>) {
+        self.state
+            .completions(code, pos, TargetInfo::from(&self.target))
+    }
+
+    fn step(&mut self, src: &str) -> Option<Output> {
+        let arena = Bump::new();
+        let target_info = TargetInfo::from(&self.target);
+
+        match self.state.step(&arena, src, target_info, PLAIN_PALETTE) {
+            ReplAction::Eval {
+                opt_mono,
+                mut problems,
+                instantiated_type,
+                compile_time,
+            } => {
+                let opt_output = run_evaluation(
+                    opt_mono,
+                    &mut problems,
+                    &self.target,
+                    EvalLimits::default(),
+                    DisplaySize::default(),
+                    compile_time,
+                )
+                .map(|output| ReplOutput {
+                    instantiated_type,
+                    ..output
+                });
+
+                if let Some(output) = &opt_output {
+                    if !output.expr.is_empty() {
+                        self.last_output = Some(output.clone());
+                    }
+                }
+
+                value_output(opt_output, problems)
+            }
+            ReplAction::Expect {
+                opt_mono,
+                mut problems,
+                failure_report,
+            } => {
+                let opt_output = run_evaluation(
+                    opt_mono,
+                    &mut problems,
+                    &self.target,
+                    EvalLimits::default(),
+                    DisplaySize::default(),
+                    None,
+                );
+                let passed = expect_passed(opt_output, failure_report, &mut problems);
+                let output = format_expect_output(PLAIN_STYLE_CODES, passed, problems);
+
+                if passed {
+                    text_output(output)
+                } else {
+                    error_output(output)
+                }
+            }
+            ReplAction::TypeOf {
+                opt_output,
+                problems,
+            } => value_output(opt_output, problems),
+            ReplAction::Doc {
+                opt_output,
+                docs,
+                problems,
+            } => {
+                let has_errors = !problems.errors.is_empty();
+                let output = format_doc_output(PLAIN_STYLE_CODES, opt_output, docs, problems);
+
+                if has_errors {
+                    error_output(output)
+                } else {
+                    text_output(output)
+                }
+            }
+            ReplAction::Env { types, problems } => {
+                text_output(format_env_output(PLAIN_STYLE_CODES, types, problems))
+            }
+            ReplAction::Search { results, problems } => {
+                text_output(format_search_output(PLAIN_STYLE_CODES, results, problems))
+            }
+            ReplAction::Load { problems }
+            | ReplAction::Set { problems }
+            | ReplAction::Record { problems } => {
+                if problems.errors.is_empty() {
+                    text_output(format_output(PLAIN_STYLE_CODES, None, problems))
+                } else {
+                    error_output(format_output(PLAIN_STYLE_CODES, None, problems))
+                }
+            }
+            // Results are never shortened in a notebook, but `:expand` can still show the
+            // last one again.
+            ReplAction::Expand => match &self.last_output {
+                Some(output) => value_output(
+                    Some(ReplOutput {
+                        dbgs: Vec::new(),
+                        shortened_expr: None,
+                        ..output.clone()
+                    }),
+                    Problems::default(),
+                ),
+                None => error_output(NO_LAST_OUTPUT.to_string()),
+            },
+            ReplAction::Save { path } => match &self.last_output {
+                Some(output) => match std::fs::write(&path, format!("{}\n", output.expr)) {
+                    Ok(()) => None,
+                    Err(err) => error_output(format!("Couldn't save the result to {path}: {err}")),
+                },
+                None => error_output(NO_LAST_OUTPUT.to_string()),
+            },
+            ReplAction::Help => text_output(strip_colors(TIPS)),
+            // Jupyter decides when the kernel stops, so there's nothing to exit.
+            ReplAction::Exit | ReplAction::Nothing => None,
+        }
+    }
+}
+
+/// The output of an entry that may have had a value, like an expression or `:type`.
+fn value_output(opt_output: Option<ReplOutput>, problems: Problems) -> Option<Output> {
+    if !problems.errors.is_empty() {
+        return error_output(format_output(PLAIN_STYLE_CODES, None, problems));
+    }
+
+    match opt_output {
+        Some(output) if !output.expr.is_empty() => {
+            let html_output = ReplOutput {
+                expr: escape_html(&output.expr),
+                expr_type: escape_html(&output.expr_type),
+                dbgs: output.dbgs.iter().map(|dbg| escape_html(dbg)).collect(),
+                shortened_expr: output.shortened_expr.as_deref().map(escape_html),
+                instantiated_type: output.instantiated_type.as_deref().map(escape_html),
+                timings: output.timings,
+            };
+            let html_problems = Problems {
+                errors: Vec::new(),
+                warnings: problems.warnings.iter().map(|w| escape_html(w)).collect(),
+            };
+
+            Some(Output::Value {
+                plain: render(PLAIN_STYLE_CODES, Some(output), problems),
+                html: format!(
+                    "<pre>{}</pre>",
+                    render(HTML_STYLE_CODES, Some(html_output), html_problems)
+                ),
+            })
+        }
+        // e.g. a def, which only shows its warnings and what any `dbg`s in it showed
+        opt_output => text_output(format_output(PLAIN_STYLE_CODES, opt_output, problems)),
+    }
+}
+
+/// Render an output the way the REPL does, without the blank line it leaves before each one.
+fn render(style_codes: StyleCodes, opt_output: Option<ReplOutput>, problems: Problems) -> String {
+    format_output(style_codes, opt_output, problems)
+        .trim_start_matches('\n')
+        .to_string()
+}
+
+fn text_output(text: String) -> Option<Output> {
+    let text = text.trim_start_matches('\n');
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(Output::Text(text.to_string()))
+    }
+}
+
+fn error_output(message: String) -> Option<Output> {
+    Some(Output::Error(message.trim().to_string()))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn records_render_as_text_and_html() {
+        let mut engine = Engine::default();
+
+        engine.execute("x = 42");
+
+        let outputs = engine.execute("{ answer: x, name: \"<b>\" }");
+        let [Output::Value { plain, html }] = outputs.as_slice() else {
+            panic!("Expected one value, but got {outputs:?}");
+        };
+
+        assert_eq!(
+            plain,
+            "{ answer: 42, name: \"<b>\" } : { answer : Num *, name : Str }"
+        );
+        assert!(html.starts_with("<pre>{ answer: 42, name: \"&lt;b&gt;\" }"));
+        assert!(html.ends_with("{ answer : Num *, name : Str }</pre>"));
+    }
+
+    #[test]
+    fn errors_stop_the_cell() {
+        let mut engine = Engine::default();
+        let outputs = engine.execute("1 + \"a\"\n2");
+
+        assert!(matches!(outputs.as_slice(), [Output::Error(_)]));
+    }
+}
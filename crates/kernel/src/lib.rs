@@ -0,0 +1,354 @@
+//! A [Jupyter] kernel for Roc, so notebooks can mix Markdown with cells of Roc code.
+//!
+//! Each code cell runs like a series of entries in `roc repl`, and every cell in the
+//! notebook shares the same REPL state, so defs from earlier cells can be used in later ones.
+//! Values come back as both `text/plain` and `text/html`, rendered the same way as in the
+//! command-line and web REPLs.
+//!
+//! [Jupyter]: https://jupyter-client.readthedocs.io/en/stable/messaging.html
+#![warn(clippy::dbg_macro)]
+mod connection;
+mod engine;
+mod message;
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use roc_repl_ui::{continuation_indent, is_incomplete};
+use serde_json::{json, Value};
+
+use connection::ConnectionInfo;
+use engine::{Engine, Output};
+use message::{Message, Signer, PROTOCOL_VERSION};
+
+/// Serve a notebook over the sockets described in the connection file that Jupyter starts
+/// the kernel with. Returns the exit code once Jupyter asks the kernel to shut down.
+pub fn run(connection_file: &Path) -> io::Result<i32> {
+    let info = ConnectionInfo::read(connection_file)?;
+    let signer = Signer::new(&info.signature_scheme, &info.key)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+    let context = zmq::Context::new();
+    let bind = |kind, port| -> io::Result<zmq::Socket> {
+        let socket = context.socket(kind)?;
+
+        socket.bind(&info.endpoint(port))?;
+
+        Ok(socket)
+    };
+
+    let shell = bind(zmq::ROUTER, info.shell_port)?;
+    let control = bind(zmq::ROUTER, info.control_port)?;
+    let iopub = bind(zmq::PUB, info.iopub_port)?;
+    // The kernel never asks for input, but Jupyter still connects to this.
+    let _stdin = bind(zmq::ROUTER, info.stdin_port)?;
+    let heartbeat = bind(zmq::REP, info.hb_port)?;
+
+    // Jupyter checks that the kernel is alive by having it echo messages back, which needs
+    // to keep happening while a cell runs.
+    thread::spawn(move || -> zmq::Result<()> {
+        loop {
+            let ping = heartbeat.recv_bytes(0)?;
+
+            heartbeat.send(ping, 0)?;
+        }
+    });
+
+    let mut kernel = Kernel {
+        session: uuid::Uuid::new_v4().to_string(),
+        signer,
+        iopub,
+        engine: Engine::default(),
+        execution_count: 0,
+    };
+
+    kernel.publish(&Message::new(
+        &kernel.session,
+        "status",
+        json!({ "execution_state": "starting" }),
+    ))?;
+
+    loop {
+        let mut items = [
+            shell.as_poll_item(zmq::POLLIN),
+            control.as_poll_item(zmq::POLLIN),
+        ];
+
+        zmq::poll(&mut items, -1)?;
+
+        let ready = [items[0].is_readable(), items[1].is_readable()];
+
+        for (socket, is_ready) in [(&shell, ready[0]), (&control, ready[1])] {
+            if !is_ready {
+                continue;
+            }
+
+            let frames = socket.recv_multipart(0)?;
+
+            match Message::from_frames(frames, &kernel.signer) {
+                Ok(request) => {
+                    if let Flow::Shutdown = kernel.handle(socket, &request)? {
+                        return Ok(0);
+                    }
+                }
+                Err(problem) => eprintln!("Ignoring a message from Jupyter: {problem}"),
+            }
+        }
+    }
+}
+
+/// Tell Jupyter about the kernel, by writing a kernel spec that runs `roc_exe kernel` to the
+/// user's Jupyter data directory. Returns the directory the spec went in.
+pub fn install(roc_exe: &Path) -> io::Result<PathBuf> {
+    let dir = jupyter_data_dir()?.join("kernels").join("roc");
+    let spec = json!({
+        "argv": [roc_exe, "kernel", "{connection_file}"],
+        "display_name": "Roc",
+        "language": "roc",
+    });
+
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("kernel.json"), format!("{spec:#}\n"))?;
+
+    Ok(dir)
+}
+
+/// Where Jupyter looks for the current user's kernel specs (among other things).
+fn jupyter_data_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("JUPYTER_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let home = |var| {
+        std::env::var_os(var).map(PathBuf::from).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("I couldn't find the Jupyter data directory, because ${var} isn't set."),
+            )
+        })
+    };
+
+    if cfg!(windows) {
+        Ok(home("APPDATA")?.join("jupyter"))
+    } else if cfg!(target_os = "macos") {
+        Ok(home("HOME")?.join("Library").join("Jupyter"))
+    } else if let Some(dir) = std::env::var_os("XDG_DATA_HOME") {
+        Ok(PathBuf::from(dir).join("jupyter"))
+    } else {
+        Ok(home("HOME")?.join(".local").join("share").join("jupyter"))
+    }
+}
+
+enum Flow {
+    Continue,
+    Shutdown,
+}
+
+struct Kernel {
+    session: String,
+    signer: Signer,
+    iopub: zmq::Socket,
+    engine: Engine,
+    /// How many cells have run, which Jupyter shows next to each one.
+    execution_count: u32,
+}
+
+impl Kernel {
+    /// Handle a request from the shell or control socket, replying on the same socket.
+    fn handle(&mut self, socket: &zmq::Socket, request: &Message) -> io::Result<Flow> {
+        self.publish(&request.child(
+            &self.session,
+            "status",
+            json!({ "execution_state": "busy" }),
+        ))?;
+
+        let mut flow = Flow::Continue;
+        let reply = match request.msg_type() {
+            "kernel_info_request" => Some(("kernel_info_reply", kernel_info())),
+            "execute_request" => Some(("execute_reply", self.execute(request)?)),
+            "is_complete_request" => {
+                let code = request.content["code"].as_str().unwrap_or_default();
+                let content = if is_incomplete(code) {
+                    json!({ "status": "incomplete", "indent": continuation_indent(code) })
+                } else {
+                    json!({ "status": "complete" })
+                };
+
+                Some(("is_complete_reply", content))
+            }
+            "complete_request" => Some(("complete_reply", self.complete(&request.content))),
+            "shutdown_request" => {
+                flow = Flow::Shutdown;
+
+                // Restarting is up to Jupyter, which starts a new kernel process to do it.
+                let restart = request.content["restart"].as_bool().unwrap_or(false);
+
+                Some((
+                    "shutdown_reply",
+                    json!({ "status": "ok", "restart": restart }),
+                ))
+            }
+            // Kernels can ignore the messages they don't support, like inspect_request.
+            _ => None,
+        };
+
+        if let Some((msg_type, content)) = reply {
+            let reply = request.reply(&self.session, msg_type, content);
+
+            socket.send_multipart(reply.to_frames(&self.signer), 0)?;
+        }
+
+        self.publish(&request.child(
+            &self.session,
+            "status",
+            json!({ "execution_state": "idle" }),
+        ))?;
+
+        Ok(flow)
+    }
+
+    /// Run a cell, publishing what it shows, and return the content of the reply.
+    fn execute(&mut self, request: &Message) -> io::Result<Value> {
+        let code = request.content["code"].as_str().unwrap_or_default();
+        let silent = request.content["silent"].as_bool().unwrap_or(false);
+        let store_history = request.content["store_history"]
+            .as_bool()
+            .unwrap_or(!silent);
+
+        if store_history {
+            self.execution_count += 1;
+        }
+
+        let count = self.execution_count;
+
+        if !silent {
+            self.publish(&request.child(
+                &self.session,
+                "execute_input",
+                json!({ "code": code, "execution_count": count }),
+            ))?;
+        }
+
+        let outputs = self.engine.execute(code);
+        // Only one value can be the result of the cell; the others are shown along the way.
+        let last_value = outputs
+            .iter()
+            .rposition(|output| matches!(output, Output::Value { .. }));
+
+        for (index, output) in outputs.into_iter().enumerate() {
+            let message = match output {
+                Output::Value { plain, html } => {
+                    let data = json!({ "text/plain": plain, "text/html": html });
+
+                    if Some(index) == last_value {
+                        request.child(
+                            &self.session,
+                            "execute_result",
+                            json!({ "execution_count": count, "data": data, "metadata": {} }),
+                        )
+                    } else {
+                        request.child(
+                            &self.session,
+                            "display_data",
+                            json!({ "data": data, "metadata": {} }),
+                        )
+                    }
+                }
+                Output::Text(text) => request.child(
+                    &self.session,
+                    "stream",
+                    json!({ "name": "stdout", "text": format!("{text}\n") }),
+                ),
+                Output::Error(message) => {
+                    let content = error_content(&message);
+
+                    if !silent {
+                        self.publish(&request.child(&self.session, "error", content.clone()))?;
+                    }
+
+                    let mut reply = content;
+
+                    reply["status"] = json!("error");
+                    reply["execution_count"] = json!(count);
+
+                    return Ok(reply);
+                }
+            };
+
+            if !silent {
+                self.publish(&message)?;
+            }
+        }
+
+        Ok(json!({
+            "status": "ok",
+            "execution_count": count,
+            "payload": [],
+            "user_expressions": {},
+        }))
+    }
+
+    fn complete(&self, content: &Value) -> Value {
+        let code = content["code"].as_str().unwrap_or_default();
+        // Jupyter counts the cursor position in Unicode code points, but completions
+        // work in bytes.
+        let cursor_pos = content["cursor_pos"].as_u64().unwrap_or_default() as usize;
+        let byte_pos = code
+            .char_indices()
+            .nth(cursor_pos)
+            .map_or(code.len(), |(index, _)| index);
+        let (start, matches) = self.engine.completions(code, byte_pos);
+
+        json!({
+            "status": "ok",
+            "matches": matches,
+            "cursor_start": code[..start].chars().count(),
+            "cursor_end": cursor_pos,
+            "metadata": {},
+        })
+    }
+
+    fn publish(&self, message: &Message) -> io::Result<()> {
+        self.iopub
+            .send_multipart(message.to_frames(&self.signer), 0)?;
+
+        Ok(())
+    }
+}
+
+fn kernel_info() -> Value {
+    json!({
+        "status": "ok",
+        "protocol_version": PROTOCOL_VERSION,
+        "implementation": "roc",
+        "implementation_version": env!("CARGO_PKG_VERSION"),
+        "language_info": {
+            "name": "roc",
+            "version": env!("CARGO_PKG_VERSION"),
+            "mimetype": "text/x-roc",
+            "file_extension": ".roc",
+        },
+        "banner": "The rockin’ roc repl",
+        "help_links": [
+            { "text": "Roc tutorial", "url": "https://www.roc-lang.org/tutorial" },
+        ],
+    })
+}
+
+/// The content of an `error` message, with the problem's title as its name.
+fn error_content(message: &str) -> Value {
+    // Problems start with a header like `── TYPE MISMATCH ───────`
+    let title = message
+        .lines()
+        .next()
+        .filter(|line| line.starts_with('─'))
+        .map(|line| line.trim_matches(|c: char| c == '─' || c.is_whitespace()))
+        .filter(|title| !title.is_empty())
+        .unwrap_or("ERROR");
+
+    json!({
+        "ename": title,
+        "evalue": "",
+        "traceback": message.lines().collect::<Vec<_>>(),
+    })
+}
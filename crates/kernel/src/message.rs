@@ -0,0 +1,228 @@
+//! Messages in the [Jupyter wire protocol], and signing them.
+//!
+//! [Jupyter wire protocol]: https://jupyter-client.readthedocs.io/en/stable/messaging.html#the-wire-protocol
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+/// The version of the messaging protocol the kernel speaks.
+pub const PROTOCOL_VERSION: &str = "5.3";
+
+/// The frame between the ZeroMQ routing identities and the message itself.
+const DELIMITER: &[u8] = b"<IDS|MSG>";
+
+/// Signs messages with the key from the connection file, and checks the signatures
+/// on the messages that come in.
+pub struct Signer {
+    /// `None` if messages aren't signed, which is what an empty key means.
+    key: Option<Vec<u8>>,
+}
+
+impl Signer {
+    pub fn new(scheme: &str, key: &str) -> Result<Self, String> {
+        if key.is_empty() {
+            Ok(Signer { key: None })
+        } else if scheme == "hmac-sha256" {
+            Ok(Signer {
+                key: Some(key.as_bytes().to_vec()),
+            })
+        } else {
+            Err(format!(
+                "The connection file asks for messages to be signed with {scheme}, but the Roc kernel only supports hmac-sha256."
+            ))
+        }
+    }
+
+    fn mac(&self, parts: &[&[u8]]) -> Option<Hmac<Sha256>> {
+        let key = self.key.as_ref()?;
+        // HMAC takes keys of any length.
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+
+        for part in parts {
+            mac.update(part);
+        }
+
+        Some(mac)
+    }
+
+    /// The hex-encoded signature of the header, parent header, metadata, and content.
+    pub fn sign(&self, parts: &[&[u8]]) -> String {
+        match self.mac(parts) {
+            Some(mac) => mac
+                .finalize()
+                .into_bytes()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            None => String::new(),
+        }
+    }
+
+    pub fn verify(&self, signature: &[u8], parts: &[&[u8]]) -> bool {
+        match self.mac(parts) {
+            Some(mac) => match decode_hex(signature) {
+                Some(signature) => mac.verify_slice(&signature).is_ok(),
+                None => false,
+            },
+            None => true,
+        }
+    }
+}
+
+fn decode_hex(hex: &[u8]) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    hex.chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).ok()?;
+
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Message {
+    /// Where ZeroMQ routes the message to. For replies, that's whoever sent the request;
+    /// for messages published on IOPub, it's the topic.
+    pub identities: Vec<Vec<u8>>,
+    pub header: Value,
+    pub parent_header: Value,
+    pub metadata: Value,
+    pub content: Value,
+}
+
+impl Message {
+    /// A message from the kernel, in the given session.
+    pub fn new(session: &str, msg_type: &str, content: Value) -> Self {
+        Message {
+            identities: vec![msg_type.as_bytes().to_vec()],
+            header: json!({
+                "msg_id": uuid::Uuid::new_v4().to_string(),
+                "session": session,
+                "username": "roc",
+                "date": chrono::Utc::now().to_rfc3339(),
+                "msg_type": msg_type,
+                "version": PROTOCOL_VERSION,
+            }),
+            parent_header: json!({}),
+            metadata: json!({}),
+            content,
+        }
+    }
+
+    /// A message that's a reply to (or, on IOPub, a side effect of) this one.
+    pub fn child(&self, session: &str, msg_type: &str, content: Value) -> Self {
+        Message {
+            parent_header: self.header.clone(),
+            ..Message::new(session, msg_type, content)
+        }
+    }
+
+    /// A reply to this message, which goes back to whoever sent it.
+    pub fn reply(&self, session: &str, msg_type: &str, content: Value) -> Self {
+        Message {
+            identities: self.identities.clone(),
+            ..self.child(session, msg_type, content)
+        }
+    }
+
+    pub fn msg_type(&self) -> &str {
+        self.header["msg_type"].as_str().unwrap_or_default()
+    }
+
+    /// Parse the frames of a multipart ZeroMQ message, checking its signature.
+    pub fn from_frames(mut frames: Vec<Vec<u8>>, signer: &Signer) -> Result<Self, String> {
+        let delimiter = frames
+            .iter()
+            .position(|frame| frame == DELIMITER)
+            .ok_or("The message has no <IDS|MSG> delimiter.")?;
+        // Any frames after the content are binary buffers, which nothing we handle uses.
+        let mut parts = frames.split_off(delimiter).into_iter().skip(1);
+        let (Some(signature), Some(header), Some(parent_header), Some(metadata), Some(content)) =
+            (parts.next(), parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err("The message is missing some of its parts.".to_string());
+        };
+
+        if !signer.verify(&signature, &[&header, &parent_header, &metadata, &content]) {
+            return Err("The message's signature is wrong.".to_string());
+        }
+
+        let parse = |bytes: &[u8]| {
+            serde_json::from_slice(bytes).map_err(|err| format!("The message isn't JSON: {err}"))
+        };
+
+        Ok(Message {
+            identities: frames,
+            header: parse(&header)?,
+            parent_header: parse(&parent_header)?,
+            metadata: parse(&metadata)?,
+            content: parse(&content)?,
+        })
+    }
+
+    /// The frames to send this message as, signed.
+    pub fn to_frames(&self, signer: &Signer) -> Vec<Vec<u8>> {
+        let header = self.header.to_string().into_bytes();
+        let parent_header = self.parent_header.to_string().into_bytes();
+        let metadata = self.metadata.to_string().into_bytes();
+        let content = self.content.to_string().into_bytes();
+        let signature = signer.sign(&[&header, &parent_header, &metadata, &content]);
+
+        let mut frames = self.identities.clone();
+
+        frames.extend([
+            DELIMITER.to_vec(),
+            signature.into_bytes(),
+            header,
+            parent_header,
+            metadata,
+            content,
+        ]);
+
+        frames
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signed_messages_round_trip() {
+        let signer = Signer::new("hmac-sha256", "secret").unwrap();
+        let request = Message {
+            identities: vec![b"client".to_vec()],
+            ..Message::new("session", "kernel_info_request", json!({}))
+        };
+        let frames = request.to_frames(&signer);
+        let parsed = Message::from_frames(frames, &signer).unwrap();
+
+        assert_eq!(parsed.identities, vec![b"client".to_vec()]);
+        assert_eq!(parsed.msg_type(), "kernel_info_request");
+        assert_eq!(parsed.header, request.header);
+
+        let reply = parsed.reply("kernel", "kernel_info_reply", json!({ "status": "ok" }));
+
+        assert_eq!(reply.identities, vec![b"client".to_vec()]);
+        assert_eq!(reply.parent_header, request.header);
+    }
+
+    #[test]
+    fn tampered_messages_are_rejected() {
+        let signer = Signer::new("hmac-sha256", "secret").unwrap();
+        let message = Message::new("session", "execute_request", json!({ "code": "1" }));
+        let mut frames = message.to_frames(&signer);
+
+        *frames.last_mut().unwrap() = json!({ "code": "2" }).to_string().into_bytes();
+
+        assert!(Message::from_frames(frames, &signer).is_err());
+
+        let other_signer = Signer::new("hmac-sha256", "other").unwrap();
+
+        assert!(Message::from_frames(message.to_frames(&other_signer), &signer).is_err());
+    }
+}
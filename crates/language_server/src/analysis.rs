@@ -0,0 +1,211 @@
+//! Type-checking a single document, and answering questions about the result.
+
+use std::path::{Path, PathBuf};
+
+use roc_can::expr::{Declarations, Expr};
+use roc_can::pattern::Pattern;
+use roc_can::traverse::{walk_expr, walk_pattern, Visitor};
+use roc_compile::{Diagnostic, LoadedModule, Options};
+use roc_module::symbol::Symbol;
+use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Region};
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::subs::Variable;
+
+/// Everything we found out about a document the last time it changed.
+pub struct Analysis {
+    lines: LineInfo,
+    /// `None` if the module didn't make it through type checking.
+    module: Option<LoadedModule>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Analysis {
+    pub fn new(path: &Path, text: &str) -> Self {
+        // This is just type-checking for the editor, so the default target is fine
+        let checked = roc_compile::check_str(path, text, &Options::default());
+
+        // The modules this one imports get their own diagnostics when they're opened.
+        let diagnostics = checked
+            .diagnostics
+            .into_iter()
+            .filter(|diagnostic| diagnostic.path == path)
+            .collect();
+
+        Self {
+            lines: LineInfo::new(text),
+            module: checked.module,
+            diagnostics,
+        }
+    }
+
+    /// The type of the innermost expression or pattern at this position, along with
+    /// where that expression or pattern is.
+    pub fn hover(&mut self, position: LineColumn) -> Option<(LineColumnRegion, String)> {
+        let module = self.module.as_mut()?;
+        let decls = module.declarations_by_id.get(&module.module_id)?;
+
+        let mut finder = Innermost {
+            region: Region::from_pos(self.lines.convert_line_column(position)),
+            found: None,
+        };
+
+        finder.visit_decls(decls);
+
+        let (region, opt_symbol, var) = finder.found?;
+        let typ = name_and_print_var(
+            var,
+            module.solved.inner_mut(),
+            module.module_id,
+            &module.interns,
+            DebugPrint::NOTHING,
+        );
+        let text = match opt_symbol {
+            Some(symbol) => format!("{} : {}", symbol.as_str(&module.interns), typ),
+            None => typ,
+        };
+
+        Some((self.lines.convert_region(region), text))
+    }
+
+    /// Where the variable at this position was defined, which may be in another module.
+    pub fn definition(&self, position: LineColumn) -> Option<(PathBuf, LineColumnRegion)> {
+        let module = self.module.as_ref()?;
+        let decls = module.declarations_by_id.get(&module.module_id)?;
+
+        let mut finder = Innermost {
+            region: Region::from_pos(self.lines.convert_line_column(position)),
+            found: None,
+        };
+
+        finder.visit_decls(decls);
+
+        let (_, symbol, _) = finder.found?;
+        let symbol = symbol?;
+        let home = symbol.module_id();
+        let region = find_definition(symbol, module.declarations_by_id.get(&home)?)?;
+        let (path, src) = module.sources.get(&home)?;
+
+        Some((path.clone(), LineInfo::new(src).convert_region(region)))
+    }
+}
+
+/// Finds the innermost typed expression or pattern that contains `region`.
+struct Innermost {
+    region: Region,
+    found: Option<(Region, Option<Symbol>, Variable)>,
+}
+
+impl Visitor for Innermost {
+    fn should_visit(&mut self, region: Region) -> bool {
+        region.contains(&self.region)
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if self.should_visit(region) {
+            let opt_symbol = match expr {
+                Expr::Var(symbol, _) => Some(*symbol),
+                _ => None,
+            };
+
+            self.found = Some((region, opt_symbol, var));
+
+            walk_expr(self, expr, var);
+        }
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern, region: Region, opt_var: Option<Variable>) {
+        if self.should_visit(region) {
+            if let Some(var) = opt_var {
+                self.found = Some((region, pattern_symbol(pattern), var));
+            }
+
+            walk_pattern(self, pattern);
+        }
+    }
+}
+
+fn pattern_symbol(pattern: &Pattern) -> Option<Symbol> {
+    match pattern {
+        Pattern::Identifier(symbol)
+        | Pattern::As(_, symbol)
+        | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => Some(*symbol),
+        _ => None,
+    }
+}
+
+/// Finds the pattern that introduces `symbol`, whether that's a top-level definition,
+/// a function argument, or anything in between.
+fn find_definition(symbol: Symbol, decls: &Declarations) -> Option<Region> {
+    let mut finder = Definition {
+        symbol,
+        found: None,
+    };
+
+    finder.visit_decls(decls);
+
+    return finder.found;
+
+    struct Definition {
+        symbol: Symbol,
+        found: Option<Region>,
+    }
+
+    impl Visitor for Definition {
+        fn should_visit(&mut self, _region: Region) -> bool {
+            self.found.is_none()
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            if self.should_visit(region) {
+                if pattern_symbol(pattern) == Some(self.symbol) {
+                    self.found = Some(region);
+                }
+
+                walk_pattern(self, pattern);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &str = "interface Test exposes [double] imports []\n\ndouble = \\n -> n * 2\n\nquadruple = \\n -> double (double n)\n";
+
+    fn analyze() -> Analysis {
+        let dir = std::env::temp_dir().join("roc_language_server_test");
+
+        Analysis::new(&dir.join("Test.roc"), SRC)
+    }
+
+    #[test]
+    fn unused_definition_warning() {
+        let analysis = analyze();
+
+        assert_eq!(analysis.diagnostics.len(), 1);
+        assert_eq!(analysis.diagnostics[0].title, "UNUSED DEFINITION");
+        assert_eq!(analysis.diagnostics[0].region.unwrap().start().line, 4);
+    }
+
+    #[test]
+    fn hover_shows_type() {
+        let mut analysis = analyze();
+        let (_, text) = analysis.hover(LineColumn { line: 2, column: 1 }).unwrap();
+
+        assert_eq!(text, "double : Num a -> Num a");
+    }
+
+    #[test]
+    fn definition_of_local_function() {
+        let analysis = analyze();
+        let (_, region) = analysis
+            .definition(LineColumn {
+                line: 4,
+                column: 20,
+            })
+            .unwrap();
+
+        assert_eq!(region.start(), LineColumn { line: 2, column: 0 });
+    }
+}
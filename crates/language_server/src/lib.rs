@@ -0,0 +1,271 @@
+//! A language server for Roc, which talks the [Language Server Protocol] over stdin and
+//! stdout. It type-checks each open document whenever it changes, reports the problems
+//! it finds, and answers hover and go-to-definition requests.
+//!
+//! Positions are exchanged as UTF-8 byte offsets within a line, which is what the
+//! compiler uses internally.
+//!
+//! [Language Server Protocol]: https://microsoft.github.io/language-server-protocol/
+mod analysis;
+mod rpc;
+
+use std::collections::HashMap;
+use std::io::{self, BufReader};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use roc_compile::{Diagnostic, LineColumn, LineColumnRegion, Severity};
+use serde_json::{json, Value};
+
+use analysis::Analysis;
+
+/// The JSON-RPC error code for a method we don't know about.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Serve requests until the client tells us to exit. Returns the exit code.
+pub fn run() -> io::Result<i32> {
+    let mut reader = BufReader::new(io::stdin());
+    let mut writer = io::stdout();
+    let mut server = Server::default();
+
+    while let Some(message) = rpc::read_message(&mut reader)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+
+        if method == "exit" {
+            return Ok(if server.shutting_down { 0 } else { 1 });
+        }
+
+        for outgoing in server.handle(method, params, message.get("id")) {
+            rpc::write_message(&mut writer, &outgoing)?;
+        }
+    }
+
+    Ok(1)
+}
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<String, Analysis>,
+    shutting_down: bool,
+}
+
+impl Server {
+    /// Handle one incoming message, returning the messages to send back.
+    fn handle(&mut self, method: &str, params: &Value, id: Option<&Value>) -> Vec<Value> {
+        let Some(id) = id else {
+            // This is a notification, so it never gets a response of its own.
+            return self.notify(method, params);
+        };
+
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "positionEncoding": "utf-8",
+                    // The whole document is sent on every change.
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                },
+                "serverInfo": { "name": "roc" },
+            }),
+            "shutdown" => {
+                self.shutting_down = true;
+
+                Value::Null
+            }
+            "textDocument/hover" => self.hover(params).unwrap_or(Value::Null),
+            "textDocument/definition" => self.definition(params).unwrap_or(Value::Null),
+            _ => {
+                return vec![json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": METHOD_NOT_FOUND,
+                        "message": format!("Unknown method: {method}"),
+                    },
+                })];
+            }
+        };
+
+        vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })]
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        let text = match method {
+            "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+            "textDocument/didChange" => params["contentChanges"]
+                .as_array()
+                .and_then(|changes| changes.last())
+                .and_then(|change| change["text"].as_str()),
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+
+                return vec![publish_diagnostics(uri, Vec::new())];
+            }
+            _ => None,
+        };
+
+        let (Some(text), Some(path)) = (text, uri_to_path(uri)) else {
+            return Vec::new();
+        };
+
+        // A bug in the compiler shouldn't take down the whole server, so report it
+        // like any other problem instead.
+        let analysis = panic::catch_unwind(|| Analysis::new(&path, text));
+        let diagnostics = match &analysis {
+            Ok(analysis) => analysis.diagnostics.iter().map(diagnostic).collect(),
+            Err(_) => vec![json!({
+                "range": range(LineColumnRegion::zero()),
+                "severity": 1,
+                "source": "roc",
+                "message": "The compiler crashed while checking this file.",
+            })],
+        };
+
+        match analysis {
+            Ok(analysis) => {
+                self.documents.insert(uri.to_string(), analysis);
+            }
+            Err(_) => {
+                self.documents.remove(uri);
+            }
+        }
+
+        vec![publish_diagnostics(uri, diagnostics)]
+    }
+
+    fn hover(&mut self, params: &Value) -> Option<Value> {
+        let position = position(&params["position"])?;
+        let analysis = self
+            .documents
+            .get_mut(params["textDocument"]["uri"].as_str()?)?;
+        let (region, text) =
+            panic::catch_unwind(AssertUnwindSafe(|| analysis.hover(position))).ok()??;
+
+        Some(json!({
+            "contents": { "kind": "markdown", "value": format!("```roc\n{text}\n```") },
+            "range": range(region),
+        }))
+    }
+
+    fn definition(&self, params: &Value) -> Option<Value> {
+        let position = position(&params["position"])?;
+        let analysis = self
+            .documents
+            .get(params["textDocument"]["uri"].as_str()?)?;
+        let (path, region) =
+            panic::catch_unwind(AssertUnwindSafe(|| analysis.definition(position))).ok()??;
+
+        Some(json!({ "uri": path_to_uri(&path), "range": range(region) }))
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+fn diagnostic(diagnostic: &Diagnostic) -> Value {
+    let severity = match diagnostic.severity {
+        Severity::RuntimeError | Severity::Fatal => 1,
+        Severity::Warning => 2,
+    };
+    let mut value = json!({
+        "range": range(diagnostic.region.unwrap_or_else(LineColumnRegion::zero)),
+        "severity": severity,
+        "source": "roc",
+        "message": diagnostic.message,
+    });
+
+    if !diagnostic.title.is_empty() {
+        value["code"] = json!(diagnostic.title);
+    }
+
+    value
+}
+
+fn position(value: &Value) -> Option<LineColumn> {
+    Some(LineColumn {
+        line: value["line"].as_u64()?.try_into().ok()?,
+        column: value["character"].as_u64()?.try_into().ok()?,
+    })
+}
+
+fn range(region: LineColumnRegion) -> Value {
+    let start = region.start();
+    let end = region.end();
+
+    json!({
+        "start": { "line": start.line, "character": start.column },
+        "end": { "line": end.line, "character": end.column },
+    })
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut index = 0;
+
+    while index < encoded.len() {
+        let hex = encoded
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (encoded[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_round_trip() {
+        let path = PathBuf::from("/home/user/my project/Main.roc");
+        let uri = path_to_uri(&path);
+
+        assert_eq!(uri, "file:///home/user/my%20project/Main.roc");
+        assert_eq!(uri_to_path(&uri), Some(path));
+    }
+
+    #[test]
+    fn unknown_request() {
+        let responses = Server::default().handle("workspace/symbol", &json!({}), Some(&json!(7)));
+
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], 7);
+        assert_eq!(responses[0]["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
@@ -0,0 +1,91 @@
+//! The framing of JSON-RPC messages that the Language Server Protocol uses over stdio:
+//! a few HTTP-style headers, a blank line, and then exactly `Content-Length` bytes of JSON.
+
+use std::io::{self, BufRead, Write};
+
+use serde_json::Value;
+
+/// Read the next message, or `None` if the input was closed.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let header = line.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message is missing a Content-Length header",
+        ));
+    };
+
+    let mut content = vec![0; content_length];
+
+    reader.read_exact(&mut content)?;
+
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn round_trip() {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize" });
+        let mut buf = Vec::new();
+
+        write_message(&mut buf, &message).unwrap();
+        write_message(&mut buf, &json!(null)).unwrap();
+
+        let mut reader = buf.as_slice();
+
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(json!(null)));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn extra_headers() {
+        let input = "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\ncontent-length: 2\r\n\r\n{}";
+
+        assert_eq!(
+            read_message(&mut input.as_bytes()).unwrap(),
+            Some(json!({}))
+        );
+    }
+}
@@ -25,10 +25,11 @@ mod generate_dylib;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LinkType {
-    // These numbers correspond to the --lib and --no-link flags
+    // These numbers correspond to the --lib, --no-link, and --lib=static flags
     Executable = 0,
     Dylib = 1,
     None = 2,
+    StaticLib = 3,
 }
 
 pub fn supported(link_type: LinkType, target: &Triple) -> bool {
@@ -108,6 +109,8 @@ pub fn generate_stub_lib(
             palette: DEFAULT_PALETTE,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Executable,
+            warning_levels: Default::default(),
+            explain_types: false,
         },
     )
     .unwrap_or_else(|problem| todo!("{:?}", problem));
@@ -445,6 +445,8 @@ pub(crate) fn surgery_pe(executable_path: &Path, metadata_path: &Path, roc_app_b
                         "__fixunsdfti",
                         "__fixunssfti",
                         "__lshrti3",
+                        "__ashlti3",
+                        "__ashrti3",
                         "memcpy_decision",
                     ]
                     .contains(&name.as_str());
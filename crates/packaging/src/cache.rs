@@ -160,6 +160,229 @@ pub fn install_package<'a>(
     }
 }
 
+/// The directory `roc vendor` copies packages into, next to the app or package's main module
+pub const VENDOR_DIR_NAME: &str = "vendor";
+
+/// Where `roc vendor` puts the package at the given URL, e.g.
+/// vendor/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE - or None if the URL is invalid.
+#[cfg(not(target_family = "wasm"))]
+pub fn vendored_package_dir(vendor_dir: &Path, url: &str) -> Option<PathBuf> {
+    PackageMetadata::try_from(url)
+        .ok()
+        .map(|metadata| vendor_dir.join(metadata.content_hash))
+}
+
+/// Like [install_package], except that a copy of the package in the given vendor dir takes
+/// precedence over the cache. Vendored packages are used without downloading anything, so
+/// builds can work where there's no network access.
+#[cfg(not(target_family = "wasm"))]
+pub fn find_package<'a>(
+    roc_cache_dir: RocCacheDir<'_>,
+    vendor_dir: &Path,
+    url: &'a str,
+) -> Result<(PathBuf, Option<&'a str>), Problem> {
+    if let Some(package_dir) = vendored_package_dir(vendor_dir, url) {
+        if package_dir.is_dir() {
+            let PackageMetadata {
+                root_module_filename,
+                ..
+            } = PackageMetadata::try_from(url).map_err(Problem::InvalidUrl)?;
+
+            return Ok((package_dir, root_module_filename));
+        }
+    }
+
+    install_package(roc_cache_dir, url)
+}
+
+/// A URL package that `install_dependencies` put in the cache.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstalledPackage {
+    pub url: String,
+    /// e.g. ~/.cache/roc/example.com/roc-packages/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE
+    pub dir: PathBuf,
+    /// The URLs of the packages this one depends on in turn
+    pub dependencies: Vec<String>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug)]
+pub enum InstallProblem {
+    /// A module couldn't be read
+    Io(PathBuf, std::io::Error),
+    /// A module's header couldn't be parsed, so its dependencies are unknown
+    InvalidHeader(PathBuf),
+    /// A URL package couldn't be downloaded, or its contents didn't match the hash in its URL
+    Download(String, Problem),
+}
+
+/// Install every URL package that the given module depends on into the cache - including the
+/// ones its platform and other packages depend on - so that building it later works offline.
+/// Packages that have been vendored next to the module are used from there instead.
+///
+/// Packages given as relative paths are followed to find their own dependencies,
+/// but they aren't copied anywhere. Returns the URL packages in the order they were found.
+#[cfg(not(target_family = "wasm"))]
+pub fn install_dependencies(
+    roc_cache_dir: RocCacheDir<'_>,
+    root_module: &Path,
+) -> Result<Vec<InstalledPackage>, InstallProblem> {
+    use std::collections::HashSet;
+
+    let vendor_dir = root_module
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(VENDOR_DIR_NAME);
+    let mut installed: Vec<InstalledPackage> = Vec::new();
+    let mut visited = HashSet::new();
+    // Each module to look at, along with the index of the URL package it's part of (if any)
+    let mut stack: Vec<(PathBuf, Option<usize>)> = vec![(root_module.to_path_buf(), None)];
+
+    while let Some((module_path, owner)) = stack.pop() {
+        if !visited.insert(module_path.clone()) {
+            continue;
+        }
+
+        for src in header_dependencies(&module_path)? {
+            let (package_root, package_owner) = if src.starts_with("https://") {
+                let (package_dir, opt_root_module) = find_package(roc_cache_dir, &vendor_dir, &src)
+                    .map_err(|problem| InstallProblem::Download(src.clone(), problem))?;
+                let package_root = package_dir.join(opt_root_module.unwrap_or("main.roc"));
+
+                if let Some(owner) = owner {
+                    let dependencies = &mut installed[owner].dependencies;
+
+                    if !dependencies.contains(&src) {
+                        dependencies.push(src.clone());
+                    }
+                }
+
+                let index = match installed.iter().position(|package| package.url == src) {
+                    Some(index) => index,
+                    None => {
+                        installed.push(InstalledPackage {
+                            url: src,
+                            dir: package_dir,
+                            dependencies: Vec::new(),
+                        });
+
+                        installed.len() - 1
+                    }
+                };
+
+                (package_root, Some(index))
+            } else {
+                // Relative paths are relative to the module that gave them, like in the loader.
+                // A package given by path is part of whichever package gave its path.
+                let package_root = module_path
+                    .parent()
+                    .unwrap_or_else(|| Path::new("."))
+                    .join(&src);
+
+                (package_root, owner)
+            };
+
+            stack.push((package_root, package_owner));
+        }
+    }
+
+    Ok(installed)
+}
+
+/// Copy every URL package that the given module depends on into the vendor dir next to it,
+/// installing them into the cache first if necessary. Packages that are already vendored are
+/// left alone. Returns the packages, with their vendored dirs.
+#[cfg(not(target_family = "wasm"))]
+pub fn vendor_dependencies(
+    roc_cache_dir: RocCacheDir<'_>,
+    root_module: &Path,
+) -> Result<Vec<InstalledPackage>, InstallProblem> {
+    let vendor_dir = root_module
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(VENDOR_DIR_NAME);
+    let mut packages = install_dependencies(roc_cache_dir, root_module)?;
+
+    for package in packages.iter_mut() {
+        let Some(dest_dir) = vendored_package_dir(&vendor_dir, &package.url) else {
+            continue;
+        };
+
+        if !dest_dir.is_dir() {
+            // fs_extra::dir::copy needs the destination directory to exist already.
+            fs::create_dir_all(&dest_dir)
+                .map_err(|err| InstallProblem::Io(dest_dir.clone(), err))?;
+            fs_extra::dir::copy(
+                &package.dir,
+                &dest_dir,
+                &fs_extra::dir::CopyOptions {
+                    content_only: true,
+                    ..Default::default()
+                },
+            )
+            .map_err(|err| {
+                InstallProblem::Download(package.url.clone(), Problem::FsExtraErr(err))
+            })?;
+        }
+
+        package.dir = dest_dir;
+    }
+
+    Ok(packages)
+}
+
+/// The package URLs and paths in a module's header, including an app's platform.
+#[cfg(not(target_family = "wasm"))]
+fn header_dependencies(module_path: &Path) -> Result<Vec<String>, InstallProblem> {
+    use bumpalo::Bump;
+    use roc_parse::ast::{ExtractSpaces, Header, Module};
+    use roc_parse::header::To;
+    use roc_parse::module::parse_header;
+    use roc_parse::state::State;
+
+    let src =
+        fs::read(module_path).map_err(|err| InstallProblem::Io(module_path.to_path_buf(), err))?;
+    let arena = Bump::new();
+
+    let Ok((Module { header, .. }, _)) = parse_header(&arena, State::new(&src)) else {
+        return Err(InstallProblem::InvalidHeader(module_path.to_path_buf()));
+    };
+
+    let (entries, opt_platform) = match header {
+        Header::App(header) => {
+            let platform = match header.provides.to.value {
+                To::NewPackage(package_name) => Some(package_name.to_str()),
+                To::ExistingPackage(_) => None,
+            };
+
+            (
+                header.packages.map(|packages| packages.item.items),
+                platform,
+            )
+        }
+        Header::Package(header) => (Some(header.packages.item.items), None),
+        Header::Platform(header) => (Some(header.packages.item.items), None),
+        Header::Interface(_) | Header::Hosted(_) => (None, None),
+    };
+
+    Ok(entries
+        .unwrap_or(&[])
+        .iter()
+        .map(|entry| {
+            entry
+                .value
+                .extract_spaces()
+                .item
+                .package_name
+                .value
+                .to_str()
+        })
+        .chain(opt_platform)
+        .map(str::to_string)
+        .collect())
+}
+
 #[cfg(windows)]
 // e.g. the "Roc" in %APPDATA%\\Roc
 const ROC_CACHE_DIR_NAME: &str = "Roc";
@@ -234,3 +457,130 @@ pub fn roc_cache_dir() -> PathBuf {
 pub fn roc_cache_dir() -> PathBuf {
     PathBuf::from(".cache").join(ROC_CACHE_DIR_NAME)
 }
+
+#[cfg(test)]
+fn write_module(path: &Path, src: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, src).unwrap();
+}
+
+#[test]
+fn install_dependencies_follows_paths_and_vendored_packages() {
+    const JSON_URL: &str = "https://example.com/json/jsonhash.tar.br";
+    const PARSER_URL: &str = "https://example.com/parser/parserhash.tar.br";
+
+    let dir = tempfile::tempdir().unwrap();
+    let root_module = dir.path().join("main.roc");
+
+    write_module(
+        &root_module,
+        &format!(
+            "app \"test\"\n    packages {{ pf: \"platform/main.roc\", json: \"{JSON_URL}\" }}\n    imports []\n    provides [main] to pf\n"
+        ),
+    );
+    write_module(
+        &dir.path().join("platform").join("main.roc"),
+        &format!(
+            "platform \"test\"\n    requires {{}} {{ main : Str }}\n    exposes []\n    packages {{ parser: \"{PARSER_URL}\" }}\n    imports []\n    provides [mainForHost]\n"
+        ),
+    );
+
+    // Both URL packages are vendored, so nothing gets downloaded - RocCacheDir::Disallowed
+    // would panic if anything did.
+    let vendor_dir = dir.path().join(VENDOR_DIR_NAME);
+
+    write_module(
+        &vendor_dir.join("jsonhash").join("main.roc"),
+        &format!("package \"json\"\n    exposes []\n    packages {{ parser: \"{PARSER_URL}\" }}\n"),
+    );
+    write_module(
+        &vendor_dir.join("parserhash").join("main.roc"),
+        "package \"parser\"\n    exposes []\n    packages {}\n",
+    );
+
+    let installed = install_dependencies(RocCacheDir::Disallowed, &root_module).unwrap();
+
+    assert_eq!(
+        installed,
+        vec![
+            InstalledPackage {
+                url: JSON_URL.to_string(),
+                dir: vendor_dir.join("jsonhash"),
+                dependencies: vec![PARSER_URL.to_string()],
+            },
+            InstalledPackage {
+                url: PARSER_URL.to_string(),
+                dir: vendor_dir.join("parserhash"),
+                dependencies: Vec::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn install_dependencies_without_url_packages() {
+    let dir = tempfile::tempdir().unwrap();
+    let root_module = dir.path().join("main.roc");
+
+    write_module(
+        &root_module,
+        "app \"test\"\n    packages { pf: \"platform/main.roc\" }\n    imports []\n    provides [main] to pf\n",
+    );
+    write_module(
+        &dir.path().join("platform").join("main.roc"),
+        "platform \"test\"\n    requires {} { main : Str }\n    exposes []\n    packages {}\n    imports []\n    provides [mainForHost]\n",
+    );
+
+    let installed = install_dependencies(RocCacheDir::Disallowed, &root_module).unwrap();
+
+    assert_eq!(installed, Vec::new());
+}
+
+#[test]
+fn install_dependencies_missing_path_package() {
+    let dir = tempfile::tempdir().unwrap();
+    let root_module = dir.path().join("main.roc");
+
+    write_module(
+        &root_module,
+        "app \"test\"\n    packages { pf: \"platform/main.roc\" }\n    imports []\n    provides [main] to pf\n",
+    );
+
+    match install_dependencies(RocCacheDir::Disallowed, &root_module) {
+        Err(InstallProblem::Io(path, _)) => {
+            assert_eq!(path, dir.path().join("platform/main.roc"));
+        }
+        other => panic!("Expected an Io problem, but got {other:?}"),
+    }
+}
+
+#[test]
+fn install_dependencies_invalid_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let root_module = dir.path().join("main.roc");
+
+    write_module(&root_module, "app \"test\" packages {");
+
+    match install_dependencies(RocCacheDir::Disallowed, &root_module) {
+        Err(InstallProblem::InvalidHeader(path)) => assert_eq!(path, root_module),
+        other => panic!("Expected an InvalidHeader problem, but got {other:?}"),
+    }
+}
+
+#[test]
+fn find_package_prefers_vendored_copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let vendor_dir = dir.path().join(VENDOR_DIR_NAME);
+
+    std::fs::create_dir_all(vendor_dir.join("pkghash")).unwrap();
+
+    assert_eq!(
+        find_package(
+            RocCacheDir::Disallowed,
+            &vendor_dir,
+            "https://example.com/pkg/pkghash.tar.br#Pkg.roc"
+        )
+        .unwrap(),
+        (vendor_dir.join("pkghash"), Some("Pkg.roc"))
+    );
+}
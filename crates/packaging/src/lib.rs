@@ -1,4 +1,6 @@
 pub mod cache;
 #[cfg(not(target_family = "wasm"))]
 pub mod https;
+#[cfg(not(target_family = "wasm"))]
+pub mod lock;
 pub mod tarball;
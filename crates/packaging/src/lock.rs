@@ -0,0 +1,213 @@
+use crate::cache::InstalledPackage;
+use crate::https::PackageMetadata;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The lockfile lives next to the app or package's main module.
+pub const LOCKFILE_NAME: &str = "roc.lock";
+
+const LOCKFILE_HEADER: &str = "\
+# This file is generated by `roc update`. Commit it, so that everyone builds with the same packages.
+# Each package is listed with the BLAKE3 hash of its contents and the packages it depends on.
+";
+
+/// Every URL package an app or package depends on, directly or transitively,
+/// as recorded in its `roc.lock`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Lockfile {
+    /// Sorted by URL
+    pub packages: Vec<LockedPackage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub url: String,
+    pub hash: String,
+    /// Sorted URLs of the packages this one depends on in turn
+    pub dependencies: Vec<String>,
+}
+
+/// e.g. `examples/roc.lock` for `examples/main.roc`
+pub fn lockfile_path(root_module: &Path) -> PathBuf {
+    root_module.with_file_name(LOCKFILE_NAME)
+}
+
+impl Lockfile {
+    /// A lockfile for the packages that were just installed. Their hashes are the ones their
+    /// contents were verified against when they were downloaded.
+    pub fn from_installed(installed: &[InstalledPackage]) -> Self {
+        let mut packages: Vec<LockedPackage> = installed
+            .iter()
+            .map(|package| {
+                let hash = PackageMetadata::try_from(package.url.as_str())
+                    .map(|metadata| metadata.content_hash.to_string())
+                    .unwrap_or_default();
+                let mut dependencies = package.dependencies.clone();
+
+                dependencies.sort();
+
+                LockedPackage {
+                    url: package.url.clone(),
+                    hash,
+                    dependencies,
+                }
+            })
+            .collect();
+
+        packages.sort_by(|a, b| a.url.cmp(&b.url));
+
+        Self { packages }
+    }
+
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let mut packages: Vec<LockedPackage> = Vec::new();
+
+        for (index, line) in src.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                packages.push(LockedPackage {
+                    url: trimmed.to_string(),
+                    hash: String::new(),
+                    dependencies: Vec::new(),
+                });
+
+                continue;
+            }
+
+            let Some(package) = packages.last_mut() else {
+                return Err(format!(
+                    "line {line_number} is indented, but there's no package URL above it"
+                ));
+            };
+
+            match trimmed.split_once(' ') {
+                Some(("hash", hash)) => package.hash = hash.trim().to_string(),
+                Some(("depends-on", url)) => package.dependencies.push(url.trim().to_string()),
+                _ => {
+                    return Err(format!(
+                        "line {line_number} should start with `hash` or `depends-on`"
+                    ))
+                }
+            }
+        }
+
+        match packages.iter().find(|package| package.hash.is_empty()) {
+            Some(package) => Err(format!("{} doesn't have a hash", package.url)),
+            None => Ok(Self { packages }),
+        }
+    }
+
+    /// How the packages that were just resolved differ from the locked ones,
+    /// as one sentence per difference. An empty Vec means they're the same.
+    pub fn differences(&self, resolved: &Lockfile) -> Vec<String> {
+        let mut differences = Vec::new();
+
+        for package in resolved.packages.iter() {
+            match self.get(&package.url) {
+                None => differences.push(format!("{} is not in {LOCKFILE_NAME}", package.url)),
+                Some(locked) if locked.hash != package.hash => differences.push(format!(
+                    "{} has the hash {}, but {LOCKFILE_NAME} expects {}",
+                    package.url, package.hash, locked.hash
+                )),
+                Some(locked) if locked.dependencies != package.dependencies => {
+                    differences.push(format!(
+                        "{} depends on different packages than {LOCKFILE_NAME} says",
+                        package.url
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        for locked in self.packages.iter() {
+            if resolved.get(&locked.url).is_none() {
+                differences.push(format!(
+                    "{} is in {LOCKFILE_NAME}, but nothing depends on it anymore",
+                    locked.url
+                ));
+            }
+        }
+
+        differences
+    }
+
+    fn get(&self, url: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|package| package.url == url)
+    }
+}
+
+impl fmt::Display for Lockfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(LOCKFILE_HEADER)?;
+
+        for package in self.packages.iter() {
+            writeln!(f, "\n{}\n    hash {}", package.url, package.hash)?;
+
+            for dependency in package.dependencies.iter() {
+                writeln!(f, "    depends-on {dependency}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn example_lockfile() -> Lockfile {
+    Lockfile {
+        packages: vec![
+            LockedPackage {
+                url: "https://example.com/json/abc.tar.br".to_string(),
+                hash: "abc".to_string(),
+                dependencies: Vec::new(),
+            },
+            LockedPackage {
+                url: "https://example.com/pf/def.tar.br".to_string(),
+                hash: "def".to_string(),
+                dependencies: vec!["https://example.com/json/abc.tar.br".to_string()],
+            },
+        ],
+    }
+}
+
+#[test]
+fn lockfile_round_trip() {
+    let lockfile = example_lockfile();
+
+    assert_eq!(Lockfile::parse(&lockfile.to_string()), Ok(lockfile));
+}
+
+#[test]
+fn lockfile_differences() {
+    let locked = example_lockfile();
+    let mut resolved = example_lockfile();
+
+    assert!(locked.differences(&resolved).is_empty());
+
+    resolved.packages[0].hash = "xyz".to_string();
+    resolved.packages.pop();
+
+    assert_eq!(
+        locked.differences(&resolved),
+        vec![
+            "https://example.com/json/abc.tar.br has the hash xyz, but roc.lock expects abc"
+                .to_string(),
+            "https://example.com/pf/def.tar.br is in roc.lock, but nothing depends on it anymore"
+                .to_string(),
+        ]
+    );
+}
+
+#[test]
+fn lockfile_parse_problem() {
+    assert_eq!(
+        Lockfile::parse("    hash abc\n"),
+        Err("line 1 is indented, but there's no package URL above it".to_string())
+    );
+}
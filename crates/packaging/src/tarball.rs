@@ -2,9 +2,10 @@ use brotli::enc::BrotliEncoderParams;
 use bumpalo::Bump;
 use flate2::write::GzEncoder;
 use roc_parse::ast::{Header, Module};
-use roc_parse::header::PlatformHeader;
+use roc_parse::header::{PackageHeader, PlatformHeader};
 use roc_parse::module::parse_header;
 use roc_parse::state::State;
+use serde::Serialize;
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{self, Read, Write};
@@ -12,6 +13,10 @@ use std::path::Path;
 use tar;
 use walkdir::WalkDir;
 
+/// The file in a package's archive that describes the package, so that tools can tell what's
+/// in it without parsing its modules.
+pub const MANIFEST_FILENAME: &str = "manifest.json";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Compression {
     Brotli,
@@ -140,8 +145,16 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
             todo!();
             // TODO report error
         }
-        Header::Package(_) => {
-            add_dot_roc_files(root_dir, &mut builder)?;
+        Header::Package(header) => {
+            let files = add_dot_roc_files(root_dir, &mut builder)?;
+            let manifest = package_manifest(&header, files);
+            let mut tar_header = tar::Header::new_gnu();
+
+            tar_header.set_size(manifest.len() as u64);
+            tar_header.set_mode(0o644);
+            tar_header.set_cksum();
+
+            builder.append_data(&mut tar_header, MANIFEST_FILENAME, manifest.as_bytes())?;
         }
         Header::Platform(PlatformHeader { imports: _, .. }) => {
             // Add all the prebuilt host files to the archive.
@@ -219,10 +232,13 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
     builder.finish()
 }
 
+/// Add every .roc file under the root dir to the archive, returning their paths in it.
 fn add_dot_roc_files<W: Write>(
     root_dir: &Path,
     builder: &mut tar::Builder<W>,
-) -> Result<(), io::Error> {
+) -> Result<Vec<String>, io::Error> {
+    let mut added = Vec::new();
+
     for entry in WalkDir::new(root_dir).into_iter().filter_entry(|entry| {
         let path = entry.path();
 
@@ -237,17 +253,51 @@ fn add_dot_roc_files<W: Write>(
         // added based on the paths of the files inside anyway. (In fact, if we don't
         // filter out directories in this step, then empty ones can sometimes be added!)
         if path.is_file() {
-            builder.append_path_with_name(
-                path,
-                // Store it without the root path, so that (for example) we don't store
-                // `examples/cli/main.roc` and therefore end up with the root of the tarball
-                // being an `examples/cli/` dir instead of having `main.roc` in the root.
-                path.strip_prefix(root_dir).unwrap(),
-            )?;
+            // Store it without the root path, so that (for example) we don't store
+            // `examples/cli/main.roc` and therefore end up with the root of the tarball
+            // being an `examples/cli/` dir instead of having `main.roc` in the root.
+            let name = path.strip_prefix(root_dir).unwrap();
+
+            builder.append_path_with_name(path, name)?;
+            added.push(name.to_string_lossy().replace('\\', "/"));
         }
     }
 
-    Ok(())
+    Ok(added)
+}
+
+/// A description of a package, written to its archive as JSON.
+#[derive(Debug, Serialize)]
+struct PackageManifest<'a> {
+    name: &'a str,
+    exposes: Vec<&'a str>,
+    files: Vec<String>,
+}
+
+/// A JSON description of a package: its name, the modules it exposes, and its files.
+fn package_manifest(header: &PackageHeader<'_>, mut files: Vec<String>) -> String {
+    let exposes = header
+        .exposes
+        .item
+        .items
+        .iter()
+        .map(|module_name| module_name.value.item().as_str())
+        .collect();
+
+    files.sort();
+
+    let manifest = PackageManifest {
+        name: header.name.value.to_str(),
+        exposes,
+        files,
+    };
+
+    let mut json =
+        serde_json::to_string_pretty(&manifest).expect("a manifest of strings always serializes");
+
+    json.push('\n');
+
+    json
 }
 
 fn read_header<'a>(
@@ -274,3 +324,86 @@ fn read_header<'a>(
 
     Ok(module)
 }
+
+#[cfg(test)]
+fn write_example_package(dir: &Path) -> std::path::PathBuf {
+    let main = dir.join("main.roc");
+
+    std::fs::write(
+        &main,
+        "package \"json\"\n    exposes [Json, Json.Decode]\n    packages {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("Json.roc"),
+        "interface Json exposes [] imports []\n",
+    )
+    .unwrap();
+    std::fs::create_dir(dir.join("Json")).unwrap();
+    std::fs::write(
+        dir.join("Json/Decode.roc"),
+        "interface Json.Decode exposes [] imports []\n",
+    )
+    .unwrap();
+    // Only .roc files go into a package's archive.
+    std::fs::write(dir.join("notes.txt"), "not part of the package").unwrap();
+
+    main
+}
+
+#[test]
+fn package_archive_has_its_modules_and_a_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = write_example_package(dir.path());
+    let mut archive_bytes = Vec::new();
+
+    write_archive(&main, &mut archive_bytes).unwrap();
+
+    let mut archive = tar::Archive::new(archive_bytes.as_slice());
+    let mut names = Vec::new();
+    let mut manifest = String::new();
+
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let name = entry.path().unwrap().to_string_lossy().replace('\\', "/");
+
+        if name == MANIFEST_FILENAME {
+            std::io::Read::read_to_string(&mut entry, &mut manifest).unwrap();
+        }
+
+        names.push(name);
+    }
+
+    names.sort();
+
+    assert_eq!(
+        names,
+        ["Json.roc", "Json/Decode.roc", "main.roc", MANIFEST_FILENAME]
+    );
+
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+
+    assert_eq!(
+        manifest,
+        serde_json::json!({
+            "name": "json",
+            "exposes": ["Json", "Json.Decode"],
+            "files": ["Json.roc", "Json/Decode.roc", "main.roc"],
+        })
+    );
+}
+
+#[test]
+fn package_archive_is_named_after_its_hash() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = write_example_package(dir.path());
+
+    let filename = build(&main, Compression::Uncompressed).unwrap();
+    let archive_bytes = std::fs::read(dir.path().join(&filename)).unwrap();
+    let hash = base64_url::encode(blake3::hash(&archive_bytes).as_bytes());
+
+    assert_eq!(filename, format!("{hash}.tar"));
+
+    // Building the same package again gives the same archive.
+    assert_eq!(build(&main, Compression::Uncompressed).unwrap(), filename);
+}
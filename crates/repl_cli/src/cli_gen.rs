@@ -2,57 +2,63 @@ use bumpalo::Bump;
 use inkwell::context::Context;
 use libloading::Library;
 use roc_build::link::llvm_module_to_dylib;
-use roc_collections::all::MutSet;
+use roc_collections::all::{MutMap, MutSet};
+#[cfg(not(windows))]
+use roc_collections::VecMap;
 use roc_error_macros::internal_error;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_gen_llvm::{run_jit_function, run_jit_function_dynamic_type};
+#[cfg(not(windows))]
+use roc_load::Expectations;
 use roc_load::{EntryPoint, MonomorphizedModule};
+#[cfg(not(windows))]
+use roc_module::symbol::{Interns, ModuleId};
 use roc_mono::ir::OptLevel;
+#[cfg(not(windows))]
+use roc_mono::layout::GlobalLayoutInterner;
 use roc_mono::layout::STLayoutInterner;
 use roc_parse::ast::Expr;
-use roc_repl_eval::eval::jit_to_ast;
-use roc_repl_eval::gen::{format_answer, ReplOutput};
+use roc_repl_eval::eval::eval_main;
+use roc_repl_eval::gen::{format_answer, DisplaySize, ReplMain, ReplOutput, Timings};
 use roc_repl_eval::{ReplApp, ReplAppMemory};
+#[cfg(not(windows))]
+use roc_repl_expect::run::{repl_dbgs_in_memory, ExpectMemory};
 use roc_std::RocStr;
 use roc_target::TargetInfo;
-use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 use roc_types::subs::Subs;
+use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 
+use crate::limits::{run_limited, Aborted, EvalLimits};
+
+/// Generate code for the module and run it. If there's a `compile_time` (from `:time`), the output
+/// includes how long each stage took.
 pub fn eval_llvm(
     mut loaded: MonomorphizedModule<'_>,
     target: &Triple,
     opt_level: OptLevel,
-) -> Option<ReplOutput> {
+    limits: EvalLimits,
+    size: DisplaySize,
+    compile_time: Option<Duration>,
+) -> Result<Option<ReplOutput>, Aborted> {
     let arena = Bump::new();
     let target_info = TargetInfo::from(target);
 
-    debug_assert_eq!(loaded.exposed_to_host.top_level_values.len(), 1);
-    let (main_fn_symbol, main_fn_var) = loaded
-        .exposed_to_host
-        .top_level_values
-        .iter()
-        .next()
-        .unwrap();
-    let main_fn_symbol = *main_fn_symbol;
-    let main_fn_var = *main_fn_var;
-
-    // pretty-print the expr type string for later.
-    let expr_type_str = name_and_print_var(
-        main_fn_var,
-        &mut loaded.subs,
-        loaded.module_id,
-        &loaded.interns,
-        DebugPrint::NOTHING,
-    );
-
-    let (_, main_fn_layout) = *loaded
-        .procedures
-        .keys()
-        .find(|(s, _)| *s == main_fn_symbol)?;
+    let Some(main) = ReplMain::find(&mut loaded) else {
+        return Ok(None);
+    };
 
     let interns = loaded.interns.clone();
+    let home = loaded.module_id;
+
+    // The types of the values that `dbg`s show are in here
+    #[cfg(not(windows))]
+    let expectations = std::mem::take(&mut loaded.expectations);
+    #[cfg(not(windows))]
+    let has_dbgs = expectations.values().any(|data| !data.dbgs.is_empty());
+
+    let code_gen_start = Instant::now();
 
     #[cfg(not(target_os = "linux"))]
     let (lib, main_fn_name, subs, layout_interner) =
@@ -60,30 +66,136 @@ pub fn eval_llvm(
             .expect("we produce a valid Dylib");
 
     #[cfg(target_os = "linux")]
-    let (lib, main_fn_name, subs, layout_interner) =
+    let (lib, main_fn_name, subs, layout_interner) = if has_dbgs {
+        // The dev backend doesn't support `dbg` yet
+        mono_module_to_dylib_llvm(&arena, target, loaded, opt_level)
+    } else {
         mono_module_to_dylib_asm(&arena, target, loaded, opt_level)
-            .expect("we produce a valid Dylib");
+    }
+    .expect("we produce a valid Dylib");
+
+    let code_gen_time = code_gen_start.elapsed();
 
+    #[cfg(not(windows))]
+    let dbg_buffer = has_dbgs.then(|| DbgBuffer::new(&lib));
+
+    let layout_interner = layout_interner.into_global();
     let mut app = CliApp { lib };
 
-    let expr = jit_to_ast(
-        &arena,
-        &mut app,
-        main_fn_name,
-        main_fn_layout,
-        main_fn_var,
-        &subs,
-        &interns,
-        layout_interner.into_global().fork(),
-        target_info,
-    );
+    let run = || {
+        let (expr, shortened) = eval_main(
+            &arena,
+            &mut app,
+            main_fn_name,
+            &main,
+            &subs,
+            &interns,
+            home,
+            layout_interner.fork(),
+            target_info,
+            size,
+        );
+
+        // The dbgs ran while evaluating the answer, so they come before it
+        #[cfg(not(windows))]
+        let mut output = match &dbg_buffer {
+            Some(dbg_buffer) => dbg_buffer.format_values(
+                &arena,
+                &expectations,
+                &interns,
+                &layout_interner,
+                size.max_width,
+            ),
+            None => Vec::new(),
+        };
+        #[cfg(windows)]
+        let mut output = Vec::new();
+
+        output.push(expr);
+        // Answers are never empty, so this means it didn't need shortening
+        output.push(shortened.unwrap_or_default());
+
+        output
+    };
 
-    let expr_str = format_answer(&arena, expr).to_string();
+    let run_start = Instant::now();
+    let mut output = if limits.is_unlimited() {
+        run()
+    } else {
+        run_limited(limits, run)?
+    };
+    let timings = compile_time.map(|compile| Timings {
+        compile,
+        code_gen: code_gen_time,
+        run: run_start.elapsed(),
+    });
 
-    Some(ReplOutput {
+    let shortened_expr = output.pop().filter(|expr| !expr.is_empty());
+    let expr_str = output.pop().unwrap_or_default();
+
+    Ok(Some(ReplOutput {
         expr: expr_str,
-        expr_type: expr_type_str,
-    })
+        expr_type: main.expr_type,
+        dbgs: output,
+        shortened_expr,
+        instantiated_type: None,
+        timings,
+    }))
+}
+
+/// Where the `dbg`s buffer the values they show during evaluation (see `LlvmBackendMode::Repl`),
+/// so they can be formatted once it's done.
+/// Shown after the dbgs that fit in the `DbgBuffer`, if some didn't.
+#[cfg(not(windows))]
+const DBG_OUTPUT_TRUNCATED: &str =
+    "(dbg output truncated: the rest of the dbgs didn't fit in the REPL's 1 MiB buffer)";
+
+#[cfg(not(windows))]
+struct DbgBuffer {
+    /// `memory` points into these bytes, which live on the heap, so they can be moved freely.
+    _bytes: Vec<u8>,
+    memory: ExpectMemory<'static>,
+}
+
+#[cfg(not(windows))]
+impl DbgBuffer {
+    /// The generated code leaves out the dbgs that don't fit (see `finish_repl_frame`
+    /// in roc_gen_llvm), so this is how much `dbg` output an evaluation can show.
+    const SIZE: usize = 1 << 20;
+
+    fn new(lib: &Library) -> Self {
+        let mut bytes = vec![0; Self::SIZE];
+        let mut memory = ExpectMemory::from_slice(&mut bytes);
+
+        memory.reset();
+        memory.set_shared_buffer(lib);
+
+        Self {
+            _bytes: bytes,
+            memory,
+        }
+    }
+
+    fn format_values<'a>(
+        &self,
+        arena: &'a Bump,
+        expectations: &VecMap<ModuleId, Expectations>,
+        interns: &'a Interns,
+        layout_interner: &GlobalLayoutInterner<'a>,
+        max_width: Option<usize>,
+    ) -> Vec<String> {
+        let mut values: Vec<String> =
+            repl_dbgs_in_memory(arena, expectations, interns, layout_interner, &self.memory)
+                .into_iter()
+                .map(|expr| format_answer(arena, expr, max_width).to_string())
+                .collect();
+
+        if self.memory.dropped_dbgs() {
+            values.push(DBG_OUTPUT_TRUNCATED.to_string());
+        }
+
+        values
+    }
 }
 
 struct CliApp {
@@ -166,7 +278,6 @@ impl ReplAppMemory for CliMemory {
     }
 }
 
-#[cfg_attr(target_os = "linux", allow(unused))]
 fn mono_module_to_dylib_llvm<'a>(
     arena: &'a Bump,
     target: &Triple,
@@ -207,9 +318,16 @@ fn mono_module_to_dylib_llvm<'a>(
         interns,
         module,
         target_info,
-        mode: LlvmBackendMode::GenTest, // so roc_panic is generated
+        // So roc_panic is generated. The REPL reads dbgs back using roc_repl_expect,
+        // which doesn't support Windows yet.
+        mode: if cfg!(windows) {
+            LlvmBackendMode::GenTest
+        } else {
+            LlvmBackendMode::Repl
+        },
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        def_locations: MutMap::default(),
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
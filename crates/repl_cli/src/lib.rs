@@ -1,24 +1,41 @@
 //! Command Line Interface (CLI) functionality for the Read-Evaluate-Print-Loop (REPL).
 mod cli_gen;
+mod limits;
 
 use bumpalo::Bump;
 use const_format::concatcp;
 use roc_load::MonomorphizedModule;
 use roc_mono::ir::OptLevel;
-use roc_repl_eval::gen::Problems;
+use roc_repl_eval::gen::{DisplaySize, Problems, ReplOutput};
 use roc_repl_ui::colors::{BLUE, END_COL, PINK};
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
-use roc_repl_ui::{format_output, is_incomplete, CONT_PROMPT, PROMPT, SHORT_INSTRUCTIONS, TIPS};
-use roc_reporting::report::{ANSI_STYLE_CODES, DEFAULT_PALETTE};
+use roc_repl_ui::{
+    continuation_indent, expect_passed, format_doc_output, format_doc_output_json,
+    format_env_output, format_env_output_json, format_expect_output, format_expect_output_json,
+    format_output, format_output_json, format_search_output, format_search_output_json,
+    is_incomplete, CONT_PROMPT, PROMPT, SHORT_INSTRUCTIONS, TIPS,
+};
+use roc_reporting::report::{
+    color_enabled, strip_colors_if_disabled, Palette, StyleCodes, ANSI_STYLE_CODES,
+    DEFAULT_PALETTE, PLAIN_PALETTE, PLAIN_STYLE_CODES,
+};
 use roc_target::TargetInfo;
+use rustyline::completion::Completer;
 use rustyline::highlight::{Highlighter, PromptInfo};
 use rustyline::validate::{self, ValidationContext, ValidationResult, Validator};
-use rustyline_derive::{Completer, Helper, Hinter};
+use rustyline::{
+    Cmd, ConditionalEventHandler, Context, Event, EventContext, EventHandler, KeyCode, KeyEvent,
+    Modifiers, RepeatCount,
+};
+use rustyline_derive::{Helper, Hinter};
 use std::borrow::Cow;
+use std::time::Duration;
 use target_lexicon::Triple;
 
 use crate::cli_gen::eval_llvm;
 
+pub use limits::EvalLimits;
+
 pub const WELCOME_MESSAGE: &str = concatcp!(
     "\n  The rockin’ ",
     BLUE,
@@ -31,30 +48,117 @@ pub const WELCOME_MESSAGE: &str = concatcp!(
     "\n\n"
 );
 
-#[derive(Completer, Helper, Hinter, Default)]
+/// How the REPL prints the result of each entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable, colored output
+    #[default]
+    Text,
+    /// One JSON object per entry, on a single line
+    Json,
+}
+
+impl OutputFormat {
+    fn palette(self) -> Palette {
+        match self {
+            OutputFormat::Text if color_enabled() => DEFAULT_PALETTE,
+            OutputFormat::Text | OutputFormat::Json => PLAIN_PALETTE,
+        }
+    }
+
+    fn format(self, opt_output: Option<ReplOutput>, problems: Problems) -> String {
+        match self {
+            OutputFormat::Text => format_output(text_style_codes(), opt_output, problems),
+            OutputFormat::Json => format_output_json(opt_output, problems),
+        }
+    }
+
+    fn format_doc(
+        self,
+        opt_output: Option<ReplOutput>,
+        docs: Option<String>,
+        problems: Problems,
+    ) -> String {
+        match self {
+            OutputFormat::Text => format_doc_output(text_style_codes(), opt_output, docs, problems),
+            OutputFormat::Json => format_doc_output_json(opt_output, docs, problems),
+        }
+    }
+
+    fn format_expect(self, passed: bool, problems: Problems) -> String {
+        match self {
+            OutputFormat::Text => format_expect_output(text_style_codes(), passed, problems),
+            OutputFormat::Json => format_expect_output_json(passed, problems),
+        }
+    }
+
+    fn format_env(self, types: Vec<(String, String)>, problems: Problems) -> String {
+        match self {
+            OutputFormat::Text => format_env_output(text_style_codes(), types, problems),
+            OutputFormat::Json => format_env_output_json(types, problems),
+        }
+    }
+
+    fn format_search(self, results: Vec<(String, String)>, problems: Problems) -> String {
+        match self {
+            OutputFormat::Text => format_search_output(text_style_codes(), results, problems),
+            OutputFormat::Json => format_search_output_json(results, problems),
+        }
+    }
+}
+
+/// The styles for text output, which has none if colors have been disabled.
+fn text_style_codes() -> StyleCodes {
+    if color_enabled() {
+        ANSI_STYLE_CODES
+    } else {
+        PLAIN_STYLE_CODES
+    }
+}
+
+#[derive(Helper, Hinter, Default)]
 pub struct ReplHelper {
     validator: InputValidator,
     state: ReplState,
 }
 
-pub fn main() -> i32 {
+/// Run the interactive REPL. Results get wrapped to fit within `width` columns, which defaults
+/// to the width of the terminal (if the output goes to one).
+pub fn main(
+    show_banner: bool,
+    output_format: OutputFormat,
+    limits: EvalLimits,
+    width: Option<usize>,
+) -> i32 {
     use rustyline::error::ReadlineError;
     use rustyline::Editor;
 
     // To debug rustyline:
     // <UNCOMMENT> env_logger::init();
     // <RUN WITH:> RUST_LOG=rustyline=debug cargo run repl 2> debug.log
-    print!("{WELCOME_MESSAGE}{SHORT_INSTRUCTIONS}");
+    // The banner would break the one-object-per-line protocol of the JSON output.
+    if show_banner && output_format == OutputFormat::Text {
+        print!(
+            "{}{SHORT_INSTRUCTIONS}",
+            strip_colors_if_disabled(WELCOME_MESSAGE)
+        );
+    }
 
     let mut editor = Editor::<ReplHelper>::new();
     let repl_helper = ReplHelper::default();
     editor.set_helper(Some(repl_helper));
+    editor.bind_sequence(
+        KeyEvent(KeyCode::Enter, Modifiers::NONE),
+        EventHandler::Conditional(Box::new(ContinuationHandler)),
+    );
     let target = Triple::host();
     let target_info = TargetInfo::from(&target);
     let mut arena = Bump::new();
+    let prompt = strip_colors_if_disabled(PROMPT);
+    let mut last_output: Option<ReplOutput> = None;
 
     loop {
-        match editor.readline(PROMPT) {
+        match editor.readline(&prompt) {
             Ok(line) => {
                 let line = line.trim();
 
@@ -66,20 +170,121 @@ pub fn main() -> i32 {
                     .state;
 
                 arena.reset();
-                match repl_state.step(&arena, line, target_info, DEFAULT_PALETTE) {
-                    ReplAction::Eval { opt_mono, problems } => {
-                        let output = evaluate(opt_mono, problems, &target);
+                let palette = output_format.palette();
+                // Check every time, in case the terminal got resized
+                let opt_terminal_size = terminal_size();
+                let size = DisplaySize {
+                    max_width: width.or(opt_terminal_size.map(|(columns, _)| columns)),
+                    max_lines: match output_format {
+                        // Leave room for the blank line before the result, the note about
+                        // `:expand` after it, and the next prompt.
+                        OutputFormat::Text => {
+                            opt_terminal_size.map(|(_, rows)| rows.saturating_sub(4).max(1))
+                        }
+                        // Programs reading the JSON want the whole value
+                        OutputFormat::Json => None,
+                    },
+                };
+                match repl_state.step(&arena, line, target_info, palette) {
+                    ReplAction::Eval {
+                        opt_mono,
+                        mut problems,
+                        instantiated_type,
+                        compile_time,
+                    } => {
+                        let opt_output = run_evaluation(
+                            opt_mono,
+                            &mut problems,
+                            &target,
+                            limits,
+                            size,
+                            compile_time,
+                        )
+                        .map(|output| ReplOutput {
+                            instantiated_type,
+                            ..output
+                        });
+
+                        // Remember the whole value, for `:expand` and `:save`
+                        if let Some(output) = &opt_output {
+                            if !output.expr.is_empty() {
+                                last_output = Some(output.clone());
+                            }
+                        }
+
+                        let output = output_format.format(opt_output, problems);
                         // If there was no output, don't print a blank line!
                         // (This happens for something like a type annotation.)
                         if !output.is_empty() {
                             println!("{output}");
                         }
                     }
+                    ReplAction::Expect {
+                        opt_mono,
+                        problems,
+                        failure_report,
+                    } => {
+                        let (output, _) = evaluate_expect_as(
+                            output_format,
+                            opt_mono,
+                            problems,
+                            failure_report,
+                            &target,
+                            limits,
+                        );
+
+                        println!("{output}");
+                    }
+                    ReplAction::TypeOf {
+                        opt_output,
+                        problems,
+                    } => {
+                        println!("{}", output_format.format(opt_output, problems));
+                    }
+                    ReplAction::Doc {
+                        opt_output,
+                        docs,
+                        problems,
+                    } => {
+                        println!("{}", output_format.format_doc(opt_output, docs, problems));
+                    }
+                    ReplAction::Env { types, problems } => {
+                        let output = output_format.format_env(types, problems);
+
+                        if !output.is_empty() {
+                            println!("{output}");
+                        }
+                    }
+                    ReplAction::Search { results, problems } => {
+                        println!("{}", output_format.format_search(results, problems));
+                    }
+                    ReplAction::Load { problems }
+                    | ReplAction::Set { problems }
+                    | ReplAction::Record { problems } => {
+                        let output = output_format.format(None, problems);
+
+                        if !output.is_empty() {
+                            println!("{output}");
+                        }
+                    }
+                    ReplAction::Expand => {
+                        let (output, _) = format_expanded(output_format, last_output.as_ref());
+
+                        println!("{output}");
+                    }
+                    ReplAction::Save { path } => {
+                        let (output, _) =
+                            save_last_output(output_format, last_output.as_ref(), &path);
+
+                        if !output.is_empty() {
+                            println!("{output}");
+                        }
+                    }
                     ReplAction::Exit => {
                         return 0;
                     }
                     ReplAction::Help => {
-                        println!("{TIPS}");
+                        println!("{}", strip_colors_if_disabled(TIPS));
                     }
                     ReplAction::Nothing => {}
                 }
@@ -104,13 +309,284 @@ pub fn main() -> i32 {
     }
 }
 
+/// Evaluate a single input non-interactively (e.g. for `roc repl --eval`), printing only
+/// the answer to stdout. Problems are printed to stderr instead, and result in exit code 1.
+pub fn eval_once(
+    src: &str,
+    output_format: OutputFormat,
+    limits: EvalLimits,
+    width: Option<usize>,
+) -> i32 {
+    let arena = Bump::new();
+    // The whole output goes to the terminal (if it goes to one), since there's no `:expand`
+    let size = DisplaySize {
+        max_width: width.or(terminal_size().map(|(columns, _)| columns)),
+        max_lines: None,
+    };
+    let target = Triple::host();
+    let target_info = TargetInfo::from(&target);
+    let mut repl_state = ReplState::new();
+    let palette = output_format.palette();
+
+    let (output, has_errors) = match repl_state.step(&arena, src, target_info, palette) {
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            instantiated_type,
+            compile_time,
+        } => evaluate_as(
+            output_format,
+            opt_mono,
+            problems,
+            instantiated_type,
+            compile_time,
+            &target,
+            limits,
+            size,
+        ),
+        ReplAction::Expect {
+            opt_mono,
+            problems,
+            failure_report,
+        } => evaluate_expect_as(
+            output_format,
+            opt_mono,
+            problems,
+            failure_report,
+            &target,
+            limits,
+        ),
+        ReplAction::TypeOf {
+            opt_output,
+            problems,
+        } => {
+            let has_errors = !problems.errors.is_empty();
+
+            (output_format.format(opt_output, problems), has_errors)
+        }
+        ReplAction::Doc {
+            opt_output,
+            docs,
+            problems,
+        } => {
+            let has_errors = !problems.errors.is_empty();
+
+            (
+                output_format.format_doc(opt_output, docs, problems),
+                has_errors,
+            )
+        }
+        ReplAction::Env { types, problems } => {
+            let has_errors = !problems.errors.is_empty();
+
+            (output_format.format_env(types, problems), has_errors)
+        }
+        ReplAction::Search { results, problems } => {
+            let has_errors = !problems.errors.is_empty();
+
+            (output_format.format_search(results, problems), has_errors)
+        }
+        ReplAction::Load { problems }
+        | ReplAction::Set { problems }
+        | ReplAction::Record { problems } => {
+            let has_errors = !problems.errors.is_empty();
+
+            (output_format.format(None, problems), has_errors)
+        }
+        // There's only the one input, so there's no result from before it
+        ReplAction::Expand => format_expanded(output_format, None),
+        ReplAction::Save { path } => save_last_output(output_format, None, &path),
+        ReplAction::Help => (strip_colors_if_disabled(TIPS).into_owned(), false),
+        ReplAction::Exit | ReplAction::Nothing => (String::new(), false),
+    };
+
+    // format_output separates entries with leading newlines, which only make sense interactively.
+    let output = output.trim_start_matches('\n');
+
+    // JSON output already describes its problems, so it always goes to stdout.
+    if has_errors && output_format == OutputFormat::Text {
+        eprintln!("{output}");
+
+        1
+    } else if has_errors {
+        println!("{output}");
+
+        1
+    } else {
+        if !output.is_empty() {
+            println!("{output}");
+        }
+
+        0
+    }
+}
+
 pub fn evaluate(
     opt_mono: Option<MonomorphizedModule<'_>>,
     problems: Problems,
+    instantiated_type: Option<String>,
+    compile_time: Option<Duration>,
     target: &Triple,
 ) -> String {
-    let opt_output = opt_mono.and_then(|mono| eval_llvm(mono, target, OptLevel::Normal));
-    format_output(ANSI_STYLE_CODES, opt_output, problems)
+    let (output, _) = evaluate_as(
+        OutputFormat::Text,
+        opt_mono,
+        problems,
+        instantiated_type,
+        compile_time,
+        target,
+        EvalLimits::default(),
+        DisplaySize::default(),
+    );
+
+    output
+}
+
+/// Evaluate and format the output, also returning whether there were any errors
+/// (including the evaluation getting aborted for exceeding its limits).
+#[allow(clippy::too_many_arguments)]
+fn evaluate_as(
+    output_format: OutputFormat,
+    opt_mono: Option<MonomorphizedModule<'_>>,
+    mut problems: Problems,
+    instantiated_type: Option<String>,
+    compile_time: Option<Duration>,
+    target: &Triple,
+    limits: EvalLimits,
+    size: DisplaySize,
+) -> (String, bool) {
+    let opt_output = run_evaluation(opt_mono, &mut problems, target, limits, size, compile_time)
+        .map(|output| ReplOutput {
+            instantiated_type,
+            ..output
+        });
+    let has_errors = !problems.errors.is_empty();
+
+    (output_format.format(opt_output, problems), has_errors)
+}
+
+/// Evaluate an `expect`'s condition and format whether it passed,
+/// also returning whether it failed.
+fn evaluate_expect_as(
+    output_format: OutputFormat,
+    opt_mono: Option<MonomorphizedModule<'_>>,
+    mut problems: Problems,
+    failure_report: String,
+    target: &Triple,
+    limits: EvalLimits,
+) -> (String, bool) {
+    // Only whether it passed gets shown, so there's nothing to wrap
+    let opt_output = run_evaluation(
+        opt_mono,
+        &mut problems,
+        target,
+        limits,
+        DisplaySize::default(),
+        None,
+    );
+    let passed = expect_passed(opt_output, failure_report, &mut problems);
+
+    (output_format.format_expect(passed, problems), !passed)
+}
+
+/// Run the compiled code, reporting it in the problems if the evaluation got aborted.
+/// If there's a `compile_time` (from `:time`), the output includes how long each stage took.
+///
+/// This is the part of evaluating an entry that other frontends to the REPL (like the
+/// Jupyter kernel) share, so that they can format the output however they need to.
+pub fn run_evaluation(
+    opt_mono: Option<MonomorphizedModule<'_>>,
+    problems: &mut Problems,
+    target: &Triple,
+    limits: EvalLimits,
+    size: DisplaySize,
+    compile_time: Option<Duration>,
+) -> Option<ReplOutput> {
+    let mono = opt_mono?;
+
+    match eval_llvm(mono, target, OptLevel::Normal, limits, size, compile_time) {
+        Ok(opt_output) => opt_output,
+        Err(aborted) => {
+            problems.errors.push(aborted.to_string());
+
+            None
+        }
+    }
+}
+
+const NO_LAST_OUTPUT: &str =
+    "There's no result yet! :expand and :save work with the result of the last expression.";
+
+/// Format the whole of the last result, for `:expand`,
+/// also returning whether there was an error (because there's no result yet).
+fn format_expanded(
+    output_format: OutputFormat,
+    last_output: Option<&ReplOutput>,
+) -> (String, bool) {
+    match last_output {
+        Some(output) => {
+            let output = ReplOutput {
+                dbgs: Vec::new(),
+                shortened_expr: None,
+                ..output.clone()
+            };
+
+            (
+                output_format.format(Some(output), Problems::default()),
+                false,
+            )
+        }
+        None => {
+            let problems = Problems {
+                errors: vec![NO_LAST_OUTPUT.to_string()],
+                warnings: Vec::new(),
+            };
+
+            (output_format.format(None, problems), true)
+        }
+    }
+}
+
+/// Write the whole of the last result's value to a file, for `:save`,
+/// also returning whether there was an error.
+fn save_last_output(
+    output_format: OutputFormat,
+    last_output: Option<&ReplOutput>,
+    path: &str,
+) -> (String, bool) {
+    let opt_error = match last_output {
+        Some(output) => std::fs::write(path, format!("{}\n", output.expr))
+            .err()
+            .map(|err| format!("Couldn't save the result to {path}: {err}")),
+        None => Some(NO_LAST_OUTPUT.to_string()),
+    };
+    let has_errors = opt_error.is_some();
+    let problems = Problems {
+        errors: opt_error.into_iter().collect(),
+        warnings: Vec::new(),
+    };
+
+    (output_format.format(None, problems), has_errors)
+}
+
+/// The number of columns and rows of the terminal that the output goes to, if it goes to one
+#[cfg(unix)]
+fn terminal_size() -> Option<(usize, usize)> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0
+        && size.ws_col > 0
+        && size.ws_row > 0
+    {
+        Some((size.ws_col as usize, size.ws_row as usize))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn terminal_size() -> Option<(usize, usize)> {
+    None
 }
 
 #[derive(Default)]
@@ -126,6 +602,44 @@ impl Validator for InputValidator {
     }
 }
 
+/// When Enter is pressed at the end of an incomplete entry (e.g. `x =`), start the next
+/// line already indented, instead of letting the validator insert a bare newline.
+struct ContinuationHandler;
+
+impl ConditionalEventHandler for ContinuationHandler {
+    fn handle(
+        &self,
+        _evt: &Event,
+        _n: RepeatCount,
+        _positive: bool,
+        ctx: &EventContext,
+    ) -> Option<Cmd> {
+        let line = ctx.line();
+
+        if ctx.pos() == line.len() && is_incomplete(line) {
+            Some(Cmd::Insert(1, format!("\n{}", continuation_indent(line))))
+        } else {
+            // Let the validator decide whether to accept the line.
+            None
+        }
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let target_info = TargetInfo::from(&Triple::host());
+
+        Ok(self.state.completions(line, pos, target_info))
+    }
+}
+
 impl Highlighter for ReplHelper {
     fn has_continuation_prompt(&self) -> bool {
         true
@@ -137,7 +651,7 @@ impl Highlighter for ReplHelper {
         info: PromptInfo<'_>,
     ) -> Cow<'b, str> {
         if info.line_no() > 0 {
-            CONT_PROMPT.into()
+            strip_colors_if_disabled(CONT_PROMPT)
         } else {
             prompt.into()
         }
@@ -0,0 +1,258 @@
+//! Limits on how long an evaluation may run and how much memory it may allocate,
+//! as set by `roc repl --timeout` and `roc repl --max-heap`.
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvalLimits {
+    /// Abort evaluations which are still running after this long
+    pub timeout: Option<Duration>,
+    /// Abort evaluations which allocate more than this many bytes
+    pub max_heap: Option<u64>,
+}
+
+impl EvalLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.max_heap.is_none()
+    }
+}
+
+/// Why an evaluation was aborted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aborted {
+    Timeout(Duration),
+    /// The evaluation crashed, which is what happens when it runs out of memory.
+    Crashed {
+        max_heap: Option<u64>,
+    },
+}
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Aborted::Timeout(timeout) => write!(
+                f,
+                "Evaluation aborted: it was still running after the {timeout:?} timeout."
+            ),
+            Aborted::Crashed {
+                max_heap: Some(max_heap),
+            } => write!(
+                f,
+                "Evaluation aborted: it crashed, most likely by allocating more than the {} heap limit.",
+                format_size(*max_heap)
+            ),
+            Aborted::Crashed { max_heap: None } => write!(f, "Evaluation aborted: it crashed."),
+        }
+    }
+}
+
+/// Format a number of bytes the way `--max-heap` accepts it, e.g. `256M`
+fn format_size(bytes: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [(1 << 30, "G"), (1 << 20, "M"), (1 << 10, "K")];
+
+    UNITS
+        .iter()
+        .find(|(size, _)| bytes >= *size && bytes % size == 0)
+        .map(|(size, unit)| format!("{}{unit}", bytes / size))
+        .unwrap_or_else(|| format!("{bytes}-byte"))
+}
+
+/// Call `eval` (which runs the JIT-compiled code and formats its output) in a child process,
+/// which gets killed if it runs longer than the timeout, and which can't grow its heap by more
+/// than `max_heap` bytes. If it finishes in time, return the output it formatted.
+#[cfg(unix)]
+pub fn run_limited<F>(limits: EvalLimits, eval: F) -> Result<Vec<String>, Aborted>
+where
+    F: FnOnce() -> Vec<String>,
+{
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::os::unix::io::FromRawFd;
+    use std::time::Instant;
+
+    let mut fds = [0; 2];
+
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        roc_error_macros::internal_error!(
+            "Could not create a pipe for the REPL's evaluation: {:?}",
+            std::io::Error::last_os_error()
+        );
+    }
+
+    let (mut reader, mut writer) =
+        unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+
+    match unsafe { libc::fork() } {
+        0 => {
+            // we are the child
+            drop(reader);
+
+            if let Some(max_heap) = limits.max_heap {
+                set_heap_limit(max_heap);
+            }
+
+            let output = eval();
+            let exit_code = match writer.write_all(&encode_output(&output)) {
+                Ok(()) => 0,
+                Err(_) => 1,
+            };
+
+            // Use _exit so we don't flush (and thereby duplicate) the parent's buffered output.
+            unsafe { libc::_exit(exit_code) }
+        }
+        -1 => {
+            roc_error_macros::internal_error!(
+                "Could not fork the REPL's evaluation: {:?}",
+                std::io::Error::last_os_error()
+            );
+        }
+        pid => {
+            drop(writer);
+
+            // Read the output while waiting, so a big one can't fill the pipe and block the child.
+            let output_reader = std::thread::spawn(move || {
+                let mut bytes = Vec::new();
+
+                reader.read_to_end(&mut bytes).map(|_| bytes)
+            });
+
+            let start = Instant::now();
+            let mut status = 0;
+
+            while unsafe { libc::waitpid(pid, &mut status, libc::WNOHANG) } == 0 {
+                if let Some(timeout) = limits.timeout {
+                    if start.elapsed() >= timeout {
+                        unsafe {
+                            libc::kill(pid, libc::SIGKILL);
+                            libc::waitpid(pid, &mut status, 0);
+                        }
+
+                        let _ = output_reader.join();
+
+                        return Err(Aborted::Timeout(timeout));
+                    }
+                }
+
+                std::thread::sleep(Duration::from_millis(5));
+            }
+
+            let output = output_reader
+                .join()
+                .ok()
+                .and_then(Result::ok)
+                .and_then(|bytes| decode_output(&bytes));
+
+            match output {
+                Some(output) if libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0 => {
+                    Ok(output)
+                }
+                _ => Err(Aborted::Crashed {
+                    max_heap: limits.max_heap,
+                }),
+            }
+        }
+    }
+}
+
+/// Processes can't be forked on this OS, so evaluations run without limits. This warns about
+/// that the first time it happens, so the limits aren't silently ignored.
+#[cfg(not(unix))]
+pub fn run_limited<F>(limits: EvalLimits, eval: F) -> Result<Vec<String>, Aborted>
+where
+    F: FnOnce() -> Vec<String>,
+{
+    static WARNING: std::sync::Once = std::sync::Once::new();
+
+    if !limits.is_unlimited() {
+        WARNING.call_once(|| {
+            eprintln!(
+                "Warning: --timeout and --max-heap aren't supported on this operating system, so evaluations run without limits."
+            )
+        });
+    }
+
+    Ok(eval())
+}
+
+/// Prefix each string with its length, so the parent can tell where one ends and the next begins
+/// (the strings are formatted Roc values, so they could contain any separator).
+#[cfg(unix)]
+fn encode_output(output: &[String]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for string in output {
+        bytes.extend_from_slice(&(string.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(string.as_bytes());
+    }
+
+    bytes
+}
+
+#[cfg(unix)]
+fn decode_output(mut bytes: &[u8]) -> Option<Vec<String>> {
+    let mut output = Vec::new();
+
+    while !bytes.is_empty() {
+        let len = u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?) as usize;
+        let string = bytes.get(8..8 + len)?;
+
+        output.push(String::from_utf8(string.to_vec()).ok()?);
+        bytes = &bytes[8 + len..];
+    }
+
+    Some(output)
+}
+
+/// Limit the data segment (which includes the heap) of this process to what it
+/// currently uses, plus `max_heap` bytes.
+#[cfg(unix)]
+fn set_heap_limit(max_heap: u64) {
+    let limit = current_data_size().saturating_add(max_heap);
+    let rlimit = libc::rlimit {
+        rlim_cur: limit as libc::rlim_t,
+        rlim_max: limit as libc::rlim_t,
+    };
+
+    unsafe {
+        libc::setrlimit(libc::RLIMIT_DATA, &rlimit);
+    }
+}
+
+/// The size of this process's data segment in bytes, or 0 if it's unknown.
+#[cfg(unix)]
+fn current_data_size() -> u64 {
+    // The 6th field of /proc/self/statm is the data segment's size in pages (Linux only).
+    let pages = std::fs::read_to_string("/proc/self/statm")
+        .ok()
+        .and_then(|statm| statm.split_whitespace().nth(5)?.parse::<u64>().ok());
+
+    match pages {
+        Some(pages) => {
+            let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+
+            pages * page_size.max(0) as u64
+        }
+        // There's no /proc on e.g. macOS, so go by the most memory this process has had
+        // resident instead. That counts the heap, as long as it's been used.
+        None => peak_resident_size(),
+    }
+}
+
+/// The most memory this process has had resident at once, in bytes, or 0 if it's unknown.
+#[cfg(unix)]
+fn peak_resident_size() -> u64 {
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+        return 0;
+    }
+
+    let max_rss = usage.ru_maxrss.max(0) as u64;
+
+    // macOS reports this in bytes, and other Unixes in kilobytes.
+    if cfg!(target_os = "macos") {
+        max_rss
+    } else {
+        max_rss * 1024
+    }
+}
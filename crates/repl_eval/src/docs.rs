@@ -0,0 +1,70 @@
+//! Looks up doc comments for the REPL's `:doc` command, the same way `roc docs` finds them.
+use bumpalo::Bump;
+use roc_load::docs::comments_or_new_lines_to_docs;
+use roc_module::ident::ModuleName;
+use roc_module::symbol::ModuleIds;
+use roc_parse::ast::{Pattern, TypeDef, TypeHeader, ValueDef};
+use roc_parse::module::{module_defs, parse_header};
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+
+/// The doc comment on the def named `ident` in the builtin module named `module_name`
+/// (e.g. `Str` and `concat`), if there is such a def and it has one.
+pub fn builtin_docs(module_name: &str, ident: &str) -> Option<String> {
+    // Modules whose names start with # are for compiler use only, and have no source.
+    if module_name.starts_with('#') {
+        return None;
+    }
+
+    let module_id = ModuleIds::default().get_id(&ModuleName::from(module_name))?;
+
+    module_docs(roc_builtins::roc::module_source(module_id), ident)
+}
+
+/// The doc comment on the top-level def (or type) named `ident` in the given module source.
+pub fn module_docs(module_src: &str, ident: &str) -> Option<String> {
+    let arena = Bump::new();
+    let (_, state) = parse_header(&arena, State::new(module_src.as_bytes())).ok()?;
+    let (_, defs, _) = module_defs().parse(&arena, state, 0).ok()?;
+
+    defs.defs().enumerate().find_map(|(index, def)| {
+        let name = match def {
+            Ok(TypeDef::Alias {
+                header: TypeHeader { name, .. },
+                ..
+            })
+            | Ok(TypeDef::Opaque {
+                header: TypeHeader { name, .. },
+                ..
+            })
+            | Ok(TypeDef::Ability {
+                header: TypeHeader { name, .. },
+                ..
+            }) => name.value,
+            Err(ValueDef::Annotation(loc_pattern, _)) => pattern_ident(&loc_pattern.value)?,
+            Err(ValueDef::AnnotatedBody {
+                ann_pattern: loc_pattern,
+                ..
+            })
+            | Err(ValueDef::Body(loc_pattern, _)) => pattern_ident(&loc_pattern.value)?,
+            Err(ValueDef::Dbg { .. })
+            | Err(ValueDef::Expect { .. })
+            | Err(ValueDef::ExpectFx { .. }) => return None,
+        };
+
+        if name != ident {
+            return None;
+        }
+
+        let spaces_before = &defs.spaces[defs.space_before[index].indices()];
+
+        comments_or_new_lines_to_docs(spaces_before)
+    })
+}
+
+fn pattern_ident<'a>(pattern: &Pattern<'a>) -> Option<&'a str> {
+    match pattern {
+        Pattern::Identifier(name) => Some(*name),
+        _ => None,
+    }
+}
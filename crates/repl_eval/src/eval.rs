@@ -21,6 +21,7 @@ use roc_types::subs::{
     Content, FlatType, GetSubsSlice, RecordFields, Subs, TagExt, TupleElems, UnionTags, Variable,
 };
 
+use crate::gen::{format_answer, format_shortened_answer, DisplaySize, ReplMain};
 use crate::{ReplApp, ReplAppMemory};
 
 struct Env<'a, 'env> {
@@ -28,9 +29,50 @@ struct Env<'a, 'env> {
     subs: &'env Subs,
     target_info: TargetInfo,
     interns: &'a Interns,
+    /// The module whose opaque types we can show the insides of
+    home: ModuleId,
     layout_cache: LayoutCache<'a>,
 }
 
+/// Run the REPL's main function, using whichever backend `app` runs code with, and format
+/// its answer to fit in `size`. Along with the whole answer, this returns a shortened one
+/// if it doesn't fit in `size.max_lines`.
+#[allow(clippy::too_many_arguments)]
+pub fn eval_main<'a, A: ReplApp<'a>>(
+    arena: &'a Bump,
+    app: &mut A,
+    main_fn_name: &str,
+    main: &ReplMain<'a>,
+    subs: &Subs,
+    interns: &'a Interns,
+    home: ModuleId,
+    layout_interner: TLLayoutInterner<'a>,
+    target_info: TargetInfo,
+    size: DisplaySize,
+) -> (String, Option<String>) {
+    let expr = jit_to_ast(
+        arena,
+        app,
+        main_fn_name,
+        main.layout,
+        main.var,
+        subs,
+        interns,
+        home,
+        layout_interner,
+        target_info,
+    );
+
+    let shortened = size
+        .max_lines
+        .and_then(|max_lines| format_shortened_answer(arena, expr, size.max_width, max_lines));
+
+    (
+        format_answer(arena, expr, size.max_width).to_string(),
+        shortened.map(str::to_string),
+    )
+}
+
 /// JIT execute the given main function, and then wrap its results in an Expr
 /// so we can display them to the user using the formatter.
 ///
@@ -39,6 +81,9 @@ struct Env<'a, 'env> {
 /// By traversing the type signature while we're traversing the layout, once
 /// we get to a struct or tag, we know what the labels are and can turn them
 /// back into the appropriate user-facing literals.
+///
+/// Values of opaque types get shown as e.g. `@Age 5` if the type was defined in the `home`
+/// module, since only code in there can see what's inside them. Otherwise they're `<opaque>`.
 #[allow(clippy::too_many_arguments)]
 pub fn jit_to_ast<'a, A: ReplApp<'a>>(
     arena: &'a Bump,
@@ -48,6 +93,7 @@ pub fn jit_to_ast<'a, A: ReplApp<'a>>(
     var: Variable,
     subs: &Subs,
     interns: &'a Interns,
+    home: ModuleId,
     layout_interner: TLLayoutInterner<'a>,
     target_info: TargetInfo,
 ) -> Expr<'a> {
@@ -56,6 +102,7 @@ pub fn jit_to_ast<'a, A: ReplApp<'a>>(
         subs,
         target_info,
         interns,
+        home,
         layout_cache: LayoutCache::new(layout_interner, target_info),
     };
 
@@ -221,6 +268,16 @@ fn apply_newtypes<'a>(
                 let field = Loc::at_zero(AssignedField::RequiredValue(label, &[], field_val));
                 expr = Expr::Record(Collection::with_items(&*arena.alloc([field])))
             }
+            NewtypeKind::Opaque(Symbol::DICT_DICT) => {
+                expr = dict_to_ast(env, expr);
+            }
+            NewtypeKind::Opaque(Symbol::SET_SET) => {
+                expr = set_to_ast(env, expr);
+            }
+            NewtypeKind::Opaque(name) if name.module_id() != env.home => {
+                // What's inside is none of our business
+                expr = OPAQUE_VALUE;
+            }
             NewtypeKind::Opaque(name) => {
                 let opaque_name = arena.alloc(format!("@{}", name.as_str(env.interns)));
                 let opaque_ref = &*arena.alloc(Loc::at_zero(Expr::OpaqueRef(opaque_name)));
@@ -233,6 +290,66 @@ fn apply_newtypes<'a>(
     expr
 }
 
+/// Dicts are shown the way you'd write them, rather than as their internal representation:
+/// `{ data, dataIndices, metadata, size }` becomes `Dict.fromList data`.
+fn dict_to_ast<'a>(env: &Env<'a, '_>, expr: Expr<'a>) -> Expr<'a> {
+    let data = match expr {
+        Expr::Record(fields) => fields.iter().find_map(|field| match field.value {
+            AssignedField::RequiredValue(label, _, value) if label.value == "data" => Some(value),
+            _ => None,
+        }),
+        _ => None,
+    };
+
+    match data {
+        Some(data) => from_list_to_ast(env, "Dict", data),
+        None => OPAQUE_VALUE,
+    }
+}
+
+/// A `Set k` wraps a `Dict k {}`, which has already been turned into `Dict.fromList [(k, {})]`;
+/// drop the `{}`s and show it as `Set.fromList [k]`.
+fn set_to_ast<'a>(env: &Env<'a, '_>, expr: Expr<'a>) -> Expr<'a> {
+    let entries = match expr {
+        Expr::Apply(_, [list], _) => match list.value {
+            Expr::List(entries) => entries,
+            _ => return OPAQUE_VALUE,
+        },
+        _ => return OPAQUE_VALUE,
+    };
+
+    let arena = env.arena;
+    let mut keys = Vec::with_capacity_in(entries.len(), arena);
+
+    for entry in entries.iter() {
+        match entry.value {
+            Expr::Tuple(elems) if !elems.is_empty() => keys.push(elems.items[0]),
+            _ => return OPAQUE_VALUE,
+        }
+    }
+
+    let list = &*arena.alloc(Loc::at_zero(Expr::List(Collection::with_items(
+        keys.into_bump_slice(),
+    ))));
+
+    from_list_to_ast(env, "Set", list)
+}
+
+fn from_list_to_ast<'a>(
+    env: &Env<'a, '_>,
+    module_name: &'a str,
+    list: &'a Loc<Expr<'a>>,
+) -> Expr<'a> {
+    let arena = env.arena;
+    let from_list = &*arena.alloc(Loc::at_zero(Expr::Var {
+        module_name,
+        ident: "fromList",
+    }));
+    let args = arena.alloc_slice_copy(&[list]);
+
+    Expr::Apply(from_list, args, CalledVia::Space)
+}
+
 fn unroll_recursion_var<'env>(env: &Env<'_, 'env>, mut content: &'env Content) -> &'env Content {
     while let Content::RecursionVar { structure, .. } = content {
         content = env.subs.get_content_without_compacting(*structure);
@@ -364,6 +481,11 @@ const OPAQUE_FUNCTION: Expr = Expr::Var {
     ident: "<function>",
 };
 
+const OPAQUE_VALUE: Expr = Expr::Var {
+    module_name: "",
+    ident: "<opaque>",
+};
+
 fn jit_to_ast_help<'a, A: ReplApp<'a>>(
     env: &mut Env<'a, '_>,
     app: &mut A,
@@ -424,11 +546,7 @@ fn jit_to_ast_help<'a, A: ReplApp<'a>>(
         }
         LayoutRepr::Builtin(Builtin::Decimal) => num_helper!(RocDec),
         LayoutRepr::Builtin(Builtin::Str) => {
-            let body = |mem: &A::Memory, addr| {
-                let string = mem.deref_str(addr);
-                let arena_str = env.arena.alloc_str(string);
-                Expr::Str(StrLiteral::PlainLine(arena_str))
-            };
+            let body = |mem: &A::Memory, addr| str_to_ast(env.arena, mem.deref_str(addr));
 
             app.call_function_returns_roc_str(env.target_info, main_fn_name, body)
         }
@@ -630,9 +748,7 @@ fn addr_to_ast<'a, M: ReplAppMemory>(
             list_to_ast(env, mem, elem_addr, len, elem_layout, raw_content)
         }
         (_, LayoutRepr::Builtin(Builtin::Str)) => {
-            let string = mem.deref_str(addr);
-            let arena_str = env.arena.alloc_str(string);
-            Expr::Str(StrLiteral::PlainLine(arena_str))
+            str_to_ast(env.arena, mem.deref_str(addr))
         }
         (_, LayoutRepr::Struct (field_layouts)) => match raw_content {
             Content::Structure(FlatType::Record(fields, _)) => {
@@ -1486,3 +1602,33 @@ fn number_literal_to_ast<T: std::fmt::Display>(arena: &Bump, num: T) -> Expr<'_>
     write!(string, "{num}").unwrap();
     Expr::Num(string.into_bump_str())
 }
+
+/// The formatter shows strings containing newlines (or quotes) as multiline `"""` strings,
+/// with the newlines as they are, so escape everything else that couldn't appear in one as-is.
+/// That way the string reads back the same when the output gets used as code.
+fn str_to_ast<'a>(arena: &'a Bump, string: &str) -> Expr<'a> {
+    use std::fmt::Write;
+
+    let mut escaped = bumpalo::collections::String::with_capacity_in(string.len(), arena);
+    let mut quotes_in_a_row = 0;
+
+    for ch in string.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            // Three quotes in a row would end a multiline string
+            '"' if quotes_in_a_row == 2 => escaped.push_str("\\\""),
+            '\n' | '"' => escaped.push(ch),
+            _ if ch.is_control() => write!(escaped, "\\u({:04X})", ch as u32).unwrap(),
+            _ => escaped.push(ch),
+        }
+
+        quotes_in_a_row = match ch {
+            '"' if quotes_in_a_row < 2 => quotes_in_a_row + 1,
+            _ => 0,
+        };
+    }
+
+    Expr::Str(StrLiteral::PlainLine(escaped.into_bump_str()))
+}
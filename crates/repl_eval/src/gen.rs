@@ -1,29 +1,112 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError, Threading};
-use roc_packaging::cache::{self, RocCacheDir};
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
+use roc_compile::{Diagnostic, Options};
+use roc_load::{LoadedModule, Threading};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_mono::ir::ProcLayout;
+use roc_mono::layout::{Builtin, GlobalLayoutInterner, LayoutCache, LayoutRepr};
 use roc_problem::Severity;
-use roc_reporting::report::Palette;
-use std::path::PathBuf;
+use roc_reporting::report::{Palette, DEFAULT_PALETTE};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use roc_fmt::annotation::Formattable;
 use roc_fmt::annotation::{Newlines, Parens};
-use roc_load::{LoadingProblem, MonomorphizedModule};
-use roc_parse::ast::Expr;
-use roc_region::all::LineInfo;
-use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
-use roc_solve::FunctionKind;
+use roc_fmt::spaces::INDENT;
+use roc_load::MonomorphizedModule;
+use roc_parse::ast::{AssignedField, Collection, CommentOrNewline, Expr};
+use roc_region::all::{LineInfo, Loc, Position, Region};
+use roc_reporting::report::{Report, RocDocAllocator};
 use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::subs::{Content, FlatType, Subs, Variable};
+use ven_pretty::DocAllocator;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReplOutput {
     pub expr: String,
     pub expr_type: String,
+    /// The values shown by `dbg` while evaluating, in the order they ran
+    pub dbgs: Vec<String>,
+    /// A version of `expr` with lists shortened, to show instead if `expr` is too long
+    pub shortened_expr: Option<String>,
+    /// What `expr_type` defaults to when it has polymorphic numbers (see `instantiated_type`)
+    pub instantiated_type: Option<String>,
+    /// How long each stage of the evaluation took, if the entry started with `:time`
+    pub timings: Option<Timings>,
 }
 
-pub fn format_answer<'a>(arena: &'a Bump, answer: Expr<'_>) -> &'a str {
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    /// Parsing, typechecking, and monomorphizing the entry
+    pub compile: Duration,
+    /// Generating machine code for it
+    pub code_gen: Duration,
+    /// Running that code and formatting the result
+    pub run: Duration,
+}
+
+/// The function that evaluates the expression entered into the REPL
+#[derive(Debug, Clone)]
+pub struct ReplMain<'a> {
+    pub symbol: Symbol,
+    pub var: Variable,
+    pub layout: ProcLayout<'a>,
+    /// The pretty-printed type of the expression
+    pub expr_type: String,
+}
+
+impl<'a> ReplMain<'a> {
+    /// Find the main function in the module, which every backend needs to do before generating
+    /// code for it. Returns `None` if there's no procedure for it.
+    pub fn find(mono: &mut MonomorphizedModule<'a>) -> Option<Self> {
+        debug_assert_eq!(mono.exposed_to_host.top_level_values.len(), 1);
+        let (symbol, var) = mono.exposed_to_host.top_level_values.iter().next()?;
+        let (symbol, var) = (*symbol, *var);
+
+        // pretty-print the expr type string for later.
+        let expr_type = name_and_print_var(
+            var,
+            &mut mono.subs,
+            mono.module_id,
+            &mono.interns,
+            DebugPrint::NOTHING,
+        );
+
+        let (_, layout) = mono
+            .procedures
+            .keys()
+            .find(|(s, _)| *s == symbol)
+            .copied()?;
+
+        Some(Self {
+            symbol,
+            var,
+            layout,
+            expr_type,
+        })
+    }
+}
+
+/// How much room there is to show a result in
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DisplaySize {
+    /// Break results which are wider than this over multiple lines
+    pub max_width: Option<usize>,
+    /// Leave out list elements from results which would take up more lines than this
+    pub max_lines: Option<usize>,
+}
+
+/// Format a value for display. If there's a `max_width`, any lists, records, and tuples
+/// that don't fit within that many columns get broken over multiple lines.
+pub fn format_answer<'a>(arena: &'a Bump, answer: Expr<'a>, max_width: Option<usize>) -> &'a str {
     match answer {
         Expr::Closure(_, _) | Expr::MalformedClosure => "<function>",
         _ => {
+            let answer = match max_width {
+                Some(max_width) => Wrapper { arena, max_width }.wrap(answer, false, 0, 0),
+                None => answer,
+            };
             let mut expr = roc_fmt::Buf::new_in(arena);
 
             answer.format_with_options(&mut expr, Parens::NotNeeded, Newlines::Yes, 0);
@@ -33,6 +116,256 @@ pub fn format_answer<'a>(arena: &'a Bump, answer: Expr<'_>) -> &'a str {
     }
 }
 
+/// If formatting a value takes more than `max_lines` lines, format it again with its lists
+/// shortened as little as possible to fit, e.g. `[1, 2, … 4,982 more elements]`.
+pub fn format_shortened_answer<'a>(
+    arena: &'a Bump,
+    answer: Expr<'a>,
+    max_width: Option<usize>,
+    max_lines: usize,
+) -> Option<&'a str> {
+    let fits = |formatted: &str| formatted.lines().count() <= max_lines;
+
+    if fits(format_answer(arena, answer, max_width)) {
+        return None;
+    }
+
+    let longest = longest_list(&answer);
+
+    if longest == 0 {
+        // There's nothing that could be left out
+        return None;
+    }
+
+    // Find the most elements every list can keep without going over. If even showing none of
+    // them is too long, that's still the shortest it can get.
+    let mut most = 0;
+    let mut shortened = format_answer(arena, shorten_lists(arena, answer, 0), max_width);
+    let mut too_many = longest;
+
+    while most + 1 < too_many {
+        let count = (most + too_many) / 2;
+        let formatted = format_answer(arena, shorten_lists(arena, answer, count), max_width);
+
+        if fits(formatted) {
+            most = count;
+            shortened = formatted;
+        } else {
+            too_many = count;
+        }
+    }
+
+    Some(shortened)
+}
+
+/// The number of elements in the longest list anywhere in the expression
+fn longest_list(expr: &Expr<'_>) -> usize {
+    match expr {
+        Expr::List(items) => items
+            .iter()
+            .map(|item| longest_list(&item.value))
+            .max()
+            .unwrap_or(0)
+            .max(items.len()),
+        Expr::Tuple(items) => items
+            .iter()
+            .map(|item| longest_list(&item.value))
+            .max()
+            .unwrap_or(0),
+        Expr::Record(fields) => fields
+            .iter()
+            .map(|field| match field.value {
+                AssignedField::RequiredValue(_, _, loc_value) => longest_list(&loc_value.value),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0),
+        Expr::Apply(_, loc_args, _) => loc_args
+            .iter()
+            .map(|arg| longest_list(&arg.value))
+            .max()
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Leave out all but the first `max_elems` elements of every list in the expression,
+/// putting a note of how many were left out in their place.
+fn shorten_lists<'a>(arena: &'a Bump, expr: Expr<'a>, max_elems: usize) -> Expr<'a> {
+    let shorten = |loc_expr: &Loc<Expr<'a>>| -> &'a Loc<Expr<'a>> {
+        let shortened = shorten_lists(arena, loc_expr.value, max_elems);
+
+        arena.alloc(Loc::at(loc_expr.region, shortened))
+    };
+
+    match expr {
+        Expr::List(items) => {
+            let mut kept = bumpalo::collections::Vec::with_capacity_in(max_elems + 1, arena);
+
+            kept.extend(items.iter().take(max_elems).map(|item| shorten(item)));
+
+            if items.len() > max_elems {
+                let note = Expr::Var {
+                    module_name: "",
+                    ident: arena.alloc_str(&more_elements_note(items.len() - max_elems)),
+                };
+
+                kept.push(&*arena.alloc(Loc::at_zero(note)));
+            }
+
+            Expr::List(Collection::with_items(kept.into_bump_slice()))
+        }
+        Expr::Tuple(items) => Expr::Tuple(items.map_items(arena, |item| shorten(item))),
+        Expr::Record(fields) => {
+            Expr::Record(fields.map_items(arena, |loc_field| match loc_field.value {
+                AssignedField::RequiredValue(name, spaces, loc_value) => Loc::at(
+                    loc_field.region,
+                    AssignedField::RequiredValue(name, spaces, shorten(loc_value)),
+                ),
+                _ => *loc_field,
+            }))
+        }
+        Expr::Apply(loc_fn, loc_args, called_via) => Expr::Apply(
+            loc_fn,
+            arena.alloc_slice_fill_iter(loc_args.iter().map(|arg| shorten(arg))),
+            called_via,
+        ),
+        _ => expr,
+    }
+}
+
+/// e.g. `… 4,982 more elements`
+fn more_elements_note(count: usize) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+
+        grouped.push(digit);
+    }
+
+    let noun = if count == 1 { "element" } else { "elements" };
+
+    format!("… {grouped} more {noun}")
+}
+
+const NEWLINE: &[CommentOrNewline<'static>] = &[CommentOrNewline::Newline];
+
+/// Puts newlines in front of the items of collections which are too wide to fit on one line,
+/// which the formatter then lays out the same way as source code written like that.
+struct Wrapper<'a> {
+    arena: &'a Bump,
+    max_width: usize,
+}
+
+impl<'a> Wrapper<'a> {
+    /// How many columns the expression takes up when formatted without any line breaks
+    fn flat_width(&self, expr: &Expr<'a>, in_apply: bool) -> usize {
+        let parens = if in_apply {
+            Parens::InApply
+        } else {
+            Parens::NotNeeded
+        };
+        let mut buf = roc_fmt::Buf::new_in(self.arena);
+
+        expr.format_with_options(&mut buf, parens, Newlines::No, 0);
+
+        buf.into_bump_str()
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// `column` is how much else shares the line with the start of the expression, including
+    /// anything that follows it (like the comma after a collection's item). `indent` is the
+    /// indentation of the line the expression starts on, which its items get indented from.
+    fn wrap(&self, expr: Expr<'a>, in_apply: bool, column: usize, indent: usize) -> Expr<'a> {
+        if column + self.flat_width(&expr, in_apply) <= self.max_width {
+            return expr;
+        }
+
+        let item_indent = indent + INDENT as usize;
+
+        match expr {
+            Expr::List(items) => Expr::List(self.wrap_items(items, item_indent)),
+            Expr::Tuple(items) => Expr::Tuple(self.wrap_items(items, item_indent)),
+            Expr::Record(fields) => Expr::Record(fields.map_items(self.arena, |loc_field| {
+                let field = match loc_field.value {
+                    AssignedField::RequiredValue(name, spaces, loc_value) => {
+                        // The value comes after `name: ` and is followed by a comma
+                        let column = item_indent + name.value.len() + 3;
+                        let value = self.wrap(loc_value.value, false, column, item_indent);
+
+                        AssignedField::RequiredValue(
+                            name,
+                            spaces,
+                            self.arena.alloc(Loc::at(loc_value.region, value)),
+                        )
+                    }
+                    other => other,
+                };
+
+                Loc::at(
+                    loc_field.region,
+                    AssignedField::SpaceBefore(self.arena.alloc(field), NEWLINE),
+                )
+            })),
+            Expr::Apply(loc_fn, loc_args, called_via) => {
+                let Some((last, rest)) = loc_args.split_last() else {
+                    return expr;
+                };
+
+                // Arguments which get broken over multiple lines go on lines of their own,
+                // except that a list, record, or tuple at the end can start on the first line.
+                let rest: Vec<_> = rest
+                    .iter()
+                    .map(|loc_arg| self.wrap(loc_arg.value, true, item_indent, item_indent))
+                    .collect();
+                let is_outdentable =
+                    matches!(last.value, Expr::List(_) | Expr::Record(_) | Expr::Tuple(_));
+                let last = if is_outdentable && !rest.iter().any(|arg| arg.is_multiline()) {
+                    let before = self.flat_width(&loc_fn.value, true)
+                        + rest
+                            .iter()
+                            .map(|arg| self.flat_width(arg, true) + 1)
+                            .sum::<usize>();
+
+                    self.wrap(last.value, true, column + before + 1, indent)
+                } else {
+                    self.wrap(last.value, true, item_indent, item_indent)
+                };
+                let mut args =
+                    bumpalo::collections::Vec::with_capacity_in(loc_args.len(), self.arena);
+
+                for (arg, loc_arg) in rest.into_iter().chain([last]).zip(loc_args) {
+                    args.push(&*self.arena.alloc(Loc::at(loc_arg.region, arg)));
+                }
+
+                Expr::Apply(loc_fn, args.into_bump_slice(), called_via)
+            }
+            _ => expr,
+        }
+    }
+
+    fn wrap_items(
+        &self,
+        items: Collection<'a, &'a Loc<Expr<'a>>>,
+        item_indent: usize,
+    ) -> Collection<'a, &'a Loc<Expr<'a>>> {
+        items.map_items(self.arena, |loc_item| {
+            // Each item is followed by a comma
+            let item = self.wrap(loc_item.value, false, item_indent + 1, item_indent);
+            let item = Expr::SpaceBefore(self.arena.alloc(item), NEWLINE);
+
+            &*self.arena.alloc(Loc::at(loc_item.region, item))
+        })
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct Problems {
     pub errors: Vec<String>,
@@ -45,138 +378,410 @@ impl Problems {
     }
 }
 
+/// Modules (e.g. from the REPL's `:load` command) that the REPL's app module imports.
+#[derive(Default, Debug, Clone)]
+pub struct ReplImports {
+    /// The directory the imported modules are in
+    pub src_dir: Option<PathBuf>,
+    /// Entries for the app header's `imports` list, e.g. `Foo.{ bar, baz }`
+    pub entries: Vec<String>,
+}
+
 pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
     defs: I,
+    imports: &ReplImports,
     expr: &str,
     target_info: TargetInfo,
     palette: Palette,
 ) -> (Option<MonomorphizedModule<'a>>, Problems) {
-    let filename = PathBuf::from("");
-    let src_dir = PathBuf::from("fake/test/path");
-    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, defs, expr);
-    let loaded = roc_load::load_and_monomorphize_from_str(
+    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, defs, imports, expr);
+    let compiled = roc_compile::compile_str(
         arena,
-        filename,
+        Path::new(""),
         module_src,
-        src_dir,
-        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
-        LoadConfig {
-            target_info,
-            function_kind: FunctionKind::LambdaSet,
-            render: roc_reporting::report::RenderTarget::ColorTerminal,
-            palette,
-            threading: Threading::Single,
-            exec_mode: ExecutionMode::Executable,
-        },
+        &repl_options(imports, target_info, palette),
     );
+    let problems = collect_problems(module_src, bytes_before_expr, compiled.diagnostics);
 
-    let mut loaded = match loaded {
-        Ok(v) => v,
-        Err(LoadMonomorphizedError::ErrorModule(m)) => {
-            todo!(
-                "error while loading module: {:?}",
-                (m.can_problems, m.type_problems)
-            );
-        }
-        Err(LoadMonomorphizedError::LoadingProblem(LoadingProblem::FormattedReport(report))) => {
-            return (
-                None,
-                Problems {
-                    errors: vec![report],
-                    warnings: Vec::new(),
-                },
-            );
-        }
-        Err(e) => {
-            todo!("error while loading module: {:?}", e)
+    (compiled.module, problems)
+}
+
+/// Like [`compile_to_mono`], but stops after type checking and returns the
+/// pretty-printed type of the expression instead of a monomorphized module.
+///
+/// This skips code generation and execution entirely, which is what the
+/// REPL's `:type` command wants.
+pub fn compile_to_type<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    defs: I,
+    imports: &ReplImports,
+    expr: &str,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> (Option<String>, Problems) {
+    let (opt_loaded, problems) = typecheck_expr(arena, defs, imports, expr, target_info, palette);
+
+    let opt_type = opt_loaded.map(|(mut loaded, var)| {
+        name_and_print_var(
+            var,
+            loaded.solved.inner_mut(),
+            loaded.module_id,
+            &loaded.interns,
+            DebugPrint::NOTHING,
+        )
+    });
+
+    (opt_type, problems)
+}
+
+/// Typecheck the expression and, if it turns out to be a record, return the names
+/// of its fields (in sorted order). Used for tab-completing `record.field` accesses.
+pub fn record_field_names<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    defs: I,
+    imports: &ReplImports,
+    expr: &str,
+    target_info: TargetInfo,
+) -> Vec<String> {
+    let (opt_loaded, problems) =
+        typecheck_expr(arena, defs, imports, expr, target_info, DEFAULT_PALETTE);
+
+    let (loaded, mut var) = match opt_loaded {
+        Some(loaded) if problems.errors.is_empty() => loaded,
+        _ => return Vec::new(),
+    };
+
+    let subs = loaded.solved.inner();
+
+    loop {
+        match subs.get_content_without_compacting(var) {
+            Content::Alias(_, _, real_var, _) => {
+                var = *real_var;
+            }
+            Content::Structure(FlatType::Record(fields, ext)) => {
+                return fields
+                    .sorted_iterator(subs, *ext)
+                    .map(|(label, _)| label.to_string())
+                    .collect();
+            }
+            _ => return Vec::new(),
         }
+    }
+}
+
+/// Typecheck the defs, and return the name and type of each value they define, sorted by name.
+/// This is what the REPL's `:env` command shows.
+pub fn def_types<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    defs: I,
+    imports: &ReplImports,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> (Vec<(String, String)>, Problems) {
+    let (opt_loaded, problems) = typecheck_expr(arena, defs, imports, "{}", target_info, palette);
+
+    let mut loaded = match opt_loaded {
+        Some((loaded, _)) => loaded,
+        None => return (Vec::new(), problems),
     };
 
-    let MonomorphizedModule {
+    let LoadedModule {
+        module_id,
         interns,
-        sources,
-        can_problems,
-        type_problems,
+        solved,
+        declarations_by_id,
+        exposed_to_host,
         ..
     } = &mut loaded;
 
-    let mut problems = Problems::default();
+    let declarations = match declarations_by_id.get(module_id) {
+        Some(declarations) => declarations,
+        None => return (Vec::new(), problems),
+    };
 
-    let errors = &mut problems.errors;
-    let warnings = &mut problems.warnings;
+    let mut types: Vec<(String, String)> = declarations
+        .symbols
+        .iter()
+        .zip(declarations.variables.iter())
+        // Skip the `replOutput` def we wrapped the (empty) expression in.
+        .filter(|(loc_symbol, _)| !exposed_to_host.contains_key(&loc_symbol.value))
+        .map(|(loc_symbol, var)| {
+            let name = loc_symbol.value.as_str(interns).to_string();
+            let var_type = name_and_print_var(
+                *var,
+                solved.inner_mut(),
+                *module_id,
+                interns,
+                DebugPrint::NOTHING,
+            );
 
-    for (home, (module_path, src)) in sources.iter() {
-        let can_probs = can_problems.remove(home).unwrap_or_default();
-        let type_probs = type_problems.remove(home).unwrap_or_default();
+            (name, var_type)
+        })
+        .collect();
 
-        let error_count = can_probs.len() + type_probs.len();
+    types.sort();
 
-        if error_count == 0 {
-            continue;
-        }
+    (types, problems)
+}
 
-        let line_info = LineInfo::new(module_src);
-        let src_lines: Vec<&str> = src.split('\n').collect();
+/// What the expression's type becomes once its polymorphic numbers get the types the backend
+/// defaults them to, e.g. `I64` for `Num *` or `List Dec` for `List (Frac *)`.
+/// Returns `None` if that's no different from its type.
+///
+/// This is what the REPL shows after the type when `:set show-instantiated` is on.
+pub fn instantiated_type(
+    mono: &mut MonomorphizedModule<'_>,
+    target_info: TargetInfo,
+) -> Option<String> {
+    let var = *mono.exposed_to_host.top_level_values.values().next()?;
+    let number_vars = find_number_vars(&mono.subs, var);
 
-        // Report parsing and canonicalization problems
-        let alloc = RocDocAllocator::new(&src_lines, *home, interns);
+    if number_vars.is_empty() {
+        return None;
+    }
 
-        for problem in can_probs.into_iter() {
-            // Filter out all warnings and errors whose regions end before this,
-            // because they must be part of the defs (excluding the most renently added def,
-            // if that's the one being evaluated) and therefore not things we should show.
-            // This filters out things like shadowing warnings and unused def warnings.
-            if problem.region().unwrap_or_default().end().offset as usize >= bytes_before_expr {
-                let report = can_problem(&alloc, &line_info, module_path.clone(), problem);
-                let severity = report.severity;
-                let mut buf = String::new();
+    // Ask for the layouts the backend would use, so this can't disagree with what it does.
+    let arena = Bump::new();
+    let interner = GlobalLayoutInterner::with_capacity(4, target_info);
+    let mut layout_cache = LayoutCache::new(interner.fork(), target_info);
+    let defaults: Vec<(Variable, Variable)> = number_vars
+        .into_iter()
+        .filter_map(|number_var| {
+            let layout = layout_cache.from_var(&arena, number_var, &mono.subs).ok()?;
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+            Some((number_var, number_type_var(layout_cache.get_repr(layout))?))
+        })
+        .collect();
 
-                match severity {
-                    Severity::Warning => {
-                        warnings.push(buf);
-                    }
-                    Severity::Fatal | Severity::RuntimeError => {
-                        errors.push(buf);
-                    }
-                }
-            }
+    // Only the printed type should change, not the one that gets compiled.
+    let subs = &mut mono.subs;
+    let snapshot = subs.snapshot();
+    let expr_type = name_and_print_var(
+        var,
+        subs,
+        mono.module_id,
+        &mono.interns,
+        DebugPrint::NOTHING,
+    );
+
+    for (number_var, default_var) in defaults {
+        let content = *subs.get_content_without_compacting(default_var);
+
+        subs.set_content(number_var, content);
+    }
+
+    let instantiated = name_and_print_var(
+        var,
+        subs,
+        mono.module_id,
+        &mono.interns,
+        DebugPrint::NOTHING,
+    );
+
+    subs.rollback_to(snapshot);
+
+    (instantiated != expr_type).then_some(instantiated)
+}
+
+/// The variables of the number types (like `Num *` or `Frac a`) that appear when printing `var`
+fn find_number_vars(subs: &Subs, var: Variable) -> Vec<Variable> {
+    let mut number_vars = Vec::new();
+    let mut seen = Vec::new();
+    let mut stack = vec![var];
+
+    while let Some(var) = stack.pop() {
+        let root = subs.get_root_key_without_compacting(var);
+
+        if seen.contains(&root) {
+            continue;
         }
 
-        for problem in type_probs {
-            if let Some(report) = type_problem(&alloc, &line_info, module_path.clone(), problem) {
-                let severity = report.severity;
-                let mut buf = String::new();
+        seen.push(root);
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+        match subs.get_content_without_compacting(var) {
+            Content::Alias(Symbol::NUM_NUM | Symbol::NUM_INT | Symbol::NUM_FRAC, ..) => {
+                number_vars.push(var);
+            }
+            // Only an alias's arguments get printed, not what it stands for
+            Content::Alias(_, args, _, _) => {
+                stack.extend(args.named_type_arguments().map(|index| subs[index]));
+            }
+            Content::Structure(FlatType::Apply(_, args)) => {
+                stack.extend(args.into_iter().map(|index| subs[index]));
+            }
+            Content::Structure(FlatType::Record(fields, ext)) => {
+                stack.extend(fields.iter_variables().map(|index| subs[index]));
+                stack.push(*ext);
+            }
+            Content::Structure(FlatType::Tuple(elems, ext)) => {
+                stack.extend(elems.iter_variables().map(|index| subs[index]));
+                stack.push(*ext);
+            }
+            Content::Structure(
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext),
+            ) => {
+                for slice_index in tags.variables() {
+                    let slice = subs[slice_index];
 
-                match severity {
-                    Severity::Warning => {
-                        warnings.push(buf);
-                    }
-                    Severity::Fatal | Severity::RuntimeError => {
-                        errors.push(buf);
-                    }
+                    stack.extend(slice.into_iter().map(|index| subs[index]));
                 }
+
+                stack.push(ext.var());
             }
+            // A function's numbers stay polymorphic until it gets called, so leave those alone.
+            _ => {}
         }
     }
 
-    (Some(loaded), problems)
+    number_vars
+}
+
+/// The builtin type variable for a number with the given layout, e.g. `I64` for `Layout::I64`
+fn number_type_var(repr: LayoutRepr<'_>) -> Option<Variable> {
+    let var = match repr {
+        LayoutRepr::Builtin(Builtin::Int(width)) => match width {
+            IntWidth::U8 => Variable::U8,
+            IntWidth::U16 => Variable::U16,
+            IntWidth::U32 => Variable::U32,
+            IntWidth::U64 => Variable::U64,
+            IntWidth::U128 => Variable::U128,
+            IntWidth::I8 => Variable::I8,
+            IntWidth::I16 => Variable::I16,
+            IntWidth::I32 => Variable::I32,
+            IntWidth::I64 => Variable::I64,
+            IntWidth::I128 => Variable::I128,
+        },
+        LayoutRepr::Builtin(Builtin::Float(FloatWidth::F32)) => Variable::F32,
+        LayoutRepr::Builtin(Builtin::Float(FloatWidth::F64)) => Variable::F64,
+        LayoutRepr::Builtin(Builtin::Decimal) => Variable::DEC,
+        _ => return None,
+    };
+
+    Some(var)
+}
+
+fn typecheck_expr<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    defs: I,
+    imports: &ReplImports,
+    expr: &str,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> (Option<(LoadedModule, Variable)>, Problems) {
+    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, defs, imports, expr);
+    let checked = roc_compile::check_str(
+        Path::new(""),
+        module_src,
+        &repl_options(imports, target_info, palette),
+    );
+    let problems = collect_problems(module_src, bytes_before_expr, checked.diagnostics);
+
+    let opt_loaded = checked.module.and_then(|loaded| {
+        debug_assert_eq!(loaded.exposed_to_host.len(), 1);
+        let var = loaded.exposed_to_host.values().next().copied()?;
+
+        Some((loaded, var))
+    });
+
+    (opt_loaded, problems)
+}
+
+fn repl_options(imports: &ReplImports, target_info: TargetInfo, palette: Palette) -> Options {
+    let src_dir = imports
+        .src_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("fake/test/path"));
+
+    Options {
+        target_info,
+        threading: Threading::Single,
+        src_dir: Some(src_dir),
+        palette: Some(palette),
+        ..Options::default()
+    }
+}
+
+fn collect_problems(
+    module_src: &str,
+    bytes_before_expr: usize,
+    diagnostics: Vec<Diagnostic>,
+) -> Problems {
+    let mut problems = Problems::default();
+    let line_info = LineInfo::new(module_src);
+
+    for diagnostic in diagnostics {
+        // Filter out all warnings and errors whose regions end before this,
+        // because they must be part of the defs (excluding the most renently added def,
+        // if that's the one being evaluated) and therefore not things we should show.
+        // This filters out things like shadowing warnings and unused def warnings.
+        // (Imported modules, which unlike the REPL's own module have a path,
+        // get all of their problems reported.)
+        let is_repl_module = diagnostic.path.as_os_str().is_empty();
+        let ends_before_expr = diagnostic.region.map_or(false, |region| {
+            (line_info.convert_line_column(region.end()).offset as usize) < bytes_before_expr
+        });
+
+        if is_repl_module && ends_before_expr {
+            continue;
+        }
+
+        if diagnostic.is_error() {
+            problems.errors.push(diagnostic.message);
+        } else {
+            problems.warnings.push(diagnostic.message);
+        }
+    }
+
+    problems
+}
+
+/// Render the report for an `expect` entered into the REPL whose condition turned out false.
+pub fn expect_failure_report(src: &str, palette: Palette) -> String {
+    let interns = Interns::default();
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let alloc = RocDocAllocator::new(&src_lines, ModuleId::ATTR, &interns);
+    let line_info = LineInfo::new(src);
+    let region = Region::new(Position::new(0), Position::new(src.trim_end().len() as u32));
+
+    let doc = alloc.stack([
+        alloc.text("This expectation failed:"),
+        alloc.region(line_info.convert_region(region)),
+    ]);
+
+    let report = Report {
+        title: "EXPECT FAILED".into(),
+        doc,
+        filename: PathBuf::from(""),
+        severity: Severity::RuntimeError,
+    };
+
+    let mut buf = String::new();
+
+    report.render_color_terminal(&mut buf, &alloc, &palette);
+
+    buf
 }
 
 fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
     defs: I,
+    imports: &ReplImports,
     expr: &str,
 ) -> (usize, &'a str) {
     const REPL_MODULE_HEADER: &str = "app \"app\" provides [replOutput] to \"./platform\"\n\n";
     const REPL_MODULE_MAIN_DEF: &str = "replOutput =\n";
     const INDENT: &str = "    ";
 
-    let mut buffer = bumpalo::collections::string::String::from_str_in(REPL_MODULE_HEADER, arena);
+    let mut buffer = bumpalo::collections::string::String::new_in(arena);
+
+    if imports.entries.is_empty() {
+        buffer.push_str(REPL_MODULE_HEADER);
+    } else {
+        buffer.push_str("app \"app\" imports [");
+        buffer.push_str(&imports.entries.join(", "));
+        buffer.push_str("] provides [replOutput] to \"./platform\"\n\n");
+    }
 
     for line in defs {
         // don't indent the defs
@@ -197,3 +802,70 @@ fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
 
     (bytes_before_expr, buffer.into_bump_str())
 }
+
+#[cfg(test)]
+mod test_shortened_answers {
+    use super::{format_shortened_answer, more_elements_note};
+    use bumpalo::Bump;
+    use roc_parse::test_helpers::parse_expr_with;
+
+    fn shortened<'a>(arena: &'a Bump, answer: &str, max_lines: usize) -> Option<&'a str> {
+        let expr = parse_expr_with(arena, arena.alloc_str(answer)).unwrap();
+
+        format_shortened_answer(arena, expr, Some(40), max_lines)
+    }
+
+    fn numbers(count: usize) -> String {
+        let numbers: Vec<String> = (1..=count).map(|n| n.to_string()).collect();
+
+        format!("[{}]", numbers.join(", "))
+    }
+
+    #[test]
+    fn short_answer_is_left_alone() {
+        let arena = Bump::new();
+
+        assert_eq!(shortened(&arena, &numbers(3), 5), None);
+    }
+
+    #[test]
+    fn long_list() {
+        let arena = Bump::new();
+        let answer = shortened(&arena, &numbers(100), 5).unwrap();
+
+        assert!(answer.lines().count() <= 5, "{answer}");
+        assert!(answer.starts_with("[1, 2, "), "{answer}");
+        assert!(answer.contains(" more elements"), "{answer}");
+        assert!(!answer.contains("100"), "{answer}");
+    }
+
+    #[test]
+    fn long_list_in_a_record() {
+        let arena = Bump::new();
+        let record = format!("{{ name: \"Sam\", scores: {} }}", numbers(1_000));
+        let answer = shortened(&arena, &record, 4).unwrap();
+
+        assert!(answer.lines().count() <= 4, "{answer}");
+        assert!(answer.contains("name: \"Sam\""), "{answer}");
+        assert!(answer.contains("scores: ["), "{answer}");
+        assert!(answer.contains(" more elements"), "{answer}");
+    }
+
+    #[test]
+    fn nothing_to_leave_out() {
+        let arena = Bump::new();
+        let fields: Vec<String> = (1..=20).map(|n| format!("field{n}: {n}")).collect();
+        let record = format!("{{ {} }}", fields.join(", "));
+
+        // Records only get shorter by shortening the lists in them
+        assert_eq!(shortened(&arena, &record, 4), None);
+    }
+
+    #[test]
+    fn more_elements() {
+        assert_eq!(more_elements_note(1), "… 1 more element");
+        assert_eq!(more_elements_note(999), "… 999 more elements");
+        assert_eq!(more_elements_note(4_982), "… 4,982 more elements");
+        assert_eq!(more_elements_note(1_234_567), "… 1,234,567 more elements");
+    }
+}
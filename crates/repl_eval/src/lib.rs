@@ -3,8 +3,10 @@ use roc_parse::ast::Expr;
 use roc_std::RocDec;
 use roc_target::TargetInfo;
 
+pub mod docs;
 pub mod eval;
 pub mod gen;
+pub mod search;
 
 pub trait ReplApp<'a> {
     type Memory: 'a + ReplAppMemory;
@@ -0,0 +1,310 @@
+//! Finds functions by their type for the REPL's `:search` command. For example,
+//! `List a, (a -> Bool) -> List a` finds `List.keepIf` and `List.dropIf`, no matter what
+//! their type variables are named or what order their arguments are in.
+use bumpalo::Bump;
+use roc_fmt::annotation::Formattable;
+use roc_module::symbol::ModuleId;
+use roc_parse::ast::{
+    AssignedField, ExtractSpaces, Header, Module, Pattern, Tag, TypeAnnotation, ValueDef,
+};
+use roc_parse::module::{module_defs, parse_header};
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+use roc_parse::type_annotation;
+
+/// The builtin modules `:search` looks through, in the order their results are listed
+const BUILTIN_MODULES: &[ModuleId] = &[
+    ModuleId::BOOL,
+    ModuleId::NUM,
+    ModuleId::STR,
+    ModuleId::LIST,
+    ModuleId::DICT,
+    ModuleId::SET,
+    ModuleId::RESULT,
+    ModuleId::BOX,
+    ModuleId::ENCODE,
+    ModuleId::DECODE,
+    ModuleId::HASH,
+    ModuleId::INSPECT,
+];
+
+/// A type to search for, with the parts of it that don't matter for matching removed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeQuery(Shape);
+
+impl TypeQuery {
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let arena = Bump::new();
+        let state = State::new(src.trim().as_bytes());
+
+        match type_annotation::located(false).parse(&arena, state, 0) {
+            Ok((_, ann, state)) if state.has_reached_end() => Ok(Self(Shape::from_ann(&ann.value))),
+            _ => Err(format!("I couldn't parse `{}` as a type.", src.trim())),
+        }
+    }
+
+    fn matches(&self, candidate: &Shape) -> bool {
+        self.0.matches(candidate, &mut Vec::new())
+    }
+}
+
+/// The builtins whose types match the query, as their qualified names (e.g. `List.keepIf`)
+/// paired with their annotated types.
+pub fn search_builtins(query: &TypeQuery) -> Vec<(String, String)> {
+    BUILTIN_MODULES
+        .iter()
+        .flat_map(|module_id| search_module(roc_builtins::roc::module_source(*module_id), query))
+        .collect()
+}
+
+/// The values an interface module exposes whose types match the query, as their qualified
+/// names paired with their annotated types. Only values with type annotations can match.
+pub fn search_module(module_src: &str, query: &TypeQuery) -> Vec<(String, String)> {
+    let arena = Bump::new();
+
+    let Ok((Module { header: Header::Interface(header), .. }, state)) =
+        parse_header(&arena, State::new(module_src.as_bytes()))
+    else {
+        return Vec::new();
+    };
+
+    let Ok((_, defs, _)) = module_defs().parse(&arena, state, 0) else {
+        return Vec::new();
+    };
+
+    let module_name = header.name.value.as_str();
+    let exposed: Vec<&str> = header
+        .exposes
+        .item
+        .items
+        .iter()
+        .map(|exposed| exposed.value.item().as_str())
+        .collect();
+
+    let mut results = Vec::new();
+
+    for def in defs.defs() {
+        let (loc_pattern, loc_ann) = match def {
+            Err(ValueDef::Annotation(loc_pattern, loc_ann))
+            | Err(ValueDef::AnnotatedBody {
+                ann_pattern: loc_pattern,
+                ann_type: loc_ann,
+                ..
+            }) => (loc_pattern, loc_ann),
+            _ => continue,
+        };
+
+        let Pattern::Identifier(ident) = loc_pattern.value.extract_spaces().item else {
+            continue;
+        };
+
+        if !exposed.contains(&ident) || !query.matches(&Shape::from_ann(&loc_ann.value)) {
+            continue;
+        }
+
+        let mut buf = roc_fmt::Buf::new_in(&arena);
+
+        loc_ann.value.extract_spaces().item.format(&mut buf, 0);
+
+        let ann = buf
+            .as_str()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        results.push((format!("{module_name}.{ident}"), ann));
+    }
+
+    results
+}
+
+/// The structure of a type annotation. Qualifiers, comments, `as` aliases, `where` clauses,
+/// and whether records and tag unions are open are all left out, since a search shouldn't
+/// have to get those right to find something.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Shape {
+    /// A named type variable
+    Var(String),
+    /// `*` or `_`, which match anything
+    Any,
+    Apply(String, Vec<Shape>),
+    Function(Vec<Shape>, Box<Shape>),
+    /// Sorted by field name
+    Record(Vec<(String, Shape)>),
+    Tuple(Vec<Shape>),
+    /// Sorted by tag name
+    TagUnion(Vec<(String, Vec<Shape>)>),
+}
+
+impl Shape {
+    fn from_ann(ann: &TypeAnnotation<'_>) -> Self {
+        match ann {
+            TypeAnnotation::Function(args, ret) => Shape::Function(
+                args.iter().map(|arg| Self::from_ann(&arg.value)).collect(),
+                Box::new(Self::from_ann(&ret.value)),
+            ),
+            TypeAnnotation::Apply(_module_name, name, args) => Shape::Apply(
+                name.to_string(),
+                args.iter().map(|arg| Self::from_ann(&arg.value)).collect(),
+            ),
+            TypeAnnotation::BoundVariable(name) => Shape::Var(name.to_string()),
+            TypeAnnotation::As(ann, _, _) | TypeAnnotation::Where(ann, _) => {
+                Self::from_ann(&ann.value)
+            }
+            TypeAnnotation::Record { fields, ext: _ } => {
+                let mut shapes: Vec<(String, Shape)> = fields
+                    .iter()
+                    .filter_map(|field| field_shape(&field.value))
+                    .collect();
+
+                shapes.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                Shape::Record(shapes)
+            }
+            TypeAnnotation::Tuple { elems, ext: _ } => Shape::Tuple(
+                elems
+                    .iter()
+                    .map(|elem| Self::from_ann(&elem.value))
+                    .collect(),
+            ),
+            TypeAnnotation::TagUnion { tags, ext: _ } => {
+                let mut shapes: Vec<(String, Vec<Shape>)> = tags
+                    .iter()
+                    .filter_map(|tag| tag_shape(&tag.value))
+                    .collect();
+
+                shapes.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                Shape::TagUnion(shapes)
+            }
+            TypeAnnotation::SpaceBefore(ann, _) | TypeAnnotation::SpaceAfter(ann, _) => {
+                Self::from_ann(ann)
+            }
+            TypeAnnotation::Inferred | TypeAnnotation::Wildcard | TypeAnnotation::Malformed(_) => {
+                Shape::Any
+            }
+        }
+    }
+
+    /// Whether `self` matches `candidate` once the type variables in `self` are renamed
+    /// according to `renaming`, which pairs them with the candidate's variables.
+    /// Function arguments can match in any order.
+    fn matches(&self, candidate: &Shape, renaming: &mut Vec<(String, String)>) -> bool {
+        match (self, candidate) {
+            (Shape::Any, _) | (_, Shape::Any) => true,
+            (Shape::Var(var), Shape::Var(candidate_var)) => {
+                match renaming
+                    .iter()
+                    .find(|(from, to)| from == var || to == candidate_var)
+                {
+                    Some((from, to)) => from == var && to == candidate_var,
+                    None => {
+                        renaming.push((var.clone(), candidate_var.clone()));
+
+                        true
+                    }
+                }
+            }
+            (Shape::Apply(name, args), Shape::Apply(candidate_name, candidate_args)) => {
+                name == candidate_name && all_match(args, candidate_args, renaming)
+            }
+            (Shape::Function(args, ret), Shape::Function(candidate_args, candidate_ret)) => {
+                args.len() == candidate_args.len()
+                    && ret.matches(candidate_ret, renaming)
+                    && match_in_any_order(
+                        args,
+                        candidate_args,
+                        &mut vec![false; candidate_args.len()],
+                        renaming,
+                    )
+            }
+            (Shape::Record(fields), Shape::Record(candidate_fields)) => {
+                fields.len() == candidate_fields.len()
+                    && fields.iter().zip(candidate_fields).all(
+                        |((name, shape), (candidate_name, candidate_shape))| {
+                            name == candidate_name && shape.matches(candidate_shape, renaming)
+                        },
+                    )
+            }
+            (Shape::Tuple(elems), Shape::Tuple(candidate_elems)) => {
+                all_match(elems, candidate_elems, renaming)
+            }
+            (Shape::TagUnion(tags), Shape::TagUnion(candidate_tags)) => {
+                tags.len() == candidate_tags.len()
+                    && tags.iter().zip(candidate_tags).all(
+                        |((name, args), (candidate_name, candidate_args))| {
+                            name == candidate_name && all_match(args, candidate_args, renaming)
+                        },
+                    )
+            }
+            _ => false,
+        }
+    }
+}
+
+fn all_match(shapes: &[Shape], candidates: &[Shape], renaming: &mut Vec<(String, String)>) -> bool {
+    shapes.len() == candidates.len()
+        && shapes
+            .iter()
+            .zip(candidates)
+            .all(|(shape, candidate)| shape.matches(candidate, renaming))
+}
+
+/// Pair each of `shapes` with a different one of the `candidates` it matches, backtracking
+/// when an earlier pairing leaves no match for a later shape.
+fn match_in_any_order(
+    shapes: &[Shape],
+    candidates: &[Shape],
+    used: &mut [bool],
+    renaming: &mut Vec<(String, String)>,
+) -> bool {
+    let Some((shape, rest)) = shapes.split_first() else {
+        return true;
+    };
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        if used[index] {
+            continue;
+        }
+
+        let mut attempt = renaming.clone();
+
+        if shape.matches(candidate, &mut attempt) {
+            used[index] = true;
+
+            if match_in_any_order(rest, candidates, used, &mut attempt) {
+                *renaming = attempt;
+
+                return true;
+            }
+
+            used[index] = false;
+        }
+    }
+
+    false
+}
+
+fn field_shape(field: &AssignedField<'_, TypeAnnotation<'_>>) -> Option<(String, Shape)> {
+    match field {
+        AssignedField::RequiredValue(name, _, ann) | AssignedField::OptionalValue(name, _, ann) => {
+            Some((name.value.to_string(), Shape::from_ann(&ann.value)))
+        }
+        AssignedField::LabelOnly(name) => Some((name.value.to_string(), Shape::Any)),
+        AssignedField::SpaceBefore(field, _) | AssignedField::SpaceAfter(field, _) => {
+            field_shape(field)
+        }
+        AssignedField::Malformed(_) => None,
+    }
+}
+
+fn tag_shape(tag: &Tag<'_>) -> Option<(String, Vec<Shape>)> {
+    match tag {
+        Tag::Apply { name, args } => Some((
+            name.value.to_string(),
+            args.iter().map(|arg| Shape::from_ann(&arg.value)).collect(),
+        )),
+        Tag::SpaceBefore(tag, _) | Tag::SpaceAfter(tag, _) => tag_shape(tag),
+        Tag::Malformed(_) => None,
+    }
+}
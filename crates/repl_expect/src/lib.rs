@@ -1,7 +1,7 @@
 //! Supports evaluating `expect` and printing contextual information when they fail.
 #[cfg(not(windows))]
 use {
-    roc_module::symbol::Interns,
+    roc_module::symbol::{Interns, ModuleId},
     roc_mono::{
         ir::ProcLayout,
         layout::{GlobalLayoutInterner, LayoutCache, Niche},
@@ -27,6 +27,7 @@ pub fn get_values<'a>(
     arena: &'a bumpalo::Bump,
     subs: &Subs,
     interns: &'a Interns,
+    home: ModuleId,
     layout_interner: &GlobalLayoutInterner<'a>,
     start: *const u8,
     start_offset: usize,
@@ -76,6 +77,7 @@ pub fn get_values<'a>(
                 variable,
                 subs,
                 interns,
+                home,
                 layout_interner.fork(),
                 target_info,
             )
@@ -128,6 +130,8 @@ mod test {
             palette: DEFAULT_PALETTE,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Test,
+            warning_levels: Default::default(),
+            explain_types: false,
         };
         let loaded = match roc_load::load_and_monomorphize_from_str(
             arena,
@@ -1209,7 +1213,7 @@ mod test {
                 t : (
                     Str,
                     Str,
-                )a
+                )
                 t = ("One", "Two")
                 "#
             ),
@@ -1253,4 +1257,38 @@ mod test {
             ),
         );
     }
+
+    #[test]
+    fn inline_lookups_in_source_order() {
+        run_expect_test(
+            indoc!(
+                r#"
+                interface Test exposes [] imports []
+
+                clamp : U8, U8, U8 -> U8
+                clamp = \low, high, n ->
+                    expect low <= high
+                    n |> Num.max low |> Num.min high
+
+                expect clamp 10 5 7 == 5
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│      expect low <= high
+                               ^^^^^^^^^^^
+
+                When it failed, these variables had these values:
+
+                low : U8
+                low = 10
+
+                high : U8
+                high = 5
+                "#
+            ),
+        );
+    }
 }
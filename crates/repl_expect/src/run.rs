@@ -11,7 +11,7 @@ use bumpalo::Bump;
 use inkwell::context::Context;
 use roc_build::link::llvm_module_to_dylib;
 use roc_can::expr::ExpectLookup;
-use roc_collections::{MutSet, VecMap};
+use roc_collections::{MutMap, MutSet, VecMap};
 use roc_error_macros::internal_error;
 use roc_gen_llvm::{
     llvm::{build::LlvmBackendMode, externs::add_default_roc_externs},
@@ -24,9 +24,11 @@ use roc_mono::{
     ir::OptLevel,
     layout::{GlobalLayoutInterner, STLayoutInterner},
 };
+use roc_parse::ast::Expr;
 use roc_region::all::Region;
 use roc_reporting::{error::expect::Renderer, report::RenderTarget};
 use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 use roc_types::subs::Subs;
 use target_lexicon::Triple;
 
@@ -40,8 +42,7 @@ pub struct ExpectMemory<'a> {
 impl<'a> ExpectMemory<'a> {
     const SHM_SIZE: usize = 1024;
 
-    #[cfg(test)]
-    pub(crate) fn from_slice(slice: &mut [u8]) -> Self {
+    pub fn from_slice(slice: &mut [u8]) -> Self {
         Self {
             ptr: slice.as_mut_ptr(),
             length: slice.len(),
@@ -108,7 +109,16 @@ impl<'a> ExpectMemory<'a> {
         }
     }
 
-    fn set_shared_buffer(&mut self, lib: &libloading::Library) {
+    /// Whether a REPL evaluation had more `dbg` output than fit in this memory. The generated
+    /// code leaves out every frame from the first one that doesn't fit, and marks that by moving
+    /// the next free position past the end.
+    pub fn dropped_dbgs(&self) -> bool {
+        let sequence = ExpectSequence { ptr: self.ptr };
+
+        sequence.next_offset() > self.length
+    }
+
+    pub fn set_shared_buffer(&mut self, lib: &libloading::Library) {
         let set_shared_buffer = run_roc_dylib!(lib, "set_shared_buffer", (*mut u8, usize), ());
         let mut result = RocCallResult::default();
         unsafe { set_shared_buffer((self.ptr, self.length), &mut result) };
@@ -473,6 +483,53 @@ pub fn render_dbgs_in_memory<'a>(
     )
 }
 
+/// The values of the `dbg`s that a REPL evaluation buffered in memory, in the order they ran.
+///
+/// Unlike `roc test`, the REPL doesn't get notified of each `dbg` as it happens, so every
+/// frame starts with the offset of the next one instead.
+pub fn repl_dbgs_in_memory<'a>(
+    arena: &'a Bump,
+    expectations: &VecMap<ModuleId, Expectations>,
+    interns: &'a Interns,
+    layout_interner: &GlobalLayoutInterner<'a>,
+    memory: &ExpectMemory,
+) -> Vec<Expr<'a>> {
+    // we always run programs as the host
+    let target_info = (&target_lexicon::Triple::host()).into();
+
+    let sequence = ExpectSequence { ptr: memory.ptr };
+    let mut values = Vec::with_capacity(sequence.count_failures());
+    let mut offset = ExpectSequence::START_OFFSET;
+
+    for _ in 0..sequence.count_failures() {
+        let next_frame_offset =
+            unsafe { std::ptr::read_unaligned(memory.ptr.add(offset) as *const usize) };
+        let frame = ExpectFrame::at_offset(memory.ptr, offset + std::mem::size_of::<usize>());
+
+        let data = match expectations.get(&frame.module_id) {
+            None => internal_error!("module {:?} has no dbgs", frame.module_id),
+            Some(data) => data,
+        };
+
+        let (_, expressions, _variables) = crate::get_values(
+            target_info,
+            arena,
+            &data.subs,
+            interns,
+            frame.module_id,
+            layout_interner,
+            memory.ptr,
+            frame.start_offset,
+            1,
+        );
+
+        values.extend(expressions);
+        offset = next_frame_offset;
+    }
+
+    values
+}
+
 fn split_expect_lookups(subs: &Subs, lookups: &[ExpectLookup]) -> Vec<Symbol> {
     lookups
         .iter()
@@ -525,18 +582,27 @@ fn render_dbg_failure<'a>(
 
     let subs = arena.alloc(&mut data.subs);
 
-    let (offset, expressions, _variables) = crate::get_values(
+    let (offset, expressions, variables) = crate::get_values(
         target_info,
         arena,
         subs,
         interns,
+        module_id,
         layout_interner,
         start,
         frame.start_offset,
         1,
     );
 
-    renderer.render_dbg(writer, &expressions, expect_region, failure_region)?;
+    let type_str = name_and_print_var(variables[0], subs, module_id, interns, DebugPrint::NOTHING);
+
+    renderer.render_dbg(
+        writer,
+        &expressions,
+        &type_str,
+        expect_region,
+        failure_region,
+    )?;
 
     Ok(offset)
 }
@@ -576,6 +642,7 @@ fn render_expect_failure<'a>(
         arena,
         &data.subs,
         interns,
+        module_id,
         layout_interner,
         start,
         frame.start_offset,
@@ -623,6 +690,10 @@ impl ExpectSequence {
         unsafe { *(self.ptr as *const usize).add(Self::COUNT_INDEX) }
     }
 
+    fn next_offset(&self) -> usize {
+        unsafe { *(self.ptr as *const usize).add(Self::OFFSET_INDEX) }
+    }
+
     fn wait_for_child(&self, sigchld: Arc<AtomicBool>) -> ChildProcessMsg {
         use std::sync::atomic::Ordering;
         let ptr = self.ptr as *const u32;
@@ -747,6 +818,7 @@ pub fn expect_mono_module_to_dylib<'a>(
         mode,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        def_locations: MutMap::default(),
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
@@ -121,6 +121,30 @@ pub fn repl_eval(input: &str) -> Out {
     }
 }
 
+/// Evaluate the input with `roc repl --eval --no-color`, which prints only the answer.
+pub fn repl_eval_once(input: &str, extra_args: &[&str]) -> Out {
+    let mut args = vec!["--no-color"];
+    args.extend_from_slice(extra_args);
+
+    repl_eval_once_with_env(input, &args, &[])
+}
+
+/// Like `repl_eval_once`, but the output is only uncolored if the args or env say so.
+pub fn repl_eval_once_with_env(input: &str, args: &[&str], envs: &[(&str, &str)]) -> Out {
+    let output = Command::new(path_to_roc_binary())
+        .args(["repl", "--eval", input])
+        .args(args)
+        .envs(envs.iter().copied())
+        .output()
+        .expect("failed to execute compiled `roc` binary in CLI test");
+
+    Out {
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+        status: output.status,
+    }
+}
+
 pub fn expect_success(input: &str, expected: &str) {
     let out = repl_eval(input.trim());
 
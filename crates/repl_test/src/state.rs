@@ -1,8 +1,8 @@
 use bumpalo::Bump;
 use indoc::indoc;
 use roc_repl_cli::{evaluate, ReplHelper};
-use roc_repl_ui::is_incomplete;
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
+use roc_repl_ui::{continuation_indent, is_incomplete, split_entries};
 use roc_reporting::report::DEFAULT_PALETTE;
 use roc_target::TargetInfo;
 use rustyline::Editor;
@@ -26,6 +26,42 @@ fn persisted_defs() {
     complete("y = 6", &mut state, "6 : Num *");
 }
 
+#[test]
+fn redefined_past_def() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+    complete("x = 6", &mut state, "6 : Num *");
+    complete("x + 1", &mut state, "7 : Num *");
+}
+
+#[test]
+fn env_lists_past_defs() {
+    let mut state = ReplState::new();
+
+    complete(r#"y = "hi""#, &mut state, r#""hi" : Str"#);
+    complete("x = 5", &mut state, "5 : Num *");
+
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    match state.step(&arena, ":env", target_info, DEFAULT_PALETTE) {
+        ReplAction::Env { types, problems } => {
+            assert!(problems.errors.is_empty());
+            assert_eq!(
+                types,
+                vec![
+                    ("x".to_string(), "Num *".to_string()),
+                    ("y".to_string(), "Str".to_string()),
+                ]
+            );
+        }
+        action => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
 #[test]
 fn annotated_body() {
     let mut input = "t : [A, B, C]".to_string();
@@ -124,6 +160,61 @@ fn partial_record_definition() {
     }
 }
 
+#[test]
+fn multiline_def() {
+    let mut input = "x =".to_string();
+
+    assert!(is_incomplete(&input));
+    assert_eq!(continuation_indent(&input), "    ");
+
+    input.push_str("\n    1 + 1");
+
+    complete(&input, &mut ReplState::new(), "2 : Num *");
+}
+
+#[test]
+fn multiline_list() {
+    let mut input = "[".to_string();
+
+    assert!(is_incomplete(&input));
+
+    input.push_str("\n    1,");
+
+    assert!(is_incomplete(&input));
+    assert_eq!(continuation_indent(&input), "    ");
+
+    input.push_str("\n    2,\n]");
+
+    complete(&input, &mut ReplState::new(), "[1, 2] : List (Num *)");
+}
+
+#[test]
+fn blank_line_ends_multiline_entry() {
+    // Entering a blank line (even one we auto-indented) gives up on the entry,
+    // so the syntax error gets reported instead of waiting forever.
+    assert!(!is_incomplete("x =\n    "));
+}
+
+#[test]
+fn split_pasted_entries() {
+    let src = indoc!(
+        r#"
+        x = 5
+        t : [A, B]
+        t = A
+
+        f =
+            \n -> n
+        f x
+        "#
+    );
+
+    assert_eq!(
+        split_entries(src),
+        vec!["x = 5", "t : [A, B]\nt = A", "f =\n    \\n -> n", "f x"]
+    );
+}
+
 #[test]
 fn tips() {
     assert!(!is_incomplete(""));
@@ -134,6 +225,141 @@ fn tips() {
     assert!(matches!(action, ReplAction::Help));
 }
 
+#[test]
+fn expand_and_save_commands() {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+    let mut state = ReplState::new();
+
+    let action = state.step(&arena, ":expand", target_info, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Expand));
+
+    match state.step(&arena, ":save out.txt", target_info, DEFAULT_PALETTE) {
+        ReplAction::Save { path } => assert_eq!(path, "out.txt"),
+        action => panic!("Unexpected action: {:?}", action),
+    }
+}
+
+#[test]
+fn show_instantiated_setting() {
+    let mut state = ReplState::new();
+
+    setting(":set show-instantiated", &mut state);
+    complete("1 + 2", &mut state, "3 : Num *  (defaults to I64)");
+    complete(
+        "[1.5]",
+        &mut state,
+        "[1.5] : List (Frac *)  (defaults to List Dec)",
+    );
+    complete("5u8", &mut state, "5 : U8");
+
+    setting(":unset show-instantiated", &mut state);
+    complete("1 + 2", &mut state, "3 : Num *");
+}
+
+#[test]
+fn unknown_setting() {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+    let mut state = ReplState::new();
+
+    match state.step(&arena, ":set colours", target_info, DEFAULT_PALETTE) {
+        ReplAction::Set { problems } => assert_eq!(
+            problems.errors,
+            vec!["There's no setting called colours. The only setting is show-instantiated."]
+        ),
+        action => panic!("Unexpected action: {:?}", action),
+    }
+}
+
+#[test]
+fn search_by_type() {
+    let mut state = ReplState::new();
+    let found = search("List x, (x -> Bool) -> List x", &mut state);
+
+    assert!(
+        found.contains(&(
+            "List.keepIf".to_string(),
+            "List a, (a -> Bool) -> List a".to_string()
+        )),
+        "{found:?}"
+    );
+    assert!(found.iter().any(|(name, _)| name == "List.dropIf"));
+    assert!(!found.iter().any(|(name, _)| name == "List.map"));
+
+    // Arguments can be given in any order
+    let found = search("Nat, Str -> Str", &mut state);
+
+    assert!(found.iter().any(|(name, _)| name == "Str.repeat"));
+
+    // Different type variables stay different
+    let found = search("List a, (a -> a) -> List a", &mut state);
+
+    assert!(!found.iter().any(|(name, _)| name == "List.map"));
+}
+
+#[test]
+fn search_for_unparseable_type() {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+    let mut state = ReplState::new();
+
+    match state.step(&arena, ":search List a ->", target_info, DEFAULT_PALETTE) {
+        ReplAction::Search { results, problems } => {
+            assert!(results.is_empty());
+            assert_eq!(
+                problems.errors,
+                vec!["I couldn't parse `List a ->` as a type."]
+            );
+        }
+        action => panic!("Unexpected action: {:?}", action),
+    }
+}
+
+#[test]
+fn time_command() {
+    let arena = Bump::new();
+    let target = Triple::host();
+    let target_info = TargetInfo::from(&target);
+    let mut state = ReplState::new();
+
+    match state.step(&arena, ":time 1 + 1", target_info, DEFAULT_PALETTE) {
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            instantiated_type,
+            compile_time: Some(compile_time),
+        } => {
+            let output = evaluate(
+                opt_mono,
+                problems,
+                instantiated_type,
+                Some(compile_time),
+                &target,
+            );
+            let output = strip_ansi_escapes::strip(output.trim()).unwrap();
+            let lines: Vec<_> = std::str::from_utf8(&output).unwrap().lines().collect();
+
+            assert_eq!(lines[0], "2 : Num *");
+            assert!(lines[2].starts_with("parse/typecheck "));
+            assert!(lines[3].starts_with("code gen "));
+            assert!(lines[4].starts_with("execution "));
+        }
+        action => panic!("Unexpected action: {:?}", action),
+    }
+
+    // Defs get timed too, and still get defined
+    let action = state.step(&arena, ":time x = 5", target_info, DEFAULT_PALETTE);
+    assert!(matches!(
+        action,
+        ReplAction::Eval {
+            compile_time: Some(_),
+            ..
+        }
+    ));
+    complete("x", &mut state, "5 : Num *");
+}
+
 #[test]
 fn standalone_annotation() {
     let mut state = ReplState::new();
@@ -148,6 +374,170 @@ fn standalone_annotation() {
     assert!(matches!(action, ReplAction::Nothing));
 }
 
+#[test]
+fn complete_builtin_module_exposed_names() {
+    let state = ReplState::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    let (start, candidates) = state.completions("Str.conc", 8, target_info);
+
+    assert_eq!(start, 0);
+    assert_eq!(candidates, vec!["Str.concat".to_string()]);
+}
+
+#[test]
+fn complete_past_defs_and_record_fields() {
+    let mut state = ReplState::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    complete(
+        r#"user = { name: "Sam", age: 30 }"#,
+        &mut state,
+        r#"{ age: 30, name: "Sam" } : { age : Num *, name : Str }"#,
+    );
+
+    let (start, candidates) = state.completions("1 + us", 6, target_info);
+
+    assert_eq!(start, 4);
+    assert_eq!(candidates, vec!["user".to_string()]);
+
+    let (start, candidates) = state.completions("user.na", 7, target_info);
+
+    assert_eq!(start, 0);
+    assert_eq!(candidates, vec!["user.name".to_string()]);
+}
+
+#[test]
+fn load_and_reload_module() {
+    let dir = std::env::temp_dir().join(format!("roc_repl_load_{}", std::process::id()));
+    let module_path = dir.join("Greeting.roc");
+    let mut state = ReplState::new();
+
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        &module_path,
+        "interface Greeting exposes [greet] imports []\n\ngreet = \\name -> \"Hi, \\(name)!\"\n",
+    )
+    .unwrap();
+
+    load(&format!(":load {}", module_path.display()), &mut state);
+    complete(r#"greet "Sam""#, &mut state, r#""Hi, Sam!" : Str"#);
+
+    std::fs::write(
+        &module_path,
+        "interface Greeting exposes [greet] imports []\n\ngreet = \\name -> \"Bye, \\(name)!\"\n",
+    )
+    .unwrap();
+
+    load(":reload", &mut state);
+    complete(r#"greet "Sam""#, &mut state, r#""Bye, Sam!" : Str"#);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn record_session() {
+    let dir = std::env::temp_dir().join(format!("roc_repl_record_{}", std::process::id()));
+    let module_path = dir.join("Session.roc");
+    let mut state = ReplState::new();
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    complete("x = 5", &mut state, "5 : Num *");
+    record(&format!(":record {}", module_path.display()), &mut state);
+    complete("y = x + 1", &mut state, "6 : Num *");
+    complete("x + y", &mut state, "11 : Num *");
+    record(":stop", &mut state);
+    complete("z = 1", &mut state, "1 : Num *");
+
+    assert_eq!(
+        std::fs::read_to_string(&module_path).unwrap(),
+        indoc!(
+            r#"
+            interface Session
+                exposes [x, y, expr1]
+                imports []
+
+            x = 5
+
+            y = x + 1
+
+            expr1 = x + y
+            "#
+        )
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// step the given `:record` or `:stop` command, and check that it succeeded.
+fn record(input: &str, state: &mut ReplState) {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    match state.step(&arena, input, target_info, DEFAULT_PALETTE) {
+        ReplAction::Record { problems } => {
+            assert!(problems.is_empty(), "Unexpected problems: {:?}", problems);
+        }
+        action => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
+/// step the given `:load` or `:reload` command, and check that it succeeded.
+fn load(input: &str, state: &mut ReplState) {
+    assert!(!is_incomplete(input));
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    match state.step(&arena, input, target_info, DEFAULT_PALETTE) {
+        ReplAction::Load { problems } => {
+            assert!(problems.is_empty(), "Unexpected problems: {:?}", problems);
+        }
+        action => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
+/// Step `:search` for the given type, which should succeed, and return the results.
+fn search(query: &str, state: &mut ReplState) -> Vec<(String, String)> {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    match state.step(
+        &arena,
+        &format!(":search {query}"),
+        target_info,
+        DEFAULT_PALETTE,
+    ) {
+        ReplAction::Search { results, problems } => {
+            assert!(problems.is_empty(), "Unexpected problems: {:?}", problems);
+
+            results
+        }
+        action => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
+/// Step a `:set` or `:unset` command, which should succeed.
+fn setting(input: &str, state: &mut ReplState) {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    match state.step(&arena, input, target_info, DEFAULT_PALETTE) {
+        ReplAction::Set { problems } => {
+            assert!(problems.is_empty(), "Unexpected problems: {:?}", problems);
+        }
+        action => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
 /// validate and step the given input, then check the Result vs the output
 /// with ANSI escape codes stripped.
 fn complete(input: &str, state: &mut ReplState, expected_start: &str) {
@@ -161,8 +551,13 @@ fn complete(input: &str, state: &mut ReplState, expected_start: &str) {
     editor.set_helper(Some(repl_helper));
 
     match action {
-        ReplAction::Eval { opt_mono, problems } => {
-            let string = evaluate(opt_mono, problems, &target);
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            instantiated_type,
+            compile_time,
+        } => {
+            let string = evaluate(opt_mono, problems, instantiated_type, compile_time, &target);
             let escaped =
                 std::string::String::from_utf8(strip_ansi_escapes::strip(string.trim()).unwrap())
                     .unwrap();
@@ -198,8 +593,13 @@ fn error(input: &str, state: &mut ReplState, expected_step_result: String) {
     editor.set_helper(Some(repl_helper));
 
     match action {
-        ReplAction::Eval { opt_mono, problems } => {
-            let string = evaluate(opt_mono, problems, &target);
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            instantiated_type,
+            compile_time,
+        } => {
+            let string = evaluate(opt_mono, problems, instantiated_type, compile_time, &target);
             let escaped =
                 std::string::String::from_utf8(strip_ansi_escapes::strip(string.trim()).unwrap())
                     .unwrap();
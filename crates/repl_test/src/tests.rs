@@ -4,11 +4,13 @@ use indoc::indoc;
 use roc_test_utils::assert_multiline_str_eq;
 
 #[cfg(not(feature = "wasm"))]
-use crate::cli::{expect_failure, expect_success, repl_eval};
+use crate::cli::{
+    expect_failure, expect_success, repl_eval, repl_eval_once, repl_eval_once_with_env,
+};
 
 #[cfg(feature = "wasm")]
 #[allow(unused_imports)]
-use crate::wasm::{expect_failure, expect_success};
+use crate::wasm::{expect_failure, expect_session, expect_success};
 
 #[test]
 fn literal_0() {
@@ -592,6 +594,28 @@ fn multiline_string_non_wasm() {
     assert!(out.status.success());
 }
 
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn multiline_string_escapes() {
+    // Only the newlines appear as they are in a multiline string; everything else gets escaped.
+    let out = repl_eval(r#""tab:\tslash:\\\nquotes:\"\"\"""#);
+    let expected = indoc!(
+        r#""""
+                tab:\tslash:\\
+                quotes:""\"
+                """ : Str"#
+    );
+
+    assert_multiline_str_eq!("", out.stderr.as_str());
+    assert_multiline_str_eq!(expected, out.stdout.trim());
+    assert!(out.status.success());
+}
+
+#[test]
+fn string_with_backslash() {
+    expect_success(r#""C:\\Users""#, r#""C:\\Users" : Str"#);
+}
+
 #[cfg(feature = "wasm")]
 #[test]
 fn multiline_string_wasm() {
@@ -725,6 +749,55 @@ fn multiline_input() {
     )
 }
 
+#[cfg(feature = "wasm")]
+#[test]
+fn session_remembers_defs() {
+    expect_session(
+        &["x = 5", "y = x + 1", "y * 2"],
+        &["5 : Num *", "6 : Num *", "12 : Num *"],
+    );
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn session_with_multiline_defs() {
+    expect_session(
+        &[
+            indoc!(
+                r#"
+                double =
+                    \n -> n * 2
+                "#
+            )
+            .trim(),
+            indoc!(
+                r#"
+                nums = [
+                    double 1,
+                    double 2,
+                ]
+                "#
+            )
+            .trim(),
+            "List.sum nums",
+        ],
+        &[
+            "<function> : Num a -> Num a",
+            "[2, 4] : List (Num *)",
+            "6 : Num *",
+        ],
+    );
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn session_redefines_def() {
+    expect_session(
+        &["x = 1", "x = 2", "x + 1"],
+        &["1 : Num *", "2 : Num *", "3 : Num *"],
+    );
+}
+
 #[test]
 fn recursive_tag_union_flat_variant() {
     expect_success(
@@ -1111,6 +1184,21 @@ fn dec_in_repl() {
     )
 }
 
+#[test]
+fn dec_addition() {
+    expect_success("1.1dec + 2.2dec", "3.3 : Dec");
+}
+
+#[test]
+fn negative_dec_between_zero_and_one() {
+    expect_success("-0.5dec", "-0.5 : Dec");
+}
+
+#[test]
+fn list_of_decs() {
+    expect_success("[1.50dec, -0.25dec, 3dec]", "[1.5, -0.25, 3] : List Dec");
+}
+
 #[test]
 fn print_i8_issue_2710() {
     expect_success(
@@ -1209,6 +1297,27 @@ fn render_nullable_unwrapped_passing_through_alias() {
     )
 }
 
+#[test]
+fn set_single() {
+    expect_success("Set.single 1u8", "Set.fromList [1] : Set U8");
+}
+
+#[test]
+fn dict_from_list() {
+    expect_success(
+        r#"Dict.fromList [("a", 1u8), ("b", 2u8)] |> Dict.insert "c" 3"#,
+        r#"Dict.fromList [("a", 1), ("b", 2), ("c", 3)] : Dict Str U8"#,
+    );
+}
+
+#[test]
+fn dict_in_record() {
+    expect_success(
+        r#"{ names: Set.fromList ["Ada"], empty: Dict.empty {} |> Dict.insert 1u8 "one" |> Dict.remove 1 }"#,
+        r#"{ empty: Dict.fromList [], names: Set.fromList ["Ada"] } : { empty : Dict U8 Str, names : Set Str }"#,
+    );
+}
+
 #[test]
 fn opaque_wrap_function() {
     expect_success(
@@ -1307,7 +1416,7 @@ fn tuple() {
             ("a", 2u32)
             "#
         ),
-        r#"("a", 2) : ( Str, U32 )*"#,
+        r#"("a", 2) : ( Str, U32 )"#,
     );
 }
 
@@ -1319,7 +1428,7 @@ fn nested_tuple() {
             ("a", (2u32, 3u32))
             "#
         ),
-        r#"("a", (2, 3)) : ( Str, ( U32, U32 )a )a"#,
+        r#"("a", (2, 3)) : ( Str, ( U32, U32 ) )"#,
     );
 }
 
@@ -1353,6 +1462,217 @@ fn interpolation_with_nested_strings() {
     );
 }
 
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn eval_flag_prints_only_the_answer() {
+    let out = repl_eval_once("1 + 2", &[]);
+
+    assert_multiline_str_eq!("3 : Num *\n", out.stdout.as_str());
+    assert_multiline_str_eq!("", out.stderr.as_str());
+    assert!(out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn eval_flag_fails_on_errors() {
+    let out = repl_eval_once("1 + \"\"", &[]);
+
+    assert_multiline_str_eq!("", out.stdout.as_str());
+    assert!(out.stderr.contains("TYPE MISMATCH"));
+    assert!(!out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn width_flag_wraps_wide_results() {
+    let out = repl_eval_once(
+        r#"[{ name: "Alice", age: 30 }, { name: "Bob", age: 40 }]"#,
+        &["--width", "32"],
+    );
+
+    assert_multiline_str_eq!(
+        indoc!(
+            r#"
+                [
+                    { age: 30, name: "Alice" },
+                    { age: 40, name: "Bob" },
+                ] : List { age : Num *, name : Str }
+            "#
+        ),
+        out.stdout.as_str()
+    );
+    assert!(out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn no_color_env_var_disables_colors() {
+    let colored = repl_eval_once_with_env("1 + 2", &[], &[]);
+
+    assert!(colored.stdout.contains('\u{1b}'));
+
+    let plain = repl_eval_once_with_env("1 + 2", &[], &[("ROC_NO_COLOR", "1")]);
+
+    assert_multiline_str_eq!("3 : Num *\n", plain.stdout.as_str());
+    assert!(plain.status.success());
+}
+
+#[cfg(all(unix, not(feature = "wasm")))]
+#[test]
+fn timeout_aborts_evaluation() {
+    let out = repl_eval_once(
+        indoc!(
+            r#"
+                countdown = \n -> if n == 0 then 0 else countdown (n - 1)
+
+                countdown 1_000_000_000_000
+            "#
+        ),
+        &["--timeout", "100ms"],
+    );
+
+    assert!(out
+        .stderr
+        .contains("Evaluation aborted: it was still running after the 100ms timeout."));
+    assert!(!out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn json_output() {
+    let out = repl_eval_once("1 + 2", &["--output", "json"]);
+
+    assert_multiline_str_eq!(
+        "{\"problems\":[],\"type\":\"Num *\",\"value\":\"3\"}\n",
+        out.stdout.as_str()
+    );
+    assert!(out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn doc_builtin() {
+    let out = repl_eval_once(":doc Str.concat", &[]);
+
+    assert_multiline_str_eq!(
+        indoc!(
+            r#"
+                Str.concat : Str, Str -> Str
+
+                Concatenates two strings together.
+                ```
+                expect Str.concat "ab" "cd" == "abcd"
+                expect Str.concat "hello" "" == "hello"
+                expect Str.concat "" "" == ""
+                ```
+            "#
+        ),
+        out.stdout.as_str()
+    );
+    assert!(out.status.success());
+}
+
+#[test]
+fn doc_builtin_ends_with_its_doc_comment() {
+    expect_success(
+        ":doc Num.maxI8",
+        "which means if you call [Num.abs] on [Num.minI8], it will overflow and crash!",
+    );
+}
+
+#[test]
+fn expect_at_prompt_passes() {
+    expect_success("expect 1 + 1 == 2", "passed");
+}
+
+#[test]
+fn expect_at_prompt_fails() {
+    expect_failure(
+        "expect 1 + 1 == 3",
+        indoc!(
+            r#"
+                ── EXPECT FAILED ───────────────────────────────────────────────────────────────
+
+                This expectation failed:
+
+                1│  expect 1 + 1 == 3
+                    ^^^^^^^^^^^^^^^^^
+            "#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dbg_prints_before_the_answer() {
+    let out = repl_eval_once(
+        indoc!(
+            r#"
+                x = 42
+
+                dbg x
+
+                x + 1
+            "#
+        ),
+        &[],
+    );
+
+    assert_multiline_str_eq!("[dbg] 42\n43 : Num *\n", out.stdout.as_str());
+    assert_multiline_str_eq!("", out.stderr.as_str());
+    assert!(out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dbg_at_prompt_evaluates_to_its_value() {
+    let out = repl_eval_once(r#"dbg "hello""#, &[]);
+
+    assert_multiline_str_eq!("[dbg] \"hello\"\n\"hello\" : Str\n", out.stdout.as_str());
+    assert!(out.status.success());
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dbg_output_that_does_not_fit_is_truncated() {
+    let out = repl_eval_once(
+        indoc!(
+            r#"
+                x = List.repeat 7u8 2000000
+
+                dbg List.len x
+
+                dbg x
+
+                List.len x
+            "#
+        ),
+        &[],
+    );
+
+    assert_multiline_str_eq!(
+        indoc!(
+            r#"
+                [dbg] 2000000
+                [dbg] (dbg output truncated: the rest of the dbgs didn't fit in the REPL's 1 MiB buffer)
+                2000000 : Nat
+            "#
+        ),
+        out.stdout.as_str()
+    );
+    assert!(out.status.success());
+}
+
+#[test]
+fn type_of_builtin() {
+    expect_success(":type Str.concat", "Str.concat : Str, Str -> Str");
+}
+
+#[test]
+fn type_of_expr_short_command() {
+    expect_success(":t [1, 2, 3]", "[1, 2, 3] : List (Num *)");
+}
+
 #[test]
 fn interpolation_with_num_to_str() {
     expect_success(
@@ -1406,6 +1726,15 @@ fn interpolation_with_nested_interpolation() {
                 Enter an expression to evaluate, or a definition (like x = 1) to use later.
 
                   - ctrl-v + ctrl-j makes a newline
+                  - :t <expr> shows the type of an expression
+                  - :doc <name> shows the documentation for a name
+                  - :search <type> finds the builtins and loaded values with a type, like Str, Str -> Str
+                  - :env lists the names you've defined, with their types
+                  - :load <file.roc> imports a module's exposed values (:reload recompiles it)
+                  - :expand shows all of a result that was too long to show in full (:save <file> writes it to a file)
+                  - :set show-instantiated shows what number types default to (:unset turns it off)
+                  - :time <expr> shows how long compiling and running an expression takes
+                  - :record <file.roc> writes what you enter from now on to a module (:stop stops)
                   - :q quits
                   - :help shows this text again
             "#
@@ -1414,3 +1743,29 @@ fn interpolation_with_nested_interpolation() {
         // In the actual repl this doesn't happen, only in the test.
     );
 }
+
+#[cfg(feature = "wasm")]
+#[test]
+fn infinite_loop_times_out() {
+    let entries = [
+        "countUp = \\n -> if n == 0 then 0 else countUp (n + 1)",
+        "countUp 1",
+    ];
+
+    expect_success(
+        &entries.join("\n"),
+        "<span class='color-red'>Evaluation timed out after 5 seconds</span> :",
+    );
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn session_continues_after_timeout() {
+    let entries = [
+        "countUp = \\n -> if n == 0 then 0 else countUp (n + 1)",
+        "countUp 1",
+        "1 + 1",
+    ];
+
+    expect_success(&entries.join("\n"), "2 : Num *");
+}
@@ -1,14 +1,26 @@
 use bumpalo::Bump;
+use roc_repl_ui::split_entries;
 use roc_wasm_interp::{
     wasi, DefaultImportDispatcher, ImportDispatcher, Instance, Value, WasiDispatcher,
 };
+use std::time::{Duration, Instant};
 
 const COMPILER_BYTES: &[u8] =
     include_bytes!("../../../target/wasm32-wasi/release-with-lto/roc_repl_wasm.wasm");
 
+const ERROR_MESSAGE_START: char = '─';
+
+/// How long the app can run before we give up on it, like EVAL_TIMEOUT_MS in the web REPL
+const EVAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+// What test_create_app returns. These match the ones in roc_repl_wasm's test externs.
+const APP_INVALID: i32 = 0;
+const APP_OK: i32 = 1;
+const APP_TIMED_OUT: i32 = 2;
+
 struct CompilerDispatcher<'a> {
     arena: &'a Bump,
-    src: &'a str,
+    src: String,
     answer: String,
     wasi: WasiDispatcher<'a>,
     app: Option<Instance<'a, DefaultImportDispatcher<'a>>>,
@@ -36,7 +48,8 @@ impl<'a> ImportDispatcher for CompilerDispatcher<'a> {
         } else if module_name == "env" {
             match function_name {
                 "test_create_app" => {
-                    // Get some bytes from the compiler Wasm instance and create the app Wasm instance
+                    // Get some bytes from the compiler Wasm instance, create the app Wasm instance,
+                    // and run it, giving up if it takes too long - like the web REPL's worker does.
                     // fn test_create_app(app_bytes_ptr: *const u8, app_bytes_len: usize) -> u32;
                     assert_eq!(arguments.len(), 2);
                     let app_bytes_ptr = arguments[0].expect_i32().unwrap() as usize;
@@ -44,33 +57,40 @@ impl<'a> ImportDispatcher for CompilerDispatcher<'a> {
                     let app_bytes = &compiler_memory[app_bytes_ptr..][..app_bytes_len];
 
                     let is_debug_mode = false;
-                    let instance = Instance::from_bytes(
+                    let mut instance = match Instance::from_bytes(
                         self.arena,
                         app_bytes,
                         DefaultImportDispatcher::default(),
                         is_debug_mode,
-                    )
-                    .unwrap();
-
-                    self.app = Some(instance);
-                    let ok = Value::I32(true as i32);
-                    Some(ok)
-                }
-                "test_run_app" => {
-                    // fn test_run_app() -> usize;
-                    assert_eq!(arguments.len(), 0);
-                    match &mut self.app {
-                        Some(instance) => {
-                            let result_addr = instance
-                                .call_export("wrapper", [])
-                                .unwrap()
+                    ) {
+                        Ok(instance) => instance,
+                        Err(_) => return Some(Value::I32(APP_INVALID)),
+                    };
+
+                    let deadline = Instant::now() + EVAL_TIMEOUT;
+                    let status = match instance.call_export_with_deadline("wrapper", [], deadline) {
+                        Ok(result) => {
+                            let result_addr = result
                                 .expect("No return address from wrapper")
                                 .expect_i32()
                                 .unwrap();
                             self.result_addr = Some(result_addr);
-                            let memory_size = instance.memory.len();
-                            Some(Value::I32(memory_size as i32))
+                            self.app = Some(instance);
+
+                            APP_OK
                         }
+                        Err(_) if Instant::now() >= deadline => APP_TIMED_OUT,
+                        Err(message) => panic!("The app crashed:\n{message}"),
+                    };
+
+                    Some(Value::I32(status))
+                }
+                "test_run_app" => {
+                    // The app already ran in test_create_app, so just report its memory size.
+                    // fn test_run_app() -> usize;
+                    assert_eq!(arguments.len(), 0);
+                    match &self.app {
+                        Some(instance) => Some(Value::I32(instance.memory.len() as i32)),
                         None => panic!("Trying to run the app but it hasn't been created"),
                     }
                 }
@@ -122,13 +142,15 @@ impl<'a> ImportDispatcher for CompilerDispatcher<'a> {
     }
 }
 
-fn run(src: &'static str) -> String {
+/// Enter each entry of the input into the same REPL, one after the other, like a session in
+/// the CLI. Returns what the REPL answered to each one.
+fn run(src: &str) -> Vec<String> {
     let arena = Bump::new();
 
     let mut instance = {
         let dispatcher = CompilerDispatcher {
             arena: &arena,
-            src,
+            src: String::new(),
             answer: String::new(),
             wasi: WasiDispatcher::default(),
             app: None,
@@ -139,30 +161,47 @@ fn run(src: &'static str) -> String {
         Instance::from_bytes(&arena, COMPILER_BYTES, dispatcher, is_debug_mode).unwrap()
     };
 
-    let len = Value::I32(src.len() as i32);
-    instance.call_export("entrypoint_from_test", [len]).unwrap();
-    instance.import_dispatcher.answer.to_owned()
+    split_entries(src)
+        .into_iter()
+        .map(|entry| {
+            let len = Value::I32(entry.len() as i32);
+            instance.import_dispatcher.src = entry;
+            instance.call_export("entrypoint_from_test", [len]).unwrap();
+
+            // We need to get rid of HTML tags, and we can be quite specific about it!
+            // If we ever write more complex test cases, we might need regex here.
+            instance
+                .import_dispatcher
+                .answer
+                .replace("<span class='color-magenta'> : </span>", " : ")
+                .trim()
+                .to_string()
+        })
+        .collect()
 }
 
+/// Check the answer to the last entry that had one, like the CLI tests check the last line.
 #[allow(dead_code)]
-pub fn expect_success(input: &'static str, expected: &str) {
-    expect(input, expected);
-}
+pub fn expect_success(input: &str, expected: &str) {
+    let answers = run(input);
+    let last_answer = answers.iter().rev().find(|answer| !answer.is_empty());
 
-#[allow(dead_code)]
-pub fn expect_failure(input: &'static str, expected: &str) {
-    expect(input, expected);
+    assert_eq!(last_answer.map(String::as_str), Some(expected));
 }
 
+/// Check everything from the first error on, since the entries before it may have answers too.
 #[allow(dead_code)]
-pub fn expect(input: &'static str, expected: &str) {
-    let raw_output = run(input);
-
-    // We need to get rid of HTML tags, and we can be quite specific about it!
-    // If we ever write more complex test cases, we might need regex here.
-    let without_html = raw_output.replace("<span class='color-magenta'> : </span>", " : ");
+pub fn expect_failure(input: &str, expected: &str) {
+    let answers = run(input).join("\n");
 
-    let clean_output = without_html.trim();
+    match answers.find(ERROR_MESSAGE_START) {
+        Some(index) => assert_eq!(&answers[index..], expected),
+        None => panic!("I expected a failure, but there is no error message in:\n\n{answers}"),
+    }
+}
 
-    assert_eq!(clean_output, expected);
+/// A session of several entries, which should each get the given answer.
+#[allow(dead_code)]
+pub fn expect_session(inputs: &[&str], expected: &[&str]) {
+    assert_eq!(run(&inputs.join("\n")), expected);
 }
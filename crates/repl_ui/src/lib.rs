@@ -10,6 +10,7 @@ use repl_state::{parse_src, ParseOutcome};
 use roc_parse::ast::{Expr, ValueDef};
 use roc_repl_eval::gen::{Problems, ReplOutput};
 use roc_reporting::report::StyleCodes;
+use serde_json::json;
 
 use crate::colors::GREEN;
 
@@ -38,6 +39,35 @@ pub const TIPS: &str = concatcp!(
             BLUE,
             "  - ",
             END_COL,
+            ":t <expr>",
+            " shows the type of an expression\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            ":doc <name>",
+            " shows the documentation for a name\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            ":search <type>",
+            " finds the builtins with a type, like ",
+            "Str, Str -> Str",
+            "\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            ":env",
+            " lists the names you've defined, with their types\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            ":set show-instantiated",
+            " shows what number types default to (",
+            ":unset",
+            " turns it off)\n",
+            BLUE,
+            "  - ",
+            END_COL,
             ":help"
         )
     } else {
@@ -58,6 +88,89 @@ pub const TIPS: &str = concatcp!(
             "  - ",
             END_COL,
             GREEN,
+            ":t <expr>",
+            END_COL,
+            " shows the type of an expression\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":doc <name>",
+            END_COL,
+            " shows the documentation for a name\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":search <type>",
+            END_COL,
+            " finds the builtins and loaded values with a type, like ",
+            GREEN,
+            "Str, Str -> Str",
+            END_COL,
+            "\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":env",
+            END_COL,
+            " lists the names you've defined, with their types\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":load <file.roc>",
+            END_COL,
+            " imports a module's exposed values (",
+            GREEN,
+            ":reload",
+            END_COL,
+            " recompiles it)\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":expand",
+            END_COL,
+            " shows all of a result that was too long to show in full (",
+            GREEN,
+            ":save <file>",
+            END_COL,
+            " writes it to a file)\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":set show-instantiated",
+            END_COL,
+            " shows what number types default to (",
+            GREEN,
+            ":unset",
+            END_COL,
+            " turns it off)\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":time <expr>",
+            END_COL,
+            " shows how long compiling and running an expression takes\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":record <file.roc>",
+            END_COL,
+            " writes what you enter from now on to a module (",
+            GREEN,
+            ":stop",
+            END_COL,
+            " stops)\n",
+            BLUE,
+            "  - ",
+            END_COL,
+            GREEN,
             ":q",
             END_COL,
             " quits\n",
@@ -78,32 +191,107 @@ pub const SHORT_INSTRUCTIONS: &str = "Enter an expression, or :help, or :q to qu
 pub const PROMPT: &str = concatcp!(BLUE, "»", END_COL, " ");
 pub const CONT_PROMPT: &str = concatcp!(BLUE, "…", END_COL, " ");
 
+/// How much further to indent each level of a multi-line entry
+const INDENT: &str = "    ";
+
 pub fn is_incomplete(input: &str) -> bool {
     let arena = Bump::new();
 
     match parse_src(&arena, input) {
-        ParseOutcome::Incomplete => !input.ends_with('\n'),
+        ParseOutcome::Incomplete => !ends_with_blank_line(input),
         // Standalone annotations are default incomplete, because we can't know
         // whether they're about to annotate a body on the next line
         // (or if not, meaning they stay standalone) until you press Enter again!
         //
-        // So it's Incomplete until you've pressed Enter again (causing the input to end in "\n")
-        ParseOutcome::ValueDef(ValueDef::Annotation(_, _)) if !input.ends_with('\n') => true,
+        // So it's Incomplete until you've pressed Enter again (causing the input to end in a blank line)
+        ParseOutcome::ValueDef(ValueDef::Annotation(_, _)) if !ends_with_blank_line(input) => true,
+        // e.g. `:time x =`
+        ParseOutcome::Time(src) => is_incomplete(src),
         ParseOutcome::Expr(Expr::When(_, _)) => {
             // There might be lots of `when` branches, so don't assume the user is done entering
             // them until they enter a blank line!
-            !input.ends_with('\n')
+            !ends_with_blank_line(input)
         }
         ParseOutcome::Empty
         | ParseOutcome::Help
         | ParseOutcome::Exit
         | ParseOutcome::ValueDef(_)
         | ParseOutcome::TypeDef(_)
+        | ParseOutcome::TypeOf(_)
+        | ParseOutcome::Doc(_)
+        | ParseOutcome::Env
+        | ParseOutcome::Load(_)
+        | ParseOutcome::Reload
+        | ParseOutcome::Expand
+        | ParseOutcome::Save(_)
+        | ParseOutcome::Set(_)
+        | ParseOutcome::Unset(_)
+        | ParseOutcome::Record(_)
+        | ParseOutcome::Stop
         | ParseOutcome::SyntaxErr
         | ParseOutcome::Expr(_) => false,
     }
 }
 
+/// Whether the user has entered a blank line (possibly containing only the
+/// indentation we inserted for them) at the end of a multi-line entry.
+fn ends_with_blank_line(input: &str) -> bool {
+    match input.rsplit_once('\n') {
+        Some((_, last_line)) => last_line.trim().is_empty(),
+        None => false,
+    }
+}
+
+/// The indentation to insert at the start of the next line of an incomplete entry.
+///
+/// This keeps the indentation of the last line, and indents one level further if
+/// that line opens a block, e.g. `x =`, `\y ->`, `when y is`, or `[`.
+pub fn continuation_indent(input: &str) -> String {
+    let last_line = input.rsplit('\n').next().unwrap_or_default();
+    let trimmed = last_line.trim_start();
+    let mut indent = last_line[..last_line.len() - trimmed.len()].to_string();
+
+    if repl_state::opens_block(trimmed) {
+        indent.push_str(INDENT);
+    }
+
+    indent
+}
+
+/// Split several lines entered at once (e.g. pasted into the web REPL) into the entries
+/// they would have been if they'd been typed into the CLI one line at a time: each line
+/// ends an entry, unless the entry is still incomplete.
+///
+/// Stepping the [repl_state::ReplState] through these one by one remembers each def along
+/// the way, just like a session in the CLI does.
+pub fn split_entries(src: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut entry = String::new();
+
+    for line in src.lines() {
+        if !entry.is_empty() {
+            entry.push('\n');
+        }
+
+        entry.push_str(line);
+
+        if !is_incomplete(&entry) {
+            if !entry.trim().is_empty() {
+                entries.push(entry.trim().to_string());
+            }
+
+            entry.clear();
+        }
+    }
+
+    // The input ended partway through an entry, so report whatever is wrong with it.
+    if !entry.trim().is_empty() {
+        entries.push(entry.trim().to_string());
+    }
+
+    entries
+}
+
 pub fn format_output(
     style_codes: StyleCodes,
     opt_output: Option<ReplOutput>,
@@ -121,7 +309,24 @@ pub fn format_output(
         buf.push('\n');
     }
 
-    if let Some(ReplOutput { expr, expr_type }) = opt_output {
+    if let Some(ReplOutput {
+        expr,
+        expr_type,
+        dbgs,
+        shortened_expr,
+        instantiated_type,
+        timings,
+    }) = opt_output
+    {
+        // Print what each `dbg` showed, in the order they ran, before the answer.
+        for value in dbgs {
+            buf.push('\n');
+            buf.push_str(style_codes.cyan);
+            buf.push_str("[dbg] ");
+            buf.push_str(style_codes.reset);
+            buf.push_str(&value);
+        }
+
         // If expr was empty, it was a type annotation or ability declaration;
         // don't print anything!
         //
@@ -133,14 +338,280 @@ pub fn format_output(
             // Print the expr and its type
             {
                 buf.push('\n');
-                buf.push_str(&expr);
+                buf.push_str(shortened_expr.as_deref().unwrap_or(&expr));
                 buf.push_str(style_codes.magenta); // Color for the type separator
                 buf.push_str(EXPR_TYPE_SEPARATOR);
                 buf.push_str(style_codes.reset);
                 buf.push_str(&expr_type);
+
+                // e.g. "5 : Num *  (defaults to I64)"
+                if let Some(instantiated_type) = instantiated_type {
+                    buf.push_str("  (defaults to ");
+                    buf.push_str(&instantiated_type);
+                    buf.push(')');
+                }
+            }
+
+            // Show how long each stage took, e.g. "execution       3.4ms"
+            if let Some(timings) = timings {
+                buf.push('\n');
+
+                for (stage, duration) in [
+                    ("parse/typecheck", timings.compile),
+                    ("code gen", timings.code_gen),
+                    ("execution", timings.run),
+                ] {
+                    buf.push('\n');
+                    buf.push_str(style_codes.magenta);
+                    buf.push_str(&format!("{stage:<16}"));
+                    buf.push_str(style_codes.reset);
+                    buf.push_str(&format!("{duration:.1?}"));
+                }
+            }
+
+            if shortened_expr.is_some() {
+                buf.push_str("\n\n");
+                buf.push_str(style_codes.green);
+                buf.push_str(":expand");
+                buf.push_str(style_codes.reset);
+                buf.push_str(" shows all of it, and ");
+                buf.push_str(style_codes.green);
+                buf.push_str(":save <file>");
+                buf.push_str(style_codes.reset);
+                buf.push_str(" writes it to a file.");
             }
         }
     }
 
     buf
 }
+
+/// Format the output of a `:doc` command: the name and its type, followed by its doc comment.
+pub fn format_doc_output(
+    style_codes: StyleCodes,
+    opt_output: Option<ReplOutput>,
+    docs: Option<String>,
+    problems: Problems,
+) -> String {
+    let has_errors = !problems.errors.is_empty();
+    let mut buf = format_output(style_codes, opt_output, problems);
+
+    match docs {
+        Some(docs) if !has_errors => {
+            buf.push_str("\n\n");
+            buf.push_str(docs.trim_end());
+        }
+        _ => {}
+    }
+
+    buf
+}
+
+/// Whether an `expect` passed, given the result of evaluating its condition.
+/// If it didn't, the reason gets added to the problems.
+pub fn expect_passed(
+    opt_output: Option<ReplOutput>,
+    failure_report: String,
+    problems: &mut Problems,
+) -> bool {
+    if !problems.errors.is_empty() {
+        return false;
+    }
+
+    match opt_output {
+        Some(ReplOutput {
+            expr, expr_type, ..
+        }) if expr_type == "Bool" => {
+            if expr == "Bool.true" {
+                true
+            } else {
+                problems.errors.push(failure_report);
+
+                false
+            }
+        }
+        Some(ReplOutput { expr_type, .. }) => {
+            problems.errors.push(format!(
+                "This expectation's condition has type {expr_type}, but it needs to be a Bool."
+            ));
+
+            false
+        }
+        None => false,
+    }
+}
+
+/// Format the output of an `expect`: `passed`, or else the reason it failed
+/// (see `expect_passed`).
+pub fn format_expect_output(style_codes: StyleCodes, passed: bool, problems: Problems) -> String {
+    let mut buf = format_output(style_codes, None, problems);
+
+    if passed {
+        buf.push_str("\npassed");
+    }
+
+    buf
+}
+
+/// Format the output of the `:env` command: each name that's been defined, with its type.
+pub fn format_env_output(
+    style_codes: StyleCodes,
+    types: Vec<(String, String)>,
+    problems: Problems,
+) -> String {
+    let has_errors = !problems.errors.is_empty();
+    let mut buf = format_output(style_codes, None, problems);
+
+    if !has_errors {
+        for (name, name_type) in types {
+            buf.push('\n');
+            buf.push_str(&name);
+            buf.push_str(style_codes.magenta);
+            buf.push_str(" : ");
+            buf.push_str(style_codes.reset);
+            buf.push_str(&name_type);
+        }
+    }
+
+    buf
+}
+
+/// Format the results of `:search`, which look like the output of `:env`.
+pub fn format_search_output(
+    style_codes: StyleCodes,
+    results: Vec<(String, String)>,
+    problems: Problems,
+) -> String {
+    if results.is_empty() && problems.errors.is_empty() {
+        let mut buf = format_output(style_codes, None, problems);
+
+        buf.push_str("\nNothing has that type.");
+
+        buf
+    } else {
+        format_env_output(style_codes, results, problems)
+    }
+}
+
+/// Format the output of a REPL entry as a single line of JSON, so that editor plugins and
+/// notebook frontends can embed the REPL without scraping styled text.
+///
+/// Problems should have been rendered without styling (e.g. using `PLAIN_PALETTE`).
+pub fn format_output_json(opt_output: Option<ReplOutput>, problems: Problems) -> String {
+    output_json(opt_output, problems).to_string()
+}
+
+/// Like `format_output_json`, but with an additional `docs` field for the output of `:doc`.
+pub fn format_doc_output_json(
+    opt_output: Option<ReplOutput>,
+    docs: Option<String>,
+    problems: Problems,
+) -> String {
+    let mut output = output_json(opt_output, problems);
+
+    output["docs"] = json!(docs);
+
+    output.to_string()
+}
+
+/// Like `format_output_json`, but for the `:env` command, which has an `env` field with
+/// the name and type of each def instead of a `value` and `type`.
+pub fn format_env_output_json(types: Vec<(String, String)>, problems: Problems) -> String {
+    let mut output = output_json(None, problems);
+
+    if let Some(fields) = output.as_object_mut() {
+        fields.remove("value");
+        fields.remove("type");
+    }
+
+    output["env"] = types
+        .into_iter()
+        .map(|(name, name_type)| json!({ "name": name, "type": name_type }))
+        .collect();
+
+    output.to_string()
+}
+
+/// Like `format_env_output_json`, but for `:search`, with a `results` field instead of `env`.
+pub fn format_search_output_json(results: Vec<(String, String)>, problems: Problems) -> String {
+    let mut output = output_json(None, problems);
+
+    if let Some(fields) = output.as_object_mut() {
+        fields.remove("value");
+        fields.remove("type");
+    }
+
+    output["results"] = results
+        .into_iter()
+        .map(|(name, name_type)| json!({ "name": name, "type": name_type }))
+        .collect();
+
+    output.to_string()
+}
+
+/// Like `format_output_json`, but for an `expect`, whose `value` is `"passed"` if it passed.
+pub fn format_expect_output_json(passed: bool, problems: Problems) -> String {
+    let mut output = output_json(None, problems);
+
+    if passed {
+        output["value"] = json!("passed");
+    }
+
+    output.to_string()
+}
+
+fn output_json(opt_output: Option<ReplOutput>, problems: Problems) -> serde_json::Value {
+    let (value, expr_type, instantiated_type, timings, dbgs) = match opt_output {
+        // Programs reading the JSON get the whole value, even if it's long
+        Some(ReplOutput {
+            expr,
+            expr_type,
+            instantiated_type,
+            timings,
+            dbgs,
+            ..
+        }) if !expr.is_empty() && problems.errors.is_empty() => (
+            Some(expr),
+            Some(expr_type),
+            instantiated_type,
+            timings,
+            dbgs,
+        ),
+        Some(ReplOutput { dbgs, .. }) => (None, None, None, None, dbgs),
+        None => (None, None, None, None, Vec::new()),
+    };
+
+    let errors = problems
+        .errors
+        .into_iter()
+        .map(|message| json!({ "severity": "error", "message": message }));
+    let warnings = problems
+        .warnings
+        .into_iter()
+        .map(|message| json!({ "severity": "warning", "message": message }));
+
+    let mut output = json!({
+        "value": value,
+        "type": expr_type,
+        "problems": errors.chain(warnings).collect::<Vec<_>>(),
+    });
+
+    if let Some(instantiated_type) = instantiated_type {
+        output["defaults_to"] = json!(instantiated_type);
+    }
+
+    // In seconds
+    if let Some(timings) = timings {
+        output["time"] = json!({
+            "compile": timings.compile.as_secs_f64(),
+            "code_gen": timings.code_gen.as_secs_f64(),
+            "run": timings.run.as_secs_f64(),
+        });
+    }
+
+    if !dbgs.is_empty() {
+        output["dbg"] = json!(dbgs);
+    }
+
+    output
+}
@@ -1,17 +1,27 @@
 use bumpalo::Bump;
 use roc_collections::MutSet;
 use roc_load::MonomorphizedModule;
-use roc_parse::ast::{Expr, Pattern, TypeDef, TypeHeader, ValueDef};
+use roc_module::ident::ModuleName;
+use roc_module::symbol::{IdentIds, ModuleIds};
+use roc_parse::ast::{Expr, Header, Module, Pattern, TypeDef, TypeHeader, ValueDef};
 use roc_parse::expr::{parse_single_def, ExprParseOptions, SingleDef};
+use roc_parse::module::parse_header;
 use roc_parse::parser::Parser;
 use roc_parse::parser::{EClosure, EExpr, EPattern};
 use roc_parse::parser::{EWhen, Either};
 use roc_parse::state::State;
 use roc_parse::{join_alias_to_body, join_ann_to_body};
 use roc_region::all::Loc;
-use roc_repl_eval::gen::{compile_to_mono, Problems};
+use roc_repl_eval::docs::{builtin_docs, module_docs};
+use roc_repl_eval::gen::{
+    compile_to_mono, compile_to_type, def_types, expect_failure_report, instantiated_type,
+    record_field_names, Problems, ReplImports, ReplOutput,
+};
+use roc_repl_eval::search::{search_builtins, search_module, TypeQuery};
 use roc_reporting::report::Palette;
 use roc_target::TargetInfo;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, PartialEq)]
 struct PastDef {
@@ -19,9 +29,187 @@ struct PastDef {
     src: String,
 }
 
+/// An interface module brought into scope using `:load`
+#[derive(Debug, Clone, PartialEq)]
+struct ImportedModule {
+    path: PathBuf,
+    name: String,
+    exposed: Vec<String>,
+}
+
+impl ImportedModule {
+    fn from_path(path: &Path) -> Result<Self, String> {
+        let bytes = std::fs::read(path)
+            .map_err(|err| format!("I couldn't read {}: {err}", path.display()))?;
+        let arena = Bump::new();
+
+        match parse_header(&arena, State::new(&bytes)) {
+            Ok((
+                Module {
+                    header: Header::Interface(header),
+                    ..
+                },
+                _,
+            )) => Ok(Self {
+                path: path.to_path_buf(),
+                name: header.name.value.as_str().to_string(),
+                exposed: header
+                    .exposes
+                    .item
+                    .items
+                    .iter()
+                    .map(|exposed| exposed.value.item().as_str().to_string())
+                    .collect(),
+            }),
+            Ok(_) => Err(format!(
+                "{} is not an interface module, so it can't be loaded into the REPL.",
+                path.display()
+            )),
+            Err(_) => Err(format!(
+                "I couldn't parse the module header of {}.",
+                path.display()
+            )),
+        }
+    }
+
+    /// The directory the module's imports get resolved relative to
+    fn dir(&self) -> &Path {
+        match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        }
+    }
+
+    /// The doc comment on the def named `ident` in this module, reading it from disk again
+    /// in case it changed since the module was loaded.
+    fn docs(&self, ident: &str) -> Option<String> {
+        let src = std::fs::read_to_string(&self.path).ok()?;
+
+        module_docs(&src, ident)
+    }
+
+    /// The entry for this module in the REPL app module's `imports` list
+    fn import_entry(&self) -> String {
+        if self.exposed.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{{ {} }}", self.name, self.exposed.join(", "))
+        }
+    }
+}
+
+/// A `.roc` file that the session's entries get written to, after `:record`
+#[derive(Debug)]
+struct Recording {
+    path: PathBuf,
+    module_name: String,
+    entries: Vec<RecordedEntry>,
+    /// How many expressions have been recorded, for naming the next one
+    exprs: usize,
+}
+
+/// An entry written to a recording
+#[derive(Debug)]
+struct RecordedEntry {
+    /// The name the entry defines, which the module exposes. (`expect`s don't define one.)
+    ident: Option<String>,
+    src: String,
+}
+
+impl Recording {
+    fn new(path: &Path) -> Result<Self, String> {
+        let module_name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| {
+                stem.starts_with(|ch: char| ch.is_ascii_uppercase())
+                    && stem.chars().all(|ch| ch.is_ascii_alphanumeric())
+            })
+            .ok_or_else(|| {
+                format!(
+                    "I can't record to {}, because the file name has to be a module name, like Session.roc",
+                    path.display()
+                )
+            })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            module_name: module_name.to_string(),
+            entries: Vec::new(),
+            exprs: 0,
+        })
+    }
+
+    fn add(&mut self, ident: Option<String>, src: String) {
+        // A module can only define each name once, so a redefinition replaces the old def.
+        if ident.is_some() {
+            self.entries.retain(|entry| entry.ident != ident);
+        }
+
+        self.entries.push(RecordedEntry { ident, src });
+    }
+
+    /// Record an expression as a def, since a module can't have bare expressions in it.
+    fn add_expr(&mut self, expr: &str) {
+        // The recording has every def from the session in it, so this is the only place to check.
+        let ident = loop {
+            self.exprs += 1;
+
+            let ident = format!("expr{}", self.exprs);
+
+            if !self
+                .entries
+                .iter()
+                .any(|entry| entry.ident.as_deref() == Some(ident.as_str()))
+            {
+                break ident;
+            }
+        };
+
+        let src = if expr.contains('\n') {
+            let indented: Vec<_> = expr.lines().map(|line| format!("    {line}")).collect();
+
+            format!("{ident} =\n{}", indented.join("\n"))
+        } else {
+            format!("{ident} = {expr}")
+        };
+
+        self.add(Some(ident), src);
+    }
+
+    /// Write the recorded entries to the file, as an interface module.
+    fn write(&self, imports: &[ImportedModule]) -> Result<(), String> {
+        let exposes: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.ident.as_deref())
+            .collect();
+        let imports: Vec<_> = imports.iter().map(ImportedModule::import_entry).collect();
+        let mut contents = format!(
+            "interface {}\n    exposes [{}]\n    imports [{}]\n",
+            self.module_name,
+            exposes.join(", "),
+            imports.join(", ")
+        );
+
+        for entry in self.entries.iter() {
+            contents.push('\n');
+            contents.push_str(entry.src.trim_end());
+            contents.push('\n');
+        }
+
+        std::fs::write(&self.path, contents)
+            .map_err(|err| format!("I couldn't write to {}: {err}", self.path.display()))
+    }
+}
+
 pub struct ReplState {
     past_defs: Vec<PastDef>,
     past_def_idents: MutSet<String>,
+    imported_modules: Vec<ImportedModule>,
+    /// Whether to show what polymorphic number types default to, e.g. `Num *  (defaults to I64)`
+    show_instantiated: bool,
+    recording: Option<Recording>,
 }
 
 impl Default for ReplState {
@@ -36,6 +224,51 @@ pub enum ReplAction<'a> {
     Eval {
         opt_mono: Option<MonomorphizedModule<'a>>,
         problems: Problems,
+        /// What the result's type defaults to, if `:set show-instantiated` is on
+        instantiated_type: Option<String>,
+        /// How long compiling the entry took, if it started with `:time`
+        compile_time: Option<Duration>,
+    },
+    TypeOf {
+        opt_output: Option<ReplOutput>,
+        problems: Problems,
+    },
+    Load {
+        problems: Problems,
+    },
+    /// The result of changing a setting using `:set` or `:unset`
+    Set {
+        problems: Problems,
+    },
+    /// The result of starting or stopping a recording, or of writing an entry to one
+    Record {
+        problems: Problems,
+    },
+    Doc {
+        opt_output: Option<ReplOutput>,
+        docs: Option<String>,
+        problems: Problems,
+    },
+    Env {
+        types: Vec<(String, String)>,
+        problems: Problems,
+    },
+    /// The names whose types matched a `:search`, with their types
+    Search {
+        results: Vec<(String, String)>,
+        problems: Problems,
+    },
+    Expect {
+        opt_mono: Option<MonomorphizedModule<'a>>,
+        problems: Problems,
+        /// What to report if the condition turns out to be false
+        failure_report: String,
+    },
+    /// Show the whole of the last result
+    Expand,
+    /// Write the whole of the last result's value to a file
+    Save {
+        path: String,
     },
     Exit,
     Help,
@@ -47,6 +280,9 @@ impl ReplState {
         Self {
             past_defs: Default::default(),
             past_def_idents: Default::default(),
+            imported_modules: Default::default(),
+            show_instantiated: false,
+            recording: None,
         }
     }
 
@@ -58,11 +294,150 @@ impl ReplState {
         palette: Palette,
     ) -> ReplAction<'a> {
         let pending_past_def;
+        let mut pending_expr = None;
         let src: &str = match parse_src(arena, line) {
             ParseOutcome::Empty | ParseOutcome::Help => return ReplAction::Help,
             ParseOutcome::Exit => return ReplAction::Exit,
+            ParseOutcome::Env => {
+                let (types, problems) = def_types(
+                    arena,
+                    self.past_defs.iter().map(|def| def.src.as_str()),
+                    &self.imports(),
+                    target_info,
+                    palette,
+                );
+
+                return ReplAction::Env { types, problems };
+            }
+            ParseOutcome::TypeOf(expr) => {
+                // Only typecheck the expression; there's no need to generate code for it.
+                let (opt_type, problems) = compile_to_type(
+                    arena,
+                    self.past_defs.iter().map(|def| def.src.as_str()),
+                    &self.imports(),
+                    expr,
+                    target_info,
+                    palette,
+                );
+
+                let opt_output = opt_type.map(|expr_type| ReplOutput {
+                    expr: expr.trim().to_string(),
+                    expr_type,
+                    dbgs: Vec::new(),
+                    shortened_expr: None,
+                    instantiated_type: None,
+                    timings: None,
+                });
+
+                return ReplAction::TypeOf {
+                    opt_output,
+                    problems,
+                };
+            }
+            ParseOutcome::Doc(name) => {
+                let name = name.trim();
+                let (opt_type, problems) = compile_to_type(
+                    arena,
+                    self.past_defs.iter().map(|def| def.src.as_str()),
+                    &self.imports(),
+                    name,
+                    target_info,
+                    palette,
+                );
+
+                let opt_output = opt_type.map(|expr_type| ReplOutput {
+                    expr: name.to_string(),
+                    expr_type,
+                    dbgs: Vec::new(),
+                    shortened_expr: None,
+                    instantiated_type: None,
+                    timings: None,
+                });
+
+                return ReplAction::Doc {
+                    opt_output,
+                    docs: self.docs(name),
+                    problems,
+                };
+            }
+            ParseOutcome::Search(query) => return self.search(query),
+            ParseOutcome::Load(path) => {
+                let problems = self.load_module(arena, Path::new(path), target_info, palette);
+
+                return ReplAction::Load { problems };
+            }
+            ParseOutcome::Reload => {
+                let problems = self.reload_modules(arena, target_info, palette);
+
+                return ReplAction::Load { problems };
+            }
+            ParseOutcome::Set(setting) => {
+                return ReplAction::Set {
+                    problems: self.set(setting, true),
+                }
+            }
+            ParseOutcome::Unset(setting) => {
+                return ReplAction::Set {
+                    problems: self.set(setting, false),
+                }
+            }
+            ParseOutcome::Time(src) => {
+                if cfg!(target_family = "wasm") {
+                    // There's no clock to read in the browser, so just evaluate it.
+                    let mut action = self.step(arena, src, target_info, palette);
+
+                    if let ReplAction::Eval { problems, .. } = &mut action {
+                        problems
+                            .warnings
+                            .push("The web version of the REPL can't time entries.".to_string());
+                    }
+
+                    return action;
+                }
+
+                let start = Instant::now();
+
+                return match self.step(arena, src, target_info, palette) {
+                    ReplAction::Eval {
+                        opt_mono,
+                        problems,
+                        instantiated_type,
+                        compile_time: _,
+                    } => ReplAction::Eval {
+                        opt_mono,
+                        problems,
+                        instantiated_type,
+                        compile_time: Some(start.elapsed()),
+                    },
+                    // Only evaluations have anything worth timing
+                    action => action,
+                };
+            }
+            ParseOutcome::Record(path) => {
+                return ReplAction::Record {
+                    problems: self.start_recording(Path::new(path)),
+                }
+            }
+            ParseOutcome::Stop => {
+                let problems = match self.recording.take() {
+                    Some(_) => Problems::default(),
+                    None => error_problems(
+                        "There's no recording to stop. Use :record <file.roc> to start one."
+                            .to_string(),
+                    ),
+                };
+
+                return ReplAction::Record { problems };
+            }
+            ParseOutcome::Expand => return ReplAction::Expand,
+            ParseOutcome::Save(path) => {
+                return ReplAction::Save {
+                    path: path.to_string(),
+                }
+            }
             ParseOutcome::Expr(_) | ParseOutcome::Incomplete | ParseOutcome::SyntaxErr => {
                 pending_past_def = None;
+                pending_expr = Some(line);
 
                 // If it's a SyntaxErr (or Incomplete at this point, meaning it will
                 // become a SyntaxErr as soon as we evaluate it),
@@ -79,11 +454,12 @@ impl ReplState {
                         _,
                     ) => {
                         // Record the standalone type annotation for future use.
-                        self.add_past_def(ident.trim_end().to_string(), line.to_string());
+                        let problems =
+                            self.add_past_def(ident.trim_end().to_string(), line.to_string());
 
                         // Return early without running eval, since standalone annotations
                         // cannot be evaluated as expressions.
-                        return ReplAction::Nothing;
+                        return nothing_unless(problems);
                     }
                     ValueDef::Body(
                         Loc {
@@ -122,11 +498,54 @@ impl ReplState {
                     | ValueDef::AnnotatedBody { .. } => {
                         todo!("handle pattern other than identifier (which repl doesn't support)")
                     }
-                    ValueDef::Dbg { .. } => {
-                        todo!("handle receiving a `dbg` - what should the repl do for that?")
+                    ValueDef::Dbg { condition, .. } => {
+                        pending_past_def = None;
+
+                        // Follow the `dbg` with its condition, so that it shows the value
+                        // and then evaluates to it.
+                        let region = condition.region;
+                        let condition_src =
+                            &line[region.start().offset as usize..region.end().offset as usize];
+                        let mut buf = bumpalo::collections::string::String::with_capacity_in(
+                            line.len() + condition_src.len() + 1,
+                            arena,
+                        );
+
+                        buf.push_str(line);
+                        buf.push('\n');
+                        buf.push_str(condition_src);
+
+                        buf.into_bump_str()
                     }
-                    ValueDef::Expect { .. } => {
-                        todo!("handle receiving an `expect` - what should the repl do for that?")
+                    ValueDef::Expect { condition, .. } => {
+                        // Evaluate just the condition; whoever runs it decides
+                        // whether the expectation passed.
+                        let region = condition.region;
+                        let condition_src =
+                            &line[region.start().offset as usize..region.end().offset as usize];
+
+                        let (opt_mono, mut problems) = compile_to_mono(
+                            arena,
+                            self.past_defs.iter().map(|def| def.src.as_str()),
+                            &self.imports(),
+                            condition_src,
+                            target_info,
+                            palette,
+                        );
+
+                        if problems.errors.is_empty() {
+                            let recorded = self.record(|recording| {
+                                recording.add(None, line.to_string());
+                            });
+
+                            problems.errors.extend(recorded.errors);
+                        }
+
+                        return ReplAction::Expect {
+                            opt_mono,
+                            problems,
+                            failure_report: expect_failure_report(line.trim(), palette),
+                        };
                     }
                     ValueDef::ExpectFx { .. } => {
                         todo!("handle receiving an `expect-fx` - what should the repl do for that?")
@@ -158,35 +577,370 @@ impl ReplState {
                 ..
             }) => {
                 // Record the type for future use.
-                self.add_past_def(ident.trim_end().to_string(), line.to_string());
+                let problems = self.add_past_def(ident.trim_end().to_string(), line.to_string());
 
                 // Return early without running eval, since none of these
                 // can be evaluated as expressions.
-                return ReplAction::Nothing;
+                return nothing_unless(problems);
             }
         };
 
-        let (opt_mono, problems) = compile_to_mono(
+        let (mut opt_mono, mut problems) = compile_to_mono(
             arena,
             self.past_defs.iter().map(|def| def.src.as_str()),
+            &self.imports(),
             src,
             target_info,
             palette,
         );
 
-        if let Some((ident, src)) = pending_past_def {
-            self.add_past_def(ident, src);
+        let instantiated_type = match &mut opt_mono {
+            Some(mono) if self.show_instantiated && problems.errors.is_empty() => {
+                instantiated_type(mono, target_info)
+            }
+            _ => None,
+        };
+
+        // Only persist the def if it compiled; otherwise every later entry would
+        // fail to compile because of it.
+        if problems.errors.is_empty() {
+            let recorded = match (pending_past_def, pending_expr) {
+                (Some((ident, src)), _) => self.add_past_def(ident, src),
+                (None, Some(expr)) => self.record(|recording| recording.add_expr(expr.trim())),
+                (None, None) => Problems::default(),
+            };
+
+            problems.errors.extend(recorded.errors);
+        }
+
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            instantiated_type,
+            compile_time: None,
+        }
+    }
+
+    /// Find tab completions for the word ending at byte offset `pos` in `line`.
+    ///
+    /// Returns the offset where the word being completed starts, along with the candidates.
+    /// `Str.` style words complete to the names exposed by that builtin module, `x.` words
+    /// (where `x` was defined earlier in this session) complete to the record's fields,
+    /// and anything else completes to builtin module names and previously defined names.
+    pub fn completions(
+        &self,
+        line: &str,
+        pos: usize,
+        target_info: TargetInfo,
+    ) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, ch)| ch.is_alphanumeric() || *ch == '_' || *ch == '.')
+            .last()
+            .map(|(index, _)| index)
+            .unwrap_or(pos);
+        let word = &before[start..];
+
+        let mut candidates: Vec<String> = match word.rsplit_once('.') {
+            Some((qualifier, prefix)) => {
+                let first_segment = qualifier.split('.').next().unwrap_or_default();
+
+                if self.past_def_idents.contains(first_segment) {
+                    let arena = Bump::new();
+                    let defs = self.past_defs.iter().map(|def| def.src.as_str());
+
+                    record_field_names(&arena, defs, &self.imports(), qualifier, target_info)
+                        .into_iter()
+                        .filter(|field| field.starts_with(prefix))
+                        .map(|field| format!("{qualifier}.{field}"))
+                        .collect()
+                } else {
+                    builtin_module_exposed_names(qualifier)
+                        .into_iter()
+                        .filter(|name| name.starts_with(prefix))
+                        .map(|name| format!("{qualifier}.{name}"))
+                        .collect()
+                }
+            }
+            None => {
+                let module_ids = ModuleIds::default();
+                let module_names = module_ids
+                    .available_modules()
+                    .map(|name| name.as_str().to_string());
+
+                let imported_names = self
+                    .imported_modules
+                    .iter()
+                    .flat_map(|module| module.exposed.iter().cloned());
+
+                module_names
+                    .chain(self.past_def_idents.iter().cloned())
+                    .chain(imported_names)
+                    .filter(|name| !name.starts_with('#') && name.starts_with(word))
+                    .collect()
+            }
+        };
+
+        candidates.sort();
+        candidates.dedup();
+
+        (start, candidates)
+    }
+
+    /// Bring the exposed values of the interface module at `path` into scope.
+    /// If the module doesn't compile, the REPL keeps the modules it had loaded before.
+    fn load_module(
+        &mut self,
+        arena: &Bump,
+        path: &Path,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Problems {
+        let module = match ImportedModule::from_path(path) {
+            Ok(module) => module,
+            Err(message) => return error_problems(message),
+        };
+
+        // The app module can only resolve imports from a single directory.
+        if let Some(other) = self
+            .imported_modules
+            .iter()
+            .find(|other| other.name != module.name && other.dir() != module.dir())
+        {
+            return error_problems(format!(
+                "{} is not in the same directory as {}, which was loaded earlier.",
+                module.path.display(),
+                other.path.display()
+            ));
+        }
+
+        let previous_modules = self.imported_modules.clone();
+
+        self.imported_modules
+            .retain(|other| other.name != module.name);
+        self.imported_modules.push(module);
+
+        let problems = self.check_imports(arena, target_info, palette);
+
+        if !problems.errors.is_empty() {
+            self.imported_modules = previous_modules;
+        }
+
+        problems
+    }
+
+    /// Turn a setting (like `show-instantiated`) on or off.
+    fn set(&mut self, setting: &str, on: bool) -> Problems {
+        match setting {
+            "show-instantiated" => {
+                self.show_instantiated = on;
+
+                Problems::default()
+            }
+            _ => error_problems(format!(
+                "There's no setting called {setting}. The only setting is show-instantiated."
+            )),
+        }
+    }
+
+    /// Re-read and recompile every module that was brought into scope using `:load`.
+    fn reload_modules(
+        &mut self,
+        arena: &Bump,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Problems {
+        if self.imported_modules.is_empty() {
+            return error_problems(
+                "There are no modules to reload. Use :load <file.roc> to load one first."
+                    .to_string(),
+            );
+        }
+
+        let reloaded: Result<Vec<_>, _> = self
+            .imported_modules
+            .iter()
+            .map(|module| ImportedModule::from_path(&module.path))
+            .collect();
+
+        match reloaded {
+            Ok(modules) => {
+                // Keep the reloaded modules even if they have errors, so that those
+                // errors keep getting reported until they're fixed and reloaded again.
+                self.imported_modules = modules;
+
+                self.check_imports(arena, target_info, palette)
+            }
+            Err(message) => error_problems(message),
+        }
+    }
+
+    /// Typecheck the imported modules (along with the past defs) without evaluating anything.
+    fn check_imports(&self, arena: &Bump, target_info: TargetInfo, palette: Palette) -> Problems {
+        let (_, problems) = compile_to_type(
+            arena,
+            self.past_defs.iter().map(|def| def.src.as_str()),
+            &self.imports(),
+            "{}",
+            target_info,
+            palette,
+        );
+
+        problems
+    }
+
+    /// The builtins, and values exposed by modules loaded using `:load`, whose types match
+    /// the query. (Defs entered into the REPL itself aren't searched.)
+    fn search<'a>(&self, query: &str) -> ReplAction<'a> {
+        let query = match TypeQuery::parse(query) {
+            Ok(query) => query,
+            Err(message) => {
+                return ReplAction::Search {
+                    results: Vec::new(),
+                    problems: Problems {
+                        errors: vec![message],
+                        warnings: Vec::new(),
+                    },
+                };
+            }
+        };
+
+        let mut results = search_builtins(&query);
+
+        for module in &self.imported_modules {
+            // Read the module again, in case it changed since it was loaded
+            if let Ok(src) = std::fs::read_to_string(&module.path) {
+                results.extend(search_module(&src, &query));
+            }
         }
 
-        ReplAction::Eval { opt_mono, problems }
+        ReplAction::Search {
+            results,
+            problems: Problems::default(),
+        }
+    }
+
+    /// The doc comment for a name like `Str.concat`, or one exposed by a module loaded using `:load`.
+    /// (Defs entered into the REPL itself can't have doc comments.)
+    fn docs(&self, name: &str) -> Option<String> {
+        match name.rsplit_once('.') {
+            Some((module_name, ident)) => {
+                match self
+                    .imported_modules
+                    .iter()
+                    .find(|module| module.name == module_name)
+                {
+                    Some(module) => module.docs(ident),
+                    None => builtin_docs(module_name, ident),
+                }
+            }
+            None => self
+                .imported_modules
+                .iter()
+                .find(|module| module.exposed.iter().any(|exposed| exposed == name))
+                .and_then(|module| module.docs(name)),
+        }
+    }
+
+    fn imports(&self) -> ReplImports {
+        ReplImports {
+            src_dir: self
+                .imported_modules
+                .first()
+                .map(|module| module.dir().to_path_buf()),
+            entries: self
+                .imported_modules
+                .iter()
+                .map(ImportedModule::import_entry)
+                .collect(),
+        }
     }
 
-    fn add_past_def(&mut self, ident: String, src: String) {
+    /// Remember a def for later entries, and write it to the recording if there is one.
+    fn add_past_def(&mut self, ident: String, src: String) -> Problems {
         let existing_idents = &mut self.past_def_idents;
 
-        existing_idents.insert(ident.clone());
+        // Redefining a name replaces its old def, rather than shadowing it.
+        if !existing_idents.insert(ident.clone()) {
+            self.past_defs.retain(|def| def.ident != ident);
+        }
+
+        let recorded = self.record(|recording| recording.add(Some(ident.clone()), src.clone()));
 
         self.past_defs.push(PastDef { ident, src });
+
+        recorded
+    }
+
+    /// Start writing the session to the `.roc` file at `path`, beginning with the defs so far
+    /// (since the entries after this might use them).
+    fn start_recording(&mut self, path: &Path) -> Problems {
+        let mut recording = match Recording::new(path) {
+            Ok(recording) => recording,
+            Err(message) => return error_problems(message),
+        };
+
+        for def in self.past_defs.iter() {
+            recording.add(Some(def.ident.clone()), def.src.clone());
+        }
+
+        self.recording = Some(recording);
+
+        self.record(|_| {})
+    }
+
+    /// Change the recording (if there is one) and write it to its file again.
+    fn record(&mut self, change: impl FnOnce(&mut Recording)) -> Problems {
+        match &mut self.recording {
+            Some(recording) => {
+                change(recording);
+
+                match recording.write(&self.imported_modules) {
+                    Ok(()) => Problems::default(),
+                    Err(message) => error_problems(message),
+                }
+            }
+            None => Problems::default(),
+        }
+    }
+}
+
+/// The action for an entry that doesn't get evaluated, unless writing it to the recording failed
+fn nothing_unless<'a>(problems: Problems) -> ReplAction<'a> {
+    if problems.is_empty() {
+        ReplAction::Nothing
+    } else {
+        ReplAction::Record { problems }
+    }
+}
+
+fn error_problems(message: String) -> Problems {
+    Problems {
+        errors: vec![message],
+        warnings: Vec::new(),
+    }
+}
+
+/// The names exposed by the builtin module with the given name (e.g. `Str`), if there is one.
+fn builtin_module_exposed_names(module_name: &str) -> Vec<String> {
+    let module_ids = ModuleIds::default();
+    let idents_by_module = IdentIds::exposed_builtins(0);
+
+    let opt_ident_ids = module_ids
+        .get_id(&ModuleName::from(module_name))
+        .and_then(|module_id| idents_by_module.get(&module_id));
+
+    match opt_ident_ids {
+        Some(ident_ids) => ident_ids
+            .ident_strs()
+            .map(|(_, name)| name)
+            // Names starting with # are for compiler use only.
+            .filter(|name| !name.starts_with('#'))
+            .map(String::from)
+            .collect(),
+        None => Vec::new(),
     }
 }
 
@@ -195,6 +949,19 @@ pub enum ParseOutcome<'a> {
     ValueDef(ValueDef<'a>),
     TypeDef(TypeDef<'a>),
     Expr(Expr<'a>),
+    TypeOf(&'a str),
+    Doc(&'a str),
+    Search(&'a str),
+    Env,
+    Load(&'a str),
+    Reload,
+    Expand,
+    Save(&'a str),
+    Set(&'a str),
+    Unset(&'a str),
+    Time(&'a str),
+    Record(&'a str),
+    Stop,
     Incomplete,
     SyntaxErr,
     Empty,
@@ -203,10 +970,52 @@ pub enum ParseOutcome<'a> {
 }
 
 pub fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
+    if let Some(expr) = strip_command(line, &[":type", ":t"]) {
+        return ParseOutcome::TypeOf(expr);
+    }
+
+    if let Some(name) = strip_command(line, &[":doc", ":help"]) {
+        return ParseOutcome::Doc(name);
+    }
+
+    if let Some(query) = strip_command(line, &[":search"]) {
+        return ParseOutcome::Search(query.trim());
+    }
+
+    if let Some(path) = strip_command(line, &[":load"]) {
+        return ParseOutcome::Load(path.trim());
+    }
+
+    if let Some(path) = strip_command(line, &[":save"]) {
+        return ParseOutcome::Save(path.trim());
+    }
+
+    if let Some(setting) = strip_command(line, &[":set"]) {
+        return ParseOutcome::Set(setting.trim());
+    }
+
+    if let Some(setting) = strip_command(line, &[":unset"]) {
+        return ParseOutcome::Unset(setting.trim());
+    }
+
+    if let Some(path) = strip_command(line, &[":record"]) {
+        return ParseOutcome::Record(path.trim());
+    }
+
+    if let Some(src) = strip_command(line, &[":time"]) {
+        return ParseOutcome::Time(src.trim_start());
+    }
+
     match line.trim().to_lowercase().as_str() {
         "" => ParseOutcome::Empty,
         ":help" => ParseOutcome::Help,
         ":exit" | ":quit" | ":q" => ParseOutcome::Exit,
+        ":reload" => ParseOutcome::Reload,
+        ":env" => ParseOutcome::Env,
+        ":expand" => ParseOutcome::Expand,
+        ":stop" => ParseOutcome::Stop,
+        // A def or expression that obviously continues on the next line, e.g. `x =`
+        _ if opens_block(line) || has_unclosed_brackets(line) => ParseOutcome::Incomplete,
         _ => {
             let src_bytes = line.as_bytes();
 
@@ -364,3 +1173,64 @@ pub fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
         }
     }
 }
+
+/// If the line is one of the given commands followed by an argument
+/// (e.g. `:type <expr>` or `:t <expr>`), return the argument part.
+fn strip_command<'a>(line: &'a str, commands: &[&str]) -> Option<&'a str> {
+    let trimmed = line.trim_start();
+
+    commands.iter().find_map(|command| {
+        let rest = trimmed.strip_prefix(command)?;
+
+        // Make sure this was the whole command name, e.g. not `:typo`
+        if rest.starts_with(char::is_whitespace) && !rest.trim().is_empty() {
+            Some(rest)
+        } else {
+            None
+        }
+    })
+}
+
+/// Whether the last line of this input ends with something that must be followed by more code,
+/// e.g. `x =`, `\y ->`, `when y is`, or an opening bracket.
+pub(crate) fn opens_block(input: &str) -> bool {
+    let last_line = input.trim_end().rsplit('\n').next().unwrap_or_default();
+    let last_word = last_line
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default();
+
+    // Note that ending in `=` also covers binary operators like `==` and `<=`,
+    // which need something after them too.
+    ["=", "->", "<-", "|>", ",", "(", "[", "{"]
+        .iter()
+        .any(|token| last_line.ends_with(token))
+        || ["is", "then", "else"].contains(&last_word)
+}
+
+/// Whether the input has more opening brackets than closing ones, ignoring string literals
+/// and comments.
+fn has_unclosed_brackets(input: &str) -> bool {
+    let mut depth: i32 = 0;
+
+    for line in input.lines() {
+        let mut chars = line.chars();
+        let mut in_string = false;
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' if in_string => {
+                    // Skip the escaped char, which might be a quote
+                    chars.next();
+                }
+                '"' => in_string = !in_string,
+                '#' if !in_string => break,
+                '(' | '[' | '{' if !in_string => depth += 1,
+                ')' | ']' | '}' if !in_string => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    depth > 0
+}
@@ -7,8 +7,8 @@ use wasm_bindgen::JsValue;
 
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(catch)]
-    pub async fn js_create_app(wasm_module_bytes: &[u8]) -> Result<(), JsValue>;
+    #[wasm_bindgen(catch, js_name = js_create_app)]
+    async fn js_create_app_raw(wasm_module_bytes: &[u8]) -> Result<(), JsValue>;
 
     pub fn js_run_app() -> usize;
 
@@ -18,6 +18,18 @@ extern "C" {
     fn log(s: &str);
 }
 
+/// Create the app and run it, in a web worker that JS stops if the app takes too long.
+/// On failure, returns a message for the user, like the test version does.
+pub async fn js_create_app(wasm_module_bytes: &[u8]) -> Result<(), String> {
+    js_create_app_raw(wasm_module_bytes)
+        .await
+        .map_err(|js_exception| {
+            js_exception
+                .as_string()
+                .unwrap_or_else(|| format!("{js_exception:?}"))
+        })
+}
+
 // To debug in the browser, start up the web REPL as per instructions in repl_www/README.md
 // and sprinkle your code with console_log!("{:?}", my_value);
 // (Or if you're running the unit tests with WASI, you can just use println! or dbg!)
@@ -33,3 +45,10 @@ macro_rules! console_log {
 pub async fn entrypoint_from_js(src: String) -> String {
     crate::repl::entrypoint_from_js(src).await
 }
+
+/// When Enter is pressed, the indentation to start a new line with if the entry is incomplete,
+/// or `undefined` if it's ready to be evaluated
+#[wasm_bindgen]
+pub fn continuation_from_js(src: String) -> Option<String> {
+    crate::repl::continuation_from_js(&src)
+}
@@ -8,16 +8,22 @@ extern "C" {
     fn test_copy_output_string(output_ptr: *const u8, output_len: usize);
 }
 
-/// Async wrapper to match the equivalent JS function
+// What test_create_app returns
+const APP_OK: u32 = 1;
+const APP_TIMED_OUT: u32 = 2;
+
+/// Async wrapper to match the equivalent JS function, which also runs the app
+/// and gives up if it takes too long
 pub async fn js_create_app(wasm_module_bytes: &[u8]) -> Result<(), String> {
-    let ok = unsafe { test_create_app(wasm_module_bytes.as_ptr(), wasm_module_bytes.len()) } != 0;
-    if ok {
-        Ok(())
-    } else {
-        Err("Compiler generated an invalid Wasm module".to_string())
+    match unsafe { test_create_app(wasm_module_bytes.as_ptr(), wasm_module_bytes.len()) } {
+        APP_OK => Ok(()),
+        // The same message as the web REPL's
+        APP_TIMED_OUT => Err("Evaluation timed out after 5 seconds".to_string()),
+        _ => Err("Compiler generated an invalid Wasm module".to_string()),
     }
 }
 
+/// The app has already run, so this only returns the size of its memory
 pub fn js_run_app() -> usize {
     unsafe { test_run_app() }
 }
@@ -9,7 +9,9 @@ extern crate console_error_panic_hook;
 #[cfg(not(feature = "wasi_test"))]
 mod externs_js;
 #[cfg(not(feature = "wasi_test"))]
-pub use externs_js::{entrypoint_from_js, js_create_app, js_get_result_and_memory, js_run_app};
+pub use externs_js::{
+    continuation_from_js, entrypoint_from_js, js_create_app, js_get_result_and_memory, js_run_app,
+};
 
 //
 // Interface with test code outside the Wasm module
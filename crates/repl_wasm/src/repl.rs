@@ -7,17 +7,17 @@ use roc_gen_wasm::wasm32_result;
 use roc_load::MonomorphizedModule;
 use roc_parse::ast::Expr;
 use roc_repl_eval::{
-    eval::jit_to_ast,
-    gen::{format_answer, ReplOutput},
+    eval::eval_main,
+    gen::{DisplaySize, ReplMain, ReplOutput},
     ReplApp, ReplAppMemory,
 };
 use roc_repl_ui::{
-    format_output,
+    continuation_indent, expect_passed, format_doc_output, format_env_output, format_expect_output,
+    format_output, format_search_output, is_incomplete,
     repl_state::{ReplAction, ReplState},
-    TIPS,
+    split_entries, TIPS,
 };
 use roc_target::TargetInfo;
-use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 
 use crate::{js_create_app, js_get_result_and_memory, js_run_app};
 
@@ -186,14 +186,40 @@ pub async fn entrypoint_from_js(src: String) -> String {
 
     // TODO: make this a global and reset it?
     let arena = &Bump::new();
+    let mut outputs = std::vec::Vec::new();
 
+    // Several lines entered at once are stepped through like the CLI would, had they been
+    // typed a line at a time, so that each def is remembered for the ones after it.
+    for entry in split_entries(&src) {
+        let output = step(arena, &entry).await;
+
+        if !output.is_empty() {
+            outputs.push(output);
+        }
+    }
+
+    outputs.join("\n")
+}
+
+/// If Enter was pressed at the end of an incomplete entry (e.g. `x =`), the indentation to
+/// start the next line with. Otherwise, the entry is ready to be evaluated.
+#[cfg_attr(feature = "wasi_test", allow(dead_code))]
+pub fn continuation_from_js(src: &str) -> Option<String> {
+    if is_incomplete(src) {
+        Some(continuation_indent(src))
+    } else {
+        None
+    }
+}
+
+async fn step(arena: &Bump, src: &str) -> String {
     // Compile the app
     let target_info = TargetInfo::default_wasm32();
 
     // Advance the REPL state machine
     let action = REPL_STATE.with(|repl_state_cell| {
         let mut repl_state = repl_state_cell.borrow_mut();
-        repl_state.step(arena, &src, target_info, DEFAULT_PALETTE_HTML)
+        repl_state.step(arena, src, target_info, DEFAULT_PALETTE_HTML)
     });
 
     // Perform the action the state machine asked for, and return the appropriate output string
@@ -203,48 +229,77 @@ pub async fn entrypoint_from_js(src: String) -> String {
             "To exit the web version of the REPL, just close the browser tab!".to_string()
         }
         ReplAction::Nothing => String::new(),
-        ReplAction::Eval { opt_mono, problems } => {
+        // Results are never shortened here, and there's no file system to save them to.
+        ReplAction::Expand | ReplAction::Save { .. } => {
+            "The web version of the REPL always shows results in full.".to_string()
+        }
+        ReplAction::TypeOf {
+            opt_output,
+            problems,
+        } => format_output(HTML_STYLE_CODES, opt_output, problems),
+        ReplAction::Doc {
+            opt_output,
+            docs,
+            problems,
+        } => format_doc_output(HTML_STYLE_CODES, opt_output, docs, problems),
+        ReplAction::Env { types, problems } => format_env_output(HTML_STYLE_CODES, types, problems),
+        ReplAction::Search { results, problems } => {
+            format_search_output(HTML_STYLE_CODES, results, problems)
+        }
+        ReplAction::Load { problems }
+        | ReplAction::Set { problems }
+        | ReplAction::Record { problems } => format_output(HTML_STYLE_CODES, None, problems),
+        // There's no `compile_time`, because the browser has no clock to read
+        ReplAction::Eval {
+            opt_mono,
+            problems,
+            instantiated_type,
+            ..
+        } => {
             let opt_output = match opt_mono {
                 Some(mono) => eval_wasm(arena, target_info, mono).await,
                 None => None,
-            };
+            }
+            .map(|output| ReplOutput {
+                instantiated_type,
+                ..output
+            });
 
             format_output(HTML_STYLE_CODES, opt_output, problems)
         }
+        ReplAction::Expect {
+            opt_mono,
+            mut problems,
+            failure_report,
+        } => {
+            let opt_output = match opt_mono {
+                Some(mono) => eval_wasm(arena, target_info, mono).await,
+                None => None,
+            };
+            let passed = expect_passed(opt_output, failure_report, &mut problems);
+
+            format_expect_output(HTML_STYLE_CODES, passed, problems)
+        }
     }
 }
 
 async fn eval_wasm<'a>(
     arena: &'a Bump,
     target_info: TargetInfo,
-    mono: MonomorphizedModule<'a>,
+    mut mono: MonomorphizedModule<'a>,
 ) -> Option<ReplOutput> {
+    let main = ReplMain::find(&mut mono)?;
+
     let MonomorphizedModule {
         module_id,
         procedures,
         mut interns,
-        mut subs,
+        subs,
         exposed_to_host,
         mut layout_interner,
         ..
     } = mono;
 
-    debug_assert_eq!(exposed_to_host.top_level_values.len(), 1);
-    let (main_fn_symbol, main_fn_var) = exposed_to_host.top_level_values.iter().next().unwrap();
-    let main_fn_symbol = *main_fn_symbol;
-    let main_fn_var = *main_fn_var;
-
-    // pretty-print the expr type string for later.
-    let expr_type = name_and_print_var(
-        main_fn_var,
-        &mut subs,
-        module_id,
-        &interns,
-        DebugPrint::NOTHING,
-    );
-
-    let (_, main_fn_layout) = *procedures.keys().find(|(s, _)| *s == main_fn_symbol)?;
-
     let app_module_bytes = {
         let env = roc_gen_wasm::Env {
             arena,
@@ -274,7 +329,7 @@ async fn eval_wasm<'a>(
             &mut module,
             WRAPPER_NAME,
             main_fn_index,
-            main_fn_layout.result,
+            main.layout.result,
         );
         called_fns.push(true);
 
@@ -286,35 +341,46 @@ async fn eval_wasm<'a>(
         buffer
     };
 
-    // Send the compiled binary out to JS, which will asynchronously create an executable WebAssembly instance
+    // Send the compiled binary out to JS, which will asynchronously create an executable WebAssembly
+    // instance and run it, giving up if it runs for too long (e.g. because of an infinite loop)
     match js_create_app(&app_module_bytes).await {
         Ok(()) => {}
-        Err(js_exception) => {
+        Err(message) => {
             return Some(ReplOutput {
-                expr: format!("<span class='color-red'>{js_exception:?}</span>"),
+                expr: format!("<span class='color-red'>{message}</span>"),
                 expr_type: String::new(),
+                dbgs: std::vec::Vec::new(),
+                shortened_expr: None,
+                instantiated_type: None,
+                timings: None,
             })
         }
     }
 
     let mut app = WasmReplApp { arena };
 
-    // Run the app and transform the result value to an AST `Expr`
-    // Restore type constructor names, and other user-facing info that was erased during compilation.
-    let res_answer = jit_to_ast(
+    // Run the app and format its answer. The web page wraps long lines itself, and there's
+    // no `:expand`, so the answer is never shortened.
+    let (expr, _) = eval_main(
         arena,
         &mut app,
         "", // main_fn_name is ignored (only passed to WasmReplApp methods)
-        main_fn_layout,
-        main_fn_var,
+        &main,
         &subs,
         &interns,
+        module_id,
         layout_interner.into_global().fork(),
         target_info,
+        DisplaySize::default(),
     );
 
-    // Transform the Expr to a string
-    let expr = format_answer(arena, res_answer).to_string();
-
-    Some(ReplOutput { expr, expr_type })
+    // The wasm backend doesn't support `dbg` yet
+    Some(ReplOutput {
+        expr,
+        expr_type: main.expr_type,
+        dbgs: std::vec::Vec::new(),
+        shortened_expr: None,
+        instantiated_type: None,
+        timings: None,
+    })
 }
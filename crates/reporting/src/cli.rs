@@ -1,9 +1,46 @@
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use roc_collections::MutMap;
 use roc_module::symbol::{Interns, ModuleId};
-use roc_region::all::LineInfo;
+use roc_problem::{Severity, WarningLevels};
+use roc_region::all::{LineInfo, Region};
 use roc_solve_problem::TypeError;
+use serde_json::json;
+
+use crate::report::{strip_colors, strip_colors_if_disabled, Report, RocDocAllocator};
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Print problems as JSON, one object per line, instead of as human-readable reports,
+/// e.g. for `--output json`. This is meant for editors and other tools.
+pub fn enable_json_output() {
+    JSON_OUTPUT.store(true, Ordering::Relaxed);
+}
+
+/// Whether problems get printed as JSON (see `enable_json_output`).
+pub fn json_output_enabled() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+static SARIF_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Print all the problems as a single [SARIF](https://sarifweb.azurewebsites.net/) log
+/// instead of as human-readable reports, e.g. for `--output sarif`. This is what
+/// GitHub code scanning (among others) reads to annotate pull requests.
+///
+/// This implies `json_output_enabled`; the problems are described the same way, but
+/// printed together once they've all been found, and there's no summary.
+pub fn enable_sarif_output() {
+    JSON_OUTPUT.store(true, Ordering::Relaxed);
+    SARIF_OUTPUT.store(true, Ordering::Relaxed);
+}
+
+/// Whether problems get printed as a SARIF log (see `enable_sarif_output`).
+pub fn sarif_output_enabled() -> bool {
+    SARIF_OUTPUT.load(Ordering::Relaxed)
+}
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
@@ -23,10 +60,21 @@ impl Problems {
     }
 
     pub fn print_to_stdout(&self, total_time: std::time::Duration) {
+        if sarif_output_enabled() {
+            // The problems were already printed as a SARIF log, which has no room for this.
+            return;
+        }
+
+        if json_output_enabled() {
+            println!("{}", self.to_json(total_time));
+
+            return;
+        }
+
         const GREEN: usize = 32;
         const YELLOW: usize = 33;
 
-        print!(
+        let summary = format!(
             "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms",
             match self.errors {
                 0 => GREEN,
@@ -48,7 +96,205 @@ impl Problems {
             },
             total_time.as_millis(),
         );
+
+        print!("{}", strip_colors_if_disabled(&summary));
+    }
+
+    /// The summary line printed after all the problems when `json_output_enabled`.
+    pub fn to_json(&self, total_time: std::time::Duration) -> serde_json::Value {
+        json!({
+            "errors": self.errors,
+            "warnings": self.warnings,
+            "time_ms": total_time.as_millis() as u64,
+        })
+    }
+}
+
+/// Describe one problem as JSON, for `json_output_enabled`.
+///
+/// Lines and columns start at 1, like in an editor.
+fn problem_json<'b>(
+    report: Report<'b>,
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    region: Option<Region>,
+) -> serde_json::Value {
+    let severity = match report.severity {
+        Severity::Warning => "warning",
+        Severity::RuntimeError => "error",
+        Severity::Fatal => "fatal",
+    };
+    let code = report.title.clone();
+    let file = report.filename.clone();
+    let region = region.map(|region| {
+        let region = lines.convert_region(region);
+
+        json!({
+            "start": { "line": region.start.line + 1, "column": region.start.column + 1 },
+            "end": { "line": region.end.line + 1, "column": region.end.column + 1 },
+        })
+    });
+
+    // Leave out the title and path; they have fields of their own.
+    let mut message = String::new();
+
+    Report {
+        title: String::new(),
+        ..report
+    }
+    .render_ci(&mut message, alloc);
+
+    json!({
+        "severity": severity,
+        "code": code,
+        "file": file,
+        "region": region,
+        "message": message.trim(),
+    })
+}
+
+/// Describe a report that was rendered before it got to us (e.g. a parse error) as JSON,
+/// for `json_output_enabled`. There's no telling where it is, other than from the message.
+pub fn formatted_report_json(report: &str) -> serde_json::Value {
+    json!({
+        "severity": "fatal",
+        "code": null,
+        "file": null,
+        "region": null,
+        "message": strip_colors(report).trim(),
+    })
+}
+
+/// Print a report that was rendered before it got to us, for `json_output_enabled`.
+/// It's the only problem there is, so it's printed right away.
+pub fn print_formatted_report_json(out: &mut impl Write, report: &str) -> std::io::Result<()> {
+    let problem = formatted_report_json(report);
+
+    if sarif_output_enabled() {
+        write_sarif_log(out, [sarif_result(problem, &[])])
+    } else {
+        writeln!(out, "{problem}")
+    }
+}
+
+/// Turn a problem, as described by `problem_json`, into a SARIF result.
+///
+/// SARIF columns count UTF-16 code units, unlike ours, which count bytes; the lines of
+/// the problem's module are needed to tell the difference.
+fn sarif_result(problem: serde_json::Value, src_lines: &[&str]) -> serde_json::Value {
+    // SARIF has no notion of a fatal problem; those stop compilation, so they're errors.
+    let level = match problem["severity"].as_str() {
+        Some("warning") => "warning",
+        _ => "error",
+    };
+    // A rule id is meant to be stable and identifier-like, so turn
+    // e.g. "UNUSED IMPORT" into "unused-import".
+    let rule_id = problem["code"]
+        .as_str()
+        .unwrap_or("compile-error")
+        .to_lowercase()
+        .replace(' ', "-");
+
+    let mut result = json!({
+        "ruleId": rule_id,
+        "level": level,
+        "message": { "text": problem["message"] },
+    });
+
+    if let Some(file) = problem["file"].as_str() {
+        let mut physical_location = json!({
+            "artifactLocation": { "uri": sarif_uri(Path::new(file)) },
+        });
+
+        if let Some(region) = problem["region"].as_object() {
+            let line_column = |position: &serde_json::Value| {
+                let line = position["line"].as_u64().unwrap_or(1);
+                let column = position["column"].as_u64().unwrap_or(1);
+                let src_line = src_lines.get(line as usize - 1).copied().unwrap_or("");
+
+                (line, utf16_column(src_line, column as usize))
+            };
+            let (start_line, start_column) = line_column(&region["start"]);
+            let (end_line, end_column) = line_column(&region["end"]);
+
+            physical_location["region"] = json!({
+                "startLine": start_line,
+                "startColumn": start_column,
+                "endLine": end_line,
+                "endColumn": end_column,
+            });
+        }
+
+        result["locations"] = json!([{ "physicalLocation": physical_location }]);
     }
+
+    result
+}
+
+/// Turn a column that counts bytes from 1 into one that counts UTF-16 code units from 1.
+fn utf16_column(src_line: &str, column: usize) -> usize {
+    let bytes = column.saturating_sub(1);
+    let code_units: usize = src_line
+        .char_indices()
+        .take_while(|(index, _)| *index < bytes)
+        .map(|(_, char)| char.len_utf16())
+        .sum();
+
+    code_units + 1
+}
+
+/// Write results, as described by `sarif_result`, as a SARIF 2.1.0 log on one line.
+fn write_sarif_log(
+    out: &mut impl Write,
+    results: impl IntoIterator<Item = serde_json::Value>,
+) -> std::io::Result<()> {
+    let results: Vec<serde_json::Value> = results.into_iter().collect();
+    let mut rule_ids: Vec<&str> = Vec::new();
+
+    for result in results.iter() {
+        if let Some(rule_id) = result["ruleId"].as_str() {
+            if !rule_ids.contains(&rule_id) {
+                rule_ids.push(rule_id);
+            }
+        }
+    }
+
+    let rules: Vec<serde_json::Value> = rule_ids
+        .into_iter()
+        .map(|rule_id| json!({ "id": rule_id }))
+        .collect();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "roc",
+                    "informationUri": "https://www.roc-lang.org",
+                    "rules": rules,
+                },
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    });
+
+    writeln!(out, "{log}")
+}
+
+/// Tools that read SARIF logs (like GitHub code scanning) want paths relative to the
+/// root of the repository, which is usually where `roc` was run from, with `/` separators.
+fn sarif_uri(path: &Path) -> String {
+    let path = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 pub fn report_problems(
@@ -57,9 +303,10 @@ pub fn report_problems(
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    warning_levels: &WarningLevels,
 ) -> Problems {
-    use crate::report::{can_problem, type_problem, Report, RocDocAllocator, DEFAULT_PALETTE};
-    use roc_problem::Severity::*;
+    use crate::report::{can_problem, type_problem, DEFAULT_PALETTE};
+    use Severity::*;
     let palette = DEFAULT_PALETTE;
 
     // This will often over-allocate total memory, but it means we definitely
@@ -81,11 +328,24 @@ pub fn report_problems(
         let problems = can_problems.remove(home).unwrap_or_default();
 
         for problem in problems.into_iter() {
-            let report = can_problem(&alloc, &lines, module_path.clone(), problem);
-            let severity = report.severity;
+            let region = problem.region();
+            let warning_name = problem.warning_name();
+            let mut report = can_problem(&alloc, &lines, module_path.clone(), problem);
+            let Some(severity) = warning_levels.severity(warning_name, report.severity) else {
+                continue;
+            };
+            report.severity = severity;
             let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            if sarif_output_enabled() {
+                let problem = problem_json(report, &alloc, &lines, region);
+
+                buf = sarif_result(problem, &src_lines).to_string();
+            } else if json_output_enabled() {
+                buf = problem_json(report, &alloc, &lines, region).to_string();
+            } else {
+                report.render_color_terminal(&mut buf, &alloc, &palette);
+            }
 
             match severity {
                 Warning => {
@@ -104,11 +364,25 @@ pub fn report_problems(
         let problems = type_problems.remove(home).unwrap_or_default();
 
         for problem in problems {
-            if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
-                let severity = report.severity;
+            let region = problem.region();
+            let warning_name = problem.warning_name();
+
+            if let Some(mut report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
+                let Some(severity) = warning_levels.severity(warning_name, report.severity) else {
+                    continue;
+                };
+                report.severity = severity;
                 let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                if sarif_output_enabled() {
+                    let problem = problem_json(report, &alloc, &lines, region);
+
+                    buf = sarif_result(problem, &src_lines).to_string();
+                } else if json_output_enabled() {
+                    buf = problem_json(report, &alloc, &lines, region).to_string();
+                } else {
+                    report.render_color_terminal(&mut buf, &alloc, &palette);
+                }
 
                 match severity {
                     Warning => {
@@ -126,6 +400,30 @@ pub fn report_problems(
         }
     }
 
+    if sarif_output_enabled() {
+        // Every problem has been found by now, so they all go in one log.
+        let results = errors
+            .iter()
+            .chain(warnings.iter())
+            .map(|result| serde_json::from_str(result).unwrap());
+
+        write_sarif_log(&mut std::io::stdout().lock(), results)
+            .expect("failed to write the SARIF log to stdout");
+    } else if json_output_enabled() {
+        // Tools can decide for themselves which problems to show, so print them all.
+        for problem in errors.iter().chain(warnings.iter()) {
+            println!("{problem}");
+        }
+    }
+
+    if json_output_enabled() {
+        return Problems {
+            fatally_errored,
+            errors: errors.len(),
+            warnings: warnings.len(),
+        };
+    }
+
     let problems_reported;
 
     // Only print warnings if there are no errors
@@ -150,7 +448,9 @@ pub fn report_problems(
     // compiling it, as it lets you clearly see where the compiler
     // errors/warnings end and the program output begins.
     if problems_reported > 0 {
-        println!("{}\u{001B}[0m\n", Report::horizontal_rule(&palette));
+        let rule = format!("{}\u{001B}[0m\n", Report::horizontal_rule(&palette));
+
+        println!("{}", strip_colors_if_disabled(&rule));
     }
 
     Problems {
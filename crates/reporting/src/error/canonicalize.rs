@@ -147,19 +147,52 @@ pub fn can_problem<'b>(
 
             title = "DEFINITIONs ONLY USED IN RECURSION".to_string();
         }
-        Problem::ExposedButNotDefined(symbol) => {
-            doc = alloc.stack([
-                alloc.symbol_unqualified(symbol).append(
-                    alloc.reflow(" is listed as exposed, but it isn't defined in this module."),
-                ),
+        Problem::ExposedButNotDefined {
+            symbol,
+            suggestion_options,
+        } => {
+            let name = symbol.as_str(alloc.interns);
+            // Only suggest names that are close enough to plausibly be what was meant,
+            // since the exposed name may well be for a definition that hasn't been written yet.
+            let max_distance = (name.len() / 3).max(1);
+            let mut suggestions: Vec<&str> = suggest::sort(
+                name,
+                suggestion_options
+                    .iter()
+                    .map(|option| option.as_ref())
+                    .filter(|option| {
+                        *option != name
+                            && distance::damerau_levenshtein(name, option) <= max_distance
+                    })
+                    .collect(),
+            );
+            suggestions.truncate(3);
+
+            let mut stack = vec![alloc.symbol_unqualified(symbol).append(
+                alloc.reflow(" is listed as exposed, but it isn't defined in this module."),
+            )];
+
+            if !suggestions.is_empty() {
+                stack.push(
+                    alloc.stack([
+                        alloc.reflow("Did you mean one of these?"),
+                        alloc
+                            .vcat(suggestions.into_iter().map(|v| alloc.string(v.to_string())))
+                            .indent(4),
+                    ]),
+                );
+            }
+
+            stack.push(
                 alloc
                     .reflow("You can fix this by adding a definition for ")
                     .append(alloc.symbol_unqualified(symbol))
                     .append(alloc.reflow(", or by removing it from "))
                     .append(alloc.keyword("exposes"))
                     .append(alloc.reflow(".")),
-            ]);
+            );
 
+            doc = alloc.stack(stack);
             title = MISSING_DEFINITION.to_string();
         }
         Problem::UnknownGeneratesWith(loc_ident) => {
@@ -10,12 +10,13 @@ use roc_types::{
     types::{ErrorType, Polarity},
 };
 
-use crate::report::{RenderTarget, RocDocAllocator, RocDocBuilder};
+use crate::report::{strip_colors_if_disabled, RenderTarget, RocDocAllocator, RocDocBuilder};
 
 pub struct Renderer<'a> {
     arena: &'a Bump,
     alloc: RocDocAllocator<'a>,
     filename: PathBuf,
+    source: &'a str,
     line_info: LineInfo,
     render_target: RenderTarget,
 }
@@ -39,6 +40,7 @@ impl<'a> Renderer<'a> {
             alloc,
             line_info,
             filename,
+            source,
             render_target,
         }
     }
@@ -170,11 +172,14 @@ impl<'a> Renderer<'a> {
         write!(writer, "{buf}")
     }
 
+    /// Renders a `dbg` like `[main.roc 4:9] x + 1 = 42 : U8`, where `x + 1` is the source of
+    /// the `dbg`'d expression (left out if it spans multiple lines) and `U8` its type.
     #[allow(clippy::too_many_arguments)]
     pub fn render_dbg<W>(
         &self,
         writer: &mut W,
         expressions: &[Expr<'_>],
+        type_str: &str,
         expect_region: Option<Region>,
         dbg_expr_region: Region,
     ) -> std::io::Result<()>
@@ -182,13 +187,23 @@ impl<'a> Renderer<'a> {
         W: std::io::Write,
     {
         let line_col_region = self.to_line_col_region(expect_region, dbg_expr_region);
-        write!(
-            writer,
+        let location = format!(
             "\u{001b}[36m[{} {}:{}] \u{001b}[0m",
             self.filename.display(),
             line_col_region.start.line + 1,
             line_col_region.start.column + 1
-        )?;
+        );
+
+        write!(writer, "{}", strip_colors_if_disabled(&location))?;
+
+        let source = self
+            .source
+            .get(dbg_expr_region.start().offset as usize..dbg_expr_region.end().offset as usize)
+            .unwrap_or_default();
+
+        if !source.is_empty() && !source.contains('\n') {
+            write!(writer, "{source} = ")?;
+        }
 
         let expr = expressions[0];
 
@@ -198,7 +213,7 @@ impl<'a> Renderer<'a> {
             expr.format(&mut buf, 0);
         }
 
-        writeln!(writer, "{}", buf.as_str())
+        writeln!(writer, "{} : {type_str}", buf.as_str())
     }
 
     pub fn render_panic<W>(
@@ -598,6 +598,30 @@ fn to_expr_report<'a>(
             }
         }
 
+        EExpr::RecordFieldPath(region) => {
+            let surroundings = Region::new(start, region.end());
+            let region = lines.convert_region(*region);
+
+            let doc = alloc.stack([
+                alloc.reflow(r"I am partway through parsing a record, and I found a nested field:"),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.concat([
+                    alloc.reflow(
+                        "Nested fields can only be given new values in record updates, like ",
+                    ),
+                    alloc.parser_suggestion("{ user & address.city: \"Paris\" }"),
+                    alloc.reflow("."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "BAD RECORD FIELD".to_string(),
+                severity: Severity::RuntimeError,
+            }
+        }
+
         EExpr::Space(error, pos) => to_space_report(alloc, lines, filename, error, *pos),
 
         &EExpr::Number(ENumber::End, pos) => {
@@ -1861,6 +1885,29 @@ fn to_pattern_report<'a>(
         &EPattern::NumLiteral(ENumber::End, pos) => {
             to_malformed_number_literal_report(alloc, lines, filename, pos)
         }
+        EPattern::StrPrefixRest(pos) => {
+            let surroundings = Region::new(start, *pos);
+            let region = LineColumnRegion::from_pos(lines.convert_pos(*pos));
+
+            let doc = alloc.stack([
+                alloc.reflow(
+                    r"I am partway through parsing a string prefix pattern, but I got stuck here:",
+                ),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.concat([
+                    alloc.reflow(r"I was expecting a name for the rest of the string, like "),
+                    alloc.parser_suggestion("\"fn \" .. rest"),
+                    alloc.reflow(", or an underscore if you don't need it."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "UNFINISHED STRING PREFIX PATTERN".to_string(),
+                severity: Severity::RuntimeError,
+            }
+        }
         _ => todo!("unhandled parse error: {:?}", parse_problem),
     }
 }
@@ -3183,6 +3230,8 @@ fn to_header_report<'a>(
             to_packages_report(alloc, lines, filename, packages, *pos)
         }
 
+        EHeader::Docs(docs, pos) => to_docs_report(alloc, lines, filename, docs, *pos),
+
         EHeader::IndentStart(pos) => {
             let surroundings = Region::new(start, *pos);
             let region = LineColumnRegion::from_pos(lines.convert_pos(*pos));
@@ -3844,6 +3893,99 @@ fn to_packages_report<'a>(
     }
 }
 
+fn to_docs_report<'a>(
+    alloc: &'a RocDocAllocator<'a>,
+    lines: &LineInfo,
+    filename: PathBuf,
+    parse_problem: &roc_parse::parser::EDocs,
+    start: Position,
+) -> Report<'a> {
+    use roc_parse::parser::EDocs;
+
+    match parse_problem {
+        EDocs::ListStart(pos) | EDocs::ListEnd(pos) | EDocs::DocsEntry(_, pos) => {
+            let surroundings = Region::new(start, *pos);
+            let region = LineColumnRegion::from_pos(lines.convert_pos(*pos));
+
+            let doc = alloc.stack([
+                alloc.reflow(r"I am partway through parsing a `docs` section, but I got stuck here:"),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.concat([
+                    alloc.reflow("I was expecting settings like "),
+                    alloc.keyword("logo"),
+                    alloc.reflow(" or "),
+                    alloc.keyword("accentColor"),
+                    alloc.reflow(", or links for the docs' header, each followed by a string, like"),
+                ]),
+                alloc
+                    .parser_suggestion(
+                        r##"docs { accentColor: "#1e7b5c", "Source code": "https://github.com/me/pkg" }"##,
+                    )
+                    .indent(4),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "WEIRD DOCS".to_string(),
+                severity: Severity::RuntimeError,
+            }
+        }
+
+        EDocs::Docs(pos) | EDocs::IndentDocs(pos) => {
+            let surroundings = Region::new(start, *pos);
+            let region = LineColumnRegion::from_pos(lines.convert_pos(*pos));
+
+            let doc = alloc.stack([
+                alloc.reflow(r"I am partway through parsing a header, but I got stuck here:"),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.concat([
+                    alloc.reflow("I am expecting the "),
+                    alloc.keyword("docs"),
+                    alloc.reflow(" keyword next, like"),
+                ]),
+                alloc
+                    .parser_suggestion(r#"docs { logo: "logo.svg" }"#)
+                    .indent(4),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "WEIRD DOCS".to_string(),
+                severity: Severity::RuntimeError,
+            }
+        }
+
+        EDocs::IndentListStart(pos) => {
+            let surroundings = Region::new(start, *pos);
+            let region = LineColumnRegion::from_pos(lines.convert_pos(*pos));
+
+            let doc = alloc.stack([
+                alloc.reflow(
+                    r"I am partway through parsing a `docs` section, but I got stuck here:",
+                ),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.reflow(
+                    "I was expecting the settings and links in curly braces next, indented more than the start of the header, like",
+                ),
+                alloc
+                    .parser_suggestion(r#"docs { logo: "logo.svg" }"#)
+                    .indent(4),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "WEIRD DOCS".to_string(),
+                severity: Severity::RuntimeError,
+            }
+        }
+
+        EDocs::Space(error, pos) => to_space_report(alloc, lines, filename, error, *pos),
+    }
+}
+
 fn to_space_report<'a>(
     alloc: &'a RocDocAllocator<'a>,
     lines: &LineInfo,
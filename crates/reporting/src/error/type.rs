@@ -248,6 +248,38 @@ pub fn type_problem<'b>(
                 severity,
             })
         }
+        TypeHole { region, typ } => {
+            let stack = [
+                alloc.reflow("This hole in the type annotation:"),
+                alloc.region(lines.convert_region(region)),
+                alloc.reflow("was inferred to be:"),
+                alloc.type_block(error_type_to_doc(alloc, typ)),
+            ];
+
+            Some(Report {
+                title: "TYPE HOLE".to_string(),
+                filename,
+                doc: alloc.stack(stack),
+                severity,
+            })
+        }
+        Explained(problem, trace) => {
+            let report = type_problem(alloc, lines, filename, *problem)?;
+            let steps = trace.into_iter().map(|step| {
+                alloc.stack([
+                    alloc.region(lines.convert_region(step.region)),
+                    add_category(alloc, alloc.text("It is"), &step.category),
+                    alloc.type_block(error_type_to_doc(alloc, step.typ)),
+                ])
+            });
+            let doc = alloc.stack([
+                report.doc,
+                alloc.reflow("Here is how I arrived at these types, step by step:"),
+                alloc.stack(steps),
+            ]);
+
+            Some(Report { doc, ..report })
+        }
     }
 }
 
@@ -741,9 +773,9 @@ fn to_expr_report<'b>(
                     alloc.text(" definition:"),
                 ]),
                 RequiredSymbol { .. } => alloc.concat([
-                    alloc.text("type annotation of "),
+                    alloc.text("type the platform requires for "),
                     the_name_text,
-                    alloc.text(" required symbol:"),
+                    alloc.text(":"),
                 ]),
             };
 
@@ -783,6 +815,18 @@ fn to_expr_report<'b>(
                             alloc.reflow(" is connected to another type in a way that isn't reflected in this annotation.")
                         ]),
                     )
+            } else if let RequiredSymbol { .. } = annotation_source {
+                type_comparison(
+                    alloc,
+                    found,
+                    expected_type,
+                    expectation_context,
+                    add_category(alloc, alloc.text(it_is), &category),
+                    alloc.text("But the platform requires it to be:"),
+                    Some(alloc.reflow(
+                        "This usually means the app was written for a different version of its platform. Either change the app to match what this version of the platform requires, or switch the app back to the platform version it was written for.",
+                    )),
+                )
             } else {
                 type_comparison(
                     alloc,
@@ -799,8 +843,13 @@ fn to_expr_report<'b>(
                 )
             };
 
+            let title = match annotation_source {
+                RequiredSymbol { .. } => "INCOMPATIBLE PLATFORM",
+                _ => "TYPE MISMATCH",
+            };
+
             Report {
-                title: "TYPE MISMATCH".to_string(),
+                title: title.to_string(),
                 filename,
                 doc: alloc.stack([
                     alloc.text("Something is off with the ").append(thing),
@@ -5060,6 +5109,7 @@ fn pattern_to_doc_help<'b>(
             Float(f) => alloc.text(f.to_string()),
             Decimal(d) => alloc.text(RocDec::from_ne_bytes(d).to_string()),
             Str(s) => alloc.string(s.into()),
+            StrPrefix(s) => alloc.string(format!("{s:?} .. _")),
         },
         List(arity, patterns) => {
             let inner = match arity {
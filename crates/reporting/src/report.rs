@@ -3,7 +3,9 @@ use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, ModuleIds, PQModuleName, PackageQualified, Symbol};
 use roc_problem::Severity;
 use roc_region::all::LineColumnRegion;
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{fmt, io};
 use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
 
@@ -134,7 +136,7 @@ impl<'b> Report<'b> {
     }
 
     /// Render to CI console output, where no colors are available.
-    pub fn render_ci(self, buf: &'b mut String, alloc: &'b RocDocAllocator<'b>) {
+    pub fn render_ci(self, buf: &mut String, alloc: &'b RocDocAllocator<'b>) {
         let err_msg = "<buffer is not a utf-8 encoded string>";
 
         self.pretty(alloc)
@@ -145,6 +147,8 @@ impl<'b> Report<'b> {
 
     /// Render to a color terminal using ANSI escape sequences,
     /// or to the web REPL, using HTML tags.
+    ///
+    /// If colors have been disabled (see `disable_color`), no styling is added.
     pub fn render_color_terminal(
         self,
         buf: &mut String,
@@ -152,6 +156,11 @@ impl<'b> Report<'b> {
         palette: &'b Palette,
     ) {
         let err_msg = "<buffer is not a utf-8 encoded string>";
+        let palette = if color_enabled() {
+            palette
+        } else {
+            &PLAIN_PALETTE
+        };
 
         self.pretty(alloc)
             .1
@@ -240,6 +249,9 @@ pub const DEFAULT_PALETTE: Palette = default_palette_from_style_codes(ANSI_STYLE
 
 pub const DEFAULT_PALETTE_HTML: Palette = default_palette_from_style_codes(HTML_STYLE_CODES);
 
+/// A palette that doesn't add any styling, for output that will be read by other programs.
+pub const PLAIN_PALETTE: Palette = default_palette_from_style_codes(PLAIN_STYLE_CODES);
+
 /// A machine-readable format for text styles (colors and other styles)
 #[derive(Debug, PartialEq)]
 pub struct StyleCodes {
@@ -270,6 +282,60 @@ pub const ANSI_STYLE_CODES: StyleCodes = StyleCodes {
     color_reset: "\u{1b}[39m",
 };
 
+pub const PLAIN_STYLE_CODES: StyleCodes = StyleCodes {
+    red: "",
+    green: "",
+    yellow: "",
+    blue: "",
+    magenta: "",
+    cyan: "",
+    white: "",
+    bold: "",
+    underline: "",
+    reset: "",
+    color_reset: "",
+};
+
+static COLOR_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Stop styling output with ANSI escape codes, e.g. for `--no-color`.
+///
+/// Setting the `ROC_NO_COLOR` environment variable to anything but an empty string does the same.
+pub fn disable_color() {
+    COLOR_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether output may be styled with ANSI escape codes (see `disable_color`).
+pub fn color_enabled() -> bool {
+    !COLOR_DISABLED.load(Ordering::Relaxed)
+        && std::env::var_os("ROC_NO_COLOR").map_or(true, |value| value.is_empty())
+}
+
+/// Remove the ANSI escape codes in `ANSI_STYLE_CODES` from the given text.
+pub fn strip_colors(str: &str) -> String {
+    str.replace(ANSI_STYLE_CODES.red, "")
+        .replace(ANSI_STYLE_CODES.green, "")
+        .replace(ANSI_STYLE_CODES.yellow, "")
+        .replace(ANSI_STYLE_CODES.blue, "")
+        .replace(ANSI_STYLE_CODES.magenta, "")
+        .replace(ANSI_STYLE_CODES.cyan, "")
+        .replace(ANSI_STYLE_CODES.white, "")
+        .replace(ANSI_STYLE_CODES.bold, "")
+        .replace(ANSI_STYLE_CODES.underline, "")
+        .replace(ANSI_STYLE_CODES.reset, "")
+        .replace(ANSI_STYLE_CODES.color_reset, "")
+}
+
+/// Remove the ANSI escape codes from text that was styled ahead of time,
+/// if colors have been disabled (see `disable_color`).
+pub fn strip_colors_if_disabled(str: &str) -> Cow<'_, str> {
+    if color_enabled() {
+        Cow::Borrowed(str)
+    } else {
+        Cow::Owned(strip_colors(str))
+    }
+}
+
 macro_rules! html_color {
     ($name: expr) => {
         concat!("<span class='color-", $name, "'>")
@@ -21,6 +21,7 @@ use roc_solve::{solve, Aliases, FunctionKind};
 use roc_solve_problem::TypeError;
 use roc_types::subs::{Content, Subs, VarStore, Variable};
 use roc_types::types::Types;
+use std::cell::Cell;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
 
@@ -51,6 +52,7 @@ pub fn infer_expr(
         exposed_by_module: &Default::default(),
         derived_module,
         function_kind: FunctionKind::LambdaSet,
+        explain_types: false,
         #[cfg(debug_assertions)]
         checkmate: None,
     };
@@ -166,7 +168,7 @@ pub fn can_expr_with<'a>(
     // visited a BinOp node we'd recursively try to apply this to each of its nested
     // operators, and then again on *their* nested operators, ultimately applying the
     // rules multiple times unnecessarily.
-    let loc_expr = operator::desugar_expr(arena, &loc_expr);
+    let loc_expr = operator::desugar_expr(arena, &Cell::new(0), &loc_expr);
 
     let mut scope = Scope::new(home, IdentIds::default(), Default::default());
 
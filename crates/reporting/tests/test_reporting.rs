@@ -17,10 +17,11 @@ mod test_reporting {
     use roc_module::symbol::{Interns, ModuleId};
     use roc_packaging::cache::RocCacheDir;
     use roc_parse::module::parse_header;
+    use roc_parse::parser::{EDocs, EHeader};
     use roc_parse::state::State;
     use roc_parse::test_helpers::parse_expr_with;
     use roc_problem::Severity;
-    use roc_region::all::LineInfo;
+    use roc_region::all::{LineInfo, Position};
     use roc_reporting::report::{
         can_problem, parse_problem, type_problem, RenderTarget, Report, ANSI_STYLE_CODES,
         DEFAULT_PALETTE,
@@ -128,6 +129,8 @@ mod test_reporting {
                 palette: DEFAULT_PALETTE,
                 threading: Threading::Single,
                 exec_mode: ExecutionMode::Check,
+                warning_levels: Default::default(),
+                explain_types: false,
                 function_kind: FunctionKind::LambdaSet,
             };
             let result = roc_load::load_and_typecheck(
@@ -398,6 +401,32 @@ mod test_reporting {
         assert_eq!(buf, expected_rendering);
     }
 
+    /// Like `report_header_problem_as`, but for a problem that's given rather than found by
+    /// parsing `src`. Some problems can't come from source code, because the parser always
+    /// backtracks out of them, e.g. in the optional `docs` section of a package header.
+    fn report_header_error_as<'a>(src: &'a str, problem: EHeader<'a>, expected_rendering: &str) {
+        use roc_parse::parser::SyntaxError;
+        use ven_pretty::DocAllocator;
+
+        let filename = filename_from_string(r"/code/proj/Main.roc");
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let interns = Interns::default();
+        let alloc = RocDocAllocator::new(&src_lines, crate::helpers::test_home(), &interns);
+        let problem = SyntaxError::Header(problem)
+            .into_file_error(filename.clone(), &State::new(src.as_bytes()));
+        let doc = parse_problem(&alloc, &lines, filename, 0, problem);
+        let mut buf = String::new();
+
+        doc.pretty(&alloc)
+            .append(alloc.line())
+            .1
+            .render_raw(70, &mut roc_reporting::report::CiWrite::new(&mut buf))
+            .expect("list_reports");
+
+        assert_eq!(buf, expected_rendering);
+    }
+
     fn color_report_problem_as(src: &str, expected_rendering: &str) {
         let mut buf: String = String::new();
         let arena = Bump::new();
@@ -2895,6 +2924,40 @@ mod test_reporting {
     "###
     );
 
+    test_no_problem!(
+        str_prefix_empty_is_exhaustive,
+        indoc!(
+            r#"
+            when "abc" is
+                "a" .. rest -> rest
+                "" .. rest -> rest
+            "#
+        )
+    );
+
+    test_report!(
+        str_prefix_empty_makes_later_patterns_redundant,
+        indoc!(
+            r#"
+            when "abc" is
+                "" .. rest -> rest
+                "a" -> "b"
+            "#
+        ),
+        @r###"
+    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+
+    The 2nd pattern is redundant:
+
+    4│       when "abc" is
+    5│           "" .. rest -> rest
+    6│>          "a" -> "b"
+
+    Any value of this shape will be handled by a previous pattern, so this
+    one should be removed.
+    "###
+    );
+
     test_report!(
         unify_alias_other,
         indoc!(
@@ -5351,6 +5414,40 @@ mod test_reporting {
     "###
     );
 
+    test_report!(
+        two_syntax_errors,
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = [1, 2, , 3]
+
+            other = [4, 5, , 6]
+            "#
+        ),
+        @r###"
+    ── UNFINISHED LIST ──────────────────────────── tmp/two_syntax_errors/Test.roc ─
+
+    I am partway through started parsing a list, but I got stuck here:
+
+    3│  main = [1, 2, , 3]
+                      ^
+
+    I was expecting to see a list entry before this comma, so try adding a
+    list entry and see if that helps?
+
+    ── UNFINISHED LIST ──────────────────────────── tmp/two_syntax_errors/Test.roc ─
+
+    I am partway through started parsing a list, but I got stuck here:
+
+    5│  other = [4, 5, , 6]
+                       ^
+
+    I was expecting to see a list entry before this comma, so try adding a
+    list entry and see if that helps?
+    "###
+    );
+
     test_report!(
         number_double_dot,
         indoc!(
@@ -6046,6 +6143,217 @@ In roc, functions are always written as a lambda, like{}
         )
     }
 
+    #[test]
+    fn docs_bad_value() {
+        report_header_problem_as(
+            indoc!(
+                r#"
+                package "test/pkg"
+                    exposes [Foo]
+                    packages {}
+                    docs { logo: 42 }
+                "#
+            ),
+            indoc!(
+                r##"
+                ── WEIRD DOCS ──────────────────────────────────────────── /code/proj/Main.roc ─
+
+                I am partway through parsing a `docs` section, but I got stuck here:
+
+                3│      packages {}
+                4│      docs { logo: 42 }
+                               ^
+
+                I was expecting settings like `logo` or `accentColor`, or links for the
+                docs' header, each followed by a string, like
+
+                    docs { accentColor: "#1e7b5c", "Source code": "https://github.com/me/pkg" }
+                "##
+            ),
+        )
+    }
+
+    #[test]
+    fn docs_missing_comma() {
+        report_header_problem_as(
+            indoc!(
+                r##"
+                package "test/pkg"
+                    exposes [Foo]
+                    packages {}
+                    docs { logo: "logo.svg" accentColor: "#1e7b5c" }
+                "##
+            ),
+            indoc!(
+                r##"
+                ── WEIRD DOCS ──────────────────────────────────────────── /code/proj/Main.roc ─
+
+                I am partway through parsing a `docs` section, but I got stuck here:
+
+                3│      packages {}
+                4│      docs { logo: "logo.svg" accentColor: "#1e7b5c" }
+                                                ^
+
+                I was expecting settings like `logo` or `accentColor`, or links for the
+                docs' header, each followed by a string, like
+
+                    docs { accentColor: "#1e7b5c", "Source code": "https://github.com/me/pkg" }
+                "##
+            ),
+        )
+    }
+
+    #[test]
+    fn docs_not_indented() {
+        report_header_problem_as(
+            indoc!(
+                r#"
+                package "test/pkg"
+                    exposes [Foo]
+                    packages {}
+                    docs
+                { logo: "logo.svg" }
+                "#
+            ),
+            indoc!(
+                r#"
+                ── WEIRD DOCS ──────────────────────────────────────────── /code/proj/Main.roc ─
+
+                I am partway through parsing a `docs` section, but I got stuck here:
+
+                3│      packages {}
+                4│      docs
+                            ^
+
+                I was expecting the settings and links in curly braces next, indented
+                more than the start of the header, like
+
+                    docs { logo: "logo.svg" }
+                "#
+            ),
+        )
+    }
+
+    #[test]
+    fn docs_tab() {
+        report_header_problem_as(
+            "package \"test/pkg\"\n    exposes [Foo]\n    packages {}\n    docs\t{ logo: \"logo.svg\" }\n",
+            indoc!(
+                r#"
+                ── TAB CHARACTER ───────────────────────────────────────── /code/proj/Main.roc ─
+
+                I encountered a tab character:
+
+                4│      docs	{ logo: "logo.svg" }
+                            ^
+
+                Tab characters are not allowed, use spaces instead.
+                "#
+            ),
+        )
+    }
+
+    #[test]
+    fn docs_keyword() {
+        let src = indoc!(
+            r#"
+            package "test/pkg"
+                exposes [Foo]
+                packages {}
+                doc { logo: "logo.svg" }
+            "#
+        );
+        let start = Position::new(src.find("\n    doc").unwrap() as u32);
+        let pos = Position::new(src.find("doc {").unwrap() as u32);
+
+        report_header_error_as(
+            src,
+            EHeader::Docs(EDocs::Docs(pos), start),
+            indoc!(
+                r#"
+                ── WEIRD DOCS ──────────────────────────────────────────── /code/proj/Main.roc ─
+
+                I am partway through parsing a header, but I got stuck here:
+
+                3│      packages {}
+                4│      doc { logo: "logo.svg" }
+                        ^
+
+                I am expecting the `docs` keyword next, like
+
+                    docs { logo: "logo.svg" }
+                "#
+            ),
+        )
+    }
+
+    #[test]
+    fn docs_keyword_not_indented() {
+        let src = indoc!(
+            r#"
+            package "test/pkg"
+                exposes [Foo]
+                packages {}
+            docs { logo: "logo.svg" }
+            "#
+        );
+        let pos = Position::new(src.find("\ndocs").unwrap() as u32);
+
+        report_header_error_as(
+            src,
+            EHeader::Docs(EDocs::IndentDocs(pos), pos),
+            indoc!(
+                r#"
+                ── WEIRD DOCS ──────────────────────────────────────────── /code/proj/Main.roc ─
+
+                I am partway through parsing a header, but I got stuck here:
+
+                3│      packages {}
+                                   ^
+
+                I am expecting the `docs` keyword next, like
+
+                    docs { logo: "logo.svg" }
+                "#
+            ),
+        )
+    }
+
+    #[test]
+    fn docs_list_start() {
+        let src = indoc!(
+            r#"
+            package "test/pkg"
+                exposes [Foo]
+                packages {}
+                docs logo: "logo.svg"
+            "#
+        );
+        let start = Position::new(src.find("\n    docs").unwrap() as u32);
+        let pos = Position::new(src.find("logo").unwrap() as u32);
+
+        report_header_error_as(
+            src,
+            EHeader::Docs(EDocs::ListStart(pos), start),
+            indoc!(
+                r##"
+                ── WEIRD DOCS ──────────────────────────────────────────── /code/proj/Main.roc ─
+
+                I am partway through parsing a `docs` section, but I got stuck here:
+
+                3│      packages {}
+                4│      docs logo: "logo.svg"
+                             ^
+
+                I was expecting settings like `logo` or `accentColor`, or links for the
+                docs' header, each followed by a string, like
+
+                    docs { accentColor: "#1e7b5c", "Source code": "https://github.com/me/pkg" }
+                "##
+            ),
+        )
+    }
+
     #[test]
     fn exposes_identifier() {
         report_header_problem_as(
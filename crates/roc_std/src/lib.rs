@@ -368,9 +368,15 @@ impl RocDec {
         // Self::DECIMAL_PLACES, this assert should remind you to change that format string as well.
         static_assertions::const_assert!(RocDec::DECIMAL_PLACES + 1 == 19);
 
+        // The sign goes first, so that it doesn't count towards the width of the digits
+        // (which would leave -0.5 without its leading zero, as "-.5").
+        if self.as_i128() < 0 {
+            string.push('-');
+        }
+
         // By using the :019 format, we're guaranteeing that numbers less than 1, say 0.01234
         // get their leading zeros placed in bytes for us. i.e. `string = b"0012340000000000000"`
-        write!(string, "{:019}", self.as_i128()).unwrap();
+        write!(string, "{:019}", self.as_i128().unsigned_abs()).unwrap();
 
         let decimal_location = string.len() - Self::DECIMAL_PLACES;
         // skip trailing zeros
@@ -300,6 +300,12 @@ mod test_roc_std {
 
         let example = RocDec::from_str("1000.000").unwrap();
         assert_eq!(format!("{example}"), "1000");
+
+        let example = RocDec::from_str("-0.5").unwrap();
+        assert_eq!(format!("{example}"), "-0.5");
+
+        let example = RocDec::from_str("-0.000000000000000001").unwrap();
+        assert_eq!(format!("{example}"), "-0.000000000000000001");
     }
 
     #[test]
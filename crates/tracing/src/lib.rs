@@ -13,6 +13,10 @@
 //! Tracing is only turned on in debug builds. Use the provided [setup_tracing] macro to turn on
 //! tracing at an executable's entry point.
 //!
+//! [setup_chrome_tracing] instead records spans (like the phases of a build) as a Chrome trace,
+//! which can be opened in [Perfetto](https://ui.perfetto.dev). That works in release builds too,
+//! where only info-level spans and events are compiled in.
+//!
 //! [directive-syntax]: https://docs.rs/tracing-subscriber/latest/tracing_subscriber/filter/struct.EnvFilter.html#directives
 
 /// Sets up tracing of a Roc executable. The value of this macro must be bound to a variable that
@@ -33,21 +37,29 @@ macro_rules! setup_tracing {
 
 pub use tracing::debug;
 pub use tracing::info;
+pub use tracing::info_span;
+pub use tracing::Span;
 
 const ENV_FILTER: &str = "ROC_LOG";
 const LOGTO_VAR: &str = "ROC_LOGTO";
 
-use tracing_subscriber::{fmt, prelude::*, EnvFilter, Layer, Registry};
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use tracing_subscriber::{filter::LevelFilter, fmt, prelude::*, EnvFilter, Layer, Registry};
 
 /// Guards issued by the underlying library used for tracing.
 /// Must not be dropped until all tracing is complete.
 pub struct TracingGuards {
     _file_appender_guard: Option<tracing_appender::non_blocking::WorkerGuard>,
+    /// Writes the end of the trace when it's dropped.
+    _chrome_guard: Option<tracing_chrome::FlushGuard>,
 }
 
 impl TracingGuards {
     pub const NONE: TracingGuards = TracingGuards {
         _file_appender_guard: None,
+        _chrome_guard: None,
     };
 }
 
@@ -66,6 +78,7 @@ pub fn setup_tracing() -> TracingGuards {
 
         TracingGuards {
             _file_appender_guard: Some(guard),
+            _chrome_guard: None,
         }
     } else {
         let stderr_layer = fmt::Layer::default()
@@ -77,3 +90,26 @@ pub fn setup_tracing() -> TracingGuards {
         TracingGuards::NONE
     }
 }
+
+/// Records info-level spans and events to a Chrome trace at `path`, instead of setting up tracing
+/// with [setup_tracing]. Each thread gets its own track, so e.g. the phases of a build show up
+/// under the worker thread that ran them.
+///
+/// This should only be called at an executable's entry point.
+pub fn setup_chrome_tracing(path: &Path) -> io::Result<TracingGuards> {
+    let file = BufWriter::new(File::create(path)?);
+    let (chrome_layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+        .writer(file)
+        .include_args(true)
+        .build();
+
+    // Debug-level events are far too many (and too slow to record) to be useful here.
+    Registry::default()
+        .with(chrome_layer.with_filter(LevelFilter::INFO))
+        .init();
+
+    Ok(TracingGuards {
+        _file_appender_guard: None,
+        _chrome_guard: Some(guard),
+    })
+}
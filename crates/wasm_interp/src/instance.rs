@@ -1,6 +1,7 @@
 use bumpalo::{collections::Vec, Bump};
 use std::fmt::{self, Write};
 use std::iter::{self, once, Iterator};
+use std::time::Instant;
 
 use roc_wasm_module::opcodes::{MemoryInstruction, OpCode};
 use roc_wasm_module::parse::{Parse, SkipBytes};
@@ -12,6 +13,10 @@ use crate::frame::Frame;
 use crate::value_store::ValueStore;
 use crate::{Error, ImportDispatcher};
 
+/// How many instructions to execute between checks of the deadline, since getting the time
+/// costs much more than most instructions do
+const INSTRUCTIONS_PER_DEADLINE_CHECK: u32 = 1 << 16;
+
 #[derive(Debug)]
 pub enum Action {
     Continue,
@@ -167,6 +172,32 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
     }
 
     pub fn call_export<A>(&mut self, fn_name: &str, arg_values: A) -> Result<Option<Value>, String>
+    where
+        A: IntoIterator<Item = Value>,
+    {
+        self.call_export_help(fn_name, arg_values, None)
+    }
+
+    /// Like [Self::call_export], but gives up if the function is still running at the deadline
+    /// (e.g. because it's stuck in an infinite loop)
+    pub fn call_export_with_deadline<A>(
+        &mut self,
+        fn_name: &str,
+        arg_values: A,
+        deadline: Instant,
+    ) -> Result<Option<Value>, String>
+    where
+        A: IntoIterator<Item = Value>,
+    {
+        self.call_export_help(fn_name, arg_values, Some(deadline))
+    }
+
+    fn call_export_help<A>(
+        &mut self,
+        fn_name: &str,
+        arg_values: A,
+        deadline: Option<Instant>,
+    ) -> Result<Option<Value>, String>
     where
         A: IntoIterator<Item = Value>,
     {
@@ -184,7 +215,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             self.value_store.push(value);
         }
 
-        self.call_export_help_after_arg_load(self.module, fn_index, n_args, ret_type)
+        self.call_export_help_after_arg_load(self.module, fn_index, n_args, ret_type, deadline)
     }
 
     pub fn call_export_from_cli(
@@ -225,7 +256,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             self.value_store.push(value);
         }
 
-        self.call_export_help_after_arg_load(module, fn_index, n_args, ret_type)
+        self.call_export_help_after_arg_load(module, fn_index, n_args, ret_type, None)
     }
 
     fn call_export_help_before_arg_load<'m>(
@@ -295,6 +326,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         fn_index: usize,
         n_args: usize,
         return_type: Option<ValueType>,
+        deadline: Option<Instant>,
     ) -> Result<Option<Value>, String> {
         self.previous_frames.clear();
         self.blocks.clear();
@@ -317,7 +349,24 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             vstack: self.value_store.depth(),
         });
 
+        let mut instructions_until_deadline_check = INSTRUCTIONS_PER_DEADLINE_CHECK;
+
         loop {
+            if let Some(deadline) = deadline {
+                instructions_until_deadline_check -= 1;
+
+                if instructions_until_deadline_check == 0 {
+                    if Instant::now() >= deadline {
+                        return Err(
+                            "ERROR: The function was still running at the deadline, so I stopped it.\n"
+                                .to_string(),
+                        );
+                    }
+
+                    instructions_until_deadline_check = INSTRUCTIONS_PER_DEADLINE_CHECK;
+                }
+            }
+
             match self.execute_next_instruction(module) {
                 Ok(Action::Continue) => {}
                 Ok(Action::Break) => {
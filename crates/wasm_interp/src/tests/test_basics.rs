@@ -12,6 +12,7 @@ use roc_wasm_module::{
     opcodes::OpCode, sections::ElementSegment, Export, ExportType, SerialBuffer, Serialize,
     Signature, Value, ValueType, WasmModule,
 };
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_loop() {
@@ -275,6 +276,67 @@ fn test_br() {
     assert_eq!(result, Value::I32(111))
 }
 
+#[test]
+fn test_deadline_stops_infinite_loop() {
+    let result = test_deadline_help(true, Duration::from_millis(10));
+
+    assert!(result.unwrap_err().contains("deadline"));
+}
+
+#[test]
+fn test_deadline_not_reached() {
+    let result = test_deadline_help(false, Duration::from_secs(60));
+
+    assert_eq!(result, Ok(Some(Value::I32(1))));
+}
+
+fn test_deadline_help(
+    infinite_loop: bool,
+    timeout: Duration,
+) -> Result<Option<Value>, std::string::String> {
+    let start_fn_name = "test";
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, start_fn_name, signature, |buf| {
+        // loop  ;; label = @1
+        buf.push(OpCode::LOOP as u8);
+        buf.push(ValueType::VOID);
+
+        //     i32.const 0 or 1
+        buf.push(OpCode::I32CONST as u8);
+        buf.encode_i32(infinite_loop as i32);
+
+        //     br_if 0 (;@1;)
+        buf.push(OpCode::BRIF as u8);
+        buf.encode_u32(0);
+
+        // end
+        buf.push(OpCode::END as u8);
+
+        // i32.const 1
+        buf.push(OpCode::I32CONST as u8);
+        buf.encode_i32(1);
+
+        buf.push(OpCode::END as u8);
+    });
+
+    let is_debug_mode = false;
+    let mut inst = Instance::for_module(
+        &arena,
+        &module,
+        DefaultImportDispatcher::default(),
+        is_debug_mode,
+    )
+    .unwrap();
+
+    inst.call_export_with_deadline(start_fn_name, [], Instant::now() + timeout)
+}
+
 #[test]
 fn test_br_if() {
     test_br_if_help(0, 222);
@@ -6,7 +6,7 @@ interface Parser.Http
         response,
     ]
     imports [
-        Parser.Core.{ Parser, ParseResult, map, keep, skip, const, oneOrMore, many },
+        Parser.Core.{ Parser, map, keep, skip, const, oneOrMore, many },
         Parser.Str.{
             RawStr,
             oneOf,
@@ -4,8 +4,8 @@ app "example"
         parser: "../package/main.roc",
     }
     imports [
-        parser.ParserCore.{ Parser, map, keep },
-        parser.ParserStr.{ RawStr, strFromRaw },
+        parser.ParserCore.{ map, keep },
+        parser.ParserStr.{ strFromRaw },
         parser.ParserCSV.{ CSV, record, field, string, nat, parseStr },
     ]
     provides [main] to pf
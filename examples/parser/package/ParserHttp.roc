@@ -6,7 +6,7 @@ interface ParserHttp
         response,
     ]
     imports [
-        ParserCore.{ Parser, ParseResult, map, keep, skip, const, oneOrMore, many },
+        ParserCore.{ Parser, map, keep, skip, const, oneOrMore, many },
         ParserStr.{ RawStr, oneOf, string, codeunit, parseStr, codeunitSatisfies, strFromRaw, anyRawString },
     ]
 
@@ -118,7 +118,7 @@
           python3
           libiconv # for examples/gui
           libxkbcommon # for examples/gui
-          debugir # used in crates/compiler/build/src/program.rs
+          debugir # used in crates/compiler/test_gen/src/helpers/llvm.rs
           cargo-criterion # for benchmarks
           simple-http-server # to view roc website when trying out edits
           wasm-pack # for repl_wasm
@@ -28,12 +28,33 @@ const repl = {
   textEncoder: new TextEncoder(),
 
   compiler: null,
-  app: null,
 
-  // Temporary storage for the address of the result of running the user's code.
-  // Used while control flow returns to Rust to allocate space to copy the app's memory buffer.
-  result_addr: 0,
+  // Runs the user's code, so that it can be stopped if it takes too long (e.g. an infinite loop)
+  // without freezing the page. Recreated after that happens.
+  appWorker: null,
+
+  // The address of the result of running the user's code, and a copy of the app's memory.
+  // Kept while control flow returns to Rust to allocate space to copy the memory into.
+  appResult: null,
+};
+
+// How long the user's code can run before we give up on it
+const EVAL_TIMEOUT_MS = 5000;
+
+// The app's Wasm module comes in, and its result address and memory go back out.
+// The app doesn't import anything, so there's nothing else to provide it with.
+const APP_WORKER_SOURCE = `
+onmessage = async ({ data: wasmModuleBytes }) => {
+  try {
+    const { instance } = await WebAssembly.instantiate(wasmModuleBytes);
+    const resultAddr = instance.exports.wrapper();
+    const memory = instance.exports.memory.buffer.slice(0);
+    postMessage({ ok: true, resultAddr, memory }, [memory]);
+  } catch (e) {
+    postMessage({ ok: false, error: \`\${e}\` });
+  }
 };
+`;
 
 // Initialise
 repl.elemSourceInput.addEventListener("input", onInput);
@@ -76,6 +97,18 @@ function onInputKeydown(event) {
       // Don't advance the caret to the next line
       event.preventDefault();
 
+      // Like the CLI REPL, Enter at the end of an incomplete entry (e.g. `x =`)
+      // starts the next line of it, already indented.
+      const el = repl.elemSourceInput;
+      if (el.selectionStart === el.value.length) {
+        const indent = roc_repl_wasm.continuation_from_js(el.value);
+        if (indent !== undefined) {
+          setInput(el.value + "\n" + indent);
+          onInput(event);
+          return;
+        }
+      }
+
       const inputText = repl.elemSourceInput.value.trim();
 
       repl.elemSourceInput.value = "";
@@ -165,35 +198,58 @@ async function processInputQueue() {
 // Callbacks to JS from Rust
 // ----------------------------------------------------------------------------
 
-// Load Wasm code into the browser's virtual machine, so we can run it later.
-// This operation is async, so we call it before entering any code shared
-// with the command-line REPL, which is sync.
+// Load the Wasm code into the browser's virtual machine, and run its `main` function
+// (via the `wrapper` function) in a worker, remembering the result for the callbacks below.
+// Running in a worker is async, so we do it before entering any code shared with the
+// command-line REPL, which is sync.
+// Rejects with a message for the user if the app crashes, or doesn't finish in time.
 async function js_create_app(wasm_module_bytes) {
-  const { instance } = await WebAssembly.instantiate(wasm_module_bytes);
-  // Keep the instance alive so we can run it later from shared REPL code
-  repl.app = instance;
-}
+  if (!repl.appWorker) {
+    const blob = new Blob([APP_WORKER_SOURCE], { type: "text/javascript" });
+    repl.appWorker = new Worker(URL.createObjectURL(blob));
+  }
 
-// Call the `main` function of the user app, via the `wrapper` function.
-function js_run_app() {
-  const { wrapper, memory } = repl.app.exports;
+  const worker = repl.appWorker;
+  repl.appResult = null;
+
+  const result = await new Promise((resolve) => {
+    const timeout = setTimeout(() => {
+      // The app is stuck. Stop it, and start a fresh worker for the next one.
+      worker.terminate();
+      repl.appWorker = null;
+      resolve({
+        ok: false,
+        error: `Evaluation timed out after ${EVAL_TIMEOUT_MS / 1000} seconds`,
+      });
+    }, EVAL_TIMEOUT_MS);
+
+    worker.onmessage = ({ data }) => {
+      clearTimeout(timeout);
+      resolve(data);
+    };
+    worker.postMessage(wasm_module_bytes);
+  });
+
+  if (!result.ok) {
+    throw result.error;
+  }
 
-  // Run the user code, and remember the result address
-  // We'll pass it to Rust in the next callback
-  repl.result_addr = wrapper();
+  repl.appResult = result;
+}
 
-  // Tell Rust how much space to reserve for its copy of the app's memory buffer.
-  // We couldn't know that size until we actually ran the app.
-  return memory.buffer.byteLength;
+// Tell Rust how much space to reserve for its copy of the app's memory buffer.
+// We couldn't know that size until we actually ran the app.
+function js_run_app() {
+  return repl.appResult.memory.byteLength;
 }
 
 // After Rust has allocated space for the app's memory buffer,
 // we copy it, and return the result address too
 function js_get_result_and_memory(buffer_alloc_addr) {
-  const appMemory = new Uint8Array(repl.app.exports.memory.buffer);
+  const appMemory = new Uint8Array(repl.appResult.memory);
   const compilerMemory = new Uint8Array(repl.compiler.memory.buffer);
   compilerMemory.set(appMemory, buffer_alloc_addr);
-  return repl.result_addr;
+  return repl.appResult.resultAddr;
 }
 
 // ----------------------------------------------------------------------------